@@ -0,0 +1,859 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Boot Manager
+//!
+//! The UEFI boot manager is a firmware policy engine that can be configured by modifying
+//! architecturally defined global NVRAM variables. The boot manager will attempt to load UEFI
+//! drivers and UEFI applications (including UEFI OS boot loaders) in an order defined by the
+//! global NVRAM variables. See [Section 3 of the UEFI Specification] for more details.
+//!
+//! Each load option entry resides in a `Boot####`, `Driver####`, `SysPrep####`,
+//! `OsRecovery####` or `PlatformRecovery####` variable where `####` is replaced by a unique
+//! option number in printable hexadecimal representation using the digits `0`-`9`, and the upper
+//! case versions of the characters `A`-`F`. The contents of these variables are an
+//! `EFI_LOAD_OPTION`, which is represented in this module by the zero-copy [`LoadOption`] type,
+//! able to both parse and serialize the packed variable format.
+//!
+//! The `BootOrder`, `BootNext` and `BootCurrent` variables hold the option numbers of these
+//! entries; see [`BootOrder`] and [`parse_boot_option_number()`] for parsing them.
+//!
+//! [`LoadOption`]: crate::boot_manager::LoadOption
+//! [`BootOrder`]: crate::boot_manager::BootOrder
+//! [`parse_boot_option_number()`]: crate::boot_manager::parse_boot_option_number
+//!
+//! [Section 3 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/03_Boot_Manager.html
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::mem;
+
+use crate::protocols::device_path::DevicePath;
+use crate::status::{EFI_BUFFER_TOO_SMALL, EFI_INVALID_PARAMETER};
+#[cfg(feature = "alloc")]
+use crate::tables::runtime_services::{
+    EFI_BOOT_CURRENT_VARIABLE_NAME, EFI_BOOT_NEXT_VARIABLE_NAME, EFI_BOOT_ORDER_VARIABLE_NAME,
+    EFI_GLOBAL_VARIABLE, EFI_RUNTIME_SERVICES, EFI_VARIABLE_ATTRIBUTES,
+    EFI_VARIABLE_BOOTSERVICE_ACCESS, EFI_VARIABLE_NON_VOLATILE, EFI_VARIABLE_RUNTIME_ACCESS,
+};
+use crate::types::{CHAR16, EFI_STATUS, UINT16, UINT32, UINT8};
+
+/// If a load option is marked as active, the boot manager will attempt to boot automatically
+/// using the device path information in the load option.
+pub const LOAD_OPTION_ACTIVE: UINT32 = 0x00000001;
+/// If a driver load option is marked as force reconnect, then all of the UEFI drivers in the
+/// system will be disconnected and reconnected after the last driver load option is processed.
+pub const LOAD_OPTION_FORCE_RECONNECT: UINT32 = 0x00000002;
+/// If any load option is marked as hidden, then the load option will not appear in the menu (if
+/// any) provided by the boot manager for load option selection.
+pub const LOAD_OPTION_HIDDEN: UINT32 = 0x00000008;
+/// Sub-field of the attributes that provides details to the boot manager to describe how it
+/// should group the load options.
+pub const LOAD_OPTION_CATEGORY: UINT32 = 0x00001F00;
+/// Boot category load options are meant to be part of the normal boot processing.
+pub const LOAD_OPTION_CATEGORY_BOOT: UINT32 = 0x00000000;
+/// Application category load options are executables which are not part of the normal boot
+/// processing but can be optionally chosen for execution if boot menu is provided.
+pub const LOAD_OPTION_CATEGORY_APP: UINT32 = 0x00000100;
+
+const ATTRIBUTES_SIZE: usize = mem::size_of::<UINT32>();
+const FILE_PATH_LIST_LENGTH_SIZE: usize = mem::size_of::<UINT16>();
+const HEADER_SIZE: usize = ATTRIBUTES_SIZE + FILE_PATH_LIST_LENGTH_SIZE;
+
+/// A zero-copy view of an `EFI_LOAD_OPTION`, the contents of `Boot####`, `Driver####` and
+/// related variables.
+///
+/// In its packed form, a load option is laid out as follows, with no padding between the fields:
+///
+/// | Field                | Size                   |
+/// | -------------------- | ---------------------- |
+/// | `Attributes`         | 4 bytes                |
+/// | `FilePathListLength` | 2 bytes                |
+/// | `Description`        | null-terminated UCS-2  |
+/// | `FilePathList`       | `FilePathListLength`   |
+/// | `OptionalData`       | remainder of the data  |
+///
+/// Since the variable contents are not guaranteed to be aligned, a parsed description is kept as
+/// raw little-endian bytes; use [`description()`] to decode it. The file path list is kept as the
+/// packed sequence of device paths it is stored as.
+///
+/// [`description()`]: ./struct.LoadOption.html#method.description
+#[derive(Clone, Copy)]
+pub struct LoadOption<'a> {
+    /// The attributes for this load option entry. See the `LOAD_OPTION_*` constants in this
+    /// module.
+    pub Attributes: UINT32,
+    /// The user readable description for the load option, without the terminating null
+    /// character.
+    pub Description: LoadOptionDescription<'a>,
+    /// A packed array of UEFI device paths. The first element of the array is a device path that
+    /// describes the device and location of the image for this load option.
+    pub FilePathList: &'a [UINT8],
+    /// The remaining bytes in the load option descriptor are a binary data buffer that is passed
+    /// to the loaded image.
+    pub OptionalData: &'a [UINT8],
+}
+
+impl<'a> LoadOption<'a> {
+    /// Creates a load option from its individual parts, ready to be serialized.
+    ///
+    /// `Description` may optionally include its terminating null character, which is stripped.
+    pub fn new(
+        Attributes: UINT32,
+        Description: &'a [CHAR16],
+        FilePathList: &'a [UINT8],
+        OptionalData: &'a [UINT8],
+    ) -> Self {
+        let len = Description
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(Description.len());
+
+        Self {
+            Attributes,
+            Description: LoadOptionDescription::Characters(&Description[..len]),
+            FilePathList,
+            OptionalData,
+        }
+    }
+
+    /// Parses a packed load option, as read from a `Boot####` or similar variable.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if the data is truncated, the description is not
+    /// null-terminated, or `FilePathListLength` exceeds the remaining data.
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub fn parse(Data: &'a [UINT8]) -> Result<Self, EFI_STATUS> {
+        if Data.len() < HEADER_SIZE {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        let Attributes = UINT32::from_le_bytes([Data[0], Data[1], Data[2], Data[3]]);
+        let FilePathListLength = UINT16::from_le_bytes([Data[4], Data[5]]) as usize;
+
+        let rest = &Data[HEADER_SIZE..];
+        let description_len = rest
+            .chunks_exact(mem::size_of::<CHAR16>())
+            .position(|c| c == [0, 0])
+            .ok_or(EFI_INVALID_PARAMETER)?
+            * mem::size_of::<CHAR16>();
+
+        let Description = LoadOptionDescription::Packed(&rest[..description_len]);
+        let rest = &rest[description_len + mem::size_of::<CHAR16>()..];
+
+        if rest.len() < FilePathListLength {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        let (FilePathList, OptionalData) = rest.split_at(FilePathListLength);
+
+        Ok(Self {
+            Attributes,
+            Description,
+            FilePathList,
+            OptionalData,
+        })
+    }
+
+    /// Returns an iterator decoding the description of the load option into UCS-2 characters.
+    pub fn description(&self) -> impl Iterator<Item = CHAR16> + 'a {
+        let (packed, characters): (&[UINT8], &[CHAR16]) = match self.Description {
+            LoadOptionDescription::Packed(bytes) => (bytes, &[]),
+            LoadOptionDescription::Characters(chars) => (&[], chars),
+        };
+
+        packed
+            .chunks_exact(mem::size_of::<CHAR16>())
+            .map(|c| CHAR16::from_le_bytes([c[0], c[1]]))
+            .chain(characters.iter().copied())
+    }
+
+    /// Returns the first device path of the file path list, which describes the device and
+    /// location of the image for this load option.
+    pub fn file_path(&self) -> Result<DevicePath<'a>, EFI_STATUS> {
+        DevicePath::new(self.FilePathList)
+    }
+
+    /// Returns `true` if the [`LOAD_OPTION_ACTIVE`] attribute is set.
+    ///
+    /// [`LOAD_OPTION_ACTIVE`]: crate::boot_manager::LOAD_OPTION_ACTIVE
+    pub fn is_active(&self) -> bool {
+        self.Attributes & LOAD_OPTION_ACTIVE != 0
+    }
+
+    /// Returns `true` if the [`LOAD_OPTION_HIDDEN`] attribute is set.
+    ///
+    /// [`LOAD_OPTION_HIDDEN`]: crate::boot_manager::LOAD_OPTION_HIDDEN
+    pub fn is_hidden(&self) -> bool {
+        self.Attributes & LOAD_OPTION_HIDDEN != 0
+    }
+
+    /// Returns the category of the load option, one of the `LOAD_OPTION_CATEGORY_*` constants.
+    pub fn category(&self) -> UINT32 {
+        self.Attributes & LOAD_OPTION_CATEGORY
+    }
+
+    /// Returns the size, in bytes, of the packed form of this load option.
+    pub fn serialized_size(&self) -> usize {
+        HEADER_SIZE
+            + self.Description.size()
+            + mem::size_of::<CHAR16>()
+            + self.FilePathList.len()
+            + self.OptionalData.len()
+    }
+
+    /// Writes the packed form of this load option into `Buffer`, returning the number of bytes
+    /// written.
+    ///
+    /// Returns [`EFI_BUFFER_TOO_SMALL`] if `Buffer` is shorter than [`serialized_size()`], and
+    /// [`EFI_INVALID_PARAMETER`] if the file path list is longer than `FilePathListLength` can
+    /// represent or the description has an odd length.
+    ///
+    /// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`serialized_size()`]: ./struct.LoadOption.html#method.serialized_size
+    pub fn serialize(&self, Buffer: &mut [UINT8]) -> Result<usize, EFI_STATUS> {
+        let FilePathListLength =
+            UINT16::try_from(self.FilePathList.len()).map_err(|_| EFI_INVALID_PARAMETER)?;
+        if let LoadOptionDescription::Packed(bytes) = self.Description {
+            if bytes.len() % mem::size_of::<CHAR16>() != 0 {
+                return Err(EFI_INVALID_PARAMETER);
+            }
+        }
+
+        let size = self.serialized_size();
+        let Buffer = Buffer.get_mut(..size).ok_or(EFI_BUFFER_TOO_SMALL)?;
+
+        let mut offset = 0;
+        let mut write = |part: &[UINT8]| {
+            Buffer[offset..offset + part.len()].copy_from_slice(part);
+            offset += part.len();
+        };
+
+        write(&self.Attributes.to_le_bytes());
+        write(&FilePathListLength.to_le_bytes());
+        match self.Description {
+            LoadOptionDescription::Packed(bytes) => write(bytes),
+            LoadOptionDescription::Characters(chars) => {
+                for c in chars {
+                    write(&c.to_le_bytes());
+                }
+            }
+        }
+        write(&[0, 0]);
+        write(self.FilePathList);
+        write(self.OptionalData);
+
+        Ok(size)
+    }
+
+    /// Serializes this load option into a newly allocated buffer.
+    ///
+    /// Fails under the same conditions as [`serialize()`], except for the buffer being too
+    /// small.
+    ///
+    /// [`serialize()`]: ./struct.LoadOption.html#method.serialize
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Result<Vec<UINT8>, EFI_STATUS> {
+        let mut buffer = alloc::vec![0; self.serialized_size()];
+        self.serialize(&mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+/// The textual description of a [`LoadOption`].
+///
+/// [`LoadOption`]: crate::boot_manager::LoadOption
+#[derive(Clone, Copy)]
+pub enum LoadOptionDescription<'a> {
+    /// Little-endian UCS-2 bytes, as found in a packed load option.
+    Packed(&'a [UINT8]),
+    /// UCS-2 characters in host byte order, as passed to [`LoadOption::new()`].
+    ///
+    /// [`LoadOption::new()`]: ./struct.LoadOption.html#method.new
+    Characters(&'a [CHAR16]),
+}
+
+impl<'a> LoadOptionDescription<'a> {
+    /// Returns the size, in bytes, of the description in its packed form, without the
+    /// terminating null character.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Packed(bytes) => bytes.len(),
+            Self::Characters(chars) => mem::size_of_val(*chars),
+        }
+    }
+}
+
+/// An iterator over the option numbers stored in a `BootOrder`, `DriverOrder` or similar
+/// variable, which is a packed array of [`UINT16`] values.
+///
+/// A trailing odd byte, which a well-formed variable never contains, is ignored.
+///
+/// [`UINT16`]: crate::types::UINT16
+#[derive(Clone)]
+pub struct BootOrder<'a> {
+    chunks: core::slice::ChunksExact<'a, UINT8>,
+}
+
+impl<'a> BootOrder<'a> {
+    /// Creates an iterator over the option numbers in the contents of an order variable.
+    pub fn new(Data: &'a [UINT8]) -> Self {
+        Self {
+            chunks: Data.chunks_exact(mem::size_of::<UINT16>()),
+        }
+    }
+}
+
+impl Iterator for BootOrder<'_> {
+    type Item = UINT16;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks
+            .next()
+            .map(|c| UINT16::from_le_bytes([c[0], c[1]]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for BootOrder<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.chunks
+            .next_back()
+            .map(|c| UINT16::from_le_bytes([c[0], c[1]]))
+    }
+}
+
+impl ExactSizeIterator for BootOrder<'_> {}
+
+impl FusedIterator for BootOrder<'_> {}
+
+/// Parses the contents of a `BootNext` or `BootCurrent` variable, which is a single [`UINT16`]
+/// option number.
+///
+/// Returns [`EFI_INVALID_PARAMETER`] if the data is not exactly two bytes long.
+///
+/// [`UINT16`]: crate::types::UINT16
+/// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+pub fn parse_boot_option_number(Data: &[UINT8]) -> Result<UINT16, EFI_STATUS> {
+    match *Data {
+        [lo, hi] => Ok(UINT16::from_le_bytes([lo, hi])),
+        _ => Err(EFI_INVALID_PARAMETER),
+    }
+}
+
+/// Returns the null-terminated `Boot####` variable name for an option number.
+pub fn boot_option_variable_name(OptionNumber: UINT16) -> [CHAR16; 9] {
+    load_option_variable_name(*b"Boot", OptionNumber)
+}
+
+/// Returns the null-terminated `Driver####` variable name for an option number.
+pub fn driver_option_variable_name(OptionNumber: UINT16) -> [CHAR16; 11] {
+    load_option_variable_name(*b"Driver", OptionNumber)
+}
+
+/// Returns the null-terminated `SysPrep####` variable name for an option number.
+pub fn sys_prep_option_variable_name(OptionNumber: UINT16) -> [CHAR16; 12] {
+    load_option_variable_name(*b"SysPrep", OptionNumber)
+}
+
+/// Parses a `Boot####` variable name, with or without its terminating null character, into its
+/// option number.
+///
+/// Returns `None` if the name is not of that form. As required by the specification, only upper
+/// case hexadecimal digits are accepted.
+pub fn parse_boot_option_variable_name(Name: &[CHAR16]) -> Option<UINT16> {
+    let Name = match Name {
+        [rest @ .., 0] => rest,
+        _ => Name,
+    };
+
+    if Name.len() != 8 {
+        return None;
+    }
+
+    let (prefix, digits) = Name.split_at(4);
+    if !prefix
+        .iter()
+        .copied()
+        .eq(b"Boot".iter().map(|&c| c as CHAR16))
+    {
+        return None;
+    }
+
+    digits.iter().try_fold(0, |acc, &c| {
+        let digit = match c {
+            0x30..=0x39 => c - 0x30,
+            0x41..=0x46 => c - 0x41 + 10,
+            _ => return None,
+        };
+
+        Some(acc << 4 | digit)
+    })
+}
+
+fn load_option_variable_name<const P: usize, const N: usize>(
+    Prefix: [u8; P],
+    OptionNumber: UINT16,
+) -> [CHAR16; N] {
+    const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+    let mut name = [0; N];
+    for (c, &p) in name.iter_mut().zip(Prefix.iter()) {
+        *c = p as CHAR16;
+    }
+    for i in 0..4 {
+        name[P + i] = DIGITS[(OptionNumber >> (12 - 4 * i)) as usize & 0xF] as CHAR16;
+    }
+
+    name
+}
+
+#[cfg(feature = "alloc")]
+const ORDER_ATTRIBUTES: EFI_VARIABLE_ATTRIBUTES =
+    EFI_VARIABLE_NON_VOLATILE | EFI_VARIABLE_BOOTSERVICE_ACCESS | EFI_VARIABLE_RUNTIME_ACCESS;
+
+/// Reads the `BootOrder` variable.
+#[cfg(feature = "alloc")]
+pub unsafe fn get_boot_order(
+    RuntimeServices: &EFI_RUNTIME_SERVICES,
+) -> Result<Vec<UINT16>, EFI_STATUS> {
+    let (_, data) =
+        RuntimeServices.get_variable(EFI_BOOT_ORDER_VARIABLE_NAME, &EFI_GLOBAL_VARIABLE)?;
+    Ok(BootOrder::new(&data).collect())
+}
+
+/// Writes the `BootOrder` variable.
+#[cfg(feature = "alloc")]
+pub unsafe fn set_boot_order(
+    RuntimeServices: &EFI_RUNTIME_SERVICES,
+    BootOrder: &[UINT16],
+) -> Result<(), EFI_STATUS> {
+    let data = BootOrder
+        .iter()
+        .flat_map(|n| n.to_le_bytes())
+        .collect::<Vec<_>>();
+    set_global_variable(RuntimeServices, EFI_BOOT_ORDER_VARIABLE_NAME, &data)
+}
+
+/// Reads the `BootNext` variable.
+#[cfg(feature = "alloc")]
+pub unsafe fn get_boot_next(RuntimeServices: &EFI_RUNTIME_SERVICES) -> Result<UINT16, EFI_STATUS> {
+    let (_, data) =
+        RuntimeServices.get_variable(EFI_BOOT_NEXT_VARIABLE_NAME, &EFI_GLOBAL_VARIABLE)?;
+    parse_boot_option_number(&data)
+}
+
+/// Writes the `BootNext` variable, or deletes it if `BootNext` is `None`.
+#[cfg(feature = "alloc")]
+pub unsafe fn set_boot_next(
+    RuntimeServices: &EFI_RUNTIME_SERVICES,
+    BootNext: Option<UINT16>,
+) -> Result<(), EFI_STATUS> {
+    match BootNext {
+        Some(n) => set_global_variable(
+            RuntimeServices,
+            EFI_BOOT_NEXT_VARIABLE_NAME,
+            &n.to_le_bytes(),
+        ),
+        None => set_global_variable(RuntimeServices, EFI_BOOT_NEXT_VARIABLE_NAME, &[]),
+    }
+}
+
+/// Reads the `BootCurrent` variable.
+#[cfg(feature = "alloc")]
+pub unsafe fn get_boot_current(
+    RuntimeServices: &EFI_RUNTIME_SERVICES,
+) -> Result<UINT16, EFI_STATUS> {
+    let (_, data) =
+        RuntimeServices.get_variable(EFI_BOOT_CURRENT_VARIABLE_NAME, &EFI_GLOBAL_VARIABLE)?;
+    parse_boot_option_number(&data)
+}
+
+#[cfg(feature = "alloc")]
+unsafe fn set_global_variable(
+    RuntimeServices: &EFI_RUNTIME_SERVICES,
+    Name: &[CHAR16],
+    Data: &[UINT8],
+) -> Result<(), EFI_STATUS> {
+    let status = RuntimeServices.SetVariable(
+        Name.as_ptr(),
+        &EFI_GLOBAL_VARIABLE,
+        ORDER_ATTRIBUTES,
+        Data.len(),
+        Data.as_ptr().cast(),
+    );
+
+    match status {
+        crate::status::EFI_SUCCESS => Ok(()),
+        _ => Err(status),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::format;
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::protocols::device_path::EFI_DEVICE_PATH_PROTOCOL;
+
+    /// `Boot0000` as exposed by OVMF: the hidden, active `UiApp` application, launched from a
+    /// firmware volume file.
+    const OVMF_BOOT0000: &[UINT8] = &[
+        0x09, 0x01, 0x00, 0x00, 0x2C, 0x00, 0x55, 0x00, 0x69, 0x00, 0x41, 0x00, 0x70, 0x00, 0x70,
+        0x00, 0x00, 0x00, 0x04, 0x07, 0x14, 0x00, 0xC9, 0xBD, 0xB8, 0x7C, 0xEB, 0xF8, 0x34, 0x4F,
+        0xAA, 0xEA, 0x3E, 0xE4, 0xAF, 0x65, 0x16, 0xA1, 0x04, 0x06, 0x14, 0x00, 0x21, 0xAA, 0x2C,
+        0x46, 0x14, 0x76, 0x03, 0x45, 0x83, 0x6E, 0x8A, 0xB6, 0xF4, 0x66, 0x23, 0x31, 0x7F, 0xFF,
+        0x04, 0x00,
+    ];
+
+    /// `Boot0001` as created by the OVMF boot manager for the first ATA disk, whose optional data
+    /// holds the GUID marking automatically created boot options.
+    const OVMF_BOOT0001: &[UINT8] = &[
+        0x01, 0x00, 0x00, 0x00, 0x1E, 0x00, 0x55, 0x00, 0x45, 0x00, 0x46, 0x00, 0x49, 0x00, 0x20,
+        0x00, 0x51, 0x00, 0x45, 0x00, 0x4D, 0x00, 0x55, 0x00, 0x20, 0x00, 0x48, 0x00, 0x41, 0x00,
+        0x52, 0x00, 0x44, 0x00, 0x44, 0x00, 0x49, 0x00, 0x53, 0x00, 0x4B, 0x00, 0x20, 0x00, 0x51,
+        0x00, 0x4D, 0x00, 0x30, 0x00, 0x30, 0x00, 0x30, 0x00, 0x30, 0x00, 0x31, 0x00, 0x20, 0x00,
+        0x00, 0x00, 0x02, 0x01, 0x0C, 0x00, 0xD0, 0x41, 0x03, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x01,
+        0x01, 0x06, 0x00, 0x01, 0x01, 0x03, 0x01, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7F, 0xFF,
+        0x04, 0x00, 0x4E, 0xAC, 0x08, 0x81, 0x11, 0x9F, 0x59, 0x4D, 0x85, 0x0E, 0xE2, 0x1A, 0x52,
+        0x2C, 0x59, 0xB2,
+    ];
+
+    fn description(Option: &LoadOption<'_>) -> std::string::String {
+        char::decode_utf16(Option.description())
+            .map(|c| c.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn parse_firmware_volume_option() {
+        let option = LoadOption::parse(OVMF_BOOT0000).unwrap();
+
+        assert!(option.is_active());
+        assert!(option.is_hidden());
+        assert_eq!(option.category(), LOAD_OPTION_CATEGORY_APP);
+        assert_eq!(description(&option), "UiApp");
+        assert_eq!(option.FilePathList.len(), 0x2C);
+        assert!(option.OptionalData.is_empty());
+        assert_eq!(
+            format!("{}", option.file_path().unwrap().display(false)),
+            "Fv(7CB8BDC9-F8EB-4F34-AAEA-3EE4AF6516A1)/FvFile(462CAA21-7614-4503-836E-8AB6F4662331)"
+        );
+    }
+
+    #[test]
+    fn parse_disk_option_with_optional_data() {
+        let option = LoadOption::parse(OVMF_BOOT0001).unwrap();
+
+        assert!(option.is_active());
+        assert!(!option.is_hidden());
+        assert_eq!(option.category(), LOAD_OPTION_CATEGORY_BOOT);
+        assert_eq!(description(&option), "UEFI QEMU HARDDISK QM00001 ");
+        assert_eq!(
+            format!("{}", option.file_path().unwrap().display(false)),
+            "PciRoot(0x0)/Pci(0x1,0x1)/Ata(Primary,Master,0x0)"
+        );
+        assert_eq!(
+            option.OptionalData,
+            &OVMF_BOOT0001[OVMF_BOOT0001.len() - 16..]
+        );
+    }
+
+    #[test]
+    fn serialize_round_trips() {
+        for data in [OVMF_BOOT0000, OVMF_BOOT0001] {
+            let option = LoadOption::parse(data).unwrap();
+            assert_eq!(option.serialized_size(), data.len());
+
+            let mut buffer = [0; 128];
+            assert_eq!(option.serialize(&mut buffer), Ok(data.len()));
+            assert_eq!(&buffer[..data.len()], data);
+
+            assert_eq!(
+                option.serialize(&mut buffer[..data.len() - 1]),
+                Err(EFI_BUFFER_TOO_SMALL)
+            );
+        }
+    }
+
+    #[test]
+    fn new_matches_captured_option() {
+        let parsed = LoadOption::parse(OVMF_BOOT0000).unwrap();
+        let option = LoadOption::new(
+            LOAD_OPTION_ACTIVE | LOAD_OPTION_HIDDEN | LOAD_OPTION_CATEGORY_APP,
+            ucs2!("UiApp"),
+            parsed.FilePathList,
+            &[],
+        );
+
+        assert!(option.description().eq(parsed.description()));
+        assert_eq!(option.serialized_size(), OVMF_BOOT0000.len());
+
+        let mut buffer = [0; 128];
+        let size = option.serialize(&mut buffer).unwrap();
+        assert_eq!(&buffer[..size], OVMF_BOOT0000);
+    }
+
+    #[test]
+    fn parse_rejects_truncated_options() {
+        for data in [OVMF_BOOT0000, OVMF_BOOT0001] {
+            let option = LoadOption::parse(data).unwrap();
+            let file_path_end = data.len() - option.OptionalData.len();
+
+            for len in 0..file_path_end {
+                assert_eq!(
+                    LoadOption::parse(&data[..len]).err(),
+                    Some(EFI_INVALID_PARAMETER),
+                    "prefix of {} bytes",
+                    len
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn file_path_is_a_valid_device_path() {
+        let option = LoadOption::parse(OVMF_BOOT0001).unwrap();
+        let path = option.file_path().unwrap();
+
+        assert_eq!(path.size(), option.FilePathList.len());
+        assert_eq!(
+            path.nodes()
+                .map(|n| (n.node_type(), n.sub_type(), n.length()))
+                .collect::<Vec<_>>(),
+            [(0x02, 0x01, 12), (0x01, 0x01, 6), (0x03, 0x01, 8)]
+        );
+        assert_eq!(
+            path.size() - path.nodes().map(|n| n.length()).sum::<usize>(),
+            core::mem::size_of::<EFI_DEVICE_PATH_PROTOCOL>()
+        );
+    }
+
+    #[test]
+    fn boot_order_iterates_option_numbers() {
+        let data = [0x01, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x34, 0x12, 0xFF];
+        let order = BootOrder::new(&data);
+
+        assert_eq!(order.len(), 4);
+        assert_eq!(
+            order.clone().collect::<Vec<_>>(),
+            [0x0001, 0x0000, 0x000A, 0x1234]
+        );
+        assert_eq!(
+            order.rev().collect::<Vec<_>>(),
+            [0x1234, 0x000A, 0x0000, 0x0001]
+        );
+    }
+
+    #[test]
+    fn boot_option_number_requires_two_bytes() {
+        assert_eq!(parse_boot_option_number(&[0x03, 0x00]), Ok(0x0003));
+        assert_eq!(parse_boot_option_number(&[0xCD, 0xAB]), Ok(0xABCD));
+        assert_eq!(
+            parse_boot_option_number(&[0x03]),
+            Err(EFI_INVALID_PARAMETER)
+        );
+        assert_eq!(
+            parse_boot_option_number(&[0x03, 0x00, 0x00]),
+            Err(EFI_INVALID_PARAMETER)
+        );
+    }
+
+    #[test]
+    fn option_variable_names() {
+        assert_eq!(&boot_option_variable_name(0x00AF)[..], ucs2!("Boot00AF"));
+        assert_eq!(
+            &driver_option_variable_name(0x1000)[..],
+            ucs2!("Driver1000")
+        );
+        assert_eq!(
+            &sys_prep_option_variable_name(0xFFFF)[..],
+            ucs2!("SysPrepFFFF")
+        );
+
+        assert_eq!(
+            parse_boot_option_variable_name(ucs2!("Boot00AF")),
+            Some(0x00AF)
+        );
+        assert_eq!(
+            parse_boot_option_variable_name(&ucs2!("Boot00AF")[..8]),
+            Some(0x00AF)
+        );
+        assert_eq!(parse_boot_option_variable_name(ucs2!("Boot00af")), None);
+        assert_eq!(parse_boot_option_variable_name(ucs2!("Boot0000A")), None);
+        assert_eq!(parse_boot_option_variable_name(ucs2!("Driver00")), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    mod variables {
+        extern crate std;
+
+        use std::cell::RefCell;
+        use std::vec::Vec;
+
+        use super::super::*;
+        use crate::status::{EFI_NOT_FOUND, EFI_SUCCESS, EFI_UNSUPPORTED};
+        use crate::types::{EFI_GUID, UINTN, VOID};
+
+        type Variable = (Vec<CHAR16>, EFI_VARIABLE_ATTRIBUTES, Vec<UINT8>);
+
+        std::thread_local! {
+            static VARIABLES: RefCell<Vec<Variable>> = const { RefCell::new(Vec::new()) };
+        }
+
+        unsafe fn name(VariableName: *const CHAR16) -> Vec<CHAR16> {
+            let mut len = 0;
+            while *VariableName.add(len) != 0 {
+                len += 1;
+            }
+            core::slice::from_raw_parts(VariableName, len + 1).to_vec()
+        }
+
+        extern "efiapi" fn get_variable(
+            VariableName: *const CHAR16,
+            VendorGuid: *const EFI_GUID,
+            Attributes: *mut EFI_VARIABLE_ATTRIBUTES,
+            DataSize: *mut UINTN,
+            Data: *mut VOID,
+        ) -> EFI_STATUS {
+            unsafe {
+                assert!(*VendorGuid == EFI_GLOBAL_VARIABLE);
+                let name = name(VariableName);
+                VARIABLES.with(|v| match v.borrow().iter().find(|(n, ..)| *n == name) {
+                    Some((_, attributes, data)) => {
+                        let size = *DataSize;
+                        *DataSize = data.len();
+                        if size < data.len() {
+                            return EFI_BUFFER_TOO_SMALL;
+                        }
+                        *Attributes = *attributes;
+                        core::ptr::copy_nonoverlapping(data.as_ptr(), Data.cast(), data.len());
+                        EFI_SUCCESS
+                    }
+                    None => EFI_NOT_FOUND,
+                })
+            }
+        }
+
+        extern "efiapi" fn set_variable(
+            VariableName: *const CHAR16,
+            VendorGuid: *const EFI_GUID,
+            Attributes: EFI_VARIABLE_ATTRIBUTES,
+            DataSize: UINTN,
+            Data: *const VOID,
+        ) -> EFI_STATUS {
+            unsafe {
+                assert!(*VendorGuid == EFI_GLOBAL_VARIABLE);
+                let name = name(VariableName);
+                let data = core::slice::from_raw_parts(Data.cast::<UINT8>(), DataSize).to_vec();
+                VARIABLES.with(|v| {
+                    let mut v = v.borrow_mut();
+                    v.retain(|(n, ..)| *n != name);
+                    if !data.is_empty() {
+                        v.push((name, Attributes, data));
+                    }
+                });
+                EFI_SUCCESS
+            }
+        }
+
+        extern "efiapi" fn unsupported() -> EFI_STATUS {
+            EFI_UNSUPPORTED
+        }
+
+        /// Builds a runtime services table backed by an in-memory variable store, with every
+        /// service other than `GetVariable()` and `SetVariable()` unsupported.
+        fn runtime_services() -> EFI_RUNTIME_SERVICES {
+            let mut table = [unsupported as *const () as usize; 17];
+            table[..3].fill(0);
+            table[9] = get_variable as *const () as usize;
+            table[11] = set_variable as *const () as usize;
+
+            // SAFETY: the table header is zeroed and every service slot holds an `efiapi`
+            // function pointer, matching the layout of `EFI_RUNTIME_SERVICES`.
+            unsafe { core::mem::transmute(table) }
+        }
+
+        fn set(Name: &[CHAR16], Data: &[UINT8]) {
+            VARIABLES.with(|v| {
+                v.borrow_mut()
+                    .push((Name.to_vec(), ORDER_ATTRIBUTES, Data.to_vec()))
+            });
+        }
+
+        fn get(Name: &[CHAR16]) -> Option<(EFI_VARIABLE_ATTRIBUTES, Vec<UINT8>)> {
+            VARIABLES.with(|v| {
+                v.borrow()
+                    .iter()
+                    .find(|(n, ..)| n == Name)
+                    .map(|(_, a, d)| (*a, d.clone()))
+            })
+        }
+
+        #[test]
+        fn boot_order_round_trips() {
+            let rt = runtime_services();
+            set(
+                EFI_BOOT_ORDER_VARIABLE_NAME,
+                &[0x01, 0x00, 0x00, 0x00, 0x02, 0x00],
+            );
+
+            unsafe {
+                assert_eq!(get_boot_order(&rt), Ok(std::vec![1, 0, 2]));
+                assert_eq!(set_boot_order(&rt, &[2, 0x1234]), Ok(()));
+                assert_eq!(get_boot_order(&rt), Ok(std::vec![2, 0x1234]));
+            }
+            assert_eq!(
+                get(EFI_BOOT_ORDER_VARIABLE_NAME),
+                Some((ORDER_ATTRIBUTES, std::vec![0x02, 0x00, 0x34, 0x12]))
+            );
+        }
+
+        #[test]
+        fn boot_next_is_set_and_deleted() {
+            let rt = runtime_services();
+
+            unsafe {
+                assert_eq!(get_boot_next(&rt), Err(EFI_NOT_FOUND));
+                assert_eq!(set_boot_next(&rt, Some(0x0003)), Ok(()));
+                assert_eq!(get_boot_next(&rt), Ok(0x0003));
+                assert_eq!(set_boot_next(&rt, None), Ok(()));
+                assert_eq!(get_boot_next(&rt), Err(EFI_NOT_FOUND));
+            }
+        }
+
+        #[test]
+        fn boot_current_is_parsed() {
+            let rt = runtime_services();
+
+            set(EFI_BOOT_CURRENT_VARIABLE_NAME, &[0x01, 0x00]);
+            unsafe { assert_eq!(get_boot_current(&rt), Ok(0x0001)) };
+
+            VARIABLES.with(|v| v.borrow_mut().clear());
+            set(EFI_BOOT_CURRENT_VARIABLE_NAME, &[0x01]);
+            unsafe { assert_eq!(get_boot_current(&rt), Err(EFI_INVALID_PARAMETER)) };
+        }
+    }
+}
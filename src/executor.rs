@@ -0,0 +1,792 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+//! # Event-Driven Async Executor
+//!
+//! This module provides [`Executor`], a minimal executor for futures which wait on UEFI events,
+//! such as the completion events of [`EFI_BLOCK_IO2_TOKEN`] and [`EFI_DISK_IO2_TOKEN`], the
+//! [`WaitForKey`] event of the console, or timers. The executor polls a single future, which may
+//! combine any number of futures with [`select()`], [`join()`] and [`Executor::timeout()`]. While
+//! the future is pending, the executor blocks in [`WaitForEvent()`] on the events the future
+//! registered through [`Executor::poll_event()`] when it was last polled, and on an event of its
+//! own that its wakers signal, so futures may also be woken from event notification functions.
+//!
+//! Events waited on by the executor must not be of type [`EVT_NOTIFY_SIGNAL`], and the executor
+//! must run at [`TPL_APPLICATION`], as required by [`WaitForEvent()`].
+//!
+//! [`Executor`]: crate::executor::Executor
+//! [`EFI_BLOCK_IO2_TOKEN`]: crate::protocols::media::block_io2::EFI_BLOCK_IO2_TOKEN
+//! [`EFI_DISK_IO2_TOKEN`]: crate::protocols::media::disk_io2::EFI_DISK_IO2_TOKEN
+//! [`WaitForKey`]: crate::protocols::console::EFI_SIMPLE_TEXT_INPUT_PROTOCOL::WaitForKey
+//! [`select()`]: crate::executor::select
+//! [`join()`]: crate::executor::join
+//! [`Executor::timeout()`]: ./struct.Executor.html#method.timeout
+//! [`Executor::poll_event()`]: ./struct.Executor.html#method.poll_event
+//! [`WaitForEvent()`]: crate::tables::boot_services::EFI_BOOT_SERVICES::WaitForEvent
+//! [`EVT_NOTIFY_SIGNAL`]: crate::tables::boot_services::EVT_NOTIFY_SIGNAL
+//! [`TPL_APPLICATION`]: crate::tables::boot_services::TPL_APPLICATION
+
+use core::cell::Cell;
+use core::future::Future;
+use core::pin::{pin, Pin};
+use core::ptr;
+use core::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+use crate::status::{
+    EFI_ALREADY_STARTED, EFI_NOT_READY, EFI_OUT_OF_RESOURCES, EFI_SUCCESS, EFI_TIMEOUT,
+};
+use crate::tables::boot_services::{Event, EFI_BOOT_SERVICES, EFI_TIMER_DELAY, TPL_APPLICATION};
+use crate::types::{EFI_EVENT, EFI_STATUS, UINT64, VOID};
+
+/// The maximum number of events a future may wait on at once.
+pub const EXECUTOR_MAX_EVENTS: usize = 32;
+
+static WAKER_VTABLE: RawWakerVTable = RawWakerVTable::new(clone_waker, wake, wake, |_| {});
+
+/// The state of an [`Executor`] shared with its wakers, which may be woken from event
+/// notification functions.
+///
+/// [`Executor`]: crate::executor::Executor
+struct WakeState {
+    boot_services: *const EFI_BOOT_SERVICES,
+    /// Whether a waker was woken since the future was last polled.
+    woken: AtomicBool,
+    /// Whether a waker was cloned while running the current future, so it may be woken later.
+    kept: AtomicBool,
+    /// The event signaled by the wakers, or null when the executor is not running.
+    event: AtomicPtr<VOID>,
+}
+
+unsafe fn clone_waker(Data: *const ()) -> RawWaker {
+    (*Data.cast::<WakeState>())
+        .kept
+        .store(true, Ordering::Relaxed);
+    RawWaker::new(Data, &WAKER_VTABLE)
+}
+
+unsafe fn wake(Data: *const ()) {
+    let state = &*Data.cast::<WakeState>();
+    state.woken.store(true, Ordering::Relaxed);
+
+    // The executor may be blocked in `WaitForEvent()`, which only returns for an event.
+    let event = state.event.load(Ordering::Relaxed);
+    if !event.is_null() {
+        (*state.boot_services).SignalEvent(event);
+    }
+}
+
+/// Runs futures to completion, blocking in [`WaitForEvent()`] while they wait on events.
+///
+/// Waking the waker passed to a future makes the executor poll it again, without waiting if it
+/// is woken while being polled, which allows futures to yield or to be driven by something other
+/// than an event, such as an event notification function.
+///
+/// [`WaitForEvent()`]: crate::tables::boot_services::EFI_BOOT_SERVICES::WaitForEvent
+pub struct Executor<'a> {
+    boot_services: &'a EFI_BOOT_SERVICES,
+    events: [Cell<EFI_EVENT>; EXECUTOR_MAX_EVENTS],
+    len: Cell<usize>,
+    overflow: Cell<bool>,
+    signaled: Cell<EFI_EVENT>,
+    running: Cell<bool>,
+    wake: WakeState,
+}
+
+impl<'a> Executor<'a> {
+    /// Creates an executor using the given boot services table.
+    ///
+    /// # Safety
+    ///
+    /// [`BootServices`] must be the boot services table of the running firmware.
+    ///
+    /// The wakers passed to futures refer to the executor, so they must not be used once it has
+    /// been moved or dropped.
+    ///
+    /// [`BootServices`]: ./struct.Executor.html#method.new
+    pub unsafe fn new(BootServices: &'a EFI_BOOT_SERVICES) -> Self {
+        Self {
+            boot_services: BootServices,
+            events: [(); EXECUTOR_MAX_EVENTS].map(|_| Cell::new(ptr::null_mut())),
+            len: Cell::new(0),
+            overflow: Cell::new(false),
+            signaled: Cell::new(ptr::null_mut()),
+            running: Cell::new(false),
+            wake: WakeState {
+                boot_services: BootServices,
+                woken: AtomicBool::new(false),
+                kept: AtomicBool::new(false),
+                event: AtomicPtr::new(ptr::null_mut()),
+            },
+        }
+    }
+
+    /// Returns the boot services table used by the executor.
+    pub fn boot_services(&self) -> &'a EFI_BOOT_SERVICES {
+        self.boot_services
+    }
+
+    /// Polls [`Future`] until it completes, and returns its output.
+    ///
+    /// # Errors
+    ///
+    /// [`EFI_ALREADY_STARTED`] - the executor is already running a future.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - the future waited on more than [`EXECUTOR_MAX_EVENTS`] events.
+    ///
+    /// [`EFI_NOT_READY`] - the future is pending without waiting on any event or keeping a waker,
+    /// so it can never complete.
+    ///
+    /// Any error returned by [`CreateEvent()`] or [`WaitForEvent()`].
+    ///
+    /// [`Future`]: ./struct.Executor.html#method.block_on
+    /// [`EFI_ALREADY_STARTED`]: crate::status::EFI_ALREADY_STARTED
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    /// [`EXECUTOR_MAX_EVENTS`]: crate::executor::EXECUTOR_MAX_EVENTS
+    /// [`EFI_NOT_READY`]: crate::status::EFI_NOT_READY
+    /// [`CreateEvent()`]: crate::tables::boot_services::EFI_BOOT_SERVICES::CreateEvent
+    /// [`WaitForEvent()`]: crate::tables::boot_services::EFI_BOOT_SERVICES::WaitForEvent
+    pub fn block_on<F: Future>(&self, Future: F) -> Result<F::Output, EFI_STATUS> {
+        if self.running.replace(true) {
+            return Err(EFI_ALREADY_STARTED);
+        }
+
+        let result = self.run(pin!(Future));
+        self.running.set(false);
+        self.signaled.set(ptr::null_mut());
+
+        result
+    }
+
+    /// Polls the event for a future, returning [`Poll::Ready`] once it has been signaled and
+    /// registering it to be waited on by the executor otherwise.
+    ///
+    /// This is the building block for futures waiting on events; [`Event`] must not be of type
+    /// [`EVT_NOTIFY_SIGNAL`].
+    ///
+    /// # Safety
+    ///
+    /// [`Event`] must remain valid until the future completes, or until it is passed to
+    /// [`unregister_event()`], which futures owning the event must do before closing it.
+    ///
+    /// [`Event`]: ./struct.Executor.html#method.poll_event
+    /// [`EVT_NOTIFY_SIGNAL`]: crate::tables::boot_services::EVT_NOTIFY_SIGNAL
+    /// [`unregister_event()`]: ./struct.Executor.html#method.unregister_event
+    pub unsafe fn poll_event(&self, Event: EFI_EVENT) -> Poll<Result<(), EFI_STATUS>> {
+        if self.signaled.get() == Event {
+            self.signaled.set(ptr::null_mut());
+            return Poll::Ready(Ok(()));
+        }
+
+        match self.boot_services.CheckEvent(Event) {
+            EFI_SUCCESS => Poll::Ready(Ok(())),
+            EFI_NOT_READY => {
+                self.register(Event);
+                Poll::Pending
+            }
+            status => Poll::Ready(Err(status)),
+        }
+    }
+
+    /// Stops waiting on [`Event`], which was registered by [`poll_event()`] for a future that is
+    /// being dropped.
+    ///
+    /// [`Event`]: ./struct.Executor.html#method.unregister_event
+    /// [`poll_event()`]: ./struct.Executor.html#method.poll_event
+    pub fn unregister_event(&self, Event: EFI_EVENT) {
+        if self.signaled.get() == Event {
+            self.signaled.set(ptr::null_mut());
+        }
+
+        let mut len = self.len.get();
+        let mut index = 0;
+        while index < len {
+            if self.events[index].get() == Event {
+                len -= 1;
+                self.events[index].set(self.events[len].get());
+            } else {
+                index += 1;
+            }
+        }
+
+        self.len.set(len);
+    }
+
+    /// Returns a future completing once [`Event`] is signaled.
+    ///
+    /// # Safety
+    ///
+    /// [`Event`] must be a valid event for as long as the returned future is alive.
+    ///
+    /// [`Event`]: ./struct.Executor.html#method.wait_event
+    pub unsafe fn wait_event(&self, Event: EFI_EVENT) -> WaitEvent<'_> {
+        WaitEvent {
+            executor: self,
+            event: Event,
+        }
+    }
+
+    /// Returns a future completing after [`TriggerTime`], in units of 100ns.
+    ///
+    /// [`TriggerTime`]: ./struct.Executor.html#method.timer
+    pub fn timer(&self, TriggerTime: UINT64) -> Result<Timer<'_>, EFI_STATUS> {
+        // SAFETY: the boot services table is valid per the contract of `new`.
+        let event = unsafe { self.boot_services.create_timer() }?;
+        event.set_timer(EFI_TIMER_DELAY::TimerRelative, TriggerTime)?;
+
+        Ok(Timer {
+            executor: self,
+            event,
+        })
+    }
+
+    /// Returns a future resolving to the output of [`Future`], or to [`EFI_TIMEOUT`] if it has not
+    /// completed after [`TriggerTime`], in units of 100ns.
+    ///
+    /// [`Future`]: ./struct.Executor.html#method.timeout
+    /// [`EFI_TIMEOUT`]: crate::status::EFI_TIMEOUT
+    /// [`TriggerTime`]: ./struct.Executor.html#method.timeout
+    pub fn timeout<F: Future>(
+        &self,
+        TriggerTime: UINT64,
+        Future: F,
+    ) -> Result<Timeout<'_, F>, EFI_STATUS> {
+        Ok(Timeout {
+            future: Future,
+            timer: self.timer(TriggerTime)?,
+        })
+    }
+
+    fn register(&self, Event: EFI_EVENT) {
+        let len = self.len.get();
+        if len == EXECUTOR_MAX_EVENTS {
+            self.overflow.set(true);
+            return;
+        }
+
+        self.events[len].set(Event);
+        self.len.set(len + 1);
+    }
+
+    fn run<T>(&self, future: Pin<&mut impl Future<Output = T>>) -> Result<T, EFI_STATUS> {
+        // SAFETY: the boot services table is valid per the contract of `new`, and an event
+        // without a notification function is only ever signaled.
+        let event = unsafe {
+            self.boot_services
+                .create_event(0, TPL_APPLICATION, None, ptr::null_mut(), None)
+        }?;
+        self.wake.kept.store(false, Ordering::Relaxed);
+        self.wake.event.store(event.as_raw(), Ordering::Relaxed);
+
+        let result = self.poll_until_ready(future, &event);
+
+        // Wakers kept beyond the future no longer signal the event, which is closed on return.
+        self.wake.event.store(ptr::null_mut(), Ordering::Relaxed);
+        result
+    }
+
+    fn poll_until_ready<T>(
+        &self,
+        mut future: Pin<&mut impl Future<Output = T>>,
+        WakeEvent: &Event<'_>,
+    ) -> Result<T, EFI_STATUS> {
+        // SAFETY: the state outlives the future, which is dropped before `block_on` returns, and
+        // wakers kept beyond it must not be used once the executor is gone per the contract of
+        // `new`.
+        let data = &self.wake as *const WakeState as *const ();
+        let waker = unsafe { Waker::from_raw(RawWaker::new(data, &WAKER_VTABLE)) };
+        let mut context = Context::from_waker(&waker);
+
+        loop {
+            self.len.set(0);
+            self.overflow.set(false);
+            // Wakes from before this poll are seen by the poll itself.
+            self.wake.woken.store(false, Ordering::Relaxed);
+            WakeEvent.check()?;
+
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return Ok(output);
+            }
+
+            // An event signaled by the last wait but not consumed belongs to a future that has
+            // completed or been dropped since.
+            self.signaled.set(ptr::null_mut());
+
+            if self.wake.woken.load(Ordering::Relaxed) {
+                continue;
+            }
+
+            if self.overflow.get() {
+                return Err(EFI_OUT_OF_RESOURCES);
+            }
+
+            let len = self.len.get();
+            if len == 0 && !self.wake.kept.load(Ordering::Relaxed) {
+                return Err(EFI_NOT_READY);
+            }
+
+            // Events signaled along with the wake event stay signaled, and are seen by the next
+            // poll.
+            let mut events = [ptr::null_mut(); EXECUTOR_MAX_EVENTS + 1];
+            events[0] = WakeEvent.as_raw();
+            for (event, registered) in events[1..].iter_mut().zip(&self.events[..len]) {
+                *event = registered.get();
+            }
+
+            // SAFETY: the boot services table is valid per the contract of `new`.
+            let index = unsafe { self.boot_services.wait_for_event(&events[..len + 1]) }?;
+
+            // `WaitForEvent()` clears the signaled state of the event, so it is recorded for the
+            // future polling it. The wake event only calls for another poll.
+            if index > 0 {
+                self.signaled.set(events[index]);
+            }
+        }
+    }
+}
+
+/// A future completing once an event is signaled, returned by [`Executor::wait_event()`].
+///
+/// [`Executor::wait_event()`]: ./struct.Executor.html#method.wait_event
+#[must_use = "futures do nothing unless polled"]
+pub struct WaitEvent<'a> {
+    executor: &'a Executor<'a>,
+    event: EFI_EVENT,
+}
+
+impl Future for WaitEvent<'_> {
+    type Output = Result<(), EFI_STATUS>;
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: the event is valid per the contract of `Executor::wait_event`.
+        unsafe { self.executor.poll_event(self.event) }
+    }
+}
+
+/// A future completing once a timer expires, returned by [`Executor::timer()`]. The timer event
+/// is closed when the future is dropped.
+///
+/// [`Executor::timer()`]: ./struct.Executor.html#method.timer
+#[must_use = "futures do nothing unless polled"]
+pub struct Timer<'a> {
+    executor: &'a Executor<'a>,
+    event: Event<'a>,
+}
+
+impl Drop for WaitEvent<'_> {
+    fn drop(&mut self) {
+        self.executor.unregister_event(self.event);
+    }
+}
+
+impl<'a> Timer<'a> {
+    /// Returns the timer event.
+    pub fn event(&self) -> &Event<'a> {
+        &self.event
+    }
+}
+
+impl Future for Timer<'_> {
+    type Output = Result<(), EFI_STATUS>;
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: the timer event is owned by the future.
+        unsafe { self.executor.poll_event(self.event.as_raw()) }
+    }
+}
+
+/// A future resolving to the output of another future, or to [`EFI_TIMEOUT`] if a timer expires
+/// first, returned by [`Executor::timeout()`].
+///
+/// [`EFI_TIMEOUT`]: crate::status::EFI_TIMEOUT
+/// [`Executor::timeout()`]: ./struct.Executor.html#method.timeout
+#[must_use = "futures do nothing unless polled"]
+pub struct Timeout<'a, F> {
+    future: F,
+    timer: Timer<'a>,
+}
+
+impl<F: Future> Future for Timeout<'_, F> {
+    type Output = Result<F::Output, EFI_STATUS>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: the fields are never moved out of the pinned future.
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+
+        if let Poll::Ready(output) = future.poll(cx) {
+            return Poll::Ready(Ok(output));
+        }
+
+        match Pin::new(&mut this.timer).poll(cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(Err(EFI_TIMEOUT)),
+            Poll::Ready(Err(status)) => Poll::Ready(Err(status)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for Timer<'_> {
+    fn drop(&mut self) {
+        self.executor.unregister_event(self.event.as_raw());
+    }
+}
+
+/// The output of [`select()`], telling which of the two futures completed first.
+///
+/// [`select()`]: crate::executor::select
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Either<A, B> {
+    /// The first future completed first.
+    Left(A),
+    /// The second future completed first.
+    Right(B),
+}
+
+/// Returns a future completing with the output of whichever of [`A`] and [`B`] completes first.
+///
+/// [`A`] is polled first, so it wins if both are ready. The other future is dropped without
+/// completing, which closes any event it owns.
+///
+/// [`A`]: crate::executor::select
+/// [`B`]: crate::executor::select
+pub fn select<A: Future, B: Future>(A: A, B: B) -> Select<A, B> {
+    Select { a: A, b: B }
+}
+
+/// A future returned by [`select()`].
+///
+/// [`select()`]: crate::executor::select
+#[must_use = "futures do nothing unless polled"]
+pub struct Select<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A: Future, B: Future> Future for Select<A, B> {
+    type Output = Either<A::Output, B::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: the fields are never moved out of the pinned future.
+        let this = unsafe { self.get_unchecked_mut() };
+        let (a, b) = unsafe {
+            (
+                Pin::new_unchecked(&mut this.a),
+                Pin::new_unchecked(&mut this.b),
+            )
+        };
+
+        if let Poll::Ready(output) = a.poll(cx) {
+            return Poll::Ready(Either::Left(output));
+        }
+
+        match b.poll(cx) {
+            Poll::Ready(output) => Poll::Ready(Either::Right(output)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Returns a future completing with the outputs of both [`A`] and [`B`], which run concurrently.
+///
+/// [`A`]: crate::executor::join
+/// [`B`]: crate::executor::join
+pub fn join<A: Future, B: Future>(A: A, B: B) -> Join<A, B> {
+    Join {
+        a: MaybeDone::Pending(A),
+        b: MaybeDone::Pending(B),
+    }
+}
+
+/// A future returned by [`join()`].
+///
+/// [`join()`]: crate::executor::join
+#[must_use = "futures do nothing unless polled"]
+pub struct Join<A: Future, B: Future> {
+    a: MaybeDone<A>,
+    b: MaybeDone<B>,
+}
+
+impl<A: Future, B: Future> Future for Join<A, B> {
+    type Output = (A::Output, B::Output);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: the fields are never moved out of the pinned future.
+        let this = unsafe { self.get_unchecked_mut() };
+        let a_done = unsafe { Pin::new_unchecked(&mut this.a) }.poll(cx);
+        let b_done = unsafe { Pin::new_unchecked(&mut this.b) }.poll(cx);
+        if !a_done || !b_done {
+            return Poll::Pending;
+        }
+
+        match (this.a.take(), this.b.take()) {
+            (Some(a), Some(b)) => Poll::Ready((a, b)),
+            _ => panic!("Join polled after completion"),
+        }
+    }
+}
+
+enum MaybeDone<F: Future> {
+    Pending(F),
+    Done(F::Output),
+    Taken,
+}
+
+impl<F: Future> MaybeDone<F> {
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> bool {
+        // SAFETY: the future is dropped in place when it completes, and never moved.
+        let this = unsafe { self.get_unchecked_mut() };
+        if let MaybeDone::Pending(future) = this {
+            match unsafe { Pin::new_unchecked(future) }.poll(cx) {
+                Poll::Ready(output) => *this = MaybeDone::Done(output),
+                Poll::Pending => return false,
+            }
+        }
+
+        true
+    }
+
+    fn take(&mut self) -> Option<F::Output> {
+        match core::mem::replace(self, MaybeDone::Taken) {
+            MaybeDone::Done(output) => Some(output),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::cell::RefCell;
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::status::{EFI_DEVICE_ERROR, EFI_UNSUPPORTED};
+    use crate::tables::boot_services::{EFI_EVENT_NOTIFY, EFI_EVENT_TYPE};
+    use crate::types::{EFI_TPL, UINTN};
+
+    /// The events that are open and those that are signaled.
+    struct Events {
+        open: Vec<EFI_EVENT>,
+        signaled: Vec<EFI_EVENT>,
+        next: usize,
+        waits: usize,
+    }
+
+    std::thread_local! {
+        static EVENTS: RefCell<Events> = const {
+            RefCell::new(Events {
+                open: Vec::new(),
+                signaled: Vec::new(),
+                next: 1,
+                waits: 0,
+            })
+        };
+        // A waker woken while the executor is blocked, as an event notification function would.
+        static NOTIFY: RefCell<Option<Waker>> = const { RefCell::new(None) };
+    }
+
+    extern "efiapi" fn create_event(
+        _: EFI_EVENT_TYPE,
+        _: EFI_TPL,
+        _: Option<EFI_EVENT_NOTIFY>,
+        _: *mut VOID,
+        Event: *mut EFI_EVENT,
+    ) -> EFI_STATUS {
+        EVENTS.with(|events| {
+            let mut events = events.borrow_mut();
+            let event = events.next as EFI_EVENT;
+            events.next += 1;
+            events.open.push(event);
+            unsafe { *Event = event };
+        });
+        EFI_SUCCESS
+    }
+
+    extern "efiapi" fn wait_for_event(
+        NumberOfEvents: UINTN,
+        Event: *mut EFI_EVENT,
+        Index: *mut UINTN,
+    ) -> EFI_STATUS {
+        if let Some(waker) = NOTIFY.with(|notify| notify.borrow_mut().take()) {
+            waker.wake();
+        }
+
+        let waited = unsafe { core::slice::from_raw_parts(Event, NumberOfEvents) };
+        EVENTS.with(|events| {
+            let mut events = events.borrow_mut();
+            events.waits += 1;
+            let index = waited
+                .iter()
+                .position(|event| events.signaled.contains(event));
+            match index {
+                Some(index) => {
+                    events.signaled.retain(|&event| event != waited[index]);
+                    unsafe { *Index = index };
+                    EFI_SUCCESS
+                }
+                // Nothing could ever signal the events.
+                None => EFI_DEVICE_ERROR,
+            }
+        })
+    }
+
+    extern "efiapi" fn signal_event(Event: EFI_EVENT) -> EFI_STATUS {
+        EVENTS.with(|events| {
+            let mut events = events.borrow_mut();
+            assert!(events.open.contains(&Event));
+            events.signaled.push(Event);
+        });
+        EFI_SUCCESS
+    }
+
+    extern "efiapi" fn close_event(Event: EFI_EVENT) -> EFI_STATUS {
+        EVENTS.with(|events| {
+            let mut events = events.borrow_mut();
+            events.open.retain(|&event| event != Event);
+            events.signaled.retain(|&event| event != Event);
+        });
+        EFI_SUCCESS
+    }
+
+    extern "efiapi" fn check_event(Event: EFI_EVENT) -> EFI_STATUS {
+        EVENTS.with(|events| {
+            let mut events = events.borrow_mut();
+            match events.signaled.iter().position(|&event| event == Event) {
+                Some(index) => {
+                    events.signaled.remove(index);
+                    EFI_SUCCESS
+                }
+                None => EFI_NOT_READY,
+            }
+        })
+    }
+
+    extern "efiapi" fn unsupported() -> EFI_STATUS {
+        EFI_UNSUPPORTED
+    }
+
+    const SLOTS: usize = core::mem::size_of::<EFI_BOOT_SERVICES>() / core::mem::size_of::<usize>();
+
+    /// Builds a boot services table with in-memory events, with every service other than those
+    /// of events unsupported.
+    fn boot_services() -> EFI_BOOT_SERVICES {
+        let mut table = [unsupported as *const () as usize; SLOTS];
+        table[..3].fill(0);
+        table[10] = create_event as *const () as usize;
+        table[12] = wait_for_event as *const () as usize;
+        table[13] = signal_event as *const () as usize;
+        table[14] = close_event as *const () as usize;
+        table[15] = check_event as *const () as usize;
+        // SAFETY: the table holds the header and function pointers of the boot services.
+        unsafe { core::mem::transmute(table) }
+    }
+
+    fn waits() -> usize {
+        EVENTS.with(|events| events.borrow().waits)
+    }
+
+    fn open_events() -> usize {
+        EVENTS.with(|events| events.borrow().open.len())
+    }
+
+    /// Polls as pending the given number of times, calling `Pending` with the context each time.
+    struct Pending<F> {
+        count: usize,
+        pending: F,
+    }
+
+    impl<F: FnMut(&mut Context<'_>) + Unpin> Future for Pending<F> {
+        type Output = usize;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<usize> {
+            if self.count == 0 {
+                return Poll::Ready(waits());
+            }
+
+            self.count -= 1;
+            (self.pending)(cx);
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn woken_while_polled() {
+        let boot_services = boot_services();
+        let executor = unsafe { Executor::new(&boot_services) };
+        let future = Pending {
+            count: 3,
+            pending: |cx: &mut Context<'_>| cx.waker().wake_by_ref(),
+        };
+        assert_eq!(executor.block_on(future), Ok(0));
+        assert_eq!(open_events(), 0);
+    }
+
+    #[test]
+    fn woken_while_waiting() {
+        let boot_services = boot_services();
+        let executor = unsafe { Executor::new(&boot_services) };
+        let future = Pending {
+            count: 2,
+            pending: |cx: &mut Context<'_>| {
+                let waker = cx.waker().clone();
+                NOTIFY.with(|notify| *notify.borrow_mut() = Some(waker));
+            },
+        };
+        assert_eq!(executor.block_on(future), Ok(2));
+        assert_eq!(open_events(), 0);
+    }
+
+    #[test]
+    fn never_woken() {
+        let boot_services = boot_services();
+        let executor = unsafe { Executor::new(&boot_services) };
+        let future = Pending {
+            count: 1,
+            pending: |_: &mut Context<'_>| {},
+        };
+        assert_eq!(executor.block_on(future), Err(EFI_NOT_READY));
+        assert_eq!(waits(), 0);
+        assert_eq!(open_events(), 0);
+    }
+
+    #[test]
+    fn wait_event() {
+        let boot_services = boot_services();
+        let executor = unsafe { Executor::new(&boot_services) };
+        let event =
+            unsafe { boot_services.create_event(0, TPL_APPLICATION, None, ptr::null_mut(), None) }
+                .unwrap();
+        let raw = event.as_raw();
+
+        // The event is signaled before the executor waits on it and on its own event.
+        let future = async {
+            let wait = unsafe { executor.wait_event(raw) };
+            let signal = Pending {
+                count: 1,
+                pending: |_: &mut Context<'_>| {
+                    signal_event(raw);
+                },
+            };
+            join(wait, signal).await
+        };
+        assert_eq!(executor.block_on(future), Ok((Ok(()), 1)));
+        assert!(EVENTS.with(|events| events.borrow().signaled.is_empty()));
+        drop(event);
+        assert_eq!(open_events(), 0);
+    }
+}
@@ -0,0 +1,452 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # BMP Decoder
+//!
+//! This module decodes Windows bitmap (BMP) images into [`EFI_GRAPHICS_OUTPUT_BLT_PIXEL`]s,
+//! ready to be drawn with [`Blt()`] or onto a [`Surface`].
+//!
+//! Uncompressed 24-bit and 32-bit images are supported, stored either bottom-up or top-down,
+//! including 32-bit images with `BI_BITFIELDS` color masks. The alpha channel, if any, is
+//! ignored. Every offset and size read from the image is checked against the image data, so
+//! decoding untrusted data fails with an error rather than reading out of bounds.
+//!
+//! [`EFI_GRAPHICS_OUTPUT_BLT_PIXEL`]: crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_PIXEL
+//! [`Blt()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::Blt
+//! [`Surface`]: crate::graphics::Surface
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::graphics::BltBuffer;
+use crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_PIXEL;
+use crate::status::{EFI_BUFFER_TOO_SMALL, EFI_INVALID_PARAMETER, EFI_UNSUPPORTED};
+use crate::types::{EFI_STATUS, UINT16, UINT32, UINT8};
+
+/// The signature at the start of a BMP file, `"BM"`.
+pub const BMP_SIGNATURE: UINT16 = 0x4D42;
+/// The size of the BMP file header in bytes.
+pub const SIZE_OF_BMP_FILE_HEADER: usize = 14;
+/// The size of the `BITMAPINFOHEADER` in bytes, the smallest information header supported.
+pub const SIZE_OF_BMP_INFO_HEADER: usize = 40;
+
+/// The pixels are stored uncompressed.
+pub const BI_RGB: UINT32 = 0;
+/// The pixels are stored uncompressed, with color masks following the information header.
+pub const BI_BITFIELDS: UINT32 = 3;
+
+/// The largest width or height accepted, so the pixel count of an image always fits in memory.
+pub const BMP_MAX_DIMENSION: UINT32 = 0x4000;
+
+/// A parsed BMP image borrowing its data.
+#[derive(Clone, Copy)]
+pub struct Bmp<'a> {
+    data: &'a [UINT8],
+    width: usize,
+    height: usize,
+    top_down: bool,
+    bytes_per_pixel: usize,
+    stride: usize,
+    masks: [UINT32; 3],
+}
+
+impl<'a> Bmp<'a> {
+    /// Parses the headers of the BMP image in `Data`.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if the image is malformed or truncated, and
+    /// [`EFI_UNSUPPORTED`] if it is valid but not in a supported format.
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    pub fn parse(Data: &'a [UINT8]) -> Result<Self, EFI_STATUS> {
+        if Data.len() < SIZE_OF_BMP_FILE_HEADER + SIZE_OF_BMP_INFO_HEADER
+            || read_u16(Data, 0) != BMP_SIGNATURE
+        {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        let offset = read_u32(Data, 10) as usize;
+        let header_size = read_u32(Data, 14) as usize;
+        let width = read_u32(Data, 18) as i32;
+        let height = read_u32(Data, 22) as i32;
+        let planes = read_u16(Data, 26);
+        let bits = read_u16(Data, 28);
+        let compression = read_u32(Data, 30);
+        if header_size < SIZE_OF_BMP_INFO_HEADER || planes != 1 || width <= 0 || height == 0 {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+        if width as UINT32 > BMP_MAX_DIMENSION || height.unsigned_abs() > BMP_MAX_DIMENSION {
+            return Err(EFI_UNSUPPORTED);
+        }
+
+        let masks = match (bits, compression) {
+            (24, BI_RGB) => [0x00FF0000, 0x0000FF00, 0x000000FF],
+            (32, BI_RGB) => [0x00FF0000, 0x0000FF00, 0x000000FF],
+            (32, BI_BITFIELDS) => {
+                // The masks are part of larger headers, and follow the smallest one otherwise.
+                let start = SIZE_OF_BMP_FILE_HEADER + SIZE_OF_BMP_INFO_HEADER;
+                if Data.len() < start + 12 {
+                    return Err(EFI_INVALID_PARAMETER);
+                }
+                let masks = [
+                    read_u32(Data, start),
+                    read_u32(Data, start + 4),
+                    read_u32(Data, start + 8),
+                ];
+                if masks.iter().any(|&mask| !is_contiguous(mask)) {
+                    return Err(EFI_UNSUPPORTED);
+                }
+                masks
+            }
+            _ => return Err(EFI_UNSUPPORTED),
+        };
+
+        let width = width as usize;
+        let rows = height.unsigned_abs() as usize;
+        let bytes_per_pixel = usize::from(bits / 8);
+        // Rows are padded to a multiple of four bytes.
+        let stride = (width * bytes_per_pixel + 3) & !3;
+        match rows
+            .checked_mul(stride)
+            .and_then(|size| size.checked_add(offset))
+        {
+            Some(end) if end <= Data.len() => {}
+            _ => return Err(EFI_INVALID_PARAMETER),
+        }
+
+        Ok(Self {
+            data: &Data[offset..],
+            width,
+            height: rows,
+            top_down: height < 0,
+            bytes_per_pixel,
+            stride,
+            masks,
+        })
+    }
+
+    /// Returns the width in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns whether the rows are stored top row first.
+    pub fn is_top_down(&self) -> bool {
+        self.top_down
+    }
+
+    /// Returns the number of bits per pixel, 24 or 32.
+    pub fn bits_per_pixel(&self) -> usize {
+        self.bytes_per_pixel * 8
+    }
+
+    /// Returns the pixel at (`X`, `Y`), counting rows from the top, or `None` if it is outside
+    /// of the image.
+    pub fn pixel(&self, X: usize, Y: usize) -> Option<EFI_GRAPHICS_OUTPUT_BLT_PIXEL> {
+        if X >= self.width || Y >= self.height {
+            return None;
+        }
+
+        Some(self.decode_pixel(&self.row(Y)[X * self.bytes_per_pixel..]))
+    }
+
+    /// Decodes the image into `Buffer`, row by row from the top.
+    ///
+    /// Returns [`EFI_BUFFER_TOO_SMALL`] if `Buffer` holds fewer than [`width()`] *
+    /// [`height()`] pixels.
+    ///
+    /// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+    /// [`width()`]: crate::graphics::bmp::Bmp::width
+    /// [`height()`]: crate::graphics::bmp::Bmp::height
+    pub fn decode_into(
+        &self,
+        Buffer: &mut [EFI_GRAPHICS_OUTPUT_BLT_PIXEL],
+    ) -> Result<(), EFI_STATUS> {
+        if Buffer.len() < self.width * self.height {
+            return Err(EFI_BUFFER_TOO_SMALL);
+        }
+
+        for (y, target) in Buffer
+            .chunks_exact_mut(self.width)
+            .take(self.height)
+            .enumerate()
+        {
+            let row = self.row(y);
+            for (pixel, source) in target
+                .iter_mut()
+                .zip(row.chunks_exact(self.bytes_per_pixel))
+            {
+                *pixel = self.decode_pixel(source);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the image into a newly allocated bitmap.
+    #[cfg(feature = "alloc")]
+    pub fn decode(&self) -> BltBuffer<Vec<EFI_GRAPHICS_OUTPUT_BLT_PIXEL>> {
+        let mut buffer = BltBuffer::with_size(self.width, self.height);
+        // The bitmap has exactly the size of the image.
+        let _ = self.decode_into(buffer.pixels_mut());
+        buffer
+    }
+
+    /// Returns the pixel data of the row `Y`, counting from the top.
+    fn row(&self, Y: usize) -> &'a [UINT8] {
+        let row = if self.top_down {
+            Y
+        } else {
+            self.height - 1 - Y
+        };
+        let start = row * self.stride;
+        &self.data[start..start + self.width * self.bytes_per_pixel]
+    }
+
+    fn decode_pixel(&self, Bytes: &[UINT8]) -> EFI_GRAPHICS_OUTPUT_BLT_PIXEL {
+        let value = match self.bytes_per_pixel {
+            3 => UINT32::from_le_bytes([Bytes[0], Bytes[1], Bytes[2], 0]),
+            _ => UINT32::from_le_bytes([Bytes[0], Bytes[1], Bytes[2], Bytes[3]]),
+        };
+        let [red, green, blue] = self.masks.map(|mask| component(value, mask));
+        EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(red, green, blue)
+    }
+}
+
+fn read_u16(Data: &[UINT8], Offset: usize) -> UINT16 {
+    UINT16::from_le_bytes([Data[Offset], Data[Offset + 1]])
+}
+
+fn read_u32(Data: &[UINT8], Offset: usize) -> UINT32 {
+    UINT32::from_le_bytes([
+        Data[Offset],
+        Data[Offset + 1],
+        Data[Offset + 2],
+        Data[Offset + 3],
+    ])
+}
+
+/// Returns whether the set bits of `Mask` are contiguous.
+fn is_contiguous(Mask: UINT32) -> bool {
+    let shifted = Mask >> Mask.trailing_zeros().min(31);
+    shifted & shifted.wrapping_add(1) == 0
+}
+
+/// Scales the bits of `Value` selected by `Mask` to an 8-bit component.
+fn component(Value: UINT32, Mask: UINT32) -> UINT8 {
+    if Mask == 0 {
+        return 0;
+    }
+
+    let max = u64::from(Mask >> Mask.trailing_zeros());
+    (u64::from((Value & Mask) >> Mask.trailing_zeros()) * 255 / max) as UINT8
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+    use std::vec::Vec;
+
+    use super::*;
+
+    const WIDTH: usize = 3;
+    const HEIGHT: usize = 2;
+
+    fn color(X: usize, Y: usize) -> EFI_GRAPHICS_OUTPUT_BLT_PIXEL {
+        EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(
+            (X * 80) as UINT8,
+            (Y * 120 + 10) as UINT8,
+            (X + Y) as UINT8,
+        )
+    }
+
+    /// Builds a 3x2 BMP image of `Bits` bits per pixel, with color masks if `Masks` is given.
+    fn image(Bits: UINT16, TopDown: bool, Masks: Option<[UINT32; 3]>) -> Vec<UINT8> {
+        let masks = Masks.unwrap_or([0x00FF0000, 0x0000FF00, 0x000000FF]);
+        let bytes_per_pixel = usize::from(Bits / 8);
+        let stride = (WIDTH * bytes_per_pixel + 3) & !3;
+        let offset = SIZE_OF_BMP_FILE_HEADER
+            + SIZE_OF_BMP_INFO_HEADER
+            + if Masks.is_some() { 12 } else { 0 };
+        let height = if TopDown {
+            -(HEIGHT as i32)
+        } else {
+            HEIGHT as i32
+        };
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&BMP_SIGNATURE.to_le_bytes());
+        data.extend_from_slice(&((offset + HEIGHT * stride) as UINT32).to_le_bytes());
+        data.extend_from_slice(&[0; 4]);
+        data.extend_from_slice(&(offset as UINT32).to_le_bytes());
+        data.extend_from_slice(&(SIZE_OF_BMP_INFO_HEADER as UINT32).to_le_bytes());
+        data.extend_from_slice(&(WIDTH as i32).to_le_bytes());
+        data.extend_from_slice(&height.to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.extend_from_slice(&Bits.to_le_bytes());
+        let compression = if Masks.is_some() {
+            BI_BITFIELDS
+        } else {
+            BI_RGB
+        };
+        data.extend_from_slice(&compression.to_le_bytes());
+        data.extend_from_slice(&[0; 20]);
+        if let Some(masks) = Masks {
+            masks
+                .iter()
+                .for_each(|mask| data.extend_from_slice(&mask.to_le_bytes()));
+        }
+
+        for row in 0..HEIGHT {
+            let y = if TopDown { row } else { HEIGHT - 1 - row };
+            for x in 0..WIDTH {
+                let pixel = color(x, y);
+                let value = [pixel.Red, pixel.Green, pixel.Blue].iter().zip(masks).fold(
+                    0,
+                    |value, (&component, mask)| {
+                        value | (UINT32::from(component) << mask.trailing_zeros()) & mask
+                    },
+                );
+                data.extend_from_slice(&value.to_le_bytes()[..bytes_per_pixel]);
+            }
+            data.resize(data.len() + stride - WIDTH * bytes_per_pixel, 0);
+        }
+
+        data
+    }
+
+    #[test]
+    fn decode() {
+        let images = [
+            (24, false, None),
+            (24, true, None),
+            (32, false, None),
+            (32, true, None),
+            (32, false, Some([0x0000FF00, 0x00FF0000, 0xFF000000])),
+        ];
+        for (bits, top_down, masks) in images {
+            let data = image(bits, top_down, masks);
+            let bmp = Bmp::parse(&data).unwrap();
+            assert_eq!(bmp.width(), WIDTH);
+            assert_eq!(bmp.height(), HEIGHT);
+            assert_eq!(bmp.bits_per_pixel(), usize::from(bits));
+            assert_eq!(bmp.is_top_down(), top_down);
+
+            let mut buffer = [EFI_GRAPHICS_OUTPUT_BLT_PIXEL::default(); WIDTH * HEIGHT];
+            bmp.decode_into(&mut buffer).unwrap();
+            for y in 0..HEIGHT {
+                for x in 0..WIDTH {
+                    assert_eq!(buffer[y * WIDTH + x], color(x, y), "({x}, {y})");
+                    assert_eq!(bmp.pixel(x, y), Some(color(x, y)));
+                }
+            }
+            assert_eq!(bmp.pixel(WIDTH, 0), None);
+            assert_eq!(bmp.pixel(0, HEIGHT), None);
+            #[cfg(feature = "alloc")]
+            assert_eq!(bmp.decode().pixels(), buffer);
+
+            assert_eq!(bmp.decode_into(&mut buffer[1..]), Err(EFI_BUFFER_TOO_SMALL));
+        }
+    }
+
+    #[test]
+    fn masks() {
+        // Narrower masks are scaled to eight bits.
+        let mut data = image(32, false, Some([0x0000001F, 0x000007E0, 0x0000F800]));
+        let offset = data.len() - HEIGHT * WIDTH * 4;
+        data[offset..].fill(0);
+        data[offset..offset + 4].copy_from_slice(&0xFFFFu32.to_le_bytes());
+        data[offset + 4..offset + 8].copy_from_slice(&0x0010u32.to_le_bytes());
+        let bmp = Bmp::parse(&data).unwrap();
+        assert_eq!(
+            bmp.pixel(0, 1),
+            Some(EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0xFF, 0xFF, 0xFF))
+        );
+        assert_eq!(
+            bmp.pixel(1, 1),
+            Some(EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0x83, 0, 0))
+        );
+
+        let data = image(32, false, Some([0x00FF00FF, 0x0000FF00, 0xFF000000]));
+        assert_eq!(Bmp::parse(&data).err(), Some(EFI_UNSUPPORTED));
+    }
+
+    #[test]
+    fn malformed() {
+        let valid = image(24, false, None);
+        let patch = |Offset: usize, Bytes: &[UINT8]| {
+            let mut data = valid.clone();
+            data[Offset..Offset + Bytes.len()].copy_from_slice(Bytes);
+            Bmp::parse(&data).err()
+        };
+
+        assert_eq!(patch(0, b"MB"), Some(EFI_INVALID_PARAMETER));
+        assert_eq!(patch(14, &12u32.to_le_bytes()), Some(EFI_INVALID_PARAMETER));
+        assert_eq!(patch(18, &0u32.to_le_bytes()), Some(EFI_INVALID_PARAMETER));
+        assert_eq!(
+            patch(18, &(-3i32).to_le_bytes()),
+            Some(EFI_INVALID_PARAMETER)
+        );
+        assert_eq!(patch(22, &0u32.to_le_bytes()), Some(EFI_INVALID_PARAMETER));
+        assert_eq!(patch(26, &2u16.to_le_bytes()), Some(EFI_INVALID_PARAMETER));
+        assert_eq!(patch(18, &0x4001u32.to_le_bytes()), Some(EFI_UNSUPPORTED));
+        assert_eq!(
+            patch(22, &(-0x4001i32).to_le_bytes()),
+            Some(EFI_UNSUPPORTED)
+        );
+        assert_eq!(patch(28, &16u16.to_le_bytes()), Some(EFI_UNSUPPORTED));
+        assert_eq!(patch(30, &1u32.to_le_bytes()), Some(EFI_UNSUPPORTED));
+        // The pixel data would extend past the end of the image.
+        assert_eq!(patch(10, &55u32.to_le_bytes()), Some(EFI_INVALID_PARAMETER));
+        assert_eq!(patch(18, &5u32.to_le_bytes()), Some(EFI_INVALID_PARAMETER));
+        assert_eq!(patch(22, &3u32.to_le_bytes()), Some(EFI_INVALID_PARAMETER));
+    }
+
+    #[test]
+    fn truncated() {
+        for (bits, masks) in [(24, None), (32, None), (32, Some([0xFF, 0xFF00, 0xFF0000]))] {
+            let data = image(bits, false, masks);
+            for length in 0..data.len() {
+                assert!(Bmp::parse(&data[..length]).is_err(), "{length}");
+            }
+        }
+    }
+
+    #[test]
+    fn mutated() {
+        let mut data = image(32, true, Some([0xFF, 0xFF00, 0xFF0000]));
+        for index in 0..SIZE_OF_BMP_FILE_HEADER + SIZE_OF_BMP_INFO_HEADER + 12 {
+            for value in [0x00, 0x01, 0x7F, 0x80, 0xFF] {
+                let original = core::mem::replace(&mut data[index], value);
+                if let Ok(bmp) = Bmp::parse(&data) {
+                    let mut buffer =
+                        vec![EFI_GRAPHICS_OUTPUT_BLT_PIXEL::default(); bmp.width() * bmp.height()];
+                    assert_eq!(bmp.decode_into(&mut buffer), Ok(()));
+                }
+                data[index] = original;
+            }
+        }
+    }
+}
@@ -0,0 +1,637 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # Off-screen Buffers
+//!
+//! This module provides [`BltBuffer`], an off-screen bitmap in the pixel format used by
+//! [`Blt()`], and [`DoubleBuffer`], which draws into a [`BltBuffer`] and copies the area that
+//! changed to the screen on [`present()`].
+//!
+//! [`BltBuffer`]: crate::graphics::BltBuffer
+//! [`Blt()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::Blt
+//! [`DoubleBuffer`]: crate::graphics::DoubleBuffer
+//! [`present()`]: crate::graphics::DoubleBuffer::present
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::graphics::{copy_areas, Rect, Surface};
+use crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_PIXEL;
+use crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL;
+use crate::types::EFI_STATUS;
+
+/// An off-screen bitmap of `Width` by `Height` [`EFI_GRAPHICS_OUTPUT_BLT_PIXEL`]s stored row by
+/// row in `B`, which may be an array, a mutable slice or a `Vec`.
+///
+/// [`EFI_GRAPHICS_OUTPUT_BLT_PIXEL`]: crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_PIXEL
+pub struct BltBuffer<B> {
+    buffer: B,
+    width: usize,
+    height: usize,
+}
+
+impl<B: AsRef<[EFI_GRAPHICS_OUTPUT_BLT_PIXEL]>> BltBuffer<B> {
+    /// Creates a bitmap of `Width` by `Height` pixels stored in `Buffer`.
+    ///
+    /// Returns `None` if `Buffer` holds fewer than `Width` * `Height` pixels.
+    pub fn new(Buffer: B, Width: usize, Height: usize) -> Option<Self> {
+        match Width.checked_mul(Height) {
+            Some(size) if size <= Buffer.as_ref().len() => Some(Self {
+                buffer: Buffer,
+                width: Width,
+                height: Height,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the pixels of the bitmap, row by row.
+    pub fn pixels(&self) -> &[EFI_GRAPHICS_OUTPUT_BLT_PIXEL] {
+        &self.buffer.as_ref()[..self.width * self.height]
+    }
+
+    /// Returns a reference to the underlying storage.
+    pub fn get_ref(&self) -> &B {
+        &self.buffer
+    }
+
+    /// Returns the underlying storage.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+
+    /// Copies `Area` of the bitmap to (`X`, `Y`) of the screen with [`blt_buffer_to_video()`].
+    ///
+    /// [`blt_buffer_to_video()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::blt_buffer_to_video
+    pub unsafe fn blt_to_video(
+        &self,
+        Protocol: &mut EFI_GRAPHICS_OUTPUT_PROTOCOL,
+        Area: Rect,
+        X: usize,
+        Y: usize,
+    ) -> Result<(), EFI_STATUS> {
+        Protocol.blt_buffer_to_video(
+            self.pixels(),
+            self.width,
+            (Area.X, Area.Y),
+            (X, Y),
+            Area.Width,
+            Area.Height,
+        )
+    }
+}
+
+impl<B: AsRef<[EFI_GRAPHICS_OUTPUT_BLT_PIXEL]> + AsMut<[EFI_GRAPHICS_OUTPUT_BLT_PIXEL]>>
+    BltBuffer<B>
+{
+    /// Returns the pixels of the bitmap mutably, row by row.
+    pub fn pixels_mut(&mut self) -> &mut [EFI_GRAPHICS_OUTPUT_BLT_PIXEL] {
+        let size = self.width * self.height;
+        &mut self.buffer.as_mut()[..size]
+    }
+
+    /// Returns a mutable reference to the underlying storage.
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.buffer
+    }
+
+    /// Copies `Area` of the screen to (`X`, `Y`) of the bitmap with [`blt_video_to_buffer()`].
+    ///
+    /// [`blt_video_to_buffer()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::blt_video_to_buffer
+    pub unsafe fn blt_from_video(
+        &mut self,
+        Protocol: &mut EFI_GRAPHICS_OUTPUT_PROTOCOL,
+        Area: Rect,
+        X: usize,
+        Y: usize,
+    ) -> Result<(), EFI_STATUS> {
+        let width = self.width;
+        Protocol.blt_video_to_buffer(
+            self.pixels_mut(),
+            width,
+            (Area.X, Area.Y),
+            (X, Y),
+            Area.Width,
+            Area.Height,
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BltBuffer<Vec<EFI_GRAPHICS_OUTPUT_BLT_PIXEL>> {
+    /// Allocates a black bitmap of `Width` by `Height` pixels.
+    pub fn with_size(Width: usize, Height: usize) -> Self {
+        Self {
+            buffer: alloc::vec![EFI_GRAPHICS_OUTPUT_BLT_PIXEL::default(); Width * Height],
+            width: Width,
+            height: Height,
+        }
+    }
+}
+
+impl<B: AsRef<[EFI_GRAPHICS_OUTPUT_BLT_PIXEL]> + AsMut<[EFI_GRAPHICS_OUTPUT_BLT_PIXEL]>> Surface
+    for BltBuffer<B>
+{
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn set_pixel(&mut self, X: usize, Y: usize, Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL) {
+        if X < self.width && Y < self.height {
+            let width = self.width;
+            self.pixels_mut()[Y * width + X] = Color;
+        }
+    }
+
+    fn pixel(&self, X: usize, Y: usize) -> Option<EFI_GRAPHICS_OUTPUT_BLT_PIXEL> {
+        if X < self.width && Y < self.height {
+            Some(self.pixels()[Y * self.width + X])
+        } else {
+            None
+        }
+    }
+
+    fn fill_span(
+        &mut self,
+        X: usize,
+        Y: usize,
+        Length: usize,
+        Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL,
+    ) {
+        let span = Rect::new(X, Y, Length, 1).intersect(self.bounds());
+        let start = span.Y * self.width + span.X;
+        self.pixels_mut()[start..start + span.Width].fill(Color);
+    }
+
+    fn blit(
+        &mut self,
+        Source: &[EFI_GRAPHICS_OUTPUT_BLT_PIXEL],
+        SourceWidth: usize,
+        Area: Rect,
+        X: usize,
+        Y: usize,
+    ) {
+        if SourceWidth == 0 {
+            return;
+        }
+
+        // Clip to the source first, then to the destination in its coordinates.
+        let source = Area.intersect(Rect::new(0, 0, SourceWidth, Source.len() / SourceWidth));
+        if source.is_empty() {
+            return;
+        }
+
+        let destination = Rect::new(
+            X.saturating_add(source.X.saturating_sub(Area.X)),
+            Y.saturating_add(source.Y.saturating_sub(Area.Y)),
+            source.Width,
+            source.Height,
+        );
+        let clipped = destination.intersect(self.bounds());
+        let width = self.width;
+        for row in 0..clipped.Height {
+            let from = (source.Y + clipped.Y - destination.Y + row) * SourceWidth
+                + source.X
+                + (clipped.X - destination.X);
+            let to = (clipped.Y + row) * width + clipped.X;
+            self.pixels_mut()[to..to + clipped.Width]
+                .copy_from_slice(&Source[from..from + clipped.Width]);
+        }
+    }
+
+    fn copy_rect(&mut self, Area: Rect, X: usize, Y: usize) {
+        let (source, destination) = match copy_areas(self.bounds(), Area, X, Y) {
+            Some(areas) => areas,
+            None => return,
+        };
+
+        let width = self.width;
+        let mut copy_row = |row: usize| {
+            let from = (source.Y + row) * width + source.X;
+            let to = (destination.Y + row) * width + destination.X;
+            self.pixels_mut().copy_within(from..from + source.Width, to);
+        };
+        if destination.Y <= source.Y {
+            (0..source.Height).for_each(&mut copy_row);
+        } else {
+            (0..source.Height).rev().for_each(&mut copy_row);
+        }
+    }
+}
+
+/// A surface drawn off-screen and copied to the screen on demand, to avoid showing partially
+/// drawn frames.
+///
+/// Drawing through [`Surface`] records the area that changed, and [`present()`] copies that
+/// area to the screen with a single [`Blt()`].
+///
+/// [`Surface`]: crate::graphics::Surface
+/// [`present()`]: crate::graphics::DoubleBuffer::present
+/// [`Blt()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::Blt
+pub struct DoubleBuffer<'a, B> {
+    protocol: &'a mut EFI_GRAPHICS_OUTPUT_PROTOCOL,
+    back: BltBuffer<B>,
+    dirty: Rect,
+}
+
+impl<'a, B: AsRef<[EFI_GRAPHICS_OUTPUT_BLT_PIXEL]> + AsMut<[EFI_GRAPHICS_OUTPUT_BLT_PIXEL]>>
+    DoubleBuffer<'a, B>
+{
+    /// Creates a double buffer for the current mode of `Protocol`, with the back buffer stored
+    /// in `Buffer`.
+    ///
+    /// Returns `None` if `Buffer` cannot hold a whole screen.
+    ///
+    /// # Safety
+    ///
+    /// `Protocol` and its mode information must be valid, and the mode must not be changed
+    /// while the double buffer exists.
+    pub unsafe fn new(Protocol: &'a mut EFI_GRAPHICS_OUTPUT_PROTOCOL, Buffer: B) -> Option<Self> {
+        let info = Protocol.info();
+        let back = BltBuffer::new(
+            Buffer,
+            info.HorizontalResolution as usize,
+            info.VerticalResolution as usize,
+        )?;
+
+        Some(Self {
+            protocol: Protocol,
+            back,
+            dirty: Rect::default(),
+        })
+    }
+
+    /// Returns the back buffer.
+    pub fn back_buffer(&self) -> &BltBuffer<B> {
+        &self.back
+    }
+
+    /// Returns the back buffer mutably, marking all of it as changed.
+    pub fn back_buffer_mut(&mut self) -> &mut BltBuffer<B> {
+        self.dirty = self.back.bounds();
+        &mut self.back
+    }
+
+    /// Returns the area changed since the last [`present()`].
+    ///
+    /// [`present()`]: crate::graphics::DoubleBuffer::present
+    pub fn dirty(&self) -> Rect {
+        self.dirty
+    }
+
+    /// Marks `Area` as changed, so the next [`present()`] copies it to the screen.
+    ///
+    /// [`present()`]: crate::graphics::DoubleBuffer::present
+    pub fn mark_dirty(&mut self, Area: Rect) {
+        self.dirty = self.dirty.union(Area.intersect(self.back.bounds()));
+    }
+
+    /// Copies the area changed since the last call to the screen.
+    pub fn present(&mut self) -> Result<(), EFI_STATUS> {
+        let dirty = self.dirty;
+        if dirty.is_empty() {
+            return Ok(());
+        }
+
+        // SAFETY: `new()` requires the protocol to stay valid, and the buffer covers the screen.
+        unsafe {
+            self.back
+                .blt_to_video(self.protocol, dirty, dirty.X, dirty.Y)?
+        };
+        self.dirty = Rect::default();
+        Ok(())
+    }
+
+    /// Copies the whole back buffer to the screen.
+    pub fn present_all(&mut self) -> Result<(), EFI_STATUS> {
+        self.dirty = self.back.bounds();
+        self.present()
+    }
+
+    /// Returns the back buffer storage.
+    pub fn into_inner(self) -> B {
+        self.back.into_inner()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> DoubleBuffer<'a, Vec<EFI_GRAPHICS_OUTPUT_BLT_PIXEL>> {
+    /// Creates a double buffer for the current mode of `Protocol`, allocating the back buffer.
+    ///
+    /// # Safety
+    ///
+    /// See [`new()`].
+    ///
+    /// [`new()`]: crate::graphics::DoubleBuffer::new
+    pub unsafe fn allocate(Protocol: &'a mut EFI_GRAPHICS_OUTPUT_PROTOCOL) -> Self {
+        let info = Protocol.info();
+        let back = BltBuffer::with_size(
+            info.HorizontalResolution as usize,
+            info.VerticalResolution as usize,
+        );
+
+        Self {
+            protocol: Protocol,
+            back,
+            dirty: Rect::default(),
+        }
+    }
+}
+
+impl<B: AsRef<[EFI_GRAPHICS_OUTPUT_BLT_PIXEL]> + AsMut<[EFI_GRAPHICS_OUTPUT_BLT_PIXEL]>> Surface
+    for DoubleBuffer<'_, B>
+{
+    fn width(&self) -> usize {
+        self.back.width
+    }
+
+    fn height(&self) -> usize {
+        self.back.height
+    }
+
+    fn set_pixel(&mut self, X: usize, Y: usize, Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL) {
+        self.back.set_pixel(X, Y, Color);
+        self.mark_dirty(Rect::new(X, Y, 1, 1));
+    }
+
+    fn pixel(&self, X: usize, Y: usize) -> Option<EFI_GRAPHICS_OUTPUT_BLT_PIXEL> {
+        self.back.pixel(X, Y)
+    }
+
+    fn fill_span(
+        &mut self,
+        X: usize,
+        Y: usize,
+        Length: usize,
+        Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL,
+    ) {
+        self.back.fill_span(X, Y, Length, Color);
+        self.mark_dirty(Rect::new(X, Y, Length, 1));
+    }
+
+    fn fill_rect(&mut self, Area: Rect, Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL) {
+        self.back.fill_rect(Area, Color);
+        self.mark_dirty(Area);
+    }
+
+    fn blit(
+        &mut self,
+        Source: &[EFI_GRAPHICS_OUTPUT_BLT_PIXEL],
+        SourceWidth: usize,
+        Area: Rect,
+        X: usize,
+        Y: usize,
+    ) {
+        self.back.blit(Source, SourceWidth, Area, X, Y);
+        self.mark_dirty(Rect::new(X, Y, Area.Width, Area.Height));
+    }
+
+    fn copy_rect(&mut self, Area: Rect, X: usize, Y: usize) {
+        self.back.copy_rect(Area, X, Y);
+        self.mark_dirty(Rect::new(X, Y, Area.Width, Area.Height));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::console::TextConsole;
+    use crate::graphics::font::{self, FONT_HEIGHT, FONT_WIDTH};
+
+    const BLACK: EFI_GRAPHICS_OUTPUT_BLT_PIXEL = EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0, 0, 0);
+    const RED: EFI_GRAPHICS_OUTPUT_BLT_PIXEL = EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0xFF, 0, 0);
+    const GREEN: EFI_GRAPHICS_OUTPUT_BLT_PIXEL = EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0, 0xFF, 0);
+
+    type Buffer = BltBuffer<[EFI_GRAPHICS_OUTPUT_BLT_PIXEL; 100]>;
+
+    fn black() -> Buffer {
+        BltBuffer::new([BLACK; 100], 10, 10).unwrap()
+    }
+
+    /// A pixel value that encodes its position, to check where pixels were copied from.
+    fn marker(X: usize, Y: usize) -> EFI_GRAPHICS_OUTPUT_BLT_PIXEL {
+        EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(X as u8, Y as u8, 1)
+    }
+
+    fn marked() -> Buffer {
+        let mut buffer = black();
+        for y in 0..10 {
+            for x in 0..10 {
+                buffer.set_pixel(x, y, marker(x, y));
+            }
+        }
+        buffer
+    }
+
+    /// Returns the area of `Surface` holding `Color`, checking that it is a rectangle.
+    fn area_of(Surface: &impl Surface, Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL) -> Rect {
+        let mut area = Rect::default();
+        for y in 0..Surface.height() {
+            for x in 0..Surface.width() {
+                if Surface.pixel(x, y) == Some(Color) {
+                    area = area.union(Rect::new(x, y, 1, 1));
+                }
+            }
+        }
+        for y in area.Y..area.bottom() {
+            for x in area.X..area.right() {
+                assert_eq!(Surface.pixel(x, y), Some(Color));
+            }
+        }
+        area
+    }
+
+    /// A surface with only the required methods, to exercise the provided ones.
+    struct Pixels(Buffer);
+
+    impl Surface for Pixels {
+        fn width(&self) -> usize {
+            self.0.width()
+        }
+
+        fn height(&self) -> usize {
+            self.0.height()
+        }
+
+        fn set_pixel(&mut self, X: usize, Y: usize, Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL) {
+            self.0.set_pixel(X, Y, Color);
+        }
+
+        fn pixel(&self, X: usize, Y: usize) -> Option<EFI_GRAPHICS_OUTPUT_BLT_PIXEL> {
+            self.0.pixel(X, Y)
+        }
+    }
+
+    #[test]
+    fn new_checks_size() {
+        assert!(BltBuffer::new([BLACK; 100], 10, 10).is_some());
+        assert!(BltBuffer::new([BLACK; 99], 10, 10).is_none());
+        assert!(BltBuffer::new([BLACK; 1], usize::MAX, 2).is_none());
+    }
+
+    #[test]
+    fn fill_is_clipped() {
+        let mut buffer = black();
+        buffer.fill_rect(Rect::new(8, 7, 5, 5), RED);
+        assert_eq!(area_of(&buffer, RED), Rect::new(8, 7, 2, 3));
+
+        let mut buffer = black();
+        buffer.fill_rect(Rect::new(20, 20, 5, 5), RED);
+        buffer.fill_span(usize::MAX, 3, 5, RED);
+        buffer.fill_span(3, usize::MAX, 5, RED);
+        buffer.fill_rect(Rect::new(usize::MAX - 1, usize::MAX - 1, 5, 5), RED);
+        assert!(area_of(&buffer, RED).is_empty());
+
+        let mut buffer = black();
+        buffer.fill_span(7, 2, usize::MAX, RED);
+        assert_eq!(area_of(&buffer, RED), Rect::new(7, 2, 3, 1));
+    }
+
+    #[test]
+    fn copy_rect_is_clipped() {
+        let mut buffer = marked();
+        buffer.copy_rect(Rect::new(6, 6, 6, 6), 0, 1);
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(buffer.pixel(x, y + 1), Some(marker(x + 6, y + 6)));
+            }
+        }
+        assert_eq!(buffer.pixel(4, 1), Some(marker(4, 1)));
+        assert_eq!(buffer.pixel(0, 5), Some(marker(0, 5)));
+
+        // Only the part of the area inside the surface moves, keeping its offset.
+        let mut buffer = marked();
+        buffer.copy_rect(Rect::new(8, 8, 4, 4), 0, 0);
+        assert_eq!(buffer.pixel(0, 0), Some(marker(8, 8)));
+        assert_eq!(buffer.pixel(1, 1), Some(marker(9, 9)));
+        assert_eq!(buffer.pixel(2, 2), Some(marker(2, 2)));
+    }
+
+    #[test]
+    fn copy_rect_overlapping() {
+        let mut buffer = marked();
+        buffer.copy_rect(Rect::new(0, 0, 10, 9), 0, 1);
+        for y in 1..10 {
+            assert_eq!(buffer.pixel(3, y), Some(marker(3, y - 1)));
+        }
+
+        let mut buffer = marked();
+        buffer.copy_rect(Rect::new(1, 0, 9, 10), 0, 0);
+        for x in 0..9 {
+            assert_eq!(buffer.pixel(x, 4), Some(marker(x + 1, 4)));
+        }
+    }
+
+    #[test]
+    fn copy_rect_off_surface() {
+        let mut buffer = marked();
+        buffer.copy_rect(Rect::new(20, 20, 5, 5), 0, 0);
+        buffer.copy_rect(Rect::new(0, 10, 10, 0), 0, 0);
+        buffer.copy_rect(Rect::new(0, 0, 5, 5), usize::MAX, usize::MAX);
+        buffer.copy_rect(Rect::new(usize::MAX, usize::MAX, 5, 5), 0, 0);
+        assert_eq!(buffer.pixels(), marked().pixels());
+
+        let mut pixels = Pixels(marked());
+        pixels.copy_rect(Rect::new(20, 20, 5, 5), 0, 0);
+        pixels.copy_rect(Rect::new(0, 0, 5, 5), usize::MAX, 0);
+        assert_eq!(pixels.0.pixels(), marked().pixels());
+    }
+
+    #[test]
+    fn blit_is_clipped() {
+        let source = [RED, GREEN, GREEN, RED];
+
+        let mut buffer = black();
+        buffer.blit(&source, 2, Rect::new(0, 0, 2, 2), 9, 9);
+        assert_eq!(buffer.pixel(9, 9), Some(RED));
+        assert_eq!(area_of(&buffer, GREEN), Rect::default());
+
+        // Only the part of the area inside the source is drawn, keeping its offset.
+        let mut buffer = black();
+        buffer.blit(&source, 2, Rect::new(1, 0, 4, 4), 3, 3);
+        assert_eq!(area_of(&buffer, GREEN), Rect::new(3, 3, 1, 1));
+        assert_eq!(area_of(&buffer, RED), Rect::new(3, 4, 1, 1));
+
+        let mut pixels = Pixels(black());
+        pixels.blit(&source, 2, Rect::new(1, 0, 4, 4), 3, 3);
+        assert_eq!(pixels.0.pixels(), buffer.pixels());
+    }
+
+    #[test]
+    fn blit_off_surface() {
+        let source = [RED; 4];
+        let mut buffer = black();
+        let mut pixels = Pixels(black());
+        for surface in [&mut buffer as &mut dyn Surface, &mut pixels] {
+            surface.blit(&source, 2, Rect::new(5, 5, 2, 2), 0, 0);
+            surface.blit(&source, 0, Rect::new(0, 0, 2, 2), 0, 0);
+            surface.blit(&source, 2, Rect::new(0, 0, 2, 2), 20, 20);
+            surface.blit(&source, 2, Rect::new(0, 0, 2, 2), usize::MAX, usize::MAX);
+            surface.blit(&source, 2, Rect::new(usize::MAX, 0, 2, 2), 0, 0);
+        }
+        assert!(area_of(&buffer, RED).is_empty());
+        assert!(area_of(&pixels, RED).is_empty());
+    }
+
+    #[test]
+    fn draw_char_is_clipped() {
+        let mut buffer = BltBuffer::new([BLACK; 256], 16, 16).unwrap();
+        buffer.draw_char(12, 8, 'A', RED, Some(GREEN));
+        let glyph = font::glyph('A');
+        for y in 0..16 {
+            for x in 0..16 {
+                let expected = if x < 12 || y < 8 {
+                    BLACK
+                } else if glyph[y - 8] & (0x80 >> (x - 12)) != 0 {
+                    RED
+                } else {
+                    GREEN
+                };
+                assert_eq!(buffer.pixel(x, y), Some(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn draw_char_off_surface() {
+        let mut buffer = black();
+        let mut pixels = Pixels(black());
+        for surface in [&mut buffer as &mut dyn Surface, &mut pixels] {
+            surface.draw_char(usize::MAX - 2, 0, 'A', RED, Some(GREEN));
+            surface.draw_char(0, usize::MAX - 2, 'A', RED, Some(GREEN));
+            surface.draw_char(usize::MAX, usize::MAX, 'A', RED, Some(GREEN));
+            surface.draw_text(usize::MAX - FONT_WIDTH, 0, "AB\nC", RED, None);
+            surface.draw_text(0, usize::MAX - FONT_HEIGHT, "A\n\nB", RED, None);
+        }
+        assert_eq!(buffer.pixels(), [BLACK; 100]);
+        assert_eq!(pixels.0.pixels(), [BLACK; 100]);
+    }
+
+    #[test]
+    fn line_feed_on_one_row() {
+        let mut console =
+            TextConsole::new(BltBuffer::new([RED; 256], 2 * FONT_WIDTH, FONT_HEIGHT).unwrap());
+        assert_eq!(console.size(), (2, 1));
+        console.output_string("\n\n");
+        assert_eq!(console.cursor_position(), (0, 0));
+        assert_eq!(console.into_inner().pixels(), [BLACK; 256]);
+    }
+}
@@ -0,0 +1,299 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # Text Console
+//!
+//! This module provides [`TextConsole`], which draws text on a [`Surface`] with the built-in
+//! 8x16 [`font`] in a grid of character cells, like the text mode of a firmware console. It can
+//! be used where no [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`] is available for a display, and as a
+//! [`ConsoleSink`].
+//!
+//! [`TextConsole`]: crate::graphics::console::TextConsole
+//! [`Surface`]: crate::graphics::Surface
+//! [`font`]: crate::graphics::font
+//! [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL
+//! [`ConsoleSink`]: crate::protocols::console::multiplex::ConsoleSink
+
+use crate::graphics::font::{FONT_HEIGHT, FONT_WIDTH};
+use crate::graphics::{Rect, Surface};
+use crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_PIXEL;
+use crate::protocols::console::multiplex::{ConsoleSink, DEFAULT_CONSOLE_ATTRIBUTE};
+use crate::status::EFI_UNSUPPORTED;
+use crate::types::{EFI_STATUS, UINTN};
+
+/// The number of pixel rows at the bottom of a cell covered by the cursor.
+const CURSOR_HEIGHT: usize = 2;
+
+/// The colors of the text attributes, indexed by [`EFI_BLACK`] to [`EFI_WHITE`].
+///
+/// [`EFI_BLACK`]: crate::protocols::console::simple_text_output::EFI_BLACK
+/// [`EFI_WHITE`]: crate::protocols::console::simple_text_output::EFI_WHITE
+pub const TEXT_PALETTE: [EFI_GRAPHICS_OUTPUT_BLT_PIXEL; 16] = [
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0x00, 0x00, 0x00),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0x00, 0x00, 0xAA),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0x00, 0xAA, 0x00),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0x00, 0xAA, 0xAA),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0xAA, 0x00, 0x00),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0xAA, 0x00, 0xAA),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0xAA, 0x55, 0x00),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0xAA, 0xAA, 0xAA),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0x55, 0x55, 0x55),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0x55, 0x55, 0xFF),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0x55, 0xFF, 0x55),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0x55, 0xFF, 0xFF),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0xFF, 0x55, 0x55),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0xFF, 0x55, 0xFF),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0xFF, 0xFF, 0x55),
+    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0xFF, 0xFF, 0xFF),
+];
+
+/// A text console drawn on a [`Surface`].
+///
+/// The console covers as many 8x16 cells as fit on the surface, and scrolls up when a line
+/// feed is written on its last row. Colors are taken from [`TEXT_PALETTE`] by attribute, as
+/// for [`SetAttribute()`]. The cursor is drawn as an underline by inverting the bottom of its
+/// cell.
+///
+/// [`Surface`]: crate::graphics::Surface
+/// [`TEXT_PALETTE`]: crate::graphics::console::TEXT_PALETTE
+/// [`SetAttribute()`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL::SetAttribute
+pub struct TextConsole<S> {
+    surface: S,
+    columns: UINTN,
+    rows: UINTN,
+    column: UINTN,
+    row: UINTN,
+    attribute: UINTN,
+    cursor_visible: bool,
+}
+
+impl<S: Surface> TextConsole<S> {
+    /// Creates a console covering `Surface`, with the cursor at (0, 0) and light gray text on
+    /// black. The surface is not cleared.
+    pub fn new(Surface: S) -> Self {
+        let mut console = Self {
+            columns: Surface.width() / FONT_WIDTH,
+            rows: Surface.height() / FONT_HEIGHT,
+            surface: Surface,
+            column: 0,
+            row: 0,
+            attribute: DEFAULT_CONSOLE_ATTRIBUTE,
+            cursor_visible: true,
+        };
+        console.toggle_cursor();
+        console
+    }
+
+    /// Returns the surface.
+    pub fn surface(&self) -> &S {
+        &self.surface
+    }
+
+    /// Returns the surface mutably. Drawing under the cursor leaves it in an inconsistent
+    /// state; hide it first with [`enable_cursor()`].
+    ///
+    /// [`enable_cursor()`]: crate::graphics::console::TextConsole::enable_cursor
+    pub fn surface_mut(&mut self) -> &mut S {
+        &mut self.surface
+    }
+
+    /// Returns the surface, with the cursor removed.
+    pub fn into_inner(mut self) -> S {
+        self.enable_cursor(false);
+        self.surface
+    }
+
+    /// Returns the number of columns and rows.
+    pub fn size(&self) -> (UINTN, UINTN) {
+        (self.columns, self.rows)
+    }
+
+    /// Returns the column and row of the cursor.
+    pub fn cursor_position(&self) -> (UINTN, UINTN) {
+        (self.column, self.row)
+    }
+
+    /// Returns the attribute of the text.
+    pub fn attribute(&self) -> UINTN {
+        self.attribute
+    }
+
+    /// Sets the attribute of the text written next.
+    pub fn set_attribute(&mut self, Attribute: UINTN) {
+        self.attribute = Attribute & 0x7F;
+    }
+
+    /// Moves the cursor to (`Column`, `Row`).
+    ///
+    /// Fails with [`EFI_UNSUPPORTED`] if the position is outside of the console.
+    ///
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    pub fn set_cursor_position(&mut self, Column: UINTN, Row: UINTN) -> Result<(), EFI_STATUS> {
+        if Column >= self.columns || Row >= self.rows {
+            return Err(EFI_UNSUPPORTED);
+        }
+
+        self.toggle_cursor();
+        self.column = Column;
+        self.row = Row;
+        self.toggle_cursor();
+        Ok(())
+    }
+
+    /// Clears the console with the background color and moves the cursor to (0, 0).
+    pub fn clear_screen(&mut self) {
+        let background = self.background();
+        self.surface.clear(background);
+        self.column = 0;
+        self.row = 0;
+        self.toggle_cursor();
+    }
+
+    /// Shows or hides the cursor.
+    pub fn enable_cursor(&mut self, Visible: bool) {
+        if Visible != self.cursor_visible {
+            self.toggle_cursor();
+            self.cursor_visible = Visible;
+            self.toggle_cursor();
+        }
+    }
+
+    /// Writes `String` at the cursor, wrapping at the end of a row.
+    ///
+    /// A carriage return moves the cursor to the start of the row, a line feed to the next row,
+    /// and a backspace one column back.
+    pub fn output_string(&mut self, String: &str) {
+        self.toggle_cursor();
+        for char in String.chars() {
+            match char {
+                '\r' => self.column = 0,
+                '\n' => self.line_feed(),
+                '\u{8}' => self.column = self.column.saturating_sub(1),
+                _ if self.columns == 0 || self.rows == 0 => {}
+                _ => {
+                    let (foreground, background) = (self.foreground(), self.background());
+                    let (x, y) = self.cell(self.column, self.row);
+                    self.surface
+                        .draw_char(x, y, char, foreground, Some(background));
+                    self.column += 1;
+                    if self.column == self.columns {
+                        self.column = 0;
+                        self.line_feed();
+                    }
+                }
+            }
+        }
+        self.toggle_cursor();
+    }
+
+    fn foreground(&self) -> EFI_GRAPHICS_OUTPUT_BLT_PIXEL {
+        TEXT_PALETTE[self.attribute & 0x0F]
+    }
+
+    fn background(&self) -> EFI_GRAPHICS_OUTPUT_BLT_PIXEL {
+        TEXT_PALETTE[(self.attribute >> 4) & 0x07]
+    }
+
+    fn cell(&self, Column: UINTN, Row: UINTN) -> (usize, usize) {
+        (Column * FONT_WIDTH, Row * FONT_HEIGHT)
+    }
+
+    /// Moves the cursor to the next row, scrolling up on the last one.
+    fn line_feed(&mut self) {
+        if self.rows == 0 {
+            return;
+        } else if self.row + 1 < self.rows {
+            self.row += 1;
+            return;
+        }
+
+        let width = self.columns * FONT_WIDTH;
+        let height = self.rows * FONT_HEIGHT;
+        let background = self.background();
+        self.surface
+            .copy_rect(Rect::new(0, FONT_HEIGHT, width, height - FONT_HEIGHT), 0, 0);
+        self.surface.fill_rect(
+            Rect::new(0, height - FONT_HEIGHT, width, FONT_HEIGHT),
+            background,
+        );
+    }
+
+    /// Draws or removes the cursor, if it is visible, by inverting the bottom of its cell.
+    fn toggle_cursor(&mut self) {
+        if !self.cursor_visible || self.column >= self.columns || self.row >= self.rows {
+            return;
+        }
+
+        let (x, y) = self.cell(self.column, self.row);
+        for y in y + FONT_HEIGHT - CURSOR_HEIGHT..y + FONT_HEIGHT {
+            for x in x..x + FONT_WIDTH {
+                if let Some(pixel) = self.surface.pixel(x, y) {
+                    let inverted =
+                        EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(!pixel.Red, !pixel.Green, !pixel.Blue);
+                    self.surface.set_pixel(x, y, inverted);
+                }
+            }
+        }
+    }
+}
+
+impl<S: Surface> ConsoleSink for TextConsole<S> {
+    fn output_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+        TextConsole::output_string(self, String);
+        Ok(())
+    }
+
+    /// Fails with [`EFI_UNSUPPORTED`] if `String` has characters other than those of the
+    /// built-in font and the control characters.
+    ///
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    fn test_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+        let printable = |char| matches!(char, ' '..='~' | '\r' | '\n' | '\u{8}');
+        if String.chars().all(printable) {
+            Ok(())
+        } else {
+            Err(EFI_UNSUPPORTED)
+        }
+    }
+
+    fn set_attribute(&mut self, Attribute: UINTN) -> Result<(), EFI_STATUS> {
+        TextConsole::set_attribute(self, Attribute);
+        Ok(())
+    }
+
+    fn set_cursor_position(&mut self, Column: UINTN, Row: UINTN) -> Result<(), EFI_STATUS> {
+        TextConsole::set_cursor_position(self, Column, Row)
+    }
+
+    fn clear_screen(&mut self) -> Result<(), EFI_STATUS> {
+        TextConsole::clear_screen(self);
+        Ok(())
+    }
+
+    fn enable_cursor(&mut self, Visible: bool) -> Result<(), EFI_STATUS> {
+        TextConsole::enable_cursor(self, Visible);
+        Ok(())
+    }
+
+    fn size(&mut self) -> Option<(UINTN, UINTN)> {
+        Some(TextConsole::size(self))
+    }
+
+    fn cursor_position(&mut self) -> Option<(UINTN, UINTN)> {
+        Some(TextConsole::cursor_position(self))
+    }
+}
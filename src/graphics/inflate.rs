@@ -0,0 +1,472 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # DEFLATE Decompression
+//!
+//! A small decoder for zlib streams of DEFLATE compressed data, as used by PNG images. See
+//! [RFC 1950] and [RFC 1951] for the formats.
+//!
+//! [RFC 1950]: https://www.rfc-editor.org/rfc/rfc1950
+//! [RFC 1951]: https://www.rfc-editor.org/rfc/rfc1951
+
+use alloc::vec::Vec;
+
+use crate::status::{EFI_CRC_ERROR, EFI_INVALID_PARAMETER, EFI_UNSUPPORTED};
+use crate::types::{EFI_STATUS, UINT16, UINT32, UINT8};
+
+const MAX_BITS: usize = 15;
+const MAX_LITERAL_CODES: usize = 288;
+const MAX_DISTANCE_CODES: usize = 30;
+
+const LENGTH_BASE: [UINT16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [UINT8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [UINT16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [UINT8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Decompresses the zlib stream in `Data`, failing if the output would exceed `Limit` bytes.
+///
+/// Returns [`EFI_INVALID_PARAMETER`] for malformed data, [`EFI_UNSUPPORTED`] for streams using a
+/// preset dictionary, and [`EFI_CRC_ERROR`] if the Adler-32 checksum does not match.
+///
+/// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+/// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+/// [`EFI_CRC_ERROR`]: crate::status::EFI_CRC_ERROR
+pub(crate) fn zlib_decompress(Data: &[UINT8], Limit: usize) -> Result<Vec<UINT8>, EFI_STATUS> {
+    if Data.len() < 6 {
+        return Err(EFI_INVALID_PARAMETER);
+    }
+
+    let (cmf, flg) = (Data[0], Data[1]);
+    if cmf & 0x0F != 8 || cmf >> 4 > 7 || (UINT16::from(cmf) << 8 | UINT16::from(flg)) % 31 != 0 {
+        return Err(EFI_INVALID_PARAMETER);
+    }
+    if flg & 0x20 != 0 {
+        return Err(EFI_UNSUPPORTED);
+    }
+
+    let mut reader = BitReader::new(&Data[2..]);
+    let output = inflate(&mut reader, Limit)?;
+    let trailer = reader.trailer().ok_or(EFI_INVALID_PARAMETER)?;
+    let checksum = UINT32::from_be_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    if checksum != adler32(&output) {
+        return Err(EFI_CRC_ERROR);
+    }
+
+    Ok(output)
+}
+
+/// Computes the Adler-32 checksum of `Data`.
+fn adler32(Data: &[UINT8]) -> UINT32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    // 5552 is the largest number of bytes that cannot overflow the sums before the modulo.
+    for chunk in Data.chunks(5552) {
+        for &byte in chunk {
+            a += UINT32::from(byte);
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+
+    b << 16 | a
+}
+
+/// Decompresses raw DEFLATE blocks until the final one.
+fn inflate(Reader: &mut BitReader<'_>, Limit: usize) -> Result<Vec<UINT8>, EFI_STATUS> {
+    let mut output = Vec::new();
+    loop {
+        let last = Reader.bits(1)? == 1;
+        match Reader.bits(2)? {
+            0 => stored(Reader, &mut output, Limit)?,
+            1 => {
+                let (literals, distances) = fixed_codes();
+                codes(Reader, &mut output, Limit, &literals, &distances)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(Reader)?;
+                codes(Reader, &mut output, Limit, &literals, &distances)?;
+            }
+            _ => return Err(EFI_INVALID_PARAMETER),
+        }
+
+        if last {
+            return Ok(output);
+        }
+    }
+}
+
+fn stored(
+    Reader: &mut BitReader<'_>,
+    Output: &mut Vec<UINT8>,
+    Limit: usize,
+) -> Result<(), EFI_STATUS> {
+    Reader.align();
+    let length = Reader.bits(16)?;
+    let complement = Reader.bits(16)?;
+    if length != !complement & 0xFFFF {
+        return Err(EFI_INVALID_PARAMETER);
+    }
+
+    let bytes = Reader.bytes(length as usize)?;
+    if Output.len() + bytes.len() > Limit {
+        return Err(EFI_INVALID_PARAMETER);
+    }
+    Output.extend_from_slice(bytes);
+    Ok(())
+}
+
+fn codes(
+    Reader: &mut BitReader<'_>,
+    Output: &mut Vec<UINT8>,
+    Limit: usize,
+    Literals: &Huffman,
+    Distances: &Huffman,
+) -> Result<(), EFI_STATUS> {
+    loop {
+        let symbol = Literals.decode(Reader)? as usize;
+        match symbol {
+            0..=255 => {
+                if Output.len() >= Limit {
+                    return Err(EFI_INVALID_PARAMETER);
+                }
+                Output.push(symbol as UINT8);
+            }
+            256 => return Ok(()),
+            _ => {
+                let index = symbol - 257;
+                if index >= LENGTH_BASE.len() {
+                    return Err(EFI_INVALID_PARAMETER);
+                }
+                let length = LENGTH_BASE[index] as usize
+                    + Reader.bits(LENGTH_EXTRA[index] as usize)? as usize;
+
+                let index = Distances.decode(Reader)? as usize;
+                if index >= DISTANCE_BASE.len() {
+                    return Err(EFI_INVALID_PARAMETER);
+                }
+                let distance = DISTANCE_BASE[index] as usize
+                    + Reader.bits(DISTANCE_EXTRA[index] as usize)? as usize;
+                if distance > Output.len() || Output.len() + length > Limit {
+                    return Err(EFI_INVALID_PARAMETER);
+                }
+
+                // The copy may overlap the bytes it produces, so it goes byte by byte.
+                let start = Output.len() - distance;
+                for i in 0..length {
+                    let byte = Output[start + i];
+                    Output.push(byte);
+                }
+            }
+        }
+    }
+}
+
+fn fixed_codes() -> (Huffman, Huffman) {
+    let mut lengths = [0; MAX_LITERAL_CODES];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+
+    // The fixed codes are complete, so building them cannot fail.
+    let literals = Huffman::new(&lengths).unwrap_or_default();
+    let distances = Huffman::new(&[5; MAX_DISTANCE_CODES]).unwrap_or_default();
+    (literals, distances)
+}
+
+fn dynamic_codes(Reader: &mut BitReader<'_>) -> Result<(Huffman, Huffman), EFI_STATUS> {
+    let literal_count = Reader.bits(5)? as usize + 257;
+    let distance_count = Reader.bits(5)? as usize + 1;
+    let length_count = Reader.bits(4)? as usize + 4;
+    if literal_count > 286 || distance_count > MAX_DISTANCE_CODES {
+        return Err(EFI_INVALID_PARAMETER);
+    }
+
+    let mut lengths = [0; 19];
+    for &index in &CODE_LENGTH_ORDER[..length_count] {
+        lengths[index] = Reader.bits(3)? as UINT8;
+    }
+    let length_code = Huffman::new(&lengths)?;
+
+    let mut lengths = [0; MAX_LITERAL_CODES + MAX_DISTANCE_CODES];
+    let total = literal_count + distance_count;
+    let mut index = 0;
+    while index < total {
+        let symbol = length_code.decode(Reader)?;
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as UINT8, 1),
+            16 if index > 0 => (lengths[index - 1], 3 + Reader.bits(2)? as usize),
+            17 => (0, 3 + Reader.bits(3)? as usize),
+            18 => (0, 11 + Reader.bits(7)? as usize),
+            _ => return Err(EFI_INVALID_PARAMETER),
+        };
+        if index + repeat > total {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+        lengths[index..index + repeat].fill(value);
+        index += repeat;
+    }
+
+    // The end of block code must be present.
+    if lengths[256] == 0 {
+        return Err(EFI_INVALID_PARAMETER);
+    }
+
+    let literals = Huffman::new(&lengths[..literal_count])?;
+    let distances = Huffman::new(&lengths[literal_count..total])?;
+    Ok((literals, distances))
+}
+
+/// A canonical Huffman code, stored as the number of codes of each length and the symbols
+/// ordered by code.
+#[derive(Clone)]
+struct Huffman {
+    counts: [UINT16; MAX_BITS + 1],
+    symbols: [UINT16; MAX_LITERAL_CODES],
+}
+
+impl Default for Huffman {
+    fn default() -> Self {
+        Self {
+            counts: [0; MAX_BITS + 1],
+            symbols: [0; MAX_LITERAL_CODES],
+        }
+    }
+}
+
+impl Huffman {
+    /// Builds the code from the code length of each symbol, rejecting over-subscribed codes.
+    fn new(Lengths: &[UINT8]) -> Result<Self, EFI_STATUS> {
+        let mut code = Self::default();
+        for &length in Lengths {
+            code.counts[length as usize] += 1;
+        }
+
+        let mut left: i32 = 1;
+        for &count in &code.counts[1..] {
+            left = (left << 1) - i32::from(count);
+            if left < 0 {
+                return Err(EFI_INVALID_PARAMETER);
+            }
+        }
+
+        let mut offsets = [0; MAX_BITS + 1];
+        for length in 1..MAX_BITS {
+            offsets[length + 1] = offsets[length] + code.counts[length];
+        }
+        for (symbol, &length) in Lengths.iter().enumerate() {
+            if length != 0 {
+                code.symbols[offsets[length as usize] as usize] = symbol as UINT16;
+                offsets[length as usize] += 1;
+            }
+        }
+
+        Ok(code)
+    }
+
+    /// Decodes a symbol, reading the code bit by bit.
+    fn decode(&self, Reader: &mut BitReader<'_>) -> Result<UINT16, EFI_STATUS> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for length in 1..=MAX_BITS {
+            code |= Reader.bits(1)? as i32;
+            let count = i32::from(self.counts[length]);
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        Err(EFI_INVALID_PARAMETER)
+    }
+}
+
+/// Reads bits least significant first, as DEFLATE packs them.
+struct BitReader<'a> {
+    data: &'a [UINT8],
+    position: usize,
+    buffer: UINT32,
+    count: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(Data: &'a [UINT8]) -> Self {
+        Self {
+            data: Data,
+            position: 0,
+            buffer: 0,
+            count: 0,
+        }
+    }
+
+    fn bits(&mut self, Count: usize) -> Result<UINT32, EFI_STATUS> {
+        while self.count < Count {
+            let byte = *self.data.get(self.position).ok_or(EFI_INVALID_PARAMETER)?;
+            self.buffer |= UINT32::from(byte) << self.count;
+            self.position += 1;
+            self.count += 8;
+        }
+
+        let value = self.buffer & ((1u64 << Count) - 1) as UINT32;
+        self.buffer = if Count == 32 { 0 } else { self.buffer >> Count };
+        self.count -= Count;
+        Ok(value)
+    }
+
+    /// Discards the bits up to the next byte boundary.
+    fn align(&mut self) {
+        self.buffer = 0;
+        self.count = 0;
+    }
+
+    fn bytes(&mut self, Count: usize) -> Result<&'a [UINT8], EFI_STATUS> {
+        let end = self
+            .position
+            .checked_add(Count)
+            .ok_or(EFI_INVALID_PARAMETER)?;
+        let bytes = self
+            .data
+            .get(self.position..end)
+            .ok_or(EFI_INVALID_PARAMETER)?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    /// Returns the four bytes following the compressed data.
+    fn trailer(&mut self) -> Option<&'a [UINT8]> {
+        self.align();
+        self.data.get(self.position..self.position + 4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use super::*;
+
+    const ZLIB_STORED: &[UINT8] = &[
+        0x78, 0x01, 0x01, 0x14, 0x00, 0xEB, 0xFF, 0x68, 0x65, 0x6C, 0x6C, 0x6F, 0x2C, 0x20, 0x68,
+        0x65, 0x6C, 0x6C, 0x6F, 0x2C, 0x20, 0x68, 0x65, 0x6C, 0x6C, 0x6F, 0x21, 0x4B, 0x1E, 0x06,
+        0xF6,
+    ];
+
+    const ZLIB_FIXED: &[UINT8] = &[
+        0x78, 0xDA, 0xCB, 0x48, 0xCD, 0xC9, 0xC9, 0xD7, 0x51, 0xC8, 0x40, 0xA2, 0x14, 0x01, 0x4B,
+        0x1E, 0x06, 0xF6,
+    ];
+
+    const ZLIB_DYNAMIC: &[UINT8] = &[
+        0x78, 0xDA, 0x85, 0xD0, 0xCB, 0x09, 0x80, 0x30, 0x14, 0x44, 0xD1, 0x56, 0xA6, 0x00, 0x17,
+        0xFE, 0x9D, 0x94, 0x63, 0xE0, 0x89, 0x8B, 0x60, 0x40, 0x03, 0xB6, 0x6F, 0x07, 0xDE, 0xF5,
+        0xD9, 0x9D, 0x94, 0x94, 0x6B, 0x6B, 0x25, 0x1E, 0xD5, 0x43, 0x39, 0xE2, 0x56, 0xBD, 0xD4,
+        0xCE, 0xD0, 0xBB, 0x97, 0xD2, 0x29, 0x19, 0x7C, 0x03, 0x5F, 0xC1, 0x17, 0xF0, 0x19, 0x7C,
+        0x02, 0x1F, 0xC1, 0x07, 0xF0, 0xFE, 0xDF, 0x0D, 0x7F, 0x86, 0x3F, 0xC3, 0x9F, 0xE1, 0xCF,
+        0xF0, 0x67, 0xF8, 0x33, 0xFC, 0x19, 0xFE, 0x0C, 0x7F, 0x86, 0xBF, 0x0F, 0x8B, 0x76, 0xD6,
+        0x4D,
+    ];
+
+    fn bottles() -> Vec<UINT8> {
+        let text: String = (80..100)
+            .rev()
+            .map(|n| std::format!("{n} bottles of beer on the wall, "))
+            .collect();
+        text.into_bytes()
+    }
+
+    #[test]
+    fn blocks() {
+        let hello = b"hello, hello, hello!";
+        assert_eq!(zlib_decompress(ZLIB_STORED, 100).unwrap(), hello);
+        assert_eq!(zlib_decompress(ZLIB_FIXED, 100).unwrap(), hello);
+        assert_eq!(zlib_decompress(ZLIB_DYNAMIC, 1000).unwrap(), bottles());
+    }
+
+    #[test]
+    fn limit() {
+        assert_eq!(zlib_decompress(ZLIB_STORED, 20).unwrap().len(), 20);
+        assert_eq!(zlib_decompress(ZLIB_STORED, 19), Err(EFI_INVALID_PARAMETER));
+        assert_eq!(zlib_decompress(ZLIB_FIXED, 19), Err(EFI_INVALID_PARAMETER));
+        let length = bottles().len();
+        assert_eq!(zlib_decompress(ZLIB_DYNAMIC, length).unwrap().len(), length);
+        assert_eq!(
+            zlib_decompress(ZLIB_DYNAMIC, length - 1),
+            Err(EFI_INVALID_PARAMETER)
+        );
+    }
+
+    #[test]
+    fn header_and_checksum() {
+        let mut data = ZLIB_FIXED.to_vec();
+        *data.last_mut().unwrap() ^= 1;
+        assert_eq!(zlib_decompress(&data, 100), Err(EFI_CRC_ERROR));
+
+        // A valid header check with a preset dictionary.
+        data[..2].copy_from_slice(&[0x78, 0xBB]);
+        assert_eq!(zlib_decompress(&data, 100), Err(EFI_UNSUPPORTED));
+
+        data[..2].copy_from_slice(&[0x78, 0xDB]);
+        assert_eq!(zlib_decompress(&data, 100), Err(EFI_INVALID_PARAMETER));
+        data[..2].copy_from_slice(&[0x79, 0xDA]);
+        assert_eq!(zlib_decompress(&data, 100), Err(EFI_INVALID_PARAMETER));
+    }
+
+    #[test]
+    fn truncated() {
+        for stream in [ZLIB_STORED, ZLIB_FIXED, ZLIB_DYNAMIC] {
+            for length in 0..stream.len() {
+                assert!(zlib_decompress(&stream[..length], 1000).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn mutated() {
+        for stream in [ZLIB_STORED, ZLIB_FIXED, ZLIB_DYNAMIC] {
+            let expected = zlib_decompress(stream, 1000).unwrap();
+            let mut data = stream.to_vec();
+            for index in 0..data.len() {
+                for mask in [0x01, 0x10, 0x80, 0xFF] {
+                    data[index] ^= mask;
+                    // Any change to the output is caught by the checksum.
+                    if let Ok(output) = zlib_decompress(&data, 1000) {
+                        assert_eq!(output, expected);
+                    }
+                    data[index] ^= mask;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,420 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # Software Drawing
+//!
+//! This module provides drawing primitives that render in software, on top of the
+//! [`EFI_GRAPHICS_OUTPUT_PROTOCOL`]: filling rectangles, blitting bitmaps and drawing text with
+//! the built-in 8x16 [`font`].
+//!
+//! Drawing goes through the [`Surface`] trait, which is implemented by the live [`FrameBuffer`]
+//! of a mode, by an off-screen [`BltBuffer`] of [`EFI_GRAPHICS_OUTPUT_BLT_PIXEL`]s, and by a
+//! [`DoubleBuffer`] that draws off-screen and copies the changed area to the screen with
+//! [`Blt()`]. As a [`BltBuffer`] only needs memory, the same drawing code runs on the host.
+//!
+//! Images to draw can be decoded from BMP files with [`bmp`], and from PNG files with `png` when
+//! the `png` feature is enabled. Text can be written to a surface as to a firmware console with
+//! a [`TextConsole`].
+//!
+//! [`EFI_GRAPHICS_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL
+//! [`font`]: crate::graphics::font
+//! [`Surface`]: crate::graphics::Surface
+//! [`FrameBuffer`]: crate::protocols::console::graphics_output::FrameBuffer
+//! [`BltBuffer`]: crate::graphics::BltBuffer
+//! [`EFI_GRAPHICS_OUTPUT_BLT_PIXEL`]: crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_PIXEL
+//! [`DoubleBuffer`]: crate::graphics::DoubleBuffer
+//! [`Blt()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::Blt
+//! [`bmp`]: crate::graphics::bmp
+//! [`TextConsole`]: crate::graphics::console::TextConsole
+
+use crate::protocols::console::graphics_output::{FrameBuffer, EFI_GRAPHICS_OUTPUT_BLT_PIXEL};
+
+pub mod bmp;
+pub mod buffer;
+pub mod console;
+pub mod font;
+#[cfg(feature = "png")]
+mod inflate;
+#[cfg(feature = "png")]
+pub mod png;
+
+pub use buffer::{BltBuffer, DoubleBuffer};
+
+/// A rectangle of pixels.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Rect {
+    /// The column of the left edge.
+    pub X: usize,
+    /// The row of the top edge.
+    pub Y: usize,
+    /// The width in pixels.
+    pub Width: usize,
+    /// The height in pixels.
+    pub Height: usize,
+}
+
+impl Rect {
+    /// Creates a rectangle of `Width` by `Height` pixels at (`X`, `Y`).
+    pub const fn new(X: usize, Y: usize, Width: usize, Height: usize) -> Self {
+        Self {
+            X,
+            Y,
+            Width,
+            Height,
+        }
+    }
+
+    /// Returns the column just past the right edge.
+    pub fn right(&self) -> usize {
+        self.X.saturating_add(self.Width)
+    }
+
+    /// Returns the row just past the bottom edge.
+    pub fn bottom(&self) -> usize {
+        self.Y.saturating_add(self.Height)
+    }
+
+    /// Returns whether the rectangle has no pixels.
+    pub fn is_empty(&self) -> bool {
+        self.Width == 0 || self.Height == 0
+    }
+
+    /// Returns whether the pixel at (`X`, `Y`) is in the rectangle.
+    pub fn contains(&self, X: usize, Y: usize) -> bool {
+        X >= self.X && X < self.right() && Y >= self.Y && Y < self.bottom()
+    }
+
+    /// Returns the pixels in both rectangles, which is empty if they do not overlap.
+    pub fn intersect(&self, Other: Rect) -> Rect {
+        let x = self.X.max(Other.X);
+        let y = self.Y.max(Other.Y);
+        let right = self.right().min(Other.right());
+        let bottom = self.bottom().min(Other.bottom());
+        if right <= x || bottom <= y {
+            return Rect::default();
+        }
+
+        Rect::new(x, y, right - x, bottom - y)
+    }
+
+    /// Returns the smallest rectangle containing both rectangles. Empty rectangles are ignored.
+    pub fn union(&self, Other: Rect) -> Rect {
+        if self.is_empty() {
+            return Other;
+        }
+        if Other.is_empty() {
+            return *self;
+        }
+
+        let x = self.X.min(Other.X);
+        let y = self.Y.min(Other.Y);
+        Rect::new(
+            x,
+            y,
+            self.right().max(Other.right()) - x,
+            self.bottom().max(Other.bottom()) - y,
+        )
+    }
+}
+
+/// A two-dimensional area of pixels that can be drawn on.
+///
+/// Only [`width()`], [`height()`], [`set_pixel()`] and [`pixel()`] need to be implemented; the
+/// drawing operations are built on them, and may be overridden where a surface can do better.
+/// Drawing is clipped to the surface, so anything drawn partly or entirely outside of it is not
+/// an error.
+///
+/// [`width()`]: crate::graphics::Surface::width
+/// [`height()`]: crate::graphics::Surface::height
+/// [`set_pixel()`]: crate::graphics::Surface::set_pixel
+/// [`pixel()`]: crate::graphics::Surface::pixel
+pub trait Surface {
+    /// Returns the width in pixels.
+    fn width(&self) -> usize;
+
+    /// Returns the height in pixels.
+    fn height(&self) -> usize;
+
+    /// Sets the pixel at (`X`, `Y`) to `Color`, if it is on the surface.
+    fn set_pixel(&mut self, X: usize, Y: usize, Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL);
+
+    /// Returns the pixel at (`X`, `Y`), or `None` if it is not on the surface.
+    fn pixel(&self, X: usize, Y: usize) -> Option<EFI_GRAPHICS_OUTPUT_BLT_PIXEL>;
+
+    /// Returns the rectangle covering the whole surface.
+    fn bounds(&self) -> Rect {
+        Rect::new(0, 0, self.width(), self.height())
+    }
+
+    /// Sets `Length` pixels of the row `Y`, starting at column `X`, to `Color`.
+    fn fill_span(
+        &mut self,
+        X: usize,
+        Y: usize,
+        Length: usize,
+        Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL,
+    ) {
+        let span = Rect::new(X, Y, Length, 1).intersect(self.bounds());
+        for x in span.X..span.right() {
+            self.set_pixel(x, span.Y, Color);
+        }
+    }
+
+    /// Fills `Area` with `Color`.
+    fn fill_rect(&mut self, Area: Rect, Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL) {
+        let area = Area.intersect(self.bounds());
+        for y in area.Y..area.bottom() {
+            self.fill_span(area.X, y, area.Width, Color);
+        }
+    }
+
+    /// Fills the whole surface with `Color`.
+    fn clear(&mut self, Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL) {
+        self.fill_rect(self.bounds(), Color);
+    }
+
+    /// Draws the one pixel wide outline of `Area` in `Color`.
+    fn draw_rect(&mut self, Area: Rect, Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL) {
+        if Area.is_empty() {
+            return;
+        }
+
+        self.fill_span(Area.X, Area.Y, Area.Width, Color);
+        self.fill_span(Area.X, Area.bottom() - 1, Area.Width, Color);
+        for y in Area.Y..Area.bottom() {
+            self.set_pixel(Area.X, y, Color);
+            self.set_pixel(Area.right() - 1, y, Color);
+        }
+    }
+
+    /// Copies `Area` of the bitmap `Source`, which holds rows of `SourceWidth` pixels, to
+    /// (`X`, `Y`).
+    ///
+    /// The parts of `Area` outside of `Source` are not copied.
+    fn blit(
+        &mut self,
+        Source: &[EFI_GRAPHICS_OUTPUT_BLT_PIXEL],
+        SourceWidth: usize,
+        Area: Rect,
+        X: usize,
+        Y: usize,
+    ) {
+        if SourceWidth == 0 {
+            return;
+        }
+
+        let source = Rect::new(0, 0, SourceWidth, Source.len() / SourceWidth);
+        let area = Area.intersect(source);
+        if area.is_empty() {
+            return;
+        }
+
+        let x = X.saturating_add(area.X.saturating_sub(Area.X));
+        let y = Y.saturating_add(area.Y.saturating_sub(Area.Y));
+        for row in 0..area.Height {
+            let start = (area.Y + row) * SourceWidth + area.X;
+            for (column, &pixel) in Source[start..start + area.Width].iter().enumerate() {
+                if let (Some(x), Some(y)) = (x.checked_add(column), y.checked_add(row)) {
+                    self.set_pixel(x, y, pixel);
+                }
+            }
+        }
+    }
+
+    /// Copies `Area` of the surface to (`X`, `Y`). The source and the destination may overlap.
+    ///
+    /// The parts of `Area` outside of the surface are not copied.
+    fn copy_rect(&mut self, Area: Rect, X: usize, Y: usize) {
+        let (source, destination) = match copy_areas(self.bounds(), Area, X, Y) {
+            Some(areas) => areas,
+            None => return,
+        };
+
+        // Copy in the direction that reads each pixel before it is overwritten.
+        let copy_row = |surface: &mut Self, row: usize| {
+            let mut copy = |column: usize| {
+                if let Some(pixel) = surface.pixel(source.X + column, source.Y + row) {
+                    surface.set_pixel(destination.X + column, destination.Y + row, pixel);
+                }
+            };
+            if destination.X <= source.X {
+                (0..source.Width).for_each(&mut copy);
+            } else {
+                (0..source.Width).rev().for_each(&mut copy);
+            }
+        };
+        if destination.Y <= source.Y {
+            (0..source.Height).for_each(|row| copy_row(self, row));
+        } else {
+            (0..source.Height).rev().for_each(|row| copy_row(self, row));
+        }
+    }
+
+    /// Draws `Char` with its top left corner at (`X`, `Y`) in `Foreground`, filling the rest of
+    /// its cell with `Background` unless it is `None`.
+    fn draw_char(
+        &mut self,
+        X: usize,
+        Y: usize,
+        Char: char,
+        Foreground: EFI_GRAPHICS_OUTPUT_BLT_PIXEL,
+        Background: Option<EFI_GRAPHICS_OUTPUT_BLT_PIXEL>,
+    ) {
+        for (row, bits) in font::glyph(Char).iter().enumerate() {
+            let y = match Y.checked_add(row) {
+                Some(y) => y,
+                None => break,
+            };
+            for column in 0..font::FONT_WIDTH {
+                let x = match X.checked_add(column) {
+                    Some(x) => x,
+                    None => break,
+                };
+                if bits & (0x80 >> column) != 0 {
+                    self.set_pixel(x, y, Foreground);
+                } else if let Some(background) = Background {
+                    self.set_pixel(x, y, background);
+                }
+            }
+        }
+    }
+
+    /// Draws `Text` starting with its top left corner at (`X`, `Y`), as [`draw_char()`] does for
+    /// each character.
+    ///
+    /// A line feed moves to the start of the next line, and a carriage return to the start of
+    /// the current line. Returns the position following the last character.
+    ///
+    /// [`draw_char()`]: crate::graphics::Surface::draw_char
+    fn draw_text(
+        &mut self,
+        X: usize,
+        Y: usize,
+        Text: &str,
+        Foreground: EFI_GRAPHICS_OUTPUT_BLT_PIXEL,
+        Background: Option<EFI_GRAPHICS_OUTPUT_BLT_PIXEL>,
+    ) -> (usize, usize) {
+        let (mut x, mut y) = (X, Y);
+        for char in Text.chars() {
+            match char {
+                '\n' => {
+                    x = X;
+                    y = y.saturating_add(font::FONT_HEIGHT);
+                }
+                '\r' => x = X,
+                _ => {
+                    self.draw_char(x, y, char, Foreground, Background);
+                    x = x.saturating_add(font::FONT_WIDTH);
+                }
+            }
+        }
+
+        (x, y)
+    }
+}
+
+impl<S: Surface + ?Sized> Surface for &mut S {
+    fn width(&self) -> usize {
+        (**self).width()
+    }
+
+    fn height(&self) -> usize {
+        (**self).height()
+    }
+
+    fn set_pixel(&mut self, X: usize, Y: usize, Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL) {
+        (**self).set_pixel(X, Y, Color)
+    }
+
+    fn pixel(&self, X: usize, Y: usize) -> Option<EFI_GRAPHICS_OUTPUT_BLT_PIXEL> {
+        (**self).pixel(X, Y)
+    }
+
+    fn fill_span(
+        &mut self,
+        X: usize,
+        Y: usize,
+        Length: usize,
+        Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL,
+    ) {
+        (**self).fill_span(X, Y, Length, Color)
+    }
+
+    fn copy_rect(&mut self, Area: Rect, X: usize, Y: usize) {
+        (**self).copy_rect(Area, X, Y)
+    }
+}
+
+/// Clips a copy of `Area` of a surface with `Bounds` to (`X`, `Y`), returning the source and the
+/// destination of the same size, or `None` if nothing is copied.
+pub(crate) fn copy_areas(Bounds: Rect, Area: Rect, X: usize, Y: usize) -> Option<(Rect, Rect)> {
+    let source = Area.intersect(Bounds);
+    if source.is_empty() {
+        return None;
+    }
+
+    let destination = Rect::new(
+        X.saturating_add(source.X.saturating_sub(Area.X)),
+        Y.saturating_add(source.Y.saturating_sub(Area.Y)),
+        source.Width,
+        source.Height,
+    );
+    let clipped = destination.intersect(Bounds);
+    if clipped.is_empty() {
+        return None;
+    }
+
+    let source = Rect::new(
+        source.X + (clipped.X - destination.X),
+        source.Y + (clipped.Y - destination.Y),
+        clipped.Width,
+        clipped.Height,
+    );
+    Some((source, clipped))
+}
+
+impl Surface for FrameBuffer<'_> {
+    fn width(&self) -> usize {
+        FrameBuffer::width(self)
+    }
+
+    fn height(&self) -> usize {
+        FrameBuffer::height(self)
+    }
+
+    fn set_pixel(&mut self, X: usize, Y: usize, Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL) {
+        let _ = self.write_pixel(X, Y, Color);
+    }
+
+    fn pixel(&self, X: usize, Y: usize) -> Option<EFI_GRAPHICS_OUTPUT_BLT_PIXEL> {
+        self.read_pixel(X, Y).ok()
+    }
+
+    fn fill_span(
+        &mut self,
+        X: usize,
+        Y: usize,
+        Length: usize,
+        Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL,
+    ) {
+        let span = Rect::new(X, Y, Length, 1).intersect(self.bounds());
+        let value = self.encode(Color);
+        for x in span.X..span.right() {
+            let _ = self.write_raw(x, span.Y, value);
+        }
+    }
+}
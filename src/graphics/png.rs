@@ -0,0 +1,581 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # PNG Decoder
+//!
+//! This module decodes Portable Network Graphics (PNG) images into
+//! [`EFI_GRAPHICS_OUTPUT_BLT_PIXEL`]s, ready to be drawn with [`Blt()`] or onto a [`Surface`].
+//! It is only available with the `png` feature.
+//!
+//! All color types and bit depths of non-interlaced images are supported. The alpha channel and
+//! transparency information, if any, are ignored. The CRC of every chunk and the checksum of the
+//! compressed data are verified, and every length is checked against the image data, so decoding
+//! untrusted data fails with an error rather than reading out of bounds. See the
+//! [PNG Specification] for the format.
+//!
+//! [`EFI_GRAPHICS_OUTPUT_BLT_PIXEL`]: crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_PIXEL
+//! [`Blt()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::Blt
+//! [`Surface`]: crate::graphics::Surface
+//!
+//! [PNG Specification]: https://www.w3.org/TR/png/
+
+use alloc::vec::Vec;
+
+use crate::graphics::inflate::zlib_decompress;
+use crate::graphics::BltBuffer;
+use crate::partition::crc32_update;
+use crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_PIXEL;
+use crate::status::{EFI_BUFFER_TOO_SMALL, EFI_CRC_ERROR, EFI_INVALID_PARAMETER, EFI_UNSUPPORTED};
+use crate::types::{EFI_STATUS, UINT32, UINT8};
+
+/// The signature at the start of a PNG file.
+pub const PNG_SIGNATURE: [UINT8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Each pixel is a grayscale sample.
+pub const PNG_COLOR_TYPE_GRAYSCALE: UINT8 = 0;
+/// Each pixel is a red, green and blue triple.
+pub const PNG_COLOR_TYPE_TRUECOLOR: UINT8 = 2;
+/// Each pixel is an index into the palette.
+pub const PNG_COLOR_TYPE_INDEXED: UINT8 = 3;
+/// Each pixel is a grayscale sample followed by an alpha sample.
+pub const PNG_COLOR_TYPE_GRAYSCALE_ALPHA: UINT8 = 4;
+/// Each pixel is a red, green and blue triple followed by an alpha sample.
+pub const PNG_COLOR_TYPE_TRUECOLOR_ALPHA: UINT8 = 6;
+
+/// The largest width or height accepted, so the pixel count of an image always fits in memory.
+pub const PNG_MAX_DIMENSION: UINT32 = 0x4000;
+
+/// A parsed PNG image borrowing its data.
+#[derive(Clone, Copy)]
+pub struct Png<'a> {
+    data: &'a [UINT8],
+    width: usize,
+    height: usize,
+    bit_depth: UINT8,
+    color_type: UINT8,
+    palette: &'a [UINT8],
+}
+
+impl<'a> Png<'a> {
+    /// Parses the PNG image in `Data`, checking the structure and CRC of all of its chunks.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if the image is malformed or truncated,
+    /// [`EFI_CRC_ERROR`] if a chunk is corrupted, and [`EFI_UNSUPPORTED`] if it is valid but
+    /// interlaced or larger than [`PNG_MAX_DIMENSION`].
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_CRC_ERROR`]: crate::status::EFI_CRC_ERROR
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    /// [`PNG_MAX_DIMENSION`]: crate::graphics::png::PNG_MAX_DIMENSION
+    pub fn parse(Data: &'a [UINT8]) -> Result<Self, EFI_STATUS> {
+        if !Data.starts_with(&PNG_SIGNATURE) {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        let data = &Data[PNG_SIGNATURE.len()..];
+        let mut chunks = Chunks { data };
+        let header = match chunks.next() {
+            Some(Ok((b"IHDR", header))) if header.len() == 13 => header,
+            Some(Err(status)) => return Err(status),
+            _ => return Err(EFI_INVALID_PARAMETER),
+        };
+
+        let width = read_u32(header, 0);
+        let height = read_u32(header, 4);
+        let bit_depth = header[8];
+        let color_type = header[9];
+        if width == 0 || height == 0 || header[10] != 0 || header[11] != 0 || header[12] > 1 {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+        let valid = match color_type {
+            PNG_COLOR_TYPE_GRAYSCALE => matches!(bit_depth, 1 | 2 | 4 | 8 | 16),
+            PNG_COLOR_TYPE_INDEXED => matches!(bit_depth, 1 | 2 | 4 | 8),
+            PNG_COLOR_TYPE_TRUECOLOR
+            | PNG_COLOR_TYPE_GRAYSCALE_ALPHA
+            | PNG_COLOR_TYPE_TRUECOLOR_ALPHA => matches!(bit_depth, 8 | 16),
+            _ => false,
+        };
+        if !valid {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+        if header[12] != 0 || width > PNG_MAX_DIMENSION || height > PNG_MAX_DIMENSION {
+            return Err(EFI_UNSUPPORTED);
+        }
+
+        let mut palette: &[UINT8] = &[];
+        let mut has_data = false;
+        let mut ended = false;
+        for chunk in chunks {
+            match chunk? {
+                (b"IEND", _) => {
+                    ended = true;
+                    break;
+                }
+                (b"PLTE", entries) if entries.len() % 3 == 0 && entries.len() <= 256 * 3 => {
+                    palette = entries
+                }
+                (b"PLTE", _) => return Err(EFI_INVALID_PARAMETER),
+                (b"IDAT", _) => has_data = true,
+                // Unknown critical chunks cannot be ignored.
+                (name, _) if name[0] & 0x20 == 0 => return Err(EFI_UNSUPPORTED),
+                _ => {}
+            }
+        }
+        if !ended || !has_data || (color_type == PNG_COLOR_TYPE_INDEXED && palette.is_empty()) {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        Ok(Self {
+            data,
+            width: width as usize,
+            height: height as usize,
+            bit_depth,
+            color_type,
+            palette,
+        })
+    }
+
+    /// Returns the width in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the number of bits per sample.
+    pub fn bit_depth(&self) -> UINT8 {
+        self.bit_depth
+    }
+
+    /// Returns the color type, one of the `PNG_COLOR_TYPE_*` constants.
+    pub fn color_type(&self) -> UINT8 {
+        self.color_type
+    }
+
+    /// Decodes the image into `Buffer`, row by row from the top.
+    ///
+    /// Returns [`EFI_BUFFER_TOO_SMALL`] if `Buffer` holds fewer than [`width()`] *
+    /// [`height()`] pixels, [`EFI_INVALID_PARAMETER`] if the image data is malformed, and
+    /// [`EFI_CRC_ERROR`] if its checksum does not match.
+    ///
+    /// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+    /// [`width()`]: crate::graphics::png::Png::width
+    /// [`height()`]: crate::graphics::png::Png::height
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_CRC_ERROR`]: crate::status::EFI_CRC_ERROR
+    pub fn decode_into(
+        &self,
+        Buffer: &mut [EFI_GRAPHICS_OUTPUT_BLT_PIXEL],
+    ) -> Result<(), EFI_STATUS> {
+        if Buffer.len() < self.width * self.height {
+            return Err(EFI_BUFFER_TOO_SMALL);
+        }
+
+        let mut compressed = Vec::new();
+        for chunk in (Chunks { data: self.data }) {
+            match chunk? {
+                (b"IDAT", data) => compressed.extend_from_slice(data),
+                (b"IEND", _) => break,
+                _ => {}
+            }
+        }
+
+        // Every row is preceded by its filter type.
+        let row_size = self.row_size();
+        let mut data = zlib_decompress(&compressed, self.height * (row_size + 1))?;
+        if data.len() != self.height * (row_size + 1) {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        let filter_size = (self.channels() * usize::from(self.bit_depth) / 8).max(1);
+        let mut previous: &[UINT8] = &[];
+        for (y, row) in data.chunks_exact_mut(row_size + 1).enumerate() {
+            let (filter, row) = row.split_at_mut(1);
+            unfilter(filter[0], row, previous, filter_size)?;
+            let target = &mut Buffer[y * self.width..(y + 1) * self.width];
+            self.convert_row(row, target)?;
+            previous = row;
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the image into a newly allocated bitmap. Errors are those of [`decode_into()`].
+    ///
+    /// [`decode_into()`]: crate::graphics::png::Png::decode_into
+    pub fn decode(&self) -> Result<BltBuffer<Vec<EFI_GRAPHICS_OUTPUT_BLT_PIXEL>>, EFI_STATUS> {
+        let mut buffer = BltBuffer::with_size(self.width, self.height);
+        self.decode_into(buffer.pixels_mut())?;
+        Ok(buffer)
+    }
+
+    fn channels(&self) -> usize {
+        match self.color_type {
+            PNG_COLOR_TYPE_TRUECOLOR => 3,
+            PNG_COLOR_TYPE_GRAYSCALE_ALPHA => 2,
+            PNG_COLOR_TYPE_TRUECOLOR_ALPHA => 4,
+            _ => 1,
+        }
+    }
+
+    /// Returns the size of a row of pixels in bytes, without its filter type.
+    fn row_size(&self) -> usize {
+        (self.width * self.channels() * usize::from(self.bit_depth) + 7) / 8
+    }
+
+    fn convert_row(
+        &self,
+        Row: &[UINT8],
+        Target: &mut [EFI_GRAPHICS_OUTPUT_BLT_PIXEL],
+    ) -> Result<(), EFI_STATUS> {
+        let channels = self.channels();
+        for (x, pixel) in Target.iter_mut().enumerate() {
+            let sample = |channel: usize| sample(Row, x * channels + channel, self.bit_depth);
+            *pixel = match self.color_type {
+                PNG_COLOR_TYPE_GRAYSCALE | PNG_COLOR_TYPE_GRAYSCALE_ALPHA => {
+                    let gray = scale(sample(0), self.bit_depth);
+                    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(gray, gray, gray)
+                }
+                PNG_COLOR_TYPE_INDEXED => {
+                    let index = usize::from(sample(0)) * 3;
+                    let entry = self
+                        .palette
+                        .get(index..index + 3)
+                        .ok_or(EFI_INVALID_PARAMETER)?;
+                    EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(entry[0], entry[1], entry[2])
+                }
+                _ => EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(
+                    scale(sample(0), self.bit_depth),
+                    scale(sample(1), self.bit_depth),
+                    scale(sample(2), self.bit_depth),
+                ),
+            };
+        }
+
+        Ok(())
+    }
+}
+
+/// Iterator over the chunks of a PNG image, checking their CRC.
+struct Chunks<'a> {
+    data: &'a [UINT8],
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = Result<(&'a [UINT8; 4], &'a [UINT8]), EFI_STATUS>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let chunk = (|| {
+            if self.data.len() < 12 {
+                return Err(EFI_INVALID_PARAMETER);
+            }
+            let length = read_u32(self.data, 0) as usize;
+            let end = length.checked_add(12).filter(|&end| end <= self.data.len());
+            let end = end.ok_or(EFI_INVALID_PARAMETER)?;
+
+            let (chunk, rest) = self.data.split_at(end);
+            if crc32_update(0, &chunk[4..end - 4]) != read_u32(chunk, end - 4) {
+                return Err(EFI_CRC_ERROR);
+            }
+            self.data = rest;
+
+            let name = <&[UINT8; 4]>::try_from(&chunk[4..8]).map_err(|_| EFI_INVALID_PARAMETER)?;
+            Ok((name, &chunk[8..end - 4]))
+        })();
+
+        // Nothing more can be read after a malformed chunk.
+        if chunk.is_err() {
+            self.data = &[];
+        }
+        Some(chunk)
+    }
+}
+
+/// Reverses the filter of type `Filter` applied to `Row`, given the previous unfiltered row, or
+/// an empty slice for the first row.
+fn unfilter(
+    Filter: UINT8,
+    Row: &mut [UINT8],
+    Previous: &[UINT8],
+    FilterSize: usize,
+) -> Result<(), EFI_STATUS> {
+    let up = |i: usize| Previous.get(i).copied().unwrap_or(0);
+    for i in 0..Row.len() {
+        let left = if i >= FilterSize {
+            Row[i - FilterSize]
+        } else {
+            0
+        };
+        let up_left = if i >= FilterSize {
+            up(i - FilterSize)
+        } else {
+            0
+        };
+        let predictor = match Filter {
+            0 => 0,
+            1 => left,
+            2 => up(i),
+            3 => ((u16::from(left) + u16::from(up(i))) / 2) as UINT8,
+            4 => paeth(left, up(i), up_left),
+            _ => return Err(EFI_INVALID_PARAMETER),
+        };
+        Row[i] = Row[i].wrapping_add(predictor);
+    }
+
+    Ok(())
+}
+
+fn paeth(Left: UINT8, Up: UINT8, UpLeft: UINT8) -> UINT8 {
+    let estimate = i16::from(Left) + i16::from(Up) - i16::from(UpLeft);
+    let distance_left = (estimate - i16::from(Left)).abs();
+    let distance_up = (estimate - i16::from(Up)).abs();
+    let distance_up_left = (estimate - i16::from(UpLeft)).abs();
+    if distance_left <= distance_up && distance_left <= distance_up_left {
+        Left
+    } else if distance_up <= distance_up_left {
+        Up
+    } else {
+        UpLeft
+    }
+}
+
+/// Returns the sample at `Index` of `Row`, keeping only the high byte of 16-bit samples.
+fn sample(Row: &[UINT8], Index: usize, BitDepth: UINT8) -> UINT8 {
+    match BitDepth {
+        16 => Row[Index * 2],
+        8 => Row[Index],
+        _ => {
+            let bits = Index * usize::from(BitDepth);
+            let shift = 8 - usize::from(BitDepth) - bits % 8;
+            (Row[bits / 8] >> shift) & ((1 << BitDepth) - 1)
+        }
+    }
+}
+
+/// Scales a sample of `BitDepth` bits to eight bits.
+fn scale(Sample: UINT8, BitDepth: UINT8) -> UINT8 {
+    match BitDepth {
+        1 => Sample * 0xFF,
+        2 => Sample * 0x55,
+        4 => Sample * 0x11,
+        _ => Sample,
+    }
+}
+
+fn read_u32(Data: &[UINT8], Offset: usize) -> UINT32 {
+    UINT32::from_be_bytes([
+        Data[Offset],
+        Data[Offset + 1],
+        Data[Offset + 2],
+        Data[Offset + 3],
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use super::*;
+
+    const PNG_RGB8: &[UINT8] = &[
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x05, 0x08, 0x02, 0x00, 0x00, 0x00, 0xED,
+        0xCF, 0xDA, 0x8C, 0x00, 0x00, 0x00, 0x0C, 0x74, 0x45, 0x58, 0x74, 0x43, 0x6F, 0x6D, 0x6D,
+        0x65, 0x6E, 0x74, 0x00, 0x74, 0x65, 0x73, 0x74, 0x57, 0x61, 0x2B, 0xE9, 0x00, 0x00, 0x00,
+        0x22, 0x49, 0x44, 0x41, 0x54, 0x78, 0xDA, 0x63, 0x60, 0xF8, 0xCF, 0xE0, 0xF0, 0x9E, 0xA1,
+        0xE1, 0x3E, 0xC3, 0x81, 0xF3, 0x0C, 0x8C, 0x1C, 0xF7, 0x19, 0x1C, 0x3E, 0xA8, 0x42, 0x10,
+        0x13, 0xC7, 0x03, 0x06, 0x8E, 0x07, 0xAA, 0x1C, 0x0F, 0x42, 0xBA, 0x1D, 0xFC, 0x00, 0x00,
+        0x00, 0x22, 0x49, 0x44, 0x41, 0x54, 0xBC, 0x38, 0x1E, 0xE4, 0x33, 0x0B, 0x38, 0x30, 0xA8,
+        0xBC, 0xF0, 0x52, 0x79, 0x11, 0xAB, 0xF2, 0x22, 0x9F, 0x05, 0x24, 0xF3, 0x41, 0x95, 0xE3,
+        0x83, 0x17, 0xC7, 0x87, 0x29, 0x00, 0x05, 0x53, 0x17, 0xE8, 0x0C, 0x9F, 0xB0, 0x50, 0x00,
+        0x00, 0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+    ];
+
+    const PNG_INDEXED: &[UINT8] = &[
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0x02, 0x02, 0x03, 0x00, 0x00, 0x00, 0xED,
+        0x04, 0xFE, 0xCE, 0x00, 0x00, 0x00, 0x0C, 0x50, 0x4C, 0x54, 0x45, 0x00, 0x00, 0x00, 0xFF,
+        0x00, 0x00, 0x00, 0xFF, 0x00, 0x00, 0x00, 0xFF, 0x9B, 0xC0, 0x13, 0xDC, 0x00, 0x00, 0x00,
+        0x0C, 0x74, 0x45, 0x58, 0x74, 0x43, 0x6F, 0x6D, 0x6D, 0x65, 0x6E, 0x74, 0x00, 0x74, 0x65,
+        0x73, 0x74, 0x57, 0x61, 0x2B, 0xE9, 0x00, 0x00, 0x00, 0x07, 0x49, 0x44, 0x41, 0x54, 0x78,
+        0xDA, 0x63, 0x94, 0x7E, 0xCA, 0x14, 0xBC, 0x64, 0xAF, 0xAC, 0x00, 0x00, 0x00, 0x07, 0x49,
+        0x44, 0x41, 0x54, 0xE8, 0x00, 0x00, 0x05, 0x0F, 0x01, 0x95, 0xDC, 0xE5, 0xD2, 0xD1, 0x00,
+        0x00, 0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+    ];
+
+    const PNG_RGB16: &[UINT8] = &[
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x02, 0x10, 0x02, 0x00, 0x00, 0x00, 0x42,
+        0x86, 0x2D, 0x0E, 0x00, 0x00, 0x00, 0x0C, 0x74, 0x45, 0x58, 0x74, 0x43, 0x6F, 0x6D, 0x6D,
+        0x65, 0x6E, 0x74, 0x00, 0x74, 0x65, 0x73, 0x74, 0x57, 0x61, 0x2B, 0xE9, 0x00, 0x00, 0x00,
+        0x13, 0x49, 0x44, 0x41, 0x54, 0x78, 0xDA, 0x63, 0x66, 0x60, 0xF8, 0xFF, 0x9F, 0x81, 0xC1,
+        0x24, 0x2C, 0xA1, 0x81, 0x81, 0xC1, 0xAF, 0x31, 0x00, 0x90, 0x91, 0xFE, 0xAE, 0x00, 0x00,
+        0x00, 0x14, 0x49, 0x44, 0x41, 0x54, 0x48, 0xB2, 0x08, 0x0A, 0x7E, 0xFC, 0xC8, 0xC0, 0x00,
+        0x24, 0x19, 0x9C, 0x15, 0x21, 0x24, 0x00, 0xC7, 0x5D, 0x0A, 0x01, 0xE9, 0x5F, 0x33, 0xC2,
+        0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+    ];
+
+    type Pixel = fn(usize, usize) -> EFI_GRAPHICS_OUTPUT_BLT_PIXEL;
+    /// An image with its width, height, bit depth, color type and expected pixels.
+    type Image = (&'static [UINT8], usize, usize, UINT8, UINT8, Pixel);
+
+    /// A 4x5 8-bit RGB image with each row using a different filter type.
+    fn rgb8(X: usize, Y: usize) -> EFI_GRAPHICS_OUTPUT_BLT_PIXEL {
+        EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(
+            (X * 64 + Y * 8) as UINT8,
+            (255 - X * 16 - Y * 32) as UINT8,
+            (X * Y * 37) as UINT8,
+        )
+    }
+
+    /// A 5x2 image of 2-bit indices into a black, red, green and blue palette.
+    fn indexed(X: usize, Y: usize) -> EFI_GRAPHICS_OUTPUT_BLT_PIXEL {
+        [
+            EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0, 0, 0),
+            EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0xFF, 0, 0),
+            EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0, 0xFF, 0),
+            EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0, 0, 0xFF),
+        ][(X + Y) % 4]
+    }
+
+    /// A 3x2 16-bit RGB image, of which only the high bytes are kept.
+    fn rgb16(X: usize, Y: usize) -> EFI_GRAPHICS_OUTPUT_BLT_PIXEL {
+        EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(
+            ((X * 0x3456 + Y * 0x1111) >> 8) as UINT8,
+            ((0xFFFF - X * 0x2000 - Y * 0x0F0F) >> 8) as UINT8,
+            ((X * Y * 0x4321) >> 8) as UINT8,
+        )
+    }
+
+    const IMAGES: [Image; 3] = [
+        (PNG_RGB8, 4, 5, 8, PNG_COLOR_TYPE_TRUECOLOR, rgb8),
+        (PNG_INDEXED, 5, 2, 2, PNG_COLOR_TYPE_INDEXED, indexed),
+        (PNG_RGB16, 3, 2, 16, PNG_COLOR_TYPE_TRUECOLOR, rgb16),
+    ];
+
+    /// Recomputes the CRC of every chunk, so changes reach the image data.
+    fn fix_crcs(Data: &mut [UINT8]) {
+        let mut offset = PNG_SIGNATURE.len();
+        while offset + 12 <= Data.len() {
+            let end = offset + 12 + read_u32(Data, offset) as usize;
+            if end > Data.len() {
+                break;
+            }
+            let crc = crc32_update(0, &Data[offset + 4..end - 4]);
+            Data[end - 4..end].copy_from_slice(&crc.to_be_bytes());
+            offset = end;
+        }
+    }
+
+    #[test]
+    fn decode() {
+        for (data, width, height, bit_depth, color_type, pixel) in IMAGES {
+            let png = Png::parse(data).unwrap();
+            assert_eq!(png.width(), width);
+            assert_eq!(png.height(), height);
+            assert_eq!(png.bit_depth(), bit_depth);
+            assert_eq!(png.color_type(), color_type);
+
+            let mut buffer = vec![EFI_GRAPHICS_OUTPUT_BLT_PIXEL::default(); width * height];
+            png.decode_into(&mut buffer).unwrap();
+            for y in 0..height {
+                for x in 0..width {
+                    assert_eq!(buffer[y * width + x], pixel(x, y), "({x}, {y})");
+                }
+            }
+            assert_eq!(png.decode().unwrap().pixels(), buffer);
+
+            buffer.pop();
+            assert_eq!(png.decode_into(&mut buffer), Err(EFI_BUFFER_TOO_SMALL));
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        let mut data = PNG_RGB8.to_vec();
+        data[0] = 0;
+        assert_eq!(Png::parse(&data).err(), Some(EFI_INVALID_PARAMETER));
+
+        // An unknown color type.
+        let mut data = PNG_RGB8.to_vec();
+        data[25] = 5;
+        fix_crcs(&mut data);
+        assert_eq!(Png::parse(&data).err(), Some(EFI_INVALID_PARAMETER));
+
+        // An interlaced image.
+        let mut data = PNG_RGB8.to_vec();
+        data[28] = 1;
+        fix_crcs(&mut data);
+        assert_eq!(Png::parse(&data).err(), Some(EFI_UNSUPPORTED));
+
+        let mut data = PNG_RGB8.to_vec();
+        data[20] = 0x40;
+        fix_crcs(&mut data);
+        assert_eq!(Png::parse(&data).err(), Some(EFI_UNSUPPORTED));
+
+        // Widening the image leaves too little data.
+        let mut data = PNG_RGB8.to_vec();
+        data[19] = 5;
+        fix_crcs(&mut data);
+        let png = Png::parse(&data).unwrap();
+        assert_eq!(png.decode().err(), Some(EFI_INVALID_PARAMETER));
+    }
+
+    #[test]
+    fn truncated() {
+        for (data, ..) in IMAGES {
+            for length in 0..data.len() {
+                let result = Png::parse(&data[..length]).and_then(|png| png.decode());
+                assert!(result.is_err(), "{length}");
+            }
+        }
+    }
+
+    #[test]
+    fn mutated() {
+        for (data, ..) in IMAGES {
+            let mut data = data.to_vec();
+            for index in 0..data.len() {
+                data[index] ^= 0xFF;
+                let result = Png::parse(&data).and_then(|png| png.decode());
+                assert!(result.is_err(), "{index}");
+                data[index] ^= 0xFF;
+            }
+
+            // With valid CRCs the changes reach the decompressor and the filters.
+            for index in PNG_SIGNATURE.len()..data.len() {
+                for mask in [0x01, 0x80, 0xFF] {
+                    let mut data = data.clone();
+                    data[index] ^= mask;
+                    fix_crcs(&mut data);
+                    if let Ok(png) = Png::parse(&data) {
+                        let _ = png.decode();
+                    }
+                }
+            }
+        }
+    }
+}
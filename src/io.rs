@@ -0,0 +1,281 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # I/O Traits
+//!
+//! Since RawUEFI is a `no_std` crate, the I/O traits of the standard library are not available.
+//! This module defines the minimal [`Read`], [`Write`] and [`Seek`] traits used by the
+//! higher-level wrappers over UEFI protocols, such as files and disks, so that parsers can
+//! operate on any of them. Errors are reported as [`EFI_STATUS`] codes.
+//!
+//! [`Read`]: crate::io::Read
+//! [`Write`]: crate::io::Write
+//! [`Seek`]: crate::io::Seek
+//! [`EFI_STATUS`]: crate::types::EFI_STATUS
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::status::{
+    EFI_BUFFER_TOO_SMALL, EFI_DEVICE_ERROR, EFI_END_OF_FILE, EFI_INVALID_PARAMETER,
+};
+use crate::types::{EFI_STATUS, UINT8};
+
+/// A source of bytes.
+pub trait Read {
+    /// Reads bytes into `Buffer`, returning the number of bytes read. A return value of `0`
+    /// indicates the end of the data, unless `Buffer` is empty.
+    fn read(&mut self, Buffer: &mut [UINT8]) -> Result<usize, EFI_STATUS>;
+
+    /// Reads exactly enough bytes to fill `Buffer`.
+    ///
+    /// Returns [`EFI_END_OF_FILE`] if the end of the data is reached first, in which case the
+    /// contents of `Buffer` are unspecified.
+    ///
+    /// [`EFI_END_OF_FILE`]: crate::status::EFI_END_OF_FILE
+    fn read_exact(&mut self, mut Buffer: &mut [UINT8]) -> Result<(), EFI_STATUS> {
+        while !Buffer.is_empty() {
+            match self.read(Buffer)? {
+                0 => return Err(EFI_END_OF_FILE),
+                n => Buffer = &mut Buffer[n..],
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads all bytes until the end of the data and appends them to `Buffer`, returning the
+    /// number of bytes read.
+    #[cfg(feature = "alloc")]
+    fn read_to_end(&mut self, Buffer: &mut Vec<UINT8>) -> Result<usize, EFI_STATUS> {
+        let start = Buffer.len();
+        loop {
+            if Buffer.capacity() - Buffer.len() < 512 {
+                Buffer.reserve(Buffer.capacity().max(512));
+            }
+
+            let len = Buffer.len();
+            Buffer.resize(Buffer.capacity(), 0);
+            match self.read(&mut Buffer[len..]) {
+                Ok(0) => {
+                    Buffer.truncate(len);
+                    return Ok(len - start);
+                }
+                Ok(n) => Buffer.truncate(len + n),
+                Err(status) => {
+                    Buffer.truncate(len);
+                    return Err(status);
+                }
+            }
+        }
+    }
+}
+
+/// A sink of bytes.
+pub trait Write {
+    /// Writes bytes from `Buffer`, returning the number of bytes written.
+    fn write(&mut self, Buffer: &[UINT8]) -> Result<usize, EFI_STATUS>;
+
+    /// Flushes any buffered data to the underlying device.
+    fn flush(&mut self) -> Result<(), EFI_STATUS>;
+
+    /// Writes all of `Buffer`.
+    ///
+    /// Returns [`EFI_DEVICE_ERROR`] if the sink stops accepting bytes before `Buffer` is
+    /// written.
+    ///
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    fn write_all(&mut self, mut Buffer: &[UINT8]) -> Result<(), EFI_STATUS> {
+        while !Buffer.is_empty() {
+            match self.write(Buffer)? {
+                0 => return Err(EFI_DEVICE_ERROR),
+                n => Buffer = &Buffer[n..],
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A position to seek to, as passed to [`Seek::seek()`].
+///
+/// [`Seek::seek()`]: crate::io::Seek::seek
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SeekFrom {
+    /// An offset from the start of the data.
+    Start(u64),
+    /// An offset from the end of the data.
+    End(i64),
+    /// An offset from the current position.
+    Current(i64),
+}
+
+/// A cursor that can be moved within a stream of bytes.
+pub trait Seek {
+    /// Moves the cursor to `Position`, returning the new position from the start of the data.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if the new position would be negative.
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    fn seek(&mut self, Position: SeekFrom) -> Result<u64, EFI_STATUS>;
+
+    /// Returns the current position from the start of the data.
+    fn stream_position(&mut self) -> Result<u64, EFI_STATUS> {
+        self.seek(SeekFrom::Current(0))
+    }
+}
+
+impl<R: Read + ?Sized> Read for &mut R {
+    fn read(&mut self, Buffer: &mut [UINT8]) -> Result<usize, EFI_STATUS> {
+        (**self).read(Buffer)
+    }
+}
+
+impl<W: Write + ?Sized> Write for &mut W {
+    fn write(&mut self, Buffer: &[UINT8]) -> Result<usize, EFI_STATUS> {
+        (**self).write(Buffer)
+    }
+
+    fn flush(&mut self) -> Result<(), EFI_STATUS> {
+        (**self).flush()
+    }
+}
+
+impl<S: Seek + ?Sized> Seek for &mut S {
+    fn seek(&mut self, Position: SeekFrom) -> Result<u64, EFI_STATUS> {
+        (**self).seek(Position)
+    }
+}
+
+impl Read for &[UINT8] {
+    fn read(&mut self, Buffer: &mut [UINT8]) -> Result<usize, EFI_STATUS> {
+        let len = Buffer.len().min(self.len());
+        let (head, tail) = self.split_at(len);
+        Buffer[..len].copy_from_slice(head);
+        *self = tail;
+        Ok(len)
+    }
+}
+
+/// A [`Read`], [`Write`] and [`Seek`] implementation over an in-memory buffer, such as a disk
+/// image loaded on the host.
+///
+/// Writes past the end of the buffer fail with [`EFI_BUFFER_TOO_SMALL`] rather than growing it.
+///
+/// [`Read`]: crate::io::Read
+/// [`Write`]: crate::io::Write
+/// [`Seek`]: crate::io::Seek
+/// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+#[derive(Clone, Debug, Default)]
+pub struct Cursor<T> {
+    inner: T,
+    position: u64,
+}
+
+impl<T> Cursor<T> {
+    /// Creates a cursor at the start of `Inner`.
+    pub fn new(Inner: T) -> Self {
+        Self {
+            inner: Inner,
+            position: 0,
+        }
+    }
+
+    /// Returns the underlying buffer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the underlying buffer mutably.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes the cursor, returning the underlying buffer.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns the current position of the cursor.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Sets the current position of the cursor.
+    pub fn set_position(&mut self, Position: u64) {
+        self.position = Position;
+    }
+}
+
+impl<T: AsRef<[UINT8]>> Cursor<T> {
+    fn remaining(&self) -> &[UINT8] {
+        let data = self.inner.as_ref();
+        let start = usize::try_from(self.position).map_or(data.len(), |p| p.min(data.len()));
+        &data[start..]
+    }
+}
+
+impl<T: AsRef<[UINT8]>> Read for Cursor<T> {
+    fn read(&mut self, Buffer: &mut [UINT8]) -> Result<usize, EFI_STATUS> {
+        let len = self.remaining().read(Buffer)?;
+        self.position += len as u64;
+        Ok(len)
+    }
+}
+
+impl<T: AsMut<[UINT8]>> Write for Cursor<T> {
+    fn write(&mut self, Buffer: &[UINT8]) -> Result<usize, EFI_STATUS> {
+        if Buffer.is_empty() {
+            return Ok(0);
+        }
+
+        let data = self.inner.as_mut();
+        let start = match usize::try_from(self.position) {
+            Ok(start) if start < data.len() => start,
+            _ => return Err(EFI_BUFFER_TOO_SMALL),
+        };
+        let len = Buffer.len().min(data.len() - start);
+        data[start..start + len].copy_from_slice(&Buffer[..len]);
+        self.position += len as u64;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> Result<(), EFI_STATUS> {
+        Ok(())
+    }
+}
+
+impl<T: AsRef<[UINT8]>> Seek for Cursor<T> {
+    fn seek(&mut self, Position: SeekFrom) -> Result<u64, EFI_STATUS> {
+        self.position = match Position {
+            SeekFrom::Start(offset) => offset,
+            SeekFrom::End(offset) => offset_position(self.inner.as_ref().len() as u64, offset)?,
+            SeekFrom::Current(offset) => offset_position(self.position, offset)?,
+        };
+        Ok(self.position)
+    }
+}
+
+/// Computes the position resulting from applying `Offset` to `Base`.
+pub(crate) fn offset_position(Base: u64, Offset: i64) -> Result<u64, EFI_STATUS> {
+    if Offset >= 0 {
+        Base.checked_add(Offset as u64)
+    } else {
+        Base.checked_sub(Offset.unsigned_abs())
+    }
+    .ok_or(EFI_INVALID_PARAMETER)
+}
@@ -34,7 +34,20 @@
 #![no_std]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(clippy::missing_safety_doc)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[macro_use]
+mod macros;
+
+pub mod boot_manager;
+pub mod executor;
+pub mod graphics;
+pub mod io;
+pub mod partition;
 pub mod protocols;
 pub mod status;
 pub mod tables;
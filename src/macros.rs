@@ -0,0 +1,37 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Internal helper macros used throughout RawUEFI.
+
+/// Expands to a `&'static [CHAR16; N]` containing the UCS-2 encoding of an ASCII string literal,
+/// including the terminating null character.
+macro_rules! ucs2 {
+    ($s:literal) => {{
+        const S: &[u8] = $s.as_bytes();
+        const N: usize = S.len() + 1;
+        const A: [$crate::types::CHAR16; N] = {
+            let mut a = [0; N];
+            let mut i = 0;
+            while i < S.len() {
+                a[i] = S[i] as $crate::types::CHAR16;
+                i += 1;
+            }
+            a
+        };
+        &A
+    }};
+}
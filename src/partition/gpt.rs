@@ -0,0 +1,941 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # GUID Partition Table
+//!
+//! This module defines the GUID Partition Table header, also known as the
+//! [`EFI_PARTITION_TABLE_HEADER`], its [`EFI_PARTITION_ENTRY`] entries, and the well-known
+//! partition type GUIDs. See [Section 5.3 of the UEFI Specification] for more details.
+//!
+//! A GPT disk holds two copies of the table: the primary header at LBA 1, usually followed by its
+//! entry array, and a backup header in the last LBA of the disk, usually preceded by a copy of the
+//! entry array. [`Gpt::read()`] validates the primary header and entry array, including their
+//! CRC32s, and falls back to the backup copy if the primary one is damaged.
+//!
+//! [`EFI_PARTITION_TABLE_HEADER`]: crate::partition::gpt::EFI_PARTITION_TABLE_HEADER
+//! [`EFI_PARTITION_ENTRY`]: crate::partition::gpt::EFI_PARTITION_ENTRY
+//! [`Gpt::read()`]: crate::partition::Gpt::read
+//!
+//! [Section 5.3 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/05_GUID_Partition_Table_Format.html#guid-partition-table-gpt-disk-layout
+
+use core::ptr;
+
+use crate::io::{Read, Seek, SeekFrom};
+use crate::partition::{crc32, crc32_update};
+use crate::status::{EFI_CRC_ERROR, EFI_INVALID_PARAMETER, EFI_NOT_FOUND, EFI_VOLUME_CORRUPTED};
+use crate::tables::EFI_TABLE_HEADER;
+use crate::types::{CHAR16, EFI_GUID, EFI_LBA, EFI_STATUS, UINT32, UINT64, UINT8};
+
+/// The signature of an [`EFI_PARTITION_TABLE_HEADER`], the ASCII string `"EFI PART"`.
+///
+/// [`EFI_PARTITION_TABLE_HEADER`]: crate::partition::gpt::EFI_PARTITION_TABLE_HEADER
+pub const EFI_PTAB_HEADER_ID: UINT64 = 0x5452415020494645;
+
+/// The revision of the [`EFI_PARTITION_TABLE_HEADER`] defined by this module.
+///
+/// [`EFI_PARTITION_TABLE_HEADER`]: crate::partition::gpt::EFI_PARTITION_TABLE_HEADER
+pub const EFI_PARTITION_TABLE_HEADER_REVISION: UINT32 = 0x00010000;
+
+/// The LBA of the primary [`EFI_PARTITION_TABLE_HEADER`].
+///
+/// [`EFI_PARTITION_TABLE_HEADER`]: crate::partition::gpt::EFI_PARTITION_TABLE_HEADER
+pub const PRIMARY_PART_HEADER_LBA: EFI_LBA = 1;
+
+/// The size of the fields of an [`EFI_PARTITION_TABLE_HEADER`] in bytes. The rest of the block
+/// is reserved and must be zero.
+///
+/// [`EFI_PARTITION_TABLE_HEADER`]: crate::partition::gpt::EFI_PARTITION_TABLE_HEADER
+pub const SIZE_OF_EFI_PARTITION_TABLE_HEADER: usize = 92;
+
+/// The size of the fields of an [`EFI_PARTITION_ENTRY`] in bytes. Entries may be larger, in which
+/// case the rest of each entry is reserved.
+///
+/// [`EFI_PARTITION_ENTRY`]: crate::partition::gpt::EFI_PARTITION_ENTRY
+pub const SIZE_OF_EFI_PARTITION_ENTRY: usize = 128;
+
+/// Partition attribute: the partition is required for the platform to function, and must not be
+/// deleted or modified.
+pub const GPT_ATTRIBUTE_REQUIRED_PARTITION: UINT64 = 0x0000000000000001;
+/// Partition attribute: the firmware must not produce an [`EFI_BLOCK_IO_PROTOCOL`] for the
+/// partition.
+///
+/// [`EFI_BLOCK_IO_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO_PROTOCOL
+pub const GPT_ATTRIBUTE_NO_BLOCK_IO_PROTOCOL: UINT64 = 0x0000000000000002;
+/// Partition attribute: the partition may be bootable by legacy BIOS firmware.
+pub const GPT_ATTRIBUTE_LEGACY_BIOS_BOOTABLE: UINT64 = 0x0000000000000004;
+/// Mask of the partition attribute bits reserved for the partition type GUID to define.
+pub const GPT_ATTRIBUTE_TYPE_SPECIFIC_MASK: UINT64 = 0xFFFF000000000000;
+
+/// Partition type of an unused [`EFI_PARTITION_ENTRY`].
+///
+/// [`EFI_PARTITION_ENTRY`]: crate::partition::gpt::EFI_PARTITION_ENTRY
+pub const EFI_PART_TYPE_UNUSED_GUID: EFI_GUID = EFI_GUID(
+    0x00000000,
+    0x0000,
+    0x0000,
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+);
+
+/// Partition type of an EFI System Partition.
+pub const EFI_PART_TYPE_EFI_SYSTEM_PART_GUID: EFI_GUID = EFI_GUID(
+    0xC12A7328,
+    0xF81F,
+    0x11D2,
+    [0xBA, 0x4B, 0x00, 0xA0, 0xC9, 0x3E, 0xC9, 0x3B],
+);
+
+/// Partition type of a partition containing a legacy MBR.
+pub const EFI_PART_TYPE_LEGACY_MBR_GUID: EFI_GUID = EFI_GUID(
+    0x024DEE41,
+    0x33E7,
+    0x11D3,
+    [0x9D, 0x69, 0x00, 0x08, 0xC7, 0x81, 0xF3, 0x9F],
+);
+
+/// Partition type of a BIOS boot partition, used by GRUB on legacy BIOS systems.
+pub const EFI_PART_TYPE_BIOS_BOOT_GUID: EFI_GUID = EFI_GUID(
+    0x21686148,
+    0x6449,
+    0x6E6F,
+    [0x74, 0x4E, 0x65, 0x65, 0x64, 0x45, 0x46, 0x49],
+);
+
+/// Partition type of a Microsoft Reserved Partition.
+pub const EFI_PART_TYPE_MICROSOFT_RESERVED_GUID: EFI_GUID = EFI_GUID(
+    0xE3C9E316,
+    0x0B5C,
+    0x4DB8,
+    [0x81, 0x7D, 0xF9, 0x2D, 0xF0, 0x02, 0x15, 0xAE],
+);
+
+/// Partition type of a Microsoft basic data partition, such as an NTFS or exFAT volume.
+pub const EFI_PART_TYPE_MICROSOFT_BASIC_DATA_GUID: EFI_GUID = EFI_GUID(
+    0xEBD0A0A2,
+    0xB9E5,
+    0x4433,
+    [0x87, 0xC0, 0x68, 0xB6, 0xB7, 0x26, 0x99, 0xC7],
+);
+
+/// Partition type of a Windows Recovery Environment partition.
+pub const EFI_PART_TYPE_WINDOWS_RECOVERY_GUID: EFI_GUID = EFI_GUID(
+    0xDE94BBA4,
+    0x06D1,
+    0x4D40,
+    [0xA1, 0x6A, 0xBF, 0xD5, 0x01, 0x79, 0xD6, 0xAC],
+);
+
+/// Partition type of a generic Linux file system data partition.
+pub const EFI_PART_TYPE_LINUX_FILESYSTEM_GUID: EFI_GUID = EFI_GUID(
+    0x0FC63DAF,
+    0x8483,
+    0x4772,
+    [0x8E, 0x79, 0x3D, 0x69, 0xD8, 0x47, 0x7D, 0xE4],
+);
+
+/// Partition type of a Linux root partition for x86.
+pub const EFI_PART_TYPE_LINUX_ROOT_X86_GUID: EFI_GUID = EFI_GUID(
+    0x44479540,
+    0xF297,
+    0x41B2,
+    [0x9A, 0xF7, 0xD1, 0x31, 0xD5, 0xF0, 0x45, 0x8A],
+);
+
+/// Partition type of a Linux root partition for x86-64.
+pub const EFI_PART_TYPE_LINUX_ROOT_X86_64_GUID: EFI_GUID = EFI_GUID(
+    0x4F68BCE3,
+    0xE8CD,
+    0x4DB1,
+    [0x96, 0xE7, 0xFB, 0xCA, 0xF9, 0x84, 0xB7, 0x09],
+);
+
+/// Partition type of a Linux root partition for 32-bit ARM.
+pub const EFI_PART_TYPE_LINUX_ROOT_ARM_GUID: EFI_GUID = EFI_GUID(
+    0x69DAD710,
+    0x2CE4,
+    0x4E3C,
+    [0xB1, 0x6C, 0x21, 0xA1, 0xD4, 0x9A, 0xBE, 0xD3],
+);
+
+/// Partition type of a Linux root partition for 64-bit ARM.
+pub const EFI_PART_TYPE_LINUX_ROOT_AARCH64_GUID: EFI_GUID = EFI_GUID(
+    0xB921B045,
+    0x1DF0,
+    0x41C3,
+    [0xAF, 0x44, 0x4C, 0x6F, 0x28, 0x0D, 0x3F, 0xAE],
+);
+
+/// Partition type of a Linux root partition for 64-bit RISC-V.
+pub const EFI_PART_TYPE_LINUX_ROOT_RISCV64_GUID: EFI_GUID = EFI_GUID(
+    0x72EC70A6,
+    0xCF74,
+    0x40E6,
+    [0xBD, 0x49, 0x4B, 0xDA, 0x08, 0xE8, 0xF2, 0x24],
+);
+
+/// Partition type of a Linux extended boot loader (`/boot`) partition.
+pub const EFI_PART_TYPE_LINUX_EXTENDED_BOOT_GUID: EFI_GUID = EFI_GUID(
+    0xBC13C2FF,
+    0x59E6,
+    0x4262,
+    [0xA3, 0x52, 0xB2, 0x75, 0xFD, 0x6F, 0x71, 0x72],
+);
+
+/// Partition type of a Linux home (`/home`) partition.
+pub const EFI_PART_TYPE_LINUX_HOME_GUID: EFI_GUID = EFI_GUID(
+    0x933AC7E1,
+    0x2EB4,
+    0x4F13,
+    [0xB8, 0x44, 0x0E, 0x14, 0xE2, 0xAE, 0xF9, 0x15],
+);
+
+/// Partition type of a Linux swap partition.
+pub const EFI_PART_TYPE_LINUX_SWAP_GUID: EFI_GUID = EFI_GUID(
+    0x0657FD6D,
+    0xA4AB,
+    0x43C4,
+    [0x84, 0xE5, 0x09, 0x33, 0xC8, 0x4B, 0x4F, 0x4F],
+);
+
+/// Partition type of a Linux LVM physical volume.
+pub const EFI_PART_TYPE_LINUX_LVM_GUID: EFI_GUID = EFI_GUID(
+    0xE6D6D379,
+    0xF507,
+    0x44C2,
+    [0xA2, 0x3C, 0x23, 0x8F, 0x2A, 0x3D, 0xF9, 0x28],
+);
+
+/// Partition type of a Linux software RAID member.
+pub const EFI_PART_TYPE_LINUX_RAID_GUID: EFI_GUID = EFI_GUID(
+    0xA19D880F,
+    0x05FC,
+    0x4D3B,
+    [0xA0, 0x06, 0x74, 0x3F, 0x0F, 0x84, 0x91, 0x1E],
+);
+
+/// Partition type of a Linux LUKS encrypted partition.
+pub const EFI_PART_TYPE_LINUX_LUKS_GUID: EFI_GUID = EFI_GUID(
+    0xCA7D7CCB,
+    0x63ED,
+    0x4C53,
+    [0x86, 0x1C, 0x17, 0x42, 0x53, 0x60, 0x59, 0xCC],
+);
+
+/// Partition type of an Apple HFS+ partition.
+pub const EFI_PART_TYPE_APPLE_HFS_GUID: EFI_GUID = EFI_GUID(
+    0x48465300,
+    0x0000,
+    0x11AA,
+    [0xAA, 0x11, 0x00, 0x30, 0x65, 0x43, 0xEC, 0xAC],
+);
+
+/// Partition type of an Apple APFS container.
+pub const EFI_PART_TYPE_APPLE_APFS_GUID: EFI_GUID = EFI_GUID(
+    0x7C3457EF,
+    0x0000,
+    0x11AA,
+    [0xAA, 0x11, 0x00, 0x30, 0x65, 0x43, 0xEC, 0xAC],
+);
+
+/// The header of a GUID Partition Table.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_PARTITION_TABLE_HEADER {
+    /// The table header. Its [`Signature`] is [`EFI_PTAB_HEADER_ID`], its [`HeaderSize`] is at
+    /// least [`SIZE_OF_EFI_PARTITION_TABLE_HEADER`], and its [`Crc32`] covers [`HeaderSize`]
+    /// bytes.
+    ///
+    /// [`Signature`]: crate::tables::EFI_TABLE_HEADER#structfield.Signature
+    /// [`EFI_PTAB_HEADER_ID`]: crate::partition::gpt::EFI_PTAB_HEADER_ID
+    /// [`HeaderSize`]: crate::tables::EFI_TABLE_HEADER#structfield.HeaderSize
+    /// [`SIZE_OF_EFI_PARTITION_TABLE_HEADER`]: crate::partition::gpt::SIZE_OF_EFI_PARTITION_TABLE_HEADER
+    /// [`Crc32`]: crate::tables::EFI_TABLE_HEADER#structfield.Crc32
+    pub Header: EFI_TABLE_HEADER,
+    /// The LBA that contains this data structure.
+    pub MyLBA: EFI_LBA,
+    /// LBA address of the alternate GUID Partition Table header.
+    pub AlternateLBA: EFI_LBA,
+    /// The first usable logical block that may be used by a partition.
+    pub FirstUsableLBA: EFI_LBA,
+    /// The last usable logical block that may be used by a partition.
+    pub LastUsableLBA: EFI_LBA,
+    /// GUID that can be used to uniquely identify the disk.
+    pub DiskGUID: EFI_GUID,
+    /// The starting LBA of the GUID Partition Entry array.
+    pub PartitionEntryLBA: EFI_LBA,
+    /// The number of Partition Entries in the GUID Partition Entry array.
+    pub NumberOfPartitionEntries: UINT32,
+    /// The size, in bytes, of each GUID Partition Entry structure. Must be a value of
+    /// `128 * 2^n` where `n` is an integer greater than or equal to zero.
+    pub SizeOfPartitionEntry: UINT32,
+    /// The CRC32 of the GUID Partition Entry array, covering [`NumberOfPartitionEntries`] times
+    /// [`SizeOfPartitionEntry`] bytes.
+    ///
+    /// [`NumberOfPartitionEntries`]: ./struct.EFI_PARTITION_TABLE_HEADER.html#structfield.NumberOfPartitionEntries
+    /// [`SizeOfPartitionEntry`]: ./struct.EFI_PARTITION_TABLE_HEADER.html#structfield.SizeOfPartitionEntry
+    pub PartitionEntryArrayCRC32: UINT32,
+}
+
+impl EFI_PARTITION_TABLE_HEADER {
+    /// Parses and validates the header held in `Block`, the contents of logical block `LBA`.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_NOT_FOUND`] - the block does not start with [`EFI_PTAB_HEADER_ID`].
+    ///
+    /// [`EFI_CRC_ERROR`] - the CRC32 of the header is wrong.
+    ///
+    /// [`EFI_VOLUME_CORRUPTED`] - a field of the header is not valid, or [`MyLBA`] is not `LBA`.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - `Block` is shorter than the header.
+    ///
+    /// [`EFI_NOT_FOUND`]: crate::status::EFI_NOT_FOUND
+    /// [`EFI_PTAB_HEADER_ID`]: crate::partition::gpt::EFI_PTAB_HEADER_ID
+    /// [`EFI_CRC_ERROR`]: crate::status::EFI_CRC_ERROR
+    /// [`EFI_VOLUME_CORRUPTED`]: crate::status::EFI_VOLUME_CORRUPTED
+    /// [`MyLBA`]: ./struct.EFI_PARTITION_TABLE_HEADER.html#structfield.MyLBA
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub fn parse(Block: &[UINT8], LBA: EFI_LBA) -> Result<Self, EFI_STATUS> {
+        let header = Self::from_bytes(Block)?;
+        let size = header.Header.HeaderSize as usize;
+        if size > Block.len() {
+            return Err(EFI_VOLUME_CORRUPTED);
+        }
+
+        let crc = crc32_update(
+            header.crc_of_fields(),
+            &Block[SIZE_OF_EFI_PARTITION_TABLE_HEADER..size],
+        );
+        if crc != header.Header.Crc32 {
+            return Err(EFI_CRC_ERROR);
+        }
+
+        header.check(LBA)?;
+        Ok(header)
+    }
+
+    /// Returns the header held in the first [`SIZE_OF_EFI_PARTITION_TABLE_HEADER`] bytes of
+    /// `Data`, checking only its signature.
+    ///
+    /// [`SIZE_OF_EFI_PARTITION_TABLE_HEADER`]: crate::partition::gpt::SIZE_OF_EFI_PARTITION_TABLE_HEADER
+    pub fn from_bytes(Data: &[UINT8]) -> Result<Self, EFI_STATUS> {
+        if Data.len() < SIZE_OF_EFI_PARTITION_TABLE_HEADER {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        // The structure is padded to a multiple of 8 bytes, past the fields stored on disk.
+        let mut bytes = [0; core::mem::size_of::<Self>()];
+        bytes[..SIZE_OF_EFI_PARTITION_TABLE_HEADER]
+            .copy_from_slice(&Data[..SIZE_OF_EFI_PARTITION_TABLE_HEADER]);
+        // SAFETY: `bytes` is the size of the structure, which is valid for any bit pattern.
+        let header: Self = unsafe { ptr::read_unaligned(bytes.as_ptr().cast()) };
+        if header.Header.Signature != EFI_PTAB_HEADER_ID {
+            return Err(EFI_NOT_FOUND);
+        }
+
+        Ok(header)
+    }
+
+    /// Returns the size of the partition entry array in bytes.
+    pub fn entry_array_size(&self) -> UINT64 {
+        self.NumberOfPartitionEntries as UINT64 * self.SizeOfPartitionEntry as UINT64
+    }
+
+    /// Returns the CRC32 of the fields of the header, with the [`Crc32`] field taken as `0`.
+    ///
+    /// [`Crc32`]: crate::tables::EFI_TABLE_HEADER#structfield.Crc32
+    fn crc_of_fields(&self) -> UINT32 {
+        // SAFETY: the fields stored on disk are at the start of the structure, without padding.
+        let mut bytes: [UINT8; SIZE_OF_EFI_PARTITION_TABLE_HEADER] =
+            unsafe { ptr::read_unaligned((self as *const Self).cast()) };
+        bytes[16..20].fill(0);
+        crc32(&bytes)
+    }
+
+    /// Checks the fields of a header with a valid CRC32 that was read from logical block `LBA`.
+    fn check(&self, LBA: EFI_LBA) -> Result<(), EFI_STATUS> {
+        let entry_size = self.SizeOfPartitionEntry as usize;
+        let valid = self.Header.HeaderSize as usize >= SIZE_OF_EFI_PARTITION_TABLE_HEADER
+            && self.MyLBA == LBA
+            && self.FirstUsableLBA <= self.LastUsableLBA
+            && entry_size % SIZE_OF_EFI_PARTITION_ENTRY == 0
+            && (entry_size / SIZE_OF_EFI_PARTITION_ENTRY).is_power_of_two();
+        if valid {
+            Ok(())
+        } else {
+            Err(EFI_VOLUME_CORRUPTED)
+        }
+    }
+}
+
+/// An entry of the GUID Partition Entry array.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_PARTITION_ENTRY {
+    /// Unique ID that defines the purpose and type of this partition. A value of
+    /// [`EFI_PART_TYPE_UNUSED_GUID`] defines that this partition entry is not being used.
+    ///
+    /// [`EFI_PART_TYPE_UNUSED_GUID`]: crate::partition::gpt::EFI_PART_TYPE_UNUSED_GUID
+    pub PartitionTypeGUID: EFI_GUID,
+    /// GUID that is unique for every partition entry, also known as the PARTUUID.
+    pub UniquePartitionGUID: EFI_GUID,
+    /// Starting LBA of the partition defined by this entry.
+    pub StartingLBA: EFI_LBA,
+    /// Ending LBA of the partition defined by this entry, inclusive.
+    pub EndingLBA: EFI_LBA,
+    /// Attribute bits, such as [`GPT_ATTRIBUTE_REQUIRED_PARTITION`].
+    ///
+    /// [`GPT_ATTRIBUTE_REQUIRED_PARTITION`]: crate::partition::gpt::GPT_ATTRIBUTE_REQUIRED_PARTITION
+    pub Attributes: UINT64,
+    /// Null-terminated string containing a human-readable name of the partition.
+    pub PartitionName: [CHAR16; 36],
+}
+
+impl EFI_PARTITION_ENTRY {
+    /// Returns the entry held in the first [`SIZE_OF_EFI_PARTITION_ENTRY`] bytes of `Data`.
+    ///
+    /// [`SIZE_OF_EFI_PARTITION_ENTRY`]: crate::partition::gpt::SIZE_OF_EFI_PARTITION_ENTRY
+    pub fn from_bytes(Data: &[UINT8]) -> Result<Self, EFI_STATUS> {
+        if Data.len() < SIZE_OF_EFI_PARTITION_ENTRY {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        // SAFETY: `Data` holds at least the size of the structure, which is valid for any bit
+        // pattern.
+        Ok(unsafe { ptr::read_unaligned(Data.as_ptr().cast()) })
+    }
+
+    /// Returns `true` if the entry describes a partition.
+    pub fn is_used(&self) -> bool {
+        self.PartitionTypeGUID != EFI_PART_TYPE_UNUSED_GUID
+    }
+
+    /// Returns the size of the partition in logical blocks.
+    pub fn size_in_blocks(&self) -> UINT64 {
+        self.EndingLBA
+            .checked_sub(self.StartingLBA)
+            .map_or(0, |len| len.saturating_add(1))
+    }
+
+    /// Returns an iterator over the characters of the partition name. Unpaired surrogates are
+    /// replaced by [`char::REPLACEMENT_CHARACTER`].
+    pub fn name(&self) -> impl Iterator<Item = char> {
+        let name = self.PartitionName;
+        let len = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+        char::decode_utf16(name.into_iter().take(len))
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+}
+
+/// A validated GUID Partition Table, read from a disk on demand.
+///
+/// Only the header is kept in memory; entries are read from the disk when requested, so no
+/// allocation is needed.
+pub struct Gpt<R> {
+    reader: R,
+    block_size: UINT64,
+    header: EFI_PARTITION_TABLE_HEADER,
+    backup: bool,
+}
+
+impl<R: Read + Seek> Gpt<R> {
+    /// Reads the GUID Partition Table of a disk with blocks of `BlockSize` bytes.
+    ///
+    /// The primary header and its entry array are used if both are valid. Otherwise the backup
+    /// header is looked for at the [`AlternateLBA`] of a primary header with a valid CRC32, or
+    /// else in the last block of the disk, and used if it and its entry array are valid.
+    ///
+    /// If neither copy is usable, the error of the primary copy is returned: see
+    /// [`EFI_PARTITION_TABLE_HEADER::parse()`], as well as [`EFI_CRC_ERROR`] for an entry array
+    /// with a wrong CRC32 and [`EFI_INVALID_PARAMETER`] for a `BlockSize` that cannot hold the
+    /// header.
+    ///
+    /// [`AlternateLBA`]: ./struct.EFI_PARTITION_TABLE_HEADER.html#structfield.AlternateLBA
+    /// [`EFI_PARTITION_TABLE_HEADER::parse()`]: crate::partition::gpt::EFI_PARTITION_TABLE_HEADER::parse
+    /// [`EFI_CRC_ERROR`]: crate::status::EFI_CRC_ERROR
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub fn read(Reader: R, BlockSize: UINT32) -> Result<Self, EFI_STATUS> {
+        if (BlockSize as usize) < SIZE_OF_EFI_PARTITION_TABLE_HEADER {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        let mut reader = Reader;
+        let block_size = BlockSize as UINT64;
+        let primary = read_header(&mut reader, block_size, PRIMARY_PART_HEADER_LBA);
+        let error =
+            match primary.and_then(|h| check_entries(&mut reader, block_size, &h).map(|_| h)) {
+                Ok(header) => return Ok(Self::new(reader, block_size, header, false)),
+                Err(error) => error,
+            };
+
+        let alternate = match primary {
+            Ok(header) => header.AlternateLBA,
+            Err(_) => match reader.seek(SeekFrom::End(0))? / block_size {
+                0 => return Err(error),
+                blocks => blocks - 1,
+            },
+        };
+        read_header(&mut reader, block_size, alternate)
+            .and_then(|h| check_entries(&mut reader, block_size, &h).map(|_| h))
+            .map(|header| Self::new(reader, block_size, header, true))
+            .map_err(|_| error)
+    }
+
+    fn new(Reader: R, BlockSize: UINT64, Header: EFI_PARTITION_TABLE_HEADER, Backup: bool) -> Self {
+        Self {
+            reader: Reader,
+            block_size: BlockSize,
+            header: Header,
+            backup: Backup,
+        }
+    }
+
+    /// Returns the header of the table in use.
+    pub fn header(&self) -> &EFI_PARTITION_TABLE_HEADER {
+        &self.header
+    }
+
+    /// Returns `true` if the primary table was damaged and the backup table is in use.
+    pub fn is_backup(&self) -> bool {
+        self.backup
+    }
+
+    /// Returns the size of the logical blocks of the disk in bytes.
+    pub fn block_size(&self) -> UINT64 {
+        self.block_size
+    }
+
+    /// Returns the GUID of the disk.
+    pub fn disk_guid(&self) -> EFI_GUID {
+        self.header.DiskGUID
+    }
+
+    /// Consumes the table, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads the partition entry at `Index` of the entry array, which may be unused.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if `Index` is not below [`NumberOfPartitionEntries`].
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`NumberOfPartitionEntries`]: ./struct.EFI_PARTITION_TABLE_HEADER.html#structfield.NumberOfPartitionEntries
+    pub fn entry(&mut self, Index: UINT32) -> Result<EFI_PARTITION_ENTRY, EFI_STATUS> {
+        if Index >= self.header.NumberOfPartitionEntries {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        let offset = entry_array_offset(self.block_size, &self.header)?
+            + Index as UINT64 * self.header.SizeOfPartitionEntry as UINT64;
+        let mut bytes = [0; SIZE_OF_EFI_PARTITION_ENTRY];
+        self.reader.seek(SeekFrom::Start(offset))?;
+        self.reader.read_exact(&mut bytes)?;
+        EFI_PARTITION_ENTRY::from_bytes(&bytes)
+    }
+
+    /// Returns an iterator over the used partition entries and their indices in the entry
+    /// array.
+    pub fn partitions(&mut self) -> Partitions<'_, R> {
+        Partitions {
+            gpt: self,
+            index: 0,
+        }
+    }
+
+    /// Returns the first used partition entry of type `PartitionType`, and its index.
+    pub fn find_by_type(
+        &mut self,
+        PartitionType: &EFI_GUID,
+    ) -> Result<Option<(UINT32, EFI_PARTITION_ENTRY)>, EFI_STATUS> {
+        self.find(|entry| entry.PartitionTypeGUID == *PartitionType)
+    }
+
+    /// Returns the partition entry with the unique partition GUID (PARTUUID) `PartitionGuid`,
+    /// and its index.
+    pub fn find_by_unique_guid(
+        &mut self,
+        PartitionGuid: &EFI_GUID,
+    ) -> Result<Option<(UINT32, EFI_PARTITION_ENTRY)>, EFI_STATUS> {
+        self.find(|entry| entry.UniquePartitionGUID == *PartitionGuid)
+    }
+
+    fn find(
+        &mut self,
+        mut predicate: impl FnMut(&EFI_PARTITION_ENTRY) -> bool,
+    ) -> Result<Option<(UINT32, EFI_PARTITION_ENTRY)>, EFI_STATUS> {
+        for partition in self.partitions() {
+            let (index, entry) = partition?;
+            if predicate(&entry) {
+                return Ok(Some((index, entry)));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+fn read_header<R: Read + Seek>(
+    Reader: &mut R,
+    BlockSize: UINT64,
+    LBA: EFI_LBA,
+) -> Result<EFI_PARTITION_TABLE_HEADER, EFI_STATUS> {
+    let offset = LBA.checked_mul(BlockSize).ok_or(EFI_VOLUME_CORRUPTED)?;
+    let mut bytes = [0; SIZE_OF_EFI_PARTITION_TABLE_HEADER];
+    Reader.seek(SeekFrom::Start(offset))?;
+    Reader.read_exact(&mut bytes)?;
+
+    let header = EFI_PARTITION_TABLE_HEADER::from_bytes(&bytes)?;
+    let size = header.Header.HeaderSize as UINT64;
+    if size < SIZE_OF_EFI_PARTITION_TABLE_HEADER as UINT64 || size > BlockSize {
+        return Err(EFI_VOLUME_CORRUPTED);
+    }
+
+    let crc = crc_of(
+        Reader,
+        header.crc_of_fields(),
+        size - SIZE_OF_EFI_PARTITION_TABLE_HEADER as UINT64,
+    )?;
+    if crc != header.Header.Crc32 {
+        return Err(EFI_CRC_ERROR);
+    }
+
+    header.check(LBA)?;
+    Ok(header)
+}
+
+fn check_entries<R: Read + Seek>(
+    Reader: &mut R,
+    BlockSize: UINT64,
+    Header: &EFI_PARTITION_TABLE_HEADER,
+) -> Result<(), EFI_STATUS> {
+    Reader.seek(SeekFrom::Start(entry_array_offset(BlockSize, Header)?))?;
+    match crc_of(Reader, 0, Header.entry_array_size())? {
+        crc if crc == Header.PartitionEntryArrayCRC32 => Ok(()),
+        _ => Err(EFI_CRC_ERROR),
+    }
+}
+
+fn entry_array_offset(
+    BlockSize: UINT64,
+    Header: &EFI_PARTITION_TABLE_HEADER,
+) -> Result<UINT64, EFI_STATUS> {
+    Header
+        .PartitionEntryLBA
+        .checked_mul(BlockSize)
+        .filter(|offset| offset.checked_add(Header.entry_array_size()).is_some())
+        .ok_or(EFI_VOLUME_CORRUPTED)
+}
+
+/// Continues `Crc` with the next `Length` bytes of `Reader`.
+fn crc_of<R: Read>(
+    Reader: &mut R,
+    mut Crc: UINT32,
+    mut Length: UINT64,
+) -> Result<UINT32, EFI_STATUS> {
+    let mut buffer = [0; 512];
+    while Length > 0 {
+        let len = buffer
+            .len()
+            .min(usize::try_from(Length).unwrap_or(usize::MAX));
+        Reader.read_exact(&mut buffer[..len])?;
+        Crc = crc32_update(Crc, &buffer[..len]);
+        Length -= len as UINT64;
+    }
+
+    Ok(Crc)
+}
+
+/// Iterator over the used entries of a GUID Partition Table, returned by [`Gpt::partitions()`].
+///
+/// Iteration stops after the first error.
+///
+/// [`Gpt::partitions()`]: crate::partition::Gpt::partitions
+pub struct Partitions<'a, R> {
+    gpt: &'a mut Gpt<R>,
+    index: UINT32,
+}
+
+impl<R: Read + Seek> Iterator for Partitions<'_, R> {
+    type Item = Result<(UINT32, EFI_PARTITION_ENTRY), EFI_STATUS>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.gpt.header.NumberOfPartitionEntries {
+            let index = self.index;
+            self.index += 1;
+            match self.gpt.entry(index) {
+                Ok(entry) if entry.is_used() => return Some(Ok((index, entry))),
+                Ok(_) => {}
+                Err(status) => {
+                    self.index = self.gpt.header.NumberOfPartitionEntries;
+                    return Some(Err(status));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::io::Cursor;
+    use crate::partition::{PartitionTable, MASTER_BOOT_RECORD};
+    use crate::status::EFI_END_OF_FILE;
+
+    const BLOCK_SIZE: usize = 512;
+    const BLOCKS: u64 = 64;
+    const ENTRIES: u32 = 16;
+    const ENTRY_BLOCKS: u64 = ENTRIES as u64 * SIZE_OF_EFI_PARTITION_ENTRY as u64 / 512;
+    const BACKUP_LBA: u64 = BLOCKS - 1;
+
+    const DISK_GUID: EFI_GUID = EFI_GUID(
+        0x8F1C5B3A,
+        0x0D4E,
+        0x4A7B,
+        [0x9C, 0x21, 0x5E, 0x6F, 0x70, 0x81, 0x92, 0xA3],
+    );
+    const ROOT_GUID: EFI_GUID = EFI_GUID(
+        0x1B2C3D4E,
+        0x5F60,
+        0x4172,
+        [0x83, 0x94, 0xA5, 0xB6, 0xC7, 0xD8, 0xE9, 0xFA],
+    );
+
+    fn block(Image: &mut [UINT8], LBA: u64) -> &mut [UINT8] {
+        let offset = LBA as usize * BLOCK_SIZE;
+        &mut Image[offset..offset + BLOCK_SIZE]
+    }
+
+    fn entry(Type: EFI_GUID, Unique: EFI_GUID, Start: u64, End: u64, Name: &str) -> [UINT8; 128] {
+        let mut bytes = [0; SIZE_OF_EFI_PARTITION_ENTRY];
+        bytes[0..16].copy_from_slice(&Type.to_bytes());
+        bytes[16..32].copy_from_slice(&Unique.to_bytes());
+        bytes[32..40].copy_from_slice(&Start.to_le_bytes());
+        bytes[40..48].copy_from_slice(&End.to_le_bytes());
+        for (i, c) in Name.encode_utf16().enumerate() {
+            bytes[56 + 2 * i..58 + 2 * i].copy_from_slice(&c.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn write_header(
+        Image: &mut [UINT8],
+        MyLBA: u64,
+        AlternateLBA: u64,
+        EntryLBA: u64,
+        EntryCrc: u32,
+    ) {
+        let mut header = [0; SIZE_OF_EFI_PARTITION_TABLE_HEADER];
+        header[0..8].copy_from_slice(&EFI_PTAB_HEADER_ID.to_le_bytes());
+        header[8..12].copy_from_slice(&EFI_PARTITION_TABLE_HEADER_REVISION.to_le_bytes());
+        header[12..16].copy_from_slice(&(SIZE_OF_EFI_PARTITION_TABLE_HEADER as u32).to_le_bytes());
+        header[24..32].copy_from_slice(&MyLBA.to_le_bytes());
+        header[32..40].copy_from_slice(&AlternateLBA.to_le_bytes());
+        header[40..48].copy_from_slice(&(2 + ENTRY_BLOCKS).to_le_bytes());
+        header[48..56].copy_from_slice(&(BACKUP_LBA - ENTRY_BLOCKS - 1).to_le_bytes());
+        header[56..72].copy_from_slice(&DISK_GUID.to_bytes());
+        header[72..80].copy_from_slice(&EntryLBA.to_le_bytes());
+        header[80..84].copy_from_slice(&ENTRIES.to_le_bytes());
+        header[84..88].copy_from_slice(&(SIZE_OF_EFI_PARTITION_ENTRY as u32).to_le_bytes());
+        header[88..92].copy_from_slice(&EntryCrc.to_le_bytes());
+        let crc = crc32(&header);
+        header[16..20].copy_from_slice(&crc.to_le_bytes());
+
+        block(Image, MyLBA)[..header.len()].copy_from_slice(&header);
+    }
+
+    /// Builds a 64-block disk image with a protective MBR, and primary and backup GUID
+    /// Partition Tables describing an EFI System Partition and a root partition.
+    fn image() -> Vec<UINT8> {
+        let mut image = std::vec![0; BLOCKS as usize * BLOCK_SIZE];
+
+        let mbr = block(&mut image, 0);
+        mbr[446 + 4] = 0xEE;
+        mbr[446 + 8..446 + 12].copy_from_slice(&1u32.to_le_bytes());
+        mbr[446 + 12..446 + 16].copy_from_slice(&(BLOCKS as u32 - 1).to_le_bytes());
+        mbr[510..512].copy_from_slice(&[0x55, 0xAA]);
+
+        let mut entries = std::vec![0; ENTRIES as usize * SIZE_OF_EFI_PARTITION_ENTRY];
+        entries[..128].copy_from_slice(&entry(
+            EFI_PART_TYPE_EFI_SYSTEM_PART_GUID,
+            DISK_GUID,
+            6,
+            29,
+            "EFI system partition",
+        ));
+        entries[256..384].copy_from_slice(&entry(
+            EFI_PART_TYPE_LINUX_ROOT_X86_64_GUID,
+            ROOT_GUID,
+            30,
+            58,
+            "root",
+        ));
+        let entry_crc = crc32(&entries);
+
+        let backup_entries = BACKUP_LBA - ENTRY_BLOCKS;
+        for lba in [2, backup_entries] {
+            let offset = lba as usize * BLOCK_SIZE;
+            image[offset..offset + entries.len()].copy_from_slice(&entries);
+        }
+        write_header(
+            &mut image,
+            PRIMARY_PART_HEADER_LBA,
+            BACKUP_LBA,
+            2,
+            entry_crc,
+        );
+        write_header(
+            &mut image,
+            BACKUP_LBA,
+            PRIMARY_PART_HEADER_LBA,
+            backup_entries,
+            entry_crc,
+        );
+
+        image
+    }
+
+    fn read(Image: &[UINT8]) -> Result<Gpt<Cursor<&[UINT8]>>, EFI_STATUS> {
+        Gpt::read(Cursor::new(Image), BLOCK_SIZE as u32)
+    }
+
+    fn assert_partitions(Gpt: &mut Gpt<Cursor<&[UINT8]>>) {
+        let partitions = Gpt.partitions().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(partitions.len(), 2);
+
+        let (index, esp) = partitions[0];
+        assert_eq!(index, 0);
+        assert!(esp.PartitionTypeGUID == EFI_PART_TYPE_EFI_SYSTEM_PART_GUID);
+        assert_eq!(esp.size_in_blocks(), 24);
+        assert!(esp.name().eq("EFI system partition".chars()));
+
+        let (index, root) = partitions[1];
+        assert_eq!(index, 2);
+        assert!(root.UniquePartitionGUID == ROOT_GUID);
+        assert!(root.name().eq("root".chars()));
+    }
+
+    #[test]
+    fn clean_image() {
+        let image = image();
+        let mut gpt = read(&image).unwrap();
+
+        assert!(!gpt.is_backup());
+        assert_eq!(gpt.header().MyLBA, PRIMARY_PART_HEADER_LBA);
+        assert_eq!(gpt.block_size(), BLOCK_SIZE as u64);
+        assert!(gpt.disk_guid() == DISK_GUID);
+        assert_partitions(&mut gpt);
+
+        let found = gpt.find_by_unique_guid(&ROOT_GUID).unwrap();
+        assert_eq!(found.map(|(index, _)| index), Some(2));
+        let found = gpt.find_by_type(&EFI_PART_TYPE_LINUX_SWAP_GUID).unwrap();
+        assert!(found.is_none());
+        assert_eq!(gpt.entry(ENTRIES).err(), Some(EFI_INVALID_PARAMETER));
+    }
+
+    #[test]
+    fn corrupted_primary_header_uses_backup() {
+        let mut image = image();
+        block(&mut image, PRIMARY_PART_HEADER_LBA)[60] ^= 0xFF;
+
+        assert_eq!(
+            EFI_PARTITION_TABLE_HEADER::parse(
+                block(&mut image, PRIMARY_PART_HEADER_LBA),
+                PRIMARY_PART_HEADER_LBA
+            )
+            .err(),
+            Some(EFI_CRC_ERROR)
+        );
+
+        let mut gpt = read(&image).unwrap();
+        assert!(gpt.is_backup());
+        assert_eq!(gpt.header().MyLBA, BACKUP_LBA);
+        assert_partitions(&mut gpt);
+    }
+
+    #[test]
+    fn corrupted_entry_array_uses_backup() {
+        let mut image = image();
+        block(&mut image, 2)[300] ^= 0x01;
+
+        let mut gpt = read(&image).unwrap();
+        assert!(gpt.is_backup());
+        assert_eq!(gpt.header().MyLBA, BACKUP_LBA);
+        assert_partitions(&mut gpt);
+
+        block(&mut image, BACKUP_LBA - ENTRY_BLOCKS)[300] ^= 0x01;
+        assert_eq!(read(&image).err(), Some(EFI_CRC_ERROR));
+    }
+
+    #[test]
+    fn missing_primary_uses_backup() {
+        let mut image = image();
+        block(&mut image, PRIMARY_PART_HEADER_LBA).fill(0);
+
+        let mut gpt = read(&image).unwrap();
+        assert!(gpt.is_backup());
+        assert_partitions(&mut gpt);
+
+        block(&mut image, BACKUP_LBA).fill(0);
+        assert_eq!(read(&image).err(), Some(EFI_NOT_FOUND));
+    }
+
+    #[test]
+    fn header_fields_are_checked() {
+        let mut image = image();
+        let header = block(&mut image, PRIMARY_PART_HEADER_LBA);
+        assert_eq!(
+            EFI_PARTITION_TABLE_HEADER::parse(header, BACKUP_LBA).err(),
+            Some(EFI_VOLUME_CORRUPTED)
+        );
+        assert_eq!(
+            EFI_PARTITION_TABLE_HEADER::parse(&header[..91], PRIMARY_PART_HEADER_LBA).err(),
+            Some(EFI_INVALID_PARAMETER)
+        );
+        assert_eq!(read(&image[..BLOCK_SIZE]).err(), Some(EFI_END_OF_FILE));
+        assert_eq!(
+            Gpt::read(Cursor::new(&image[..]), 64).err(),
+            Some(EFI_INVALID_PARAMETER)
+        );
+    }
+
+    #[test]
+    fn protective_mbr_leads_to_gpt() {
+        let image = image();
+
+        let mbr = MASTER_BOOT_RECORD::from_bytes(&image).unwrap();
+        assert!(mbr.is_protective());
+
+        match PartitionTable::read(Cursor::new(&image[..]), BLOCK_SIZE as u32) {
+            Ok(PartitionTable::Gpt(gpt)) => assert!(!gpt.is_backup()),
+            _ => panic!("expected a GUID Partition Table"),
+        }
+    }
+
+    #[test]
+    fn truncated_images_do_not_panic() {
+        let image = image();
+        for len in (0..image.len()).step_by(61) {
+            let _ = read(&image[..len]).map(|mut gpt| gpt.partitions().count());
+        }
+    }
+}
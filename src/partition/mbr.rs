@@ -0,0 +1,296 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # Master Boot Record
+//!
+//! This module defines the legacy Master Boot Record, also known as the [`MASTER_BOOT_RECORD`],
+//! and its [`MBR_PARTITION_RECORD`] entries. A GUID Partition Table disk starts with a protective
+//! MBR, which covers the whole disk with a single partition of type [`PMBR_GPT_PARTITION`]. See
+//! [Section 5.2 of the UEFI Specification] for more details.
+//!
+//! [`MASTER_BOOT_RECORD`]: crate::partition::mbr::MASTER_BOOT_RECORD
+//! [`MBR_PARTITION_RECORD`]: crate::partition::mbr::MBR_PARTITION_RECORD
+//! [`PMBR_GPT_PARTITION`]: crate::partition::mbr::PMBR_GPT_PARTITION
+//!
+//! [Section 5.2 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/05_GUID_Partition_Table_Format.html#lba-0-format
+
+use core::ptr;
+
+use crate::io::{Read, Seek, SeekFrom};
+use crate::status::{EFI_INVALID_PARAMETER, EFI_NOT_FOUND};
+use crate::types::{EFI_STATUS, UINT16, UINT32, UINT8};
+
+/// The signature found in the last two bytes of a valid [`MASTER_BOOT_RECORD`].
+///
+/// [`MASTER_BOOT_RECORD`]: crate::partition::mbr::MASTER_BOOT_RECORD
+pub const MBR_SIGNATURE: UINT16 = 0xAA55;
+
+/// The size of a [`MASTER_BOOT_RECORD`] in bytes.
+///
+/// [`MASTER_BOOT_RECORD`]: crate::partition::mbr::MASTER_BOOT_RECORD
+pub const MBR_SIZE: usize = 512;
+
+/// The number of partition records in a [`MASTER_BOOT_RECORD`].
+///
+/// [`MASTER_BOOT_RECORD`]: crate::partition::mbr::MASTER_BOOT_RECORD
+pub const MAX_MBR_PARTITIONS: usize = 4;
+
+/// The [`OSIndicator`] of the partition of a protective MBR covering a GUID Partition Table.
+///
+/// [`OSIndicator`]: ./struct.MBR_PARTITION_RECORD.html#structfield.OSIndicator
+pub const PMBR_GPT_PARTITION: UINT8 = 0xEE;
+
+/// The [`OSIndicator`] of an EFI System Partition on a legacy MBR disk.
+///
+/// [`OSIndicator`]: ./struct.MBR_PARTITION_RECORD.html#structfield.OSIndicator
+pub const EFI_PARTITION: UINT8 = 0xEF;
+
+/// The [`OSIndicator`] of a DOS extended partition.
+///
+/// [`OSIndicator`]: ./struct.MBR_PARTITION_RECORD.html#structfield.OSIndicator
+pub const EXTENDED_DOS_PARTITION: UINT8 = 0x05;
+
+/// The [`OSIndicator`] of a Windows extended partition.
+///
+/// [`OSIndicator`]: ./struct.MBR_PARTITION_RECORD.html#structfield.OSIndicator
+pub const EXTENDED_WINDOWS_PARTITION: UINT8 = 0x0F;
+
+/// A partition record of a [`MASTER_BOOT_RECORD`].
+///
+/// [`MASTER_BOOT_RECORD`]: crate::partition::mbr::MASTER_BOOT_RECORD
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct MBR_PARTITION_RECORD {
+    /// `0x80` indicates that this is the bootable legacy partition. Must be `0` for the partition
+    /// of a protective MBR.
+    pub BootIndicator: UINT8,
+    /// Start of the partition in CHS address format.
+    pub StartHead: UINT8,
+    /// Start of the partition in CHS address format.
+    pub StartSector: UINT8,
+    /// Start of the partition in CHS address format.
+    pub StartTrack: UINT8,
+    /// Type of partition, such as [`PMBR_GPT_PARTITION`] or [`EFI_PARTITION`].
+    ///
+    /// [`PMBR_GPT_PARTITION`]: crate::partition::mbr::PMBR_GPT_PARTITION
+    /// [`EFI_PARTITION`]: crate::partition::mbr::EFI_PARTITION
+    pub OSIndicator: UINT8,
+    /// End of the partition in CHS address format.
+    pub EndHead: UINT8,
+    /// End of the partition in CHS address format.
+    pub EndSector: UINT8,
+    /// End of the partition in CHS address format.
+    pub EndTrack: UINT8,
+    /// Starting LBA of the partition on the disk, in little-endian byte order.
+    pub StartingLBA: [UINT8; 4],
+    /// Size of the partition in LBA units of logical blocks, in little-endian byte order.
+    pub SizeInLBA: [UINT8; 4],
+}
+
+impl MBR_PARTITION_RECORD {
+    /// Returns the starting LBA of the partition.
+    pub fn starting_lba(&self) -> UINT32 {
+        UINT32::from_le_bytes(self.StartingLBA)
+    }
+
+    /// Returns the size of the partition in logical blocks.
+    pub fn size_in_lba(&self) -> UINT32 {
+        UINT32::from_le_bytes(self.SizeInLBA)
+    }
+
+    /// Returns `true` if the record describes a partition.
+    pub fn is_used(&self) -> bool {
+        self.OSIndicator != 0 && self.size_in_lba() != 0
+    }
+
+    /// Returns `true` if the partition is marked as the bootable legacy partition.
+    pub fn is_bootable(&self) -> bool {
+        self.BootIndicator == 0x80
+    }
+
+    /// Returns `true` if the partition is an extended partition holding further logical
+    /// partitions.
+    pub fn is_extended(&self) -> bool {
+        matches!(
+            self.OSIndicator,
+            EXTENDED_DOS_PARTITION | EXTENDED_WINDOWS_PARTITION
+        )
+    }
+}
+
+/// The legacy Master Boot Record, found in the first logical block of a disk.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct MASTER_BOOT_RECORD {
+    /// x86 code used on a non-UEFI system to select a partition and load its first logical block.
+    pub BootStrapCode: [UINT8; 440],
+    /// Unique disk signature, used by some operating systems to identify the disk.
+    pub UniqueMbrSignature: [UINT8; 4],
+    /// Unknown. Unused by UEFI firmware.
+    pub Unknown: [UINT8; 2],
+    /// Array of four legacy MBR partition records.
+    pub Partition: [MBR_PARTITION_RECORD; MAX_MBR_PARTITIONS],
+    /// Set to [`MBR_SIGNATURE`].
+    ///
+    /// [`MBR_SIGNATURE`]: crate::partition::mbr::MBR_SIGNATURE
+    pub Signature: UINT16,
+}
+
+impl MASTER_BOOT_RECORD {
+    /// Parses the Master Boot Record at the start of `Data`.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if `Data` is shorter than [`MBR_SIZE`] and
+    /// [`EFI_NOT_FOUND`] if it does not end with [`MBR_SIGNATURE`].
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`MBR_SIZE`]: crate::partition::mbr::MBR_SIZE
+    /// [`EFI_NOT_FOUND`]: crate::status::EFI_NOT_FOUND
+    /// [`MBR_SIGNATURE`]: crate::partition::mbr::MBR_SIGNATURE
+    pub fn from_bytes(Data: &[UINT8]) -> Result<Self, EFI_STATUS> {
+        if Data.len() < MBR_SIZE {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        // SAFETY: `Data` holds at least `MBR_SIZE` bytes, the size of the structure, and the
+        // structure is valid for any bit pattern.
+        let mbr: Self = unsafe { ptr::read_unaligned(Data.as_ptr().cast()) };
+        if mbr.Signature != MBR_SIGNATURE {
+            return Err(EFI_NOT_FOUND);
+        }
+
+        Ok(mbr)
+    }
+
+    /// Reads the Master Boot Record from the first [`MBR_SIZE`] bytes of a disk.
+    ///
+    /// [`MBR_SIZE`]: crate::partition::mbr::MBR_SIZE
+    pub fn read<R: Read + Seek>(mut Reader: R) -> Result<Self, EFI_STATUS> {
+        let mut bytes = [0; MBR_SIZE];
+        Reader.seek(SeekFrom::Start(0))?;
+        Reader.read_exact(&mut bytes)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Returns `true` if this is a protective MBR, guarding a GUID Partition Table.
+    ///
+    /// This is the case if one of the partitions is of type [`PMBR_GPT_PARTITION`] and starts
+    /// at LBA 1. Hybrid MBRs, which describe some GPT partitions as legacy partitions as well,
+    /// are therefore also considered protective.
+    ///
+    /// [`PMBR_GPT_PARTITION`]: crate::partition::mbr::PMBR_GPT_PARTITION
+    pub fn is_protective(&self) -> bool {
+        self.Partition
+            .iter()
+            .any(|p| p.OSIndicator == PMBR_GPT_PARTITION && p.starting_lba() == 1)
+    }
+
+    /// Returns an iterator over the used partition records and their indices.
+    pub fn partitions(&self) -> impl Iterator<Item = (usize, MBR_PARTITION_RECORD)> + '_ {
+        self.Partition
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, p)| p.is_used())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Cursor;
+    use crate::partition::PartitionTable;
+
+    fn record(Bootable: bool, Type: UINT8, Start: UINT32, Size: UINT32) -> [UINT8; 16] {
+        let mut bytes = [0; 16];
+        bytes[0] = if Bootable { 0x80 } else { 0 };
+        bytes[4] = Type;
+        bytes[8..12].copy_from_slice(&Start.to_le_bytes());
+        bytes[12..16].copy_from_slice(&Size.to_le_bytes());
+        bytes
+    }
+
+    fn mbr(Records: &[[UINT8; 16]]) -> [UINT8; MBR_SIZE] {
+        let mut bytes = [0; MBR_SIZE];
+        for (i, r) in Records.iter().enumerate() {
+            bytes[446 + 16 * i..462 + 16 * i].copy_from_slice(r);
+        }
+        bytes[510..].copy_from_slice(&MBR_SIGNATURE.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn legacy_mbr() {
+        let bytes = mbr(&[
+            record(true, 0x0C, 2048, 1024),
+            [0; 16],
+            record(false, EXTENDED_WINDOWS_PARTITION, 4096, 8192),
+        ]);
+        let mbr = MASTER_BOOT_RECORD::from_bytes(&bytes).unwrap();
+
+        assert!(!mbr.is_protective());
+        let mut partitions = mbr.partitions();
+        let (index, first) = partitions.next().unwrap();
+        assert_eq!(index, 0);
+        assert!(first.is_bootable());
+        assert_eq!((first.starting_lba(), first.size_in_lba()), (2048, 1024));
+        let (index, second) = partitions.next().unwrap();
+        assert_eq!(index, 2);
+        assert!(second.is_extended());
+        assert!(partitions.next().is_none());
+
+        match PartitionTable::read(Cursor::new(&bytes[..]), 512) {
+            Ok(PartitionTable::Mbr(mbr)) => assert_eq!(mbr.partitions().count(), 2),
+            _ => panic!("expected a legacy MBR"),
+        }
+    }
+
+    #[test]
+    fn protective_and_hybrid_mbr() {
+        let protective = mbr(&[record(false, PMBR_GPT_PARTITION, 1, 0xFFFF_FFFF)]);
+        assert!(MASTER_BOOT_RECORD::from_bytes(&protective)
+            .unwrap()
+            .is_protective());
+
+        let hybrid = mbr(&[
+            record(false, PMBR_GPT_PARTITION, 1, 2047),
+            record(true, EFI_PARTITION, 2048, 1024),
+        ]);
+        assert!(MASTER_BOOT_RECORD::from_bytes(&hybrid)
+            .unwrap()
+            .is_protective());
+
+        let misplaced = mbr(&[record(false, PMBR_GPT_PARTITION, 2, 2047)]);
+        assert!(!MASTER_BOOT_RECORD::from_bytes(&misplaced)
+            .unwrap()
+            .is_protective());
+    }
+
+    #[test]
+    fn invalid_mbr() {
+        let mut bytes = mbr(&[]);
+        assert_eq!(
+            MASTER_BOOT_RECORD::from_bytes(&bytes[..MBR_SIZE - 1]).err(),
+            Some(EFI_INVALID_PARAMETER)
+        );
+
+        bytes[511] = 0;
+        assert_eq!(
+            MASTER_BOOT_RECORD::from_bytes(&bytes).err(),
+            Some(EFI_NOT_FOUND)
+        );
+    }
+}
@@ -0,0 +1,105 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # Partition Tables
+//!
+//! This module parses the partition tables a UEFI firmware recognizes on block devices: the GUID
+//! Partition Table in [`gpt`], and the legacy and protective Master Boot Records in [`mbr`]. See
+//! [Section 5 of the UEFI Specification] for more details.
+//!
+//! The parsers read through the [`Read`] and [`Seek`] traits, so the same code runs on a
+//! [`DiskStream`] at boot time and on a [`Cursor`] over a disk image on the host.
+//!
+//! [`gpt`]: crate::partition::gpt
+//! [`mbr`]: crate::partition::mbr
+//! [`Read`]: crate::io::Read
+//! [`Seek`]: crate::io::Seek
+//! [`DiskStream`]: crate::protocols::media::disk_io::DiskStream
+//! [`Cursor`]: crate::io::Cursor
+//!
+//! [Section 5 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/05_GUID_Partition_Table_Format.html
+
+use crate::io::{Read, Seek};
+use crate::types::{EFI_STATUS, UINT32, UINT8};
+
+pub mod gpt;
+pub mod mbr;
+
+pub use gpt::Gpt;
+pub use mbr::MASTER_BOOT_RECORD;
+
+/// The partition table found on a disk by [`PartitionTable::read()`].
+///
+/// [`PartitionTable::read()`]: crate::partition::PartitionTable::read
+#[allow(clippy::large_enum_variant)]
+pub enum PartitionTable<R> {
+    /// A GUID Partition Table, possibly recovered from its backup copy.
+    Gpt(Gpt<R>),
+    /// A legacy Master Boot Record that does not protect a GUID Partition Table.
+    Mbr(MASTER_BOOT_RECORD),
+}
+
+impl<R: Read + Seek> PartitionTable<R> {
+    /// Reads the partition table of a disk with blocks of `BlockSize` bytes.
+    ///
+    /// A GUID Partition Table is looked for unless the disk starts with a valid legacy MBR that
+    /// is not a protective MBR. Errors are those of [`Gpt::read()`].
+    ///
+    /// [`Gpt::read()`]: crate::partition::Gpt::read
+    pub fn read(mut Reader: R, BlockSize: UINT32) -> Result<Self, EFI_STATUS> {
+        match MASTER_BOOT_RECORD::read(&mut Reader) {
+            Ok(mbr) if !mbr.is_protective() => Ok(Self::Mbr(mbr)),
+            _ => Gpt::read(Reader, BlockSize).map(Self::Gpt),
+        }
+    }
+}
+
+const CRC32_TABLE: [UINT32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as UINT32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB88320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Computes the standard 32-bit CRC of `Data`, as used by the [`EFI_TABLE_HEADER`] and the GUID
+/// Partition Table.
+///
+/// [`EFI_TABLE_HEADER`]: crate::tables::EFI_TABLE_HEADER
+pub fn crc32(Data: &[UINT8]) -> UINT32 {
+    crc32_update(0, Data)
+}
+
+/// Continues the 32-bit CRC `Crc` of some data with the bytes of `Data`, so that
+/// `crc32_update(crc32(a), b)` equals the CRC of `a` followed by `b`.
+pub fn crc32_update(Crc: UINT32, Data: &[UINT8]) -> UINT32 {
+    !Data.iter().fold(!Crc, |crc, &byte| {
+        CRC32_TABLE[((crc ^ byte as UINT32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
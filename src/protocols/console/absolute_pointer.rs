@@ -0,0 +1,232 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Absolute Pointer Protocol
+//!
+//! This module defines the Absolute Pointer Protocol, also known as the
+//! [`EFI_ABSOLUTE_POINTER_PROTOCOL`], which reports the position and button state of an
+//! absolute pointing device such as a touchscreen or a digitizer. See
+//! [Section 12.7 of the UEFI Specification] for more details.
+//!
+//! [`EFI_ABSOLUTE_POINTER_PROTOCOL`]: crate::protocols::console::EFI_ABSOLUTE_POINTER_PROTOCOL
+//!
+//! [Section 12.7 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/12_Protocols_Console_Support.html#absolute-pointer-protocol
+
+use crate::protocols::Protocol;
+use crate::status::{EFI_NOT_READY, EFI_SUCCESS};
+use crate::types::{BOOLEAN, EFI_EVENT, EFI_GUID, EFI_STATUS, UINT32, UINT64};
+
+/// GUID for the [`EFI_ABSOLUTE_POINTER_PROTOCOL`].
+///
+/// [`EFI_ABSOLUTE_POINTER_PROTOCOL`]: crate::protocols::console::EFI_ABSOLUTE_POINTER_PROTOCOL
+pub const EFI_ABSOLUTE_POINTER_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0x8D59D32B,
+    0xC655,
+    0x4AE9,
+    [0x9B, 0x15, 0xF2, 0x59, 0x04, 0x99, 0x2A, 0x43],
+);
+
+/// The device supports an alternate button input.
+pub const EFI_ABSP_SupportsAltActive: UINT32 = 0x00000001;
+/// The device returns pressure data in [`CurrentZ`].
+///
+/// [`CurrentZ`]: ./struct.EFI_ABSOLUTE_POINTER_STATE.html#structfield.CurrentZ
+pub const EFI_ABSP_SupportsPressureAsZ: UINT32 = 0x00000002;
+
+/// The touch sensor is active.
+pub const EFI_ABSP_TouchActive: UINT32 = 0x00000001;
+/// The alternate button is active.
+pub const EFI_ABS_AltActive: UINT32 = 0x00000002;
+
+/// Provides services that allow information about an absolute pointer device to be retrieved.
+///
+/// The [`EFI_ABSOLUTE_POINTER_PROTOCOL`] provides a set of services for a pointer device that
+/// can be used as an input device from an application written to this specification. The
+/// services include the ability to reset the pointer device, retrieve the state of the pointer
+/// device, and retrieve the capabilities of the pointer device.
+///
+/// [`EFI_ABSOLUTE_POINTER_PROTOCOL`]: crate::protocols::console::EFI_ABSOLUTE_POINTER_PROTOCOL
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_ABSOLUTE_POINTER_PROTOCOL {
+    Reset: EFI_ABSOLUTE_POINTER_RESET,
+    GetState: EFI_ABSOLUTE_POINTER_GET_STATE,
+    /// Event to use with [`EFI_BOOT_SERVICES.WaitForEvent()`] to wait for input from the pointer
+    /// device.
+    ///
+    /// [`EFI_BOOT_SERVICES.WaitForEvent()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-waitforevent
+    pub WaitForInput: EFI_EVENT,
+    /// Pointer to [`EFI_ABSOLUTE_POINTER_MODE`] data.
+    ///
+    /// [`EFI_ABSOLUTE_POINTER_MODE`]: crate::protocols::console::absolute_pointer::EFI_ABSOLUTE_POINTER_MODE
+    pub Mode: *mut EFI_ABSOLUTE_POINTER_MODE,
+}
+
+unsafe impl Protocol for EFI_ABSOLUTE_POINTER_PROTOCOL {
+    const GUID: EFI_GUID = EFI_ABSOLUTE_POINTER_PROTOCOL_GUID;
+}
+
+impl EFI_ABSOLUTE_POINTER_PROTOCOL {
+    /// Resets the pointer device hardware.
+    ///
+    /// The [`Reset()`] function resets the pointer device hardware. As part of initialization
+    /// process, the firmware/device will make a quick but reasonable attempt to verify that the
+    /// device is functioning. If the `ExtendedVerification` flag is `TRUE` the firmware may take
+    /// an extended amount of time to verify the device is operating on reset. Otherwise the reset
+    /// operation is to occur as quickly as possible.
+    ///
+    /// # Parameters
+    ///
+    /// ## `ExtendedVerification`
+    ///
+    /// Indicates that the driver may perform a more exhaustive verification operation of the
+    /// device during reset.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the device was reset.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device is not functioning correctly and could not be reset.
+    ///
+    /// [`Reset()`]: ./struct.EFI_ABSOLUTE_POINTER_PROTOCOL.html#method.Reset
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    pub unsafe fn Reset(&mut self, ExtendedVerification: BOOLEAN) -> EFI_STATUS {
+        (self.Reset)(self, ExtendedVerification)
+    }
+
+    /// Retrieves the current state of a pointer device.
+    ///
+    /// The [`GetState()`] function retrieves the current state of a pointer device. This includes
+    /// information on the active state associated with the pointer device and the current
+    /// position of the axes associated with the pointer device. If the state of the pointer
+    /// device has not changed since the last call to [`GetState()`], then [`EFI_NOT_READY`] is
+    /// returned. If the state of the pointer device has changed since the last call, then the
+    /// state information is placed in `State`, and [`EFI_SUCCESS`] is returned.
+    ///
+    /// # Parameters
+    ///
+    /// ## `State`
+    ///
+    /// A pointer to the state information on the pointer device.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the state of the pointer device was returned in `State`.
+    ///
+    /// [`EFI_NOT_READY`] - the state of the pointer device has not changed since the last call
+    /// to [`GetState()`].
+    ///
+    /// [`EFI_DEVICE_ERROR`] - a device error occurred while attempting to retrieve the pointer
+    /// device's current state.
+    ///
+    /// [`GetState()`]: ./struct.EFI_ABSOLUTE_POINTER_PROTOCOL.html#method.GetState
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_NOT_READY`]: crate::status::EFI_NOT_READY
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    pub unsafe fn GetState(&mut self, State: *mut EFI_ABSOLUTE_POINTER_STATE) -> EFI_STATUS {
+        (self.GetState)(self, State)
+    }
+
+    /// Returns the capabilities of the pointer device.
+    pub unsafe fn mode(&self) -> &EFI_ABSOLUTE_POINTER_MODE {
+        &*self.Mode
+    }
+
+    /// Returns the state of the pointer device, or `None` if it has not changed since the last
+    /// call.
+    pub unsafe fn get_state(&mut self) -> Result<Option<EFI_ABSOLUTE_POINTER_STATE>, EFI_STATUS> {
+        let mut state = EFI_ABSOLUTE_POINTER_STATE::default();
+        match self.GetState(&mut state) {
+            EFI_SUCCESS => Ok(Some(state)),
+            EFI_NOT_READY => Ok(None),
+            status => Err(status),
+        }
+    }
+}
+
+/// The capabilities of an absolute pointer device.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_ABSOLUTE_POINTER_MODE {
+    /// The Absolute Minimum of the device on the x-axis.
+    pub AbsoluteMinX: UINT64,
+    /// The Absolute Minimum of the device on the y-axis.
+    pub AbsoluteMinY: UINT64,
+    /// The Absolute Minimum of the device on the z-axis.
+    pub AbsoluteMinZ: UINT64,
+    /// The Absolute Maximum of the device on the x-axis. If 0, then the pointer device does not
+    /// support an x-axis.
+    pub AbsoluteMaxX: UINT64,
+    /// The Absolute Maximum of the device on the y-axis. If 0, then the pointer device does not
+    /// support a y-axis.
+    pub AbsoluteMaxY: UINT64,
+    /// The Absolute Maximum of the device on the z-axis. If 0, then the pointer device does not
+    /// support a z-axis.
+    pub AbsoluteMaxZ: UINT64,
+    /// A combination of [`EFI_ABSP_SupportsAltActive`] and [`EFI_ABSP_SupportsPressureAsZ`].
+    ///
+    /// [`EFI_ABSP_SupportsAltActive`]: crate::protocols::console::absolute_pointer::EFI_ABSP_SupportsAltActive
+    /// [`EFI_ABSP_SupportsPressureAsZ`]: crate::protocols::console::absolute_pointer::EFI_ABSP_SupportsPressureAsZ
+    pub Attributes: UINT32,
+}
+
+/// The state of an absolute pointer device.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub struct EFI_ABSOLUTE_POINTER_STATE {
+    /// The unsigned position of the activation on the x-axis. If the [`AbsoluteMinX`] and the
+    /// [`AbsoluteMaxX`] fields of the [`EFI_ABSOLUTE_POINTER_MODE`] are both 0, then this field
+    /// is ignored.
+    ///
+    /// [`AbsoluteMinX`]: ./struct.EFI_ABSOLUTE_POINTER_MODE.html#structfield.AbsoluteMinX
+    /// [`AbsoluteMaxX`]: ./struct.EFI_ABSOLUTE_POINTER_MODE.html#structfield.AbsoluteMaxX
+    /// [`EFI_ABSOLUTE_POINTER_MODE`]: crate::protocols::console::absolute_pointer::EFI_ABSOLUTE_POINTER_MODE
+    pub CurrentX: UINT64,
+    /// The unsigned position of the activation on the y-axis. If the [`AbsoluteMinY`] and the
+    /// [`AbsoluteMaxY`] fields of the [`EFI_ABSOLUTE_POINTER_MODE`] are both 0, then this field
+    /// is ignored.
+    ///
+    /// [`AbsoluteMinY`]: ./struct.EFI_ABSOLUTE_POINTER_MODE.html#structfield.AbsoluteMinY
+    /// [`AbsoluteMaxY`]: ./struct.EFI_ABSOLUTE_POINTER_MODE.html#structfield.AbsoluteMaxY
+    /// [`EFI_ABSOLUTE_POINTER_MODE`]: crate::protocols::console::absolute_pointer::EFI_ABSOLUTE_POINTER_MODE
+    pub CurrentY: UINT64,
+    /// The unsigned position of the activation on the z-axis, or the pressure if
+    /// [`EFI_ABSP_SupportsPressureAsZ`] is set. If the [`AbsoluteMinZ`] and the [`AbsoluteMaxZ`]
+    /// fields of the [`EFI_ABSOLUTE_POINTER_MODE`] are both 0, then this field is ignored.
+    ///
+    /// [`EFI_ABSP_SupportsPressureAsZ`]: crate::protocols::console::absolute_pointer::EFI_ABSP_SupportsPressureAsZ
+    /// [`AbsoluteMinZ`]: ./struct.EFI_ABSOLUTE_POINTER_MODE.html#structfield.AbsoluteMinZ
+    /// [`AbsoluteMaxZ`]: ./struct.EFI_ABSOLUTE_POINTER_MODE.html#structfield.AbsoluteMaxZ
+    /// [`EFI_ABSOLUTE_POINTER_MODE`]: crate::protocols::console::absolute_pointer::EFI_ABSOLUTE_POINTER_MODE
+    pub CurrentZ: UINT64,
+    /// A combination of [`EFI_ABSP_TouchActive`] and [`EFI_ABS_AltActive`].
+    ///
+    /// [`EFI_ABSP_TouchActive`]: crate::protocols::console::absolute_pointer::EFI_ABSP_TouchActive
+    /// [`EFI_ABS_AltActive`]: crate::protocols::console::absolute_pointer::EFI_ABS_AltActive
+    pub ActiveButtons: UINT32,
+}
+
+type EFI_ABSOLUTE_POINTER_RESET = extern "efiapi" fn(
+    This: *mut EFI_ABSOLUTE_POINTER_PROTOCOL,
+    ExtendedVerification: BOOLEAN,
+) -> EFI_STATUS;
+
+type EFI_ABSOLUTE_POINTER_GET_STATE = extern "efiapi" fn(
+    This: *mut EFI_ABSOLUTE_POINTER_PROTOCOL,
+    State: *mut EFI_ABSOLUTE_POINTER_STATE,
+) -> EFI_STATUS;
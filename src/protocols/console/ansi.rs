@@ -0,0 +1,828 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # ANSI Escape Sequences
+//!
+//! This module provides [`AnsiWriter`], which interprets the ANSI (ECMA-48 and VT100) escape
+//! sequences in text written to a [`ConsoleSink`], such as a [`TextOutputSink`] over the
+//! [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`], so that output formatted for terminals renders on UEFI
+//! consoles. Colors and bold text are mapped to [`SetAttribute()`] with the `EFI_*` colors,
+//! cursor movement to [`SetCursorPosition()`], and erasing the screen to [`ClearScreen()`].
+//!
+//! The following sequences are interpreted; other well-formed sequences are dropped:
+//!
+//! | Sequence                    | Effect                                                   |
+//! | --------------------------- | -------------------------------------------------------- |
+//! | `CSI n m`                   | Select Graphic Rendition: colors, bold, reverse, reset   |
+//! | `CSI n A` / `B` / `C` / `D` | Move the cursor up, down, forward or back                |
+//! | `CSI n E` / `F`             | Move the cursor to the start of a following or preceding line |
+//! | `CSI n G`                   | Move the cursor to a column                              |
+//! | `CSI n ; m H` / `f`         | Move the cursor to a row and column                      |
+//! | `CSI n J`                   | Erase part of or the whole screen                        |
+//! | `CSI n K`                   | Erase part of or the whole line                          |
+//! | `CSI s` / `ESC 7`           | Save the cursor position                                 |
+//! | `CSI u` / `ESC 8`           | Restore the cursor position                              |
+//! | `CSI ? 25 h` / `l`          | Show or hide the cursor                                  |
+//! | `ESC c`                     | Reset the attribute and clear the screen                 |
+//!
+//! [`AnsiWriter`]: crate::protocols::console::ansi::AnsiWriter
+//! [`ConsoleSink`]: crate::protocols::console::multiplex::ConsoleSink
+//! [`TextOutputSink`]: crate::protocols::console::multiplex::TextOutputSink
+//! [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL
+//! [`SetAttribute()`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL::SetAttribute
+//! [`SetCursorPosition()`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL::SetCursorPosition
+//! [`ClearScreen()`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL::ClearScreen
+
+use core::fmt;
+
+use crate::protocols::console::multiplex::{ConsoleSink, DEFAULT_CONSOLE_ATTRIBUTE};
+use crate::protocols::console::simple_text_output::{EFI_BRIGHT, EFI_TEXT_ATTR};
+use crate::status::EFI_UNSUPPORTED;
+use crate::types::{EFI_STATUS, UINTN};
+
+/// The maximum number of parameters of a control sequence; further ones are ignored.
+pub const ANSI_MAX_PARAMETERS: usize = 16;
+
+/// The escape character starting every sequence.
+const ESC: char = '\u{1B}';
+
+/// Spaces written to erase parts of the screen.
+const SPACES: &str = "                                ";
+
+/// Converts between an ANSI color number and an `EFI_*` color, from [`EFI_BLACK`] to
+/// [`EFI_LIGHTGRAY`].
+///
+/// ANSI colors have red in bit 0 and blue in bit 2, `EFI_*` colors the other way round, so the
+/// conversion is the same in both directions.
+///
+/// [`EFI_BLACK`]: crate::protocols::console::simple_text_output::EFI_BLACK
+/// [`EFI_LIGHTGRAY`]: crate::protocols::console::simple_text_output::EFI_LIGHTGRAY
+pub const fn ansi_color(Color: UINTN) -> UINTN {
+    (Color & 0b010) | ((Color & 0b001) << 2) | ((Color & 0b100) >> 2)
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum State {
+    Ground,
+    Escape,
+    EscapeIntermediate,
+    Csi,
+    Osc,
+    OscEscape,
+}
+
+/// Interprets ANSI escape sequences in the text written to a [`ConsoleSink`].
+///
+/// Text is passed to the sink as it is, except for the escape sequences, which are turned into
+/// calls to the sink. A sequence may be split over several writes. Through
+/// [`core::fmt::Write`], line feeds are also translated to carriage return and line feed
+/// pairs, as text from Unix tools has line feeds only.
+///
+/// Moving the cursor relative to its position and erasing need the sink to report its size
+/// and cursor position; where it does not, or where the sink does not support an operation, the
+/// sequence has no effect.
+///
+/// [`ConsoleSink`]: crate::protocols::console::multiplex::ConsoleSink
+pub struct AnsiWriter<W> {
+    sink: W,
+    state: State,
+    parameters: [UINTN; ANSI_MAX_PARAMETERS],
+    count: usize,
+    private: bool,
+    foreground: UINTN,
+    background: UINTN,
+    bold: bool,
+    reverse: bool,
+    saved: Option<(UINTN, UINTN)>,
+}
+
+impl<W: ConsoleSink> AnsiWriter<W> {
+    /// Creates an interpreter writing to `Sink`, which is assumed to have the
+    /// [`DEFAULT_CONSOLE_ATTRIBUTE`].
+    ///
+    /// [`DEFAULT_CONSOLE_ATTRIBUTE`]: crate::protocols::console::multiplex::DEFAULT_CONSOLE_ATTRIBUTE
+    pub fn new(Sink: W) -> Self {
+        Self {
+            sink: Sink,
+            state: State::Ground,
+            parameters: [0; ANSI_MAX_PARAMETERS],
+            count: 0,
+            private: false,
+            foreground: DEFAULT_CONSOLE_ATTRIBUTE & 0x0F,
+            background: DEFAULT_CONSOLE_ATTRIBUTE >> 4,
+            bold: false,
+            reverse: false,
+            saved: None,
+        }
+    }
+
+    /// Returns the sink.
+    pub fn get_ref(&self) -> &W {
+        &self.sink
+    }
+
+    /// Returns the sink mutably.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.sink
+    }
+
+    /// Returns the sink.
+    pub fn into_inner(self) -> W {
+        self.sink
+    }
+
+    /// Returns the attribute selected by the escape sequences written so far.
+    pub fn attribute(&self) -> UINTN {
+        let foreground = self.foreground | if self.bold { EFI_BRIGHT } else { 0 };
+        if self.reverse {
+            EFI_TEXT_ATTR(self.background, foreground & 0x07)
+        } else {
+            EFI_TEXT_ATTR(foreground, self.background)
+        }
+    }
+
+    /// Writes `String`, interpreting its escape sequences.
+    ///
+    /// Stops at the first operation the sink fails, other than with [`EFI_UNSUPPORTED`].
+    ///
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    pub fn output_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+        let mut text = 0;
+        for (index, char) in String.char_indices() {
+            if self.state == State::Ground && char != ESC {
+                continue;
+            }
+
+            if text < index {
+                self.sink.output_string(&String[text..index])?;
+            }
+            text = index + char.len_utf8();
+            self.step(char)?;
+        }
+
+        if self.state == State::Ground && text < String.len() {
+            self.sink.output_string(&String[text..])?;
+        }
+
+        Ok(())
+    }
+
+    /// Advances the state machine over a character that is not plain text.
+    fn step(&mut self, Char: char) -> Result<(), EFI_STATUS> {
+        match (self.state, Char) {
+            (State::Osc, ESC) => {
+                self.state = State::OscEscape;
+                Ok(())
+            }
+            (_, ESC) => {
+                self.state = State::Escape;
+                Ok(())
+            }
+            (State::Escape, '[') => {
+                self.state = State::Csi;
+                self.parameters = [0; ANSI_MAX_PARAMETERS];
+                self.count = 0;
+                self.private = false;
+                Ok(())
+            }
+            (State::Escape, ']') => {
+                self.state = State::Osc;
+                Ok(())
+            }
+            (State::Escape, '\u{20}'..='\u{2F}') => {
+                self.state = State::EscapeIntermediate;
+                Ok(())
+            }
+            (State::Escape, _) => {
+                self.state = State::Ground;
+                self.escape(Char)
+            }
+            (State::EscapeIntermediate, '\u{20}'..='\u{2F}') => Ok(()),
+            (State::EscapeIntermediate, _) => {
+                self.state = State::Ground;
+                Ok(())
+            }
+            (State::Csi, '0'..='9') => {
+                let index = self.count.min(ANSI_MAX_PARAMETERS - 1);
+                let parameter = &mut self.parameters[index];
+                *parameter = parameter
+                    .saturating_mul(10)
+                    .saturating_add(Char as UINTN - '0' as UINTN);
+                Ok(())
+            }
+            (State::Csi, ';' | ':') => {
+                self.count += 1;
+                Ok(())
+            }
+            (State::Csi, '<'..='?') => {
+                self.private = true;
+                Ok(())
+            }
+            (State::Csi, '\u{40}'..='\u{7E}') => {
+                self.state = State::Ground;
+                self.count = (self.count + 1).min(ANSI_MAX_PARAMETERS);
+                if self.private {
+                    self.private_sequence(Char)
+                } else {
+                    self.control_sequence(Char)
+                }
+            }
+            (State::Csi, '\u{20}'..='\u{2F}') => Ok(()),
+            (State::Csi, _) => {
+                self.state = State::Ground;
+                Ok(())
+            }
+            (State::Osc, '\u{7}') => {
+                self.state = State::Ground;
+                Ok(())
+            }
+            (State::Osc, _) => Ok(()),
+            (State::OscEscape, '\\') => {
+                self.state = State::Ground;
+                Ok(())
+            }
+            (State::OscEscape, _) => {
+                self.state = State::Osc;
+                Ok(())
+            }
+            (State::Ground, _) => Ok(()),
+        }
+    }
+
+    /// Returns parameter `Index`, or `Fallback` if it is missing or zero.
+    fn parameter(&self, Index: usize, Fallback: UINTN) -> UINTN {
+        match self.parameters[..self.count].get(Index) {
+            Some(&parameter) if parameter != 0 => parameter,
+            _ => Fallback,
+        }
+    }
+
+    /// Interprets `ESC Final`.
+    fn escape(&mut self, Final: char) -> Result<(), EFI_STATUS> {
+        match Final {
+            '7' => {
+                self.saved = self.sink.cursor_position();
+                Ok(())
+            }
+            '8' => self.restore_cursor(),
+            'c' => {
+                self.reset_attribute();
+                supported(self.sink.set_attribute(self.attribute()))?;
+                supported(self.sink.clear_screen())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Interprets `CSI ? Parameters Final`.
+    fn private_sequence(&mut self, Final: char) -> Result<(), EFI_STATUS> {
+        match (self.parameter(0, 0), Final) {
+            (25, 'h') => supported(self.sink.enable_cursor(true)),
+            (25, 'l') => supported(self.sink.enable_cursor(false)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Interprets `CSI Parameters Final`.
+    fn control_sequence(&mut self, Final: char) -> Result<(), EFI_STATUS> {
+        let count = self.parameter(0, 1);
+        match Final {
+            'm' => self.select_graphic_rendition(),
+            'A' => self.move_cursor(|(column, row), _| (column, row.saturating_sub(count))),
+            'B' => self.move_cursor(|(column, row), _| (column, row.saturating_add(count))),
+            'C' => self.move_cursor(|(column, row), _| (column.saturating_add(count), row)),
+            'D' => self.move_cursor(|(column, row), _| (column.saturating_sub(count), row)),
+            'E' => self.move_cursor(|(_, row), _| (0, row.saturating_add(count))),
+            'F' => self.move_cursor(|(_, row), _| (0, row.saturating_sub(count))),
+            'G' => self.move_cursor(|(_, row), _| (count - 1, row)),
+            'H' | 'f' => {
+                let (row, column) = (self.parameter(0, 1), self.parameter(1, 1));
+                self.move_cursor(|_, _| (column - 1, row - 1))
+            }
+            'J' => match self.parameter(0, 0) {
+                0 => self.erase(|(column, row), (columns, rows)| {
+                    (row * columns + column, rows * columns)
+                }),
+                1 => self.erase(|(column, row), (columns, _)| (0, row * columns + column + 1)),
+                2 | 3 => {
+                    let position = self.sink.cursor_position();
+                    supported(self.sink.clear_screen())?;
+                    match position {
+                        Some((column, row)) => {
+                            supported(self.sink.set_cursor_position(column, row))
+                        }
+                        None => Ok(()),
+                    }
+                }
+                _ => Ok(()),
+            },
+            'K' => match self.parameter(0, 0) {
+                0 => self.erase(|(column, row), (columns, _)| {
+                    (row * columns + column, (row + 1) * columns)
+                }),
+                1 => self.erase(|(column, row), (columns, _)| {
+                    (row * columns, row * columns + column + 1)
+                }),
+                2 => self.erase(|(_, row), (columns, _)| (row * columns, (row + 1) * columns)),
+                _ => Ok(()),
+            },
+            's' => {
+                self.saved = self.sink.cursor_position();
+                Ok(())
+            }
+            'u' => self.restore_cursor(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Interprets the parameters of `CSI Parameters m`.
+    fn select_graphic_rendition(&mut self) -> Result<(), EFI_STATUS> {
+        if self.count == 0 {
+            self.count = 1;
+        }
+
+        for index in 0..self.count {
+            match self.parameters[index] {
+                0 => self.reset_attribute(),
+                1 => self.bold = true,
+                22 => self.bold = false,
+                7 => self.reverse = true,
+                27 => self.reverse = false,
+                parameter @ 30..=37 => self.foreground = ansi_color(parameter - 30),
+                39 => self.foreground = DEFAULT_CONSOLE_ATTRIBUTE & 0x0F,
+                parameter @ 40..=47 => self.background = ansi_color(parameter - 40),
+                49 => self.background = DEFAULT_CONSOLE_ATTRIBUTE >> 4,
+                parameter @ 90..=97 => {
+                    self.foreground = ansi_color(parameter - 90) | EFI_BRIGHT;
+                }
+                // UEFI consoles have no bright backgrounds.
+                parameter @ 100..=107 => self.background = ansi_color(parameter - 100),
+                _ => {}
+            }
+        }
+
+        supported(self.sink.set_attribute(self.attribute()))
+    }
+
+    fn reset_attribute(&mut self) {
+        self.foreground = DEFAULT_CONSOLE_ATTRIBUTE & 0x0F;
+        self.background = DEFAULT_CONSOLE_ATTRIBUTE >> 4;
+        self.bold = false;
+        self.reverse = false;
+    }
+
+    fn restore_cursor(&mut self) -> Result<(), EFI_STATUS> {
+        match self.saved {
+            Some((column, row)) => supported(self.sink.set_cursor_position(column, row)),
+            None => Ok(()),
+        }
+    }
+
+    /// Moves the cursor to the position `Target` computes from the cursor position and the size,
+    /// clamped to the screen.
+    fn move_cursor(
+        &mut self,
+        Target: impl FnOnce((UINTN, UINTN), (UINTN, UINTN)) -> (UINTN, UINTN),
+    ) -> Result<(), EFI_STATUS> {
+        let (position, (columns, rows)) = match (self.sink.cursor_position(), self.sink.size()) {
+            (Some(position), Some(size)) if size.0 > 0 && size.1 > 0 => (position, size),
+            _ => return Ok(()),
+        };
+
+        let (column, row) = Target(position, (columns, rows));
+        supported(
+            self.sink
+                .set_cursor_position(column.min(columns - 1), row.min(rows - 1)),
+        )
+    }
+
+    /// Writes spaces over the cells from the first to before the second index that `Range`
+    /// computes from the cursor position and the size, counting row by row, then moves the
+    /// cursor back.
+    fn erase(
+        &mut self,
+        Range: impl FnOnce((UINTN, UINTN), (UINTN, UINTN)) -> (UINTN, UINTN),
+    ) -> Result<(), EFI_STATUS> {
+        let (position, (columns, rows)) = match (self.sink.cursor_position(), self.sink.size()) {
+            (Some(position), Some(size)) if size.0 > 0 && size.1 > 0 => (position, size),
+            _ => return Ok(()),
+        };
+
+        // Writing the last cell of the screen would scroll it.
+        let (start, end) = Range(position, (columns, rows));
+        let end = end.min(columns * rows - 1);
+        if start < end {
+            supported(
+                self.sink
+                    .set_cursor_position(start % columns, start / columns),
+            )?;
+            let mut remaining = end - start;
+            while remaining > 0 {
+                let count = remaining.min(SPACES.len());
+                self.sink.output_string(&SPACES[..count])?;
+                remaining -= count;
+            }
+        }
+
+        supported(self.sink.set_cursor_position(position.0, position.1))
+    }
+}
+
+impl<W: ConsoleSink> ConsoleSink for AnsiWriter<W> {
+    fn output_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+        AnsiWriter::output_string(self, String)
+    }
+
+    fn test_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+        self.sink.test_string(String)
+    }
+
+    fn set_attribute(&mut self, Attribute: UINTN) -> Result<(), EFI_STATUS> {
+        self.sink.set_attribute(Attribute)
+    }
+
+    fn set_cursor_position(&mut self, Column: UINTN, Row: UINTN) -> Result<(), EFI_STATUS> {
+        self.sink.set_cursor_position(Column, Row)
+    }
+
+    fn clear_screen(&mut self) -> Result<(), EFI_STATUS> {
+        self.sink.clear_screen()
+    }
+
+    fn enable_cursor(&mut self, Visible: bool) -> Result<(), EFI_STATUS> {
+        self.sink.enable_cursor(Visible)
+    }
+
+    fn flush(&mut self) -> Result<(), EFI_STATUS> {
+        self.sink.flush()
+    }
+
+    fn size(&mut self) -> Option<(UINTN, UINTN)> {
+        self.sink.size()
+    }
+
+    fn cursor_position(&mut self) -> Option<(UINTN, UINTN)> {
+        self.sink.cursor_position()
+    }
+}
+
+impl<W: ConsoleSink> fmt::Write for AnsiWriter<W> {
+    /// Writes `s`, interpreting its escape sequences and translating line feeds to carriage
+    /// return and line feed pairs.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut lines = s.split('\n');
+        if let Some(line) = lines.next() {
+            self.output_string(line).map_err(|_| fmt::Error)?;
+        }
+        for line in lines {
+            self.output_string("\r\n").map_err(|_| fmt::Error)?;
+            self.output_string(line).map_err(|_| fmt::Error)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Ignores [`EFI_UNSUPPORTED`], as a terminal ignores what it cannot do.
+///
+/// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+fn supported(Result: Result<(), EFI_STATUS>) -> Result<(), EFI_STATUS> {
+    match Result {
+        Err(EFI_UNSUPPORTED) => Ok(()),
+        result => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::fmt::Write as _;
+    use std::string::{String, ToString};
+    use std::vec;
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::protocols::console::simple_text_output::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        Attribute(UINTN),
+        Cursor(UINTN, UINTN),
+        Output(String),
+        Clear,
+        EnableCursor(bool),
+    }
+
+    /// A console sink that records the calls it receives and the characters they produce,
+    /// wrapping at the end of a row like a firmware console.
+    struct Recorder {
+        calls: Vec<Call>,
+        columns: UINTN,
+        rows: UINTN,
+        cells: Vec<Vec<char>>,
+        cursor: (UINTN, UINTN),
+    }
+
+    impl Recorder {
+        fn row(&self, Row: UINTN) -> String {
+            self.cells[Row].iter().collect()
+        }
+    }
+
+    impl ConsoleSink for Recorder {
+        fn output_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+            self.calls.push(Call::Output(String.to_string()));
+            for char in String.chars() {
+                let (column, row) = self.cursor;
+                match char {
+                    '\r' => self.cursor.0 = 0,
+                    '\n' => self.cursor.1 = (row + 1).min(self.rows - 1),
+                    _ => {
+                        assert!(
+                            (column, row) != (self.columns - 1, self.rows - 1),
+                            "wrote the bottom right cell"
+                        );
+                        self.cells[row][column] = char;
+                        self.cursor = match column + 1 {
+                            next if next == self.columns => (0, row + 1),
+                            next => (next, row),
+                        };
+                    }
+                }
+            }
+
+            Ok(())
+        }
+
+        fn set_attribute(&mut self, Attribute: UINTN) -> Result<(), EFI_STATUS> {
+            self.calls.push(Call::Attribute(Attribute));
+            Ok(())
+        }
+
+        fn set_cursor_position(&mut self, Column: UINTN, Row: UINTN) -> Result<(), EFI_STATUS> {
+            assert!(Column < self.columns && Row < self.rows);
+            self.calls.push(Call::Cursor(Column, Row));
+            self.cursor = (Column, Row);
+            Ok(())
+        }
+
+        fn clear_screen(&mut self) -> Result<(), EFI_STATUS> {
+            self.calls.push(Call::Clear);
+            self.cells = vec![vec![' '; self.columns]; self.rows];
+            self.cursor = (0, 0);
+            Ok(())
+        }
+
+        fn enable_cursor(&mut self, Visible: bool) -> Result<(), EFI_STATUS> {
+            self.calls.push(Call::EnableCursor(Visible));
+            Ok(())
+        }
+
+        fn size(&mut self) -> Option<(UINTN, UINTN)> {
+            Some((self.columns, self.rows))
+        }
+
+        fn cursor_position(&mut self) -> Option<(UINTN, UINTN)> {
+            Some(self.cursor)
+        }
+    }
+
+    /// Returns a writer over a screen of `Columns` by `Rows` filled with `#`.
+    fn terminal(Columns: UINTN, Rows: UINTN) -> AnsiWriter<Recorder> {
+        AnsiWriter::new(Recorder {
+            calls: Vec::new(),
+            columns: Columns,
+            rows: Rows,
+            cells: vec![vec!['#'; Columns]; Rows],
+            cursor: (0, 0),
+        })
+    }
+
+    fn take_calls(Writer: &mut AnsiWriter<Recorder>) -> Vec<Call> {
+        core::mem::take(&mut Writer.get_mut().calls)
+    }
+
+    #[test]
+    fn ansi_colors() {
+        let colors = [
+            EFI_BLACK,
+            EFI_RED,
+            EFI_GREEN,
+            EFI_BROWN,
+            EFI_BLUE,
+            EFI_MAGENTA,
+            EFI_CYAN,
+            EFI_LIGHTGRAY,
+        ];
+        for (ansi, &color) in colors.iter().enumerate() {
+            assert_eq!(ansi_color(ansi), color);
+            assert_eq!(ansi_color(color), ansi);
+        }
+    }
+
+    #[test]
+    fn select_graphic_rendition() {
+        let mut writer = terminal(10, 5);
+        writer.output_string("\x1B[31ma\x1B[34;43mb").unwrap();
+        assert_eq!(
+            take_calls(&mut writer),
+            [
+                Call::Attribute(EFI_TEXT_ATTR(EFI_RED, EFI_BLACK)),
+                Call::Output("a".to_string()),
+                Call::Attribute(EFI_TEXT_ATTR(EFI_BLUE, EFI_BROWN)),
+                Call::Output("b".to_string()),
+            ]
+        );
+
+        for (sequence, attribute) in [
+            ("\x1B[1;32m", EFI_TEXT_ATTR(EFI_LIGHTGREEN, EFI_BROWN)),
+            ("\x1B[22m", EFI_TEXT_ATTR(EFI_GREEN, EFI_BROWN)),
+            ("\x1B[1;7m", EFI_TEXT_ATTR(EFI_BROWN, EFI_GREEN)),
+            ("\x1B[27;39m", EFI_TEXT_ATTR(EFI_WHITE, EFI_BROWN)),
+            ("\x1B[0m", DEFAULT_CONSOLE_ATTRIBUTE),
+            ("\x1B[95;106m", EFI_TEXT_ATTR(EFI_LIGHTMAGENTA, EFI_CYAN)),
+            ("\x1B[49m", EFI_TEXT_ATTR(EFI_LIGHTMAGENTA, EFI_BLACK)),
+            ("\x1B[m", DEFAULT_CONSOLE_ATTRIBUTE),
+            ("\x1B[36;;1m", EFI_TEXT_ATTR(EFI_WHITE, EFI_BLACK)),
+        ] {
+            writer.output_string(sequence).unwrap();
+            assert_eq!(writer.attribute(), attribute, "{:?}", sequence);
+            assert_eq!(take_calls(&mut writer), [Call::Attribute(attribute)]);
+        }
+
+        writer.output_string("\x1B[41m\x1Bc").unwrap();
+        assert_eq!(writer.attribute(), DEFAULT_CONSOLE_ATTRIBUTE);
+        assert_eq!(
+            take_calls(&mut writer),
+            [
+                Call::Attribute(EFI_TEXT_ATTR(EFI_WHITE, EFI_RED)),
+                Call::Attribute(DEFAULT_CONSOLE_ATTRIBUTE),
+                Call::Clear,
+            ]
+        );
+    }
+
+    #[test]
+    fn cursor_movement_is_clamped() {
+        let mut writer = terminal(10, 5);
+        for (sequence, position) in [
+            ("\x1B[3;4H", (3, 2)),
+            ("\x1B[10A", (3, 0)),
+            ("\x1B[B", (3, 1)),
+            ("\x1B[99C", (9, 1)),
+            ("\x1B[2D", (7, 1)),
+            ("\x1B[99D", (0, 1)),
+            ("\x1B[99B", (0, 4)),
+            ("\x1B[2F", (0, 2)),
+            ("\x1B[E", (0, 3)),
+            ("\x1B[5G", (4, 3)),
+            ("\x1B[0;0H", (0, 0)),
+            ("\x1B[99;99f", (9, 4)),
+            ("\x1B[H", (0, 0)),
+        ] {
+            writer.output_string(sequence).unwrap();
+            assert_eq!(
+                take_calls(&mut writer),
+                [Call::Cursor(position.0, position.1)]
+            );
+        }
+
+        writer.output_string("\x1B[2;2H\x1B7\x1B[H\x1B8").unwrap();
+        writer.output_string("\x1B[s\x1B[4;4H\x1B[u").unwrap();
+        assert_eq!(
+            take_calls(&mut writer),
+            [
+                Call::Cursor(1, 1),
+                Call::Cursor(0, 0),
+                Call::Cursor(1, 1),
+                Call::Cursor(3, 3),
+                Call::Cursor(1, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn erase_in_line() {
+        let mut writer = terminal(10, 3);
+        writer.output_string("\x1B[2;5H").unwrap();
+        take_calls(&mut writer);
+
+        writer.output_string("\x1B[K").unwrap();
+        assert_eq!(
+            take_calls(&mut writer),
+            [
+                Call::Cursor(4, 1),
+                Call::Output("      ".to_string()),
+                Call::Cursor(4, 1),
+            ]
+        );
+        assert_eq!(writer.get_ref().row(1), "####      ");
+
+        writer.output_string("\x1B[3;5H\x1B[1K").unwrap();
+        assert_eq!(writer.get_ref().row(2), "     #####");
+        writer.output_string("\x1B[1;5H\x1B[2K").unwrap();
+        assert_eq!(writer.get_ref().row(0), "          ");
+        assert_eq!(writer.get_ref().cursor, (4, 0));
+    }
+
+    #[test]
+    fn erase_in_display() {
+        let mut writer = terminal(10, 3);
+        writer.output_string("\x1B[2;5H\x1B[1J").unwrap();
+        assert_eq!(writer.get_ref().row(0), "          ");
+        assert_eq!(writer.get_ref().row(1), "     #####");
+        assert_eq!(writer.get_ref().row(2), "##########");
+
+        // The bottom right cell is left alone, as writing it would scroll the screen.
+        writer.output_string("\x1B[2;7H\x1B[0J").unwrap();
+        assert_eq!(writer.get_ref().row(1), "     #    ");
+        assert_eq!(writer.get_ref().row(2), "         #");
+        assert_eq!(writer.get_ref().cursor, (6, 1));
+
+        take_calls(&mut writer);
+        writer.output_string("\x1B[2J").unwrap();
+        assert_eq!(take_calls(&mut writer), [Call::Clear, Call::Cursor(6, 1)]);
+    }
+
+    #[test]
+    fn sequences_split_across_writes() {
+        let mut writer = terminal(10, 5);
+        for part in [
+            "a\x1B",
+            "[",
+            "3",
+            "1;4",
+            "4mb",
+            "\x1B]0;ti",
+            "tle\x1B",
+            "\\c",
+        ] {
+            writer.output_string(part).unwrap();
+        }
+        assert_eq!(
+            take_calls(&mut writer),
+            [
+                Call::Output("a".to_string()),
+                Call::Attribute(EFI_TEXT_ATTR(EFI_RED, EFI_BLUE)),
+                Call::Output("b".to_string()),
+                Call::Output("c".to_string()),
+            ]
+        );
+
+        write!(writer, "\x1B[?25l1\n2\x1B[?25h").unwrap();
+        assert_eq!(
+            take_calls(&mut writer),
+            [
+                Call::EnableCursor(false),
+                Call::Output("1".to_string()),
+                Call::Output("\r\n".to_string()),
+                Call::Output("2".to_string()),
+                Call::EnableCursor(true),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_sequences() {
+        let mut writer = terminal(10, 5);
+        writer
+            .output_string("\x1B[3\u{1}a\x1B[5zb\x1B(Bc\x1B]2;x\u{7}d\x1B[?1049he")
+            .unwrap();
+        assert_eq!(
+            take_calls(&mut writer),
+            ["a", "b", "c", "d", "e"].map(|text| Call::Output(text.to_string()))
+        );
+
+        // Overlong numbers saturate, and parameters past the last are folded into it.
+        writer
+            .output_string("\x1B[3;3H\x1B[99999999999999999999999999A")
+            .unwrap();
+        assert_eq!(
+            take_calls(&mut writer),
+            [Call::Cursor(2, 2), Call::Cursor(2, 0)]
+        );
+
+        let mut sequence = String::from("\x1B[");
+        for _ in 0..ANSI_MAX_PARAMETERS + 4 {
+            sequence.push_str("1;");
+        }
+        sequence.push_str("31m");
+        writer.output_string(&sequence).unwrap();
+        assert_eq!(writer.attribute(), EFI_TEXT_ATTR(EFI_WHITE, EFI_BLACK));
+    }
+}
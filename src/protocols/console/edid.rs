@@ -0,0 +1,580 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI EDID Protocols
+//!
+//! This module defines the protocols giving access to the Extended Display Identification Data
+//! (EDID) of a video output device: the [`EFI_EDID_DISCOVERED_PROTOCOL`] with the EDID read from
+//! the device, the [`EFI_EDID_ACTIVE_PROTOCOL`] with the EDID in use, and the
+//! [`EFI_EDID_OVERRIDE_PROTOCOL`] which lets the platform override the EDID of a device. See
+//! [Section 12.9.2 of the UEFI Specification] for more details.
+//!
+//! The [`Edid`] parser decodes the 128-byte base EDID block, including the preferred detailed
+//! timing, which [`EFI_GRAPHICS_OUTPUT_PROTOCOL::preferred_mode()`] uses to select the native
+//! resolution of a panel.
+//!
+//! [`EFI_EDID_DISCOVERED_PROTOCOL`]: crate::protocols::console::edid::EFI_EDID_DISCOVERED_PROTOCOL
+//! [`EFI_EDID_ACTIVE_PROTOCOL`]: crate::protocols::console::edid::EFI_EDID_ACTIVE_PROTOCOL
+//! [`EFI_EDID_OVERRIDE_PROTOCOL`]: crate::protocols::console::edid::EFI_EDID_OVERRIDE_PROTOCOL
+//! [`Edid`]: crate::protocols::console::edid::Edid
+//! [`EFI_GRAPHICS_OUTPUT_PROTOCOL::preferred_mode()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::preferred_mode
+//!
+//! [Section 12.9.2 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/12_Protocols_Console_Support.html#edid-discovered-protocol
+
+use core::{ptr, slice};
+
+use crate::protocols::Protocol;
+use crate::status::{EFI_CRC_ERROR, EFI_INVALID_PARAMETER, EFI_SUCCESS};
+use crate::types::{EFI_GUID, EFI_HANDLE, EFI_STATUS, UINT16, UINT32, UINT8, UINTN};
+
+/// GUID for the [`EFI_EDID_DISCOVERED_PROTOCOL`].
+///
+/// [`EFI_EDID_DISCOVERED_PROTOCOL`]: crate::protocols::console::edid::EFI_EDID_DISCOVERED_PROTOCOL
+pub const EFI_EDID_DISCOVERED_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0x1C0C34F6,
+    0xD380,
+    0x41FA,
+    [0xA0, 0x49, 0x8A, 0xD0, 0x6C, 0x1A, 0x66, 0xAA],
+);
+
+/// GUID for the [`EFI_EDID_ACTIVE_PROTOCOL`].
+///
+/// [`EFI_EDID_ACTIVE_PROTOCOL`]: crate::protocols::console::edid::EFI_EDID_ACTIVE_PROTOCOL
+pub const EFI_EDID_ACTIVE_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0xBD8C1056,
+    0x9F36,
+    0x44EC,
+    [0x92, 0xA8, 0xA6, 0x33, 0x7F, 0x81, 0x79, 0x86],
+);
+
+/// GUID for the [`EFI_EDID_OVERRIDE_PROTOCOL`].
+///
+/// [`EFI_EDID_OVERRIDE_PROTOCOL`]: crate::protocols::console::edid::EFI_EDID_OVERRIDE_PROTOCOL
+pub const EFI_EDID_OVERRIDE_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0x48ECB431,
+    0xFB72,
+    0x45C0,
+    [0xA9, 0x22, 0xF4, 0x58, 0xFE, 0x04, 0x0B, 0xD5],
+);
+
+/// The EDID returned by [`GetEdid()`] must not be used; the EDID of the device is used instead.
+///
+/// [`GetEdid()`]: crate::protocols::console::edid::EFI_EDID_OVERRIDE_PROTOCOL::GetEdid
+pub const EFI_EDID_OVERRIDE_DONT_OVERRIDE: UINT32 = 0x01;
+/// The video output device may be hot plugged, so its EDID must be retrieved again.
+pub const EFI_EDID_OVERRIDE_ENABLE_HOT_PLUG: UINT32 = 0x02;
+
+/// The size of an EDID block in bytes.
+pub const EDID_BLOCK_SIZE: usize = 128;
+/// The fixed pattern at the start of an EDID base block.
+pub const EDID_HEADER: [UINT8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+
+/// Contains the EDID information retrieved from a video output device.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_EDID_DISCOVERED_PROTOCOL {
+    /// The size, in bytes, of the [`Edid`] buffer. Zero if no EDID information is available.
+    ///
+    /// [`Edid`]: ./struct.EFI_EDID_DISCOVERED_PROTOCOL.html#structfield.Edid
+    pub SizeOfEdid: UINT32,
+    /// A pointer to a read-only array of bytes that contains the EDID information for an active
+    /// video output device. Null if no EDID information is available.
+    pub Edid: *mut UINT8,
+}
+
+unsafe impl Protocol for EFI_EDID_DISCOVERED_PROTOCOL {
+    const GUID: EFI_GUID = EFI_EDID_DISCOVERED_PROTOCOL_GUID;
+}
+
+impl EFI_EDID_DISCOVERED_PROTOCOL {
+    /// Returns the EDID information of the device, or `None` if it has none.
+    pub unsafe fn edid(&self) -> Option<&[UINT8]> {
+        edid_bytes(self.Edid, self.SizeOfEdid as usize)
+    }
+
+    /// Parses the base block of the EDID information of the device with [`Edid::parse()`].
+    ///
+    /// [`Edid::parse()`]: crate::protocols::console::edid::Edid::parse
+    pub unsafe fn parse(&self) -> Result<Edid, EFI_STATUS> {
+        Edid::parse(self.edid().unwrap_or(&[]))
+    }
+}
+
+/// Contains the EDID information for an active video output device.
+///
+/// This is either the EDID information retrieved from the [`EFI_EDID_OVERRIDE_PROTOCOL`] if an
+/// override is available, or an identical copy of the EDID information from the
+/// [`EFI_EDID_DISCOVERED_PROTOCOL`] if no overrides are available.
+///
+/// [`EFI_EDID_OVERRIDE_PROTOCOL`]: crate::protocols::console::edid::EFI_EDID_OVERRIDE_PROTOCOL
+/// [`EFI_EDID_DISCOVERED_PROTOCOL`]: crate::protocols::console::edid::EFI_EDID_DISCOVERED_PROTOCOL
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_EDID_ACTIVE_PROTOCOL {
+    /// The size, in bytes, of the [`Edid`] buffer. Zero if no EDID information is available.
+    ///
+    /// [`Edid`]: ./struct.EFI_EDID_ACTIVE_PROTOCOL.html#structfield.Edid
+    pub SizeOfEdid: UINT32,
+    /// A pointer to a read-only array of bytes that contains the EDID information for an active
+    /// video output device. Null if no EDID information is available.
+    pub Edid: *mut UINT8,
+}
+
+unsafe impl Protocol for EFI_EDID_ACTIVE_PROTOCOL {
+    const GUID: EFI_GUID = EFI_EDID_ACTIVE_PROTOCOL_GUID;
+}
+
+impl EFI_EDID_ACTIVE_PROTOCOL {
+    /// Returns the EDID information of the device, or `None` if it has none.
+    pub unsafe fn edid(&self) -> Option<&[UINT8]> {
+        edid_bytes(self.Edid, self.SizeOfEdid as usize)
+    }
+
+    /// Parses the base block of the EDID information of the device with [`Edid::parse()`].
+    ///
+    /// [`Edid::parse()`]: crate::protocols::console::edid::Edid::parse
+    pub unsafe fn parse(&self) -> Result<Edid, EFI_STATUS> {
+        Edid::parse(self.edid().unwrap_or(&[]))
+    }
+}
+
+/// Produced by the platform to allow the platform to provide EDID information to the producer of
+/// the Graphics Output protocol.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_EDID_OVERRIDE_PROTOCOL {
+    GetEdid: EFI_EDID_OVERRIDE_PROTOCOL_GET_EDID,
+}
+
+unsafe impl Protocol for EFI_EDID_OVERRIDE_PROTOCOL {
+    const GUID: EFI_GUID = EFI_EDID_OVERRIDE_PROTOCOL_GUID;
+}
+
+impl EFI_EDID_OVERRIDE_PROTOCOL {
+    /// Returns policy information and potentially a replacement EDID for the specified video
+    /// output device.
+    ///
+    /// This function returns the EDID information to use for the video output device
+    /// `ChildHandle`, or whether the discovered EDID is to be used. `Attributes` returns a
+    /// combination of [`EFI_EDID_OVERRIDE_DONT_OVERRIDE`] and
+    /// [`EFI_EDID_OVERRIDE_ENABLE_HOT_PLUG`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `ChildHandle`
+    ///
+    /// A pointer to a child handle that represents a possible video output device.
+    ///
+    /// ## `Attributes`
+    ///
+    /// A pointer to the attributes associated with `ChildHandle` video output device.
+    ///
+    /// ## `EdidSize`
+    ///
+    /// A pointer to the size, in bytes, of the `Edid` buffer.
+    ///
+    /// ## `Edid`
+    ///
+    /// A pointer to the callee allocated buffer that contains the EDID information associated
+    /// with `ChildHandle`. If `EdidSize` is 0, then a pointer to null is returned.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - valid overrides returned for `ChildHandle`.
+    ///
+    /// [`EFI_UNSUPPORTED`] - `ChildHandle` has no overrides.
+    ///
+    /// [`EFI_EDID_OVERRIDE_DONT_OVERRIDE`]: crate::protocols::console::edid::EFI_EDID_OVERRIDE_DONT_OVERRIDE
+    /// [`EFI_EDID_OVERRIDE_ENABLE_HOT_PLUG`]: crate::protocols::console::edid::EFI_EDID_OVERRIDE_ENABLE_HOT_PLUG
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    pub unsafe fn GetEdid(
+        &mut self,
+        ChildHandle: *mut EFI_HANDLE,
+        Attributes: *mut UINT32,
+        EdidSize: *mut UINTN,
+        Edid: *mut *mut UINT8,
+    ) -> EFI_STATUS {
+        (self.GetEdid)(self, ChildHandle, Attributes, EdidSize, Edid)
+    }
+
+    /// Returns the override attributes for the video output device `ChildHandle`, and the EDID
+    /// information replacing its own, if any.
+    pub unsafe fn get_edid(
+        &mut self,
+        ChildHandle: EFI_HANDLE,
+    ) -> Result<(UINT32, Option<&[UINT8]>), EFI_STATUS> {
+        let mut handle = ChildHandle;
+        let mut attributes = 0;
+        let mut size = 0;
+        let mut edid = ptr::null_mut();
+        match self.GetEdid(&mut handle, &mut attributes, &mut size, &mut edid) {
+            EFI_SUCCESS => Ok((attributes, edid_bytes(edid, size))),
+            status => Err(status),
+        }
+    }
+}
+
+unsafe fn edid_bytes<'a>(Edid: *const UINT8, Size: usize) -> Option<&'a [UINT8]> {
+    if Edid.is_null() || Size == 0 {
+        None
+    } else {
+        Some(slice::from_raw_parts(Edid, Size))
+    }
+}
+
+/// A parsed EDID base block.
+#[derive(Clone, Copy)]
+pub struct Edid {
+    block: [UINT8; EDID_BLOCK_SIZE],
+}
+
+impl Edid {
+    /// Parses the EDID base block at the start of `Data`.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if `Data` is shorter than [`EDID_BLOCK_SIZE`] or does
+    /// not start with the [`EDID_HEADER`], and [`EFI_CRC_ERROR`] if the checksum of the block
+    /// does not match.
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EDID_BLOCK_SIZE`]: crate::protocols::console::edid::EDID_BLOCK_SIZE
+    /// [`EDID_HEADER`]: crate::protocols::console::edid::EDID_HEADER
+    /// [`EFI_CRC_ERROR`]: crate::status::EFI_CRC_ERROR
+    pub fn parse(Data: &[UINT8]) -> Result<Self, EFI_STATUS> {
+        if Data.len() < EDID_BLOCK_SIZE || !Data.starts_with(&EDID_HEADER) {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        let mut block = [0; EDID_BLOCK_SIZE];
+        block.copy_from_slice(&Data[..EDID_BLOCK_SIZE]);
+        // The bytes of the block, including the checksum, add up to zero.
+        if block.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)) != 0 {
+            return Err(EFI_CRC_ERROR);
+        }
+
+        Ok(Self { block })
+    }
+
+    /// Returns the raw bytes of the block.
+    pub fn as_bytes(&self) -> &[UINT8; EDID_BLOCK_SIZE] {
+        &self.block
+    }
+
+    /// Returns the three letter manufacturer ID assigned by the UEFI Forum's PNP ID registry,
+    /// such as `*b"DEL"`.
+    pub fn manufacturer_id(&self) -> [UINT8; 3] {
+        let id = UINT16::from_be_bytes([self.block[8], self.block[9]]);
+        [10, 5, 0].map(|shift| b'@' + ((id >> shift) & 0x1F) as UINT8)
+    }
+
+    /// Returns the product code assigned by the manufacturer.
+    pub fn product_code(&self) -> UINT16 {
+        UINT16::from_le_bytes([self.block[10], self.block[11]])
+    }
+
+    /// Returns the serial number, or zero if not used.
+    pub fn serial_number(&self) -> UINT32 {
+        UINT32::from_le_bytes([
+            self.block[12],
+            self.block[13],
+            self.block[14],
+            self.block[15],
+        ])
+    }
+
+    /// Returns the year of manufacture, or of the model if the week of manufacture is `0xFF`.
+    pub fn manufacture_year(&self) -> UINT16 {
+        1990 + UINT16::from(self.block[17])
+    }
+
+    /// Returns the EDID version and revision, such as `(1, 4)`.
+    pub fn version(&self) -> (UINT8, UINT8) {
+        (self.block[18], self.block[19])
+    }
+
+    /// Returns whether the video input is digital.
+    pub fn is_digital(&self) -> bool {
+        self.block[20] & 0x80 != 0
+    }
+
+    /// Returns the horizontal and vertical size of the screen in centimeters, or `None` if it is
+    /// unknown or variable.
+    pub fn physical_size(&self) -> Option<(UINT8, UINT8)> {
+        match (self.block[21], self.block[22]) {
+            (0, _) | (_, 0) => None,
+            size => Some(size),
+        }
+    }
+
+    /// Returns the number of extension blocks following this block.
+    pub fn extension_count(&self) -> UINT8 {
+        self.block[126]
+    }
+
+    /// Returns the detailed timings among the four 18-byte descriptors of the block.
+    pub fn detailed_timings(&self) -> impl Iterator<Item = DetailedTiming> + '_ {
+        self.descriptors().filter_map(DetailedTiming::parse)
+    }
+
+    /// Returns the preferred timing of the display, which is its native resolution for panels.
+    ///
+    /// This is the first descriptor of the block, if it is a detailed timing.
+    pub fn preferred_timing(&self) -> Option<DetailedTiming> {
+        self.descriptors().next().and_then(DetailedTiming::parse)
+    }
+
+    /// Returns the display product name, if the block has a descriptor for it.
+    pub fn display_name(&self) -> Option<&str> {
+        self.descriptors()
+            .find(|descriptor| descriptor[..5] == [0, 0, 0, 0xFC, 0])
+            .and_then(|descriptor| {
+                let text = &descriptor[5..];
+                let end = text
+                    .iter()
+                    .position(|&byte| byte == b'\n')
+                    .unwrap_or(text.len());
+                core::str::from_utf8(&text[..end]).ok().map(str::trim_end)
+            })
+    }
+
+    fn descriptors(&self) -> impl Iterator<Item = &[UINT8]> + '_ {
+        self.block[54..126].chunks_exact(18)
+    }
+}
+
+/// A detailed timing descriptor of an EDID block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DetailedTiming {
+    /// The pixel clock in kHz.
+    pub PixelClock: UINT32,
+    /// The number of visible pixels of a line.
+    pub HorizontalActive: UINT16,
+    /// The number of pixels of the horizontal blanking interval.
+    pub HorizontalBlanking: UINT16,
+    /// The number of visible lines.
+    pub VerticalActive: UINT16,
+    /// The number of lines of the vertical blanking interval.
+    pub VerticalBlanking: UINT16,
+    /// The number of pixels from the end of the active line to the horizontal sync pulse.
+    pub HorizontalSyncOffset: UINT16,
+    /// The width of the horizontal sync pulse in pixels.
+    pub HorizontalSyncWidth: UINT16,
+    /// The number of lines from the end of the active area to the vertical sync pulse.
+    pub VerticalSyncOffset: UINT8,
+    /// The width of the vertical sync pulse in lines.
+    pub VerticalSyncWidth: UINT8,
+    /// The horizontal size of the image in millimeters.
+    pub HorizontalImageSize: UINT16,
+    /// The vertical size of the image in millimeters.
+    pub VerticalImageSize: UINT16,
+    /// Whether the timing is interlaced.
+    pub Interlaced: bool,
+}
+
+impl DetailedTiming {
+    /// Parses an 18-byte descriptor, returning `None` if it is not a detailed timing.
+    pub fn parse(Descriptor: &[UINT8]) -> Option<Self> {
+        let d = Descriptor.get(..18)?;
+        let clock = UINT16::from_le_bytes([d[0], d[1]]);
+        if clock == 0 {
+            return None;
+        }
+
+        let high = |low: UINT8, bits: UINT8| UINT16::from(low) | UINT16::from(bits) << 8;
+        Some(Self {
+            PixelClock: UINT32::from(clock) * 10,
+            HorizontalActive: high(d[2], d[4] >> 4),
+            HorizontalBlanking: high(d[3], d[4] & 0x0F),
+            VerticalActive: high(d[5], d[7] >> 4),
+            VerticalBlanking: high(d[6], d[7] & 0x0F),
+            HorizontalSyncOffset: high(d[8], d[11] >> 6),
+            HorizontalSyncWidth: high(d[9], (d[11] >> 4) & 0x03),
+            VerticalSyncOffset: (d[10] >> 4) | ((d[11] >> 2) & 0x03) << 4,
+            VerticalSyncWidth: (d[10] & 0x0F) | (d[11] & 0x03) << 4,
+            HorizontalImageSize: high(d[12], d[14] >> 4),
+            VerticalImageSize: high(d[13], d[14] & 0x0F),
+            Interlaced: d[17] & 0x80 != 0,
+        })
+    }
+
+    /// Returns the refresh rate in Hz, rounded to the nearest integer.
+    pub fn refresh_rate(&self) -> UINT32 {
+        let total = (UINT32::from(self.HorizontalActive) + UINT32::from(self.HorizontalBlanking))
+            * (UINT32::from(self.VerticalActive) + UINT32::from(self.VerticalBlanking));
+        if total == 0 {
+            return 0;
+        }
+
+        (self.PixelClock * 1000 + total / 2) / total
+    }
+}
+
+type EFI_EDID_OVERRIDE_PROTOCOL_GET_EDID = extern "efiapi" fn(
+    This: *mut EFI_EDID_OVERRIDE_PROTOCOL,
+    ChildHandle: *mut EFI_HANDLE,
+    Attributes: *mut UINT32,
+    EdidSize: *mut UINTN,
+    Edid: *mut *mut UINT8,
+) -> EFI_STATUS;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The base block of a QEMU-style monitor with a 1920x1080 preferred timing.
+    const QEMU_EDID: [UINT8; EDID_BLOCK_SIZE] = [
+        0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x49, 0x14, 0x34, 0x12, 0x00, 0x00, 0x00,
+        0x00, 0x2A, 0x20, 0x01, 0x04, 0xA5, 0x35, 0x1E, 0x78, 0x06, 0xEE, 0x91, 0xA3, 0x54, 0x4C,
+        0x99, 0x26, 0x0F, 0x50, 0x54, 0x21, 0x08, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x02, 0x3A, 0x80, 0x18, 0x71, 0x38,
+        0x2D, 0x40, 0x58, 0x2C, 0x45, 0x00, 0x0F, 0x28, 0x21, 0x00, 0x00, 0x1E, 0x00, 0x00, 0x00,
+        0xFD, 0x00, 0x32, 0x7D, 0x1E, 0xA0, 0x1E, 0x00, 0x0A, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20,
+        0x00, 0x00, 0x00, 0xFC, 0x00, 0x51, 0x45, 0x4D, 0x55, 0x20, 0x4D, 0x6F, 0x6E, 0x69, 0x74,
+        0x6F, 0x72, 0x0A, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x60,
+    ];
+
+    #[test]
+    fn parse_block() {
+        let edid = Edid::parse(&QEMU_EDID).unwrap();
+        assert_eq!(edid.as_bytes(), &QEMU_EDID);
+        assert_eq!(edid.manufacturer_id(), *b"RHT");
+        assert_eq!(edid.product_code(), 0x1234);
+        assert_eq!(edid.serial_number(), 0);
+        assert_eq!(edid.manufacture_year(), 2022);
+        assert_eq!(edid.version(), (1, 4));
+        assert!(edid.is_digital());
+        assert_eq!(edid.physical_size(), Some((53, 30)));
+        assert_eq!(edid.extension_count(), 0);
+        assert_eq!(edid.display_name(), Some("QEMU Monitor"));
+        assert_eq!(edid.detailed_timings().count(), 1);
+
+        // Trailing extension blocks are ignored.
+        let mut data = [0; 2 * EDID_BLOCK_SIZE];
+        data[..EDID_BLOCK_SIZE].copy_from_slice(&QEMU_EDID);
+        assert!(Edid::parse(&data).is_ok());
+    }
+
+    #[test]
+    fn parse_rejects_bad_blocks() {
+        assert_eq!(
+            Edid::parse(&QEMU_EDID[..EDID_BLOCK_SIZE - 1]).err(),
+            Some(EFI_INVALID_PARAMETER)
+        );
+
+        let mut block = QEMU_EDID;
+        block[7] = 0xFF;
+        block[127] = block[127].wrapping_sub(0xFF);
+        assert_eq!(Edid::parse(&block).err(), Some(EFI_INVALID_PARAMETER));
+
+        let mut block = QEMU_EDID;
+        block[21] += 1;
+        assert_eq!(Edid::parse(&block).err(), Some(EFI_CRC_ERROR));
+        block[127] -= 1;
+        assert!(Edid::parse(&block).is_ok());
+    }
+
+    #[test]
+    fn manufacturer_id() {
+        let mut block = QEMU_EDID;
+        for (id, bytes) in [
+            (*b"DEL", [0x10, 0xAC]),
+            (*b"AAA", [0x04, 0x21]),
+            (*b"ZZZ", [0x6B, 0x5A]),
+        ] {
+            let checksum = block[8].wrapping_add(block[9]).wrapping_add(block[127]);
+            block[8..10].copy_from_slice(&bytes);
+            block[127] = checksum.wrapping_sub(bytes[0]).wrapping_sub(bytes[1]);
+            assert_eq!(Edid::parse(&block).unwrap().manufacturer_id(), id);
+        }
+    }
+
+    #[test]
+    fn preferred_timing() {
+        let timing = Edid::parse(&QEMU_EDID).unwrap().preferred_timing().unwrap();
+        assert_eq!(
+            timing,
+            DetailedTiming {
+                PixelClock: 148_500,
+                HorizontalActive: 1920,
+                HorizontalBlanking: 280,
+                VerticalActive: 1080,
+                VerticalBlanking: 45,
+                HorizontalSyncOffset: 88,
+                HorizontalSyncWidth: 44,
+                VerticalSyncOffset: 4,
+                VerticalSyncWidth: 5,
+                HorizontalImageSize: 527,
+                VerticalImageSize: 296,
+                Interlaced: false,
+            }
+        );
+        assert_eq!(timing.refresh_rate(), 60);
+    }
+
+    #[test]
+    fn detailed_timing_high_bits() {
+        // 3840x2160 at 60 Hz, whose active and blanking counts use the upper nibbles.
+        let timing = DetailedTiming::parse(&[
+            0x08, 0xE8, 0x00, 0x30, 0xF2, 0x70, 0x5A, 0x80, 0xB0, 0x58, 0x8A, 0x00, 0x50, 0x1D,
+            0x74, 0x00, 0x00, 0x1E,
+        ])
+        .unwrap();
+        assert_eq!(timing.PixelClock, 594_000);
+        assert_eq!(
+            (timing.HorizontalActive, timing.HorizontalBlanking),
+            (3840, 560)
+        );
+        assert_eq!((timing.VerticalActive, timing.VerticalBlanking), (2160, 90));
+        assert_eq!(
+            (timing.HorizontalSyncOffset, timing.HorizontalSyncWidth),
+            (176, 88)
+        );
+        assert_eq!(
+            (timing.VerticalSyncOffset, timing.VerticalSyncWidth),
+            (8, 10)
+        );
+        assert_eq!(
+            (timing.HorizontalImageSize, timing.VerticalImageSize),
+            (1872, 1053)
+        );
+        assert_eq!(timing.refresh_rate(), 60);
+
+        // The upper bits of all four sync fields share a byte.
+        let mut descriptor = [0; 18];
+        descriptor[..2].copy_from_slice(&1u16.to_le_bytes());
+        descriptor[11] = 0b10_01_11_00;
+        descriptor[17] = 0x80;
+        let timing = DetailedTiming::parse(&descriptor).unwrap();
+        assert_eq!(
+            (timing.HorizontalSyncOffset, timing.HorizontalSyncWidth),
+            (0x200, 0x100)
+        );
+        assert_eq!(
+            (timing.VerticalSyncOffset, timing.VerticalSyncWidth),
+            (0x30, 0)
+        );
+        assert!(timing.Interlaced);
+        assert_eq!(timing.refresh_rate(), 0);
+    }
+
+    #[test]
+    fn detailed_timing_rejects_other_descriptors() {
+        assert_eq!(DetailedTiming::parse(&QEMU_EDID[54..71]), None);
+        assert_eq!(DetailedTiming::parse(&QEMU_EDID[72..90]), None);
+    }
+}
@@ -0,0 +1,1234 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Graphics Output Protocol
+//!
+//! This module defines the Graphics Output Protocol, also known as the
+//! [`EFI_GRAPHICS_OUTPUT_PROTOCOL`], which provides a linear framebuffer and block transfer
+//! (Blt) operations for a graphics device. See [Section 12.9 of the UEFI Specification] for more
+//! details.
+//!
+//! Besides the raw bindings, [`EFI_GRAPHICS_OUTPUT_PROTOCOL::modes()`] iterates over the modes
+//! supported by the device, [`EFI_GRAPHICS_OUTPUT_PROTOCOL::best_mode()`] selects a mode by
+//! resolution, and [`FrameBuffer`] gives stride-aware access to the pixels of the current mode
+//! in any of the framebuffer pixel formats.
+//!
+//! [`EFI_GRAPHICS_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL
+//! [`EFI_GRAPHICS_OUTPUT_PROTOCOL::modes()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::modes
+//! [`EFI_GRAPHICS_OUTPUT_PROTOCOL::best_mode()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::best_mode
+//! [`FrameBuffer`]: crate::protocols::console::graphics_output::FrameBuffer
+//!
+//! [Section 12.9 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/12_Protocols_Console_Support.html#graphics-output-protocol
+
+use core::marker::PhantomData;
+use core::ptr;
+
+use crate::protocols::console::edid::Edid;
+use crate::protocols::Protocol;
+use crate::status::{EFI_DEVICE_ERROR, EFI_INVALID_PARAMETER, EFI_SUCCESS};
+use crate::tables::boot_services::EFI_BOOT_SERVICES;
+use crate::types::{EFI_GUID, EFI_PHYSICAL_ADDRESS, EFI_STATUS, UINT32, UINT8, UINTN};
+
+/// GUID for the [`EFI_GRAPHICS_OUTPUT_PROTOCOL`].
+///
+/// [`EFI_GRAPHICS_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL
+pub const EFI_GRAPHICS_OUTPUT_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0x9042A9DE,
+    0x23DC,
+    0x4A38,
+    [0x96, 0xFB, 0x7A, 0xDE, 0xD0, 0x80, 0x51, 0x6A],
+);
+
+/// The format of the pixels of a framebuffer.
+///
+/// See the `Pixel*` constants in this module for the defined formats.
+pub type EFI_GRAPHICS_PIXEL_FORMAT = UINT32;
+
+/// A pixel is 32 bits: byte zero is red, byte one is green, byte two is blue, and byte three is
+/// reserved.
+pub const PixelRedGreenBlueReserved8BitPerColor: EFI_GRAPHICS_PIXEL_FORMAT = 0;
+/// A pixel is 32 bits: byte zero is blue, byte one is green, byte two is red, and byte three is
+/// reserved.
+pub const PixelBlueGreenRedReserved8BitPerColor: EFI_GRAPHICS_PIXEL_FORMAT = 1;
+/// The pixel definition is given by the [`EFI_PIXEL_BITMASK`] of the mode.
+///
+/// [`EFI_PIXEL_BITMASK`]: crate::protocols::console::graphics_output::EFI_PIXEL_BITMASK
+pub const PixelBitMask: EFI_GRAPHICS_PIXEL_FORMAT = 2;
+/// The device does not support a physical framebuffer; only [`Blt()`] may be used.
+///
+/// [`Blt()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::Blt
+pub const PixelBltOnly: EFI_GRAPHICS_PIXEL_FORMAT = 3;
+/// Valid pixel formats are less than this value.
+pub const PixelFormatMax: EFI_GRAPHICS_PIXEL_FORMAT = 4;
+
+/// Provides a basic abstraction to set video modes and copy pixels to and from the graphics
+/// controller's frame buffer.
+///
+/// The linear address of the hardware frame buffer is also exposed so software can write
+/// directly to the video hardware.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_GRAPHICS_OUTPUT_PROTOCOL {
+    QueryMode: EFI_GRAPHICS_OUTPUT_PROTOCOL_QUERY_MODE,
+    SetMode: EFI_GRAPHICS_OUTPUT_PROTOCOL_SET_MODE,
+    Blt: EFI_GRAPHICS_OUTPUT_PROTOCOL_BLT,
+    /// Pointer to [`EFI_GRAPHICS_OUTPUT_PROTOCOL_MODE`] data.
+    ///
+    /// [`EFI_GRAPHICS_OUTPUT_PROTOCOL_MODE`]: crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_PROTOCOL_MODE
+    pub Mode: *mut EFI_GRAPHICS_OUTPUT_PROTOCOL_MODE,
+}
+
+unsafe impl Protocol for EFI_GRAPHICS_OUTPUT_PROTOCOL {
+    const GUID: EFI_GUID = EFI_GRAPHICS_OUTPUT_PROTOCOL_GUID;
+}
+
+impl EFI_GRAPHICS_OUTPUT_PROTOCOL {
+    /// Returns information for an available graphics mode that the graphics device and the set of
+    /// active video output devices supports.
+    ///
+    /// The [`QueryMode()`] function returns information for an available graphics mode that the
+    /// graphics device and the set of active video output devices supports. If `ModeNumber` is
+    /// not between `0` and [`MaxMode`] - 1, then [`EFI_INVALID_PARAMETER`] is returned.
+    ///
+    /// `SizeOfInfo` returns the size, in bytes, of the `Info` buffer. `Info` is allocated by the
+    /// firmware and must be freed by the caller with [`EFI_BOOT_SERVICES.FreePool()`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `ModeNumber`
+    ///
+    /// The mode number to return information on.
+    ///
+    /// ## `SizeOfInfo`
+    ///
+    /// A pointer to the size, in bytes, of the `Info` buffer.
+    ///
+    /// ## `Info`
+    ///
+    /// A pointer to a callee allocated buffer that returns information about `ModeNumber`.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - valid mode information was returned.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - a hardware error occurred trying to retrieve the video mode.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - `ModeNumber` is not valid.
+    ///
+    /// [`QueryMode()`]: ./struct.EFI_GRAPHICS_OUTPUT_PROTOCOL.html#method.QueryMode
+    /// [`MaxMode`]: ./struct.EFI_GRAPHICS_OUTPUT_PROTOCOL_MODE.html#structfield.MaxMode
+    /// [`EFI_BOOT_SERVICES.FreePool()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-freepool
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn QueryMode(
+        &mut self,
+        ModeNumber: UINT32,
+        SizeOfInfo: *mut UINTN,
+        Info: *mut *mut EFI_GRAPHICS_OUTPUT_MODE_INFORMATION,
+    ) -> EFI_STATUS {
+        (self.QueryMode)(self, ModeNumber, SizeOfInfo, Info)
+    }
+
+    /// Sets the video device into the specified mode and clears the visible portions of the
+    /// output display to black.
+    ///
+    /// The [`SetMode()`] function sets the graphics device and the set of active video output
+    /// devices to the video mode specified by `ModeNumber`. If `ModeNumber` is not supported
+    /// [`EFI_UNSUPPORTED`] is returned.
+    ///
+    /// If a device error occurs while attempting to set the video mode, then
+    /// [`EFI_DEVICE_ERROR`] is returned. Otherwise, the graphics device is set to the requested
+    /// geometry, the set of active output devices are set to the requested geometry, the visible
+    /// portion of the hardware frame buffer is cleared to black, and [`EFI_SUCCESS`] is returned.
+    ///
+    /// # Parameters
+    ///
+    /// ## `ModeNumber`
+    ///
+    /// Abstraction that defines the current video mode.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the graphics mode specified by `ModeNumber` was selected.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device had an error and could not complete the request.
+    ///
+    /// [`EFI_UNSUPPORTED`] - `ModeNumber` is not supported by this device.
+    ///
+    /// [`SetMode()`]: ./struct.EFI_GRAPHICS_OUTPUT_PROTOCOL.html#method.SetMode
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    pub unsafe fn SetMode(&mut self, ModeNumber: UINT32) -> EFI_STATUS {
+        (self.SetMode)(self, ModeNumber)
+    }
+
+    /// Blt a rectangle of pixels on the graphics screen. Blt stands for BLock Transfer.
+    ///
+    /// The [`Blt()`] function is used to draw the `BltBuffer` rectangle onto the video screen,
+    /// to copy a rectangle of the video screen into the `BltBuffer`, to fill a rectangle of the
+    /// video screen with a single pixel, or to move a rectangle within the video screen, as
+    /// selected by `BltOperation`.
+    ///
+    /// # Parameters
+    ///
+    /// ## `BltBuffer`
+    ///
+    /// The data to transfer to the graphics screen, or the buffer receiving the data read from
+    /// the screen. For [`EfiBltVideoFill`], only its first pixel is used. May be null for
+    /// [`EfiBltVideoToVideo`].
+    ///
+    /// ## `BltOperation`
+    ///
+    /// The operation to perform when copying `BltBuffer` on to the graphics screen.
+    ///
+    /// ## `SourceX`, `SourceY`
+    ///
+    /// The coordinates of the source for the operation.
+    ///
+    /// ## `DestinationX`, `DestinationY`
+    ///
+    /// The coordinates of the destination for the operation.
+    ///
+    /// ## `Width`, `Height`
+    ///
+    /// The width and height of the rectangle in pixels.
+    ///
+    /// ## `Delta`
+    ///
+    /// Not used for [`EfiBltVideoFill`] or the [`EfiBltVideoToVideo`] operation. If a `Delta` of
+    /// zero is used, the entire `BltBuffer` is being operated on. If a subrectangle of the
+    /// `BltBuffer` is being used then `Delta` represents the number of bytes in a row of the
+    /// `BltBuffer`.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - `BltBuffer` was drawn to the graphics screen.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - `BltOperation` is not valid.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device had an error and could not complete the request.
+    ///
+    /// [`Blt()`]: ./struct.EFI_GRAPHICS_OUTPUT_PROTOCOL.html#method.Blt
+    /// [`EfiBltVideoFill`]: crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_OPERATION::EfiBltVideoFill
+    /// [`EfiBltVideoToVideo`]: crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_OPERATION::EfiBltVideoToVideo
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    #[allow(clippy::too_many_arguments)]
+    pub unsafe fn Blt(
+        &mut self,
+        BltBuffer: *mut EFI_GRAPHICS_OUTPUT_BLT_PIXEL,
+        BltOperation: EFI_GRAPHICS_OUTPUT_BLT_OPERATION,
+        SourceX: UINTN,
+        SourceY: UINTN,
+        DestinationX: UINTN,
+        DestinationY: UINTN,
+        Width: UINTN,
+        Height: UINTN,
+        Delta: UINTN,
+    ) -> EFI_STATUS {
+        (self.Blt)(
+            self,
+            BltBuffer,
+            BltOperation,
+            SourceX,
+            SourceY,
+            DestinationX,
+            DestinationY,
+            Width,
+            Height,
+            Delta,
+        )
+    }
+
+    /// Returns the current mode of the device.
+    pub unsafe fn mode(&self) -> &EFI_GRAPHICS_OUTPUT_PROTOCOL_MODE {
+        &*self.Mode
+    }
+
+    /// Returns a copy of the information of the current mode.
+    pub unsafe fn info(&self) -> EFI_GRAPHICS_OUTPUT_MODE_INFORMATION {
+        *self.mode().Info
+    }
+
+    /// Returns a copy of the information of mode `ModeNumber`, freeing the buffer allocated by
+    /// [`QueryMode()`] with [`FreePool()`] once copied.
+    ///
+    /// [`QueryMode()`]: ./struct.EFI_GRAPHICS_OUTPUT_PROTOCOL.html#method.QueryMode
+    /// [`FreePool()`]: crate::tables::boot_services::EFI_BOOT_SERVICES::FreePool
+    pub unsafe fn query_mode(
+        &mut self,
+        ModeNumber: UINT32,
+        BootServices: &EFI_BOOT_SERVICES,
+    ) -> Result<EFI_GRAPHICS_OUTPUT_MODE_INFORMATION, EFI_STATUS> {
+        let mut size = 0;
+        let mut info = ptr::null_mut();
+        match self.QueryMode(ModeNumber, &mut size, &mut info) {
+            EFI_SUCCESS if info.is_null() => Err(EFI_DEVICE_ERROR),
+            EFI_SUCCESS => {
+                let value = ptr::read_unaligned(info);
+                BootServices.FreePool(info.cast());
+                Ok(value)
+            }
+            status => Err(status),
+        }
+    }
+
+    /// Returns an iterator over the modes of the device and their information, queried with
+    /// [`query_mode()`].
+    ///
+    /// [`query_mode()`]: ./struct.EFI_GRAPHICS_OUTPUT_PROTOCOL.html#method.query_mode
+    pub unsafe fn modes<'a>(&'a mut self, BootServices: &'a EFI_BOOT_SERVICES) -> Modes<'a> {
+        let count = self.mode().MaxMode;
+        Modes {
+            protocol: self,
+            boot_services: BootServices,
+            next: 0,
+            count,
+        }
+    }
+
+    /// Returns the number of the first mode with a resolution of exactly `HorizontalResolution`
+    /// by `VerticalResolution` pixels, preferring modes with a framebuffer.
+    ///
+    /// Returns the error of the first mode that cannot be queried with [`query_mode()`].
+    ///
+    /// [`query_mode()`]: ./struct.EFI_GRAPHICS_OUTPUT_PROTOCOL.html#method.query_mode
+    pub unsafe fn find_mode(
+        &mut self,
+        HorizontalResolution: UINT32,
+        VerticalResolution: UINT32,
+        BootServices: &EFI_BOOT_SERVICES,
+    ) -> Result<Option<UINT32>, EFI_STATUS> {
+        self.select_mode(BootServices, |info| {
+            if info.HorizontalResolution == HorizontalResolution
+                && info.VerticalResolution == VerticalResolution
+            {
+                Some(u64::from(info.PixelFormat != PixelBltOnly))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the number of the mode with the largest resolution that fits within
+    /// `MaxHorizontalResolution` by `MaxVerticalResolution` pixels, among the modes with a
+    /// framebuffer.
+    ///
+    /// Returns the error of the first mode that cannot be queried with [`query_mode()`].
+    ///
+    /// [`query_mode()`]: ./struct.EFI_GRAPHICS_OUTPUT_PROTOCOL.html#method.query_mode
+    pub unsafe fn best_mode(
+        &mut self,
+        MaxHorizontalResolution: UINT32,
+        MaxVerticalResolution: UINT32,
+        BootServices: &EFI_BOOT_SERVICES,
+    ) -> Result<Option<UINT32>, EFI_STATUS> {
+        self.select_mode(BootServices, |info| {
+            if info.PixelFormat != PixelBltOnly
+                && info.HorizontalResolution <= MaxHorizontalResolution
+                && info.VerticalResolution <= MaxVerticalResolution
+            {
+                Some(u64::from(info.HorizontalResolution) * u64::from(info.VerticalResolution))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the number of the mode matching the native resolution of the display described by
+    /// `Edid`, falling back to the largest mode that fits within it.
+    ///
+    /// The native resolution is that of the [`preferred_timing()`] of the display. Returns
+    /// `None` if the display has no preferred timing or no mode fits, and the error of the first
+    /// mode that cannot be queried with [`query_mode()`].
+    ///
+    /// [`preferred_timing()`]: crate::protocols::console::edid::Edid::preferred_timing
+    /// [`query_mode()`]: ./struct.EFI_GRAPHICS_OUTPUT_PROTOCOL.html#method.query_mode
+    pub unsafe fn preferred_mode(
+        &mut self,
+        Edid: &Edid,
+        BootServices: &EFI_BOOT_SERVICES,
+    ) -> Result<Option<UINT32>, EFI_STATUS> {
+        let timing = match Edid.preferred_timing() {
+            Some(timing) => timing,
+            None => return Ok(None),
+        };
+        let (width, height) = (
+            UINT32::from(timing.HorizontalActive),
+            UINT32::from(timing.VerticalActive),
+        );
+
+        match self.find_mode(width, height, BootServices)? {
+            Some(mode) => Ok(Some(mode)),
+            None => self.best_mode(width, height, BootServices),
+        }
+    }
+
+    /// Returns the number of the first mode with the highest score, skipping modes that `score`
+    /// rejects.
+    unsafe fn select_mode(
+        &mut self,
+        BootServices: &EFI_BOOT_SERVICES,
+        mut score: impl FnMut(&EFI_GRAPHICS_OUTPUT_MODE_INFORMATION) -> Option<u64>,
+    ) -> Result<Option<UINT32>, EFI_STATUS> {
+        let mut best: Option<(u64, UINT32)> = None;
+        for mode in self.modes(BootServices) {
+            let (mode, info) = mode?;
+            match score(&info) {
+                Some(value) if best.map_or(true, |(b, _)| value > b) => best = Some((value, mode)),
+                _ => {}
+            }
+        }
+
+        Ok(best.map(|(_, mode)| mode))
+    }
+
+    /// Sets the device to mode `ModeNumber`.
+    pub unsafe fn set_mode(&mut self, ModeNumber: UINT32) -> Result<(), EFI_STATUS> {
+        match self.SetMode(ModeNumber) {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Fills the rectangle of `Width` by `Height` pixels at (`X`, `Y`) of the screen with
+    /// `Color`, using [`EfiBltVideoFill`].
+    ///
+    /// [`EfiBltVideoFill`]: crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_OPERATION::EfiBltVideoFill
+    pub unsafe fn blt_video_fill(
+        &mut self,
+        Color: EFI_GRAPHICS_OUTPUT_BLT_PIXEL,
+        X: usize,
+        Y: usize,
+        Width: usize,
+        Height: usize,
+    ) -> Result<(), EFI_STATUS> {
+        self.check_screen(X, Y, Width, Height)?;
+        let mut color = Color;
+        self.blt(
+            &mut color,
+            EFI_GRAPHICS_OUTPUT_BLT_OPERATION::EfiBltVideoFill,
+            (0, 0),
+            (X, Y),
+            (Width, Height),
+            0,
+        )
+    }
+
+    /// Copies the rectangle of `Width` by `Height` pixels at `Source` of the screen to
+    /// `Destination` of `Buffer`, using [`EfiBltVideoToBltBuffer`].
+    ///
+    /// `Buffer` holds rows of `BufferWidth` pixels. Returns [`EFI_INVALID_PARAMETER`] without
+    /// calling the firmware if either rectangle is out of bounds.
+    ///
+    /// [`EfiBltVideoToBltBuffer`]: crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_OPERATION::EfiBltVideoToBltBuffer
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn blt_video_to_buffer(
+        &mut self,
+        Buffer: &mut [EFI_GRAPHICS_OUTPUT_BLT_PIXEL],
+        BufferWidth: usize,
+        Source: (usize, usize),
+        Destination: (usize, usize),
+        Width: usize,
+        Height: usize,
+    ) -> Result<(), EFI_STATUS> {
+        self.check_screen(Source.0, Source.1, Width, Height)?;
+        check_buffer(Buffer.len(), BufferWidth, Destination, Width, Height)?;
+        self.blt(
+            Buffer.as_mut_ptr(),
+            EFI_GRAPHICS_OUTPUT_BLT_OPERATION::EfiBltVideoToBltBuffer,
+            Source,
+            Destination,
+            (Width, Height),
+            BufferWidth,
+        )
+    }
+
+    /// Copies the rectangle of `Width` by `Height` pixels at `Source` of `Buffer` to
+    /// `Destination` of the screen, using [`EfiBltBufferToVideo`].
+    ///
+    /// `Buffer` holds rows of `BufferWidth` pixels. Returns [`EFI_INVALID_PARAMETER`] without
+    /// calling the firmware if either rectangle is out of bounds.
+    ///
+    /// [`EfiBltBufferToVideo`]: crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_OPERATION::EfiBltBufferToVideo
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn blt_buffer_to_video(
+        &mut self,
+        Buffer: &[EFI_GRAPHICS_OUTPUT_BLT_PIXEL],
+        BufferWidth: usize,
+        Source: (usize, usize),
+        Destination: (usize, usize),
+        Width: usize,
+        Height: usize,
+    ) -> Result<(), EFI_STATUS> {
+        check_buffer(Buffer.len(), BufferWidth, Source, Width, Height)?;
+        self.check_screen(Destination.0, Destination.1, Width, Height)?;
+        // The firmware only reads from the buffer for this operation.
+        self.blt(
+            Buffer.as_ptr() as *mut _,
+            EFI_GRAPHICS_OUTPUT_BLT_OPERATION::EfiBltBufferToVideo,
+            Source,
+            Destination,
+            (Width, Height),
+            BufferWidth,
+        )
+    }
+
+    /// Moves the rectangle of `Width` by `Height` pixels at `Source` of the screen to
+    /// `Destination`, using [`EfiBltVideoToVideo`]. The rectangles may overlap.
+    ///
+    /// [`EfiBltVideoToVideo`]: crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_OPERATION::EfiBltVideoToVideo
+    pub unsafe fn blt_video_to_video(
+        &mut self,
+        Source: (usize, usize),
+        Destination: (usize, usize),
+        Width: usize,
+        Height: usize,
+    ) -> Result<(), EFI_STATUS> {
+        self.check_screen(Source.0, Source.1, Width, Height)?;
+        self.check_screen(Destination.0, Destination.1, Width, Height)?;
+        self.blt(
+            ptr::null_mut(),
+            EFI_GRAPHICS_OUTPUT_BLT_OPERATION::EfiBltVideoToVideo,
+            Source,
+            Destination,
+            (Width, Height),
+            0,
+        )
+    }
+
+    unsafe fn blt(
+        &mut self,
+        Buffer: *mut EFI_GRAPHICS_OUTPUT_BLT_PIXEL,
+        Operation: EFI_GRAPHICS_OUTPUT_BLT_OPERATION,
+        Source: (usize, usize),
+        Destination: (usize, usize),
+        Size: (usize, usize),
+        BufferWidth: usize,
+    ) -> Result<(), EFI_STATUS> {
+        let delta = BufferWidth * core::mem::size_of::<EFI_GRAPHICS_OUTPUT_BLT_PIXEL>();
+        match self.Blt(
+            Buffer,
+            Operation,
+            Source.0,
+            Source.1,
+            Destination.0,
+            Destination.1,
+            Size.0,
+            Size.1,
+            delta,
+        ) {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    unsafe fn check_screen(
+        &self,
+        X: usize,
+        Y: usize,
+        Width: usize,
+        Height: usize,
+    ) -> Result<(), EFI_STATUS> {
+        let info = self.info();
+        match (X.checked_add(Width), Y.checked_add(Height)) {
+            (Some(right), Some(bottom))
+                if right <= info.HorizontalResolution as usize
+                    && bottom <= info.VerticalResolution as usize =>
+            {
+                Ok(())
+            }
+            _ => Err(EFI_INVALID_PARAMETER),
+        }
+    }
+
+    /// Returns the framebuffer of the current mode, or `None` if the mode has no framebuffer.
+    pub unsafe fn frame_buffer(&mut self) -> Option<FrameBuffer<'_>> {
+        let mode = self.mode();
+        FrameBuffer::new(
+            mode.FrameBufferBase as usize as *mut UINT8,
+            mode.FrameBufferSize,
+            &*mode.Info,
+        )
+    }
+}
+
+/// Checks that the rectangle of `Width` by `Height` pixels at `Position` fits in a buffer of
+/// `Length` pixels holding rows of `BufferWidth` pixels.
+fn check_buffer(
+    Length: usize,
+    BufferWidth: usize,
+    Position: (usize, usize),
+    Width: usize,
+    Height: usize,
+) -> Result<(), EFI_STATUS> {
+    if Width == 0 || Height == 0 {
+        return Ok(());
+    }
+
+    let end = Position
+        .0
+        .checked_add(Width)
+        .filter(|&right| right <= BufferWidth)
+        .and_then(|right| {
+            Position
+                .1
+                .checked_add(Height - 1)?
+                .checked_mul(BufferWidth)?
+                .checked_add(right)
+        });
+    match end {
+        Some(end) if end <= Length => Ok(()),
+        _ => Err(EFI_INVALID_PARAMETER),
+    }
+}
+
+/// Iterator over the modes of a graphics device, returned by
+/// [`EFI_GRAPHICS_OUTPUT_PROTOCOL::modes()`].
+///
+/// [`EFI_GRAPHICS_OUTPUT_PROTOCOL::modes()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::modes
+pub struct Modes<'a> {
+    protocol: &'a mut EFI_GRAPHICS_OUTPUT_PROTOCOL,
+    boot_services: &'a EFI_BOOT_SERVICES,
+    next: UINT32,
+    count: UINT32,
+}
+
+impl Iterator for Modes<'_> {
+    type Item = Result<(UINT32, EFI_GRAPHICS_OUTPUT_MODE_INFORMATION), EFI_STATUS>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.count {
+            return None;
+        }
+
+        let mode = self.next;
+        self.next += 1;
+        // SAFETY: the protocol was valid when the iterator was created.
+        let info = unsafe { self.protocol.query_mode(mode, self.boot_services) };
+        Some(info.map(|info| (mode, info)))
+    }
+}
+
+/// Information about the current mode of a graphics device.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_GRAPHICS_OUTPUT_PROTOCOL_MODE {
+    /// The number of modes supported by [`QueryMode()`] and [`SetMode()`].
+    ///
+    /// [`QueryMode()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::QueryMode
+    /// [`SetMode()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::SetMode
+    pub MaxMode: UINT32,
+    /// Current mode of the graphics device. Valid mode numbers are `0` to [`MaxMode`] - 1.
+    ///
+    /// [`MaxMode`]: ./struct.EFI_GRAPHICS_OUTPUT_PROTOCOL_MODE.html#structfield.MaxMode
+    pub Mode: UINT32,
+    /// Pointer to read-only [`EFI_GRAPHICS_OUTPUT_MODE_INFORMATION`] data.
+    ///
+    /// [`EFI_GRAPHICS_OUTPUT_MODE_INFORMATION`]: crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_MODE_INFORMATION
+    pub Info: *mut EFI_GRAPHICS_OUTPUT_MODE_INFORMATION,
+    /// Size of [`Info`] structure in bytes.
+    ///
+    /// [`Info`]: ./struct.EFI_GRAPHICS_OUTPUT_PROTOCOL_MODE.html#structfield.Info
+    pub SizeOfInfo: UINTN,
+    /// Base address of graphics linear frame buffer. Starting from `FrameBufferBase`, the frame
+    /// buffer contains [`VerticalResolution`] * [`PixelsPerScanLine`] pixels.
+    ///
+    /// [`VerticalResolution`]: ./struct.EFI_GRAPHICS_OUTPUT_MODE_INFORMATION.html#structfield.VerticalResolution
+    /// [`PixelsPerScanLine`]: ./struct.EFI_GRAPHICS_OUTPUT_MODE_INFORMATION.html#structfield.PixelsPerScanLine
+    pub FrameBufferBase: EFI_PHYSICAL_ADDRESS,
+    /// Amount of frame buffer needed to support the active mode as defined by
+    /// [`PixelsPerScanLine`] * [`VerticalResolution`] * pixel element size.
+    ///
+    /// [`PixelsPerScanLine`]: ./struct.EFI_GRAPHICS_OUTPUT_MODE_INFORMATION.html#structfield.PixelsPerScanLine
+    /// [`VerticalResolution`]: ./struct.EFI_GRAPHICS_OUTPUT_MODE_INFORMATION.html#structfield.VerticalResolution
+    pub FrameBufferSize: UINTN,
+}
+
+/// Information about a mode of a graphics device.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_GRAPHICS_OUTPUT_MODE_INFORMATION {
+    /// The version of this data structure. A value of zero represents the structure as defined
+    /// in this specification.
+    pub Version: UINT32,
+    /// The size of video screen in pixels in the X dimension.
+    pub HorizontalResolution: UINT32,
+    /// The size of video screen in pixels in the Y dimension.
+    pub VerticalResolution: UINT32,
+    /// Enumeration that defines the physical format of the pixel. A value of [`PixelBltOnly`]
+    /// implies that a linear frame buffer is not available for this mode.
+    ///
+    /// [`PixelBltOnly`]: crate::protocols::console::graphics_output::PixelBltOnly
+    pub PixelFormat: EFI_GRAPHICS_PIXEL_FORMAT,
+    /// The bit mask of the pixel format. Only valid if [`PixelFormat`] is [`PixelBitMask`].
+    ///
+    /// [`PixelFormat`]: ./struct.EFI_GRAPHICS_OUTPUT_MODE_INFORMATION.html#structfield.PixelFormat
+    /// [`PixelBitMask`]: crate::protocols::console::graphics_output::PixelBitMask
+    pub PixelInformation: EFI_PIXEL_BITMASK,
+    /// Defines the number of pixel elements per video memory line. This may be larger than
+    /// [`HorizontalResolution`] for performance reasons.
+    ///
+    /// [`HorizontalResolution`]: ./struct.EFI_GRAPHICS_OUTPUT_MODE_INFORMATION.html#structfield.HorizontalResolution
+    pub PixelsPerScanLine: UINT32,
+}
+
+/// The bits of a pixel that hold each color component, for the [`PixelBitMask`] format.
+///
+/// The bits of the masks must not overlap, and the bits of each mask must be contiguous.
+///
+/// [`PixelBitMask`]: crate::protocols::console::graphics_output::PixelBitMask
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_PIXEL_BITMASK {
+    /// The bits used for red.
+    pub RedMask: UINT32,
+    /// The bits used for green.
+    pub GreenMask: UINT32,
+    /// The bits used for blue.
+    pub BlueMask: UINT32,
+    /// The reserved bits.
+    pub ReservedMask: UINT32,
+}
+
+/// A pixel as used by [`Blt()`].
+///
+/// [`Blt()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::Blt
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[repr(C)]
+pub struct EFI_GRAPHICS_OUTPUT_BLT_PIXEL {
+    /// The blue component.
+    pub Blue: UINT8,
+    /// The green component.
+    pub Green: UINT8,
+    /// The red component.
+    pub Red: UINT8,
+    /// Reserved, must be zero.
+    pub Reserved: UINT8,
+}
+
+impl EFI_GRAPHICS_OUTPUT_BLT_PIXEL {
+    /// Creates a pixel from its red, green and blue components.
+    pub const fn new(Red: UINT8, Green: UINT8, Blue: UINT8) -> Self {
+        Self {
+            Blue,
+            Green,
+            Red,
+            Reserved: 0,
+        }
+    }
+}
+
+/// The operation performed by [`Blt()`].
+///
+/// [`Blt()`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL::Blt
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub enum EFI_GRAPHICS_OUTPUT_BLT_OPERATION {
+    /// Write data from the first pixel of the buffer directly to every pixel of the destination
+    /// rectangle on the video display.
+    EfiBltVideoFill,
+    /// Read data from the source rectangle on the video display into the destination rectangle
+    /// of the buffer.
+    EfiBltVideoToBltBuffer,
+    /// Write data from the source rectangle of the buffer directly to the destination rectangle
+    /// on the video display.
+    EfiBltBufferToVideo,
+    /// Copy from the source rectangle on the video display to the destination rectangle on the
+    /// video display.
+    EfiBltVideoToVideo,
+    /// The number of operations.
+    EfiGraphicsOutputBltOperationMax,
+}
+
+/// Stride-aware access to the linear framebuffer of a graphics mode.
+///
+/// Pixels are given as [`EFI_GRAPHICS_OUTPUT_BLT_PIXEL`]s and encoded to the pixel format of the
+/// mode, which may be [`PixelRedGreenBlueReserved8BitPerColor`],
+/// [`PixelBlueGreenRedReserved8BitPerColor`] or [`PixelBitMask`]. Rows are
+/// [`PixelsPerScanLine`] pixels apart, which may be more than the visible width.
+///
+/// [`EFI_GRAPHICS_OUTPUT_BLT_PIXEL`]: crate::protocols::console::graphics_output::EFI_GRAPHICS_OUTPUT_BLT_PIXEL
+/// [`PixelRedGreenBlueReserved8BitPerColor`]: crate::protocols::console::graphics_output::PixelRedGreenBlueReserved8BitPerColor
+/// [`PixelBlueGreenRedReserved8BitPerColor`]: crate::protocols::console::graphics_output::PixelBlueGreenRedReserved8BitPerColor
+/// [`PixelBitMask`]: crate::protocols::console::graphics_output::PixelBitMask
+/// [`PixelsPerScanLine`]: ./struct.EFI_GRAPHICS_OUTPUT_MODE_INFORMATION.html#structfield.PixelsPerScanLine
+pub struct FrameBuffer<'a> {
+    base: *mut UINT8,
+    size: usize,
+    info: EFI_GRAPHICS_OUTPUT_MODE_INFORMATION,
+    bytes_per_pixel: usize,
+    _marker: PhantomData<&'a mut [UINT8]>,
+}
+
+impl<'a> FrameBuffer<'a> {
+    /// Creates a framebuffer of `Size` bytes at `Base` laid out as described by `Info`.
+    ///
+    /// Returns `None` if the pixel format has no framebuffer or is not valid.
+    ///
+    /// # Safety
+    ///
+    /// `Base` must be valid for reads and writes of `Size` bytes for the lifetime `'a`.
+    pub unsafe fn new(
+        Base: *mut UINT8,
+        Size: usize,
+        Info: &EFI_GRAPHICS_OUTPUT_MODE_INFORMATION,
+    ) -> Option<Self> {
+        let bytes_per_pixel = match Info.PixelFormat {
+            PixelRedGreenBlueReserved8BitPerColor | PixelBlueGreenRedReserved8BitPerColor => 4,
+            PixelBitMask => {
+                let mask = Info.PixelInformation;
+                let bits = mask.RedMask | mask.GreenMask | mask.BlueMask | mask.ReservedMask;
+                (32 - bits.leading_zeros() as usize + 7) / 8
+            }
+            _ => 0,
+        };
+        if Base.is_null() || bytes_per_pixel == 0 {
+            return None;
+        }
+
+        Some(Self {
+            base: Base,
+            size: Size,
+            info: *Info,
+            bytes_per_pixel,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the information of the mode the framebuffer belongs to.
+    pub fn info(&self) -> &EFI_GRAPHICS_OUTPUT_MODE_INFORMATION {
+        &self.info
+    }
+
+    /// Returns the visible width in pixels.
+    pub fn width(&self) -> usize {
+        self.info.HorizontalResolution as usize
+    }
+
+    /// Returns the visible height in pixels.
+    pub fn height(&self) -> usize {
+        self.info.VerticalResolution as usize
+    }
+
+    /// Returns the distance between rows in pixels.
+    pub fn stride(&self) -> usize {
+        self.info.PixelsPerScanLine as usize
+    }
+
+    /// Returns the size of a pixel in bytes.
+    pub fn bytes_per_pixel(&self) -> usize {
+        self.bytes_per_pixel
+    }
+
+    /// Returns a pointer to the start of the framebuffer.
+    pub fn as_mut_ptr(&mut self) -> *mut UINT8 {
+        self.base
+    }
+
+    /// Encodes `Pixel` in the pixel format of the framebuffer.
+    pub fn encode(&self, Pixel: EFI_GRAPHICS_OUTPUT_BLT_PIXEL) -> UINT32 {
+        match self.info.PixelFormat {
+            PixelRedGreenBlueReserved8BitPerColor => {
+                UINT32::from_le_bytes([Pixel.Red, Pixel.Green, Pixel.Blue, 0])
+            }
+            PixelBlueGreenRedReserved8BitPerColor => {
+                UINT32::from_le_bytes([Pixel.Blue, Pixel.Green, Pixel.Red, 0])
+            }
+            _ => {
+                let mask = self.info.PixelInformation;
+                to_mask(Pixel.Red, mask.RedMask)
+                    | to_mask(Pixel.Green, mask.GreenMask)
+                    | to_mask(Pixel.Blue, mask.BlueMask)
+            }
+        }
+    }
+
+    /// Decodes a value in the pixel format of the framebuffer.
+    pub fn decode(&self, Value: UINT32) -> EFI_GRAPHICS_OUTPUT_BLT_PIXEL {
+        match self.info.PixelFormat {
+            PixelRedGreenBlueReserved8BitPerColor => {
+                let [red, green, blue, _] = Value.to_le_bytes();
+                EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(red, green, blue)
+            }
+            PixelBlueGreenRedReserved8BitPerColor => {
+                let [blue, green, red, _] = Value.to_le_bytes();
+                EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(red, green, blue)
+            }
+            _ => {
+                let mask = self.info.PixelInformation;
+                EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(
+                    from_mask(Value, mask.RedMask),
+                    from_mask(Value, mask.GreenMask),
+                    from_mask(Value, mask.BlueMask),
+                )
+            }
+        }
+    }
+
+    /// Writes `Pixel` at (`X`, `Y`).
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if the position is outside of the visible area.
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub fn write_pixel(
+        &mut self,
+        X: usize,
+        Y: usize,
+        Pixel: EFI_GRAPHICS_OUTPUT_BLT_PIXEL,
+    ) -> Result<(), EFI_STATUS> {
+        let value = self.encode(Pixel);
+        self.write_raw(X, Y, value)
+    }
+
+    /// Writes the already encoded `Value` at (`X`, `Y`).
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if the position is outside of the visible area.
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub fn write_raw(&mut self, X: usize, Y: usize, Value: UINT32) -> Result<(), EFI_STATUS> {
+        let offset = self.offset(X, Y).ok_or(EFI_INVALID_PARAMETER)?;
+        // SAFETY: `offset()` checked that the pixel is within the framebuffer.
+        unsafe { self.store(offset, Value) };
+        Ok(())
+    }
+
+    /// Writes `Pixels` to the row `Y`, starting at column `X`.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] without writing anything if the span does not fit in
+    /// the visible area.
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub fn write_span(
+        &mut self,
+        X: usize,
+        Y: usize,
+        Pixels: &[EFI_GRAPHICS_OUTPUT_BLT_PIXEL],
+    ) -> Result<(), EFI_STATUS> {
+        if Pixels.is_empty() {
+            return Ok(());
+        }
+
+        let last = X
+            .checked_add(Pixels.len() - 1)
+            .ok_or(EFI_INVALID_PARAMETER)?;
+        let start = self.offset(X, Y).ok_or(EFI_INVALID_PARAMETER)?;
+        self.offset(last, Y).ok_or(EFI_INVALID_PARAMETER)?;
+        for (i, &pixel) in Pixels.iter().enumerate() {
+            let value = self.encode(pixel);
+            // SAFETY: the first and last pixels of the span are within the framebuffer.
+            unsafe { self.store(start + i * self.bytes_per_pixel, value) };
+        }
+
+        Ok(())
+    }
+
+    /// Reads the pixel at (`X`, `Y`).
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if the position is outside of the visible area.
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub fn read_pixel(
+        &self,
+        X: usize,
+        Y: usize,
+    ) -> Result<EFI_GRAPHICS_OUTPUT_BLT_PIXEL, EFI_STATUS> {
+        let offset = self.offset(X, Y).ok_or(EFI_INVALID_PARAMETER)?;
+        let mut bytes = [0; 4];
+        for (i, byte) in bytes.iter_mut().enumerate().take(self.bytes_per_pixel) {
+            // SAFETY: `offset()` checked that the pixel is within the framebuffer.
+            *byte = unsafe { ptr::read_volatile(self.base.add(offset + i)) };
+        }
+
+        Ok(self.decode(UINT32::from_le_bytes(bytes)))
+    }
+
+    /// Returns the byte offset of the pixel at (`X`, `Y`), if it is visible and within the
+    /// framebuffer.
+    fn offset(&self, X: usize, Y: usize) -> Option<usize> {
+        if X >= self.width() || Y >= self.height() {
+            return None;
+        }
+
+        let offset = Y
+            .checked_mul(self.stride())?
+            .checked_add(X)?
+            .checked_mul(self.bytes_per_pixel)?;
+        match offset.checked_add(self.bytes_per_pixel) {
+            Some(end) if end <= self.size => Some(offset),
+            _ => None,
+        }
+    }
+
+    /// Stores `Value` at `Offset`.
+    ///
+    /// # Safety
+    ///
+    /// The pixel at `Offset` must be within the framebuffer.
+    unsafe fn store(&mut self, Offset: usize, Value: UINT32) {
+        let pixel = self.base.add(Offset);
+        if self.bytes_per_pixel == 4 && pixel as usize % 4 == 0 {
+            ptr::write_volatile(pixel.cast::<UINT32>(), Value);
+        } else {
+            for (i, byte) in Value
+                .to_le_bytes()
+                .iter()
+                .take(self.bytes_per_pixel)
+                .enumerate()
+            {
+                ptr::write_volatile(pixel.add(i), *byte);
+            }
+        }
+    }
+}
+
+/// Scales the 8-bit `Component` to the bits of `Mask`.
+fn to_mask(Component: UINT8, Mask: UINT32) -> UINT32 {
+    if Mask == 0 {
+        return 0;
+    }
+
+    let max = u64::from(Mask >> Mask.trailing_zeros());
+    (((u64::from(Component) * max + 127) / 255) as UINT32) << Mask.trailing_zeros()
+}
+
+/// Scales the bits of `Value` selected by `Mask` to an 8-bit component.
+fn from_mask(Value: UINT32, Mask: UINT32) -> UINT8 {
+    if Mask == 0 {
+        return 0;
+    }
+
+    let max = u64::from(Mask >> Mask.trailing_zeros());
+    (u64::from((Value & Mask) >> Mask.trailing_zeros()) * 255 / max) as UINT8
+}
+
+type EFI_GRAPHICS_OUTPUT_PROTOCOL_QUERY_MODE = extern "efiapi" fn(
+    This: *mut EFI_GRAPHICS_OUTPUT_PROTOCOL,
+    ModeNumber: UINT32,
+    SizeOfInfo: *mut UINTN,
+    Info: *mut *mut EFI_GRAPHICS_OUTPUT_MODE_INFORMATION,
+) -> EFI_STATUS;
+
+type EFI_GRAPHICS_OUTPUT_PROTOCOL_SET_MODE =
+    extern "efiapi" fn(This: *mut EFI_GRAPHICS_OUTPUT_PROTOCOL, ModeNumber: UINT32) -> EFI_STATUS;
+
+type EFI_GRAPHICS_OUTPUT_PROTOCOL_BLT = extern "efiapi" fn(
+    This: *mut EFI_GRAPHICS_OUTPUT_PROTOCOL,
+    BltBuffer: *mut EFI_GRAPHICS_OUTPUT_BLT_PIXEL,
+    BltOperation: EFI_GRAPHICS_OUTPUT_BLT_OPERATION,
+    SourceX: UINTN,
+    SourceY: UINTN,
+    DestinationX: UINTN,
+    DestinationY: UINTN,
+    Width: UINTN,
+    Height: UINTN,
+    Delta: UINTN,
+) -> EFI_STATUS;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: EFI_GRAPHICS_OUTPUT_BLT_PIXEL = EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0xFF, 0x00, 0x00);
+    const ORANGE: EFI_GRAPHICS_OUTPUT_BLT_PIXEL =
+        EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0xFF, 0x80, 0x10);
+    const NO_MASK: EFI_PIXEL_BITMASK = EFI_PIXEL_BITMASK {
+        RedMask: 0,
+        GreenMask: 0,
+        BlueMask: 0,
+        ReservedMask: 0,
+    };
+
+    fn mode(
+        PixelFormat: EFI_GRAPHICS_PIXEL_FORMAT,
+        PixelInformation: EFI_PIXEL_BITMASK,
+        Width: UINT32,
+        Height: UINT32,
+        Stride: UINT32,
+    ) -> EFI_GRAPHICS_OUTPUT_MODE_INFORMATION {
+        EFI_GRAPHICS_OUTPUT_MODE_INFORMATION {
+            Version: 0,
+            HorizontalResolution: Width,
+            VerticalResolution: Height,
+            PixelFormat,
+            PixelInformation,
+            PixelsPerScanLine: Stride,
+        }
+    }
+
+    fn frame<'a>(
+        Memory: &'a mut [UINT8],
+        Info: &EFI_GRAPHICS_OUTPUT_MODE_INFORMATION,
+    ) -> FrameBuffer<'a> {
+        unsafe { FrameBuffer::new(Memory.as_mut_ptr(), Memory.len(), Info) }.unwrap()
+    }
+
+    #[test]
+    fn rgb_and_bgr_pixels() {
+        let mut memory = [0; 16];
+        let info = mode(PixelRedGreenBlueReserved8BitPerColor, NO_MASK, 2, 2, 2);
+        let mut fb = frame(&mut memory, &info);
+        assert_eq!(fb.bytes_per_pixel(), 4);
+        assert_eq!(fb.encode(ORANGE), 0x0010_80FF);
+        fb.write_pixel(1, 0, ORANGE).unwrap();
+        assert_eq!(fb.read_pixel(1, 0), Ok(ORANGE));
+        assert_eq!(memory[4..8], [0xFF, 0x80, 0x10, 0x00]);
+
+        let mut memory = [0; 16];
+        let info = mode(PixelBlueGreenRedReserved8BitPerColor, NO_MASK, 2, 2, 2);
+        let mut fb = frame(&mut memory, &info);
+        assert_eq!(fb.encode(ORANGE), 0x00FF_8010);
+        assert_eq!(
+            fb.decode(0xAA00_FF00),
+            EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0, 0xFF, 0)
+        );
+        fb.write_pixel(0, 1, ORANGE).unwrap();
+        assert_eq!(fb.read_pixel(0, 1), Ok(ORANGE));
+        assert_eq!(memory[8..12], [0x10, 0x80, 0xFF, 0x00]);
+    }
+
+    #[test]
+    fn bitmask_pixels() {
+        // RGB 5:6:5, packed into two bytes per pixel.
+        let mask = EFI_PIXEL_BITMASK {
+            RedMask: 0xF800,
+            GreenMask: 0x07E0,
+            BlueMask: 0x001F,
+            ReservedMask: 0,
+        };
+        let mut memory = [0; 8];
+        let info = mode(PixelBitMask, mask, 2, 2, 2);
+        let mut fb = frame(&mut memory, &info);
+        assert_eq!(fb.bytes_per_pixel(), 2);
+        assert_eq!(fb.encode(RED), 0xF800);
+        assert_eq!(
+            fb.encode(EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0xFF, 0xFF, 0xFF)),
+            0xFFFF
+        );
+        assert_eq!(
+            fb.decode(0x07E0),
+            EFI_GRAPHICS_OUTPUT_BLT_PIXEL::new(0, 0xFF, 0)
+        );
+        fb.write_pixel(1, 1, RED).unwrap();
+        assert_eq!(memory, [0, 0, 0, 0, 0, 0, 0x00, 0xF8]);
+
+        // Components at the top of each byte of a three byte pixel, with unused bits between
+        // them.
+        let mask = EFI_PIXEL_BITMASK {
+            RedMask: 0x00F8_0000,
+            GreenMask: 0x0000_FC00,
+            BlueMask: 0x0000_00F8,
+            ReservedMask: 0,
+        };
+        let mut memory = [0; 12];
+        let info = mode(PixelBitMask, mask, 2, 2, 2);
+        let mut fb = frame(&mut memory, &info);
+        assert_eq!(fb.bytes_per_pixel(), 3);
+        assert_eq!(fb.encode(ORANGE), 0x00F8_8010);
+        assert_eq!(fb.decode(0x00F8_8010 | 0x0007_0307), fb.decode(0x00F8_8010));
+        fb.write_pixel(1, 0, ORANGE).unwrap();
+        fb.write_pixel(0, 1, RED).unwrap();
+        let pixel = fb.read_pixel(1, 0).unwrap();
+        assert_eq!((pixel.Red, pixel.Green, pixel.Blue), (0xFF, 0x81, 0x10));
+        assert_eq!(fb.read_pixel(0, 1), Ok(RED));
+        assert_eq!(
+            memory,
+            [0, 0, 0, 0x10, 0x80, 0xF8, 0x00, 0x00, 0xF8, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn formats_without_framebuffer() {
+        let mut memory = [0; 16];
+        for info in [
+            mode(PixelBltOnly, NO_MASK, 2, 2, 2),
+            mode(PixelBitMask, NO_MASK, 2, 2, 2),
+        ] {
+            let fb = unsafe { FrameBuffer::new(memory.as_mut_ptr(), memory.len(), &info) };
+            assert!(fb.is_none());
+        }
+    }
+
+    #[test]
+    fn mask_scaling() {
+        assert_eq!(to_mask(0xFF, 0), 0);
+        assert_eq!(from_mask(0xFFFF_FFFF, 0), 0);
+        assert_eq!(to_mask(0xFF, 0x001F), 0x001F);
+        assert_eq!(to_mask(0x80, 0x001F), 16);
+        assert_eq!(to_mask(0x80, 0x03E0), 16 << 5);
+        assert_eq!(from_mask(16 << 5, 0x03E0), 131);
+        assert_eq!(from_mask(0xFFFF_FFFF, 0x03E0), 0xFF);
+        assert_eq!(to_mask(0xFF, 0xFFC0_0000), 0xFFC0_0000);
+
+        for component in 0..=0xFF {
+            assert_eq!(from_mask(to_mask(component, 0xFF00), 0xFF00), component);
+        }
+        for value in 0..0x40 {
+            assert_eq!(to_mask(from_mask(value << 5, 0x07E0), 0x07E0), value << 5);
+        }
+    }
+
+    #[test]
+    fn spans() {
+        // Three visible pixels per row, and a fourth padding pixel.
+        let mut memory = [0; 32];
+        let info = mode(PixelRedGreenBlueReserved8BitPerColor, NO_MASK, 3, 2, 4);
+        let mut fb = frame(&mut memory, &info);
+        assert_eq!(fb.write_span(0, 0, &[RED; 3]), Ok(()));
+        assert_eq!(
+            fb.write_span(1, 1, &[ORANGE; 3]),
+            Err(EFI_INVALID_PARAMETER)
+        );
+        assert_eq!(fb.write_span(3, 0, &[ORANGE]), Err(EFI_INVALID_PARAMETER));
+        assert_eq!(fb.write_span(0, 2, &[ORANGE]), Err(EFI_INVALID_PARAMETER));
+        assert_eq!(fb.write_span(usize::MAX, 0, &[]), Ok(()));
+        assert_eq!(fb.write_span(1, 1, &[ORANGE; 2]), Ok(()));
+
+        for x in 0..3 {
+            assert_eq!(fb.read_pixel(x, 0), Ok(RED));
+        }
+        assert_eq!(fb.read_pixel(3, 0), Err(EFI_INVALID_PARAMETER));
+        assert_eq!(
+            fb.read_pixel(0, 1),
+            Ok(EFI_GRAPHICS_OUTPUT_BLT_PIXEL::default())
+        );
+        assert_eq!(fb.read_pixel(2, 1), Ok(ORANGE));
+        // The padding pixels were left untouched.
+        assert_eq!(memory[12..16], [0; 4]);
+        assert_eq!(memory[28..32], [0; 4]);
+    }
+
+    #[test]
+    fn truncated_framebuffer() {
+        // The last row ends at the last visible pixel, without padding.
+        let mut memory = [0; 28];
+        let info = mode(PixelRedGreenBlueReserved8BitPerColor, NO_MASK, 3, 2, 4);
+        let mut fb = frame(&mut memory, &info);
+        assert_eq!(fb.write_span(0, 1, &[RED; 3]), Ok(()));
+
+        let mut fb = frame(&mut memory[..27], &info);
+        assert_eq!(
+            fb.write_span(0, 1, &[ORANGE; 3]),
+            Err(EFI_INVALID_PARAMETER)
+        );
+        assert_eq!(fb.write_pixel(2, 1, ORANGE), Err(EFI_INVALID_PARAMETER));
+        assert_eq!(fb.read_pixel(1, 1), Ok(RED));
+    }
+}
@@ -0,0 +1,919 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # Line Editor
+//!
+//! This module provides [`LineEditor`], which reads a line of text from the keyboard with
+//! readline-style editing, history and completion, and [`Keyboard`], which reads keystrokes
+//! with [`ReadKeyStrokeEx()`], falling back to [`ReadKeyStroke()`] when the
+//! [`EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL`] is not available.
+//!
+//! The line is displayed on a [`ConsoleSink`], usually a [`TextOutputSink`] over the
+//! [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`] of the console, and is scrolled horizontally when it is
+//! longer than the rest of the row. The following keys are recognized, where `Ctrl` keys are
+//! read from the shift state of [`EFI_KEY_DATA`], or as control characters:
+//!
+//! | Key                          | Action                                              |
+//! | ---------------------------- | --------------------------------------------------- |
+//! | Left, `Ctrl-B`               | Move the cursor one character back                  |
+//! | Right, `Ctrl-F`              | Move the cursor one character forward               |
+//! | Home, `Ctrl-A`               | Move the cursor to the start of the line            |
+//! | End, `Ctrl-E`                | Move the cursor to the end of the line              |
+//! | Backspace                    | Delete the character before the cursor              |
+//! | Delete, `Ctrl-D`             | Delete the character at the cursor                  |
+//! | `Ctrl-K`                     | Delete from the cursor to the end of the line       |
+//! | `Ctrl-U`                     | Delete from the start of the line to the cursor     |
+//! | `Ctrl-W`                     | Delete the word before the cursor                   |
+//! | Escape                       | Delete the whole line                               |
+//! | Up, `Ctrl-P`                 | Show the previous line of the history               |
+//! | Down, `Ctrl-N`               | Show the next line of the history                   |
+//! | Tab                          | Insert the completion returned by the completer     |
+//! | Enter                        | Accept the line                                     |
+//! | `Ctrl-C`                     | Cancel the line                                     |
+//! | `Ctrl-D` on an empty line    | End the input                                       |
+//!
+//! [`LineEditor`]: crate::protocols::console::line_editor::LineEditor
+//! [`Keyboard`]: crate::protocols::console::line_editor::Keyboard
+//! [`ReadKeyStrokeEx()`]: crate::protocols::console::EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL::ReadKeyStrokeEx
+//! [`ReadKeyStroke()`]: crate::protocols::console::EFI_SIMPLE_TEXT_INPUT_PROTOCOL::ReadKeyStroke
+//! [`EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL
+//! [`ConsoleSink`]: crate::protocols::console::multiplex::ConsoleSink
+//! [`TextOutputSink`]: crate::protocols::console::multiplex::TextOutputSink
+//! [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL
+//! [`EFI_KEY_DATA`]: crate::protocols::console::simple_text_input_ex::EFI_KEY_DATA
+
+use crate::protocols::console::multiplex::{ConsoleSink, DEFAULT_CONSOLE_SIZE};
+use crate::protocols::console::simple_text_input::{
+    CHAR_BACKSPACE, CHAR_CARRIAGE_RETURN, CHAR_LINEFEED, CHAR_TAB, SCAN_DELETE, SCAN_DOWN,
+    SCAN_END, SCAN_ESC, SCAN_HOME, SCAN_LEFT, SCAN_RIGHT, SCAN_UP,
+};
+use crate::protocols::console::simple_text_input_ex::{
+    EFI_KEY_DATA, EFI_KEY_STATE, EFI_LEFT_CONTROL_PRESSED, EFI_RIGHT_CONTROL_PRESSED,
+    EFI_SHIFT_STATE_VALID,
+};
+use crate::protocols::console::{
+    EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL, EFI_SIMPLE_TEXT_INPUT_PROTOCOL,
+};
+use crate::status::{EFI_ABORTED, EFI_UNSUPPORTED};
+use crate::types::{CHAR16, EFI_EVENT, EFI_STATUS, UINTN};
+
+/// Backspaces written to move the cursor back.
+const BACKSPACES: &str =
+    "\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}";
+
+/// Spaces written to erase the end of the displayed line.
+const SPACES: &str = "                ";
+
+/// Returns the control character typed with `Ctrl` and `Letter`.
+const fn ctrl(Letter: u8) -> CHAR16 {
+    (Letter & 0x1F) as CHAR16
+}
+
+/// A source of keystrokes, reading from the [`EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL`] if there is
+/// one and otherwise from the [`EFI_SIMPLE_TEXT_INPUT_PROTOCOL`].
+///
+/// [`EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL
+/// [`EFI_SIMPLE_TEXT_INPUT_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_INPUT_PROTOCOL
+pub struct Keyboard<'a> {
+    ex: Option<&'a mut EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL>,
+    simple: Option<&'a mut EFI_SIMPLE_TEXT_INPUT_PROTOCOL>,
+}
+
+impl<'a> Keyboard<'a> {
+    /// Creates a keyboard reading from `Ex`, or from `Simple` if `Ex` is `None` or does not
+    /// support reading keystrokes.
+    ///
+    /// # Safety
+    ///
+    /// The protocols must be valid for the lifetime `'a`.
+    pub unsafe fn new(
+        Ex: Option<&'a mut EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL>,
+        Simple: Option<&'a mut EFI_SIMPLE_TEXT_INPUT_PROTOCOL>,
+    ) -> Self {
+        Self {
+            ex: Ex,
+            simple: Simple,
+        }
+    }
+
+    /// Returns the event signaled when a key is available, to use with
+    /// [`EFI_BOOT_SERVICES.WaitForEvent()`].
+    ///
+    /// [`EFI_BOOT_SERVICES.WaitForEvent()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-waitforevent
+    pub fn wait_event(&self) -> Option<EFI_EVENT> {
+        match (&self.ex, &self.simple) {
+            (Some(ex), _) => Some(ex.WaitForKeyEx),
+            (None, Some(simple)) => Some(simple.WaitForKey),
+            (None, None) => None,
+        }
+    }
+
+    /// Reads the next keystroke, or returns `None` if there is none.
+    ///
+    /// Keystrokes read with [`ReadKeyStroke()`] have no shift or toggle state. Fails with
+    /// [`EFI_UNSUPPORTED`] if there is no protocol to read from.
+    ///
+    /// [`ReadKeyStroke()`]: crate::protocols::console::EFI_SIMPLE_TEXT_INPUT_PROTOCOL::ReadKeyStroke
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    pub fn read_key(&mut self) -> Result<Option<EFI_KEY_DATA>, EFI_STATUS> {
+        if let Some(ex) = self.ex.as_mut() {
+            // SAFETY: `new()` requires the protocol to be valid.
+            match unsafe { ex.read_key_stroke_ex() } {
+                Err(EFI_UNSUPPORTED) if self.simple.is_some() => self.ex = None,
+                result => return result,
+            }
+        }
+
+        let simple = self.simple.as_mut().ok_or(EFI_UNSUPPORTED)?;
+        // SAFETY: `new()` requires the protocol to be valid.
+        let key = unsafe { simple.read_key_stroke()? };
+        Ok(key.map(|key| EFI_KEY_DATA {
+            Key: key,
+            KeyState: EFI_KEY_STATE {
+                KeyShiftState: 0,
+                KeyToggleState: 0,
+            },
+        }))
+    }
+}
+
+/// How the editing of a line ended.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LineEvent {
+    /// The line was accepted with Enter.
+    Accepted,
+    /// The line was cancelled with `Ctrl-C`.
+    Cancelled,
+    /// The input was ended with `Ctrl-D` on an empty line.
+    EndOfInput,
+}
+
+/// A callback completing the text before the cursor, returning the text to insert.
+pub type Completer<'a> = dyn FnMut(&str) -> Option<&'a str> + 'a;
+
+/// Edits a line of up to `N` bytes of UTF-8, keeping the last `H` accepted lines as history.
+///
+/// [`read_line()`] reads and edits a line until it is accepted. To wait for keys in another
+/// way, for example together with other events, call [`start()`] and then [`handle_key()`] for
+/// each key.
+///
+/// [`read_line()`]: crate::protocols::console::line_editor::LineEditor::read_line
+/// [`start()`]: crate::protocols::console::line_editor::LineEditor::start
+/// [`handle_key()`]: crate::protocols::console::line_editor::LineEditor::handle_key
+pub struct LineEditor<'a, const N: usize, const H: usize> {
+    line: [u8; N],
+    length: usize,
+    cursor: usize,
+    history: [([u8; N], usize); H],
+    history_count: usize,
+    history_start: usize,
+    browsing: Option<usize>,
+    draft: [u8; N],
+    draft_length: usize,
+    completer: Option<&'a mut Completer<'a>>,
+    width: UINTN,
+    offset: usize,
+    screen: usize,
+    drawn: usize,
+}
+
+impl<'a, const N: usize, const H: usize> LineEditor<'a, N, H> {
+    /// Creates an editor with an empty history.
+    pub fn new() -> Self {
+        Self {
+            line: [0; N],
+            length: 0,
+            cursor: 0,
+            history: [([0; N], 0); H],
+            history_count: 0,
+            history_start: 0,
+            browsing: None,
+            draft: [0; N],
+            draft_length: 0,
+            completer: None,
+            width: 1,
+            offset: 0,
+            screen: 0,
+            drawn: 0,
+        }
+    }
+
+    /// Sets the callback called on Tab with the text before the cursor, returning the text to
+    /// insert at the cursor, if any.
+    pub fn set_completer(&mut self, Completer: &'a mut Completer<'a>) {
+        self.completer = Some(Completer);
+    }
+
+    /// Returns the line.
+    pub fn line(&self) -> &str {
+        // The line only ever holds whole characters.
+        core::str::from_utf8(&self.line[..self.length]).unwrap_or_default()
+    }
+
+    /// Returns the byte offset of the cursor in the line.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns the number of lines in the history.
+    pub fn history_len(&self) -> usize {
+        self.history_count
+    }
+
+    /// Returns the line of the history at `Index`, counted from the most recent one.
+    pub fn history_entry(&self, Index: usize) -> Option<&str> {
+        if Index >= self.history_count {
+            return None;
+        }
+
+        let (line, length) =
+            &self.history[(self.history_start + self.history_count - 1 - Index) % H];
+        core::str::from_utf8(&line[..*length]).ok()
+    }
+
+    /// Adds `Line` to the history, unless it is empty, longer than `N` bytes, or the same as
+    /// the most recent line. The oldest line is dropped when the history is full.
+    pub fn add_history(&mut self, Line: &str) {
+        if H == 0 || Line.is_empty() || Line.len() > N || self.history_entry(0) == Some(Line) {
+            return;
+        }
+
+        let index = if self.history_count < H {
+            self.history_count += 1;
+            (self.history_start + self.history_count - 1) % H
+        } else {
+            let index = self.history_start;
+            self.history_start = (self.history_start + 1) % H;
+            index
+        };
+        self.history[index].0[..Line.len()].copy_from_slice(Line.as_bytes());
+        self.history[index].1 = Line.len();
+    }
+
+    /// Clears the history.
+    pub fn clear_history(&mut self) {
+        self.history_count = 0;
+        self.history_start = 0;
+        self.browsing = None;
+    }
+
+    /// Writes `Prompt` and starts editing an empty line after it.
+    pub fn start<S: ConsoleSink + ?Sized>(
+        &mut self,
+        Output: &mut S,
+        Prompt: &str,
+    ) -> Result<(), EFI_STATUS> {
+        Output.output_string(Prompt)?;
+        Output.flush()?;
+
+        let (columns, _) = Output.size().unwrap_or(DEFAULT_CONSOLE_SIZE);
+        let column = match Output.cursor_position() {
+            Some((column, _)) => column,
+            None => Prompt.chars().count() % columns.max(1),
+        };
+        // Leave the last column free, as writing it moves the cursor to the next row.
+        self.width = columns.saturating_sub(column + 1).max(1);
+        self.length = 0;
+        self.cursor = 0;
+        self.browsing = None;
+        self.offset = 0;
+        self.screen = 0;
+        self.drawn = 0;
+        Ok(())
+    }
+
+    /// Handles `Key`, updating the displayed line, and returns how the editing ended, if it did.
+    ///
+    /// Accepted lines are added to the history.
+    pub fn handle_key<S: ConsoleSink + ?Sized>(
+        &mut self,
+        Output: &mut S,
+        Key: EFI_KEY_DATA,
+    ) -> Result<Option<LineEvent>, EFI_STATUS> {
+        let shift = Key.KeyState.KeyShiftState;
+        let control = shift & EFI_SHIFT_STATE_VALID != 0
+            && shift & (EFI_LEFT_CONTROL_PRESSED | EFI_RIGHT_CONTROL_PRESSED) != 0;
+        let char = match Key.Key.UnicodeChar {
+            char if control && (char as u8).is_ascii_alphabetic() && char < 0x80 => {
+                ctrl(char as u8)
+            }
+            char => char,
+        };
+
+        match (Key.Key.ScanCode, char) {
+            (SCAN_LEFT, _) | (_, 0x02) => self.cursor = self.previous(self.cursor),
+            (SCAN_RIGHT, _) | (_, 0x06) => self.cursor = self.next(self.cursor),
+            (SCAN_HOME, _) | (_, 0x01) => self.cursor = 0,
+            (SCAN_END, _) | (_, 0x05) => self.cursor = self.length,
+            (SCAN_DELETE, _) => self.remove(self.cursor, self.next(self.cursor)),
+            (_, 0x04) if self.length == 0 => return self.finish(Output, LineEvent::EndOfInput),
+            (_, 0x04) => self.remove(self.cursor, self.next(self.cursor)),
+            (_, CHAR_BACKSPACE | 0x7F) => self.remove(self.previous(self.cursor), self.cursor),
+            (_, 0x0B) => self.remove(self.cursor, self.length),
+            (_, 0x15) => self.remove(0, self.cursor),
+            (_, 0x17) => self.remove(self.previous_word(), self.cursor),
+            (SCAN_ESC, _) => self.remove(0, self.length),
+            (SCAN_UP, _) | (_, 0x10) => self.history_previous(),
+            (SCAN_DOWN, _) | (_, 0x0E) => self.history_next(),
+            (_, CHAR_TAB) => self.complete(),
+            (_, CHAR_CARRIAGE_RETURN | CHAR_LINEFEED) => {
+                let line = self.line;
+                let length = self.length;
+                if let Ok(line) = core::str::from_utf8(&line[..length]) {
+                    self.add_history(line);
+                }
+                return self.finish(Output, LineEvent::Accepted);
+            }
+            (_, 0x03) => return self.finish(Output, LineEvent::Cancelled),
+            (_, char) if char >= 0x20 => {
+                if let Some(char) = char::from_u32(char as u32) {
+                    self.insert(char);
+                }
+            }
+            _ => return Ok(None),
+        }
+
+        self.render(Output)?;
+        Ok(None)
+    }
+
+    /// Writes `Prompt`, then reads keys from `Input` and edits the line until it is accepted,
+    /// returning it, or `None` at the end of the input.
+    ///
+    /// Polls `Input` while no key is available. Fails with [`EFI_ABORTED`] if the line is
+    /// cancelled.
+    ///
+    /// [`EFI_ABORTED`]: crate::status::EFI_ABORTED
+    pub fn read_line<S: ConsoleSink + ?Sized>(
+        &mut self,
+        Input: &mut Keyboard,
+        Output: &mut S,
+        Prompt: &str,
+    ) -> Result<Option<&str>, EFI_STATUS> {
+        self.start(Output, Prompt)?;
+        loop {
+            let key = match Input.read_key()? {
+                Some(key) => key,
+                None => {
+                    core::hint::spin_loop();
+                    continue;
+                }
+            };
+
+            match self.handle_key(Output, key)? {
+                Some(LineEvent::Accepted) => return Ok(Some(self.line())),
+                Some(LineEvent::EndOfInput) => return Ok(None),
+                Some(LineEvent::Cancelled) => return Err(EFI_ABORTED),
+                None => {}
+            }
+        }
+    }
+
+    fn previous(&self, Index: usize) -> usize {
+        self.line()[..Index]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next(&self, Index: usize) -> usize {
+        self.line()[Index..]
+            .chars()
+            .next()
+            .map_or(Index, |char| Index + char.len_utf8())
+    }
+
+    /// Returns the start of the word before the cursor, skipping spaces after it.
+    fn previous_word(&self) -> usize {
+        let before = self.line()[..self.cursor].trim_end_matches(' ');
+        before.rfind(' ').map_or(0, |index| index + 1)
+    }
+
+    fn insert(&mut self, Char: char) {
+        let size = Char.len_utf8();
+        if self.length + size > N {
+            return;
+        }
+
+        self.line
+            .copy_within(self.cursor..self.length, self.cursor + size);
+        Char.encode_utf8(&mut self.line[self.cursor..]);
+        self.cursor += size;
+        self.length += size;
+    }
+
+    fn remove(&mut self, Start: usize, End: usize) {
+        self.line.copy_within(End..self.length, Start);
+        self.length -= End - Start;
+        if self.cursor >= End {
+            self.cursor -= End - Start;
+        } else if self.cursor > Start {
+            self.cursor = Start;
+        }
+    }
+
+    fn set_line(&mut self, Line: &[u8]) {
+        self.line[..Line.len()].copy_from_slice(Line);
+        self.length = Line.len();
+        self.cursor = Line.len();
+    }
+
+    fn history_previous(&mut self) {
+        let index = match self.browsing {
+            None if self.history_count > 0 => {
+                self.draft = self.line;
+                self.draft_length = self.length;
+                0
+            }
+            Some(index) if index + 1 < self.history_count => index + 1,
+            _ => return,
+        };
+
+        self.browsing = Some(index);
+        let (line, length) =
+            self.history[(self.history_start + self.history_count - 1 - index) % H];
+        self.set_line(&line[..length]);
+    }
+
+    fn history_next(&mut self) {
+        match self.browsing {
+            Some(0) => {
+                self.browsing = None;
+                let draft = self.draft;
+                self.set_line(&draft[..self.draft_length]);
+            }
+            Some(index) => {
+                self.browsing = Some(index - 1);
+                let (line, length) =
+                    self.history[(self.history_start + self.history_count - index) % H];
+                self.set_line(&line[..length]);
+            }
+            None => {}
+        }
+    }
+
+    fn complete(&mut self) {
+        let completer = match self.completer.take() {
+            Some(completer) => completer,
+            None => return,
+        };
+
+        let line = self.line;
+        if let Ok(before) = core::str::from_utf8(&line[..self.cursor]) {
+            if let Some(completion) = completer(before) {
+                completion.chars().for_each(|char| self.insert(char));
+            }
+        }
+        self.completer = Some(completer);
+    }
+
+    /// Moves the cursor to the end of the line and to the next row, and ends the editing.
+    fn finish<S: ConsoleSink + ?Sized>(
+        &mut self,
+        Output: &mut S,
+        Event: LineEvent,
+    ) -> Result<Option<LineEvent>, EFI_STATUS> {
+        if Event == LineEvent::Cancelled {
+            self.remove(0, self.length);
+        } else {
+            self.cursor = self.length;
+        }
+        self.render(Output)?;
+        Output.output_string("\r\n")?;
+        Output.flush()?;
+        Ok(Some(Event))
+    }
+
+    /// Redraws the visible part of the line, moving the cursor only with backspaces so that it
+    /// works on any console.
+    fn render<S: ConsoleSink + ?Sized>(&mut self, Output: &mut S) -> Result<(), EFI_STATUS> {
+        let line = self.line;
+        let text = core::str::from_utf8(&line[..self.length]).unwrap_or_default();
+        let count = text.chars().count();
+        let cursor = text[..self.cursor].chars().count();
+
+        // Scroll as little as possible to keep the cursor visible.
+        let width = self.width;
+        self.offset = self.offset.min(count.saturating_sub(width - 1));
+        if cursor < self.offset {
+            self.offset = cursor;
+        } else if cursor >= self.offset + width {
+            self.offset = cursor + 1 - width;
+        }
+
+        let start = text
+            .char_indices()
+            .nth(self.offset)
+            .map_or(text.len(), |(index, _)| index);
+        let visible = &text[start..];
+        let end = visible
+            .char_indices()
+            .nth(width)
+            .map_or(visible.len(), |(index, _)| index);
+        let visible = &visible[..end];
+        let shown = visible.chars().count();
+        let erased = self.drawn.saturating_sub(shown);
+
+        repeat(Output, BACKSPACES, self.screen)?;
+        Output.output_string(visible)?;
+        repeat(Output, SPACES, erased)?;
+        repeat(Output, BACKSPACES, shown + erased - (cursor - self.offset))?;
+        Output.flush()?;
+
+        self.drawn = shown;
+        self.screen = cursor - self.offset;
+        Ok(())
+    }
+}
+
+impl<const N: usize, const H: usize> Default for LineEditor<'_, N, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writes `Count` characters of `Run`, a string of one repeated ASCII character.
+fn repeat<S: ConsoleSink + ?Sized>(
+    Output: &mut S,
+    Run: &str,
+    mut Count: usize,
+) -> Result<(), EFI_STATUS> {
+    while Count > 0 {
+        let chunk = Count.min(Run.len());
+        Output.output_string(&Run[..chunk])?;
+        Count -= chunk;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::protocols::console::simple_text_input::{EFI_INPUT_KEY, SCAN_NULL};
+    use crate::protocols::console::simple_text_input_ex::EFI_KEY_TOGGLE_STATE;
+
+    type Editor<'a> = LineEditor<'a, 64, 3>;
+
+    /// A console of one row, moving its cursor like a terminal and recording the output.
+    struct Screen {
+        columns: UINTN,
+        row: Vec<char>,
+        column: UINTN,
+        output: String,
+    }
+
+    impl Screen {
+        fn new(Columns: UINTN) -> Self {
+            Self {
+                columns: Columns,
+                row: std::vec![' '; Columns],
+                column: 0,
+                output: String::new(),
+            }
+        }
+
+        fn text(&self) -> String {
+            self.row.iter().collect::<String>().trim_end().into()
+        }
+
+        fn take(&mut self) -> String {
+            core::mem::take(&mut self.output)
+        }
+    }
+
+    impl ConsoleSink for Screen {
+        fn output_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+            for char in String.chars() {
+                match char {
+                    '\u{8}' => self.column = self.column.checked_sub(1).expect("first column"),
+                    '\r' => self.column = 0,
+                    '\n' => {}
+                    char => {
+                        assert!(self.column + 1 < self.columns, "wrote the last column");
+                        self.row[self.column] = char;
+                        self.column += 1;
+                    }
+                }
+            }
+            self.output.push_str(String);
+            Ok(())
+        }
+
+        fn size(&mut self) -> Option<(UINTN, UINTN)> {
+            Some((self.columns, 25))
+        }
+
+        fn cursor_position(&mut self) -> Option<(UINTN, UINTN)> {
+            Some((self.column, 0))
+        }
+    }
+
+    fn key_with(ScanCode: u16, UnicodeChar: CHAR16, KeyShiftState: u32) -> EFI_KEY_DATA {
+        EFI_KEY_DATA {
+            Key: EFI_INPUT_KEY {
+                ScanCode,
+                UnicodeChar,
+            },
+            KeyState: EFI_KEY_STATE {
+                KeyShiftState,
+                KeyToggleState: 0 as EFI_KEY_TOGGLE_STATE,
+            },
+        }
+    }
+
+    fn char_key(Char: char) -> EFI_KEY_DATA {
+        key_with(SCAN_NULL, Char as CHAR16, 0)
+    }
+
+    fn scan_key(ScanCode: u16) -> EFI_KEY_DATA {
+        key_with(ScanCode, 0, 0)
+    }
+
+    fn control_key(Letter: char) -> EFI_KEY_DATA {
+        key_with(
+            SCAN_NULL,
+            Letter as CHAR16,
+            EFI_SHIFT_STATE_VALID | EFI_LEFT_CONTROL_PRESSED,
+        )
+    }
+
+    fn press(editor: &mut Editor, screen: &mut Screen, Key: EFI_KEY_DATA) -> Option<LineEvent> {
+        editor.handle_key(screen, Key).unwrap()
+    }
+
+    fn type_text(editor: &mut Editor, screen: &mut Screen, Text: &str) {
+        for char in Text.chars() {
+            assert_eq!(press(editor, screen, char_key(char)), None);
+        }
+    }
+
+    #[test]
+    fn edits_multibyte_characters() {
+        let mut screen = Screen::new(40);
+        let mut editor = Editor::new();
+        editor.start(&mut screen, "> ").unwrap();
+
+        type_text(&mut editor, &mut screen, "h€llo wörld");
+        assert_eq!(editor.line(), "h€llo wörld");
+        assert_eq!(editor.cursor(), "h€llo wörld".len());
+        assert_eq!(screen.text(), "> h€llo wörld");
+        assert_eq!(screen.column, 13);
+
+        for _ in 0..3 {
+            press(&mut editor, &mut screen, scan_key(SCAN_LEFT));
+        }
+        assert_eq!(editor.cursor(), "h€llo wö".len());
+        press(&mut editor, &mut screen, char_key('\u{8}'));
+        assert_eq!(editor.line(), "h€llo wrld");
+        assert_eq!(editor.cursor(), "h€llo w".len());
+        assert_eq!(screen.text(), "> h€llo wrld");
+        assert_eq!(screen.column, 9);
+
+        press(&mut editor, &mut screen, scan_key(SCAN_HOME));
+        press(&mut editor, &mut screen, scan_key(SCAN_RIGHT));
+        press(&mut editor, &mut screen, scan_key(SCAN_DELETE));
+        assert_eq!(editor.line(), "hllo wrld");
+        assert_eq!(editor.cursor(), 1);
+        type_text(&mut editor, &mut screen, "é");
+        assert_eq!(editor.line(), "héllo wrld");
+        assert_eq!(editor.cursor(), "hé".len());
+        assert_eq!(screen.text(), "> héllo wrld");
+        assert_eq!(screen.column, 4);
+
+        // Unpaired surrogates are not characters.
+        press(&mut editor, &mut screen, key_with(SCAN_NULL, 0xD83D, 0));
+        assert_eq!(editor.line(), "héllo wrld");
+    }
+
+    #[test]
+    fn maps_control_keys_from_the_shift_state() {
+        let mut screen = Screen::new(40);
+        let mut editor = Editor::new();
+        editor.start(&mut screen, "> ").unwrap();
+        type_text(&mut editor, &mut screen, "abc def");
+
+        press(&mut editor, &mut screen, control_key('a'));
+        assert_eq!(editor.cursor(), 0);
+        press(&mut editor, &mut screen, control_key('F'));
+        assert_eq!(editor.cursor(), 1);
+        press(&mut editor, &mut screen, control_key('e'));
+        assert_eq!(editor.cursor(), 7);
+        press(&mut editor, &mut screen, control_key('b'));
+        press(&mut editor, &mut screen, control_key('k'));
+        assert_eq!(editor.line(), "abc de");
+        press(&mut editor, &mut screen, control_key('b'));
+        press(&mut editor, &mut screen, control_key('u'));
+        assert_eq!(editor.line(), "e");
+        assert_eq!(editor.cursor(), 0);
+        assert_eq!(screen.text(), "> e");
+
+        // Control characters work without the shift state.
+        press(&mut editor, &mut screen, char_key('\u{5}'));
+        assert_eq!(editor.cursor(), 1);
+
+        // The shift state is ignored unless it is valid, and only applies to letters.
+        press(
+            &mut editor,
+            &mut screen,
+            key_with(SCAN_NULL, 'a' as CHAR16, EFI_LEFT_CONTROL_PRESSED),
+        );
+        press(&mut editor, &mut screen, control_key('1'));
+        assert_eq!(editor.line(), "ea1");
+        assert_eq!(screen.text(), "> ea1");
+    }
+
+    #[test]
+    fn control_w_deletes_the_previous_word() {
+        let mut screen = Screen::new(40);
+        let mut editor = Editor::new();
+        editor.start(&mut screen, "> ").unwrap();
+        type_text(&mut editor, &mut screen, "git  commit   -m");
+
+        press(&mut editor, &mut screen, control_key('w'));
+        assert_eq!(editor.line(), "git  commit   ");
+        press(&mut editor, &mut screen, control_key('w'));
+        assert_eq!(editor.line(), "git  ");
+        assert_eq!(screen.text(), "> git");
+        assert_eq!(screen.column, 7);
+
+        type_text(&mut editor, &mut screen, "push");
+        press(&mut editor, &mut screen, scan_key(SCAN_HOME));
+        for _ in 0..3 {
+            press(&mut editor, &mut screen, scan_key(SCAN_RIGHT));
+        }
+        press(&mut editor, &mut screen, control_key('w'));
+        assert_eq!(editor.line(), "  push");
+        assert_eq!(editor.cursor(), 0);
+
+        press(&mut editor, &mut screen, control_key('w'));
+        assert_eq!(editor.line(), "  push");
+    }
+
+    #[test]
+    fn browses_the_history_and_restores_the_draft() {
+        let mut screen = Screen::new(40);
+        let mut editor = Editor::new();
+        for line in ["one", "two", "", "two", "three", "four"] {
+            editor.add_history(line);
+        }
+
+        // The oldest line is dropped once the history is full.
+        assert_eq!(editor.history_len(), 3);
+        assert_eq!(editor.history_entry(0), Some("four"));
+        assert_eq!(editor.history_entry(1), Some("three"));
+        assert_eq!(editor.history_entry(2), Some("two"));
+        assert_eq!(editor.history_entry(3), None);
+
+        editor.start(&mut screen, "> ").unwrap();
+        type_text(&mut editor, &mut screen, "dr");
+        for expected in ["four", "three", "two", "two"] {
+            press(&mut editor, &mut screen, scan_key(SCAN_UP));
+            assert_eq!(editor.line(), expected);
+            assert_eq!(editor.cursor(), expected.len());
+        }
+        assert_eq!(screen.text(), "> two");
+
+        for expected in ["three", "four", "dr", "dr"] {
+            press(&mut editor, &mut screen, control_key('n'));
+            assert_eq!(editor.line(), expected);
+        }
+        assert_eq!(screen.text(), "> dr");
+
+        press(&mut editor, &mut screen, control_key('p'));
+        type_text(&mut editor, &mut screen, "!");
+        assert_eq!(
+            press(&mut editor, &mut screen, char_key('\r')),
+            Some(LineEvent::Accepted)
+        );
+        assert_eq!(editor.line(), "four!");
+        assert_eq!(editor.history_entry(0), Some("four!"));
+        assert_eq!(editor.history_entry(2), Some("three"));
+    }
+
+    #[test]
+    fn inserts_the_completion_at_the_cursor() {
+        let mut calls = Vec::new();
+        let mut completer = |Before: &str| {
+            calls.push(String::from(Before));
+            Before.ends_with("he").then_some("llo")
+        };
+        let mut screen = Screen::new(40);
+        let mut editor = Editor::new();
+        editor.set_completer(&mut completer);
+        editor.start(&mut screen, "> ").unwrap();
+
+        type_text(&mut editor, &mut screen, "say he!");
+        press(&mut editor, &mut screen, scan_key(SCAN_LEFT));
+        press(&mut editor, &mut screen, char_key('\t'));
+        assert_eq!(editor.line(), "say hello!");
+        assert_eq!(editor.cursor(), 9);
+        assert_eq!(screen.text(), "> say hello!");
+        assert_eq!(screen.column, 11);
+
+        press(&mut editor, &mut screen, scan_key(SCAN_HOME));
+        press(&mut editor, &mut screen, char_key('\t'));
+        assert_eq!(editor.line(), "say hello!");
+        assert_eq!(calls, ["say he", ""]);
+    }
+
+    #[test]
+    fn scrolls_long_lines_with_backspaces_and_spaces() {
+        // Seven columns are left after the prompt, keeping the last one free.
+        let mut screen = Screen::new(10);
+        let mut editor = Editor::new();
+        editor.start(&mut screen, "> ").unwrap();
+        screen.take();
+
+        type_text(&mut editor, &mut screen, "abcdefghi");
+        assert_eq!(screen.text(), "> defghi");
+        screen.take();
+
+        type_text(&mut editor, &mut screen, "j");
+        assert_eq!(screen.take(), "\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}efghij");
+        assert_eq!(screen.text(), "> efghij");
+        assert_eq!(screen.column, 8);
+
+        press(&mut editor, &mut screen, scan_key(SCAN_HOME));
+        assert_eq!(
+            screen.take(),
+            "\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}abcdefg\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}\u{8}"
+        );
+        assert_eq!(screen.text(), "> abcdefg");
+        assert_eq!(screen.column, 2);
+
+        // The shorter tail is drawn over the end of the longer head.
+        press(&mut editor, &mut screen, scan_key(SCAN_END));
+        assert_eq!(screen.take(), "efghij \u{8}");
+        assert_eq!(screen.text(), "> efghij");
+        assert_eq!(screen.column, 8);
+
+        press(&mut editor, &mut screen, char_key('\u{8}'));
+        assert_eq!(screen.text(), "> defghi");
+        assert_eq!(screen.column, 8);
+        for _ in 0..6 {
+            press(&mut editor, &mut screen, scan_key(SCAN_LEFT));
+        }
+        assert_eq!(screen.text(), "> defghi");
+        assert_eq!(screen.column, 2);
+        press(&mut editor, &mut screen, scan_key(SCAN_LEFT));
+        assert_eq!(screen.text(), "> cdefghi");
+        assert_eq!(screen.column, 2);
+    }
+
+    #[test]
+    fn ends_cancels_and_accepts_lines() {
+        let mut screen = Screen::new(40);
+        let mut editor = Editor::new();
+        editor.start(&mut screen, "> ").unwrap();
+        screen.take();
+
+        assert_eq!(
+            press(&mut editor, &mut screen, control_key('d')),
+            Some(LineEvent::EndOfInput)
+        );
+        assert_eq!(screen.take(), "\r\n");
+
+        editor.start(&mut screen, "> ").unwrap();
+        type_text(&mut editor, &mut screen, "ab");
+        press(&mut editor, &mut screen, scan_key(SCAN_HOME));
+        assert_eq!(press(&mut editor, &mut screen, char_key('\u{4}')), None);
+        assert_eq!(editor.line(), "b");
+        assert_eq!(
+            press(&mut editor, &mut screen, control_key('c')),
+            Some(LineEvent::Cancelled)
+        );
+        assert_eq!(editor.line(), "");
+        assert_eq!(editor.history_len(), 0);
+
+        editor.start(&mut screen, "> ").unwrap();
+        type_text(&mut editor, &mut screen, "ok");
+        press(&mut editor, &mut screen, scan_key(SCAN_HOME));
+        screen.take();
+        assert_eq!(
+            press(&mut editor, &mut screen, char_key('\n')),
+            Some(LineEvent::Accepted)
+        );
+        assert_eq!(screen.take(), "ok\r\n");
+        assert_eq!(editor.history_entry(0), Some("ok"));
+    }
+}
@@ -25,13 +25,53 @@
 //! | -------------------- | ------------------------------------- |
 //! | Simple Text Input    | [`EFI_SIMPLE_TEXT_INPUT_PROTOCOL`]    |
 //! | Simple Text Input Ex | [`EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL`] |
+//! | Simple Pointer       | [`EFI_SIMPLE_POINTER_PROTOCOL`]       |
+//! | Absolute Pointer     | [`EFI_ABSOLUTE_POINTER_PROTOCOL`]     |
+//! | Serial I/O           | [`EFI_SERIAL_IO_PROTOCOL`]            |
+//! | Graphics Output      | [`EFI_GRAPHICS_OUTPUT_PROTOCOL`]      |
+//! | EDID Discovered      | [`EFI_EDID_DISCOVERED_PROTOCOL`]      |
+//! | EDID Active          | [`EFI_EDID_ACTIVE_PROTOCOL`]          |
+//! | EDID Override        | [`EFI_EDID_OVERRIDE_PROTOCOL`]        |
 //!
 //! [`EFI_SIMPLE_TEXT_INPUT_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_INPUT_PROTOCOL
+//! [`EFI_SIMPLE_POINTER_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_POINTER_PROTOCOL
+//! [`EFI_ABSOLUTE_POINTER_PROTOCOL`]: crate::protocols::console::EFI_ABSOLUTE_POINTER_PROTOCOL
+//! [`EFI_SERIAL_IO_PROTOCOL`]: crate::protocols::console::EFI_SERIAL_IO_PROTOCOL
+//! [`EFI_GRAPHICS_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL
+//! [`EFI_EDID_DISCOVERED_PROTOCOL`]: crate::protocols::console::EFI_EDID_DISCOVERED_PROTOCOL
+//! [`EFI_EDID_ACTIVE_PROTOCOL`]: crate::protocols::console::EFI_EDID_ACTIVE_PROTOCOL
+//! [`EFI_EDID_OVERRIDE_PROTOCOL`]: crate::protocols::console::EFI_EDID_OVERRIDE_PROTOCOL
+//!
+//! Output to several consoles at once is provided by [`multiplex`], and the interpretation of
+//! ANSI escape sequences by [`ansi`]. Text-mode user interface widgets are provided by [`tui`],
+//! and line editing with history by [`line_editor`].
+//!
+//! [`multiplex`]: crate::protocols::console::multiplex
+//! [`ansi`]: crate::protocols::console::ansi
+//! [`tui`]: crate::protocols::console::tui
+//! [`line_editor`]: crate::protocols::console::line_editor
 
+pub mod absolute_pointer;
+pub mod ansi;
+pub mod edid;
+pub mod graphics_output;
+pub mod line_editor;
+pub mod multiplex;
+pub mod pointer;
+pub mod serial_io;
+pub mod simple_pointer;
 pub mod simple_text_input;
 pub mod simple_text_input_ex;
 pub mod simple_text_output;
+pub mod tui;
 
+pub use absolute_pointer::EFI_ABSOLUTE_POINTER_PROTOCOL;
+pub use edid::{
+    EFI_EDID_ACTIVE_PROTOCOL, EFI_EDID_DISCOVERED_PROTOCOL, EFI_EDID_OVERRIDE_PROTOCOL,
+};
+pub use graphics_output::EFI_GRAPHICS_OUTPUT_PROTOCOL;
+pub use serial_io::EFI_SERIAL_IO_PROTOCOL;
+pub use simple_pointer::EFI_SIMPLE_POINTER_PROTOCOL;
 pub use simple_text_input::EFI_SIMPLE_TEXT_INPUT_PROTOCOL;
 pub use simple_text_input_ex::EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL;
 pub use simple_text_output::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL;
@@ -0,0 +1,998 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # Console Multiplexing
+//!
+//! This module provides [`ConsoleMux`], which sends console output to several sinks at once, for
+//! example the [`ConOut`] and [`StdErr`] consoles of the system table, a serial port and a text
+//! console drawn on the [`EFI_GRAPHICS_OUTPUT_PROTOCOL`]. Any type implementing [`ConsoleSink`]
+//! can be a sink; the [`TextOutputSink`] over an [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`], the
+//! [`SerialConsole`] and the [`TextConsole`] do.
+//!
+//! A sink failing does not stop the output to the others. The failure is recorded for the
+//! sink, and as its cursor and attribute may then differ from those of the other sinks, they
+//! are set again before the next output to it.
+//!
+//! [`ConsoleMux`]: crate::protocols::console::multiplex::ConsoleMux
+//! [`ConOut`]: crate::tables::system::EFI_SYSTEM_TABLE::ConOut
+//! [`StdErr`]: crate::tables::system::EFI_SYSTEM_TABLE::StdErr
+//! [`EFI_GRAPHICS_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_GRAPHICS_OUTPUT_PROTOCOL
+//! [`ConsoleSink`]: crate::protocols::console::multiplex::ConsoleSink
+//! [`TextOutputSink`]: crate::protocols::console::multiplex::TextOutputSink
+//! [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL
+//! [`SerialConsole`]: crate::protocols::console::serial_io::SerialConsole
+//! [`TextConsole`]: crate::graphics::console::TextConsole
+
+use core::fmt;
+
+use crate::io::Write;
+use crate::protocols::console::ansi::ansi_color;
+use crate::protocols::console::serial_io::SerialConsole;
+use crate::protocols::console::simple_text_output::{
+    EFI_BLACK, EFI_BRIGHT, EFI_LIGHTGRAY, EFI_TEXT_ATTR,
+};
+use crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL;
+use crate::status::{
+    EFI_DEVICE_ERROR, EFI_ERROR, EFI_OUT_OF_RESOURCES, EFI_SUCCESS, EFI_UNSUPPORTED,
+};
+use crate::types::{BOOLEAN, CHAR16, EFI_STATUS, UINTN};
+
+/// The number of UCS-2 characters sent to [`OutputString()`] at once by a [`TextOutputSink`].
+///
+/// [`OutputString()`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL::OutputString
+/// [`TextOutputSink`]: crate::protocols::console::multiplex::TextOutputSink
+pub const CONSOLE_SINK_CHUNK_SIZE: usize = 128;
+
+/// The number of columns and rows assumed by a [`ConsoleMux`] when no sink reports its size.
+///
+/// [`ConsoleMux`]: crate::protocols::console::multiplex::ConsoleMux
+pub const DEFAULT_CONSOLE_SIZE: (UINTN, UINTN) = (80, 25);
+
+/// A destination of console output.
+///
+/// The operations mirror those of the [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`]. Text passed to
+/// [`output_string()`] has carriage return and line feed pairs as line endings. Sinks without
+/// a notion of attributes, cursor or screen keep the default implementations, which return
+/// [`EFI_UNSUPPORTED`].
+///
+/// [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL
+/// [`output_string()`]: crate::protocols::console::multiplex::ConsoleSink::output_string
+/// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+pub trait ConsoleSink {
+    /// Writes `String` at the cursor position.
+    fn output_string(&mut self, String: &str) -> Result<(), EFI_STATUS>;
+
+    /// Checks that all characters of `String` can be displayed, failing with
+    /// [`EFI_UNSUPPORTED`] if some cannot. Sinks that cannot tell keep the default
+    /// implementation, which accepts any text.
+    ///
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    fn test_string(&mut self, _String: &str) -> Result<(), EFI_STATUS> {
+        Ok(())
+    }
+
+    /// Sets the foreground and background colors, as an [`EFI_TEXT_ATTR()`] value.
+    ///
+    /// [`EFI_TEXT_ATTR()`]: crate::protocols::console::simple_text_output::EFI_TEXT_ATTR
+    fn set_attribute(&mut self, _Attribute: UINTN) -> Result<(), EFI_STATUS> {
+        Err(EFI_UNSUPPORTED)
+    }
+
+    /// Moves the cursor to (`Column`, `Row`), counted from zero at the top left corner.
+    fn set_cursor_position(&mut self, _Column: UINTN, _Row: UINTN) -> Result<(), EFI_STATUS> {
+        Err(EFI_UNSUPPORTED)
+    }
+
+    /// Clears the screen with the background color and moves the cursor to (0, 0).
+    fn clear_screen(&mut self) -> Result<(), EFI_STATUS> {
+        Err(EFI_UNSUPPORTED)
+    }
+
+    /// Shows or hides the cursor.
+    fn enable_cursor(&mut self, _Visible: bool) -> Result<(), EFI_STATUS> {
+        Err(EFI_UNSUPPORTED)
+    }
+
+    /// Sends any buffered output.
+    fn flush(&mut self) -> Result<(), EFI_STATUS> {
+        Ok(())
+    }
+
+    /// Returns the number of columns and rows, if the sink has a screen.
+    fn size(&mut self) -> Option<(UINTN, UINTN)> {
+        None
+    }
+
+    /// Returns the column and row of the cursor, if the sink has a screen.
+    fn cursor_position(&mut self) -> Option<(UINTN, UINTN)> {
+        None
+    }
+}
+
+/// The attribute of a [`ConsoleMux`] until one is set, light gray on black.
+///
+/// [`ConsoleMux`]: crate::protocols::console::multiplex::ConsoleMux
+pub const DEFAULT_CONSOLE_ATTRIBUTE: UINTN = EFI_TEXT_ATTR(EFI_LIGHTGRAY, EFI_BLACK);
+
+/// Sends console output to up to `N` [`ConsoleSink`]s.
+///
+/// The multiplexer keeps the attribute, the cursor position and the cursor visibility that the
+/// sinks should have. The cursor moves with the text output on a screen of the smallest size
+/// reported by the sinks, or [`DEFAULT_CONSOLE_SIZE`] if none does.
+///
+/// Every operation is tried on every enabled sink. It succeeds if any sink succeeds, fails with
+/// [`EFI_UNSUPPORTED`] if no sink supports it, and otherwise fails with the status of the first
+/// sink that failed. The last failure of each sink is kept until [`clear_errors()`].
+///
+/// [`ConsoleSink`]: crate::protocols::console::multiplex::ConsoleSink
+/// [`DEFAULT_CONSOLE_SIZE`]: crate::protocols::console::multiplex::DEFAULT_CONSOLE_SIZE
+/// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+/// [`clear_errors()`]: crate::protocols::console::multiplex::ConsoleMux::clear_errors
+pub struct ConsoleMux<'a, const N: usize> {
+    sinks: [Option<Slot<'a>>; N],
+    attribute: UINTN,
+    column: UINTN,
+    row: UINTN,
+    columns: UINTN,
+    rows: UINTN,
+    cursor_visible: bool,
+}
+
+struct Slot<'a> {
+    sink: &'a mut dyn ConsoleSink,
+    enabled: bool,
+    stale: bool,
+    error: Option<EFI_STATUS>,
+    failures: usize,
+}
+
+impl<'a, const N: usize> ConsoleMux<'a, N> {
+    /// Creates a multiplexer without sinks.
+    pub fn new() -> Self {
+        Self {
+            sinks: [(); N].map(|_| None),
+            attribute: DEFAULT_CONSOLE_ATTRIBUTE,
+            column: 0,
+            row: 0,
+            columns: DEFAULT_CONSOLE_SIZE.0,
+            rows: DEFAULT_CONSOLE_SIZE.1,
+            cursor_visible: true,
+        }
+    }
+
+    /// Adds `Sink`, returning its index.
+    ///
+    /// The first sink with a cursor position gives the multiplexer its position. The sink is
+    /// set to the attribute and the cursor position of the multiplexer before its first output.
+    /// Fails with [`EFI_OUT_OF_RESOURCES`] if there are already `N` sinks.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    pub fn add(&mut self, Sink: &'a mut dyn ConsoleSink) -> Result<usize, EFI_STATUS> {
+        let index = self
+            .sinks
+            .iter()
+            .position(Option::is_none)
+            .ok_or(EFI_OUT_OF_RESOURCES)?;
+
+        if self.sinks.iter().all(Option::is_none) {
+            if let Some((column, row)) = Sink.cursor_position() {
+                self.column = column;
+                self.row = row;
+            }
+        }
+
+        self.sinks[index] = Some(Slot {
+            sink: Sink,
+            enabled: true,
+            stale: true,
+            error: None,
+            failures: 0,
+        });
+        self.update_size();
+        Ok(index)
+    }
+
+    /// Removes and returns the sink at `Index`.
+    pub fn remove(&mut self, Index: usize) -> Option<&'a mut dyn ConsoleSink> {
+        let slot = self.sinks.get_mut(Index)?.take()?;
+        self.update_size();
+        Some(slot.sink)
+    }
+
+    /// Returns the number of sinks.
+    pub fn len(&self) -> usize {
+        self.sinks.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    /// Returns `true` if there are no sinks.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Enables or disables the output to the sink at `Index`.
+    ///
+    /// A sink that is enabled again is brought up to date before its next output.
+    pub fn set_enabled(&mut self, Index: usize, Enabled: bool) {
+        if let Some(Some(slot)) = self.sinks.get_mut(Index) {
+            slot.stale |= Enabled && !slot.enabled;
+            slot.enabled = Enabled;
+        }
+    }
+
+    /// Returns the status of the last failure of the sink at `Index`, if any.
+    pub fn error(&self, Index: usize) -> Option<EFI_STATUS> {
+        self.slot(Index).and_then(|slot| slot.error)
+    }
+
+    /// Returns the number of operations that failed on the sink at `Index` since it last
+    /// succeeded.
+    pub fn failures(&self, Index: usize) -> usize {
+        self.slot(Index).map_or(0, |slot| slot.failures)
+    }
+
+    /// Forgets the failures of all sinks.
+    pub fn clear_errors(&mut self) {
+        for slot in self.sinks.iter_mut().flatten() {
+            slot.error = None;
+            slot.failures = 0;
+        }
+    }
+
+    /// Returns the attribute of the console.
+    pub fn attribute(&self) -> UINTN {
+        self.attribute
+    }
+
+    /// Returns the column and row of the cursor.
+    pub fn cursor_position(&self) -> (UINTN, UINTN) {
+        (self.column, self.row)
+    }
+
+    /// Returns the number of columns and rows of the console.
+    pub fn size(&self) -> (UINTN, UINTN) {
+        (self.columns, self.rows)
+    }
+
+    /// Returns whether the cursor is visible.
+    pub fn cursor_visible(&self) -> bool {
+        self.cursor_visible
+    }
+
+    /// Writes `String`, which has carriage return and line feed pairs as line endings, to all
+    /// sinks. Use [`core::fmt::Write`] to translate line feeds.
+    pub fn output_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+        let result = self.broadcast(|sink| sink.output_string(String));
+        for char in String.chars() {
+            self.advance(char);
+        }
+
+        result
+    }
+
+    /// Checks that all enabled sinks can display `String`.
+    ///
+    /// Unlike the other operations, this fails with the status of the first sink that fails,
+    /// even if others succeed, as the text is only displayed correctly if all sinks can.
+    pub fn test_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+        self.sinks
+            .iter_mut()
+            .flatten()
+            .filter(|slot| slot.enabled)
+            .try_for_each(|slot| slot.sink.test_string(String))
+    }
+
+    /// Sets the attribute of all sinks.
+    pub fn set_attribute(&mut self, Attribute: UINTN) -> Result<(), EFI_STATUS> {
+        self.attribute = Attribute;
+        self.broadcast(|sink| sink.set_attribute(Attribute))
+    }
+
+    /// Moves the cursor of all sinks to (`Column`, `Row`).
+    ///
+    /// Fails with [`EFI_UNSUPPORTED`] if the position is outside of the console.
+    ///
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    pub fn set_cursor_position(&mut self, Column: UINTN, Row: UINTN) -> Result<(), EFI_STATUS> {
+        if Column >= self.columns || Row >= self.rows {
+            return Err(EFI_UNSUPPORTED);
+        }
+
+        self.column = Column;
+        self.row = Row;
+        self.broadcast(|sink| sink.set_cursor_position(Column, Row))
+    }
+
+    /// Clears the screen of all sinks and moves the cursor to (0, 0).
+    pub fn clear_screen(&mut self) -> Result<(), EFI_STATUS> {
+        self.column = 0;
+        self.row = 0;
+        self.broadcast(|sink| sink.clear_screen())
+    }
+
+    /// Shows or hides the cursor of all sinks.
+    pub fn enable_cursor(&mut self, Visible: bool) -> Result<(), EFI_STATUS> {
+        self.cursor_visible = Visible;
+        self.broadcast(|sink| sink.enable_cursor(Visible))
+    }
+
+    /// Sends the buffered output of all sinks.
+    pub fn flush(&mut self) -> Result<(), EFI_STATUS> {
+        self.broadcast(|sink| sink.flush())
+    }
+
+    fn slot(&self, Index: usize) -> Option<&Slot<'a>> {
+        self.sinks.get(Index).and_then(Option::as_ref)
+    }
+
+    fn update_size(&mut self) {
+        let (columns, rows) = self
+            .sinks
+            .iter_mut()
+            .flatten()
+            .filter_map(|slot| slot.sink.size())
+            .reduce(|(c1, r1), (c2, r2)| (c1.min(c2), r1.min(r2)))
+            .unwrap_or(DEFAULT_CONSOLE_SIZE);
+
+        self.columns = columns.max(1);
+        self.rows = rows.max(1);
+        self.column = self.column.min(self.columns - 1);
+        self.row = self.row.min(self.rows - 1);
+    }
+
+    /// Moves the cursor as writing `Char` does.
+    fn advance(&mut self, Char: char) {
+        match Char {
+            '\r' => self.column = 0,
+            '\n' => self.row = (self.row + 1).min(self.rows - 1),
+            '\u{8}' => self.column = self.column.saturating_sub(1),
+            _ => {
+                self.column += 1;
+                if self.column == self.columns {
+                    self.column = 0;
+                    self.row = (self.row + 1).min(self.rows - 1);
+                }
+            }
+        }
+    }
+
+    /// Applies `Operation` to all enabled sinks, first bringing stale ones up to date.
+    fn broadcast(
+        &mut self,
+        mut Operation: impl FnMut(&mut dyn ConsoleSink) -> Result<(), EFI_STATUS>,
+    ) -> Result<(), EFI_STATUS> {
+        let (attribute, column, row, visible) =
+            (self.attribute, self.column, self.row, self.cursor_visible);
+        let mut succeeded = false;
+        let mut failure = None;
+
+        for slot in self.sinks.iter_mut().flatten() {
+            if !slot.enabled {
+                continue;
+            }
+
+            let result = if slot.stale {
+                sync(slot.sink, attribute, column, row, visible).and_then(|_| Operation(slot.sink))
+            } else {
+                Operation(slot.sink)
+            };
+
+            match result {
+                Ok(()) => {
+                    slot.stale = false;
+                    slot.failures = 0;
+                    succeeded = true;
+                }
+                Err(EFI_UNSUPPORTED) => slot.stale = false,
+                Err(status) => {
+                    slot.stale = true;
+                    slot.error = Some(status);
+                    slot.failures += 1;
+                    failure.get_or_insert(status);
+                }
+            }
+        }
+
+        match failure {
+            _ if succeeded => Ok(()),
+            Some(status) => Err(status),
+            None if self.sinks.iter().flatten().any(|slot| slot.enabled) => Err(EFI_UNSUPPORTED),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<S: ConsoleSink + ?Sized> ConsoleSink for &mut S {
+    fn output_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+        (**self).output_string(String)
+    }
+
+    fn test_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+        (**self).test_string(String)
+    }
+
+    fn set_attribute(&mut self, Attribute: UINTN) -> Result<(), EFI_STATUS> {
+        (**self).set_attribute(Attribute)
+    }
+
+    fn set_cursor_position(&mut self, Column: UINTN, Row: UINTN) -> Result<(), EFI_STATUS> {
+        (**self).set_cursor_position(Column, Row)
+    }
+
+    fn clear_screen(&mut self) -> Result<(), EFI_STATUS> {
+        (**self).clear_screen()
+    }
+
+    fn enable_cursor(&mut self, Visible: bool) -> Result<(), EFI_STATUS> {
+        (**self).enable_cursor(Visible)
+    }
+
+    fn flush(&mut self) -> Result<(), EFI_STATUS> {
+        (**self).flush()
+    }
+
+    fn size(&mut self) -> Option<(UINTN, UINTN)> {
+        (**self).size()
+    }
+
+    fn cursor_position(&mut self) -> Option<(UINTN, UINTN)> {
+        (**self).cursor_position()
+    }
+}
+
+impl<const N: usize> ConsoleSink for ConsoleMux<'_, N> {
+    fn output_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+        ConsoleMux::output_string(self, String)
+    }
+
+    fn test_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+        ConsoleMux::test_string(self, String)
+    }
+
+    fn set_attribute(&mut self, Attribute: UINTN) -> Result<(), EFI_STATUS> {
+        ConsoleMux::set_attribute(self, Attribute)
+    }
+
+    fn set_cursor_position(&mut self, Column: UINTN, Row: UINTN) -> Result<(), EFI_STATUS> {
+        ConsoleMux::set_cursor_position(self, Column, Row)
+    }
+
+    fn clear_screen(&mut self) -> Result<(), EFI_STATUS> {
+        ConsoleMux::clear_screen(self)
+    }
+
+    fn enable_cursor(&mut self, Visible: bool) -> Result<(), EFI_STATUS> {
+        ConsoleMux::enable_cursor(self, Visible)
+    }
+
+    fn flush(&mut self) -> Result<(), EFI_STATUS> {
+        ConsoleMux::flush(self)
+    }
+
+    fn size(&mut self) -> Option<(UINTN, UINTN)> {
+        Some(ConsoleMux::size(self))
+    }
+
+    fn cursor_position(&mut self) -> Option<(UINTN, UINTN)> {
+        Some(ConsoleMux::cursor_position(self))
+    }
+}
+
+impl<const N: usize> Default for ConsoleMux<'_, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Write for ConsoleMux<'_, N> {
+    /// Writes `s` to all sinks, translating line feeds to carriage return and line feed pairs.
+    ///
+    /// Fails only if the text could not be written to any sink.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut lines = s.split('\n');
+        let mut result = lines.next().map_or(Ok(()), |line| self.output_string(line));
+        for line in lines {
+            let status = self.output_string("\r\n");
+            result = result.and(status).and(self.output_string(line));
+        }
+
+        result.map_err(|_| fmt::Error)
+    }
+}
+
+/// Sets the attribute, cursor position and cursor visibility of `Sink`, ignoring the operations
+/// it does not support.
+fn sync(
+    Sink: &mut dyn ConsoleSink,
+    Attribute: UINTN,
+    Column: UINTN,
+    Row: UINTN,
+    Visible: bool,
+) -> Result<(), EFI_STATUS> {
+    let supported = |result| match result {
+        Err(EFI_UNSUPPORTED) => Ok(()),
+        result => result,
+    };
+
+    supported(Sink.set_attribute(Attribute))?;
+    supported(Sink.set_cursor_position(Column, Row))?;
+    supported(Sink.enable_cursor(Visible))
+}
+
+/// Writes an escape sequence to `Console`.
+fn write_escape(Console: &mut SerialConsole, Sequence: fmt::Arguments) -> Result<(), EFI_STATUS> {
+    fmt::Write::write_fmt(Console, Sequence)
+        .map_err(|_| Console.take_error().unwrap_or(EFI_DEVICE_ERROR))
+}
+
+/// Calls `Function` with null-terminated UCS-2 chunks of `String`, replacing the characters
+/// that UCS-2 cannot encode with U+FFFD.
+fn for_each_ucs2_chunk(
+    String: &str,
+    mut Function: impl FnMut(*mut CHAR16) -> Result<(), EFI_STATUS>,
+) -> Result<(), EFI_STATUS> {
+    let mut buffer = [0 as CHAR16; CONSOLE_SINK_CHUNK_SIZE + 1];
+    let mut length = 0;
+    let mut chars = String.chars().peekable();
+    while let Some(char) = chars.next() {
+        buffer[length] = u16::try_from(char as u32).unwrap_or(0xFFFD);
+        length += 1;
+        if length == CONSOLE_SINK_CHUNK_SIZE || chars.peek().is_none() {
+            buffer[length] = 0;
+            Function(buffer.as_mut_ptr())?;
+            length = 0;
+        }
+    }
+
+    Ok(())
+}
+
+/// Maps the result of a raw call, treating warnings as success.
+fn check(Status: EFI_STATUS) -> Result<(), EFI_STATUS> {
+    if EFI_ERROR(Status) {
+        Err(Status)
+    } else {
+        Ok(())
+    }
+}
+
+/// A [`ConsoleSink`] writing to an [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`], such as the [`ConOut`]
+/// console of the system table.
+///
+/// [`ConsoleSink`]: crate::protocols::console::multiplex::ConsoleSink
+/// [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL
+/// [`ConOut`]: crate::tables::system::EFI_SYSTEM_TABLE::ConOut
+pub struct TextOutputSink<'a> {
+    protocol: &'a mut EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL,
+}
+
+impl<'a> TextOutputSink<'a> {
+    /// Creates a sink writing to `Protocol`.
+    ///
+    /// # Safety
+    ///
+    /// `Protocol` and its mode must be valid for the lifetime `'a`.
+    pub unsafe fn new(Protocol: &'a mut EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL) -> Self {
+        Self { protocol: Protocol }
+    }
+
+    /// Returns the text output protocol.
+    pub fn protocol(&mut self) -> &mut EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL {
+        self.protocol
+    }
+}
+
+impl ConsoleSink for TextOutputSink<'_> {
+    /// Writes `String` with [`OutputString()`], replacing the characters that UCS-2 cannot
+    /// encode with U+FFFD. The warning for characters without a glyph is not an error.
+    ///
+    /// [`OutputString()`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL::OutputString
+    fn output_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+        // SAFETY: `new()` requires the protocol to be valid.
+        for_each_ucs2_chunk(String, |chunk| {
+            check(unsafe { self.protocol.OutputString(chunk) })
+        })
+    }
+
+    /// Tests `String` with [`TestString()`], converted as by [`output_string()`].
+    ///
+    /// [`TestString()`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL::TestString
+    /// [`output_string()`]: crate::protocols::console::multiplex::ConsoleSink::output_string
+    fn test_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+        // SAFETY: `new()` requires the protocol to be valid.
+        for_each_ucs2_chunk(String, |chunk| {
+            check(unsafe { self.protocol.TestString(chunk) })
+        })
+    }
+
+    fn set_attribute(&mut self, Attribute: UINTN) -> Result<(), EFI_STATUS> {
+        // SAFETY: `new()` requires the protocol to be valid.
+        check(unsafe { self.protocol.SetAttribute(Attribute) })
+    }
+
+    fn set_cursor_position(&mut self, Column: UINTN, Row: UINTN) -> Result<(), EFI_STATUS> {
+        // SAFETY: `new()` requires the protocol to be valid.
+        check(unsafe { self.protocol.SetCursorPosition(Column, Row) })
+    }
+
+    fn clear_screen(&mut self) -> Result<(), EFI_STATUS> {
+        // SAFETY: `new()` requires the protocol to be valid.
+        check(unsafe { self.protocol.ClearScreen() })
+    }
+
+    fn enable_cursor(&mut self, Visible: bool) -> Result<(), EFI_STATUS> {
+        // SAFETY: `new()` requires the protocol to be valid.
+        check(unsafe { self.protocol.EnableCursor(Visible as BOOLEAN) })
+    }
+
+    fn size(&mut self) -> Option<(UINTN, UINTN)> {
+        let (mut columns, mut rows) = (0, 0);
+        // SAFETY: `new()` requires the protocol and its mode to be valid.
+        match unsafe {
+            self.protocol
+                .QueryMode((*self.protocol.Mode).Mode as UINTN, &mut columns, &mut rows)
+        } {
+            EFI_SUCCESS => Some((columns, rows)),
+            _ => None,
+        }
+    }
+
+    fn cursor_position(&mut self) -> Option<(UINTN, UINTN)> {
+        // SAFETY: `new()` requires the protocol and its mode to be valid.
+        let mode = unsafe { &*self.protocol.Mode };
+        Some((mode.CursorColumn as UINTN, mode.CursorRow as UINTN))
+    }
+}
+
+impl ConsoleSink for SerialConsole<'_> {
+    /// Writes `String` as UTF-8.
+    fn output_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+        self.write_all(String.as_bytes())
+    }
+
+    /// Sets the colors with an ANSI Select Graphic Rendition escape sequence.
+    fn set_attribute(&mut self, Attribute: UINTN) -> Result<(), EFI_STATUS> {
+        let foreground = Attribute & 0x0F;
+        let background = (Attribute >> 4) & 0x07;
+        let bright = if foreground & EFI_BRIGHT != 0 {
+            ";1"
+        } else {
+            ""
+        };
+        write_escape(
+            self,
+            format_args!(
+                "\x1B[0;{}{};{}m",
+                30 + ansi_color(foreground & 0x07),
+                bright,
+                40 + ansi_color(background)
+            ),
+        )
+    }
+
+    /// Moves the cursor with an ANSI Cursor Position escape sequence.
+    fn set_cursor_position(&mut self, Column: UINTN, Row: UINTN) -> Result<(), EFI_STATUS> {
+        write_escape(self, format_args!("\x1B[{};{}H", Row + 1, Column + 1))
+    }
+
+    /// Clears the screen with ANSI Erase in Display and Cursor Position escape sequences.
+    fn clear_screen(&mut self) -> Result<(), EFI_STATUS> {
+        self.write_all(b"\x1B[2J\x1B[H")
+    }
+
+    /// Shows or hides the cursor with the DEC Text Cursor Enable Mode escape sequence.
+    fn enable_cursor(&mut self, Visible: bool) -> Result<(), EFI_STATUS> {
+        self.write_all(if Visible { b"\x1B[?25h" } else { b"\x1B[?25l" })
+    }
+
+    fn flush(&mut self) -> Result<(), EFI_STATUS> {
+        Write::flush(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::cell::RefCell;
+    use core::fmt::Write as _;
+    use std::rc::Rc;
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::protocols::console::simple_text_output::{EFI_BLUE, EFI_YELLOW};
+
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        Output(String),
+        Attribute(UINTN),
+        Cursor(UINTN, UINTN),
+        Clear,
+        EnableCursor(bool),
+    }
+
+    #[derive(Default)]
+    struct State {
+        calls: Vec<Call>,
+        failure: Option<EFI_STATUS>,
+    }
+
+    /// A sink recording the operations it is asked to do, failing them all while `failure` is
+    /// set. A sink without a screen only supports output.
+    struct Recorder {
+        state: Rc<RefCell<State>>,
+        screen: Option<(UINTN, UINTN)>,
+        cursor: Option<(UINTN, UINTN)>,
+    }
+
+    impl Recorder {
+        fn call(&mut self, Call: Call, Supported: bool) -> Result<(), EFI_STATUS> {
+            let mut state = self.state.borrow_mut();
+            if let Some(status) = state.failure {
+                return Err(status);
+            }
+            if !Supported {
+                return Err(EFI_UNSUPPORTED);
+            }
+
+            state.calls.push(Call);
+            Ok(())
+        }
+    }
+
+    impl ConsoleSink for Recorder {
+        fn output_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+            self.call(Call::Output(String.to_string()), true)
+        }
+
+        fn test_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+            match self.screen {
+                Some(_) if String.contains('\u{2592}') => Err(EFI_UNSUPPORTED),
+                _ => Ok(()),
+            }
+        }
+
+        fn set_attribute(&mut self, Attribute: UINTN) -> Result<(), EFI_STATUS> {
+            let screen = self.screen.is_some();
+            self.call(Call::Attribute(Attribute), screen)
+        }
+
+        fn set_cursor_position(&mut self, Column: UINTN, Row: UINTN) -> Result<(), EFI_STATUS> {
+            let screen = self.screen.is_some();
+            self.call(Call::Cursor(Column, Row), screen)
+        }
+
+        fn clear_screen(&mut self) -> Result<(), EFI_STATUS> {
+            let screen = self.screen.is_some();
+            self.call(Call::Clear, screen)
+        }
+
+        fn enable_cursor(&mut self, Visible: bool) -> Result<(), EFI_STATUS> {
+            let screen = self.screen.is_some();
+            self.call(Call::EnableCursor(Visible), screen)
+        }
+
+        fn size(&mut self) -> Option<(UINTN, UINTN)> {
+            self.screen
+        }
+
+        fn cursor_position(&mut self) -> Option<(UINTN, UINTN)> {
+            self.cursor
+        }
+    }
+
+    fn recorder(Screen: Option<(UINTN, UINTN)>) -> (Recorder, Rc<RefCell<State>>) {
+        let state = Rc::new(RefCell::new(State::default()));
+        let recorder = Recorder {
+            state: state.clone(),
+            screen: Screen,
+            cursor: None,
+        };
+        (recorder, state)
+    }
+
+    fn take_calls(State: &Rc<RefCell<State>>) -> Vec<Call> {
+        core::mem::take(&mut State.borrow_mut().calls)
+    }
+
+    fn set_failure(State: &Rc<RefCell<State>>, Failure: Option<EFI_STATUS>) {
+        State.borrow_mut().failure = Failure;
+    }
+
+    fn synced(Column: UINTN, Row: UINTN, Output: &str) -> Vec<Call> {
+        std::vec![
+            Call::Attribute(DEFAULT_CONSOLE_ATTRIBUTE),
+            Call::Cursor(Column, Row),
+            Call::EnableCursor(true),
+            Call::Output(Output.to_string()),
+        ]
+    }
+
+    #[test]
+    fn failing_sink_does_not_block_others() {
+        let (mut first, first_state) = recorder(Some((80, 25)));
+        let (mut second, second_state) = recorder(Some((80, 25)));
+        let mut mux = ConsoleMux::<2>::new();
+        assert_eq!(mux.add(&mut first), Ok(0));
+        assert_eq!(mux.add(&mut second), Ok(1));
+
+        set_failure(&first_state, Some(EFI_DEVICE_ERROR));
+        assert_eq!(mux.output_string("hello"), Ok(()));
+        assert_eq!(mux.output_string(", world"), Ok(()));
+        assert_eq!(take_calls(&first_state), []);
+        let mut expected = synced(0, 0, "hello");
+        expected.push(Call::Output(", world".to_string()));
+        assert_eq!(take_calls(&second_state), expected);
+
+        assert_eq!(mux.error(0), Some(EFI_DEVICE_ERROR));
+        assert_eq!(mux.failures(0), 2);
+        assert_eq!(mux.error(1), None);
+        assert_eq!(mux.failures(1), 0);
+
+        // The first failure is reported when no sink succeeds.
+        set_failure(&second_state, Some(EFI_UNSUPPORTED));
+        set_failure(&first_state, Some(EFI_DEVICE_ERROR));
+        assert_eq!(mux.clear_screen(), Err(EFI_DEVICE_ERROR));
+        assert_eq!(mux.failures(0), 3);
+        assert_eq!(mux.error(1), None);
+
+        mux.clear_errors();
+        assert_eq!(mux.error(0), None);
+        assert_eq!(mux.failures(0), 0);
+        assert_eq!(mux.error(7), None);
+        assert_eq!(mux.failures(7), 0);
+    }
+
+    #[test]
+    fn stale_sink_is_resynced() {
+        let (mut first, first_state) = recorder(Some((80, 25)));
+        let (mut second, second_state) = recorder(Some((80, 25)));
+        let mut mux = ConsoleMux::<2>::new();
+        mux.add(&mut first).unwrap();
+        mux.add(&mut second).unwrap();
+        mux.output_string("").unwrap();
+        take_calls(&first_state);
+        take_calls(&second_state);
+
+        set_failure(&second_state, Some(EFI_DEVICE_ERROR));
+        let attribute = EFI_TEXT_ATTR(EFI_YELLOW, EFI_BLUE);
+        mux.set_attribute(attribute).unwrap();
+        mux.output_string("abc\r\nde").unwrap();
+        mux.enable_cursor(false).unwrap();
+        assert_eq!(mux.cursor_position(), (2, 1));
+
+        // The sink recovers, and is brought up to date before the next output.
+        set_failure(&second_state, None);
+        mux.output_string("f").unwrap();
+        assert_eq!(
+            take_calls(&second_state),
+            [
+                Call::Attribute(attribute),
+                Call::Cursor(2, 1),
+                Call::EnableCursor(false),
+                Call::Output("f".to_string()),
+            ]
+        );
+        assert_eq!(mux.failures(1), 0);
+        assert_eq!(mux.error(1), Some(EFI_DEVICE_ERROR));
+
+        // A failure while resyncing leaves the sink stale.
+        set_failure(&second_state, Some(EFI_DEVICE_ERROR));
+        mux.output_string("g").unwrap();
+        set_failure(&second_state, None);
+        mux.output_string("h").unwrap();
+        assert_eq!(
+            take_calls(&second_state),
+            [
+                Call::Attribute(attribute),
+                Call::Cursor(4, 1),
+                Call::EnableCursor(false),
+                Call::Output("h".to_string()),
+            ]
+        );
+        assert_eq!(
+            take_calls(&first_state),
+            [
+                Call::Attribute(attribute),
+                Call::Output("abc\r\nde".to_string()),
+                Call::EnableCursor(false),
+                Call::Output("f".to_string()),
+                Call::Output("g".to_string()),
+                Call::Output("h".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn reenabled_sink_is_resynced() {
+        let (mut first, first_state) = recorder(Some((80, 25)));
+        let mut mux = ConsoleMux::<1>::new();
+        mux.add(&mut first).unwrap();
+        mux.output_string("a").unwrap();
+        take_calls(&first_state);
+
+        mux.set_enabled(0, false);
+        assert_eq!(mux.output_string("b"), Ok(()));
+        mux.set_enabled(0, true);
+        mux.output_string("c").unwrap();
+        assert_eq!(take_calls(&first_state), synced(2, 0, "c"));
+    }
+
+    #[test]
+    fn sinks_without_screen() {
+        let (mut first, first_state) = recorder(None);
+        let mut mux = ConsoleMux::<2>::new();
+        mux.add(&mut first).unwrap();
+        assert_eq!(mux.size(), DEFAULT_CONSOLE_SIZE);
+
+        // Unsupported operations are not failures.
+        assert_eq!(mux.set_attribute(0x1F), Err(EFI_UNSUPPORTED));
+        assert_eq!(mux.output_string("x"), Ok(()));
+        assert_eq!(mux.error(0), None);
+        assert_eq!(take_calls(&first_state), [Call::Output("x".to_string())]);
+
+        // The console has the size of the smallest screen, and the position of the first sink.
+        let (mut second, _) = recorder(Some((40, 10)));
+        second.cursor = Some((39, 9));
+        mux.add(&mut second).unwrap();
+        assert_eq!(mux.size(), (40, 10));
+        assert_eq!(mux.cursor_position(), (1, 0));
+        assert_eq!(mux.set_cursor_position(40, 0), Err(EFI_UNSUPPORTED));
+        assert_eq!(mux.set_cursor_position(39, 9), Ok(()));
+        assert_eq!(mux.test_string("\u{2592}"), Err(EFI_UNSUPPORTED));
+        assert_eq!(mux.add(&mut recorder(None).0), Err(EFI_OUT_OF_RESOURCES));
+    }
+
+    #[test]
+    fn cursor_follows_output() {
+        let (mut first, first_state) = recorder(Some((4, 2)));
+        let mut mux = ConsoleMux::<1>::new();
+        mux.add(&mut first).unwrap();
+        write!(mux, "abcde\nf\u{8}").unwrap();
+        assert_eq!(mux.cursor_position(), (0, 1));
+        let calls = take_calls(&first_state);
+        assert_eq!(
+            calls[3..],
+            [
+                Call::Output("abcde".to_string()),
+                Call::Output("\r\n".to_string()),
+                Call::Output("f\u{8}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn ucs2_chunks() {
+        let text: String = "a\u{1F600}"
+            .chars()
+            .cycle()
+            .take(CONSOLE_SINK_CHUNK_SIZE + 3)
+            .collect();
+        let mut chunks = Vec::new();
+        for_each_ucs2_chunk(&text, |chunk| {
+            let length = (0..).find(|&i| unsafe { *chunk.add(i) } == 0).unwrap();
+            chunks.push(unsafe { core::slice::from_raw_parts(chunk, length) }.to_vec());
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].len(), CONSOLE_SINK_CHUNK_SIZE);
+        assert_eq!(chunks[0][..2], [b'a' as CHAR16, 0xFFFD]);
+        assert_eq!(chunks[1], [b'a' as CHAR16, 0xFFFD, b'a' as CHAR16]);
+    }
+}
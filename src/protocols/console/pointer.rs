@@ -0,0 +1,465 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # Pointer Events
+//!
+//! This module merges the input of [`EFI_SIMPLE_POINTER_PROTOCOL`] and
+//! [`EFI_ABSOLUTE_POINTER_PROTOCOL`] devices into a single stream of [`PointerEvent`]s, so that
+//! mice and touchscreens drive the same on-screen pointer.
+//!
+//! Relative movement is converted from device counts to pixels, and absolute coordinates are
+//! scaled from the range of the device to the screen. Both update one pointer position, which
+//! is clamped to the screen.
+//!
+//! [`EFI_SIMPLE_POINTER_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_POINTER_PROTOCOL
+//! [`EFI_ABSOLUTE_POINTER_PROTOCOL`]: crate::protocols::console::EFI_ABSOLUTE_POINTER_PROTOCOL
+//! [`PointerEvent`]: crate::protocols::console::pointer::PointerEvent
+
+use crate::protocols::console::absolute_pointer::EFI_ABSOLUTE_POINTER_STATE;
+use crate::protocols::console::simple_pointer::EFI_SIMPLE_POINTER_STATE;
+use crate::protocols::console::{EFI_ABSOLUTE_POINTER_PROTOCOL, EFI_SIMPLE_POINTER_PROTOCOL};
+use crate::types::{EFI_EVENT, EFI_STATUS, INT32, INT64, UINT32, UINT64};
+
+/// The primary button: the left mouse button, or touching a touchscreen.
+pub const POINTER_BUTTON_PRIMARY: UINT32 = 0x00000001;
+/// The secondary button: the right mouse button, or the alternate button of a touchscreen.
+pub const POINTER_BUTTON_SECONDARY: UINT32 = 0x00000002;
+
+/// The default speed of relative pointer devices, in pixels per millimeter of movement.
+pub const DEFAULT_POINTER_SPEED: UINT32 = 4;
+
+/// The kind of device that produced a [`PointerEvent`].
+///
+/// [`PointerEvent`]: crate::protocols::console::pointer::PointerEvent
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PointerSource {
+    /// A relative device, through the [`EFI_SIMPLE_POINTER_PROTOCOL`].
+    ///
+    /// [`EFI_SIMPLE_POINTER_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_POINTER_PROTOCOL
+    Relative,
+    /// An absolute device, through the [`EFI_ABSOLUTE_POINTER_PROTOCOL`].
+    ///
+    /// [`EFI_ABSOLUTE_POINTER_PROTOCOL`]: crate::protocols::console::EFI_ABSOLUTE_POINTER_PROTOCOL
+    Absolute,
+}
+
+/// A change of the state of a pointer device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PointerEvent {
+    /// The kind of device that produced the event.
+    pub Source: PointerSource,
+    /// The column of the pointer on the screen after the event.
+    pub X: usize,
+    /// The row of the pointer on the screen after the event.
+    pub Y: usize,
+    /// The horizontal movement of the pointer in pixels.
+    pub DeltaX: INT32,
+    /// The vertical movement of the pointer in pixels.
+    pub DeltaY: INT32,
+    /// The movement along the z-axis in device counts, such as a scroll wheel. Always zero for
+    /// absolute devices.
+    pub DeltaZ: INT32,
+    /// The buttons held after the event, a combination of [`POINTER_BUTTON_PRIMARY`] and
+    /// [`POINTER_BUTTON_SECONDARY`].
+    ///
+    /// [`POINTER_BUTTON_PRIMARY`]: crate::protocols::console::pointer::POINTER_BUTTON_PRIMARY
+    /// [`POINTER_BUTTON_SECONDARY`]: crate::protocols::console::pointer::POINTER_BUTTON_SECONDARY
+    pub Buttons: UINT32,
+    /// The buttons pressed by the event.
+    pub Pressed: UINT32,
+    /// The buttons released by the event.
+    pub Released: UINT32,
+}
+
+/// A stream of [`PointerEvent`]s from up to one relative and one absolute pointer device.
+///
+/// Iterating yields the pending events of the devices, and ends when no device has a pending
+/// event. Wait on [`wait_events()`] with [`EFI_BOOT_SERVICES.WaitForEvent()`] before iterating
+/// again.
+///
+/// [`PointerEvent`]: crate::protocols::console::pointer::PointerEvent
+/// [`wait_events()`]: crate::protocols::console::pointer::PointerEvents::wait_events
+/// [`EFI_BOOT_SERVICES.WaitForEvent()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-waitforevent
+pub struct PointerEvents<'a> {
+    simple: Option<&'a mut EFI_SIMPLE_POINTER_PROTOCOL>,
+    absolute: Option<&'a mut EFI_ABSOLUTE_POINTER_PROTOCOL>,
+    events: [EFI_EVENT; 2],
+    event_count: usize,
+    width: usize,
+    height: usize,
+    x: usize,
+    y: usize,
+    buttons: UINT32,
+    speed: UINT32,
+    remainder: (INT64, INT64),
+}
+
+impl<'a> PointerEvents<'a> {
+    /// Creates a stream for a screen of `Width` by `Height` pixels, with the pointer at its
+    /// center and no devices.
+    pub fn new(Width: usize, Height: usize) -> Self {
+        Self {
+            simple: None,
+            absolute: None,
+            events: [core::ptr::null_mut(); 2],
+            event_count: 0,
+            width: Width.max(1),
+            height: Height.max(1),
+            x: Width / 2,
+            y: Height / 2,
+            buttons: 0,
+            speed: DEFAULT_POINTER_SPEED,
+            remainder: (0, 0),
+        }
+    }
+
+    /// Adds a relative pointer device to the stream, replacing the previous one.
+    ///
+    /// # Safety
+    ///
+    /// `Protocol` and its mode must be valid for the lifetime `'a`.
+    pub unsafe fn with_simple(mut self, Protocol: &'a mut EFI_SIMPLE_POINTER_PROTOCOL) -> Self {
+        self.simple = Some(Protocol);
+        self.update_events();
+        self
+    }
+
+    /// Adds an absolute pointer device to the stream, replacing the previous one.
+    ///
+    /// # Safety
+    ///
+    /// `Protocol` and its mode must be valid for the lifetime `'a`.
+    pub unsafe fn with_absolute(mut self, Protocol: &'a mut EFI_ABSOLUTE_POINTER_PROTOCOL) -> Self {
+        self.absolute = Some(Protocol);
+        self.update_events();
+        self
+    }
+
+    /// Returns the events signaled when a device of the stream has input, to be waited on with
+    /// [`EFI_BOOT_SERVICES.WaitForEvent()`].
+    ///
+    /// [`EFI_BOOT_SERVICES.WaitForEvent()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-waitforevent
+    pub fn wait_events(&self) -> &[EFI_EVENT] {
+        &self.events[..self.event_count]
+    }
+
+    /// Returns the position of the pointer.
+    pub fn position(&self) -> (usize, usize) {
+        (self.x, self.y)
+    }
+
+    /// Moves the pointer to (`X`, `Y`), clamped to the screen.
+    pub fn set_position(&mut self, X: usize, Y: usize) {
+        self.x = X.min(self.width - 1);
+        self.y = Y.min(self.height - 1);
+    }
+
+    /// Returns the buttons held, a combination of [`POINTER_BUTTON_PRIMARY`] and
+    /// [`POINTER_BUTTON_SECONDARY`].
+    ///
+    /// [`POINTER_BUTTON_PRIMARY`]: crate::protocols::console::pointer::POINTER_BUTTON_PRIMARY
+    /// [`POINTER_BUTTON_SECONDARY`]: crate::protocols::console::pointer::POINTER_BUTTON_SECONDARY
+    pub fn buttons(&self) -> UINT32 {
+        self.buttons
+    }
+
+    /// Sets the speed of relative devices in pixels per millimeter of movement. Devices that do
+    /// not report their resolution move one pixel per count.
+    pub fn set_speed(&mut self, PixelsPerMillimeter: UINT32) {
+        self.speed = PixelsPerMillimeter;
+    }
+
+    /// Changes the size of the screen, clamping the pointer to it.
+    pub fn set_screen_size(&mut self, Width: usize, Height: usize) {
+        self.width = Width.max(1);
+        self.height = Height.max(1);
+        self.set_position(self.x, self.y);
+    }
+
+    /// Returns the next pending event of the devices, or `None` if there is none.
+    pub fn poll(&mut self) -> Result<Option<PointerEvent>, EFI_STATUS> {
+        if let Some(protocol) = self.simple.as_deref_mut() {
+            // SAFETY: `with_simple()` requires the protocol to be valid.
+            if let Some(state) = unsafe { protocol.get_state()? } {
+                let (x, y) = unsafe { (protocol.mode().ResolutionX, protocol.mode().ResolutionY) };
+                return Ok(Some(self.relative(state, x, y)));
+            }
+        }
+
+        if let Some(protocol) = self.absolute.as_deref_mut() {
+            // SAFETY: `with_absolute()` requires the protocol to be valid.
+            if let Some(state) = unsafe { protocol.get_state()? } {
+                let mode = unsafe { *protocol.mode() };
+                let x = scale(
+                    state.CurrentX,
+                    mode.AbsoluteMinX,
+                    mode.AbsoluteMaxX,
+                    self.width,
+                );
+                let y = scale(
+                    state.CurrentY,
+                    mode.AbsoluteMinY,
+                    mode.AbsoluteMaxY,
+                    self.height,
+                );
+                return Ok(Some(self.absolute(state, x, y)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn relative(
+        &mut self,
+        State: EFI_SIMPLE_POINTER_STATE,
+        ResolutionX: UINT64,
+        ResolutionY: UINT64,
+    ) -> PointerEvent {
+        let dx = to_pixels(
+            State.RelativeMovementX,
+            ResolutionX,
+            self.speed,
+            &mut self.remainder.0,
+        );
+        let dy = to_pixels(
+            State.RelativeMovementY,
+            ResolutionY,
+            self.speed,
+            &mut self.remainder.1,
+        );
+        let x = offset(self.x, dx, self.width);
+        let y = offset(self.y, dy, self.height);
+
+        let mut buttons = 0;
+        if State.LeftButton != 0 {
+            buttons |= POINTER_BUTTON_PRIMARY;
+        }
+        if State.RightButton != 0 {
+            buttons |= POINTER_BUTTON_SECONDARY;
+        }
+
+        let mut event = self.update(PointerSource::Relative, x, y, buttons);
+        event.DeltaZ = State.RelativeMovementZ;
+        event
+    }
+
+    fn absolute(
+        &mut self,
+        State: EFI_ABSOLUTE_POINTER_STATE,
+        X: Option<usize>,
+        Y: Option<usize>,
+    ) -> PointerEvent {
+        // The button bits of the protocol match ours.
+        let buttons = State.ActiveButtons & (POINTER_BUTTON_PRIMARY | POINTER_BUTTON_SECONDARY);
+        self.update(
+            PointerSource::Absolute,
+            X.unwrap_or(self.x),
+            Y.unwrap_or(self.y),
+            buttons,
+        )
+    }
+
+    fn update(
+        &mut self,
+        Source: PointerSource,
+        X: usize,
+        Y: usize,
+        Buttons: UINT32,
+    ) -> PointerEvent {
+        let event = PointerEvent {
+            Source,
+            X,
+            Y,
+            DeltaX: (X as INT64 - self.x as INT64) as INT32,
+            DeltaY: (Y as INT64 - self.y as INT64) as INT32,
+            DeltaZ: 0,
+            Buttons,
+            Pressed: Buttons & !self.buttons,
+            Released: self.buttons & !Buttons,
+        };
+
+        self.x = X;
+        self.y = Y;
+        self.buttons = Buttons;
+        event
+    }
+
+    fn update_events(&mut self) {
+        self.event_count = 0;
+        let simple = self.simple.as_deref().map(|protocol| protocol.WaitForInput);
+        let absolute = self
+            .absolute
+            .as_deref()
+            .map(|protocol| protocol.WaitForInput);
+        for event in [simple, absolute].into_iter().flatten() {
+            self.events[self.event_count] = event;
+            self.event_count += 1;
+        }
+    }
+}
+
+impl Iterator for PointerEvents<'_> {
+    type Item = Result<PointerEvent, EFI_STATUS>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.poll().transpose()
+    }
+}
+
+/// Converts `Counts` of movement of a device with `Resolution` counts per millimeter to pixels,
+/// carrying the fraction of a pixel over to the next movement in `Remainder`.
+fn to_pixels(Counts: INT32, Resolution: UINT64, Speed: UINT32, Remainder: &mut INT64) -> INT64 {
+    if Resolution == 0 {
+        return INT64::from(Counts);
+    }
+
+    let resolution = Resolution.min(INT64::MAX as UINT64) as INT64;
+    let scaled = INT64::from(Counts) * INT64::from(Speed) + *Remainder;
+    *Remainder = scaled % resolution;
+    scaled / resolution
+}
+
+/// Moves `Position` by `Delta`, clamped to `0..Limit`.
+fn offset(Position: usize, Delta: INT64, Limit: usize) -> usize {
+    let position = (Position as INT64).saturating_add(Delta);
+    position.clamp(0, Limit as INT64 - 1) as usize
+}
+
+/// Scales `Value` from `Min..=Max` to `0..Limit`, or returns `None` if the axis is not supported.
+fn scale(Value: UINT64, Min: UINT64, Max: UINT64, Limit: usize) -> Option<usize> {
+    if Max <= Min {
+        return None;
+    }
+
+    let value = u128::from(Value.clamp(Min, Max) - Min);
+    Some((value * (Limit as u128 - 1) / u128::from(Max - Min)) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movement(X: INT32, Y: INT32, Left: bool) -> EFI_SIMPLE_POINTER_STATE {
+        EFI_SIMPLE_POINTER_STATE {
+            RelativeMovementX: X,
+            RelativeMovementY: Y,
+            RelativeMovementZ: 0,
+            LeftButton: Left as _,
+            RightButton: 0,
+        }
+    }
+
+    #[test]
+    fn pixels_carry_remainder() {
+        let mut remainder = 0;
+        // Eight counts per millimeter at four pixels per millimeter is half a pixel per count.
+        assert_eq!(to_pixels(3, 8, 4, &mut remainder), 1);
+        assert_eq!(remainder, 4);
+        assert_eq!(to_pixels(1, 8, 4, &mut remainder), 1);
+        assert_eq!(remainder, 0);
+
+        assert_eq!(to_pixels(-1, 8, 4, &mut remainder), 0);
+        assert_eq!(to_pixels(-1, 8, 4, &mut remainder), -1);
+        assert_eq!(remainder, 0);
+
+        assert_eq!(to_pixels(100, 8, 0, &mut remainder), 0);
+        assert_eq!(to_pixels(INT32::MAX, UINT64::MAX, 4, &mut remainder), 0);
+    }
+
+    #[test]
+    fn pixels_without_resolution() {
+        let mut remainder = 0;
+        assert_eq!(to_pixels(-7, 0, 4, &mut remainder), -7);
+        assert_eq!(
+            to_pixels(INT32::MIN, 0, 4, &mut remainder),
+            INT64::from(INT32::MIN)
+        );
+        assert_eq!(remainder, 0);
+    }
+
+    #[test]
+    fn offset_clamps_to_screen() {
+        assert_eq!(offset(5, 3, 10), 8);
+        assert_eq!(offset(5, 10, 10), 9);
+        assert_eq!(offset(5, -6, 10), 0);
+        assert_eq!(offset(0, INT64::MIN, 10), 0);
+        assert_eq!(offset(9, INT64::MAX, 10), 9);
+        assert_eq!(offset(0, 1, 1), 0);
+    }
+
+    #[test]
+    fn scale_absolute_axes() {
+        assert_eq!(scale(0, 0, 100, 11), Some(0));
+        assert_eq!(scale(50, 0, 100, 11), Some(5));
+        assert_eq!(scale(100, 0, 100, 11), Some(10));
+        // Out of range positions are clamped to the edges of the screen.
+        assert_eq!(scale(5, 10, 110, 11), Some(0));
+        assert_eq!(scale(500, 10, 110, 11), Some(10));
+        assert_eq!(scale(UINT64::MAX, 0, UINT64::MAX, 1920), Some(1919));
+        assert_eq!(scale(7, 0, 100, 1), Some(0));
+        // An axis without range is not supported.
+        assert_eq!(scale(0, 0, 0, 11), None);
+        assert_eq!(scale(0, 100, 10, 11), None);
+    }
+
+    #[test]
+    fn relative_events() {
+        let mut events = PointerEvents::new(100, 50);
+        events.set_speed(1);
+        assert_eq!(events.position(), (50, 25));
+
+        let event = events.relative(movement(20, -40, true), 2, 0);
+        assert_eq!((event.X, event.Y), (60, 0));
+        assert_eq!((event.DeltaX, event.DeltaY), (10, -25));
+        assert_eq!(event.Source, PointerSource::Relative);
+        assert_eq!(event.Pressed, POINTER_BUTTON_PRIMARY);
+
+        let event = events.relative(movement(1000, 1000, false), 0, 0);
+        assert_eq!((event.X, event.Y), (99, 49));
+        assert_eq!((event.DeltaX, event.DeltaY), (39, 49));
+        assert_eq!((event.Buttons, event.Released), (0, POINTER_BUTTON_PRIMARY));
+    }
+
+    #[test]
+    fn absolute_events() {
+        let mut events = PointerEvents::new(100, 50);
+        let state = EFI_ABSOLUTE_POINTER_STATE {
+            ActiveButtons: POINTER_BUTTON_SECONDARY | 0x4,
+            ..Default::default()
+        };
+
+        let event = events.absolute(state, Some(99), None);
+        assert_eq!((event.X, event.Y), (99, 25));
+        assert_eq!(event.DeltaX, 49);
+        assert_eq!(event.Source, PointerSource::Absolute);
+        assert_eq!(event.Buttons, POINTER_BUTTON_SECONDARY);
+    }
+
+    #[test]
+    fn empty_screen() {
+        let mut events = PointerEvents::new(0, 0);
+        assert_eq!(events.position(), (0, 0));
+        let event = events.relative(movement(10, 10, false), 0, 0);
+        assert_eq!((event.X, event.Y), (0, 0));
+
+        events.set_screen_size(10, 10);
+        events.set_position(20, 5);
+        assert_eq!(events.position(), (9, 5));
+        events.set_screen_size(4, 0);
+        assert_eq!(events.position(), (3, 0));
+    }
+}
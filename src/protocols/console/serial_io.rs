@@ -0,0 +1,885 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Serial I/O Protocol
+//!
+//! This module defines the Serial I/O Protocol, also known as the [`EFI_SERIAL_IO_PROTOCOL`],
+//! which is used to communicate with UART-style serial devices. See
+//! [Section 12.8 of the UEFI Specification] for more details.
+//!
+//! [`SerialConsole`] buffers the output to a serial device and implements [`core::fmt::Write`],
+//! so a serial port can serve as the console of a headless machine.
+//!
+//! [`EFI_SERIAL_IO_PROTOCOL`]: crate::protocols::console::EFI_SERIAL_IO_PROTOCOL
+//! [`SerialConsole`]: crate::protocols::console::serial_io::SerialConsole
+//!
+//! [Section 12.8 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/12_Protocols_Console_Support.html#serial-i-o-protocol
+
+use core::{fmt, mem};
+
+use crate::io::{Read, Write};
+use crate::protocols::Protocol;
+use crate::status::{EFI_SUCCESS, EFI_TIMEOUT};
+use crate::types::{EFI_GUID, EFI_STATUS, UINT32, UINT64, UINT8, UINTN, VOID};
+
+/// GUID for the [`EFI_SERIAL_IO_PROTOCOL`].
+///
+/// [`EFI_SERIAL_IO_PROTOCOL`]: crate::protocols::console::EFI_SERIAL_IO_PROTOCOL
+pub const EFI_SERIAL_IO_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0xBB25CF6F,
+    0xF1D4,
+    0x11D2,
+    [0x9A, 0x0C, 0x00, 0x90, 0x27, 0x3F, 0xC1, 0xFD],
+);
+
+/// GUID of the [`DeviceTypeGuid`] of a serial device used as a terminal.
+///
+/// [`DeviceTypeGuid`]: ./struct.EFI_SERIAL_IO_PROTOCOL.html#structfield.DeviceTypeGuid
+pub const EFI_SERIAL_TERMINAL_DEVICE_TYPE_GUID: EFI_GUID = EFI_GUID(
+    0x6AD9A60F,
+    0x5815,
+    0x4C7C,
+    [0x8A, 0x10, 0x50, 0x53, 0xD2, 0xBF, 0x7A, 0x1B],
+);
+
+/// The revision of the [`EFI_SERIAL_IO_PROTOCOL`] defined by version 1.0 of the specification.
+///
+/// [`EFI_SERIAL_IO_PROTOCOL`]: crate::protocols::console::EFI_SERIAL_IO_PROTOCOL
+pub const EFI_SERIAL_IO_PROTOCOL_REVISION: UINT32 = 0x00010000;
+/// The revision of the [`EFI_SERIAL_IO_PROTOCOL`] adding the [`DeviceTypeGuid`] field.
+///
+/// [`EFI_SERIAL_IO_PROTOCOL`]: crate::protocols::console::EFI_SERIAL_IO_PROTOCOL
+/// [`DeviceTypeGuid`]: ./struct.EFI_SERIAL_IO_PROTOCOL.html#structfield.DeviceTypeGuid
+pub const EFI_SERIAL_IO_PROTOCOL_REVISION1p1: UINT32 = 0x00010001;
+
+/// Data Terminal Ready, writable with [`SetControl()`].
+///
+/// [`SetControl()`]: crate::protocols::console::EFI_SERIAL_IO_PROTOCOL::SetControl
+pub const EFI_SERIAL_DATA_TERMINAL_READY: UINT32 = 0x0001;
+/// Request To Send, writable with [`SetControl()`].
+///
+/// [`SetControl()`]: crate::protocols::console::EFI_SERIAL_IO_PROTOCOL::SetControl
+pub const EFI_SERIAL_REQUEST_TO_SEND: UINT32 = 0x0002;
+/// Clear To Send, read-only.
+pub const EFI_SERIAL_CLEAR_TO_SEND: UINT32 = 0x0010;
+/// Data Set Ready, read-only.
+pub const EFI_SERIAL_DATA_SET_READY: UINT32 = 0x0020;
+/// Ring Indicate, read-only.
+pub const EFI_SERIAL_RING_INDICATE: UINT32 = 0x0040;
+/// Carrier Detect, read-only.
+pub const EFI_SERIAL_CARRIER_DETECT: UINT32 = 0x0080;
+/// The receive buffer is empty, read-only.
+pub const EFI_SERIAL_INPUT_BUFFER_EMPTY: UINT32 = 0x0100;
+/// The transmit buffer is empty, read-only.
+pub const EFI_SERIAL_OUTPUT_BUFFER_EMPTY: UINT32 = 0x0200;
+/// Hardware loopback, writable with [`SetControl()`].
+///
+/// [`SetControl()`]: crate::protocols::console::EFI_SERIAL_IO_PROTOCOL::SetControl
+pub const EFI_SERIAL_HARDWARE_LOOPBACK_ENABLE: UINT32 = 0x1000;
+/// Software loopback, writable with [`SetControl()`].
+///
+/// [`SetControl()`]: crate::protocols::console::EFI_SERIAL_IO_PROTOCOL::SetControl
+pub const EFI_SERIAL_SOFTWARE_LOOPBACK_ENABLE: UINT32 = 0x2000;
+/// Hardware flow control, writable with [`SetControl()`].
+///
+/// [`SetControl()`]: crate::protocols::console::EFI_SERIAL_IO_PROTOCOL::SetControl
+pub const EFI_SERIAL_HARDWARE_FLOW_CONTROL_ENABLE: UINT32 = 0x4000;
+
+/// The size of the output buffer of a [`SerialConsole`] in bytes.
+///
+/// [`SerialConsole`]: crate::protocols::console::serial_io::SerialConsole
+pub const SERIAL_CONSOLE_BUFFER_SIZE: usize = 256;
+
+/// Used to communicate with any type of character-based I/O device.
+///
+/// The Serial I/O protocol is used to communicate with UART-style serial devices. These can be
+/// standard UART serial ports in PC-AT systems, serial ports attached to a USB interface, or
+/// potentially any character-based I/O device.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_SERIAL_IO_PROTOCOL {
+    /// The revision to which the [`EFI_SERIAL_IO_PROTOCOL`] adheres. All future revisions must
+    /// be backwards compatible.
+    ///
+    /// [`EFI_SERIAL_IO_PROTOCOL`]: crate::protocols::console::EFI_SERIAL_IO_PROTOCOL
+    pub Revision: UINT32,
+    Reset: EFI_SERIAL_RESET,
+    SetAttributes: EFI_SERIAL_SET_ATTRIBUTES,
+    SetControl: EFI_SERIAL_SET_CONTROL_BITS,
+    GetControl: EFI_SERIAL_GET_CONTROL_BITS,
+    Write: EFI_SERIAL_WRITE,
+    Read: EFI_SERIAL_READ,
+    /// Pointer to [`SERIAL_IO_MODE`] data.
+    ///
+    /// [`SERIAL_IO_MODE`]: crate::protocols::console::serial_io::SERIAL_IO_MODE
+    pub Mode: *mut SERIAL_IO_MODE,
+    /// Pointer to a GUID identifying the device connected to the serial port. Only present if
+    /// [`Revision`] is at least [`EFI_SERIAL_IO_PROTOCOL_REVISION1p1`].
+    ///
+    /// [`Revision`]: ./struct.EFI_SERIAL_IO_PROTOCOL.html#structfield.Revision
+    /// [`EFI_SERIAL_IO_PROTOCOL_REVISION1p1`]: crate::protocols::console::serial_io::EFI_SERIAL_IO_PROTOCOL_REVISION1p1
+    pub DeviceTypeGuid: *const EFI_GUID,
+}
+
+unsafe impl Protocol for EFI_SERIAL_IO_PROTOCOL {
+    const GUID: EFI_GUID = EFI_SERIAL_IO_PROTOCOL_GUID;
+}
+
+impl EFI_SERIAL_IO_PROTOCOL {
+    /// Resets the serial device.
+    ///
+    /// The [`Reset()`] function resets the hardware of a serial device.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the serial device was reset.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the serial device could not be reset.
+    ///
+    /// [`Reset()`]: ./struct.EFI_SERIAL_IO_PROTOCOL.html#method.Reset
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    pub unsafe fn Reset(&mut self) -> EFI_STATUS {
+        (self.Reset)(self)
+    }
+
+    /// Sets the baud rate, receive FIFO depth, transmit/receive time out, parity, data bits, and
+    /// stop bits on a serial device.
+    ///
+    /// The [`SetAttributes()`] function sets the baud rate, receive-FIFO depth,
+    /// transmit/receive time out, parity, data bits, and stop bits on a serial device. A value
+    /// of zero for `BaudRate`, `ReceiveFifoDepth`, `Timeout` or `DataBits`, and the default
+    /// values of `Parity` and `StopBits`, select the default of the device.
+    ///
+    /// The controller for a serial device is programmed with the specified attributes. If the
+    /// `Parity`, `DataBits`, or `StopBits` values are not valid, then an error will be returned.
+    /// If the specified `BaudRate` is below the minimum baud rate supported by the serial
+    /// device, an error will be returned. The nearest baud rate supported by the serial device
+    /// will be selected without exceeding the `BaudRate` parameter. If the specified
+    /// `ReceiveFifoDepth` is below the smallest FIFO size supported by the serial device, an
+    /// error will be returned. The nearest FIFO size supported by the serial device will be
+    /// selected without exceeding the `ReceiveFifoDepth` parameter.
+    ///
+    /// # Parameters
+    ///
+    /// ## `BaudRate`
+    ///
+    /// The requested baud rate.
+    ///
+    /// ## `ReceiveFifoDepth`
+    ///
+    /// The requested depth of the FIFO on the receive side of the serial interface.
+    ///
+    /// ## `Timeout`
+    ///
+    /// The requested time out for a single character in microseconds. This timeout applies to
+    /// both the transmit and receive side of the interface.
+    ///
+    /// ## `Parity`
+    ///
+    /// The type of parity to use on this serial device.
+    ///
+    /// ## `DataBits`
+    ///
+    /// The number of data bits to use on this serial device.
+    ///
+    /// ## `StopBits`
+    ///
+    /// The number of stop bits to use on this serial device.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the new attributes were set on the serial device.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - one or more of the attributes has an unsupported value.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the serial device is not functioning correctly.
+    ///
+    /// [`SetAttributes()`]: ./struct.EFI_SERIAL_IO_PROTOCOL.html#method.SetAttributes
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    pub unsafe fn SetAttributes(
+        &mut self,
+        BaudRate: UINT64,
+        ReceiveFifoDepth: UINT32,
+        Timeout: UINT32,
+        Parity: EFI_PARITY_TYPE,
+        DataBits: UINT8,
+        StopBits: EFI_STOP_BITS_TYPE,
+    ) -> EFI_STATUS {
+        (self.SetAttributes)(
+            self,
+            BaudRate,
+            ReceiveFifoDepth,
+            Timeout,
+            Parity,
+            DataBits,
+            StopBits,
+        )
+    }
+
+    /// Sets the control bits on a serial device.
+    ///
+    /// The [`SetControl()`] function is used to assert or deassert the control signals on a
+    /// serial device. Only [`EFI_SERIAL_REQUEST_TO_SEND`], [`EFI_SERIAL_DATA_TERMINAL_READY`],
+    /// [`EFI_SERIAL_HARDWARE_LOOPBACK_ENABLE`], [`EFI_SERIAL_SOFTWARE_LOOPBACK_ENABLE`] and
+    /// [`EFI_SERIAL_HARDWARE_FLOW_CONTROL_ENABLE`] can be set.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Control`
+    ///
+    /// Sets the bits of Control that are settable.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the new control bits were set on the serial device.
+    ///
+    /// [`EFI_UNSUPPORTED`] - the serial device does not support this operation.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the serial device is not functioning correctly.
+    ///
+    /// [`SetControl()`]: ./struct.EFI_SERIAL_IO_PROTOCOL.html#method.SetControl
+    /// [`EFI_SERIAL_REQUEST_TO_SEND`]: crate::protocols::console::serial_io::EFI_SERIAL_REQUEST_TO_SEND
+    /// [`EFI_SERIAL_DATA_TERMINAL_READY`]: crate::protocols::console::serial_io::EFI_SERIAL_DATA_TERMINAL_READY
+    /// [`EFI_SERIAL_HARDWARE_LOOPBACK_ENABLE`]: crate::protocols::console::serial_io::EFI_SERIAL_HARDWARE_LOOPBACK_ENABLE
+    /// [`EFI_SERIAL_SOFTWARE_LOOPBACK_ENABLE`]: crate::protocols::console::serial_io::EFI_SERIAL_SOFTWARE_LOOPBACK_ENABLE
+    /// [`EFI_SERIAL_HARDWARE_FLOW_CONTROL_ENABLE`]: crate::protocols::console::serial_io::EFI_SERIAL_HARDWARE_FLOW_CONTROL_ENABLE
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    pub unsafe fn SetControl(&mut self, Control: UINT32) -> EFI_STATUS {
+        (self.SetControl)(self, Control)
+    }
+
+    /// Retrieves the status of the control bits on a serial device.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Control`
+    ///
+    /// A pointer to return the current control signals from the serial device.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the control bits were read from the serial device.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the serial device is not functioning correctly.
+    ///
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    pub unsafe fn GetControl(&mut self, Control: *mut UINT32) -> EFI_STATUS {
+        (self.GetControl)(self, Control)
+    }
+
+    /// Writes data to a serial device.
+    ///
+    /// The [`Write()`] function writes the specified number of bytes to a serial device. If a
+    /// time out error occurs while data is being sent to the serial port, transmission of this
+    /// buffer will terminate, and [`EFI_TIMEOUT`] will be returned. In all cases the number of
+    /// bytes actually written to the serial device is returned in `BufferSize`.
+    ///
+    /// # Parameters
+    ///
+    /// ## `BufferSize`
+    ///
+    /// On input, the size of the `Buffer`. On output, the amount of data actually written.
+    ///
+    /// ## `Buffer`
+    ///
+    /// The buffer of data to write.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the data was written.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error.
+    ///
+    /// [`EFI_TIMEOUT`] - the data write was stopped due to a timeout.
+    ///
+    /// [`Write()`]: ./struct.EFI_SERIAL_IO_PROTOCOL.html#method.Write
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_TIMEOUT`]: crate::status::EFI_TIMEOUT
+    pub unsafe fn Write(&mut self, BufferSize: *mut UINTN, Buffer: *mut VOID) -> EFI_STATUS {
+        (self.Write)(self, BufferSize, Buffer)
+    }
+
+    /// Reads data from a serial device.
+    ///
+    /// The [`Read()`] function reads a specified number of bytes from a serial device. If a
+    /// time out error or an overrun error is detected while data is being read from the serial
+    /// device, then no more characters will be read, and an error will be returned. In all
+    /// cases the number of bytes actually read is returned in `BufferSize`.
+    ///
+    /// # Parameters
+    ///
+    /// ## `BufferSize`
+    ///
+    /// On input, the size of the `Buffer`. On output, the amount of data returned in `Buffer`.
+    ///
+    /// ## `Buffer`
+    ///
+    /// The buffer to return the data into.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the data was read.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the serial device reported an error.
+    ///
+    /// [`EFI_TIMEOUT`] - the operation was stopped due to a timeout or overrun.
+    ///
+    /// [`Read()`]: ./struct.EFI_SERIAL_IO_PROTOCOL.html#method.Read
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_TIMEOUT`]: crate::status::EFI_TIMEOUT
+    pub unsafe fn Read(&mut self, BufferSize: *mut UINTN, Buffer: *mut VOID) -> EFI_STATUS {
+        (self.Read)(self, BufferSize, Buffer)
+    }
+
+    /// Returns the current attributes of the serial device.
+    pub unsafe fn mode(&self) -> &SERIAL_IO_MODE {
+        &*self.Mode
+    }
+
+    /// Returns the GUID identifying the device connected to the serial port, if the revision of
+    /// the protocol defines it.
+    pub unsafe fn device_type(&self) -> Option<EFI_GUID> {
+        if self.Revision < EFI_SERIAL_IO_PROTOCOL_REVISION1p1 || self.DeviceTypeGuid.is_null() {
+            None
+        } else {
+            Some(*self.DeviceTypeGuid)
+        }
+    }
+
+    /// Sets the attributes of the serial device. See [`SetAttributes()`] for the meaning of the
+    /// parameters.
+    ///
+    /// [`SetAttributes()`]: ./struct.EFI_SERIAL_IO_PROTOCOL.html#method.SetAttributes
+    pub unsafe fn set_attributes(
+        &mut self,
+        BaudRate: UINT64,
+        ReceiveFifoDepth: UINT32,
+        Timeout: UINT32,
+        Parity: EFI_PARITY_TYPE,
+        DataBits: UINT8,
+        StopBits: EFI_STOP_BITS_TYPE,
+    ) -> Result<(), EFI_STATUS> {
+        match self.SetAttributes(
+            BaudRate,
+            ReceiveFifoDepth,
+            Timeout,
+            Parity,
+            DataBits,
+            StopBits,
+        ) {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Sets the writable control bits of the serial device to `Control`.
+    pub unsafe fn set_control(&mut self, Control: UINT32) -> Result<(), EFI_STATUS> {
+        match self.SetControl(Control) {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Returns the control bits of the serial device.
+    pub unsafe fn get_control(&mut self) -> Result<UINT32, EFI_STATUS> {
+        let mut control = 0;
+        match self.GetControl(&mut control) {
+            EFI_SUCCESS => Ok(control),
+            status => Err(status),
+        }
+    }
+
+    /// Writes `Buffer` to the serial device, returning the number of bytes written.
+    ///
+    /// A timeout after some bytes were written is not an error; [`EFI_TIMEOUT`] is returned
+    /// only if no byte could be written.
+    ///
+    /// [`EFI_TIMEOUT`]: crate::status::EFI_TIMEOUT
+    pub unsafe fn write(&mut self, Buffer: &[UINT8]) -> Result<usize, EFI_STATUS> {
+        let mut size = Buffer.len();
+        match self.Write(&mut size, Buffer.as_ptr() as *mut VOID) {
+            EFI_SUCCESS => Ok(size),
+            EFI_TIMEOUT if size > 0 => Ok(size),
+            status => Err(status),
+        }
+    }
+
+    /// Reads into `Buffer` from the serial device, returning the number of bytes read.
+    ///
+    /// A timeout is not an error; the bytes received until then are returned, which may be
+    /// none.
+    pub unsafe fn read(&mut self, Buffer: &mut [UINT8]) -> Result<usize, EFI_STATUS> {
+        let mut size = Buffer.len();
+        match self.Read(&mut size, Buffer.as_mut_ptr().cast()) {
+            EFI_SUCCESS | EFI_TIMEOUT => Ok(size),
+            status => Err(status),
+        }
+    }
+}
+
+/// The current attributes of a serial device.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct SERIAL_IO_MODE {
+    /// A mask of the control bits that the device supports.
+    pub ControlMask: UINT32,
+    /// If applicable, the number of microseconds to wait before timing out a Read or Write
+    /// operation.
+    pub Timeout: UINT32,
+    /// If applicable, the current baud rate setting of the device; otherwise, baud rate has the
+    /// value of zero to indicate that device runs at the device's designed speed.
+    pub BaudRate: UINT64,
+    /// The number of characters the device will buffer on input.
+    pub ReceiveFifoDepth: UINT32,
+    /// The number of data bits in each character.
+    pub DataBits: UINT32,
+    /// If applicable, this is the [`EFI_PARITY_TYPE`] that is computed or checked as each
+    /// character is transmitted or received. If the device does not support parity the value
+    /// is the default parity value.
+    ///
+    /// [`EFI_PARITY_TYPE`]: crate::protocols::console::serial_io::EFI_PARITY_TYPE
+    pub Parity: UINT32,
+    /// If applicable, the [`EFI_STOP_BITS_TYPE`] number of stop bits per character. If the
+    /// device does not support stop bits the value is the default stop bit value.
+    ///
+    /// [`EFI_STOP_BITS_TYPE`]: crate::protocols::console::serial_io::EFI_STOP_BITS_TYPE
+    pub StopBits: UINT32,
+}
+
+/// The parity of a serial device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub enum EFI_PARITY_TYPE {
+    /// The default parity of the device.
+    DefaultParity,
+    /// No parity bit.
+    NoParity,
+    /// Even parity.
+    EvenParity,
+    /// Odd parity.
+    OddParity,
+    /// The parity bit is always set.
+    MarkParity,
+    /// The parity bit is always clear.
+    SpaceParity,
+}
+
+/// The number of stop bits of a serial device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub enum EFI_STOP_BITS_TYPE {
+    /// The default number of stop bits of the device.
+    DefaultStopBits,
+    /// One stop bit.
+    OneStopBit,
+    /// One and a half stop bits, only valid with 5 data bits.
+    OneFiveStopBits,
+    /// Two stop bits.
+    TwoStopBits,
+}
+
+/// A line-oriented console on a serial device.
+///
+/// Output is buffered and sent when a line is complete, when the buffer is full, or on
+/// [`flush()`]. Through [`core::fmt::Write`], line feeds are translated to the carriage return
+/// and line feed pair expected by terminals, so the console can be used with [`write!`] and
+/// [`writeln!`]. Through [`Write`] and [`Read`], bytes are passed unchanged.
+///
+/// The buffer is flushed when the console is dropped.
+///
+/// [`flush()`]: crate::io::Write::flush
+/// [`Write`]: crate::io::Write
+/// [`Read`]: crate::io::Read
+pub struct SerialConsole<'a> {
+    protocol: &'a mut EFI_SERIAL_IO_PROTOCOL,
+    buffer: [UINT8; SERIAL_CONSOLE_BUFFER_SIZE],
+    length: usize,
+    error: Option<EFI_STATUS>,
+    // The failure to send a line whose bytes were already accepted by `write()`, reported by the
+    // next write.
+    send_error: Option<EFI_STATUS>,
+    // Whether the last line read ended with a carriage return, so a line feed right after it
+    // belongs to the same terminator.
+    carriage_return: bool,
+}
+
+impl<'a> SerialConsole<'a> {
+    /// Creates a console on the serial device `Protocol`.
+    ///
+    /// # Safety
+    ///
+    /// `Protocol` must be valid for the lifetime `'a`.
+    pub unsafe fn new(Protocol: &'a mut EFI_SERIAL_IO_PROTOCOL) -> Self {
+        Self {
+            protocol: Protocol,
+            buffer: [0; SERIAL_CONSOLE_BUFFER_SIZE],
+            length: 0,
+            error: None,
+            send_error: None,
+            carriage_return: false,
+        }
+    }
+
+    /// Returns the serial device.
+    pub fn protocol(&mut self) -> &mut EFI_SERIAL_IO_PROTOCOL {
+        self.protocol
+    }
+
+    /// Returns and clears the status of the last failed write through [`core::fmt::Write`],
+    /// which can only report that an error happened.
+    pub fn take_error(&mut self) -> Option<EFI_STATUS> {
+        self.error.take()
+    }
+
+    /// Reads a byte, or returns `None` if none arrived before the timeout of the device.
+    pub fn read_byte(&mut self) -> Result<Option<UINT8>, EFI_STATUS> {
+        let mut byte = [0];
+        match self.read(&mut byte)? {
+            0 => Ok(None),
+            _ => Ok(Some(byte[0])),
+        }
+    }
+
+    /// Reads a line into `Buffer`, echoing it, and returns its length without the line
+    /// terminator.
+    ///
+    /// The line ends with a carriage return, a line feed, or both in that order. Backspace and
+    /// delete remove the last byte. Input beyond the size of `Buffer` is discarded.
+    pub fn read_line(&mut self, Buffer: &mut [UINT8]) -> Result<usize, EFI_STATUS> {
+        let mut length = 0;
+        loop {
+            self.flush()?;
+            let byte = match self.read_byte()? {
+                Some(byte) => byte,
+                None => continue,
+            };
+            if mem::replace(&mut self.carriage_return, false) && byte == b'\n' {
+                continue;
+            }
+
+            match byte {
+                b'\r' | b'\n' => {
+                    self.carriage_return = byte == b'\r';
+                    self.write_all(b"\r\n")?;
+                    self.flush()?;
+                    return Ok(length);
+                }
+                0x08 | 0x7F if length > 0 => {
+                    length -= 1;
+                    self.write_all(b"\x08 \x08")?;
+                }
+                0x08 | 0x7F => {}
+                _ if length < Buffer.len() => {
+                    Buffer[length] = byte;
+                    length += 1;
+                    self.write_all(&[byte])?;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Sends the buffered bytes to the device, keeping those that could not be sent for the
+    /// next attempt.
+    ///
+    /// Fails with [`EFI_TIMEOUT`] if the device accepts no byte.
+    ///
+    /// [`EFI_TIMEOUT`]: crate::status::EFI_TIMEOUT
+    fn send_buffer(&mut self) -> Result<(), EFI_STATUS> {
+        let mut sent = 0;
+        let mut result = Ok(());
+        while sent < self.length {
+            // SAFETY: `new()` requires the protocol to be valid.
+            match unsafe { self.protocol.write(&self.buffer[sent..self.length]) } {
+                Ok(0) => {
+                    result = Err(EFI_TIMEOUT);
+                    break;
+                }
+                Ok(written) => sent += written.min(self.length - sent),
+                Err(status) => {
+                    result = Err(status);
+                    break;
+                }
+            }
+        }
+
+        self.buffer.copy_within(sent..self.length, 0);
+        self.length -= sent;
+        result
+    }
+}
+
+impl Read for SerialConsole<'_> {
+    /// Reads the bytes received before the timeout of the device, which may be none.
+    fn read(&mut self, Buffer: &mut [UINT8]) -> Result<usize, EFI_STATUS> {
+        // SAFETY: `new()` requires the protocol to be valid.
+        unsafe { self.protocol.read(Buffer) }
+    }
+}
+
+impl Write for SerialConsole<'_> {
+    fn write(&mut self, Buffer: &[UINT8]) -> Result<usize, EFI_STATUS> {
+        if let Some(status) = self.send_error.take() {
+            return Err(status);
+        }
+        if self.length == SERIAL_CONSOLE_BUFFER_SIZE {
+            self.send_buffer()?;
+        }
+
+        let count = Buffer.len().min(SERIAL_CONSOLE_BUFFER_SIZE - self.length);
+        self.buffer[self.length..self.length + count].copy_from_slice(&Buffer[..count]);
+        self.length += count;
+        if Buffer[..count].contains(&b'\n') {
+            // The bytes are buffered, so they must not be written again by the caller.
+            self.send_error = self.send_buffer().err();
+        }
+
+        Ok(count)
+    }
+
+    fn flush(&mut self) -> Result<(), EFI_STATUS> {
+        // Sending the buffer again supersedes a failure to send it from `write()`.
+        self.send_error = None;
+        self.send_buffer()
+    }
+}
+
+impl fmt::Write for SerialConsole<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut lines = s.split('\n');
+        let mut result = lines
+            .next()
+            .map_or(Ok(()), |line| self.write_all(line.as_bytes()));
+        for line in lines {
+            result = result
+                .and_then(|_| self.write_all(b"\r\n"))
+                .and_then(|_| self.write_all(line.as_bytes()));
+        }
+
+        result.map_err(|status| {
+            self.error = Some(status);
+            fmt::Error
+        })
+    }
+}
+
+impl Drop for SerialConsole<'_> {
+    fn drop(&mut self) {
+        let _ = self.send_buffer();
+    }
+}
+
+type EFI_SERIAL_RESET = extern "efiapi" fn(This: *mut EFI_SERIAL_IO_PROTOCOL) -> EFI_STATUS;
+
+type EFI_SERIAL_SET_ATTRIBUTES = extern "efiapi" fn(
+    This: *mut EFI_SERIAL_IO_PROTOCOL,
+    BaudRate: UINT64,
+    ReceiveFifoDepth: UINT32,
+    Timeout: UINT32,
+    Parity: EFI_PARITY_TYPE,
+    DataBits: UINT8,
+    StopBits: EFI_STOP_BITS_TYPE,
+) -> EFI_STATUS;
+
+type EFI_SERIAL_SET_CONTROL_BITS =
+    extern "efiapi" fn(This: *mut EFI_SERIAL_IO_PROTOCOL, Control: UINT32) -> EFI_STATUS;
+
+type EFI_SERIAL_GET_CONTROL_BITS =
+    extern "efiapi" fn(This: *mut EFI_SERIAL_IO_PROTOCOL, Control: *mut UINT32) -> EFI_STATUS;
+
+type EFI_SERIAL_WRITE = extern "efiapi" fn(
+    This: *mut EFI_SERIAL_IO_PROTOCOL,
+    BufferSize: *mut UINTN,
+    Buffer: *mut VOID,
+) -> EFI_STATUS;
+
+type EFI_SERIAL_READ = extern "efiapi" fn(
+    This: *mut EFI_SERIAL_IO_PROTOCOL,
+    BufferSize: *mut UINTN,
+    Buffer: *mut VOID,
+) -> EFI_STATUS;
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::cell::{Cell, RefCell};
+    use core::ptr;
+    use std::collections::VecDeque;
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::status::EFI_UNSUPPORTED;
+
+    std::thread_local! {
+        // The number of bytes the device accepts before it stalls.
+        static BUDGET: Cell<usize> = const { Cell::new(usize::MAX) };
+        static SENT: RefCell<Vec<UINT8>> = const { RefCell::new(Vec::new()) };
+        static RECEIVED: RefCell<VecDeque<UINT8>> = const { RefCell::new(VecDeque::new()) };
+    }
+
+    extern "efiapi" fn reset(_: *mut EFI_SERIAL_IO_PROTOCOL) -> EFI_STATUS {
+        EFI_UNSUPPORTED
+    }
+
+    extern "efiapi" fn set_attributes(
+        _: *mut EFI_SERIAL_IO_PROTOCOL,
+        _: UINT64,
+        _: UINT32,
+        _: UINT32,
+        _: EFI_PARITY_TYPE,
+        _: UINT8,
+        _: EFI_STOP_BITS_TYPE,
+    ) -> EFI_STATUS {
+        EFI_UNSUPPORTED
+    }
+
+    extern "efiapi" fn set_control(_: *mut EFI_SERIAL_IO_PROTOCOL, _: UINT32) -> EFI_STATUS {
+        EFI_UNSUPPORTED
+    }
+
+    extern "efiapi" fn get_control(_: *mut EFI_SERIAL_IO_PROTOCOL, _: *mut UINT32) -> EFI_STATUS {
+        EFI_UNSUPPORTED
+    }
+
+    /// Accepts at most two bytes at a time, within the budget.
+    extern "efiapi" fn write(
+        _: *mut EFI_SERIAL_IO_PROTOCOL,
+        BufferSize: *mut UINTN,
+        Buffer: *mut VOID,
+    ) -> EFI_STATUS {
+        unsafe {
+            let size = (*BufferSize).min(2).min(BUDGET.with(Cell::get));
+            let bytes = core::slice::from_raw_parts(Buffer as *const UINT8, size);
+            SENT.with(|sent| sent.borrow_mut().extend_from_slice(bytes));
+            BUDGET.with(|budget| budget.set(budget.get() - size));
+            *BufferSize = size;
+        }
+        EFI_SUCCESS
+    }
+
+    extern "efiapi" fn read(
+        _: *mut EFI_SERIAL_IO_PROTOCOL,
+        BufferSize: *mut UINTN,
+        Buffer: *mut VOID,
+    ) -> EFI_STATUS {
+        unsafe {
+            let buffer = core::slice::from_raw_parts_mut(Buffer as *mut UINT8, *BufferSize);
+            let size = RECEIVED.with(|received| {
+                let mut received = received.borrow_mut();
+                let size = buffer.len().min(received.len());
+                buffer
+                    .iter_mut()
+                    .zip(received.drain(..size))
+                    .for_each(|(byte, received)| *byte = received);
+                size
+            });
+            *BufferSize = size;
+            if size < buffer.len() {
+                EFI_TIMEOUT
+            } else {
+                EFI_SUCCESS
+            }
+        }
+    }
+
+    fn device(Received: &[UINT8]) -> EFI_SERIAL_IO_PROTOCOL {
+        BUDGET.with(|budget| budget.set(usize::MAX));
+        SENT.with(|sent| sent.borrow_mut().clear());
+        RECEIVED.with(|received| *received.borrow_mut() = Received.iter().copied().collect());
+        EFI_SERIAL_IO_PROTOCOL {
+            Revision: EFI_SERIAL_IO_PROTOCOL_REVISION,
+            Reset: reset,
+            SetAttributes: set_attributes,
+            SetControl: set_control,
+            GetControl: get_control,
+            Write: write,
+            Read: read,
+            Mode: ptr::null_mut(),
+            DeviceTypeGuid: ptr::null(),
+        }
+    }
+
+    fn sent() -> Vec<UINT8> {
+        SENT.with(|sent| sent.borrow().clone())
+    }
+
+    #[test]
+    fn partial_writes() {
+        let mut device = device(b"");
+        let mut console = unsafe { SerialConsole::new(&mut device) };
+        console.write_all(b"hello").unwrap();
+        assert_eq!(sent(), b"");
+        fmt::Write::write_str(&mut console, "!\n").unwrap();
+        assert_eq!(sent(), b"hello!\r\n");
+    }
+
+    #[test]
+    fn stalled_device() {
+        let mut device = device(b"");
+        let mut console = unsafe { SerialConsole::new(&mut device) };
+        BUDGET.with(|budget| budget.set(3));
+        console.write_all(b"abcdef").unwrap();
+        assert_eq!(console.flush(), Err(EFI_TIMEOUT));
+        assert_eq!(sent(), b"abc");
+
+        // The bytes that were not sent are kept for the next attempt.
+        assert_eq!(console.flush(), Err(EFI_TIMEOUT));
+        BUDGET.with(|budget| budget.set(usize::MAX));
+        console.flush().unwrap();
+        assert_eq!(sent(), b"abcdef");
+    }
+
+    #[test]
+    fn failed_line_is_not_sent_twice() {
+        let mut device = device(b"");
+        let mut console = unsafe { SerialConsole::new(&mut device) };
+        BUDGET.with(|budget| budget.set(2));
+        assert_eq!(console.write(b"ab\ncd"), Ok(5));
+        assert_eq!(sent(), b"ab");
+
+        // The failure is reported by the next write, which accepts nothing.
+        assert_eq!(console.write(b"ef"), Err(EFI_TIMEOUT));
+        BUDGET.with(|budget| budget.set(usize::MAX));
+        assert_eq!(console.write(b"ef"), Ok(2));
+        console.flush().unwrap();
+        assert_eq!(sent(), b"ab\ncdef");
+    }
+
+    #[test]
+    fn read_lines() {
+        let mut device = device(b"one\r\ntwo\n\r\nfoo\x08\x7Fur\r\x08five\r\r\n");
+        let mut console = unsafe { SerialConsole::new(&mut device) };
+        let mut buffer = [0; 3];
+        for line in [&b"one"[..], b"two", b"", b"fur", b"fiv", b""] {
+            let length = console.read_line(&mut buffer).unwrap();
+            assert_eq!(&buffer[..length], line);
+        }
+        assert_eq!(
+            sent(),
+            b"one\r\ntwo\r\n\r\nfoo\x08 \x08\x08 \x08ur\r\nfiv\r\n\r\n"
+        );
+    }
+}
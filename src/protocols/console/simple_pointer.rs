@@ -0,0 +1,195 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Simple Pointer Protocol
+//!
+//! This module defines the Simple Pointer Protocol, also known as the
+//! [`EFI_SIMPLE_POINTER_PROTOCOL`], which reports the relative movement and button state of a
+//! pointer device such as a mouse or a trackball. See [Section 12.5 of the UEFI Specification]
+//! for more details.
+//!
+//! [`EFI_SIMPLE_POINTER_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_POINTER_PROTOCOL
+//!
+//! [Section 12.5 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/12_Protocols_Console_Support.html#simple-pointer-protocol
+
+use crate::protocols::Protocol;
+use crate::status::{EFI_NOT_READY, EFI_SUCCESS};
+use crate::types::{BOOLEAN, EFI_EVENT, EFI_GUID, EFI_STATUS, INT32, UINT64};
+
+/// GUID for the [`EFI_SIMPLE_POINTER_PROTOCOL`].
+///
+/// [`EFI_SIMPLE_POINTER_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_POINTER_PROTOCOL
+pub const EFI_SIMPLE_POINTER_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0x31878C87,
+    0x0B75,
+    0x11D5,
+    [0x9A, 0x4F, 0x00, 0x90, 0x27, 0x3F, 0xC1, 0x4D],
+);
+
+/// Provides services that allow information about a pointer device to be retrieved.
+///
+/// The [`EFI_SIMPLE_POINTER_PROTOCOL`] provides a set of services for a pointer device that can
+/// be used as an input device from an application written to this specification. The services
+/// include the ability to reset the pointer device, retrieve the state of the pointer device,
+/// and retrieve the capabilities of the pointer device.
+///
+/// [`EFI_SIMPLE_POINTER_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_POINTER_PROTOCOL
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_SIMPLE_POINTER_PROTOCOL {
+    Reset: EFI_SIMPLE_POINTER_RESET,
+    GetState: EFI_SIMPLE_POINTER_GET_STATE,
+    /// Event to use with [`EFI_BOOT_SERVICES.WaitForEvent()`] to wait for input from the pointer
+    /// device.
+    ///
+    /// [`EFI_BOOT_SERVICES.WaitForEvent()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-waitforevent
+    pub WaitForInput: EFI_EVENT,
+    /// Pointer to [`EFI_SIMPLE_POINTER_MODE`] data.
+    ///
+    /// [`EFI_SIMPLE_POINTER_MODE`]: crate::protocols::console::simple_pointer::EFI_SIMPLE_POINTER_MODE
+    pub Mode: *mut EFI_SIMPLE_POINTER_MODE,
+}
+
+unsafe impl Protocol for EFI_SIMPLE_POINTER_PROTOCOL {
+    const GUID: EFI_GUID = EFI_SIMPLE_POINTER_PROTOCOL_GUID;
+}
+
+impl EFI_SIMPLE_POINTER_PROTOCOL {
+    /// Resets the pointer device hardware.
+    ///
+    /// The [`Reset()`] function resets the pointer device hardware. As part of initialization
+    /// process, the firmware/device will make a quick but reasonable attempt to verify that the
+    /// device is functioning. If the `ExtendedVerification` flag is `TRUE` the firmware may take
+    /// an extended amount of time to verify the device is operating on reset. Otherwise the reset
+    /// operation is to occur as quickly as possible.
+    ///
+    /// # Parameters
+    ///
+    /// ## `ExtendedVerification`
+    ///
+    /// Indicates that the driver may perform a more exhaustive verification operation of the
+    /// device during reset.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the device was reset.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device is not functioning correctly and could not be reset.
+    ///
+    /// [`Reset()`]: ./struct.EFI_SIMPLE_POINTER_PROTOCOL.html#method.Reset
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    pub unsafe fn Reset(&mut self, ExtendedVerification: BOOLEAN) -> EFI_STATUS {
+        (self.Reset)(self, ExtendedVerification)
+    }
+
+    /// Retrieves the current state of a pointer device.
+    ///
+    /// The [`GetState()`] function retrieves the current state of a pointer device. This includes
+    /// information on the buttons associated with the pointer device and the distance that each
+    /// of the axes associated with the pointer device has been moved. If the state of the pointer
+    /// device has not changed since the last call to [`GetState()`], then [`EFI_NOT_READY`] is
+    /// returned. If the state of the pointer device has changed since the last call, then the
+    /// state information is placed in `State`, and [`EFI_SUCCESS`] is returned.
+    ///
+    /// # Parameters
+    ///
+    /// ## `State`
+    ///
+    /// A pointer to the state information on the pointer device.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the state of the pointer device was returned in `State`.
+    ///
+    /// [`EFI_NOT_READY`] - the state of the pointer device has not changed since the last call
+    /// to [`GetState()`].
+    ///
+    /// [`EFI_DEVICE_ERROR`] - a device error occurred while attempting to retrieve the pointer
+    /// device's current state.
+    ///
+    /// [`GetState()`]: ./struct.EFI_SIMPLE_POINTER_PROTOCOL.html#method.GetState
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_NOT_READY`]: crate::status::EFI_NOT_READY
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    pub unsafe fn GetState(&mut self, State: *mut EFI_SIMPLE_POINTER_STATE) -> EFI_STATUS {
+        (self.GetState)(self, State)
+    }
+
+    /// Returns the capabilities of the pointer device.
+    pub unsafe fn mode(&self) -> &EFI_SIMPLE_POINTER_MODE {
+        &*self.Mode
+    }
+
+    /// Returns the state of the pointer device, or `None` if it has not changed since the last
+    /// call.
+    pub unsafe fn get_state(&mut self) -> Result<Option<EFI_SIMPLE_POINTER_STATE>, EFI_STATUS> {
+        let mut state = EFI_SIMPLE_POINTER_STATE::default();
+        match self.GetState(&mut state) {
+            EFI_SUCCESS => Ok(Some(state)),
+            EFI_NOT_READY => Ok(None),
+            status => Err(status),
+        }
+    }
+}
+
+/// The capabilities of a pointer device.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_SIMPLE_POINTER_MODE {
+    /// The resolution of the pointer device on the x-axis in counts/mm. If 0, then the pointer
+    /// device does not support an x-axis.
+    pub ResolutionX: UINT64,
+    /// The resolution of the pointer device on the y-axis in counts/mm. If 0, then the pointer
+    /// device does not support a y-axis.
+    pub ResolutionY: UINT64,
+    /// The resolution of the pointer device on the z-axis in counts/mm. If 0, then the pointer
+    /// device does not support a z-axis.
+    pub ResolutionZ: UINT64,
+    /// `TRUE` if a left button is present on the pointer device. Otherwise `FALSE`.
+    pub LeftButton: BOOLEAN,
+    /// `TRUE` if a right button is present on the pointer device. Otherwise `FALSE`.
+    pub RightButton: BOOLEAN,
+}
+
+/// The state of a pointer device.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub struct EFI_SIMPLE_POINTER_STATE {
+    /// The signed distance in counts that the pointer device has been moved along the x-axis.
+    pub RelativeMovementX: INT32,
+    /// The signed distance in counts that the pointer device has been moved along the y-axis.
+    pub RelativeMovementY: INT32,
+    /// The signed distance in counts that the pointer device has been moved along the z-axis.
+    pub RelativeMovementZ: INT32,
+    /// If `TRUE`, then the left button of the pointer device is being pressed. If `FALSE`, then
+    /// the left button of the pointer device is not being pressed.
+    pub LeftButton: BOOLEAN,
+    /// If `TRUE`, then the right button of the pointer device is being pressed. If `FALSE`, then
+    /// the right button of the pointer device is not being pressed.
+    pub RightButton: BOOLEAN,
+}
+
+type EFI_SIMPLE_POINTER_RESET = extern "efiapi" fn(
+    This: *mut EFI_SIMPLE_POINTER_PROTOCOL,
+    ExtendedVerification: BOOLEAN,
+) -> EFI_STATUS;
+
+type EFI_SIMPLE_POINTER_GET_STATE = extern "efiapi" fn(
+    This: *mut EFI_SIMPLE_POINTER_PROTOCOL,
+    State: *mut EFI_SIMPLE_POINTER_STATE,
+) -> EFI_STATUS;
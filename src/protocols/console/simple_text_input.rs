@@ -21,6 +21,8 @@
 //!
 //! [`EFI_SIMPLE_TEXT_INPUT_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_INPUT_PROTOCOL
 
+use crate::protocols::Protocol;
+use crate::status::{EFI_NOT_READY, EFI_SUCCESS};
 use crate::types::{BOOLEAN, CHAR16, EFI_EVENT, EFI_GUID, EFI_STATUS, UINT16};
 
 /// GUID for the [`EFI_SIMPLE_TEXT_INPUT_PROTOCOL`].
@@ -50,6 +52,10 @@ pub struct EFI_SIMPLE_TEXT_INPUT_PROTOCOL {
     pub WaitForKey: EFI_EVENT,
 }
 
+unsafe impl Protocol for EFI_SIMPLE_TEXT_INPUT_PROTOCOL {
+    const GUID: EFI_GUID = EFI_SIMPLE_TEXT_INPUT_PROTOCOL_GUID;
+}
+
 impl EFI_SIMPLE_TEXT_INPUT_PROTOCOL {
     /// Resets the input device hardware.
     ///
@@ -121,6 +127,19 @@ impl EFI_SIMPLE_TEXT_INPUT_PROTOCOL {
     pub unsafe fn ReadKeyStroke(&mut self, Key: *mut EFI_INPUT_KEY) -> EFI_STATUS {
         (self.ReadKeyStroke)(self, Key)
     }
+
+    /// Reads the next keystroke, or returns `None` if there is none.
+    pub unsafe fn read_key_stroke(&mut self) -> Result<Option<EFI_INPUT_KEY>, EFI_STATUS> {
+        let mut key = EFI_INPUT_KEY {
+            ScanCode: 0,
+            UnicodeChar: 0,
+        };
+        match self.ReadKeyStroke(&mut key) {
+            EFI_SUCCESS => Ok(Some(key)),
+            EFI_NOT_READY => Ok(None),
+            status => Err(status),
+        }
+    }
 }
 
 /// A keystroke from the input device.
@@ -133,6 +152,60 @@ pub struct EFI_INPUT_KEY {
     pub UnicodeChar: CHAR16,
 }
 
+pub const SCAN_NULL: UINT16 = 0x0000;
+pub const SCAN_UP: UINT16 = 0x0001;
+pub const SCAN_DOWN: UINT16 = 0x0002;
+pub const SCAN_RIGHT: UINT16 = 0x0003;
+pub const SCAN_LEFT: UINT16 = 0x0004;
+pub const SCAN_HOME: UINT16 = 0x0005;
+pub const SCAN_END: UINT16 = 0x0006;
+pub const SCAN_INSERT: UINT16 = 0x0007;
+pub const SCAN_DELETE: UINT16 = 0x0008;
+pub const SCAN_PAGE_UP: UINT16 = 0x0009;
+pub const SCAN_PAGE_DOWN: UINT16 = 0x000A;
+pub const SCAN_F1: UINT16 = 0x000B;
+pub const SCAN_F2: UINT16 = 0x000C;
+pub const SCAN_F3: UINT16 = 0x000D;
+pub const SCAN_F4: UINT16 = 0x000E;
+pub const SCAN_F5: UINT16 = 0x000F;
+pub const SCAN_F6: UINT16 = 0x0010;
+pub const SCAN_F7: UINT16 = 0x0011;
+pub const SCAN_F8: UINT16 = 0x0012;
+pub const SCAN_F9: UINT16 = 0x0013;
+pub const SCAN_F10: UINT16 = 0x0014;
+pub const SCAN_F11: UINT16 = 0x0015;
+pub const SCAN_F12: UINT16 = 0x0016;
+pub const SCAN_ESC: UINT16 = 0x0017;
+pub const SCAN_F13: UINT16 = 0x0068;
+pub const SCAN_F14: UINT16 = 0x0069;
+pub const SCAN_F15: UINT16 = 0x006A;
+pub const SCAN_F16: UINT16 = 0x006B;
+pub const SCAN_F17: UINT16 = 0x006C;
+pub const SCAN_F18: UINT16 = 0x006D;
+pub const SCAN_F19: UINT16 = 0x006E;
+pub const SCAN_F20: UINT16 = 0x006F;
+pub const SCAN_F21: UINT16 = 0x0070;
+pub const SCAN_F22: UINT16 = 0x0071;
+pub const SCAN_F23: UINT16 = 0x0072;
+pub const SCAN_F24: UINT16 = 0x0073;
+pub const SCAN_MUTE: UINT16 = 0x007F;
+pub const SCAN_VOLUME_UP: UINT16 = 0x0080;
+pub const SCAN_VOLUME_DOWN: UINT16 = 0x0081;
+
+pub const SCAN_BRIGHTNESS_UP: UINT16 = 0x0100;
+pub const SCAN_BRIGHTNESS_DOWN: UINT16 = 0x0101;
+pub const SCAN_SUSPEND: UINT16 = 0x0102;
+pub const SCAN_HIBERNATE: UINT16 = 0x0103;
+pub const SCAN_TOGGLE_DISPLAY: UINT16 = 0x0104;
+pub const SCAN_RECOVERY: UINT16 = 0x0105;
+pub const SCAN_EJECT: UINT16 = 0x0106;
+
+pub const CHAR_NULL: CHAR16 = 0x0000;
+pub const CHAR_BACKSPACE: CHAR16 = 0x0008;
+pub const CHAR_TAB: CHAR16 = 0x0009;
+pub const CHAR_LINEFEED: CHAR16 = 0x000A;
+pub const CHAR_CARRIAGE_RETURN: CHAR16 = 0x000D;
+
 type EFI_INPUT_RESET = extern "efiapi" fn(
     This: *mut EFI_SIMPLE_TEXT_INPUT_PROTOCOL,
     ExtendedVerification: BOOLEAN,
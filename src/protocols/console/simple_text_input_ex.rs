@@ -21,7 +21,9 @@
 //! [`EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL`].
 
 use crate::protocols::console::simple_text_input::EFI_INPUT_KEY;
-use crate::types::{BOOLEAN, EFI_GUID, EFI_STATUS, UINT32, UINT8, VOID};
+use crate::protocols::Protocol;
+use crate::status::{EFI_NOT_READY, EFI_SUCCESS};
+use crate::types::{BOOLEAN, EFI_EVENT, EFI_GUID, EFI_STATUS, UINT32, UINT8, VOID};
 
 /// GUID for the [`EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL`].
 ///
@@ -47,11 +49,19 @@ pub const EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
 pub struct EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL {
     Reset: EFI_INPUT_RESET_EX,
     ReadKeyStrokeEx: EFI_INPUT_READ_KEY_EX,
+    /// Event to use with [`EFI_BOOT_SERVICES.WaitForEvent()`] to wait for a key to be available.
+    ///
+    /// [`EFI_BOOT_SERVICES.WaitForEvent()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-waitforevent
+    pub WaitForKeyEx: EFI_EVENT,
     SetState: EFI_SET_STATE,
     RegisterKeyNotify: EFI_REGISTER_KEYSTROKE_NOTIFY,
     UnregisterKeyNotify: EFI_UNREGISTER_KEYSTROKE_NOTIFY,
 }
 
+unsafe impl Protocol for EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL {
+    const GUID: EFI_GUID = EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL_GUID;
+}
+
 impl EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL {
     /// Resets the input device hardware.
     ///
@@ -158,6 +168,25 @@ impl EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL {
         (self.ReadKeyStrokeEx)(self, KeyData)
     }
 
+    /// Reads the next keystroke, or returns `None` if there is none.
+    pub unsafe fn read_key_stroke_ex(&mut self) -> Result<Option<EFI_KEY_DATA>, EFI_STATUS> {
+        let mut key = EFI_KEY_DATA {
+            Key: EFI_INPUT_KEY {
+                ScanCode: 0,
+                UnicodeChar: 0,
+            },
+            KeyState: EFI_KEY_STATE {
+                KeyShiftState: 0,
+                KeyToggleState: 0,
+            },
+        };
+        match self.ReadKeyStrokeEx(&mut key) {
+            EFI_SUCCESS => Ok(Some(key)),
+            EFI_NOT_READY => Ok(None),
+            status => Err(status),
+        }
+    }
+
     /// Set certain state for the input device.
     ///
     /// The [`SetState()`] function allows the input device hardware to have state settings adjusted.
@@ -229,6 +258,7 @@ impl EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL {
     /// [`KeyData.KeyState.KeyShiftState`]: ./struct.EFI_KEY_STATE.html#structfield.KeyShiftState
     /// [`KeyData`]: ./struct.EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL.html#keydata-1
     /// [`KeyNotificationFunction`]: ./struct.EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL.html#keynotificationfunction
+    /// [`TPL_CALLBACK`]: crate::tables::boot_services::TPL_CALLBACK
     /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
     /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
     pub unsafe fn RegisterKeyNotify(
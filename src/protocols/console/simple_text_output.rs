@@ -21,18 +21,27 @@
 //!
 //! [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL
 
+use crate::protocols::Protocol;
 use crate::types::{BOOLEAN, CHAR16, EFI_GUID, EFI_STATUS, INT32, UINTN};
 
 /// GUID for the [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`].
 ///
 /// [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL
-pub const EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+pub const EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
     0x387477C2,
     0x69D7,
     0x11D2,
     [0x8E, 0x39, 0x00, 0xA0, 0xC9, 0x69, 0x72, 0x3B],
 );
 
+/// Former, misnamed alias of [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL_GUID`]. This is not the GUID of the
+/// [`EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL`].
+///
+/// [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL_GUID`]: crate::protocols::console::simple_text_output::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL_GUID
+/// [`EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL
+#[deprecated(note = "use `EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL_GUID`")]
+pub const EFI_SIMPLE_TEXT_INPUT_EX_PROTOCOL_GUID: EFI_GUID = EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL_GUID;
+
 #[derive(Clone, Copy)]
 #[repr(C)]
 pub struct EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL {
@@ -51,6 +60,10 @@ pub struct EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL {
     pub Mode: *mut SIMPLE_TEXT_OUTPUT_MODE,
 }
 
+unsafe impl Protocol for EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL {
+    const GUID: EFI_GUID = EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL_GUID;
+}
+
 impl EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL {
     /// Resets the text output device hardware.
     ///
@@ -445,6 +458,15 @@ pub const EFI_BACKGROUND_MAGENTA: UINTN = 0x50;
 pub const EFI_BACKGROUND_BROWN: UINTN = 0x60;
 pub const EFI_BACKGROUND_LIGHTGRAY: UINTN = 0x70;
 
+/// Combines a foreground color and a background color, one of the colors up to
+/// [`EFI_LIGHTGRAY`], into an attribute for [`SetAttribute()`].
+///
+/// [`EFI_LIGHTGRAY`]: crate::protocols::console::simple_text_output::EFI_LIGHTGRAY
+/// [`SetAttribute()`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL::SetAttribute
+pub const fn EFI_TEXT_ATTR(Foreground: UINTN, Background: UINTN) -> UINTN {
+    Foreground | (Background << 4)
+}
+
 type EFI_TEXT_RESET = extern "efiapi" fn(
     This: *mut EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL,
     ExtendedVerification: BOOLEAN,
@@ -0,0 +1,1066 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # Text-mode User Interface
+//!
+//! This module provides widgets for text consoles: bordered [`Window`]s, scrollable [`Menu`]s,
+//! [`ProgressBar`]s and [`MessageBox`]es. They are drawn through a [`Tui`] on any
+//! [`ConsoleSink`], so they run on the [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`] through a
+//! [`TextOutputSink`], on a [`ConsoleMux`], or on the host against a sink that records the calls
+//! it receives.
+//!
+//! Borders, bars and markers use the `BOXDRAW_*`, `BLOCKELEMENT_*`, `GEOMETRICSHAPE_*` and
+//! `ARROW_*` characters of the [`simple_text_output`] module. Not every console can display
+//! them, so [`Tui::new()`] checks them with [`TestString()`] and falls back to ASCII for those
+//! that cannot be displayed.
+//!
+//! [`Window`]: crate::protocols::console::tui::Window
+//! [`Menu`]: crate::protocols::console::tui::Menu
+//! [`ProgressBar`]: crate::protocols::console::tui::ProgressBar
+//! [`MessageBox`]: crate::protocols::console::tui::MessageBox
+//! [`Tui`]: crate::protocols::console::tui::Tui
+//! [`ConsoleSink`]: crate::protocols::console::multiplex::ConsoleSink
+//! [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL
+//! [`TextOutputSink`]: crate::protocols::console::multiplex::TextOutputSink
+//! [`ConsoleMux`]: crate::protocols::console::multiplex::ConsoleMux
+//! [`simple_text_output`]: crate::protocols::console::simple_text_output
+//! [`Tui::new()`]: crate::protocols::console::tui::Tui::new
+//! [`TestString()`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL::TestString
+
+use crate::graphics::Rect;
+use crate::protocols::console::multiplex::{ConsoleSink, DEFAULT_CONSOLE_SIZE};
+use crate::protocols::console::simple_text_input::{
+    CHAR_CARRIAGE_RETURN, EFI_INPUT_KEY, SCAN_DOWN, SCAN_END, SCAN_HOME, SCAN_PAGE_DOWN,
+    SCAN_PAGE_UP, SCAN_UP,
+};
+use crate::protocols::console::simple_text_output::{
+    ARROW_DOWN, ARROW_UP, BLOCKELEMENT_FULL_BLOCK, BLOCKELEMENT_LIGHT_SHADE,
+    BOXDRAW_DOUBLE_DOWN_LEFT, BOXDRAW_DOUBLE_DOWN_RIGHT, BOXDRAW_DOUBLE_HORIZONTAL,
+    BOXDRAW_DOUBLE_UP_LEFT, BOXDRAW_DOUBLE_UP_RIGHT, BOXDRAW_DOUBLE_VERTICAL, BOXDRAW_DOWN_LEFT,
+    BOXDRAW_DOWN_RIGHT, BOXDRAW_HORIZONTAL, BOXDRAW_UP_LEFT, BOXDRAW_UP_RIGHT, BOXDRAW_VERTICAL,
+    EFI_BLACK, EFI_BLUE, EFI_LIGHTGRAY, EFI_TEXT_ATTR, EFI_WHITE, GEOMETRICSHAPE_RIGHT_TRIANGLE,
+};
+use crate::types::{CHAR16, EFI_STATUS, UINTN};
+
+/// The number of characters written at once when repeating a character.
+const REPEAT_CHUNK: usize = 32;
+
+/// Converts a glyph constant of the [`simple_text_output`] module to a `char`.
+///
+/// [`simple_text_output`]: crate::protocols::console::simple_text_output
+const fn glyph(Char: CHAR16) -> char {
+    match char::from_u32(Char as u32) {
+        Some(char) => char,
+        None => char::REPLACEMENT_CHARACTER,
+    }
+}
+
+/// The characters of a box border.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BoxStyle {
+    /// The top and bottom edges.
+    pub Horizontal: char,
+    /// The left and right edges.
+    pub Vertical: char,
+    /// The top left corner.
+    pub TopLeft: char,
+    /// The top right corner.
+    pub TopRight: char,
+    /// The bottom left corner.
+    pub BottomLeft: char,
+    /// The bottom right corner.
+    pub BottomRight: char,
+}
+
+impl BoxStyle {
+    /// A border of single lines.
+    pub const SINGLE: Self = Self {
+        Horizontal: glyph(BOXDRAW_HORIZONTAL),
+        Vertical: glyph(BOXDRAW_VERTICAL),
+        TopLeft: glyph(BOXDRAW_DOWN_RIGHT),
+        TopRight: glyph(BOXDRAW_DOWN_LEFT),
+        BottomLeft: glyph(BOXDRAW_UP_RIGHT),
+        BottomRight: glyph(BOXDRAW_UP_LEFT),
+    };
+
+    /// A border of double lines.
+    pub const DOUBLE: Self = Self {
+        Horizontal: glyph(BOXDRAW_DOUBLE_HORIZONTAL),
+        Vertical: glyph(BOXDRAW_DOUBLE_VERTICAL),
+        TopLeft: glyph(BOXDRAW_DOUBLE_DOWN_RIGHT),
+        TopRight: glyph(BOXDRAW_DOUBLE_DOWN_LEFT),
+        BottomLeft: glyph(BOXDRAW_DOUBLE_UP_RIGHT),
+        BottomRight: glyph(BOXDRAW_DOUBLE_UP_LEFT),
+    };
+
+    /// A border of ASCII characters.
+    pub const ASCII: Self = Self {
+        Horizontal: '-',
+        Vertical: '|',
+        TopLeft: '+',
+        TopRight: '+',
+        BottomLeft: '+',
+        BottomRight: '+',
+    };
+
+    const fn chars(&self) -> [char; 6] {
+        [
+            self.Horizontal,
+            self.Vertical,
+            self.TopLeft,
+            self.TopRight,
+            self.BottomLeft,
+            self.BottomRight,
+        ]
+    }
+}
+
+/// The characters used to draw the widgets.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Glyphs {
+    /// The border of a [`Window`].
+    ///
+    /// [`Window`]: crate::protocols::console::tui::Window
+    pub Single: BoxStyle,
+    /// The border of a [`Window`] with a double border.
+    ///
+    /// [`Window`]: crate::protocols::console::tui::Window
+    pub Double: BoxStyle,
+    /// The completed part of a [`ProgressBar`].
+    ///
+    /// [`ProgressBar`]: crate::protocols::console::tui::ProgressBar
+    pub FullBlock: char,
+    /// The remaining part of a [`ProgressBar`].
+    ///
+    /// [`ProgressBar`]: crate::protocols::console::tui::ProgressBar
+    pub LightShade: char,
+    /// The mark of a [`Menu`] that can scroll up.
+    ///
+    /// [`Menu`]: crate::protocols::console::tui::Menu
+    pub ArrowUp: char,
+    /// The mark of a [`Menu`] that can scroll down.
+    ///
+    /// [`Menu`]: crate::protocols::console::tui::Menu
+    pub ArrowDown: char,
+    /// The mark of the selected item of a [`Menu`].
+    ///
+    /// [`Menu`]: crate::protocols::console::tui::Menu
+    pub Pointer: char,
+}
+
+impl Glyphs {
+    /// The box drawing, block element, geometric shape and arrow characters of the
+    /// [`simple_text_output`] module.
+    ///
+    /// [`simple_text_output`]: crate::protocols::console::simple_text_output
+    pub const UNICODE: Self = Self {
+        Single: BoxStyle::SINGLE,
+        Double: BoxStyle::DOUBLE,
+        FullBlock: glyph(BLOCKELEMENT_FULL_BLOCK),
+        LightShade: glyph(BLOCKELEMENT_LIGHT_SHADE),
+        ArrowUp: glyph(ARROW_UP),
+        ArrowDown: glyph(ARROW_DOWN),
+        Pointer: glyph(GEOMETRICSHAPE_RIGHT_TRIANGLE),
+    };
+
+    /// ASCII replacements, which any console can display.
+    pub const ASCII: Self = Self {
+        Single: BoxStyle::ASCII,
+        Double: BoxStyle::ASCII,
+        FullBlock: '#',
+        LightShade: '.',
+        ArrowUp: '^',
+        ArrowDown: 'v',
+        Pointer: '>',
+    };
+
+    /// Returns the [`UNICODE`] glyphs that `Sink` can display, according to
+    /// [`test_string()`], and the [`ASCII`] replacements of the others.
+    ///
+    /// [`UNICODE`]: crate::protocols::console::tui::Glyphs::UNICODE
+    /// [`test_string()`]: crate::protocols::console::multiplex::ConsoleSink::test_string
+    /// [`ASCII`]: crate::protocols::console::tui::Glyphs::ASCII
+    pub fn detect<S: ConsoleSink + ?Sized>(Sink: &mut S) -> Self {
+        let mut supports = |chars: &[char]| {
+            let mut buffer = [0; 4 * 6];
+            let mut length = 0;
+            for char in chars {
+                length += char.encode_utf8(&mut buffer[length..]).len();
+            }
+
+            core::str::from_utf8(&buffer[..length])
+                .map_or(false, |string| Sink.test_string(string).is_ok())
+        };
+
+        let single = if supports(&BoxStyle::SINGLE.chars()) {
+            BoxStyle::SINGLE
+        } else {
+            BoxStyle::ASCII
+        };
+        let double = if supports(&BoxStyle::DOUBLE.chars()) {
+            BoxStyle::DOUBLE
+        } else {
+            single
+        };
+        let (unicode, ascii) = (Self::UNICODE, Self::ASCII);
+        let blocks = supports(&[unicode.FullBlock, unicode.LightShade]);
+        let arrows = supports(&[unicode.ArrowUp, unicode.ArrowDown]);
+        let pointer = supports(&[unicode.Pointer]);
+
+        Self {
+            Single: single,
+            Double: double,
+            FullBlock: if blocks {
+                unicode.FullBlock
+            } else {
+                ascii.FullBlock
+            },
+            LightShade: if blocks {
+                unicode.LightShade
+            } else {
+                ascii.LightShade
+            },
+            ArrowUp: if arrows {
+                unicode.ArrowUp
+            } else {
+                ascii.ArrowUp
+            },
+            ArrowDown: if arrows {
+                unicode.ArrowDown
+            } else {
+                ascii.ArrowDown
+            },
+            Pointer: if pointer {
+                unicode.Pointer
+            } else {
+                ascii.Pointer
+            },
+        }
+    }
+}
+
+/// Draws text and widgets at given positions of a [`ConsoleSink`].
+///
+/// Positions are in character cells, with (0, 0) at the top left corner. Text is clipped to
+/// the screen, and the bottom right cell is never written, as writing it scrolls UEFI consoles.
+///
+/// [`ConsoleSink`]: crate::protocols::console::multiplex::ConsoleSink
+pub struct Tui<S> {
+    sink: S,
+    glyphs: Glyphs,
+    columns: UINTN,
+    rows: UINTN,
+}
+
+impl<S: ConsoleSink> Tui<S> {
+    /// Creates a user interface on `Sink`, with the glyphs it can display as found by
+    /// [`Glyphs::detect()`].
+    ///
+    /// [`Glyphs::detect()`]: crate::protocols::console::tui::Glyphs::detect
+    pub fn new(mut Sink: S) -> Self {
+        let glyphs = Glyphs::detect(&mut Sink);
+        Self::with_glyphs(Sink, glyphs)
+    }
+
+    /// Creates a user interface on `Sink` drawn with `Glyphs`.
+    pub fn with_glyphs(mut Sink: S, Glyphs: Glyphs) -> Self {
+        let (columns, rows) = Sink.size().unwrap_or(DEFAULT_CONSOLE_SIZE);
+        Self {
+            sink: Sink,
+            glyphs: Glyphs,
+            columns,
+            rows,
+        }
+    }
+
+    /// Returns the glyphs the widgets are drawn with.
+    pub fn glyphs(&self) -> &Glyphs {
+        &self.glyphs
+    }
+
+    /// Returns the number of columns and rows.
+    pub fn size(&self) -> (UINTN, UINTN) {
+        (self.columns, self.rows)
+    }
+
+    /// Returns the area of the whole screen.
+    pub fn bounds(&self) -> Rect {
+        Rect::new(0, 0, self.columns, self.rows)
+    }
+
+    /// Returns the sink.
+    pub fn get_ref(&self) -> &S {
+        &self.sink
+    }
+
+    /// Returns the sink mutably.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Returns the sink.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+
+    /// Clears the screen with the background of `Attribute`.
+    pub fn clear(&mut self, Attribute: UINTN) -> Result<(), EFI_STATUS> {
+        self.sink.set_attribute(Attribute)?;
+        self.sink.clear_screen()
+    }
+
+    /// Writes `Text` in `Attribute` at (`Column`, `Row`), clipped to the screen. `Text` must not
+    /// contain control characters.
+    pub fn put_str(
+        &mut self,
+        Column: UINTN,
+        Row: UINTN,
+        Attribute: UINTN,
+        Text: &str,
+    ) -> Result<(), EFI_STATUS> {
+        if Column >= self.columns || Row >= self.rows {
+            return Ok(());
+        }
+
+        let mut width = self.columns - Column;
+        if Row == self.rows - 1 {
+            width -= 1;
+        }
+        let end = Text
+            .char_indices()
+            .nth(width)
+            .map_or(Text.len(), |(index, _)| index);
+        if end == 0 {
+            return Ok(());
+        }
+
+        self.sink.set_attribute(Attribute)?;
+        self.sink.set_cursor_position(Column, Row)?;
+        self.sink.output_string(&Text[..end])
+    }
+
+    /// Writes `Count` times `Char` in `Attribute` from (`Column`, `Row`) to the right, clipped to
+    /// the screen.
+    pub fn put_repeated(
+        &mut self,
+        Column: UINTN,
+        Row: UINTN,
+        Attribute: UINTN,
+        Char: char,
+        Count: usize,
+    ) -> Result<(), EFI_STATUS> {
+        let mut buffer = [0; 4 * REPEAT_CHUNK];
+        let size = Char.len_utf8();
+        for chunk in buffer.chunks_exact_mut(size) {
+            Char.encode_utf8(chunk);
+        }
+
+        let count = Count.min(self.columns.saturating_sub(Column));
+        let mut done = 0;
+        while done < count {
+            let chunk = (count - done).min(REPEAT_CHUNK);
+            // The buffer only holds whole encodings of `Char`.
+            let text = core::str::from_utf8(&buffer[..chunk * size]).unwrap_or_default();
+            self.put_str(Column + done, Row, Attribute, text)?;
+            done += chunk;
+        }
+
+        Ok(())
+    }
+
+    /// Fills `Area` with `Char` in `Attribute`.
+    pub fn fill(&mut self, Area: Rect, Attribute: UINTN, Char: char) -> Result<(), EFI_STATUS> {
+        let area = Area.intersect(self.bounds());
+        for row in area.Y..area.bottom() {
+            self.put_repeated(area.X, row, Attribute, Char, area.Width)?;
+        }
+
+        Ok(())
+    }
+
+    /// Draws the border of `Area` with `Style` in `Attribute`.
+    pub fn draw_box(
+        &mut self,
+        Area: Rect,
+        Attribute: UINTN,
+        Style: &BoxStyle,
+    ) -> Result<(), EFI_STATUS> {
+        if Area.Width < 2 || Area.Height < 2 {
+            return Ok(());
+        }
+
+        let (right, bottom) = (Area.right() - 1, Area.bottom() - 1);
+        let inner = Area.Width - 2;
+        self.put_repeated(Area.X, Area.Y, Attribute, Style.TopLeft, 1)?;
+        self.put_repeated(Area.X + 1, Area.Y, Attribute, Style.Horizontal, inner)?;
+        self.put_repeated(right, Area.Y, Attribute, Style.TopRight, 1)?;
+        for row in Area.Y + 1..bottom {
+            self.put_repeated(Area.X, row, Attribute, Style.Vertical, 1)?;
+            self.put_repeated(right, row, Attribute, Style.Vertical, 1)?;
+        }
+        self.put_repeated(Area.X, bottom, Attribute, Style.BottomLeft, 1)?;
+        self.put_repeated(Area.X + 1, bottom, Attribute, Style.Horizontal, inner)?;
+        self.put_repeated(right, bottom, Attribute, Style.BottomRight, 1)
+    }
+}
+
+/// A bordered window with an optional title.
+#[derive(Clone, Copy, Debug)]
+pub struct Window<'a> {
+    /// The area of the window, including its border.
+    pub Area: Rect,
+    /// The title, drawn centered in the top border.
+    pub Title: &'a str,
+    /// The attribute of the border, the title and the interior.
+    pub Attribute: UINTN,
+    /// Whether the border is drawn with double lines.
+    pub Double: bool,
+}
+
+impl<'a> Window<'a> {
+    /// Creates a window covering `Area` titled `Title`, with a single border in white on blue.
+    pub fn new(Area: Rect, Title: &'a str) -> Self {
+        Self {
+            Area,
+            Title,
+            Attribute: EFI_TEXT_ATTR(EFI_WHITE, EFI_BLUE),
+            Double: false,
+        }
+    }
+
+    /// Returns the area inside of the border.
+    pub fn interior(&self) -> Rect {
+        Rect::new(
+            self.Area.X + 1,
+            self.Area.Y + 1,
+            self.Area.Width.saturating_sub(2),
+            self.Area.Height.saturating_sub(2),
+        )
+    }
+
+    /// Draws the window, clearing its interior.
+    pub fn draw<S: ConsoleSink>(&self, Tui: &mut Tui<S>) -> Result<(), EFI_STATUS> {
+        let style = if self.Double {
+            Tui.glyphs.Double
+        } else {
+            Tui.glyphs.Single
+        };
+        Tui.fill(self.interior(), self.Attribute, ' ')?;
+        Tui.draw_box(self.Area, self.Attribute, &style)?;
+
+        let length = self.Title.chars().count();
+        if length > 0 && self.Area.Width >= 6 {
+            let length = length.min(self.Area.Width - 6);
+            let column = self.Area.X + (self.Area.Width - length - 2) / 2;
+            Tui.put_repeated(column, self.Area.Y, self.Attribute, ' ', length + 2)?;
+            Tui.put_str(
+                column + 1,
+                self.Area.Y,
+                self.Attribute,
+                truncate(self.Title, length),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A list of items of which one is selected, scrolling to keep it visible.
+///
+/// The selected item is marked and highlighted. The last column shows whether there are items
+/// above or below the visible ones.
+pub struct Menu<'a> {
+    area: Rect,
+    items: &'a [&'a str],
+    selected: usize,
+    top: usize,
+    attribute: UINTN,
+    selected_attribute: UINTN,
+}
+
+impl<'a> Menu<'a> {
+    /// Creates a menu of `Items` covering `Area`, with the first item selected, in light gray
+    /// on black with the selected item in black on light gray.
+    pub fn new(Area: Rect, Items: &'a [&'a str]) -> Self {
+        Self {
+            area: Area,
+            items: Items,
+            selected: 0,
+            top: 0,
+            attribute: EFI_TEXT_ATTR(EFI_LIGHTGRAY, EFI_BLACK),
+            selected_attribute: EFI_TEXT_ATTR(EFI_BLACK, EFI_LIGHTGRAY),
+        }
+    }
+
+    /// Sets the attribute of the items and of the selected item.
+    pub fn set_attributes(&mut self, Attribute: UINTN, SelectedAttribute: UINTN) {
+        self.attribute = Attribute;
+        self.selected_attribute = SelectedAttribute;
+    }
+
+    /// Returns the area of the menu.
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+
+    /// Returns the items.
+    pub fn items(&self) -> &'a [&'a str] {
+        self.items
+    }
+
+    /// Returns the index of the selected item.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Selects the item at `Index`, or the last one if there are fewer items.
+    pub fn select(&mut self, Index: usize) {
+        self.selected = Index.min(self.items.len().saturating_sub(1));
+        let height = self.area.Height.max(1);
+        if self.selected < self.top {
+            self.top = self.selected;
+        } else if self.selected >= self.top + height {
+            self.top = self.selected + 1 - height;
+        }
+    }
+
+    /// Selects the next item, if any.
+    pub fn select_next(&mut self) {
+        self.select(self.selected + 1);
+    }
+
+    /// Selects the previous item, if any.
+    pub fn select_previous(&mut self) {
+        self.select(self.selected.saturating_sub(1));
+    }
+
+    /// Moves the selection for the arrow, page and home and end keys, and returns the selected
+    /// index when `Key` is the enter key.
+    pub fn handle_key(&mut self, Key: EFI_INPUT_KEY) -> Option<usize> {
+        let page = self.area.Height.max(1);
+        match Key.ScanCode {
+            SCAN_UP => self.select_previous(),
+            SCAN_DOWN => self.select_next(),
+            SCAN_PAGE_UP => self.select(self.selected.saturating_sub(page)),
+            SCAN_PAGE_DOWN => self.select(self.selected + page),
+            SCAN_HOME => self.select(0),
+            SCAN_END => self.select(self.items.len()),
+            _ if Key.UnicodeChar == CHAR_CARRIAGE_RETURN && !self.items.is_empty() => {
+                return Some(self.selected);
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    /// Draws the visible items.
+    pub fn draw<S: ConsoleSink>(&self, Tui: &mut Tui<S>) -> Result<(), EFI_STATUS> {
+        let width = self.area.Width;
+        if width < 4 {
+            return Ok(());
+        }
+
+        for row in 0..self.area.Height {
+            let index = self.top + row;
+            let (column, y) = (self.area.X, self.area.Y + row);
+            let attribute = if index == self.selected {
+                self.selected_attribute
+            } else {
+                self.attribute
+            };
+
+            let marker = if index == self.selected {
+                Tui.glyphs.Pointer
+            } else {
+                ' '
+            };
+            Tui.put_repeated(column, y, attribute, marker, 1)?;
+            Tui.put_repeated(column + 1, y, attribute, ' ', 1)?;
+
+            let text = self
+                .items
+                .get(index)
+                .map_or("", |item| truncate(item, width - 3));
+            let length = text.chars().count();
+            Tui.put_str(column + 2, y, attribute, text)?;
+            Tui.put_repeated(column + 2 + length, y, attribute, ' ', width - 3 - length)?;
+
+            let scroll = if row == 0 && self.top > 0 {
+                Tui.glyphs.ArrowUp
+            } else if row + 1 == self.area.Height && index + 1 < self.items.len() {
+                Tui.glyphs.ArrowDown
+            } else {
+                ' '
+            };
+            Tui.put_repeated(column + width - 1, y, self.attribute, scroll, 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A horizontal bar showing the progress of an operation, followed by its percentage.
+#[derive(Clone, Copy, Debug)]
+pub struct ProgressBar {
+    /// The column of the start of the bar.
+    pub Column: UINTN,
+    /// The row of the bar.
+    pub Row: UINTN,
+    /// The width of the bar, including the percentage.
+    pub Width: UINTN,
+    /// The attribute of the bar.
+    pub Attribute: UINTN,
+    completed: u64,
+    total: u64,
+}
+
+impl ProgressBar {
+    /// Creates an empty bar of `Width` columns at (`Column`, `Row`), in white on black.
+    pub fn new(Column: UINTN, Row: UINTN, Width: UINTN) -> Self {
+        Self {
+            Column,
+            Row,
+            Width,
+            Attribute: EFI_TEXT_ATTR(EFI_WHITE, EFI_BLACK),
+            completed: 0,
+            total: 1,
+        }
+    }
+
+    /// Sets the progress to `Completed` out of `Total`.
+    pub fn set_progress(&mut self, Completed: u64, Total: u64) {
+        self.total = Total.max(1);
+        self.completed = Completed.min(self.total);
+    }
+
+    /// Returns the progress in percent.
+    pub fn percent(&self) -> u64 {
+        (self.completed as u128 * 100 / self.total as u128) as u64
+    }
+
+    /// Draws the bar.
+    pub fn draw<S: ConsoleSink>(&self, Tui: &mut Tui<S>) -> Result<(), EFI_STATUS> {
+        let bar = self.Width.saturating_sub(5);
+        let filled = (self.completed as u128 * bar as u128 / self.total as u128) as usize;
+        let (full, shade) = (Tui.glyphs.FullBlock, Tui.glyphs.LightShade);
+        Tui.put_repeated(self.Column, self.Row, self.Attribute, full, filled)?;
+        Tui.put_repeated(
+            self.Column + filled,
+            self.Row,
+            self.Attribute,
+            shade,
+            bar - filled,
+        )?;
+
+        let percent = self.percent();
+        let mut label = *b" 100%";
+        label[1] = if percent == 100 { b'1' } else { b' ' };
+        label[2] = if percent >= 10 {
+            b'0' + (percent / 10 % 10) as u8
+        } else {
+            b' '
+        };
+        label[3] = b'0' + (percent % 10) as u8;
+        let label = core::str::from_utf8(&label).unwrap_or_default();
+        Tui.put_str(
+            self.Column + bar,
+            self.Row,
+            self.Attribute,
+            &label[..self.Width.min(5)],
+        )
+    }
+}
+
+/// A message in a window centered on the screen, with an OK button.
+#[derive(Clone, Copy, Debug)]
+pub struct MessageBox<'a> {
+    /// The title of the window.
+    pub Title: &'a str,
+    /// The message, of one or more lines separated by line feeds.
+    pub Message: &'a str,
+    /// The attribute of the window and the message.
+    pub Attribute: UINTN,
+    /// The attribute of the button.
+    pub ButtonAttribute: UINTN,
+}
+
+impl<'a> MessageBox<'a> {
+    /// The text of the button.
+    const BUTTON: &'static str = "[ OK ]";
+
+    /// Creates a message box showing `Message` titled `Title`, in white on blue with the button
+    /// in black on light gray.
+    pub fn new(Title: &'a str, Message: &'a str) -> Self {
+        Self {
+            Title,
+            Message,
+            Attribute: EFI_TEXT_ATTR(EFI_WHITE, EFI_BLUE),
+            ButtonAttribute: EFI_TEXT_ATTR(EFI_BLACK, EFI_LIGHTGRAY),
+        }
+    }
+
+    /// Returns the area the message box covers on a screen of `Columns` by `Rows`.
+    pub fn area(&self, Columns: UINTN, Rows: UINTN) -> Rect {
+        let lines = self.Message.split('\n');
+        let text = lines
+            .clone()
+            .map(|line| line.chars().count())
+            .chain([self.Title.chars().count() + 2, Self::BUTTON.len()])
+            .max()
+            .unwrap_or(0);
+        let width = (text + 4).min(Columns);
+        let height = (lines.count() + 4).min(Rows);
+        Rect::new((Columns - width) / 2, (Rows - height) / 2, width, height)
+    }
+
+    /// Draws the message box, returning its area.
+    pub fn draw<S: ConsoleSink>(&self, Tui: &mut Tui<S>) -> Result<Rect, EFI_STATUS> {
+        let (columns, rows) = Tui.size();
+        let area = self.area(columns, rows);
+        let window = Window {
+            Area: area,
+            Title: self.Title,
+            Attribute: self.Attribute,
+            Double: true,
+        };
+        window.draw(Tui)?;
+
+        let interior = window.interior();
+        if interior.Width < 2 || interior.Height < 2 {
+            return Ok(area);
+        }
+
+        let width = interior.Width - 2;
+        for (row, line) in self
+            .Message
+            .split('\n')
+            .take(interior.Height - 2)
+            .enumerate()
+        {
+            Tui.put_str(
+                interior.X + 1,
+                interior.Y + row,
+                self.Attribute,
+                truncate(line, width),
+            )?;
+        }
+
+        let button = truncate(Self::BUTTON, width);
+        let column = interior.X + (interior.Width - button.len()) / 2;
+        Tui.put_str(column, interior.bottom() - 1, self.ButtonAttribute, button)?;
+        Ok(area)
+    }
+}
+
+/// Returns the first `Count` characters of `Text`.
+fn truncate(Text: &str, Count: usize) -> &str {
+    let end = Text
+        .char_indices()
+        .nth(Count)
+        .map_or(Text.len(), |(index, _)| index);
+    &Text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::{String, ToString};
+    use std::vec;
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::protocols::console::simple_text_input::SCAN_NULL;
+    use crate::status::EFI_UNSUPPORTED;
+
+    const NORMAL: UINTN = EFI_TEXT_ATTR(EFI_LIGHTGRAY, EFI_BLACK);
+    const INVERSE: UINTN = EFI_TEXT_ATTR(EFI_BLACK, EFI_LIGHTGRAY);
+
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        Attribute(UINTN),
+        Cursor(UINTN, UINTN),
+        Output(String),
+        Clear,
+    }
+
+    /// A console sink that records the calls it receives and the screen they produce, and
+    /// cannot display the characters of `unsupported`.
+    struct Recorder {
+        calls: Vec<Call>,
+        columns: UINTN,
+        rows: UINTN,
+        cells: Vec<Vec<(char, UINTN)>>,
+        cursor: (UINTN, UINTN),
+        attribute: UINTN,
+        unsupported: &'static str,
+    }
+
+    impl Recorder {
+        fn new(Columns: UINTN, Rows: UINTN) -> Self {
+            Self {
+                calls: Vec::new(),
+                columns: Columns,
+                rows: Rows,
+                cells: vec![vec![(' ', 0); Columns]; Rows],
+                cursor: (0, 0),
+                attribute: 0,
+                unsupported: "",
+            }
+        }
+
+        fn row(&self, Row: UINTN) -> String {
+            self.cells[Row].iter().map(|&(char, _)| char).collect()
+        }
+
+        fn attribute(&self, Column: UINTN, Row: UINTN) -> UINTN {
+            self.cells[Row][Column].1
+        }
+    }
+
+    impl ConsoleSink for Recorder {
+        fn output_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+            self.calls.push(Call::Output(String.to_string()));
+            for char in String.chars() {
+                let (column, row) = self.cursor;
+                assert!(column < self.columns, "wrapped at row {row}");
+                assert!(
+                    (column, row) != (self.columns - 1, self.rows - 1),
+                    "wrote the bottom right cell"
+                );
+                self.cells[row][column] = (char, self.attribute);
+                self.cursor.0 += 1;
+            }
+
+            Ok(())
+        }
+
+        fn test_string(&mut self, String: &str) -> Result<(), EFI_STATUS> {
+            if String.chars().any(|char| self.unsupported.contains(char)) {
+                Err(EFI_UNSUPPORTED)
+            } else {
+                Ok(())
+            }
+        }
+
+        fn set_attribute(&mut self, Attribute: UINTN) -> Result<(), EFI_STATUS> {
+            self.calls.push(Call::Attribute(Attribute));
+            self.attribute = Attribute;
+            Ok(())
+        }
+
+        fn set_cursor_position(&mut self, Column: UINTN, Row: UINTN) -> Result<(), EFI_STATUS> {
+            assert!(Column < self.columns && Row < self.rows);
+            self.calls.push(Call::Cursor(Column, Row));
+            self.cursor = (Column, Row);
+            Ok(())
+        }
+
+        fn clear_screen(&mut self) -> Result<(), EFI_STATUS> {
+            self.calls.push(Call::Clear);
+            self.cells = vec![vec![(' ', self.attribute); self.columns]; self.rows];
+            self.cursor = (0, 0);
+            Ok(())
+        }
+
+        fn size(&mut self) -> Option<(UINTN, UINTN)> {
+            Some((self.columns, self.rows))
+        }
+    }
+
+    fn recording(Columns: UINTN, Rows: UINTN, Glyphs: Glyphs) -> Tui<Recorder> {
+        Tui::with_glyphs(Recorder::new(Columns, Rows), Glyphs)
+    }
+
+    fn key(ScanCode: u16, UnicodeChar: CHAR16) -> EFI_INPUT_KEY {
+        EFI_INPUT_KEY {
+            ScanCode,
+            UnicodeChar,
+        }
+    }
+
+    #[test]
+    fn draw_box() {
+        let mut tui = recording(20, 10, Glyphs::UNICODE);
+        tui.draw_box(Rect::new(1, 1, 5, 3), NORMAL, &BoxStyle::SINGLE)
+            .unwrap();
+        let screen = tui.get_ref();
+        assert_eq!(screen.row(0).trim_end(), "");
+        assert_eq!(screen.row(1).trim_end(), " ┌───┐");
+        assert_eq!(screen.row(2).trim_end(), " │   │");
+        assert_eq!(screen.row(3).trim_end(), " └───┘");
+        assert_eq!(screen.attribute(5, 3), NORMAL);
+        assert_eq!(screen.attribute(3, 2), 0);
+
+        // Boxes too small for a border are not drawn.
+        let mut tui = recording(20, 10, Glyphs::UNICODE);
+        tui.draw_box(Rect::new(1, 1, 1, 5), NORMAL, &BoxStyle::SINGLE)
+            .unwrap();
+        tui.draw_box(Rect::new(1, 1, 5, 1), NORMAL, &BoxStyle::SINGLE)
+            .unwrap();
+        assert_eq!(tui.get_ref().calls, []);
+    }
+
+    #[test]
+    fn draw_window() {
+        let mut tui = recording(20, 10, Glyphs::ASCII);
+        let mut window = Window::new(Rect::new(0, 0, 12, 4), "Title");
+        window.Double = true;
+        window.draw(&mut tui).unwrap();
+        let screen = tui.get_ref();
+        assert_eq!(screen.row(0).trim_end(), "+- Title --+");
+        assert_eq!(screen.row(1).trim_end(), "|          |");
+        assert_eq!(screen.row(3).trim_end(), "+----------+");
+        assert_eq!(screen.attribute(5, 2), window.Attribute);
+
+        // A window over the bottom right corner leaves that cell alone.
+        let mut tui = recording(20, 10, Glyphs::ASCII);
+        Window::new(Rect::new(15, 7, 5, 3), "")
+            .draw(&mut tui)
+            .unwrap();
+        assert_eq!(tui.get_ref().row(9), "               +--- ");
+    }
+
+    #[test]
+    fn put_str_clips() {
+        let mut tui = recording(10, 3, Glyphs::ASCII);
+        tui.put_str(8, 1, NORMAL, "xyz").unwrap();
+        assert_eq!(
+            tui.get_ref().calls,
+            [
+                Call::Attribute(NORMAL),
+                Call::Cursor(8, 1),
+                Call::Output("xy".to_string())
+            ]
+        );
+
+        // The last cell of the last row is never written.
+        let mut tui = recording(10, 3, Glyphs::ASCII);
+        tui.put_str(5, 2, INVERSE, "abcdefgh").unwrap();
+        tui.put_str(9, 2, INVERSE, "x").unwrap();
+        tui.put_str(10, 0, INVERSE, "x").unwrap();
+        tui.put_str(0, 3, INVERSE, "x").unwrap();
+        assert_eq!(
+            tui.get_ref().calls,
+            [
+                Call::Attribute(INVERSE),
+                Call::Cursor(5, 2),
+                Call::Output("abcd".to_string())
+            ]
+        );
+
+        let mut tui = recording(10, 3, Glyphs::UNICODE);
+        tui.fill(Rect::new(0, 0, 100, 100), NORMAL, '█').unwrap();
+        let screen = tui.get_ref();
+        assert_eq!(screen.row(1), "██████████");
+        assert_eq!(screen.row(2), "█████████ ");
+        assert_eq!(screen.attribute(9, 2), 0);
+    }
+
+    #[test]
+    fn put_repeated_in_chunks() {
+        let mut tui = recording(80, 25, Glyphs::UNICODE);
+        tui.put_repeated(2, 0, NORMAL, '─', 70).unwrap();
+        let outputs: Vec<usize> = tui
+            .get_ref()
+            .calls
+            .iter()
+            .filter_map(|call| match call {
+                Call::Output(text) => Some(text.chars().count()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(outputs, [32, 32, 6]);
+        assert_eq!(tui.get_ref().row(0).trim(), "─".repeat(70));
+    }
+
+    #[test]
+    fn menu_scrolls() {
+        let items = ["zero", "one", "two", "three", "four", "five"];
+        let mut menu = Menu::new(Rect::new(0, 0, 10, 3), &items);
+        let mut tui = recording(20, 5, Glyphs::ASCII);
+        let mut check = |Menu: &Menu, Rows: [&str; 3]| {
+            Menu.draw(&mut tui).unwrap();
+            for (row, expected) in Rows.iter().enumerate() {
+                assert_eq!(&tui.get_ref().row(row)[..10], *expected);
+            }
+        };
+
+        check(&menu, ["> zero    ", "  one     ", "  two    v"]);
+        assert_eq!(menu.handle_key(key(SCAN_DOWN, 0)), None);
+        menu.handle_key(key(SCAN_DOWN, 0));
+        check(&menu, ["  zero    ", "  one     ", "> two    v"]);
+        menu.handle_key(key(SCAN_DOWN, 0));
+        assert_eq!((menu.selected(), menu.top), (3, 1));
+        check(&menu, ["  one    ^", "  two     ", "> three  v"]);
+        menu.handle_key(key(SCAN_END, 0));
+        check(&menu, ["  three  ^", "  four    ", "> five    "]);
+        menu.handle_key(key(SCAN_DOWN, 0));
+        assert_eq!(menu.selected(), 5);
+        menu.handle_key(key(SCAN_PAGE_UP, 0));
+        check(&menu, ["> two    ^", "  three   ", "  four   v"]);
+        menu.handle_key(key(SCAN_UP, 0));
+        check(&menu, ["> one    ^", "  two     ", "  three  v"]);
+        menu.handle_key(key(SCAN_HOME, 0));
+        check(&menu, ["> zero    ", "  one     ", "  two    v"]);
+        menu.handle_key(key(SCAN_PAGE_DOWN, 0));
+        assert_eq!((menu.selected(), menu.top), (3, 1));
+        assert_eq!(
+            menu.handle_key(key(SCAN_NULL, CHAR_CARRIAGE_RETURN)),
+            Some(3)
+        );
+
+        // The selected item is highlighted, but not its scroll mark.
+        menu.draw(&mut tui).unwrap();
+        let screen = tui.get_ref();
+        assert_eq!(screen.attribute(0, 2), INVERSE);
+        assert_eq!(screen.attribute(8, 2), INVERSE);
+        assert_eq!(screen.attribute(9, 2), NORMAL);
+        assert_eq!(screen.attribute(0, 1), NORMAL);
+
+        let mut empty = Menu::new(Rect::new(0, 0, 10, 3), &[]);
+        assert_eq!(empty.handle_key(key(SCAN_DOWN, 0)), None);
+        assert_eq!(empty.handle_key(key(SCAN_NULL, CHAR_CARRIAGE_RETURN)), None);
+    }
+
+    #[test]
+    fn detect_glyphs() {
+        let detect = |Unsupported: &'static str| {
+            let mut sink = Recorder::new(80, 25);
+            sink.unsupported = Unsupported;
+            let tui = Tui::new(sink);
+            assert_eq!(tui.get_ref().calls, []);
+            *tui.glyphs()
+        };
+
+        assert_eq!(detect(""), Glyphs::UNICODE);
+        assert_eq!(detect("┌═░↑►"), Glyphs::ASCII);
+
+        // Without double lines, double borders fall back to single ones.
+        let glyphs = detect("╔");
+        assert_eq!(glyphs.Single, BoxStyle::SINGLE);
+        assert_eq!(glyphs.Double, BoxStyle::SINGLE);
+
+        let glyphs = detect("─↓");
+        assert_eq!(glyphs.Single, BoxStyle::ASCII);
+        assert_eq!(glyphs.Double, BoxStyle::DOUBLE);
+        assert_eq!((glyphs.ArrowUp, glyphs.ArrowDown), ('^', 'v'));
+        assert_eq!((glyphs.FullBlock, glyphs.LightShade), ('█', '░'));
+        assert_eq!(glyphs.Pointer, Glyphs::UNICODE.Pointer);
+    }
+}
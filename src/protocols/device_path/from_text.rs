@@ -0,0 +1,1156 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Device Path From Text Protocol
+//!
+//! This module defines the Device Path From Text Protocol, also known as the
+//! [`EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL`], which converts text to device paths and device nodes.
+//! See [Section 10.7 of the UEFI Specification] for more details.
+//!
+//! Firmware does not always provide this protocol, so the same conversion is also implemented in
+//! Rust by [`DevicePathBuilder::push_text()`], which appends the nodes described by a text device
+//! path to a caller-supplied buffer. It accepts the text produced by [`DevicePath::display()`],
+//! as well as the other forms described in [Section 10.6.1.6 of the UEFI Specification] that are
+//! accepted by EDK II.
+//!
+//! [`EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL
+//! [`DevicePathBuilder::push_text()`]: crate::protocols::device_path::DevicePathBuilder::push_text
+//! [`DevicePath::display()`]: crate::protocols::device_path::DevicePath::display
+//!
+//! [Section 10.7 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/10_Protocols_Device_Path_Protocol.html#device-path-from-text-protocol
+//! [Section 10.6.1.6 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/10_Protocols_Device_Path_Protocol.html#text-device-node-reference
+
+use core::str::FromStr;
+
+use crate::protocols::device_path::nodes::*;
+use crate::protocols::device_path::*;
+use crate::protocols::Protocol;
+use crate::status::EFI_INVALID_PARAMETER;
+use crate::types::{
+    EFI_IPv4_ADDRESS, EFI_IPv6_ADDRESS, CHAR16, EFI_GUID, EFI_MAC_ADDRESS, EFI_STATUS, UINT8,
+};
+
+/// GUID for the [`EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL`].
+///
+/// [`EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL
+pub const EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0x05C99A21,
+    0xC70F,
+    0x4AD2,
+    [0x8A, 0x5F, 0x35, 0xDF, 0x33, 0x43, 0xF5, 0x1E],
+);
+
+/// The Device Path From Text protocol converts text to device paths and device nodes.
+///
+/// Every device path returned by this protocol is allocated from pool memory, and must be freed
+/// by the caller with [`EFI_BOOT_SERVICES.FreePool()`] once it is no longer needed.
+///
+/// [`EFI_BOOT_SERVICES.FreePool()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-freepool
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL {
+    ConvertTextToDeviceNode: EFI_DEVICE_PATH_FROM_TEXT_NODE,
+    ConvertTextToDevicePath: EFI_DEVICE_PATH_FROM_TEXT_PATH,
+}
+
+unsafe impl Protocol for EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL {
+    const GUID: EFI_GUID = EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL_GUID;
+}
+
+impl EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL {
+    /// Converts text to a device node.
+    ///
+    /// # Parameters
+    ///
+    /// ## `TextDeviceNode`
+    ///
+    /// Points to the null-terminated text representation of a device node.
+    ///
+    /// # Return Value
+    ///
+    /// A pointer to the allocated device node, or null if `TextDeviceNode` is null or there was
+    /// insufficient memory.
+    pub unsafe fn ConvertTextToDeviceNode(
+        &self,
+        TextDeviceNode: *const CHAR16,
+    ) -> *mut EFI_DEVICE_PATH_PROTOCOL {
+        (self.ConvertTextToDeviceNode)(TextDeviceNode)
+    }
+
+    /// Converts text to a device path.
+    ///
+    /// # Parameters
+    ///
+    /// ## `TextDevicePath`
+    ///
+    /// Points to the null-terminated text representation of a device path. Conversion starts with
+    /// the first character and continues until the first non-device path character.
+    ///
+    /// # Return Value
+    ///
+    /// A pointer to the allocated device path, or null if `TextDevicePath` is null or there was
+    /// insufficient memory.
+    pub unsafe fn ConvertTextToDevicePath(
+        &self,
+        TextDevicePath: *const CHAR16,
+    ) -> *mut EFI_DEVICE_PATH_PROTOCOL {
+        (self.ConvertTextToDevicePath)(TextDevicePath)
+    }
+}
+
+impl<'a> DevicePathBuilder<'a> {
+    /// Appends the nodes described by the text representation of a device path, such as
+    /// `PciRoot(0x0)/Pci(0x1F,0x2)/Sata(0x0,0xFFFF,0x0)`.
+    ///
+    /// Nodes are separated by `/` and instances by `,`, outside of the parentheses enclosing the
+    /// parameters of a node. Text that is not of the form `Name(Parameters)`, or whose `Name` is
+    /// unknown, is appended as a [`FILEPATH_DEVICE_PATH`] node. Missing numeric parameters default to zero, and numbers are
+    /// read as hexadecimal when prefixed with `0x`, and as decimal otherwise.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if the parentheses of a node are unbalanced or a
+    /// parameter is malformed or out of range, and [`EFI_BUFFER_TOO_SMALL`] if the buffer is too
+    /// small. The builder is left unchanged on error.
+    ///
+    /// [`FILEPATH_DEVICE_PATH`]: crate::protocols::device_path::nodes::FILEPATH_DEVICE_PATH
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+    pub fn push_text(&mut self, Text: &str) -> Result<&mut Self, EFI_STATUS> {
+        let len = self.len;
+        for (i, instance) in split_top_level(Text, ',').enumerate() {
+            let result = if i == 0 {
+                Ok(())
+            } else {
+                self.push_end_instance().map(drop)
+            };
+
+            let result = result.and_then(|()| {
+                split_top_level(instance, '/')
+                    .filter(|node| !node.is_empty())
+                    .try_for_each(|node| push_node_text(self, node))
+            });
+
+            if let Err(status) = result {
+                self.len = len;
+                return Err(status);
+            }
+        }
+
+        Ok(self)
+    }
+}
+
+const HEADER: EFI_DEVICE_PATH_PROTOCOL = EFI_DEVICE_PATH_PROTOCOL::new(0, 0, 0);
+
+fn push_node_text(b: &mut DevicePathBuilder<'_>, text: &str) -> Result<(), EFI_STATUS> {
+    let (name, params) = match node_syntax(text)? {
+        Some(node) => node,
+        None => return b.push_file_path(text).map(drop),
+    };
+    let mut p = Params(params.split(','));
+
+    match name {
+        "Path" => {
+            let (ty, sub_type) = (num(p.next())?, num(p.next())?);
+            push_hex_node(b, ty, sub_type, p.next())
+        }
+        "HardwarePath" => push_hex_node(b, HARDWARE_DEVICE_PATH, num(p.next())?, p.next()),
+        "AcpiPath" => push_hex_node(b, ACPI_DEVICE_PATH, num(p.next())?, p.next()),
+        "Msg" => push_hex_node(b, MESSAGING_DEVICE_PATH, num(p.next())?, p.next()),
+        "MediaPath" => push_hex_node(b, MEDIA_DEVICE_PATH, num(p.next())?, p.next()),
+        "BbsPath" => push_hex_node(b, BBS_DEVICE_PATH, num(p.next())?, p.next()),
+
+        "Pci" => b
+            .push(&PCI_DEVICE_PATH {
+                Header: HEADER,
+                Device: num(p.next())?,
+                Function: num(p.next())?,
+            })
+            .map(drop),
+        "PcCard" => b
+            .push(&PCCARD_DEVICE_PATH {
+                Header: HEADER,
+                FunctionNumber: num(p.next())?,
+            })
+            .map(drop),
+        "MemoryMapped" => b
+            .push(&MEMMAP_DEVICE_PATH {
+                Header: HEADER,
+                MemoryType: num(p.next())?,
+                StartingAddress: num(p.next())?,
+                EndingAddress: num(p.next())?,
+            })
+            .map(drop),
+        "VenHw" => push_vendor(b, HARDWARE_DEVICE_PATH, HW_VENDOR_DP, &mut p),
+        "Ctrl" => b
+            .push(&CONTROLLER_DEVICE_PATH {
+                Header: HEADER,
+                ControllerNumber: num(p.next())?,
+            })
+            .map(drop),
+        "BMC" => b
+            .push(&BMC_DEVICE_PATH {
+                Header: HEADER,
+                InterfaceType: num(p.next())?,
+                BaseAddress: num(p.next())?,
+            })
+            .map(drop),
+
+        "Acpi" => push_acpi(b, eisa_id(p.next())?, num(p.next())?),
+        "PciRoot" => push_acpi(b, EISA_PNP_ID(PNP_PCI_ROOT), num(p.next())?),
+        "PcieRoot" => push_acpi(b, EISA_PNP_ID(PNP_PCIE_ROOT), num(p.next())?),
+        "Floppy" => push_acpi(b, EISA_PNP_ID(0x0604), num(p.next())?),
+        "Keyboard" => push_acpi(b, EISA_PNP_ID(0x0301), num(p.next())?),
+        "Serial" => push_acpi(b, EISA_PNP_ID(0x0501), num(p.next())?),
+        "ParallelPort" => push_acpi(b, EISA_PNP_ID(0x0401), num(p.next())?),
+        "AcpiEx" => {
+            let node = ACPI_EXTENDED_HID_DEVICE_PATH {
+                Header: HEADER,
+                HID: eisa_id(p.next())?,
+                CID: eisa_id(p.next())?,
+                UID: num(p.next())?,
+            };
+            let (hid, cid, uid) = (p.next(), p.next(), p.next());
+            push_acpi_ex(b, &node, hid, uid, cid)
+        }
+        "AcpiExp" => {
+            let (hid, cid) = (eisa_id(p.next())?, eisa_id(p.next())?);
+            let uid = p.next();
+            let (UID, uid) = match num(uid) {
+                Ok(UID) => (UID, ""),
+                Err(_) => (0, uid),
+            };
+            let node = ACPI_EXTENDED_HID_DEVICE_PATH {
+                Header: HEADER,
+                HID: hid,
+                UID,
+                CID: cid,
+            };
+            push_acpi_ex(b, &node, "", uid, "")
+        }
+        "AcpiAdr" => {
+            let data = b.reserve(ACPI_DEVICE_PATH, ACPI_ADR_DP, params.split(',').count() * 4)?;
+            for (chunk, adr) in data.chunks_exact_mut(4).zip(params.split(',')) {
+                chunk.copy_from_slice(&num::<u32>(adr)?.to_le_bytes());
+            }
+            Ok(())
+        }
+        "NvdimmAcpiAdr" => b
+            .push(&ACPI_NVDIMM_DEVICE_PATH {
+                Header: HEADER,
+                NFITDeviceHandle: num(p.next())?,
+            })
+            .map(drop),
+
+        "Ata" => {
+            let (first, second, third) = (p.next(), p.next(), p.next());
+            let node = if second.is_empty() && third.is_empty() {
+                ATAPI_DEVICE_PATH {
+                    Header: HEADER,
+                    PrimarySecondary: 0,
+                    SlaveMaster: 0,
+                    Lun: num(first)?,
+                }
+            } else {
+                ATAPI_DEVICE_PATH {
+                    Header: HEADER,
+                    PrimarySecondary: keyword(first, &["Primary", "Secondary"])?,
+                    SlaveMaster: keyword(second, &["Master", "Slave"])?,
+                    Lun: num(third)?,
+                }
+            };
+            b.push(&node).map(drop)
+        }
+        "Scsi" => b
+            .push(&SCSI_DEVICE_PATH {
+                Header: HEADER,
+                Pun: num(p.next())?,
+                Lun: num(p.next())?,
+            })
+            .map(drop),
+        "Fibre" => b
+            .push(&FIBRECHANNEL_DEVICE_PATH {
+                Header: HEADER,
+                Reserved: 0,
+                WWN: num(p.next())?,
+                Lun: num(p.next())?,
+            })
+            .map(drop),
+        "FibreEx" => b
+            .push(&FIBRECHANNELEX_DEVICE_PATH {
+                Header: HEADER,
+                Reserved: 0,
+                WWN: num::<u64>(p.next())?.to_be_bytes(),
+                Lun: num::<u64>(p.next())?.to_be_bytes(),
+            })
+            .map(drop),
+        "I1394" => b
+            .push(&F1394_DEVICE_PATH {
+                Header: HEADER,
+                Reserved: 0,
+                Guid: hex_num(p.next())?,
+            })
+            .map(drop),
+        "USB" => b
+            .push(&USB_DEVICE_PATH {
+                Header: HEADER,
+                ParentPortNumber: num(p.next())?,
+                InterfaceNumber: num(p.next())?,
+            })
+            .map(drop),
+        "UsbClass" => push_usb_class(b, &mut p, None),
+        "UsbAudio" => push_usb_class(b, &mut p, Some(1)),
+        "UsbCDCControl" => push_usb_class(b, &mut p, Some(2)),
+        "UsbHID" => push_usb_class(b, &mut p, Some(3)),
+        "UsbImage" => push_usb_class(b, &mut p, Some(6)),
+        "UsbPrinter" => push_usb_class(b, &mut p, Some(7)),
+        "UsbMassStorage" => push_usb_class(b, &mut p, Some(8)),
+        "UsbHub" => push_usb_class(b, &mut p, Some(9)),
+        "UsbCDCData" => push_usb_class(b, &mut p, Some(10)),
+        "UsbSmartCard" => push_usb_class(b, &mut p, Some(11)),
+        "UsbVideo" => push_usb_class(b, &mut p, Some(14)),
+        "UsbDiagnostic" => push_usb_class(b, &mut p, Some(0xDC)),
+        "UsbWireless" => push_usb_class(b, &mut p, Some(0xE0)),
+        "UsbDeviceFirmwareUpdate" => push_usb_application_class(b, &mut p, 1),
+        "UsbIrdaBridge" => push_usb_application_class(b, &mut p, 2),
+        "UsbTestAndMeasurement" => push_usb_application_class(b, &mut p, 3),
+        "UsbWwid" => {
+            let node = USB_WWID_DEVICE_PATH {
+                Header: HEADER,
+                VendorId: num(p.next())?,
+                ProductId: num(p.next())?,
+                InterfaceNumber: num(p.next())?,
+            };
+            let serial = p.next();
+            let serial = serial
+                .strip_prefix('"')
+                .and_then(|s| s.strip_suffix('"'))
+                .unwrap_or(serial);
+            let len = ucs2_len(serial)?;
+            let data = b.reserve(MESSAGING_DEVICE_PATH, MSG_USB_WWID_DP, 6 + len)?;
+            data[..6].copy_from_slice(&node_body(&node)[..6]);
+            write_ucs2(&mut data[6..], serial);
+            Ok(())
+        }
+        "Unit" => b
+            .push(&DEVICE_LOGICAL_UNIT_DEVICE_PATH {
+                Header: HEADER,
+                Lun: num(p.next())?,
+            })
+            .map(drop),
+        "Sata" => b
+            .push(&SATA_DEVICE_PATH {
+                Header: HEADER,
+                HBAPortNumber: num(p.next())?,
+                PortMultiplierPortNumber: num(p.next())?,
+                Lun: num(p.next())?,
+            })
+            .map(drop),
+        "I2O" => b
+            .push(&I2O_DEVICE_PATH {
+                Header: HEADER,
+                Tid: num(p.next())?,
+            })
+            .map(drop),
+        "Infiniband" => b
+            .push(&INFINIBAND_DEVICE_PATH {
+                Header: HEADER,
+                ResourceFlags: num(p.next())?,
+                PortGid: guid(p.next())?.to_bytes(),
+                ServiceId: num(p.next())?,
+                TargetPortId: num(p.next())?,
+                DeviceId: num(p.next())?,
+            })
+            .map(drop),
+        "VenMsg" => push_vendor(b, MESSAGING_DEVICE_PATH, MSG_VENDOR_DP, &mut p),
+        "VenPcAnsi" => push_messaging_vendor(b, EFI_PC_ANSI_GUID),
+        "VenVt100" => push_messaging_vendor(b, EFI_VT_100_GUID),
+        "VenVt100Plus" => push_messaging_vendor(b, EFI_VT_100_PLUS_GUID),
+        "VenUtf8" => push_messaging_vendor(b, EFI_VT_UTF8_GUID),
+        "DebugPort" => push_messaging_vendor(b, EFI_DEBUGPORT_PROTOCOL_GUID),
+        "UartFlowCtrl" => b
+            .push(&UART_FLOW_CONTROL_DEVICE_PATH {
+                Header: HEADER,
+                Guid: DEVICE_PATH_MESSAGING_UART_FLOW_CONTROL,
+                FlowControlMap: keyword(p.next(), &["None", "Hardware", "XonXoff"])?,
+            })
+            .map(drop),
+        "SAS" => {
+            let (SasAddress, Lun, RelativeTargetPort) =
+                (num(p.next())?, num(p.next())?, num(p.next())?);
+            let DeviceTopology = sas_topology(&mut p)?;
+            b.push(&SAS_DEVICE_PATH {
+                Header: HEADER,
+                Guid: EFI_SAS_DEVICE_PATH_GUID,
+                Reserved: num(p.next())?,
+                SasAddress,
+                Lun,
+                DeviceTopology,
+                RelativeTargetPort,
+            })
+            .map(drop)
+        }
+        "SasEx" => {
+            let (address, lun) = (num::<u64>(p.next())?, num::<u64>(p.next())?);
+            let RelativeTargetPort = num(p.next())?;
+            b.push(&SASEX_DEVICE_PATH {
+                Header: HEADER,
+                SasAddress: address.to_be_bytes(),
+                Lun: lun.to_be_bytes(),
+                DeviceTopology: sas_topology(&mut p)?,
+                RelativeTargetPort,
+            })
+            .map(drop)
+        }
+        "NVMe" => {
+            let NamespaceId = num(p.next())?;
+            let mut uuid = [0; 8];
+            let mut bytes = p.next().split('-');
+            for byte in &mut uuid {
+                decode_hex(
+                    bytes.next().unwrap_or_default(),
+                    core::slice::from_mut(byte),
+                )?;
+            }
+            if bytes.next().is_some() {
+                return Err(EFI_INVALID_PARAMETER);
+            }
+            b.push(&NVME_NAMESPACE_DEVICE_PATH {
+                Header: HEADER,
+                NamespaceId,
+                NamespaceUuid: u64::from_be_bytes(uuid),
+            })
+            .map(drop)
+        }
+        "MAC" => {
+            let mut Addr = [0; 32];
+            let address = p.next();
+            if address.len() > Addr.len() * 2 {
+                return Err(EFI_INVALID_PARAMETER);
+            }
+            decode_hex(address, &mut Addr[..address.len() / 2])?;
+            b.push(&MAC_ADDR_DEVICE_PATH {
+                Header: HEADER,
+                MacAddress: EFI_MAC_ADDRESS { Addr },
+                IfType: num(p.next())?,
+            })
+            .map(drop)
+        }
+        "IPv4" => {
+            let remote = ipv4(p.next())?;
+            let protocol = network_protocol(p.next())?;
+            let origin = keyword(p.next(), &["DHCP", "Static"])?;
+            let local = ipv4(p.next())?;
+            b.push(&IPv4_DEVICE_PATH {
+                Header: HEADER,
+                LocalIpAddress: EFI_IPv4_ADDRESS { Addr: local },
+                RemoteIpAddress: EFI_IPv4_ADDRESS { Addr: remote },
+                LocalPort: 0,
+                RemotePort: 0,
+                Protocol: protocol,
+                StaticIpAddress: origin,
+                GatewayIpAddress: EFI_IPv4_ADDRESS {
+                    Addr: ipv4(p.next())?,
+                },
+                SubnetMask: EFI_IPv4_ADDRESS {
+                    Addr: ipv4(p.next())?,
+                },
+            })
+            .map(drop)
+        }
+        "IPv6" => {
+            let remote = ipv6(p.next())?;
+            let protocol = network_protocol(p.next())?;
+            let origin = keyword(
+                p.next(),
+                &["Static", "StatelessAutoConfigure", "StatefulAutoConfigure"],
+            )?;
+            let local = ipv6(p.next())?;
+            b.push(&IPv6_DEVICE_PATH {
+                Header: HEADER,
+                LocalIpAddress: EFI_IPv6_ADDRESS { Addr: local },
+                RemoteIpAddress: EFI_IPv6_ADDRESS { Addr: remote },
+                LocalPort: 0,
+                RemotePort: 0,
+                Protocol: protocol,
+                IpAddressOrigin: origin,
+                PrefixLength: num(p.next())?,
+                GatewayIpAddress: EFI_IPv6_ADDRESS {
+                    Addr: ipv6(p.next())?,
+                },
+            })
+            .map(drop)
+        }
+        "Vlan" => b
+            .push(&VLAN_DEVICE_PATH {
+                Header: HEADER,
+                VlanId: num(p.next())?,
+            })
+            .map(drop),
+        "Uart" => b
+            .push(&UART_DEVICE_PATH {
+                Header: HEADER,
+                Reserved: 0,
+                BaudRate: default_or_num(p.next())?,
+                DataBits: default_or_num(p.next())?,
+                Parity: keyword(p.next(), &["D", "N", "E", "O", "M", "S"])?,
+                StopBits: keyword(p.next(), &["D", "1", "1.5", "2"])?,
+            })
+            .map(drop),
+        "iSCSI" => {
+            let name = p.next();
+            let TargetPortalGroupTag = num(p.next())?;
+            let Lun = num::<u64>(p.next())?.swap_bytes();
+            let mut LoginOption = 0;
+            if p.next() == "CRC32C" {
+                LoginOption |= 0x0002;
+            }
+            if p.next() == "CRC32C" {
+                LoginOption |= 0x0008;
+            }
+            match p.next() {
+                "None" => LoginOption |= 0x0800,
+                "CHAP_UNI" => LoginOption |= 0x1000,
+                _ => {}
+            }
+            let node = ISCSI_DEVICE_PATH {
+                Header: HEADER,
+                NetworkProtocol: u16::from(p.next() != "TCP"),
+                LoginOption,
+                Lun,
+                TargetPortalGroupTag,
+            };
+            b.push_raw(
+                MESSAGING_DEVICE_PATH,
+                MSG_ISCSI_DP,
+                &[node_body(&node), name.as_bytes(), &[0]],
+            )
+            .map(drop)
+        }
+        "Uri" => b
+            .push_raw(MESSAGING_DEVICE_PATH, MSG_URI_DP, &[params.as_bytes()])
+            .map(drop),
+        "UFS" => b
+            .push(&UFS_DEVICE_PATH {
+                Header: HEADER,
+                Pun: num(p.next())?,
+                Lun: num(p.next())?,
+            })
+            .map(drop),
+        "SD" => b
+            .push(&SD_DEVICE_PATH {
+                Header: HEADER,
+                SlotNumber: num(p.next())?,
+            })
+            .map(drop),
+        "eMMC" => b
+            .push(&EMMC_DEVICE_PATH {
+                Header: HEADER,
+                SlotNumber: num(p.next())?,
+            })
+            .map(drop),
+        "Bluetooth" => {
+            let mut BD_ADDR = [0; 6];
+            decode_hex(p.next(), &mut BD_ADDR)?;
+            b.push(&BLUETOOTH_DEVICE_PATH {
+                Header: HEADER,
+                BD_ADDR,
+            })
+            .map(drop)
+        }
+        "Wi-Fi" => {
+            let mut SSId = [0; 32];
+            SSId.get_mut(..params.len())
+                .ok_or(EFI_INVALID_PARAMETER)?
+                .copy_from_slice(params.as_bytes());
+            b.push(&WIFI_DEVICE_PATH {
+                Header: HEADER,
+                SSId,
+            })
+            .map(drop)
+        }
+        "BluetoothLE" => {
+            let mut Address = [0; 6];
+            decode_hex(p.next(), &mut Address)?;
+            b.push(&BLUETOOTH_LE_DEVICE_PATH {
+                Header: HEADER,
+                Address,
+                Type: num(p.next())?,
+            })
+            .map(drop)
+        }
+        "Dns" => {
+            let is_ipv6 = params.contains(':');
+            let count = params.split(',').count();
+            let data = b.reserve(MESSAGING_DEVICE_PATH, MSG_DNS_DP, 1 + count * 16)?;
+            data[0] = UINT8::from(is_ipv6);
+            for (chunk, address) in data[1..].chunks_exact_mut(16).zip(params.split(',')) {
+                if is_ipv6 {
+                    chunk.copy_from_slice(&ipv6(address)?);
+                } else {
+                    chunk[..4].copy_from_slice(&ipv4(address)?);
+                    chunk[4..].fill(0);
+                }
+            }
+            Ok(())
+        }
+
+        "HD" => {
+            let PartitionNumber = num(p.next())?;
+            let (signature_type, signature) = (p.next(), p.next());
+            let mut Signature = [0; 16];
+            let (MBRType, SignatureType) = match signature_type {
+                "MBR" => {
+                    Signature[..4].copy_from_slice(&num::<u32>(signature)?.to_le_bytes());
+                    (MBR_TYPE_PCAT, SIGNATURE_TYPE_MBR)
+                }
+                "GPT" => {
+                    Signature = guid(signature)?.to_bytes();
+                    (MBR_TYPE_EFI_PARTITION_TABLE_HEADER, SIGNATURE_TYPE_GUID)
+                }
+                other => (0, num(other)?),
+            };
+            b.push(&HARDDRIVE_DEVICE_PATH {
+                Header: HEADER,
+                PartitionNumber,
+                PartitionStart: num(p.next())?,
+                PartitionSize: num(p.next())?,
+                Signature,
+                MBRType,
+                SignatureType,
+            })
+            .map(drop)
+        }
+        "CDROM" => b
+            .push(&CDROM_DEVICE_PATH {
+                Header: HEADER,
+                BootEntry: num(p.next())?,
+                PartitionStart: num(p.next())?,
+                PartitionSize: num(p.next())?,
+            })
+            .map(drop),
+        "VenMedia" => push_vendor(b, MEDIA_DEVICE_PATH, MEDIA_VENDOR_DP, &mut p),
+        "Media" => b
+            .push(&MEDIA_PROTOCOL_DEVICE_PATH {
+                Header: HEADER,
+                Protocol: guid(p.next())?,
+            })
+            .map(drop),
+        "FvFile" => b
+            .push(&MEDIA_FW_VOL_FILEPATH_DEVICE_PATH {
+                Header: HEADER,
+                FvFileName: guid(p.next())?,
+            })
+            .map(drop),
+        "Fv" => b
+            .push(&MEDIA_FW_VOL_DEVICE_PATH {
+                Header: HEADER,
+                FvName: guid(p.next())?,
+            })
+            .map(drop),
+        "Offset" => b
+            .push(&MEDIA_RELATIVE_OFFSET_RANGE_DEVICE_PATH {
+                Header: HEADER,
+                Reserved: 0,
+                StartingOffset: num(p.next())?,
+                EndingOffset: num(p.next())?,
+            })
+            .map(drop),
+        "RamDisk" => push_ram_disk(b, &mut p, None),
+        "VirtualDisk" => push_ram_disk(b, &mut p, Some(EFI_VIRTUAL_DISK_GUID)),
+        "VirtualCD" => push_ram_disk(b, &mut p, Some(EFI_VIRTUAL_CD_GUID)),
+        "PersistentVirtualDisk" => push_ram_disk(b, &mut p, Some(EFI_PERSISTENT_VIRTUAL_DISK_GUID)),
+        "PersistentVirtualCD" => push_ram_disk(b, &mut p, Some(EFI_PERSISTENT_VIRTUAL_CD_GUID)),
+
+        "BBS" => {
+            let DeviceType = match p.next() {
+                "Floppy" => BBS_TYPE_FLOPPY,
+                "HD" => BBS_TYPE_HARDDRIVE,
+                "CDROM" => BBS_TYPE_CDROM,
+                "PCMCIA" => BBS_TYPE_PCMCIA,
+                "USB" => BBS_TYPE_USB,
+                "Network" => BBS_TYPE_EMBEDDED_NETWORK,
+                other => num(other)?,
+            };
+            let description = p.next();
+            let node = BBS_BBS_DEVICE_PATH {
+                Header: HEADER,
+                DeviceType,
+                StatusFlag: num(p.next())?,
+            };
+            b.push_raw(
+                BBS_DEVICE_PATH,
+                BBS_BBS_DP,
+                &[node_body(&node), description.as_bytes(), &[0]],
+            )
+            .map(drop)
+        }
+
+        // Like EDK2, text naming an unknown node is taken to be a file path.
+        _ => b.push_file_path(text).map(drop),
+    }
+}
+
+/// Splits a text device node of the form `Name(Parameters)` into its name and parameters.
+///
+/// Returns `None` for text with balanced parentheses that is not of that form, such as a file
+/// path, and [`EFI_INVALID_PARAMETER`] if the parentheses are unbalanced.
+///
+/// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+fn node_syntax(text: &str) -> Result<Option<(&str, &str)>, EFI_STATUS> {
+    let mut depth = 0usize;
+    let mut first_close = None;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth = depth.checked_sub(1).ok_or(EFI_INVALID_PARAMETER)?;
+                if depth == 0 && first_close.is_none() {
+                    first_close = Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return Err(EFI_INVALID_PARAMETER);
+    }
+
+    let open = match text.find('(') {
+        Some(open) => open,
+        None => return Ok(None),
+    };
+    let name = &text[..open];
+    if name.is_empty()
+        || !name.bytes().all(|c| c.is_ascii_alphanumeric())
+        || first_close != Some(text.len() - 1)
+    {
+        return Ok(None);
+    }
+
+    Ok(Some((name, &text[open + 1..text.len() - 1])))
+}
+
+/// The comma-separated parameters of a text device node. Missing parameters are returned as
+/// empty strings.
+struct Params<'t>(core::str::Split<'t, char>);
+
+impl<'t> Params<'t> {
+    fn next(&mut self) -> &'t str {
+        self.0.next().unwrap_or_default()
+    }
+}
+
+/// Splits `text` at every `separator` that is not enclosed in parentheses.
+fn split_top_level(text: &str, separator: char) -> impl Iterator<Item = &str> {
+    let mut rest = Some(text);
+    core::iter::from_fn(move || {
+        let text = rest?;
+        let mut depth = 0usize;
+        for (i, c) in text.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                c if c == separator && depth == 0 => {
+                    rest = Some(&text[i + c.len_utf8()..]);
+                    return Some(&text[..i]);
+                }
+                _ => {}
+            }
+        }
+        rest = None;
+        Some(text)
+    })
+}
+
+fn push_hex_node(
+    b: &mut DevicePathBuilder<'_>,
+    ty: UINT8,
+    sub_type: UINT8,
+    hex: &str,
+) -> Result<(), EFI_STATUS> {
+    if hex.len() % 2 != 0 {
+        return Err(EFI_INVALID_PARAMETER);
+    }
+
+    decode_hex(hex, b.reserve(ty, sub_type, hex.len() / 2)?)
+}
+
+fn push_vendor(
+    b: &mut DevicePathBuilder<'_>,
+    ty: UINT8,
+    sub_type: UINT8,
+    p: &mut Params<'_>,
+) -> Result<(), EFI_STATUS> {
+    let guid = guid(p.next())?;
+    let hex = p.next();
+    if hex.len() % 2 != 0 {
+        return Err(EFI_INVALID_PARAMETER);
+    }
+
+    let data = b.reserve(ty, sub_type, 16 + hex.len() / 2)?;
+    data[..16].copy_from_slice(&guid.to_bytes());
+    decode_hex(hex, &mut data[16..])
+}
+
+fn push_messaging_vendor(b: &mut DevicePathBuilder<'_>, Guid: EFI_GUID) -> Result<(), EFI_STATUS> {
+    b.push(&MESSAGING_VENDOR_DEVICE_PATH {
+        Header: HEADER,
+        Guid,
+    })
+    .map(drop)
+}
+
+fn push_acpi(b: &mut DevicePathBuilder<'_>, HID: u32, UID: u32) -> Result<(), EFI_STATUS> {
+    b.push(&ACPI_HID_DEVICE_PATH {
+        Header: HEADER,
+        HID,
+        UID,
+    })
+    .map(drop)
+}
+
+fn push_acpi_ex(
+    b: &mut DevicePathBuilder<'_>,
+    node: &ACPI_EXTENDED_HID_DEVICE_PATH,
+    hid: &str,
+    uid: &str,
+    cid: &str,
+) -> Result<(), EFI_STATUS> {
+    b.push_raw(
+        ACPI_DEVICE_PATH,
+        ACPI_EXTENDED_DP,
+        &[
+            node_body(node),
+            hid.as_bytes(),
+            &[0],
+            uid.as_bytes(),
+            &[0],
+            cid.as_bytes(),
+            &[0],
+        ],
+    )
+    .map(drop)
+}
+
+fn push_usb_class(
+    b: &mut DevicePathBuilder<'_>,
+    p: &mut Params<'_>,
+    class: Option<u8>,
+) -> Result<(), EFI_STATUS> {
+    b.push(&USB_CLASS_DEVICE_PATH {
+        Header: HEADER,
+        VendorId: num(p.next())?,
+        ProductId: num(p.next())?,
+        DeviceClass: match class {
+            Some(class) => class,
+            None => num(p.next())?,
+        },
+        DeviceSubClass: num(p.next())?,
+        DeviceProtocol: num(p.next())?,
+    })
+    .map(drop)
+}
+
+fn push_usb_application_class(
+    b: &mut DevicePathBuilder<'_>,
+    p: &mut Params<'_>,
+    sub_class: u8,
+) -> Result<(), EFI_STATUS> {
+    b.push(&USB_CLASS_DEVICE_PATH {
+        Header: HEADER,
+        VendorId: num(p.next())?,
+        ProductId: num(p.next())?,
+        DeviceClass: 0xFE,
+        DeviceSubClass: sub_class,
+        DeviceProtocol: num(p.next())?,
+    })
+    .map(drop)
+}
+
+fn push_ram_disk(
+    b: &mut DevicePathBuilder<'_>,
+    p: &mut Params<'_>,
+    type_guid: Option<EFI_GUID>,
+) -> Result<(), EFI_STATUS> {
+    let (StartingAddr, EndingAddr, Instance) = (num(p.next())?, num(p.next())?, num(p.next())?);
+    b.push(&MEDIA_RAM_DISK_DEVICE_PATH {
+        Header: HEADER,
+        StartingAddr,
+        EndingAddr,
+        TypeGuid: match type_guid {
+            Some(type_guid) => type_guid,
+            None => guid(p.next())?,
+        },
+        Instance,
+    })
+    .map(drop)
+}
+
+/// Parses the `Topology,Location,Connect,DriveBay` parameters of a SAS node.
+fn sas_topology(p: &mut Params<'_>) -> Result<u16, EFI_STATUS> {
+    let (kind, location, connect, drive_bay) = (p.next(), p.next(), p.next(), p.next());
+    let mut topology = match kind {
+        "NoTopology" => return Ok(0),
+        "SAS" | "SATA" => match num::<u16>(drive_bay)? {
+            0 => 0x1,
+            bay => 0x2 | ((bay - 1) << 8),
+        },
+        other => return num(other),
+    };
+
+    if kind == "SATA" {
+        topology |= 0x10;
+    }
+    topology |= match location {
+        "Internal" => 0,
+        "External" => 1,
+        other => num::<u16>(other)? & 0x1,
+    } << 5;
+    topology |= match connect {
+        "Direct" => 0,
+        "Expanded" => 1,
+        other => num::<u16>(other)? & 0x3,
+    } << 6;
+
+    Ok(topology)
+}
+
+/// Parses a number, in hexadecimal when prefixed with `0x` and in decimal otherwise. An empty
+/// string is parsed as zero.
+fn num<T: TryFrom<u64>>(text: &str) -> Result<T, EFI_STATUS> {
+    let value = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None if text.is_empty() => Ok(0),
+        None => text.parse(),
+    };
+
+    value
+        .ok()
+        .and_then(|value| T::try_from(value).ok())
+        .ok_or(EFI_INVALID_PARAMETER)
+}
+
+/// Parses a hexadecimal number without prefix.
+fn hex_num<T: TryFrom<u64>>(text: &str) -> Result<T, EFI_STATUS> {
+    u64::from_str_radix(text, 16)
+        .ok()
+        .and_then(|value| T::try_from(value).ok())
+        .ok_or(EFI_INVALID_PARAMETER)
+}
+
+/// Parses a parameter that is either `DEFAULT` or a number.
+fn default_or_num<T: TryFrom<u64>>(text: &str) -> Result<T, EFI_STATUS> {
+    num(if text == "DEFAULT" { "0" } else { text })
+}
+
+/// Parses a parameter that is either one of `keywords`, standing for its index, or a number.
+fn keyword<T: TryFrom<u64>>(text: &str, keywords: &[&str]) -> Result<T, EFI_STATUS> {
+    match keywords.iter().position(|&k| k == text) {
+        Some(index) => T::try_from(index as u64).map_err(|_| EFI_INVALID_PARAMETER),
+        None => num(text),
+    }
+}
+
+fn network_protocol(text: &str) -> Result<u16, EFI_STATUS> {
+    match text {
+        "TCP" => Ok(6),
+        "UDP" => Ok(17),
+        other => num(other),
+    }
+}
+
+/// Parses an EISA ID such as `PNP0A03`, or a number.
+fn eisa_id(text: &str) -> Result<u32, EFI_STATUS> {
+    let bytes = text.as_bytes();
+    if bytes.len() != 7 || !bytes[..3].iter().all(u8::is_ascii_uppercase) {
+        return num(text);
+    }
+
+    let vendor = bytes[..3]
+        .iter()
+        .fold(0, |acc, &c| (acc << 5) | u32::from(c - b'A' + 1));
+    Ok((hex_num::<u32>(&text[3..])? << 16) | vendor)
+}
+
+fn guid(text: &str) -> Result<EFI_GUID, EFI_STATUS> {
+    EFI_GUID::from_str(text)
+}
+
+fn ipv4(text: &str) -> Result<[u8; 4], EFI_STATUS> {
+    let mut address = [0; 4];
+    if text.is_empty() {
+        return Ok(address);
+    }
+
+    let mut parts = text.split('.');
+    for byte in &mut address {
+        *byte = parts
+            .next()
+            .and_then(|part| part.parse().ok())
+            .ok_or(EFI_INVALID_PARAMETER)?;
+    }
+
+    match parts.next() {
+        Some(_) => Err(EFI_INVALID_PARAMETER),
+        None => Ok(address),
+    }
+}
+
+fn ipv6(text: &str) -> Result<[u8; 16], EFI_STATUS> {
+    let mut address = [0; 16];
+    if text.is_empty() {
+        return Ok(address);
+    }
+
+    let (head, tail) = match text.find("::") {
+        Some(i) => (&text[..i], Some(&text[i + 2..])),
+        None => (text, None),
+    };
+    let groups = |part: &str| {
+        part.split(':')
+            .filter(|g| !part.is_empty() || !g.is_empty())
+            .count()
+    };
+    let (head_len, tail_len) = (groups(head), tail.map_or(0, groups));
+    if head_len + tail_len > 8 || (tail.is_none() && head_len != 8) {
+        return Err(EFI_INVALID_PARAMETER);
+    }
+
+    let mut write = |part: &str, start: usize| -> Result<(), EFI_STATUS> {
+        for (i, group) in part
+            .split(':')
+            .filter(|g| !part.is_empty() || !g.is_empty())
+            .enumerate()
+        {
+            if group.len() > 4 {
+                return Err(EFI_INVALID_PARAMETER);
+            }
+            let value = hex_num::<u16>(group)?;
+            address[(start + i) * 2..(start + i) * 2 + 2].copy_from_slice(&value.to_be_bytes());
+        }
+        Ok(())
+    };
+    write(head, 0)?;
+    if let Some(tail) = tail {
+        write(tail, 8 - tail_len)?;
+    }
+
+    Ok(address)
+}
+
+/// Decodes a string of hexadecimal digit pairs into `bytes`, which must be exactly large enough.
+fn decode_hex(text: &str, bytes: &mut [u8]) -> Result<(), EFI_STATUS> {
+    if text.len() != bytes.len() * 2 {
+        return Err(EFI_INVALID_PARAMETER);
+    }
+
+    for (byte, pair) in bytes.iter_mut().zip(text.as_bytes().chunks_exact(2)) {
+        let pair = core::str::from_utf8(pair).map_err(|_| EFI_INVALID_PARAMETER)?;
+        *byte = hex_num(pair)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the size of `text` encoded in UCS-2, without a null terminator.
+fn ucs2_len(text: &str) -> Result<usize, EFI_STATUS> {
+    if text.chars().any(|c| c as u32 > 0xFFFF) {
+        return Err(EFI_INVALID_PARAMETER);
+    }
+
+    Ok(text.encode_utf16().count() * core::mem::size_of::<CHAR16>())
+}
+
+fn write_ucs2(bytes: &mut [u8], text: &str) {
+    for (chunk, c) in bytes.chunks_exact_mut(2).zip(text.encode_utf16()) {
+        chunk.copy_from_slice(&c.to_le_bytes());
+    }
+}
+
+type EFI_DEVICE_PATH_FROM_TEXT_NODE =
+    extern "efiapi" fn(TextDeviceNode: *const CHAR16) -> *mut EFI_DEVICE_PATH_PROTOCOL;
+
+type EFI_DEVICE_PATH_FROM_TEXT_PATH =
+    extern "efiapi" fn(TextDevicePath: *const CHAR16) -> *mut EFI_DEVICE_PATH_PROTOCOL;
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::format;
+
+    use super::*;
+
+    fn round_trip(Text: &str) -> std::string::String {
+        let mut buffer = [0; 256];
+        let mut builder = DevicePathBuilder::new(&mut buffer);
+        builder.push_text(Text).unwrap();
+        format!("{}", builder.finish().unwrap().display(false))
+    }
+
+    fn rejects(Text: &str) -> bool {
+        let mut buffer = [0; 256];
+        DevicePathBuilder::new(&mut buffer).push_text(Text).err() == Some(EFI_INVALID_PARAMETER)
+    }
+
+    #[test]
+    fn ipv4_prints_gateway_and_subnet_mask_by_node_length() {
+        assert_eq!(
+            round_trip("IPv4(192.168.0.1,TCP,DHCP,192.168.0.2)"),
+            "IPv4(192.168.0.1,TCP,DHCP,192.168.0.2,0.0.0.0,0.0.0.0)"
+        );
+        assert_eq!(
+            round_trip("IPv4(192.168.0.1,UDP,Static,192.168.0.2,192.168.0.254,255.255.255.0)"),
+            "IPv4(192.168.0.1,UDP,Static,192.168.0.2,192.168.0.254,255.255.255.0)"
+        );
+        assert_eq!(
+            round_trip("IPv4(192.168.0.1,UDP,Static,192.168.0.2,0.0.0.0,255.255.255.0)"),
+            "IPv4(192.168.0.1,UDP,Static,192.168.0.2,0.0.0.0,255.255.255.0)"
+        );
+        // A UEFI 2.0 node, which ends after the StaticIpAddress field.
+        assert_eq!(
+            round_trip("Msg(12,C0A80002C0A8000100000000060000)"),
+            "IPv4(192.168.0.1,TCP,DHCP,192.168.0.2)"
+        );
+    }
+
+    #[test]
+    fn file_path_without_node_syntax() {
+        assert_eq!(
+            round_trip("PciRoot(0x0)/Pci(0x1,0x1)/\\EFI\\BOOT\\BOOTX64.EFI"),
+            "PciRoot(0x0)/Pci(0x1,0x1)/\\EFI\\BOOT\\BOOTX64.EFI"
+        );
+        assert_eq!(round_trip("\\setup(1).efi"), "\\setup(1).efi");
+    }
+
+    #[test]
+    fn unknown_node_name_is_file_path() {
+        assert_eq!(round_trip("NotANode(0x1)"), "NotANode(0x1)");
+        assert_eq!(
+            round_trip("PciRoot(0x0)/Pci(0x1,0x1)/BOOTX64(1)/Pci(0x2,0x0)"),
+            "PciRoot(0x0)/Pci(0x1,0x1)/BOOTX64(1)/Pci(0x2,0x0)"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_node_syntax() {
+        assert!(rejects("Pci(0x1,0x1"));
+        assert!(rejects("PciRoot(0x0)/Pci(0x1,0x1"));
+        assert!(rejects("Pci(0x1,0x1))"));
+        assert!(rejects("Pci0x1,0x1)"));
+    }
+}
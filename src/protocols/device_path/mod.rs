@@ -0,0 +1,628 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Device Path Protocol
+//!
+//! This module defines the Device Path Protocol, also known as the [`EFI_DEVICE_PATH_PROTOCOL`],
+//! along with the nodes a device path is made of.
+//!
+//! A device path is a packed sequence of variable-length nodes, each starting with an
+//! [`EFI_DEVICE_PATH_PROTOCOL`] header that gives the type, sub-type and total length of the node.
+//! A device path is terminated by an end of entire device path node, and may contain several
+//! instances separated by end of instance nodes. The nodes are not aligned, so every node type
+//! in [`nodes`] is a `#[repr(C, packed)]` structure. See [Section 10 of the UEFI Specification]
+//! for more details.
+//!
+//! Packed device paths are walked with [`DevicePathNodes`], which validates the length of every
+//! node against the available data, and validated device paths are represented by [`DevicePath`].
+//! New device paths are assembled into a caller-supplied buffer with [`DevicePathBuilder`].
+//!
+//! The textual representation of device paths, such as
+//! `PciRoot(0x0)/Pci(0x1F,0x2)/Sata(0x0,0xFFFF,0x0)/HD(1,GPT,...)`, is produced by
+//! [`DevicePath::display()`] and parsed by [`DevicePathBuilder::push_text()`], independently of the
+//! [`EFI_DEVICE_PATH_TO_TEXT_PROTOCOL`] and [`EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL`] that firmware
+//! may provide. Both follow the conventions of the EDK II implementation of these protocols, so
+//! the text is the same whichever is used.
+//!
+//! [`EFI_DEVICE_PATH_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_PROTOCOL
+//! [`nodes`]: crate::protocols::device_path::nodes
+//! [`DevicePathNodes`]: crate::protocols::device_path::DevicePathNodes
+//! [`DevicePath`]: crate::protocols::device_path::DevicePath
+//! [`DevicePathBuilder`]: crate::protocols::device_path::DevicePathBuilder
+//! [`DevicePath::display()`]: ./struct.DevicePath.html#method.display
+//! [`DevicePathBuilder::push_text()`]: ./struct.DevicePathBuilder.html#method.push_text
+//! [`EFI_DEVICE_PATH_TO_TEXT_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_TO_TEXT_PROTOCOL
+//! [`EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL
+//!
+//! [Section 10 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/10_Protocols_Device_Path_Protocol.html
+
+use core::iter::FusedIterator;
+use core::{mem, slice};
+
+use crate::protocols::Protocol;
+use crate::status::{EFI_BUFFER_TOO_SMALL, EFI_INVALID_PARAMETER};
+use crate::types::{CHAR16, EFI_GUID, EFI_STATUS, UINT16, UINT8};
+
+pub mod from_text;
+pub mod nodes;
+pub mod to_text;
+pub mod utilities;
+
+pub use from_text::EFI_DEVICE_PATH_FROM_TEXT_PROTOCOL;
+pub use nodes::DevicePathNodeKind;
+pub use to_text::EFI_DEVICE_PATH_TO_TEXT_PROTOCOL;
+pub use utilities::EFI_DEVICE_PATH_UTILITIES_PROTOCOL;
+
+/// GUID for the [`EFI_DEVICE_PATH_PROTOCOL`].
+///
+/// [`EFI_DEVICE_PATH_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_PROTOCOL
+pub const EFI_DEVICE_PATH_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0x09576E91,
+    0x6D3F,
+    0x11D2,
+    [0x8E, 0x39, 0x00, 0xA0, 0xC9, 0x69, 0x72, 0x3B],
+);
+
+/// The header of every device path node.
+///
+/// The Device Path Protocol can be used on any device handle to obtain generic path/location
+/// information concerning the physical device or logical device. If the handle does not logically
+/// map to a physical device, the handle may not necessarily support the device path protocol.
+///
+/// The header is immediately followed by the type-specific data of the node, up to a total of
+/// [`Length`] bytes.
+///
+/// [`Length`]: ./struct.EFI_DEVICE_PATH_PROTOCOL.html#structfield.Length
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_DEVICE_PATH_PROTOCOL {
+    /// The type of the device path node, such as [`HARDWARE_DEVICE_PATH`].
+    ///
+    /// [`HARDWARE_DEVICE_PATH`]: crate::protocols::device_path::HARDWARE_DEVICE_PATH
+    pub Type: UINT8,
+    /// The sub-type of the device path node, whose meaning depends on [`Type`].
+    ///
+    /// [`Type`]: ./struct.EFI_DEVICE_PATH_PROTOCOL.html#structfield.Type
+    pub SubType: UINT8,
+    /// The length of the device path node in bytes, including the header, in little-endian
+    /// byte order.
+    pub Length: [UINT8; 2],
+}
+
+unsafe impl Protocol for EFI_DEVICE_PATH_PROTOCOL {
+    const GUID: EFI_GUID = EFI_DEVICE_PATH_PROTOCOL_GUID;
+}
+
+impl EFI_DEVICE_PATH_PROTOCOL {
+    /// Creates a device path node header.
+    pub const fn new(Type: UINT8, SubType: UINT8, Length: UINT16) -> Self {
+        Self {
+            Type,
+            SubType,
+            Length: Length.to_le_bytes(),
+        }
+    }
+
+    /// Returns the length of the device path node in bytes, including the header.
+    pub const fn length(&self) -> UINT16 {
+        UINT16::from_le_bytes(self.Length)
+    }
+}
+
+/// Hardware Device Path. Defines how a device is attached to the resource domain of a system.
+pub const HARDWARE_DEVICE_PATH: UINT8 = 0x01;
+/// ACPI Device Path. Used to describe devices whose enumeration is not described in an
+/// industry-standard fashion.
+pub const ACPI_DEVICE_PATH: UINT8 = 0x02;
+/// Messaging Device Path. Used to describe the connection of devices outside the resource domain
+/// of the system.
+pub const MESSAGING_DEVICE_PATH: UINT8 = 0x03;
+/// Media Device Path. Used to describe the portion of a medium that is being abstracted by a boot
+/// service.
+pub const MEDIA_DEVICE_PATH: UINT8 = 0x04;
+/// BIOS Boot Specification Device Path. Used to point to boot legacy operating systems.
+pub const BBS_DEVICE_PATH: UINT8 = 0x05;
+/// End of Hardware Device Path.
+pub const END_DEVICE_PATH_TYPE: UINT8 = 0x7F;
+
+pub const HW_PCI_DP: UINT8 = 0x01;
+pub const HW_PCCARD_DP: UINT8 = 0x02;
+pub const HW_MEMMAP_DP: UINT8 = 0x03;
+pub const HW_VENDOR_DP: UINT8 = 0x04;
+pub const HW_CONTROLLER_DP: UINT8 = 0x05;
+pub const HW_BMC_DP: UINT8 = 0x06;
+
+pub const ACPI_DP: UINT8 = 0x01;
+pub const ACPI_EXTENDED_DP: UINT8 = 0x02;
+pub const ACPI_ADR_DP: UINT8 = 0x03;
+pub const ACPI_NVDIMM_DP: UINT8 = 0x04;
+
+pub const MSG_ATAPI_DP: UINT8 = 0x01;
+pub const MSG_SCSI_DP: UINT8 = 0x02;
+pub const MSG_FIBRECHANNEL_DP: UINT8 = 0x03;
+pub const MSG_1394_DP: UINT8 = 0x04;
+pub const MSG_USB_DP: UINT8 = 0x05;
+pub const MSG_I2O_DP: UINT8 = 0x06;
+pub const MSG_INFINIBAND_DP: UINT8 = 0x09;
+pub const MSG_VENDOR_DP: UINT8 = 0x0A;
+pub const MSG_MAC_ADDR_DP: UINT8 = 0x0B;
+pub const MSG_IPv4_DP: UINT8 = 0x0C;
+pub const MSG_IPv6_DP: UINT8 = 0x0D;
+pub const MSG_UART_DP: UINT8 = 0x0E;
+pub const MSG_USB_CLASS_DP: UINT8 = 0x0F;
+pub const MSG_USB_WWID_DP: UINT8 = 0x10;
+pub const MSG_DEVICE_LOGICAL_UNIT_DP: UINT8 = 0x11;
+pub const MSG_SATA_DP: UINT8 = 0x12;
+pub const MSG_ISCSI_DP: UINT8 = 0x13;
+pub const MSG_VLAN_DP: UINT8 = 0x14;
+pub const MSG_FIBRECHANNELEX_DP: UINT8 = 0x15;
+pub const MSG_SASEX_DP: UINT8 = 0x16;
+pub const MSG_NVME_NAMESPACE_DP: UINT8 = 0x17;
+pub const MSG_URI_DP: UINT8 = 0x18;
+pub const MSG_UFS_DP: UINT8 = 0x19;
+pub const MSG_SD_DP: UINT8 = 0x1A;
+pub const MSG_BLUETOOTH_DP: UINT8 = 0x1B;
+pub const MSG_WIFI_DP: UINT8 = 0x1C;
+pub const MSG_EMMC_DP: UINT8 = 0x1D;
+pub const MSG_BLUETOOTH_LE_DP: UINT8 = 0x1E;
+pub const MSG_DNS_DP: UINT8 = 0x1F;
+pub const MSG_NVDIMM_NAMESPACE_DP: UINT8 = 0x20;
+pub const MSG_REST_SERVICE_DP: UINT8 = 0x21;
+pub const MSG_NVME_OF_NAMESPACE_DP: UINT8 = 0x22;
+
+pub const MEDIA_HARDDRIVE_DP: UINT8 = 0x01;
+pub const MEDIA_CDROM_DP: UINT8 = 0x02;
+pub const MEDIA_VENDOR_DP: UINT8 = 0x03;
+pub const MEDIA_FILEPATH_DP: UINT8 = 0x04;
+pub const MEDIA_PROTOCOL_DP: UINT8 = 0x05;
+pub const MEDIA_PIWG_FW_FILE_DP: UINT8 = 0x06;
+pub const MEDIA_PIWG_FW_VOL_DP: UINT8 = 0x07;
+pub const MEDIA_RELATIVE_OFFSET_RANGE_DP: UINT8 = 0x08;
+pub const MEDIA_RAM_DISK_DP: UINT8 = 0x09;
+
+pub const BBS_BBS_DP: UINT8 = 0x01;
+
+/// End This Instance of a Device Path and start a new Device Path.
+pub const END_INSTANCE_DEVICE_PATH_SUBTYPE: UINT8 = 0x01;
+/// End Entire Device Path.
+pub const END_ENTIRE_DEVICE_PATH_SUBTYPE: UINT8 = 0xFF;
+
+const HEADER_SIZE: usize = mem::size_of::<EFI_DEVICE_PATH_PROTOCOL>();
+
+/// A device path node structure with a fixed type and sub-type, such as [`PCI_DEVICE_PATH`].
+///
+/// # Safety
+///
+/// Implementors must be `#[repr(C, packed)]` plain data structures starting with an
+/// [`EFI_DEVICE_PATH_PROTOCOL`] header, valid for any bit pattern.
+///
+/// [`PCI_DEVICE_PATH`]: crate::protocols::device_path::nodes::PCI_DEVICE_PATH
+/// [`EFI_DEVICE_PATH_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_PROTOCOL
+pub unsafe trait DevicePathNodeStruct: Copy {
+    /// The [`Type`] of the node.
+    ///
+    /// [`Type`]: ./struct.EFI_DEVICE_PATH_PROTOCOL.html#structfield.Type
+    const TYPE: UINT8;
+    /// The [`SubType`] of the node.
+    ///
+    /// [`SubType`]: ./struct.EFI_DEVICE_PATH_PROTOCOL.html#structfield.SubType
+    const SUB_TYPE: UINT8;
+}
+
+/// Returns the bytes of a node structure following its header.
+fn node_body<T: DevicePathNodeStruct>(Node: &T) -> &[UINT8] {
+    // SAFETY: `T` is plain packed data, so all of its bytes are initialized.
+    let node =
+        unsafe { slice::from_raw_parts((Node as *const T).cast::<UINT8>(), mem::size_of::<T>()) };
+    &node[HEADER_SIZE..]
+}
+
+/// A single node of a device path, whose length has been checked against the data it was read
+/// from.
+#[derive(Clone, Copy)]
+pub struct DevicePathNode<'a> {
+    bytes: &'a [UINT8],
+}
+
+impl<'a> DevicePathNode<'a> {
+    /// Reads the device path node at the start of `Data`.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if `Data` is shorter than a node header, or the length of
+    /// the node is shorter than its header or longer than `Data`.
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub fn new(Data: &'a [UINT8]) -> Result<Self, EFI_STATUS> {
+        if Data.len() < HEADER_SIZE {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        let len = UINT16::from_le_bytes([Data[2], Data[3]]) as usize;
+        if len < HEADER_SIZE || len > Data.len() {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        Ok(Self {
+            bytes: &Data[..len],
+        })
+    }
+
+    /// Returns the [`Type`] of the node.
+    ///
+    /// [`Type`]: ./struct.EFI_DEVICE_PATH_PROTOCOL.html#structfield.Type
+    pub fn node_type(&self) -> UINT8 {
+        self.bytes[0]
+    }
+
+    /// Returns the [`SubType`] of the node.
+    ///
+    /// [`SubType`]: ./struct.EFI_DEVICE_PATH_PROTOCOL.html#structfield.SubType
+    pub fn sub_type(&self) -> UINT8 {
+        self.bytes[1]
+    }
+
+    /// Returns the length of the node in bytes, including its header.
+    pub fn length(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns the type-specific data of the node, following its header.
+    pub fn data(&self) -> &'a [UINT8] {
+        &self.bytes[HEADER_SIZE..]
+    }
+
+    /// Returns the whole node, including its header.
+    pub fn as_bytes(&self) -> &'a [UINT8] {
+        self.bytes
+    }
+
+    /// Returns `true` if this is an end of instance or end of entire device path node.
+    pub fn is_end(&self) -> bool {
+        self.node_type() == END_DEVICE_PATH_TYPE
+    }
+
+    /// Returns `true` if this is an end of entire device path node.
+    pub fn is_end_entire(&self) -> bool {
+        self.is_end() && self.sub_type() == END_ENTIRE_DEVICE_PATH_SUBTYPE
+    }
+
+    /// Returns `true` if this is an end of instance node.
+    pub fn is_end_instance(&self) -> bool {
+        self.is_end() && self.sub_type() == END_INSTANCE_DEVICE_PATH_SUBTYPE
+    }
+
+    /// Reinterprets the node as the node structure `T`, if its type and sub-type match and it is
+    /// long enough to hold `T`.
+    ///
+    /// Any data following the structure within the node is available through [`trailing()`].
+    ///
+    /// [`trailing()`]: ./struct.DevicePathNode.html#method.trailing
+    pub fn cast<T: DevicePathNodeStruct>(&self) -> Option<&'a T> {
+        if self.node_type() != T::TYPE
+            || self.sub_type() != T::SUB_TYPE
+            || self.bytes.len() < mem::size_of::<T>()
+        {
+            return None;
+        }
+
+        // SAFETY: `T` is packed, so has an alignment of 1, is valid for any bit pattern, and the
+        // node has been checked to be large enough.
+        Some(unsafe { &*self.bytes.as_ptr().cast::<T>() })
+    }
+
+    /// Returns the variable-length data following the node structure `T` within the node.
+    pub fn trailing<T: DevicePathNodeStruct>(&self) -> &'a [UINT8] {
+        self.bytes.get(mem::size_of::<T>()..).unwrap_or(&[])
+    }
+
+    /// Decodes the node into its typed representation.
+    ///
+    /// Nodes of unknown types, or too short for their type, are returned as
+    /// [`DevicePathNodeKind::Unknown`].
+    ///
+    /// [`DevicePathNodeKind::Unknown`]: crate::protocols::device_path::DevicePathNodeKind::Unknown
+    pub fn kind(&self) -> DevicePathNodeKind<'a> {
+        DevicePathNodeKind::new(*self)
+    }
+}
+
+/// A bounds-checked iterator over the nodes of a packed device path.
+///
+/// Iteration ends after the end of entire device path node, which is not yielded itself; end of
+/// instance nodes are yielded so that the instances of a multi-instance device path can be told
+/// apart. If a node is malformed, or the data ends without an end of entire device path node,
+/// [`EFI_INVALID_PARAMETER`] is yielded as the final item.
+///
+/// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+#[derive(Clone)]
+pub struct DevicePathNodes<'a> {
+    remaining: &'a [UINT8],
+    done: bool,
+}
+
+impl<'a> DevicePathNodes<'a> {
+    /// Creates an iterator over the nodes of the packed device path in `Data`.
+    pub fn new(Data: &'a [UINT8]) -> Self {
+        Self {
+            remaining: Data,
+            done: false,
+        }
+    }
+
+    /// Returns the data following the last node returned, or following the end of entire device
+    /// path node once iteration has completed.
+    pub fn remaining(&self) -> &'a [UINT8] {
+        self.remaining
+    }
+}
+
+impl<'a> Iterator for DevicePathNodes<'a> {
+    type Item = Result<DevicePathNode<'a>, EFI_STATUS>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match DevicePathNode::new(self.remaining) {
+            Ok(node) => {
+                self.remaining = &self.remaining[node.length()..];
+                if node.is_end_entire() {
+                    self.done = true;
+                    return None;
+                }
+
+                Some(Ok(node))
+            }
+            Err(status) => {
+                self.done = true;
+                Some(Err(status))
+            }
+        }
+    }
+}
+
+impl FusedIterator for DevicePathNodes<'_> {}
+
+/// A validated packed device path, terminated by an end of entire device path node.
+#[derive(Clone, Copy)]
+pub struct DevicePath<'a> {
+    bytes: &'a [UINT8],
+}
+
+impl<'a> DevicePath<'a> {
+    /// Validates the packed device path at the start of `Data`.
+    ///
+    /// Any data following the end of entire device path node is ignored, so this can be used
+    /// on, for example, the `FilePathList` of a load option containing several device paths.
+    /// Returns [`EFI_INVALID_PARAMETER`] if a node is malformed or no end of entire device path
+    /// node is found.
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub fn new(Data: &'a [UINT8]) -> Result<Self, EFI_STATUS> {
+        let mut nodes = DevicePathNodes::new(Data);
+        for node in nodes.by_ref() {
+            node?;
+        }
+
+        let len = Data.len() - nodes.remaining().len();
+        Ok(Self {
+            bytes: &Data[..len],
+        })
+    }
+
+    /// Creates a device path from a pointer to its first node, such as one returned by firmware.
+    ///
+    /// # Safety
+    ///
+    /// `DevicePath` must point to a well-formed device path terminated by an end of entire device
+    /// path node, which must remain valid and unmodified for the lifetime `'a`.
+    pub unsafe fn from_ptr(DevicePath: *const EFI_DEVICE_PATH_PROTOCOL) -> Self {
+        let start = DevicePath.cast::<UINT8>();
+        let mut len = 0;
+        loop {
+            let node = &*start.add(len).cast::<EFI_DEVICE_PATH_PROTOCOL>();
+            len += node.length() as usize;
+            if node.Type == END_DEVICE_PATH_TYPE && node.SubType == END_ENTIRE_DEVICE_PATH_SUBTYPE {
+                break;
+            }
+        }
+
+        Self {
+            bytes: slice::from_raw_parts(start, len),
+        }
+    }
+
+    /// Returns an iterator over the nodes of the device path, excluding the final end of entire
+    /// device path node. The end of instance nodes separating the instances of a multi-instance
+    /// device path are included.
+    pub fn nodes(&self) -> impl Iterator<Item = DevicePathNode<'a>> + Clone + 'a {
+        DevicePathNodes::new(self.bytes).filter_map(Result::ok)
+    }
+
+    /// Returns the packed device path, including the end of entire device path node.
+    pub fn as_bytes(&self) -> &'a [UINT8] {
+        self.bytes
+    }
+
+    /// Returns a pointer to the first node of the device path, suitable to be passed to firmware.
+    pub fn as_ptr(&self) -> *const EFI_DEVICE_PATH_PROTOCOL {
+        self.bytes.as_ptr().cast()
+    }
+
+    /// Returns the size of the device path in bytes, including the end of entire device path
+    /// node.
+    pub fn size(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+/// Assembles a device path into a caller-supplied buffer.
+///
+/// Nodes are appended one after another; the header of each node is filled in by the builder, so
+/// the `Header` field of node structures passed to it may be left zeroed. [`finish()`] appends
+/// the end of entire device path node and returns the resulting [`DevicePath`].
+///
+/// Once an append fails with [`EFI_BUFFER_TOO_SMALL`], the builder is left unchanged, so the
+/// caller may retry with a larger buffer.
+///
+/// [`finish()`]: ./struct.DevicePathBuilder.html#method.finish
+/// [`DevicePath`]: crate::protocols::device_path::DevicePath
+/// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+pub struct DevicePathBuilder<'a> {
+    buffer: &'a mut [UINT8],
+    len: usize,
+}
+
+impl<'a> DevicePathBuilder<'a> {
+    /// Creates a builder writing into `Buffer`.
+    pub fn new(Buffer: &'a mut [UINT8]) -> Self {
+        Self {
+            buffer: Buffer,
+            len: 0,
+        }
+    }
+
+    /// Appends a fixed-size node.
+    pub fn push<T: DevicePathNodeStruct>(&mut self, Node: &T) -> Result<&mut Self, EFI_STATUS> {
+        self.push_with_data(Node, &[])
+    }
+
+    /// Appends a node made of the node structure `T` followed by variable-length data, such as
+    /// the path name of a [`FILEPATH_DEVICE_PATH`].
+    ///
+    /// [`FILEPATH_DEVICE_PATH`]: crate::protocols::device_path::nodes::FILEPATH_DEVICE_PATH
+    pub fn push_with_data<T: DevicePathNodeStruct>(
+        &mut self,
+        Node: &T,
+        Data: &[UINT8],
+    ) -> Result<&mut Self, EFI_STATUS> {
+        self.push_raw(T::TYPE, T::SUB_TYPE, &[node_body(Node), Data])
+    }
+
+    /// Appends a [`FILEPATH_DEVICE_PATH`] node with the given path name, such as
+    /// `\EFI\BOOT\BOOTX64.EFI`.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if the path name contains characters that cannot be
+    /// represented in UCS-2.
+    ///
+    /// [`FILEPATH_DEVICE_PATH`]: crate::protocols::device_path::nodes::FILEPATH_DEVICE_PATH
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub fn push_file_path(&mut self, PathName: &str) -> Result<&mut Self, EFI_STATUS> {
+        if PathName.chars().any(|c| c as u32 > 0xFFFF) {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        let len = (PathName.encode_utf16().count() + 1) * mem::size_of::<CHAR16>();
+        let data = self.reserve(MEDIA_DEVICE_PATH, MEDIA_FILEPATH_DP, len)?;
+        for (chunk, c) in data
+            .chunks_exact_mut(2)
+            .zip(PathName.encode_utf16().chain([0]))
+        {
+            chunk.copy_from_slice(&c.to_le_bytes());
+        }
+
+        Ok(self)
+    }
+
+    /// Appends an existing node, such as one read from another device path.
+    pub fn push_node(&mut self, Node: DevicePathNode<'_>) -> Result<&mut Self, EFI_STATUS> {
+        self.push_raw(Node.node_type(), Node.sub_type(), &[Node.data()])
+    }
+
+    /// Appends every node of an existing device path, excluding its end of entire device path
+    /// node.
+    pub fn push_path(&mut self, Path: DevicePath<'_>) -> Result<&mut Self, EFI_STATUS> {
+        let len = self.len;
+        for node in Path.nodes() {
+            if let Err(status) = self.push_node(node) {
+                self.len = len;
+                return Err(status);
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Appends an end of instance node, starting a new instance of a multi-instance device path.
+    pub fn push_end_instance(&mut self) -> Result<&mut Self, EFI_STATUS> {
+        self.push_raw(END_DEVICE_PATH_TYPE, END_INSTANCE_DEVICE_PATH_SUBTYPE, &[])
+    }
+
+    /// Appends a node from its type, sub-type and data following the header.
+    pub fn push_raw(
+        &mut self,
+        Type: UINT8,
+        SubType: UINT8,
+        Data: &[&[UINT8]],
+    ) -> Result<&mut Self, EFI_STATUS> {
+        let len = Data.iter().map(|d| d.len()).sum::<usize>();
+        let node = self.reserve(Type, SubType, len)?;
+
+        let mut offset = 0;
+        for part in Data {
+            node[offset..offset + part.len()].copy_from_slice(part);
+            offset += part.len();
+        }
+
+        Ok(self)
+    }
+
+    /// Appends the header of a node whose data is `len` bytes long, and returns the data area to
+    /// be filled in by the caller.
+    fn reserve(
+        &mut self,
+        Type: UINT8,
+        SubType: UINT8,
+        len: usize,
+    ) -> Result<&mut [UINT8], EFI_STATUS> {
+        let len = HEADER_SIZE + len;
+        let Length = UINT16::try_from(len).map_err(|_| EFI_INVALID_PARAMETER)?;
+        let node = self
+            .buffer
+            .get_mut(self.len..self.len + len)
+            .ok_or(EFI_BUFFER_TOO_SMALL)?;
+
+        node[0] = Type;
+        node[1] = SubType;
+        node[2..4].copy_from_slice(&Length.to_le_bytes());
+
+        self.len += len;
+        Ok(&mut node[HEADER_SIZE..])
+    }
+
+    /// Returns the number of bytes written so far, excluding the end of entire device path node
+    /// appended by [`finish()`].
+    ///
+    /// [`finish()`]: ./struct.DevicePathBuilder.html#method.finish
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no node has been appended yet.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends the end of entire device path node and returns the completed device path.
+    pub fn finish(mut self) -> Result<DevicePath<'a>, EFI_STATUS> {
+        self.push_raw(END_DEVICE_PATH_TYPE, END_ENTIRE_DEVICE_PATH_SUBTYPE, &[])?;
+        let buffer: &'a [UINT8] = self.buffer;
+        Ok(DevicePath {
+            bytes: &buffer[..self.len],
+        })
+    }
+}
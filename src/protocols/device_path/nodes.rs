@@ -0,0 +1,903 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # Device Path Nodes
+//!
+//! This module defines the structures of the individual device path nodes, as described in
+//! [Section 10.3 of the UEFI Specification], and the [`DevicePathNodeKind`] enumeration used to
+//! access them in a typed manner.
+//!
+//! Every structure starts with an [`EFI_DEVICE_PATH_PROTOCOL`] header and is `#[repr(C, packed)]`,
+//! since device path nodes are not aligned. Fields must therefore be copied out of a node before
+//! taking a reference to them. Nodes with a variable-length part, such as [`FILEPATH_DEVICE_PATH`],
+//! only define their fixed-size part; the rest of the node is returned alongside the structure by
+//! [`DevicePathNodeKind`].
+//!
+//! [`DevicePathNodeKind`]: crate::protocols::device_path::nodes::DevicePathNodeKind
+//! [`EFI_DEVICE_PATH_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_PROTOCOL
+//! [`FILEPATH_DEVICE_PATH`]: crate::protocols::device_path::nodes::FILEPATH_DEVICE_PATH
+//!
+//! [Section 10.3 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/10_Protocols_Device_Path_Protocol.html#device-path-nodes
+
+use crate::protocols::device_path::*;
+use crate::types::{
+    EFI_IPv4_ADDRESS, EFI_IPv6_ADDRESS, BOOLEAN, EFI_GUID, EFI_MAC_ADDRESS, UINT16, UINT32, UINT64,
+    UINT8,
+};
+
+macro_rules! device_path_node {
+    ($($(#[$meta:meta])* $name:ident($ty:expr, $sub:expr) { $($(#[$fmeta:meta])* $field:ident: $fty:ty,)* })*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Clone, Copy)]
+            #[repr(C, packed)]
+            pub struct $name {
+                /// The header of the node.
+                pub Header: EFI_DEVICE_PATH_PROTOCOL,
+                $($(#[$fmeta])* pub $field: $fty,)*
+            }
+
+            unsafe impl DevicePathNodeStruct for $name {
+                const TYPE: UINT8 = $ty;
+                const SUB_TYPE: UINT8 = $sub;
+            }
+        )*
+    };
+}
+
+/// Converts a 16-bit PNP identifier into the compressed EISA identifier used in the `HID` field of
+/// ACPI device path nodes.
+pub const fn EISA_PNP_ID(PnpId: UINT32) -> UINT32 {
+    (PnpId << 16) | 0x41D0
+}
+
+/// Extracts the 16-bit product identifier from a compressed EISA identifier.
+pub const fn EISA_ID_TO_NUM(EisaId: UINT32) -> UINT32 {
+    EisaId >> 16
+}
+
+/// Returns `true` if a compressed EISA identifier is a PNP identifier.
+pub const fn IS_PNP_ID(EisaId: UINT32) -> bool {
+    EisaId & 0xFFFF == 0x41D0
+}
+
+/// PNP identifier of a PCI root bridge.
+pub const PNP_PCI_ROOT: UINT32 = 0x0A03;
+/// PNP identifier of a PCI Express root bridge.
+pub const PNP_PCIE_ROOT: UINT32 = 0x0A08;
+
+/// The partition is described by a legacy MBR partition record.
+pub const MBR_TYPE_PCAT: UINT8 = 0x01;
+/// The partition is described by a GUID Partition Table entry.
+pub const MBR_TYPE_EFI_PARTITION_TABLE_HEADER: UINT8 = 0x02;
+
+/// No disk signature.
+pub const NO_DISK_SIGNATURE: UINT8 = 0x00;
+/// The disk signature is the 32-bit MBR signature, at offset `0x1B8` of the MBR.
+pub const SIGNATURE_TYPE_MBR: UINT8 = 0x01;
+/// The disk signature is the GUID of the GPT partition.
+pub const SIGNATURE_TYPE_GUID: UINT8 = 0x02;
+
+/// BIOS Boot Specification device type of a floppy drive.
+pub const BBS_TYPE_FLOPPY: UINT16 = 0x01;
+/// BIOS Boot Specification device type of a hard drive.
+pub const BBS_TYPE_HARDDRIVE: UINT16 = 0x02;
+/// BIOS Boot Specification device type of a CD-ROM drive.
+pub const BBS_TYPE_CDROM: UINT16 = 0x03;
+/// BIOS Boot Specification device type of a PCMCIA device.
+pub const BBS_TYPE_PCMCIA: UINT16 = 0x04;
+/// BIOS Boot Specification device type of a USB device.
+pub const BBS_TYPE_USB: UINT16 = 0x05;
+/// BIOS Boot Specification device type of an embedded network device.
+pub const BBS_TYPE_EMBEDDED_NETWORK: UINT16 = 0x06;
+/// BIOS Boot Specification device type of a BEV (Bootstrap Entry Vector) device.
+pub const BBS_TYPE_BEV: UINT16 = 0x80;
+/// BIOS Boot Specification device type of an unknown device.
+pub const BBS_TYPE_UNKNOWN: UINT16 = 0xFF;
+
+/// Vendor GUID of a PC ANSI terminal, in a vendor-defined messaging node.
+pub const EFI_PC_ANSI_GUID: EFI_GUID = EFI_GUID(
+    0xE0C14753,
+    0xF9BE,
+    0x11D2,
+    [0x9A, 0x0C, 0x00, 0x90, 0x27, 0x3F, 0xC1, 0x4D],
+);
+
+/// Vendor GUID of a VT-100 terminal, in a vendor-defined messaging node.
+pub const EFI_VT_100_GUID: EFI_GUID = EFI_GUID(
+    0xDFA66065,
+    0xB419,
+    0x11D3,
+    [0x9A, 0x2D, 0x00, 0x90, 0x27, 0x3F, 0xC1, 0x4D],
+);
+
+/// Vendor GUID of a VT-100+ terminal, in a vendor-defined messaging node.
+pub const EFI_VT_100_PLUS_GUID: EFI_GUID = EFI_GUID(
+    0x7BAEC70B,
+    0x57E0,
+    0x4C76,
+    [0x8E, 0x87, 0x2F, 0x9E, 0x28, 0x08, 0x83, 0x43],
+);
+
+/// Vendor GUID of a VT-UTF8 terminal, in a vendor-defined messaging node.
+pub const EFI_VT_UTF8_GUID: EFI_GUID = EFI_GUID(
+    0xAD15A0D6,
+    0x8BEC,
+    0x4ACF,
+    [0xA0, 0x73, 0xD0, 0x1D, 0xE7, 0x7E, 0x2D, 0x88],
+);
+
+/// Vendor GUID of a [`UART_FLOW_CONTROL_DEVICE_PATH`].
+///
+/// [`UART_FLOW_CONTROL_DEVICE_PATH`]: crate::protocols::device_path::nodes::UART_FLOW_CONTROL_DEVICE_PATH
+pub const DEVICE_PATH_MESSAGING_UART_FLOW_CONTROL: EFI_GUID = EFI_GUID(
+    0x37499A9D,
+    0x542F,
+    0x4C89,
+    [0xA0, 0x26, 0x35, 0xDA, 0x14, 0x20, 0x94, 0xE4],
+);
+
+/// Vendor GUID of a [`SAS_DEVICE_PATH`].
+///
+/// [`SAS_DEVICE_PATH`]: crate::protocols::device_path::nodes::SAS_DEVICE_PATH
+pub const EFI_SAS_DEVICE_PATH_GUID: EFI_GUID = EFI_GUID(
+    0xD487DDB4,
+    0x008B,
+    0x11D9,
+    [0xAF, 0xDC, 0x00, 0x10, 0x83, 0xFF, 0xCA, 0x4D],
+);
+
+/// Vendor GUID of the debug port, in a vendor-defined messaging node.
+pub const EFI_DEBUGPORT_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0xEBA4E8D2,
+    0x3858,
+    0x41EC,
+    [0xA2, 0x81, 0x26, 0x47, 0xBA, 0x96, 0x60, 0xD0],
+);
+
+/// RAM disk type GUID of a virtual disk in volatile memory.
+pub const EFI_VIRTUAL_DISK_GUID: EFI_GUID = EFI_GUID(
+    0x77AB535A,
+    0x45FC,
+    0x624B,
+    [0x55, 0x60, 0xF7, 0xB2, 0x81, 0xD1, 0xF9, 0x6E],
+);
+
+/// RAM disk type GUID of a virtual CD in volatile memory.
+pub const EFI_VIRTUAL_CD_GUID: EFI_GUID = EFI_GUID(
+    0x3D5ABD30,
+    0x4175,
+    0x87CE,
+    [0x6D, 0x64, 0xD2, 0xAD, 0xE5, 0x23, 0xC4, 0xBB],
+);
+
+/// RAM disk type GUID of a virtual disk in persistent memory.
+pub const EFI_PERSISTENT_VIRTUAL_DISK_GUID: EFI_GUID = EFI_GUID(
+    0x5CEA02C9,
+    0x4D07,
+    0x69D3,
+    [0x26, 0x9F, 0x44, 0x96, 0xFB, 0xE0, 0x96, 0xF9],
+);
+
+/// RAM disk type GUID of a virtual CD in persistent memory.
+pub const EFI_PERSISTENT_VIRTUAL_CD_GUID: EFI_GUID = EFI_GUID(
+    0x08018188,
+    0x42CD,
+    0xBB48,
+    [0x10, 0x0F, 0x53, 0x87, 0xD5, 0x3D, 0xED, 0x3D],
+);
+
+device_path_node! {
+    /// PCI Device Path. Describes a device on a PCI bus.
+    PCI_DEVICE_PATH(HARDWARE_DEVICE_PATH, HW_PCI_DP) {
+        /// PCI Function Number.
+        Function: UINT8,
+        /// PCI Device Number.
+        Device: UINT8,
+    }
+
+    /// PCCARD Device Path.
+    PCCARD_DEVICE_PATH(HARDWARE_DEVICE_PATH, HW_PCCARD_DP) {
+        /// Function Number (0 = First Function).
+        FunctionNumber: UINT8,
+    }
+
+    /// Memory Mapped Device Path.
+    MEMMAP_DEVICE_PATH(HARDWARE_DEVICE_PATH, HW_MEMMAP_DP) {
+        /// Memory type of the region.
+        MemoryType: UINT32,
+        /// Starting memory address.
+        StartingAddress: UINT64,
+        /// Ending memory address.
+        EndingAddress: UINT64,
+    }
+
+    /// Vendor-Defined Hardware Device Path. Followed by vendor-defined data.
+    HARDWARE_VENDOR_DEVICE_PATH(HARDWARE_DEVICE_PATH, HW_VENDOR_DP) {
+        /// Vendor-assigned GUID that defines the data that follows.
+        Guid: EFI_GUID,
+    }
+
+    /// Controller Device Path.
+    CONTROLLER_DEVICE_PATH(HARDWARE_DEVICE_PATH, HW_CONTROLLER_DP) {
+        /// Controller number.
+        ControllerNumber: UINT32,
+    }
+
+    /// Baseboard Management Controller Device Path.
+    BMC_DEVICE_PATH(HARDWARE_DEVICE_PATH, HW_BMC_DP) {
+        /// The Baseboard Management Controller host interface type.
+        InterfaceType: UINT8,
+        /// Base address (either memory-mapped or I/O) of the Baseboard Management Controller.
+        BaseAddress: UINT64,
+    }
+
+    /// ACPI Device Path. Identifies a device by its `_HID` and `_UID`.
+    ACPI_HID_DEVICE_PATH(ACPI_DEVICE_PATH, ACPI_DP) {
+        /// Device’s PnP hardware ID stored in a numeric 32-bit compressed EISA-type ID.
+        HID: UINT32,
+        /// Unique ID that is required by ACPI if two devices have the same `_HID`.
+        UID: UINT32,
+    }
+
+    /// Expanded ACPI Device Path. Followed by the null-terminated `HIDSTR`, `UIDSTR` and `CIDSTR`
+    /// ASCII strings.
+    ACPI_EXTENDED_HID_DEVICE_PATH(ACPI_DEVICE_PATH, ACPI_EXTENDED_DP) {
+        /// Device’s PnP hardware ID stored in a numeric 32-bit compressed EISA-type ID.
+        HID: UINT32,
+        /// Unique ID that is required by ACPI if two devices have the same `_HID`.
+        UID: UINT32,
+        /// Device’s compatible PnP hardware ID stored in a numeric 32-bit compressed EISA-type ID.
+        CID: UINT32,
+    }
+
+    /// ACPI `_ADR` Device Path. Followed by any additional `_ADR` values, for devices with
+    /// multiple outputs.
+    ACPI_ADR_DEVICE_PATH(ACPI_DEVICE_PATH, ACPI_ADR_DP) {
+        /// `_ADR` value.
+        ADR: UINT32,
+    }
+
+    /// NVDIMM Device Path.
+    ACPI_NVDIMM_DEVICE_PATH(ACPI_DEVICE_PATH, ACPI_NVDIMM_DP) {
+        /// NFIT Device Handle of the NVDIMM.
+        NFITDeviceHandle: UINT32,
+    }
+
+    /// ATAPI Device Path.
+    ATAPI_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_ATAPI_DP) {
+        /// Set to zero for primary, or one for secondary.
+        PrimarySecondary: UINT8,
+        /// Set to zero for master, or one for slave mode.
+        SlaveMaster: UINT8,
+        /// Logical Unit Number.
+        Lun: UINT16,
+    }
+
+    /// SCSI Device Path.
+    SCSI_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_SCSI_DP) {
+        /// Target ID on the SCSI bus.
+        Pun: UINT16,
+        /// Logical Unit Number.
+        Lun: UINT16,
+    }
+
+    /// Fibre Channel Device Path.
+    FIBRECHANNEL_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_FIBRECHANNEL_DP) {
+        /// Reserved.
+        Reserved: UINT32,
+        /// Fibre Channel World Wide Name.
+        WWN: UINT64,
+        /// Fibre Channel Logical Unit Number.
+        Lun: UINT64,
+    }
+
+    /// Fibre Channel Ex Device Path.
+    FIBRECHANNELEX_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_FIBRECHANNELEX_DP) {
+        /// Reserved.
+        Reserved: UINT32,
+        /// 8 byte array containing Fibre Channel End Device Port Name.
+        WWN: [UINT8; 8],
+        /// 8 byte array containing Fibre Channel Logical Unit Number.
+        Lun: [UINT8; 8],
+    }
+
+    /// 1394 Device Path.
+    F1394_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_1394_DP) {
+        /// Reserved.
+        Reserved: UINT32,
+        /// 1394 Global Unique ID.
+        Guid: UINT64,
+    }
+
+    /// USB Device Path.
+    USB_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_USB_DP) {
+        /// USB Parent Port Number.
+        ParentPortNumber: UINT8,
+        /// USB Interface Number.
+        InterfaceNumber: UINT8,
+    }
+
+    /// USB Class Device Path.
+    USB_CLASS_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_USB_CLASS_DP) {
+        /// Vendor ID assigned by USB-IF. A value of `0xFFFF` will match any Vendor ID.
+        VendorId: UINT16,
+        /// Product ID assigned by USB-IF. A value of `0xFFFF` will match any Product ID.
+        ProductId: UINT16,
+        /// The class code assigned by the USB-IF. A value of `0xFF` will match any class code.
+        DeviceClass: UINT8,
+        /// The subclass code assigned by the USB-IF. A value of `0xFF` will match any subclass
+        /// code.
+        DeviceSubClass: UINT8,
+        /// The protocol code assigned by the USB-IF. A value of `0xFF` will match any protocol
+        /// code.
+        DeviceProtocol: UINT8,
+    }
+
+    /// USB WWID Device Path. Followed by the UCS-2 serial number of the device.
+    USB_WWID_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_USB_WWID_DP) {
+        /// USB interface number.
+        InterfaceNumber: UINT16,
+        /// USB vendor ID of the device.
+        VendorId: UINT16,
+        /// USB product ID of the device.
+        ProductId: UINT16,
+    }
+
+    /// Device Logical Unit Device Path.
+    DEVICE_LOGICAL_UNIT_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_DEVICE_LOGICAL_UNIT_DP) {
+        /// Logical Unit Number for the interface.
+        Lun: UINT8,
+    }
+
+    /// SATA Device Path.
+    SATA_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_SATA_DP) {
+        /// The HBA port number that facilitates the connection to the device or a port
+        /// multiplier.
+        HBAPortNumber: UINT16,
+        /// The port multiplier port number that facilitates the connection to the device, or
+        /// `0xFFFF` if the device is directly connected to the HBA.
+        PortMultiplierPortNumber: UINT16,
+        /// Logical Unit Number.
+        Lun: UINT16,
+    }
+
+    /// iSCSI Device Path. Followed by the iSCSI Target Name.
+    ISCSI_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_ISCSI_DP) {
+        /// Network protocol, `0` for TCP and `1`-`0xFFFF` reserved.
+        NetworkProtocol: UINT16,
+        /// iSCSI login options.
+        LoginOption: UINT16,
+        /// iSCSI Logical Unit Number.
+        Lun: UINT64,
+        /// iSCSI Target Portal group tag the initiator intends to establish a session with.
+        TargetPortalGroupTag: UINT16,
+    }
+
+    /// I2O Device Path.
+    I2O_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_I2O_DP) {
+        /// Target ID (TID) for a device.
+        Tid: UINT32,
+    }
+
+    /// InfiniBand Device Path.
+    INFINIBAND_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_INFINIBAND_DP) {
+        /// Flags to help identify/manage the InfiniBand device path node.
+        ResourceFlags: UINT32,
+        /// 128-bit Global Identifier for remote fabric port.
+        PortGid: [UINT8; 16],
+        /// 64-bit unique identifier to remote IOC or server process.
+        ServiceId: UINT64,
+        /// 64-bit persistent ID of remote IOC port.
+        TargetPortId: UINT64,
+        /// 64-bit persistent ID of remote device.
+        DeviceId: UINT64,
+    }
+
+    /// Vendor-Defined Messaging Device Path. Followed by vendor-defined data.
+    MESSAGING_VENDOR_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_VENDOR_DP) {
+        /// Vendor-assigned GUID that defines the data that follows.
+        Guid: EFI_GUID,
+    }
+
+    /// UART Flow Control Messaging Device Path. A vendor-defined messaging node identified by
+    /// [`DEVICE_PATH_MESSAGING_UART_FLOW_CONTROL`].
+    ///
+    /// [`DEVICE_PATH_MESSAGING_UART_FLOW_CONTROL`]: crate::protocols::device_path::nodes::DEVICE_PATH_MESSAGING_UART_FLOW_CONTROL
+    UART_FLOW_CONTROL_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_VENDOR_DP) {
+        /// [`DEVICE_PATH_MESSAGING_UART_FLOW_CONTROL`].
+        ///
+        /// [`DEVICE_PATH_MESSAGING_UART_FLOW_CONTROL`]: crate::protocols::device_path::nodes::DEVICE_PATH_MESSAGING_UART_FLOW_CONTROL
+        Guid: EFI_GUID,
+        /// Bitmap of the flow control in use: `0` for none, `1` for hardware and `2` for
+        /// XON/XOFF.
+        FlowControlMap: UINT32,
+    }
+
+    /// Serial Attached SCSI (SAS) Device Path. A vendor-defined messaging node identified by
+    /// [`EFI_SAS_DEVICE_PATH_GUID`].
+    ///
+    /// [`EFI_SAS_DEVICE_PATH_GUID`]: crate::protocols::device_path::nodes::EFI_SAS_DEVICE_PATH_GUID
+    SAS_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_VENDOR_DP) {
+        /// [`EFI_SAS_DEVICE_PATH_GUID`].
+        ///
+        /// [`EFI_SAS_DEVICE_PATH_GUID`]: crate::protocols::device_path::nodes::EFI_SAS_DEVICE_PATH_GUID
+        Guid: EFI_GUID,
+        /// Reserved for future use.
+        Reserved: UINT32,
+        /// SAS Address for Serial Attached SCSI Target.
+        SasAddress: UINT64,
+        /// SAS Logical Unit Number.
+        Lun: UINT64,
+        /// More information about the device and its interconnect.
+        DeviceTopology: UINT16,
+        /// Relative Target Port (RTP).
+        RelativeTargetPort: UINT16,
+    }
+
+    /// MAC Address Device Path.
+    MAC_ADDR_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_MAC_ADDR_DP) {
+        /// The MAC address for a network interface padded with 0s.
+        MacAddress: EFI_MAC_ADDRESS,
+        /// Network interface type (i.e. 802.3, FDDI).
+        IfType: UINT8,
+    }
+
+    /// IPv4 Device Path.
+    IPv4_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_IPv4_DP) {
+        /// The local IPv4 address.
+        LocalIpAddress: EFI_IPv4_ADDRESS,
+        /// The remote IPv4 address.
+        RemoteIpAddress: EFI_IPv4_ADDRESS,
+        /// The local port number.
+        LocalPort: UINT16,
+        /// The remote port number.
+        RemotePort: UINT16,
+        /// The network protocol (i.e. UDP, TCP).
+        Protocol: UINT16,
+        /// `0x00` - the source IP address was assigned through DHCP; `0x01` - the source IP
+        /// address is statically bound.
+        StaticIpAddress: BOOLEAN,
+        /// The gateway IP address.
+        GatewayIpAddress: EFI_IPv4_ADDRESS,
+        /// The subnet mask.
+        SubnetMask: EFI_IPv4_ADDRESS,
+    }
+
+    /// IPv4 Device Path, as defined by version 2.0 of the UEFI Specification, which lacks the
+    /// gateway IP address and subnet mask.
+    IPv4_LEGACY_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_IPv4_DP) {
+        /// The local IPv4 address.
+        LocalIpAddress: EFI_IPv4_ADDRESS,
+        /// The remote IPv4 address.
+        RemoteIpAddress: EFI_IPv4_ADDRESS,
+        /// The local port number.
+        LocalPort: UINT16,
+        /// The remote port number.
+        RemotePort: UINT16,
+        /// The network protocol (i.e. UDP, TCP).
+        Protocol: UINT16,
+        /// `0x00` - the source IP address was assigned through DHCP; `0x01` - the source IP
+        /// address is statically bound.
+        StaticIpAddress: BOOLEAN,
+    }
+
+    /// IPv6 Device Path.
+    IPv6_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_IPv6_DP) {
+        /// The local IPv6 address.
+        LocalIpAddress: EFI_IPv6_ADDRESS,
+        /// The remote IPv6 address.
+        RemoteIpAddress: EFI_IPv6_ADDRESS,
+        /// The local port number.
+        LocalPort: UINT16,
+        /// The remote port number.
+        RemotePort: UINT16,
+        /// The network protocol (i.e. UDP, TCP).
+        Protocol: UINT16,
+        /// `0x00` - manually configured, `0x01` - stateless auto-configuration, `0x02` - stateful
+        /// configuration.
+        IpAddressOrigin: UINT8,
+        /// The prefix length.
+        PrefixLength: UINT8,
+        /// The gateway IP address.
+        GatewayIpAddress: EFI_IPv6_ADDRESS,
+    }
+
+    /// VLAN Device Path.
+    VLAN_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_VLAN_DP) {
+        /// VLAN identifier (0-4094).
+        VlanId: UINT16,
+    }
+
+    /// UART Device Path.
+    UART_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_UART_DP) {
+        /// Reserved.
+        Reserved: UINT32,
+        /// The baud rate setting for the UART style device. A value of `0` means that the device’s
+        /// default baud rate will be used.
+        BaudRate: UINT64,
+        /// The number of data bits for the UART style device. A value of `0` means that the
+        /// device’s default number of data bits will be used.
+        DataBits: UINT8,
+        /// The parity setting for the UART style device.
+        Parity: UINT8,
+        /// The number of stop bits for the UART style device.
+        StopBits: UINT8,
+    }
+
+    /// Serial Attached SCSI (SAS) Ex Device Path.
+    SASEX_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_SASEX_DP) {
+        /// 8-byte array of the SAS Address for Serial Attached SCSI Target Port.
+        SasAddress: [UINT8; 8],
+        /// 8-byte array of the SAS Logical Unit Number.
+        Lun: [UINT8; 8],
+        /// More information about the device and its interconnect.
+        DeviceTopology: UINT16,
+        /// Relative Target Port (RTP).
+        RelativeTargetPort: UINT16,
+    }
+
+    /// NVM Express Namespace Device Path.
+    NVME_NAMESPACE_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_NVME_NAMESPACE_DP) {
+        /// Namespace identifier (NSID). The values of `0` and `0xFFFFFFFF` are invalid.
+        NamespaceId: UINT32,
+        /// The IEEE Extended Unique Identifier (EUI-64), or `0` if not supported.
+        NamespaceUuid: UINT64,
+    }
+
+    /// Uniform Resource Identifier (URI) Device Path. Followed by the URI, which is not
+    /// null-terminated.
+    URI_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_URI_DP) {}
+
+    /// Universal Flash Storage (UFS) Device Path.
+    UFS_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_UFS_DP) {
+        /// Target ID on the UFS interface (PUN).
+        Pun: UINT8,
+        /// Logical Unit Number (LUN).
+        Lun: UINT8,
+    }
+
+    /// SD (Secure Digital) Device Path.
+    SD_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_SD_DP) {
+        /// Slot number.
+        SlotNumber: UINT8,
+    }
+
+    /// Bluetooth Device Path.
+    BLUETOOTH_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_BLUETOOTH_DP) {
+        /// 48-bit Bluetooth device address.
+        BD_ADDR: [UINT8; 6],
+    }
+
+    /// Wi-Fi Device Path.
+    WIFI_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_WIFI_DP) {
+        /// Service set identifier, as a null-padded octet string.
+        SSId: [UINT8; 32],
+    }
+
+    /// eMMC (Embedded Multi-Media Card) Device Path.
+    EMMC_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_EMMC_DP) {
+        /// Slot number.
+        SlotNumber: UINT8,
+    }
+
+    /// Bluetooth LE Device Path.
+    BLUETOOTH_LE_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_BLUETOOTH_LE_DP) {
+        /// 48-bit Bluetooth device address.
+        Address: [UINT8; 6],
+        /// `0x00` - public device address, `0x01` - random device address.
+        Type: UINT8,
+    }
+
+    /// DNS Device Path. Followed by the addresses of the DNS servers.
+    DNS_DEVICE_PATH(MESSAGING_DEVICE_PATH, MSG_DNS_DP) {
+        /// `0x00` - the DNS server addresses are IPv4 addresses, `0x01` - IPv6 addresses.
+        IsIPv6: UINT8,
+    }
+
+    /// Hard Drive Media Device Path. Represents a partition on a hard drive.
+    HARDDRIVE_DEVICE_PATH(MEDIA_DEVICE_PATH, MEDIA_HARDDRIVE_DP) {
+        /// Describes the entry in a partition table, starting with entry 1. Partition number zero
+        /// represents the entire device.
+        PartitionNumber: UINT32,
+        /// Starting LBA of the partition on the hard drive.
+        PartitionStart: UINT64,
+        /// Size of the partition in units of Logical Blocks.
+        PartitionSize: UINT64,
+        /// Signature unique to this partition, as described by [`SignatureType`].
+        ///
+        /// [`SignatureType`]: ./struct.HARDDRIVE_DEVICE_PATH.html#structfield.SignatureType
+        Signature: [UINT8; 16],
+        /// Partition format, [`MBR_TYPE_PCAT`] or [`MBR_TYPE_EFI_PARTITION_TABLE_HEADER`].
+        ///
+        /// [`MBR_TYPE_PCAT`]: crate::protocols::device_path::nodes::MBR_TYPE_PCAT
+        /// [`MBR_TYPE_EFI_PARTITION_TABLE_HEADER`]: crate::protocols::device_path::nodes::MBR_TYPE_EFI_PARTITION_TABLE_HEADER
+        MBRType: UINT8,
+        /// Type of disk signature, [`NO_DISK_SIGNATURE`], [`SIGNATURE_TYPE_MBR`] or
+        /// [`SIGNATURE_TYPE_GUID`].
+        ///
+        /// [`NO_DISK_SIGNATURE`]: crate::protocols::device_path::nodes::NO_DISK_SIGNATURE
+        /// [`SIGNATURE_TYPE_MBR`]: crate::protocols::device_path::nodes::SIGNATURE_TYPE_MBR
+        /// [`SIGNATURE_TYPE_GUID`]: crate::protocols::device_path::nodes::SIGNATURE_TYPE_GUID
+        SignatureType: UINT8,
+    }
+
+    /// CD-ROM Media Device Path. Defines a system partition that exists on a CD-ROM.
+    CDROM_DEVICE_PATH(MEDIA_DEVICE_PATH, MEDIA_CDROM_DP) {
+        /// Boot Entry number from the Boot Catalog. The Initial/Default entry is defined as zero.
+        BootEntry: UINT32,
+        /// Starting RBA of the partition on the medium.
+        PartitionStart: UINT64,
+        /// Size of the partition in units of Blocks, also called Sectors.
+        PartitionSize: UINT64,
+    }
+
+    /// Vendor-Defined Media Device Path. Followed by vendor-defined data.
+    MEDIA_VENDOR_DEVICE_PATH(MEDIA_DEVICE_PATH, MEDIA_VENDOR_DP) {
+        /// Vendor-assigned GUID that defines the data that follows.
+        Guid: EFI_GUID,
+    }
+
+    /// File Path Media Device Path. Followed by the null-terminated UCS-2 path name.
+    FILEPATH_DEVICE_PATH(MEDIA_DEVICE_PATH, MEDIA_FILEPATH_DP) {}
+
+    /// Media Protocol Device Path. Identifies the protocol used to access the media.
+    MEDIA_PROTOCOL_DEVICE_PATH(MEDIA_DEVICE_PATH, MEDIA_PROTOCOL_DP) {
+        /// The ID of the protocol.
+        Protocol: EFI_GUID,
+    }
+
+    /// PIWG Firmware File Device Path.
+    MEDIA_FW_VOL_FILEPATH_DEVICE_PATH(MEDIA_DEVICE_PATH, MEDIA_PIWG_FW_FILE_DP) {
+        /// Firmware file name.
+        FvFileName: EFI_GUID,
+    }
+
+    /// PIWG Firmware Volume Device Path.
+    MEDIA_FW_VOL_DEVICE_PATH(MEDIA_DEVICE_PATH, MEDIA_PIWG_FW_VOL_DP) {
+        /// Firmware volume name.
+        FvName: EFI_GUID,
+    }
+
+    /// Relative Offset Range Device Path.
+    MEDIA_RELATIVE_OFFSET_RANGE_DEVICE_PATH(MEDIA_DEVICE_PATH, MEDIA_RELATIVE_OFFSET_RANGE_DP) {
+        /// Reserved.
+        Reserved: UINT32,
+        /// Offset of the first byte, relative to the parent device node.
+        StartingOffset: UINT64,
+        /// Offset of the last byte, relative to the parent device node.
+        EndingOffset: UINT64,
+    }
+
+    /// RAM Disk Device Path.
+    MEDIA_RAM_DISK_DEVICE_PATH(MEDIA_DEVICE_PATH, MEDIA_RAM_DISK_DP) {
+        /// Starting memory address.
+        StartingAddr: UINT64,
+        /// Ending memory address.
+        EndingAddr: UINT64,
+        /// GUID that defines the type of the RAM Disk.
+        TypeGuid: EFI_GUID,
+        /// RAM disk instance number, if supported. The default value is zero.
+        Instance: UINT16,
+    }
+
+    /// BIOS Boot Specification Device Path. Followed by the null-terminated ASCII description
+    /// string.
+    BBS_BBS_DEVICE_PATH(BBS_DEVICE_PATH, BBS_BBS_DP) {
+        /// Device type as defined by the BIOS Boot Specification.
+        DeviceType: UINT16,
+        /// Status flags as defined by the BIOS Boot Specification.
+        StatusFlag: UINT16,
+    }
+}
+
+/// A device path node, decoded according to its type and sub-type.
+///
+/// Variants of nodes with a variable-length part hold that part, as raw bytes, alongside the
+/// fixed-size node structure.
+#[derive(Clone, Copy)]
+pub enum DevicePathNodeKind<'a> {
+    /// PCI device path node.
+    Pci(&'a PCI_DEVICE_PATH),
+    /// PCCARD device path node.
+    Pccard(&'a PCCARD_DEVICE_PATH),
+    /// Memory mapped device path node.
+    MemoryMapped(&'a MEMMAP_DEVICE_PATH),
+    /// Hardware vendor device path node, with its vendor-defined data.
+    HardwareVendor(&'a HARDWARE_VENDOR_DEVICE_PATH, &'a [UINT8]),
+    /// Controller device path node.
+    Controller(&'a CONTROLLER_DEVICE_PATH),
+    /// Baseboard management controller device path node.
+    Bmc(&'a BMC_DEVICE_PATH),
+    /// ACPI device path node.
+    Acpi(&'a ACPI_HID_DEVICE_PATH),
+    /// Expanded ACPI device path node, with its `HIDSTR`, `UIDSTR` and `CIDSTR` strings.
+    ExpandedAcpi(&'a ACPI_EXTENDED_HID_DEVICE_PATH, &'a [UINT8]),
+    /// ACPI `_ADR` device path node, with its additional `_ADR` values.
+    Adr(&'a ACPI_ADR_DEVICE_PATH, &'a [UINT8]),
+    /// NVDIMM device path node.
+    Nvdimm(&'a ACPI_NVDIMM_DEVICE_PATH),
+    /// ATAPI device path node.
+    Atapi(&'a ATAPI_DEVICE_PATH),
+    /// SCSI device path node.
+    Scsi(&'a SCSI_DEVICE_PATH),
+    /// Fibre Channel device path node.
+    FibreChannel(&'a FIBRECHANNEL_DEVICE_PATH),
+    /// Fibre Channel Ex device path node.
+    FibreChannelEx(&'a FIBRECHANNELEX_DEVICE_PATH),
+    /// 1394 device path node.
+    F1394(&'a F1394_DEVICE_PATH),
+    /// USB device path node.
+    Usb(&'a USB_DEVICE_PATH),
+    /// USB class device path node.
+    UsbClass(&'a USB_CLASS_DEVICE_PATH),
+    /// USB WWID device path node, with its serial number.
+    UsbWwid(&'a USB_WWID_DEVICE_PATH, &'a [UINT8]),
+    /// Device logical unit device path node.
+    LogicalUnit(&'a DEVICE_LOGICAL_UNIT_DEVICE_PATH),
+    /// SATA device path node.
+    Sata(&'a SATA_DEVICE_PATH),
+    /// iSCSI device path node, with its target name.
+    Iscsi(&'a ISCSI_DEVICE_PATH, &'a [UINT8]),
+    /// I2O device path node.
+    I2O(&'a I2O_DEVICE_PATH),
+    /// InfiniBand device path node.
+    InfiniBand(&'a INFINIBAND_DEVICE_PATH),
+    /// Messaging vendor device path node, with its vendor-defined data.
+    MessagingVendor(&'a MESSAGING_VENDOR_DEVICE_PATH, &'a [UINT8]),
+    /// MAC address device path node.
+    MacAddress(&'a MAC_ADDR_DEVICE_PATH),
+    /// IPv4 device path node.
+    IPv4(&'a IPv4_DEVICE_PATH),
+    /// IPv4 device path node in its UEFI 2.0 form, without a gateway IP address and subnet mask.
+    IPv4Legacy(&'a IPv4_LEGACY_DEVICE_PATH),
+    /// IPv6 device path node.
+    IPv6(&'a IPv6_DEVICE_PATH),
+    /// VLAN device path node.
+    Vlan(&'a VLAN_DEVICE_PATH),
+    /// UART device path node.
+    Uart(&'a UART_DEVICE_PATH),
+    /// Serial Attached SCSI Ex device path node.
+    SasEx(&'a SASEX_DEVICE_PATH),
+    /// NVM Express namespace device path node.
+    NvmeNamespace(&'a NVME_NAMESPACE_DEVICE_PATH),
+    /// URI device path node, with its URI.
+    Uri(&'a URI_DEVICE_PATH, &'a [UINT8]),
+    /// UFS device path node.
+    Ufs(&'a UFS_DEVICE_PATH),
+    /// SD device path node.
+    Sd(&'a SD_DEVICE_PATH),
+    /// Bluetooth device path node.
+    Bluetooth(&'a BLUETOOTH_DEVICE_PATH),
+    /// Wi-Fi device path node.
+    Wifi(&'a WIFI_DEVICE_PATH),
+    /// eMMC device path node.
+    Emmc(&'a EMMC_DEVICE_PATH),
+    /// Bluetooth LE device path node.
+    BluetoothLe(&'a BLUETOOTH_LE_DEVICE_PATH),
+    /// DNS device path node, with its DNS server addresses.
+    Dns(&'a DNS_DEVICE_PATH, &'a [UINT8]),
+    /// Hard drive media device path node.
+    HardDrive(&'a HARDDRIVE_DEVICE_PATH),
+    /// CD-ROM media device path node.
+    CdRom(&'a CDROM_DEVICE_PATH),
+    /// Media vendor device path node, with its vendor-defined data.
+    MediaVendor(&'a MEDIA_VENDOR_DEVICE_PATH, &'a [UINT8]),
+    /// File path media device path node, with its null-terminated UCS-2 path name.
+    FilePath(&'a FILEPATH_DEVICE_PATH, &'a [UINT8]),
+    /// Media protocol device path node.
+    MediaProtocol(&'a MEDIA_PROTOCOL_DEVICE_PATH),
+    /// PIWG firmware file device path node.
+    PiwgFirmwareFile(&'a MEDIA_FW_VOL_FILEPATH_DEVICE_PATH),
+    /// PIWG firmware volume device path node.
+    PiwgFirmwareVolume(&'a MEDIA_FW_VOL_DEVICE_PATH),
+    /// Relative offset range device path node.
+    RelativeOffsetRange(&'a MEDIA_RELATIVE_OFFSET_RANGE_DEVICE_PATH),
+    /// RAM disk device path node.
+    RamDisk(&'a MEDIA_RAM_DISK_DEVICE_PATH),
+    /// BIOS boot specification device path node, with its description string.
+    Bbs(&'a BBS_BBS_DEVICE_PATH, &'a [UINT8]),
+    /// End of instance node, separating the instances of a multi-instance device path.
+    EndInstance,
+    /// End of entire device path node.
+    EndEntire,
+    /// A node of an unknown type or sub-type, or too short for its type.
+    Unknown(DevicePathNode<'a>),
+}
+
+impl<'a> DevicePathNodeKind<'a> {
+    /// Decodes a device path node.
+    pub fn new(Node: DevicePathNode<'a>) -> Self {
+        fn fixed<'a, T: DevicePathNodeStruct>(
+            node: DevicePathNode<'a>,
+            variant: fn(&'a T) -> DevicePathNodeKind<'a>,
+        ) -> DevicePathNodeKind<'a> {
+            node.cast::<T>()
+                .map_or(DevicePathNodeKind::Unknown(node), variant)
+        }
+
+        fn variable<'a, T: DevicePathNodeStruct>(
+            node: DevicePathNode<'a>,
+            variant: fn(&'a T, &'a [UINT8]) -> DevicePathNodeKind<'a>,
+        ) -> DevicePathNodeKind<'a> {
+            node.cast::<T>()
+                .map_or(DevicePathNodeKind::Unknown(node), |n| {
+                    variant(n, node.trailing::<T>())
+                })
+        }
+
+        match (Node.node_type(), Node.sub_type()) {
+            (HARDWARE_DEVICE_PATH, HW_PCI_DP) => fixed(Node, Self::Pci),
+            (HARDWARE_DEVICE_PATH, HW_PCCARD_DP) => fixed(Node, Self::Pccard),
+            (HARDWARE_DEVICE_PATH, HW_MEMMAP_DP) => fixed(Node, Self::MemoryMapped),
+            (HARDWARE_DEVICE_PATH, HW_VENDOR_DP) => variable(Node, Self::HardwareVendor),
+            (HARDWARE_DEVICE_PATH, HW_CONTROLLER_DP) => fixed(Node, Self::Controller),
+            (HARDWARE_DEVICE_PATH, HW_BMC_DP) => fixed(Node, Self::Bmc),
+            (ACPI_DEVICE_PATH, ACPI_DP) => fixed(Node, Self::Acpi),
+            (ACPI_DEVICE_PATH, ACPI_EXTENDED_DP) => variable(Node, Self::ExpandedAcpi),
+            (ACPI_DEVICE_PATH, ACPI_ADR_DP) => variable(Node, Self::Adr),
+            (ACPI_DEVICE_PATH, ACPI_NVDIMM_DP) => fixed(Node, Self::Nvdimm),
+            (MESSAGING_DEVICE_PATH, MSG_ATAPI_DP) => fixed(Node, Self::Atapi),
+            (MESSAGING_DEVICE_PATH, MSG_SCSI_DP) => fixed(Node, Self::Scsi),
+            (MESSAGING_DEVICE_PATH, MSG_FIBRECHANNEL_DP) => fixed(Node, Self::FibreChannel),
+            (MESSAGING_DEVICE_PATH, MSG_FIBRECHANNELEX_DP) => fixed(Node, Self::FibreChannelEx),
+            (MESSAGING_DEVICE_PATH, MSG_1394_DP) => fixed(Node, Self::F1394),
+            (MESSAGING_DEVICE_PATH, MSG_USB_DP) => fixed(Node, Self::Usb),
+            (MESSAGING_DEVICE_PATH, MSG_USB_CLASS_DP) => fixed(Node, Self::UsbClass),
+            (MESSAGING_DEVICE_PATH, MSG_USB_WWID_DP) => variable(Node, Self::UsbWwid),
+            (MESSAGING_DEVICE_PATH, MSG_DEVICE_LOGICAL_UNIT_DP) => fixed(Node, Self::LogicalUnit),
+            (MESSAGING_DEVICE_PATH, MSG_SATA_DP) => fixed(Node, Self::Sata),
+            (MESSAGING_DEVICE_PATH, MSG_ISCSI_DP) => variable(Node, Self::Iscsi),
+            (MESSAGING_DEVICE_PATH, MSG_I2O_DP) => fixed(Node, Self::I2O),
+            (MESSAGING_DEVICE_PATH, MSG_INFINIBAND_DP) => fixed(Node, Self::InfiniBand),
+            (MESSAGING_DEVICE_PATH, MSG_VENDOR_DP) => variable(Node, Self::MessagingVendor),
+            (MESSAGING_DEVICE_PATH, MSG_MAC_ADDR_DP) => fixed(Node, Self::MacAddress),
+            (MESSAGING_DEVICE_PATH, MSG_IPv4_DP) => match Node.cast() {
+                Some(n) => Self::IPv4(n),
+                None => fixed(Node, Self::IPv4Legacy),
+            },
+            (MESSAGING_DEVICE_PATH, MSG_IPv6_DP) => fixed(Node, Self::IPv6),
+            (MESSAGING_DEVICE_PATH, MSG_VLAN_DP) => fixed(Node, Self::Vlan),
+            (MESSAGING_DEVICE_PATH, MSG_UART_DP) => fixed(Node, Self::Uart),
+            (MESSAGING_DEVICE_PATH, MSG_SASEX_DP) => fixed(Node, Self::SasEx),
+            (MESSAGING_DEVICE_PATH, MSG_NVME_NAMESPACE_DP) => fixed(Node, Self::NvmeNamespace),
+            (MESSAGING_DEVICE_PATH, MSG_URI_DP) => variable(Node, Self::Uri),
+            (MESSAGING_DEVICE_PATH, MSG_UFS_DP) => fixed(Node, Self::Ufs),
+            (MESSAGING_DEVICE_PATH, MSG_SD_DP) => fixed(Node, Self::Sd),
+            (MESSAGING_DEVICE_PATH, MSG_BLUETOOTH_DP) => fixed(Node, Self::Bluetooth),
+            (MESSAGING_DEVICE_PATH, MSG_WIFI_DP) => fixed(Node, Self::Wifi),
+            (MESSAGING_DEVICE_PATH, MSG_EMMC_DP) => fixed(Node, Self::Emmc),
+            (MESSAGING_DEVICE_PATH, MSG_BLUETOOTH_LE_DP) => fixed(Node, Self::BluetoothLe),
+            (MESSAGING_DEVICE_PATH, MSG_DNS_DP) => variable(Node, Self::Dns),
+            (MEDIA_DEVICE_PATH, MEDIA_HARDDRIVE_DP) => fixed(Node, Self::HardDrive),
+            (MEDIA_DEVICE_PATH, MEDIA_CDROM_DP) => fixed(Node, Self::CdRom),
+            (MEDIA_DEVICE_PATH, MEDIA_VENDOR_DP) => variable(Node, Self::MediaVendor),
+            (MEDIA_DEVICE_PATH, MEDIA_FILEPATH_DP) => variable(Node, Self::FilePath),
+            (MEDIA_DEVICE_PATH, MEDIA_PROTOCOL_DP) => fixed(Node, Self::MediaProtocol),
+            (MEDIA_DEVICE_PATH, MEDIA_PIWG_FW_FILE_DP) => fixed(Node, Self::PiwgFirmwareFile),
+            (MEDIA_DEVICE_PATH, MEDIA_PIWG_FW_VOL_DP) => fixed(Node, Self::PiwgFirmwareVolume),
+            (MEDIA_DEVICE_PATH, MEDIA_RELATIVE_OFFSET_RANGE_DP) => {
+                fixed(Node, Self::RelativeOffsetRange)
+            }
+            (MEDIA_DEVICE_PATH, MEDIA_RAM_DISK_DP) => fixed(Node, Self::RamDisk),
+            (BBS_DEVICE_PATH, BBS_BBS_DP) => variable(Node, Self::Bbs),
+            (END_DEVICE_PATH_TYPE, END_INSTANCE_DEVICE_PATH_SUBTYPE) => Self::EndInstance,
+            (END_DEVICE_PATH_TYPE, END_ENTIRE_DEVICE_PATH_SUBTYPE) => Self::EndEntire,
+            _ => Self::Unknown(Node),
+        }
+    }
+}
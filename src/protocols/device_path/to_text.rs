@@ -0,0 +1,898 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Device Path To Text Protocol
+//!
+//! This module defines the Device Path To Text Protocol, also known as the
+//! [`EFI_DEVICE_PATH_TO_TEXT_PROTOCOL`], which converts device paths and device nodes to text.
+//! See [Section 10.6 of the UEFI Specification] for more details.
+//!
+//! Firmware does not always provide this protocol, so the same conversion is also implemented in
+//! Rust by [`DevicePath::display()`] and [`DevicePathNode::display()`], which return values
+//! implementing [`Display`]. The text follows the format described in [Section 10.6.1.6 of the
+//! UEFI Specification] as produced by EDK II, with hexadecimal numbers in uppercase.
+//!
+//! [`EFI_DEVICE_PATH_TO_TEXT_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_TO_TEXT_PROTOCOL
+//! [`DevicePath::display()`]: crate::protocols::device_path::DevicePath::display
+//! [`DevicePathNode::display()`]: crate::protocols::device_path::DevicePathNode::display
+//! [`Display`]: core::fmt::Display
+//!
+//! [Section 10.6 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/10_Protocols_Device_Path_Protocol.html#device-path-to-text-protocol
+//! [Section 10.6.1.6 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/10_Protocols_Device_Path_Protocol.html#text-device-node-reference
+
+use core::fmt::{self, Display, Formatter, Write};
+use core::mem;
+
+use crate::protocols::device_path::nodes::*;
+use crate::protocols::device_path::{
+    DevicePath, DevicePathNode, DevicePathNodeKind, ACPI_DEVICE_PATH, BBS_DEVICE_PATH,
+    EFI_DEVICE_PATH_PROTOCOL, HARDWARE_DEVICE_PATH, MEDIA_DEVICE_PATH, MESSAGING_DEVICE_PATH,
+};
+use crate::protocols::Protocol;
+use crate::types::{EFI_IPv4_ADDRESS, BOOLEAN, CHAR16, EFI_GUID};
+
+/// GUID for the [`EFI_DEVICE_PATH_TO_TEXT_PROTOCOL`].
+///
+/// [`EFI_DEVICE_PATH_TO_TEXT_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_TO_TEXT_PROTOCOL
+pub const EFI_DEVICE_PATH_TO_TEXT_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0x8B843E20,
+    0x8132,
+    0x4852,
+    [0x90, 0xCC, 0x55, 0x1A, 0x4E, 0x4A, 0x7F, 0x1C],
+);
+
+/// The Device Path To Text protocol converts device nodes and paths to text.
+///
+/// Every string returned by this protocol is allocated from pool memory, and must be freed by the
+/// caller with [`EFI_BOOT_SERVICES.FreePool()`] once it is no longer needed.
+///
+/// [`EFI_BOOT_SERVICES.FreePool()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-freepool
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_DEVICE_PATH_TO_TEXT_PROTOCOL {
+    ConvertDeviceNodeToText: EFI_DEVICE_PATH_TO_TEXT_NODE,
+    ConvertDevicePathToText: EFI_DEVICE_PATH_TO_TEXT_PATH,
+}
+
+unsafe impl Protocol for EFI_DEVICE_PATH_TO_TEXT_PROTOCOL {
+    const GUID: EFI_GUID = EFI_DEVICE_PATH_TO_TEXT_PROTOCOL_GUID;
+}
+
+impl EFI_DEVICE_PATH_TO_TEXT_PROTOCOL {
+    /// Converts a device node to its text representation.
+    ///
+    /// # Parameters
+    ///
+    /// ## `DeviceNode`
+    ///
+    /// Points to the device node to be converted.
+    ///
+    /// ## `DisplayOnly`
+    ///
+    /// If `DisplayOnly` is `TRUE`, then the shorter text representation of the display node is
+    /// used, where applicable. If `DisplayOnly` is `FALSE`, then the longer text representation
+    /// of the display node is used.
+    ///
+    /// ## `AllowShortcuts`
+    ///
+    /// If `AllowShortcuts` is `TRUE`, then the shortcut forms of text representation for a
+    /// device node can be used, where applicable.
+    ///
+    /// # Return Value
+    ///
+    /// A pointer to the allocated null-terminated text representation of the device node, or
+    /// null if `DeviceNode` is null or there was insufficient memory.
+    pub unsafe fn ConvertDeviceNodeToText(
+        &self,
+        DeviceNode: *const EFI_DEVICE_PATH_PROTOCOL,
+        DisplayOnly: BOOLEAN,
+        AllowShortcuts: BOOLEAN,
+    ) -> *mut CHAR16 {
+        (self.ConvertDeviceNodeToText)(DeviceNode, DisplayOnly, AllowShortcuts)
+    }
+
+    /// Converts a device path to its text representation.
+    ///
+    /// # Parameters
+    ///
+    /// ## `DevicePath`
+    ///
+    /// Points to the device path to be converted.
+    ///
+    /// ## `DisplayOnly`
+    ///
+    /// If `DisplayOnly` is `TRUE`, then the shorter text representation of the display node is
+    /// used, where applicable. If `DisplayOnly` is `FALSE`, then the longer text representation
+    /// of the display node is used.
+    ///
+    /// ## `AllowShortcuts`
+    ///
+    /// If `AllowShortcuts` is `TRUE`, then the shortcut forms of text representation for a
+    /// device node can be used, where applicable.
+    ///
+    /// # Return Value
+    ///
+    /// A pointer to the allocated null-terminated text representation of the device path, or
+    /// null if `DevicePath` is null or there was insufficient memory.
+    pub unsafe fn ConvertDevicePathToText(
+        &self,
+        DevicePath: *const EFI_DEVICE_PATH_PROTOCOL,
+        DisplayOnly: BOOLEAN,
+        AllowShortcuts: BOOLEAN,
+    ) -> *mut CHAR16 {
+        (self.ConvertDevicePathToText)(DevicePath, DisplayOnly, AllowShortcuts)
+    }
+}
+
+impl<'a> DevicePath<'a> {
+    /// Returns a value implementing [`Display`] that formats the text representation of the
+    /// device path.
+    ///
+    /// Nodes are separated by `/` and instances by `,`. If `DisplayOnly` is `true`, then the
+    /// shorter text representation of nodes is used, where applicable.
+    ///
+    /// [`Display`]: core::fmt::Display
+    pub fn display(&self, DisplayOnly: bool) -> DevicePathDisplay<'a> {
+        DevicePathDisplay {
+            path: *self,
+            display_only: DisplayOnly,
+        }
+    }
+}
+
+impl<'a> DevicePathNode<'a> {
+    /// Returns a value implementing [`Display`] that formats the text representation of the
+    /// device node.
+    ///
+    /// If `DisplayOnly` is `true`, then the shorter text representation of the node is used,
+    /// where applicable.
+    ///
+    /// [`Display`]: core::fmt::Display
+    pub fn display(&self, DisplayOnly: bool) -> DevicePathNodeDisplay<'a> {
+        DevicePathNodeDisplay {
+            node: *self,
+            display_only: DisplayOnly,
+        }
+    }
+}
+
+/// Formats the text representation of a device path, as returned by [`DevicePath::display()`].
+///
+/// [`DevicePath::display()`]: crate::protocols::device_path::DevicePath::display
+#[derive(Clone, Copy)]
+pub struct DevicePathDisplay<'a> {
+    path: DevicePath<'a>,
+    display_only: bool,
+}
+
+impl Display for DevicePathDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for node in self.path.nodes() {
+            if node.is_end_instance() {
+                f.write_char(',')?;
+                first = true;
+                continue;
+            }
+
+            if !first {
+                f.write_char('/')?;
+            }
+            first = false;
+
+            fmt_node(f, node, self.display_only)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats the text representation of a device node, as returned by
+/// [`DevicePathNode::display()`].
+///
+/// [`DevicePathNode::display()`]: crate::protocols::device_path::DevicePathNode::display
+#[derive(Clone, Copy)]
+pub struct DevicePathNodeDisplay<'a> {
+    node: DevicePathNode<'a>,
+    display_only: bool,
+}
+
+impl Display for DevicePathNodeDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt_node(f, self.node, self.display_only)
+    }
+}
+
+fn fmt_node(f: &mut Formatter<'_>, node: DevicePathNode<'_>, display_only: bool) -> fmt::Result {
+    use DevicePathNodeKind::*;
+
+    match node.kind() {
+        Pci(n) => write!(f, "Pci(0x{:X},0x{:X})", { n.Device }, { n.Function }),
+        Pccard(n) => write!(f, "PcCard(0x{:X})", { n.FunctionNumber }),
+        MemoryMapped(n) => write!(
+            f,
+            "MemoryMapped(0x{:X},0x{:X},0x{:X})",
+            { n.MemoryType },
+            { n.StartingAddress },
+            { n.EndingAddress }
+        ),
+        HardwareVendor(n, data) => fmt_vendor(f, "Hw", n.Guid, data),
+        Controller(n) => write!(f, "Ctrl(0x{:X})", { n.ControllerNumber }),
+        Bmc(n) => write!(f, "BMC(0x{:X},0x{:X})", { n.InterfaceType }, {
+            n.BaseAddress
+        }),
+        Acpi(n) => fmt_acpi(f, n.HID, n.UID),
+        ExpandedAcpi(n, data) => fmt_acpi_ex(f, n, data, display_only),
+        Adr(n, data) => {
+            write!(f, "AcpiAdr(0x{:X}", { n.ADR })?;
+            for adr in data.chunks_exact(4) {
+                write!(
+                    f,
+                    ",0x{:X}",
+                    u32::from_le_bytes([adr[0], adr[1], adr[2], adr[3]])
+                )?;
+            }
+            f.write_char(')')
+        }
+        Nvdimm(n) => write!(f, "NvdimmAcpiAdr(0x{:X})", { n.NFITDeviceHandle }),
+        Atapi(n) => {
+            if display_only {
+                write!(f, "Ata(0x{:X})", { n.Lun })
+            } else {
+                write!(
+                    f,
+                    "Ata({},{},0x{:X})",
+                    if n.PrimarySecondary != 0 {
+                        "Secondary"
+                    } else {
+                        "Primary"
+                    },
+                    if n.SlaveMaster != 0 {
+                        "Slave"
+                    } else {
+                        "Master"
+                    },
+                    { n.Lun }
+                )
+            }
+        }
+        Scsi(n) => write!(f, "Scsi(0x{:X},0x{:X})", { n.Pun }, { n.Lun }),
+        FibreChannel(n) => write!(f, "Fibre(0x{:X},0x{:X})", { n.WWN }, { n.Lun }),
+        FibreChannelEx(n) => {
+            f.write_str("FibreEx(0x")?;
+            fmt_hex(f, &{ n.WWN })?;
+            f.write_str(",0x")?;
+            fmt_hex(f, &{ n.Lun })?;
+            f.write_char(')')
+        }
+        F1394(n) => write!(f, "I1394({:016X})", { n.Guid }),
+        Usb(n) => write!(f, "USB(0x{:X},0x{:X})", { n.ParentPortNumber }, {
+            n.InterfaceNumber
+        }),
+        UsbClass(n) => fmt_usb_class(f, n),
+        UsbWwid(n, data) => {
+            write!(
+                f,
+                "UsbWwid(0x{:X},0x{:X},0x{:X},\"",
+                { n.VendorId },
+                { n.ProductId },
+                { n.InterfaceNumber }
+            )?;
+            fmt_ucs2(f, data)?;
+            f.write_str("\")")
+        }
+        LogicalUnit(n) => write!(f, "Unit(0x{:X})", { n.Lun }),
+        Sata(n) => write!(
+            f,
+            "Sata(0x{:X},0x{:X},0x{:X})",
+            { n.HBAPortNumber },
+            { n.PortMultiplierPortNumber },
+            { n.Lun }
+        ),
+        Iscsi(n, data) => {
+            let options = n.LoginOption;
+            f.write_str("iSCSI(")?;
+            fmt_ascii(f, data)?;
+            write!(f, ",0x{:X},0x", { n.TargetPortalGroupTag })?;
+            fmt_hex(f, &{ n.Lun }.to_le_bytes())?;
+            write!(
+                f,
+                ",{},{},{},{})",
+                if options & 0x0002 != 0 {
+                    "CRC32C"
+                } else {
+                    "None"
+                },
+                if options & 0x0008 != 0 {
+                    "CRC32C"
+                } else {
+                    "None"
+                },
+                if options & 0x0800 != 0 {
+                    "None"
+                } else if options & 0x1000 != 0 {
+                    "CHAP_UNI"
+                } else {
+                    "CHAP_BI"
+                },
+                if n.NetworkProtocol == 0 {
+                    "TCP"
+                } else {
+                    "reserved"
+                }
+            )
+        }
+        I2O(n) => write!(f, "I2O(0x{:X})", { n.Tid }),
+        InfiniBand(n) => {
+            write!(f, "Infiniband(0x{:X},", { n.ResourceFlags })?;
+            fmt_guid(f, EFI_GUID::from_bytes(n.PortGid))?;
+            write!(
+                f,
+                ",0x{:X},0x{:X},0x{:X})",
+                { n.ServiceId },
+                { n.TargetPortId },
+                { n.DeviceId }
+            )
+        }
+        MessagingVendor(n, data) => fmt_messaging_vendor(f, node, n.Guid, data),
+        MacAddress(n) => {
+            let len = if n.IfType == 0 || n.IfType == 1 {
+                6
+            } else {
+                32
+            };
+            f.write_str("MAC(")?;
+            fmt_hex(f, &n.MacAddress.Addr[..len])?;
+            write!(f, ",0x{:X})", n.IfType)
+        }
+        IPv4(n) => {
+            // Like EDK2, the gateway and subnet mask are only printed for a node of exactly the
+            // current size, even when unset.
+            let gateway = (node.length() == mem::size_of::<IPv4_DEVICE_PATH>())
+                .then_some((n.GatewayIpAddress, n.SubnetMask));
+            // SAFETY: both structures are packed, and the legacy node is a prefix of the current
+            // one.
+            let legacy = unsafe { &*(n as *const IPv4_DEVICE_PATH).cast() };
+            fmt_ipv4_node(f, legacy, gateway, display_only)
+        }
+        IPv4Legacy(n) => fmt_ipv4_node(f, n, None, display_only),
+        IPv6(n) => {
+            f.write_str("IPv6(")?;
+            fmt_ipv6(f, &n.RemoteIpAddress.Addr)?;
+            if !display_only {
+                f.write_char(',')?;
+                fmt_protocol(f, n.Protocol)?;
+                f.write_str(match n.IpAddressOrigin {
+                    0 => ",Static,",
+                    1 => ",StatelessAutoConfigure,",
+                    _ => ",StatefulAutoConfigure,",
+                })?;
+                fmt_ipv6(f, &n.LocalIpAddress.Addr)?;
+                write!(f, ",0x{:X},", n.PrefixLength)?;
+                fmt_ipv6(f, &n.GatewayIpAddress.Addr)?;
+            }
+            f.write_char(')')
+        }
+        Vlan(n) => write!(f, "Vlan({})", { n.VlanId }),
+        Uart(n) => {
+            f.write_str("Uart(")?;
+            match n.BaudRate {
+                0 => f.write_str("DEFAULT,")?,
+                baud => write!(f, "{},", baud)?,
+            }
+            match n.DataBits {
+                0 => f.write_str("DEFAULT,")?,
+                bits => write!(f, "{},", bits)?,
+            }
+            f.write_char(match n.Parity {
+                0 => 'D',
+                1 => 'N',
+                2 => 'E',
+                3 => 'O',
+                4 => 'M',
+                5 => 'S',
+                _ => 'x',
+            })?;
+            f.write_str(match n.StopBits {
+                0 => ",D)",
+                1 => ",1)",
+                2 => ",1.5)",
+                3 => ",2)",
+                _ => ",x)",
+            })
+        }
+        SasEx(n) => {
+            f.write_str("SasEx(0x")?;
+            fmt_hex(f, &{ n.SasAddress })?;
+            f.write_str(",0x")?;
+            fmt_hex(f, &{ n.Lun })?;
+            write!(f, ",0x{:X},", { n.RelativeTargetPort })?;
+            fmt_sas_topology(f, n.DeviceTopology)?;
+            f.write_char(')')
+        }
+        NvmeNamespace(n) => {
+            write!(f, "NVMe(0x{:X},", { n.NamespaceId })?;
+            for (i, byte) in { n.NamespaceUuid }.to_be_bytes().iter().enumerate() {
+                if i != 0 {
+                    f.write_char('-')?;
+                }
+                write!(f, "{:02X}", byte)?;
+            }
+            f.write_char(')')
+        }
+        Uri(_, data) => {
+            f.write_str("Uri(")?;
+            fmt_ascii(f, data)?;
+            f.write_char(')')
+        }
+        Ufs(n) => write!(f, "UFS(0x{:X},0x{:X})", n.Pun, n.Lun),
+        Sd(n) => write!(f, "SD(0x{:X})", n.SlotNumber),
+        Bluetooth(n) => {
+            f.write_str("Bluetooth(")?;
+            fmt_hex(f, &n.BD_ADDR)?;
+            f.write_char(')')
+        }
+        Wifi(n) => {
+            f.write_str("Wi-Fi(")?;
+            fmt_ascii(f, &n.SSId)?;
+            f.write_char(')')
+        }
+        Emmc(n) => write!(f, "eMMC(0x{:X})", n.SlotNumber),
+        BluetoothLe(n) => {
+            f.write_str("BluetoothLE(")?;
+            fmt_hex(f, &n.Address)?;
+            write!(f, ",0x{:02X})", n.Type)
+        }
+        Dns(n, data) => {
+            f.write_str("Dns(")?;
+            for (i, address) in data.chunks_exact(16).enumerate() {
+                if i != 0 {
+                    f.write_char(',')?;
+                }
+                if n.IsIPv6 != 0 {
+                    fmt_ipv6(f, address)?;
+                } else {
+                    fmt_ipv4(f, &address[..4])?;
+                }
+            }
+            f.write_char(')')
+        }
+        HardDrive(n) => {
+            write!(f, "HD({},", { n.PartitionNumber })?;
+            match n.SignatureType {
+                SIGNATURE_TYPE_MBR => {
+                    let signature = [
+                        n.Signature[0],
+                        n.Signature[1],
+                        n.Signature[2],
+                        n.Signature[3],
+                    ];
+                    write!(f, "MBR,0x{:08X}", u32::from_le_bytes(signature))?;
+                }
+                SIGNATURE_TYPE_GUID => {
+                    f.write_str("GPT,")?;
+                    fmt_guid(f, EFI_GUID::from_bytes(n.Signature))?;
+                }
+                other => write!(f, "{},0", other)?,
+            }
+            if !display_only {
+                write!(f, ",0x{:X},0x{:X}", { n.PartitionStart }, {
+                    n.PartitionSize
+                })?;
+            }
+            f.write_char(')')
+        }
+        CdRom(n) => {
+            write!(f, "CDROM(0x{:X}", { n.BootEntry })?;
+            if !display_only {
+                write!(f, ",0x{:X},0x{:X}", { n.PartitionStart }, {
+                    n.PartitionSize
+                })?;
+            }
+            f.write_char(')')
+        }
+        MediaVendor(n, data) => fmt_vendor(f, "Media", n.Guid, data),
+        FilePath(_, data) => fmt_ucs2(f, data),
+        MediaProtocol(n) => {
+            f.write_str("Media(")?;
+            fmt_guid(f, n.Protocol)?;
+            f.write_char(')')
+        }
+        PiwgFirmwareFile(n) => {
+            f.write_str("FvFile(")?;
+            fmt_guid(f, n.FvFileName)?;
+            f.write_char(')')
+        }
+        PiwgFirmwareVolume(n) => {
+            f.write_str("Fv(")?;
+            fmt_guid(f, n.FvName)?;
+            f.write_char(')')
+        }
+        RelativeOffsetRange(n) => write!(f, "Offset(0x{:X},0x{:X})", { n.StartingOffset }, {
+            n.EndingOffset
+        }),
+        RamDisk(n) => {
+            let name = match n.TypeGuid {
+                EFI_VIRTUAL_DISK_GUID => "VirtualDisk",
+                EFI_VIRTUAL_CD_GUID => "VirtualCD",
+                EFI_PERSISTENT_VIRTUAL_DISK_GUID => "PersistentVirtualDisk",
+                EFI_PERSISTENT_VIRTUAL_CD_GUID => "PersistentVirtualCD",
+                _ => "RamDisk",
+            };
+            write!(
+                f,
+                "{}(0x{:X},0x{:X},{}",
+                name,
+                { n.StartingAddr },
+                { n.EndingAddr },
+                { n.Instance }
+            )?;
+            if name == "RamDisk" {
+                f.write_char(',')?;
+                fmt_guid(f, n.TypeGuid)?;
+            }
+            f.write_char(')')
+        }
+        Bbs(n, data) => {
+            f.write_str("BBS(")?;
+            match n.DeviceType {
+                BBS_TYPE_FLOPPY => f.write_str("Floppy")?,
+                BBS_TYPE_HARDDRIVE => f.write_str("HD")?,
+                BBS_TYPE_CDROM => f.write_str("CDROM")?,
+                BBS_TYPE_PCMCIA => f.write_str("PCMCIA")?,
+                BBS_TYPE_USB => f.write_str("USB")?,
+                BBS_TYPE_EMBEDDED_NETWORK => f.write_str("Network")?,
+                other => write!(f, "0x{:X}", other)?,
+            }
+            f.write_char(',')?;
+            fmt_ascii(f, data)?;
+            if !display_only {
+                write!(f, ",0x{:X}", { n.StatusFlag })?;
+            }
+            f.write_char(')')
+        }
+        EndInstance => f.write_char(','),
+        EndEntire => Ok(()),
+        Unknown(node) => fmt_unknown(f, node),
+    }
+}
+
+fn fmt_unknown(f: &mut Formatter<'_>, node: DevicePathNode<'_>) -> fmt::Result {
+    match node.node_type() {
+        HARDWARE_DEVICE_PATH => write!(f, "HardwarePath({}", node.sub_type())?,
+        ACPI_DEVICE_PATH => write!(f, "AcpiPath({}", node.sub_type())?,
+        MESSAGING_DEVICE_PATH => write!(f, "Msg({}", node.sub_type())?,
+        MEDIA_DEVICE_PATH => write!(f, "MediaPath({}", node.sub_type())?,
+        BBS_DEVICE_PATH => write!(f, "BbsPath({}", node.sub_type())?,
+        other => write!(f, "Path({},{}", other, node.sub_type())?,
+    }
+
+    if !node.data().is_empty() {
+        f.write_char(',')?;
+        fmt_hex(f, node.data())?;
+    }
+
+    f.write_char(')')
+}
+
+fn fmt_vendor(f: &mut Formatter<'_>, kind: &str, guid: EFI_GUID, data: &[u8]) -> fmt::Result {
+    write!(f, "Ven{}(", kind)?;
+    fmt_guid(f, guid)?;
+    if !data.is_empty() {
+        f.write_char(',')?;
+        fmt_hex(f, data)?;
+    }
+    f.write_char(')')
+}
+
+fn fmt_messaging_vendor(
+    f: &mut Formatter<'_>,
+    node: DevicePathNode<'_>,
+    guid: EFI_GUID,
+    data: &[u8],
+) -> fmt::Result {
+    match guid {
+        EFI_PC_ANSI_GUID => f.write_str("VenPcAnsi()"),
+        EFI_VT_100_GUID => f.write_str("VenVt100()"),
+        EFI_VT_100_PLUS_GUID => f.write_str("VenVt100Plus()"),
+        EFI_VT_UTF8_GUID => f.write_str("VenUtf8()"),
+        EFI_DEBUGPORT_PROTOCOL_GUID => f.write_str("DebugPort()"),
+        DEVICE_PATH_MESSAGING_UART_FLOW_CONTROL => {
+            match node.cast::<UART_FLOW_CONTROL_DEVICE_PATH>() {
+                Some(n) => f.write_str(match n.FlowControlMap & 0x3 {
+                    0 => "UartFlowCtrl(None)",
+                    1 => "UartFlowCtrl(Hardware)",
+                    2 => "UartFlowCtrl(XonXoff)",
+                    _ => "UartFlowCtrl()",
+                }),
+                None => fmt_vendor(f, "Msg", guid, data),
+            }
+        }
+        EFI_SAS_DEVICE_PATH_GUID => match node.cast::<SAS_DEVICE_PATH>() {
+            Some(n) => {
+                write!(
+                    f,
+                    "SAS(0x{:X},0x{:X},0x{:X},",
+                    { n.SasAddress },
+                    { n.Lun },
+                    { n.RelativeTargetPort }
+                )?;
+                fmt_sas_topology(f, n.DeviceTopology)?;
+                write!(f, ",0x{:X})", { n.Reserved })
+            }
+            None => fmt_vendor(f, "Msg", guid, data),
+        },
+        _ => fmt_vendor(f, "Msg", guid, data),
+    }
+}
+
+fn fmt_acpi(f: &mut Formatter<'_>, hid: u32, uid: u32) -> fmt::Result {
+    if !IS_PNP_ID(hid) {
+        return write!(f, "Acpi(0x{:08X},0x{:X})", hid, uid);
+    }
+
+    match EISA_ID_TO_NUM(hid) {
+        PNP_PCI_ROOT => write!(f, "PciRoot(0x{:X})", uid),
+        PNP_PCIE_ROOT => write!(f, "PcieRoot(0x{:X})", uid),
+        0x0604 => write!(f, "Floppy(0x{:X})", uid),
+        0x0301 => write!(f, "Keyboard(0x{:X})", uid),
+        0x0501 => write!(f, "Serial(0x{:X})", uid),
+        0x0401 => write!(f, "ParallelPort(0x{:X})", uid),
+        id => write!(f, "Acpi(PNP{:04X},0x{:X})", id, uid),
+    }
+}
+
+fn fmt_acpi_ex(
+    f: &mut Formatter<'_>,
+    n: &ACPI_EXTENDED_HID_DEVICE_PATH,
+    data: &[u8],
+    display_only: bool,
+) -> fmt::Result {
+    let (hid, uid, cid) = (n.HID, n.UID, n.CID);
+    let mut strings = data.split(|&b| b == 0);
+    let hid_str = strings.next().unwrap_or_default();
+    let uid_str = strings.next().unwrap_or_default();
+    let cid_str = strings.next().unwrap_or_default();
+
+    if display_only
+        && IS_PNP_ID(hid)
+        && (EISA_ID_TO_NUM(hid) == PNP_PCI_ROOT || EISA_ID_TO_NUM(hid) == PNP_PCIE_ROOT)
+        && hid_str.is_empty()
+        && cid_str.is_empty()
+    {
+        let name = if EISA_ID_TO_NUM(hid) == PNP_PCI_ROOT {
+            "PciRoot"
+        } else {
+            "PcieRoot"
+        };
+        write!(f, "{}(", name)?;
+        if uid_str.is_empty() {
+            write!(f, "0x{:X}", uid)?;
+        } else {
+            fmt_ascii(f, uid_str)?;
+        }
+        return f.write_char(')');
+    }
+
+    if hid_str.is_empty() && cid_str.is_empty() && (uid == 0 || uid_str.is_empty()) {
+        f.write_str("AcpiExp(")?;
+        fmt_eisa_id(f, hid)?;
+        f.write_char(',')?;
+        if cid == 0 {
+            f.write_char('0')?;
+        } else {
+            fmt_eisa_id(f, cid)?;
+        }
+        f.write_char(',')?;
+        if uid_str.is_empty() {
+            write!(f, "0x{:X}", uid)?;
+        } else {
+            fmt_ascii(f, uid_str)?;
+        }
+        return f.write_char(')');
+    }
+
+    f.write_str("AcpiEx(")?;
+    fmt_eisa_id(f, hid)?;
+    f.write_char(',')?;
+    fmt_eisa_id(f, cid)?;
+    write!(f, ",0x{:X},", uid)?;
+    fmt_ascii(f, hid_str)?;
+    f.write_char(',')?;
+    fmt_ascii(f, cid_str)?;
+    f.write_char(',')?;
+    fmt_ascii(f, uid_str)?;
+    f.write_char(')')
+}
+
+fn fmt_eisa_id(f: &mut Formatter<'_>, id: u32) -> fmt::Result {
+    for shift in [10, 5, 0] {
+        f.write_char((b'A' - 1 + ((id >> shift) & 0x1F) as u8) as char)?;
+    }
+    write!(f, "{:04X}", id >> 16)
+}
+
+fn fmt_usb_class(f: &mut Formatter<'_>, n: &USB_CLASS_DEVICE_PATH) -> fmt::Result {
+    let (vendor, product) = (n.VendorId, n.ProductId);
+    let (class, sub_class, protocol) = (n.DeviceClass, n.DeviceSubClass, n.DeviceProtocol);
+
+    let name = match class {
+        1 => "UsbAudio",
+        2 => "UsbCDCControl",
+        3 => "UsbHID",
+        6 => "UsbImage",
+        7 => "UsbPrinter",
+        8 => "UsbMassStorage",
+        9 => "UsbHub",
+        10 => "UsbCDCData",
+        11 => "UsbSmartCard",
+        14 => "UsbVideo",
+        0xDC => "UsbDiagnostic",
+        0xE0 => "UsbWireless",
+        0xFE => {
+            let name = match sub_class {
+                1 => "UsbDeviceFirmwareUpdate",
+                2 => "UsbIrdaBridge",
+                3 => "UsbTestAndMeasurement",
+                _ => "",
+            };
+            if !name.is_empty() {
+                return write!(
+                    f,
+                    "{}(0x{:X},0x{:X},0x{:X})",
+                    name, vendor, product, protocol
+                );
+            }
+            ""
+        }
+        _ => "",
+    };
+
+    if name.is_empty() {
+        write!(
+            f,
+            "UsbClass(0x{:X},0x{:X},0x{:X},0x{:X},0x{:X})",
+            vendor, product, class, sub_class, protocol
+        )
+    } else {
+        write!(
+            f,
+            "{}(0x{:X},0x{:X},0x{:X},0x{:X})",
+            name, vendor, product, sub_class, protocol
+        )
+    }
+}
+
+fn fmt_sas_topology(f: &mut Formatter<'_>, topology: u16) -> fmt::Result {
+    match topology & 0x0F {
+        0 => return f.write_str("NoTopology,0,0,0"),
+        1 | 2 => {}
+        _ => return write!(f, "0x{:X},0,0,0", topology),
+    }
+
+    f.write_str(if topology & 0x10 != 0 {
+        "SATA,"
+    } else {
+        "SAS,"
+    })?;
+    f.write_str(if topology & 0x20 != 0 {
+        "External,"
+    } else {
+        "Internal,"
+    })?;
+    f.write_str(if topology & 0x40 != 0 {
+        "Expanded,"
+    } else {
+        "Direct,"
+    })?;
+    if topology & 0x0F == 1 {
+        f.write_char('0')
+    } else {
+        write!(f, "{}", ((topology >> 8) & 0xFF) + 1)
+    }
+}
+
+fn fmt_protocol(f: &mut Formatter<'_>, protocol: u16) -> fmt::Result {
+    match protocol {
+        6 => f.write_str("TCP"),
+        17 => f.write_str("UDP"),
+        other => write!(f, "0x{:X}", other),
+    }
+}
+
+fn fmt_ipv4_node(
+    f: &mut Formatter<'_>,
+    n: &IPv4_LEGACY_DEVICE_PATH,
+    gateway: Option<(EFI_IPv4_ADDRESS, EFI_IPv4_ADDRESS)>,
+    display_only: bool,
+) -> fmt::Result {
+    f.write_str("IPv4(")?;
+    fmt_ipv4(f, &n.RemoteIpAddress.Addr)?;
+    if !display_only {
+        f.write_char(',')?;
+        fmt_protocol(f, n.Protocol)?;
+        f.write_str(if n.StaticIpAddress != 0 {
+            ",Static,"
+        } else {
+            ",DHCP,"
+        })?;
+        fmt_ipv4(f, &n.LocalIpAddress.Addr)?;
+        if let Some((gateway, subnet_mask)) = gateway {
+            f.write_char(',')?;
+            fmt_ipv4(f, &gateway.Addr)?;
+            f.write_char(',')?;
+            fmt_ipv4(f, &subnet_mask.Addr)?;
+        }
+    }
+    f.write_char(')')
+}
+
+fn fmt_ipv4(f: &mut Formatter<'_>, address: &[u8]) -> fmt::Result {
+    write!(
+        f,
+        "{}.{}.{}.{}",
+        address[0], address[1], address[2], address[3]
+    )
+}
+
+fn fmt_ipv6(f: &mut Formatter<'_>, address: &[u8]) -> fmt::Result {
+    for (i, group) in address.chunks_exact(2).enumerate() {
+        if i != 0 {
+            f.write_char(':')?;
+        }
+        write!(f, "{:02X}{:02X}", group[0], group[1])?;
+    }
+    Ok(())
+}
+
+fn fmt_guid(f: &mut Formatter<'_>, guid: EFI_GUID) -> fmt::Result {
+    write!(f, "{}", guid)
+}
+
+fn fmt_hex(f: &mut Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    bytes.iter().try_for_each(|b| write!(f, "{:02X}", b))
+}
+
+/// Writes a string of 8-bit characters, up to its first null character.
+fn fmt_ascii(f: &mut Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    bytes
+        .iter()
+        .take_while(|&&b| b != 0)
+        .try_for_each(|&b| f.write_char(b as char))
+}
+
+/// Writes a string of little-endian UCS-2 characters, up to its first null character.
+fn fmt_ucs2(f: &mut Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    let chars = bytes
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|&c| c != 0);
+    char::decode_utf16(chars)
+        .try_for_each(|c| f.write_char(c.unwrap_or(char::REPLACEMENT_CHARACTER)))
+}
+
+type EFI_DEVICE_PATH_TO_TEXT_NODE = extern "efiapi" fn(
+    DeviceNode: *const EFI_DEVICE_PATH_PROTOCOL,
+    DisplayOnly: BOOLEAN,
+    AllowShortcuts: BOOLEAN,
+) -> *mut CHAR16;
+
+type EFI_DEVICE_PATH_TO_TEXT_PATH = extern "efiapi" fn(
+    DevicePath: *const EFI_DEVICE_PATH_PROTOCOL,
+    DisplayOnly: BOOLEAN,
+    AllowShortcuts: BOOLEAN,
+) -> *mut CHAR16;
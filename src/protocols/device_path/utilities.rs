@@ -0,0 +1,294 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Device Path Utilities Protocol
+//!
+//! This module defines the Device Path Utilities Protocol, also known as the
+//! [`EFI_DEVICE_PATH_UTILITIES_PROTOCOL`], which creates and manipulates device paths and device
+//! nodes. See [Section 10.5 of the UEFI Specification] for more details.
+//!
+//! [`EFI_DEVICE_PATH_UTILITIES_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_UTILITIES_PROTOCOL
+//!
+//! [Section 10.5 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/10_Protocols_Device_Path_Protocol.html#device-path-utilities-protocol
+
+use crate::protocols::device_path::EFI_DEVICE_PATH_PROTOCOL;
+use crate::protocols::Protocol;
+use crate::types::{BOOLEAN, EFI_GUID, UINT16, UINT8, UINTN};
+
+/// GUID for the [`EFI_DEVICE_PATH_UTILITIES_PROTOCOL`].
+///
+/// [`EFI_DEVICE_PATH_UTILITIES_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_UTILITIES_PROTOCOL
+pub const EFI_DEVICE_PATH_UTILITIES_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0x0379BE4E,
+    0xD706,
+    0x437D,
+    [0xB0, 0x37, 0xED, 0xB8, 0x2F, 0xB7, 0x72, 0xA4],
+);
+
+/// The Device Path Utilities protocol creates and manipulates device paths and device nodes.
+///
+/// Every device path returned by this protocol is allocated from pool memory, and must be freed
+/// by the caller with [`EFI_BOOT_SERVICES.FreePool()`] once it is no longer needed.
+///
+/// [`EFI_BOOT_SERVICES.FreePool()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-freepool
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_DEVICE_PATH_UTILITIES_PROTOCOL {
+    GetDevicePathSize: EFI_DEVICE_PATH_UTILS_GET_DEVICE_PATH_SIZE,
+    DuplicateDevicePath: EFI_DEVICE_PATH_UTILS_DUP_DEVICE_PATH,
+    AppendDevicePath: EFI_DEVICE_PATH_UTILS_APPEND_PATH,
+    AppendDeviceNode: EFI_DEVICE_PATH_UTILS_APPEND_NODE,
+    AppendDevicePathInstance: EFI_DEVICE_PATH_UTILS_APPEND_INSTANCE,
+    GetNextDevicePathInstance: EFI_DEVICE_PATH_UTILS_GET_NEXT_INSTANCE,
+    IsDevicePathMultiInstance: EFI_DEVICE_PATH_UTILS_IS_MULTI_INSTANCE,
+    CreateDeviceNode: EFI_DEVICE_PATH_UTILS_CREATE_NODE,
+}
+
+unsafe impl Protocol for EFI_DEVICE_PATH_UTILITIES_PROTOCOL {
+    const GUID: EFI_GUID = EFI_DEVICE_PATH_UTILITIES_PROTOCOL_GUID;
+}
+
+impl EFI_DEVICE_PATH_UTILITIES_PROTOCOL {
+    /// Returns the size of the device path, in bytes.
+    ///
+    /// # Parameters
+    ///
+    /// ## `DevicePath`
+    ///
+    /// Points to the start of the device path.
+    ///
+    /// # Return Value
+    ///
+    /// The size of the specified device path, in bytes, including the end of entire device path
+    /// node. If `DevicePath` is null, then `0` is returned.
+    pub unsafe fn GetDevicePathSize(&self, DevicePath: *const EFI_DEVICE_PATH_PROTOCOL) -> UINTN {
+        (self.GetDevicePathSize)(DevicePath)
+    }
+
+    /// Creates a duplicate of the specified path.
+    ///
+    /// # Parameters
+    ///
+    /// ## `DevicePath`
+    ///
+    /// Points to the source device path.
+    ///
+    /// # Return Value
+    ///
+    /// A pointer to the duplicate device path, or null if there was insufficient memory or
+    /// `DevicePath` is null.
+    pub unsafe fn DuplicateDevicePath(
+        &self,
+        DevicePath: *const EFI_DEVICE_PATH_PROTOCOL,
+    ) -> *mut EFI_DEVICE_PATH_PROTOCOL {
+        (self.DuplicateDevicePath)(DevicePath)
+    }
+
+    /// Creates a new path by appending the second device path to the first.
+    ///
+    /// If `Src1` is null and `Src2` is non-null, then a duplicate of `Src2` is returned. If `Src1`
+    /// is non-null and `Src2` is null, then a duplicate of `Src1` is returned. If both are null,
+    /// then a copy of an end of entire device path is returned.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Src1`
+    ///
+    /// Points to the first device path.
+    ///
+    /// ## `Src2`
+    ///
+    /// Points to the second device path.
+    ///
+    /// # Return Value
+    ///
+    /// A pointer to the newly created device path, or null if memory could not be allocated.
+    pub unsafe fn AppendDevicePath(
+        &self,
+        Src1: *const EFI_DEVICE_PATH_PROTOCOL,
+        Src2: *const EFI_DEVICE_PATH_PROTOCOL,
+    ) -> *mut EFI_DEVICE_PATH_PROTOCOL {
+        (self.AppendDevicePath)(Src1, Src2)
+    }
+
+    /// Creates a new path by appending the device node to the device path.
+    ///
+    /// If `DeviceNode` is null, then a copy of `DevicePath` is returned. If `DevicePath` is null,
+    /// then a copy of `DeviceNode`, followed by an end of entire device path node, is returned.
+    /// If both are null, then a copy of an end of entire device path node is returned.
+    ///
+    /// # Parameters
+    ///
+    /// ## `DevicePath`
+    ///
+    /// Points to the device path.
+    ///
+    /// ## `DeviceNode`
+    ///
+    /// Points to the device node.
+    ///
+    /// # Return Value
+    ///
+    /// A pointer to the allocated device node, or null if there was insufficient memory.
+    pub unsafe fn AppendDeviceNode(
+        &self,
+        DevicePath: *const EFI_DEVICE_PATH_PROTOCOL,
+        DeviceNode: *const EFI_DEVICE_PATH_PROTOCOL,
+    ) -> *mut EFI_DEVICE_PATH_PROTOCOL {
+        (self.AppendDeviceNode)(DevicePath, DeviceNode)
+    }
+
+    /// Creates a new path by appending the specified device path instance to the specified device
+    /// path.
+    ///
+    /// # Parameters
+    ///
+    /// ## `DevicePath`
+    ///
+    /// Points to the device path. If null, then a copy of `DevicePathInstance` is returned.
+    ///
+    /// ## `DevicePathInstance`
+    ///
+    /// Points to the device path instance.
+    ///
+    /// # Return Value
+    ///
+    /// A pointer to the newly created device path, or null if memory could not be allocated or
+    /// `DevicePathInstance` is null.
+    pub unsafe fn AppendDevicePathInstance(
+        &self,
+        DevicePath: *const EFI_DEVICE_PATH_PROTOCOL,
+        DevicePathInstance: *const EFI_DEVICE_PATH_PROTOCOL,
+    ) -> *mut EFI_DEVICE_PATH_PROTOCOL {
+        (self.AppendDevicePathInstance)(DevicePath, DevicePathInstance)
+    }
+
+    /// Creates a copy of the current device path instance and returns a pointer to the next
+    /// device path instance.
+    ///
+    /// # Parameters
+    ///
+    /// ## `DevicePathInstance`
+    ///
+    /// On input, this holds the pointer to the current device path instance. On output, this
+    /// holds the pointer to the next device path instance, or null if there are no more device
+    /// path instances in the device path.
+    ///
+    /// ## `DevicePathInstanceSize`
+    ///
+    /// On output, this holds the size of the device path instance, in bytes, or zero if
+    /// `DevicePathInstance` is null. If null, then the instance size is not output.
+    ///
+    /// # Return Value
+    ///
+    /// A pointer to the copy of the current device path instance, or null if
+    /// `DevicePathInstance` was null on entry or there was insufficient memory.
+    pub unsafe fn GetNextDevicePathInstance(
+        &self,
+        DevicePathInstance: *mut *mut EFI_DEVICE_PATH_PROTOCOL,
+        DevicePathInstanceSize: *mut UINTN,
+    ) -> *mut EFI_DEVICE_PATH_PROTOCOL {
+        (self.GetNextDevicePathInstance)(DevicePathInstance, DevicePathInstanceSize)
+    }
+
+    /// Returns whether a device path is multi-instance.
+    ///
+    /// # Parameters
+    ///
+    /// ## `DevicePath`
+    ///
+    /// Points to the device path. If null, then `FALSE` is returned.
+    ///
+    /// # Return Value
+    ///
+    /// `TRUE` if the device path has more than one instance, `FALSE` if it is empty or contains
+    /// only a single instance.
+    pub unsafe fn IsDevicePathMultiInstance(
+        &self,
+        DevicePath: *const EFI_DEVICE_PATH_PROTOCOL,
+    ) -> BOOLEAN {
+        (self.IsDevicePathMultiInstance)(DevicePath)
+    }
+
+    /// Creates a device node.
+    ///
+    /// # Parameters
+    ///
+    /// ## `NodeType`
+    ///
+    /// The device node type for the new device node.
+    ///
+    /// ## `NodeSubType`
+    ///
+    /// The device node sub-type for the new device node.
+    ///
+    /// ## `NodeLength`
+    ///
+    /// The length of the device node, in bytes. It must be at least the size of the
+    /// [`EFI_DEVICE_PATH_PROTOCOL`] header.
+    ///
+    /// # Return Value
+    ///
+    /// A pointer to the newly created device node, with its data following the header zeroed, or
+    /// null if `NodeLength` is less than the size of the header or there was insufficient memory.
+    ///
+    /// [`EFI_DEVICE_PATH_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_PROTOCOL
+    pub unsafe fn CreateDeviceNode(
+        &self,
+        NodeType: UINT8,
+        NodeSubType: UINT8,
+        NodeLength: UINT16,
+    ) -> *mut EFI_DEVICE_PATH_PROTOCOL {
+        (self.CreateDeviceNode)(NodeType, NodeSubType, NodeLength)
+    }
+}
+
+type EFI_DEVICE_PATH_UTILS_GET_DEVICE_PATH_SIZE =
+    extern "efiapi" fn(DevicePath: *const EFI_DEVICE_PATH_PROTOCOL) -> UINTN;
+
+type EFI_DEVICE_PATH_UTILS_DUP_DEVICE_PATH = extern "efiapi" fn(
+    DevicePath: *const EFI_DEVICE_PATH_PROTOCOL,
+) -> *mut EFI_DEVICE_PATH_PROTOCOL;
+
+type EFI_DEVICE_PATH_UTILS_APPEND_PATH = extern "efiapi" fn(
+    Src1: *const EFI_DEVICE_PATH_PROTOCOL,
+    Src2: *const EFI_DEVICE_PATH_PROTOCOL,
+) -> *mut EFI_DEVICE_PATH_PROTOCOL;
+
+type EFI_DEVICE_PATH_UTILS_APPEND_NODE = extern "efiapi" fn(
+    DevicePath: *const EFI_DEVICE_PATH_PROTOCOL,
+    DeviceNode: *const EFI_DEVICE_PATH_PROTOCOL,
+) -> *mut EFI_DEVICE_PATH_PROTOCOL;
+
+type EFI_DEVICE_PATH_UTILS_APPEND_INSTANCE = extern "efiapi" fn(
+    DevicePath: *const EFI_DEVICE_PATH_PROTOCOL,
+    DevicePathInstance: *const EFI_DEVICE_PATH_PROTOCOL,
+) -> *mut EFI_DEVICE_PATH_PROTOCOL;
+
+type EFI_DEVICE_PATH_UTILS_GET_NEXT_INSTANCE = extern "efiapi" fn(
+    DevicePathInstance: *mut *mut EFI_DEVICE_PATH_PROTOCOL,
+    DevicePathInstanceSize: *mut UINTN,
+) -> *mut EFI_DEVICE_PATH_PROTOCOL;
+
+type EFI_DEVICE_PATH_UTILS_IS_MULTI_INSTANCE =
+    extern "efiapi" fn(DevicePath: *const EFI_DEVICE_PATH_PROTOCOL) -> BOOLEAN;
+
+type EFI_DEVICE_PATH_UTILS_CREATE_NODE = extern "efiapi" fn(
+    NodeType: UINT8,
+    NodeSubType: UINT8,
+    NodeLength: UINT16,
+) -> *mut EFI_DEVICE_PATH_PROTOCOL;
@@ -0,0 +1,502 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # Owned File Handles
+//!
+//! This module defines [`File`], an owned handle to an [`EFI_FILE_PROTOCOL`] instance that is
+//! closed when dropped. [`File`] implements the [`Read`], [`Write`] and [`Seek`] traits of the
+//! [`io`] module.
+//!
+//! [`File`]: crate::protocols::file_system::File
+//! [`EFI_FILE_PROTOCOL`]: crate::protocols::file_system::EFI_FILE_PROTOCOL
+//! [`Read`]: crate::io::Read
+//! [`Write`]: crate::io::Write
+//! [`Seek`]: crate::io::Seek
+//! [`io`]: crate::io
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::mem;
+use core::ptr::{self, NonNull};
+
+use crate::io::{offset_position, Read, Seek, SeekFrom, Write};
+#[cfg(feature = "alloc")]
+use crate::protocols::file_system::info::FileInfoBuf;
+use crate::protocols::file_system::info::{
+    FileInfo, FileSystemInfo, EFI_FILE_INFO_ID, EFI_FILE_SYSTEM_INFO_ID,
+};
+use crate::protocols::file_system::{
+    EFI_FILE_MODE_READ, EFI_FILE_POSITION_END_OF_FILE, EFI_FILE_PROTOCOL,
+};
+use crate::status::{
+    EFI_BUFFER_TOO_SMALL, EFI_INVALID_PARAMETER, EFI_SUCCESS, EFI_WARN_DELETE_FAILURE,
+};
+use crate::types::{CHAR16, EFI_GUID, EFI_STATUS, UINT64, UINT8, UINTN};
+
+/// The longest path component, in characters, accepted by [`File::open()`] and
+/// [`File::open_path()`].
+///
+/// [`File::open()`]: crate::protocols::file_system::File::open
+/// [`File::open_path()`]: crate::protocols::file_system::File::open_path
+pub const MAX_FILE_NAME_LENGTH: usize = 255;
+
+/// An owned handle to an open file or directory.
+///
+/// The handle is closed with [`EFI_FILE_PROTOCOL.Close()`] when the [`File`] is dropped. Use
+/// [`File::close()`] to observe the status of closing the handle.
+///
+/// [`EFI_FILE_PROTOCOL.Close()`]: crate::protocols::file_system::EFI_FILE_PROTOCOL::Close
+/// [`File`]: crate::protocols::file_system::File
+/// [`File::close()`]: crate::protocols::file_system::File::close
+pub struct File {
+    protocol: NonNull<EFI_FILE_PROTOCOL>,
+}
+
+impl File {
+    /// Takes ownership of a file handle returned by the firmware.
+    ///
+    /// # Safety
+    ///
+    /// `Protocol` must be a valid, non-null, open file handle that is not closed elsewhere.
+    pub unsafe fn from_raw(Protocol: *mut EFI_FILE_PROTOCOL) -> Self {
+        Self {
+            protocol: NonNull::new_unchecked(Protocol),
+        }
+    }
+
+    /// Releases ownership of the file handle without closing it.
+    pub fn into_raw(self) -> *mut EFI_FILE_PROTOCOL {
+        let protocol = self.protocol.as_ptr();
+        mem::forget(self);
+        protocol
+    }
+
+    /// Returns the underlying file handle.
+    pub fn as_ptr(&self) -> *mut EFI_FILE_PROTOCOL {
+        self.protocol.as_ptr()
+    }
+
+    fn protocol(&mut self) -> &mut EFI_FILE_PROTOCOL {
+        // SAFETY: the handle is valid and open for the lifetime of `self`.
+        unsafe { self.protocol.as_mut() }
+    }
+
+    /// Opens the file named by the null-terminated `FileName`, relative to this file's location.
+    ///
+    /// See [`EFI_FILE_PROTOCOL.Open()`] for the meaning of `OpenMode` and `Attributes`.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if `FileName` is not null-terminated.
+    ///
+    /// [`EFI_FILE_PROTOCOL.Open()`]: crate::protocols::file_system::EFI_FILE_PROTOCOL::Open
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub fn open_ucs2(
+        &mut self,
+        FileName: &[CHAR16],
+        OpenMode: UINT64,
+        Attributes: UINT64,
+    ) -> Result<File, EFI_STATUS> {
+        if !FileName.contains(&0) {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        let mut handle = ptr::null_mut();
+        // SAFETY: `FileName` is null-terminated and `handle` is valid for writes.
+        match unsafe {
+            self.protocol()
+                .Open(&mut handle, FileName.as_ptr(), OpenMode, Attributes)
+        } {
+            EFI_SUCCESS if !handle.is_null() => Ok(unsafe { File::from_raw(handle) }),
+            EFI_SUCCESS => Err(EFI_INVALID_PARAMETER),
+            status => Err(status),
+        }
+    }
+
+    /// Opens the file named by `FileName`, relative to this file's location.
+    ///
+    /// `FileName` is passed to the firmware as is, so it may contain `\` separators, `.` and
+    /// `..`. Returns [`EFI_INVALID_PARAMETER`] if it is longer than [`MAX_FILE_NAME_LENGTH`]
+    /// characters or contains characters outside of UCS-2.
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`MAX_FILE_NAME_LENGTH`]: crate::protocols::file_system::file::MAX_FILE_NAME_LENGTH
+    pub fn open(
+        &mut self,
+        FileName: &str,
+        OpenMode: UINT64,
+        Attributes: UINT64,
+    ) -> Result<File, EFI_STATUS> {
+        let mut name = [0; MAX_FILE_NAME_LENGTH + 1];
+        encode_name(FileName, &mut name)?;
+        self.open_ucs2(&name, OpenMode, Attributes)
+    }
+
+    /// Opens the file at `Path`, relative to this file's location, one path component at a time.
+    ///
+    /// Components may be separated by either `/` or `\`, and a leading separator starts from the
+    /// root directory of the volume. Intermediate directories are opened read-only and closed
+    /// once the next component is open; `OpenMode` and `Attributes` only apply to the last
+    /// component. An empty path opens this file again.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if a component is longer than
+    /// [`MAX_FILE_NAME_LENGTH`] characters or contains characters outside of UCS-2.
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`MAX_FILE_NAME_LENGTH`]: crate::protocols::file_system::file::MAX_FILE_NAME_LENGTH
+    pub fn open_path(
+        &mut self,
+        Path: &str,
+        OpenMode: UINT64,
+        Attributes: UINT64,
+    ) -> Result<File, EFI_STATUS> {
+        let mut components = Path.split(['/', '\\']).filter(|c| !c.is_empty()).peekable();
+        let mut current = if Path.starts_with(['/', '\\']) {
+            let (mode, attributes) = match components.peek() {
+                Some(_) => (EFI_FILE_MODE_READ, 0),
+                None => (OpenMode, Attributes),
+            };
+            Some(self.open("\\", mode, attributes)?)
+        } else {
+            None
+        };
+
+        while let Some(component) = components.next() {
+            let (mode, attributes) = match components.peek() {
+                Some(_) => (EFI_FILE_MODE_READ, 0),
+                None => (OpenMode, Attributes),
+            };
+            let parent = match current.as_mut() {
+                Some(parent) => parent,
+                None => &mut *self,
+            };
+            current = Some(parent.open(component, mode, attributes)?);
+        }
+
+        match current {
+            Some(file) => Ok(file),
+            None => self.open(".", OpenMode, Attributes),
+        }
+    }
+
+    /// Closes the file, flushing any cached data to the device.
+    pub fn close(self) -> Result<(), EFI_STATUS> {
+        let protocol = self.into_raw();
+        // SAFETY: ownership of the open handle was released by `into_raw()`.
+        match unsafe { (*protocol).Close() } {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Closes and deletes the file.
+    ///
+    /// The handle is closed even if the file could not be deleted, in which case
+    /// [`EFI_WARN_DELETE_FAILURE`] is returned.
+    ///
+    /// [`EFI_WARN_DELETE_FAILURE`]: crate::status::EFI_WARN_DELETE_FAILURE
+    pub fn delete(self) -> Result<(), EFI_STATUS> {
+        let protocol = self.into_raw();
+        // SAFETY: ownership of the open handle was released by `into_raw()`.
+        match unsafe { (*protocol).Delete() } {
+            EFI_SUCCESS => Ok(()),
+            EFI_WARN_DELETE_FAILURE => Err(EFI_WARN_DELETE_FAILURE),
+            status => Err(status),
+        }
+    }
+
+    /// Returns the current position within the file.
+    pub fn position(&mut self) -> Result<UINT64, EFI_STATUS> {
+        let mut position = 0;
+        // SAFETY: `position` is valid for writes.
+        match unsafe { self.protocol().GetPosition(&mut position) } {
+            EFI_SUCCESS => Ok(position),
+            status => Err(status),
+        }
+    }
+
+    /// Sets the current position within the file. For directories, only a position of `0` is
+    /// allowed, which restarts the reading of directory entries.
+    pub fn set_position(&mut self, Position: UINT64) -> Result<(), EFI_STATUS> {
+        // SAFETY: the handle is valid.
+        match unsafe { self.protocol().SetPosition(Position) } {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Returns the size of the file in bytes, leaving the position unchanged.
+    pub fn size(&mut self) -> Result<UINT64, EFI_STATUS> {
+        let position = self.position()?;
+        self.set_position(EFI_FILE_POSITION_END_OF_FILE)?;
+        let size = self.position();
+        self.set_position(position)?;
+        size
+    }
+
+    /// Reads the information of type `InformationType` into `Buffer`, returning the number of
+    /// bytes written.
+    ///
+    /// Returns [`EFI_BUFFER_TOO_SMALL`] if `Buffer` cannot hold the information; use
+    /// [`info_size()`] to find out the required size. Since some implementations write the
+    /// structure in place, `Buffer` should be 8-byte aligned.
+    ///
+    /// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+    /// [`info_size()`]: ./struct.File.html#method.info_size
+    pub fn get_info(
+        &mut self,
+        InformationType: &EFI_GUID,
+        Buffer: &mut [UINT8],
+    ) -> Result<usize, EFI_STATUS> {
+        let mut size: UINTN = Buffer.len();
+        // SAFETY: `Buffer` is valid for writes of `size` bytes.
+        match unsafe {
+            self.protocol()
+                .GetInfo(InformationType, &mut size, Buffer.as_mut_ptr().cast())
+        } {
+            EFI_SUCCESS => Ok(size),
+            status => Err(status),
+        }
+    }
+
+    /// Returns the size of the buffer required to read the information of type
+    /// `InformationType`.
+    pub fn info_size(&mut self, InformationType: &EFI_GUID) -> Result<usize, EFI_STATUS> {
+        let mut size: UINTN = 0;
+        // SAFETY: a zero-sized buffer is never written to.
+        match unsafe {
+            self.protocol()
+                .GetInfo(InformationType, &mut size, ptr::null_mut())
+        } {
+            EFI_SUCCESS | EFI_BUFFER_TOO_SMALL => Ok(size),
+            status => Err(status),
+        }
+    }
+
+    /// Sets the information of type `InformationType` from `Buffer`.
+    pub fn set_info(
+        &mut self,
+        InformationType: &EFI_GUID,
+        Buffer: &[UINT8],
+    ) -> Result<(), EFI_STATUS> {
+        // SAFETY: `Buffer` is valid for reads of its length.
+        match unsafe {
+            self.protocol()
+                .SetInfo(InformationType, Buffer.len(), Buffer.as_ptr().cast())
+        } {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Reads the [`EFI_FILE_INFO`] of the file into `Buffer`.
+    ///
+    /// [`EFI_FILE_INFO`]: crate::protocols::file_system::info::EFI_FILE_INFO
+    pub fn info<'b>(&mut self, Buffer: &'b mut [UINT8]) -> Result<FileInfo<'b>, EFI_STATUS> {
+        let size = self.get_info(&EFI_FILE_INFO_ID, Buffer)?;
+        FileInfo::new(&Buffer[..size])
+    }
+
+    /// Reads the [`EFI_FILE_SYSTEM_INFO`] of the volume the file resides on into `Buffer`.
+    ///
+    /// [`EFI_FILE_SYSTEM_INFO`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_INFO
+    pub fn file_system_info<'b>(
+        &mut self,
+        Buffer: &'b mut [UINT8],
+    ) -> Result<FileSystemInfo<'b>, EFI_STATUS> {
+        let size = self.get_info(&EFI_FILE_SYSTEM_INFO_ID, Buffer)?;
+        FileSystemInfo::new(&Buffer[..size])
+    }
+
+    /// Reads the information of type `InformationType` into a newly allocated buffer.
+    #[cfg(feature = "alloc")]
+    pub fn get_info_vec(&mut self, InformationType: &EFI_GUID) -> Result<Vec<UINT8>, EFI_STATUS> {
+        loop {
+            let mut buffer = alloc::vec![0; self.info_size(InformationType)?];
+            match self.get_info(InformationType, &mut buffer) {
+                Ok(size) => {
+                    buffer.truncate(size);
+                    return Ok(buffer);
+                }
+                Err(EFI_BUFFER_TOO_SMALL) => continue,
+                Err(status) => return Err(status),
+            }
+        }
+    }
+
+    /// Reads the [`EFI_FILE_INFO`] of the file into a newly allocated buffer.
+    ///
+    /// [`EFI_FILE_INFO`]: crate::protocols::file_system::info::EFI_FILE_INFO
+    #[cfg(feature = "alloc")]
+    pub fn info_buf(&mut self) -> Result<FileInfoBuf, EFI_STATUS> {
+        FileInfoBuf::new(self.get_info_vec(&EFI_FILE_INFO_ID)?)
+    }
+
+    /// Reads the next entry of a directory into `Buffer`, returning `None` once all entries have
+    /// been read.
+    ///
+    /// Returns [`EFI_BUFFER_TOO_SMALL`] without advancing if `Buffer` cannot hold the entry. A
+    /// buffer of [`SIZE_OF_EFI_FILE_INFO`] plus 512 bytes holds any entry whose name is at most
+    /// [`MAX_FILE_NAME_LENGTH`] characters long.
+    ///
+    /// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+    /// [`SIZE_OF_EFI_FILE_INFO`]: crate::protocols::file_system::info::SIZE_OF_EFI_FILE_INFO
+    /// [`MAX_FILE_NAME_LENGTH`]: crate::protocols::file_system::file::MAX_FILE_NAME_LENGTH
+    pub fn read_entry<'b>(
+        &mut self,
+        Buffer: &'b mut [UINT8],
+    ) -> Result<Option<FileInfo<'b>>, EFI_STATUS> {
+        match self.read(Buffer)? {
+            0 => Ok(None),
+            size => FileInfo::new(&Buffer[..size]).map(Some),
+        }
+    }
+
+    /// Returns an iterator over the remaining entries of a directory.
+    ///
+    /// Call [`set_position(0)`] first to iterate from the first entry.
+    ///
+    /// [`set_position(0)`]: ./struct.File.html#method.set_position
+    #[cfg(feature = "alloc")]
+    pub fn read_dir(&mut self) -> ReadDir<'_> {
+        ReadDir {
+            directory: self,
+            buffer: Vec::new(),
+            done: false,
+        }
+    }
+
+    fn read_raw(&mut self, Buffer: &mut [UINT8]) -> (EFI_STATUS, usize) {
+        let mut size: UINTN = Buffer.len();
+        // SAFETY: `Buffer` is valid for writes of `size` bytes.
+        let status = unsafe { self.protocol().Read(&mut size, Buffer.as_mut_ptr().cast()) };
+        (status, size)
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        // SAFETY: the handle is open and owned by `self`.
+        unsafe {
+            self.protocol().Close();
+        }
+    }
+}
+
+impl Read for File {
+    /// Reads data from the file. For directories, this reads the next [`EFI_FILE_INFO`] entry.
+    ///
+    /// [`EFI_FILE_INFO`]: crate::protocols::file_system::info::EFI_FILE_INFO
+    fn read(&mut self, Buffer: &mut [UINT8]) -> Result<usize, EFI_STATUS> {
+        match self.read_raw(Buffer) {
+            (EFI_SUCCESS, size) => Ok(size),
+            (status, _) => Err(status),
+        }
+    }
+}
+
+impl Write for File {
+    fn write(&mut self, Buffer: &[UINT8]) -> Result<usize, EFI_STATUS> {
+        let mut size: UINTN = Buffer.len();
+        // SAFETY: `Buffer` is valid for reads of `size` bytes.
+        match unsafe { self.protocol().Write(&mut size, Buffer.as_ptr().cast()) } {
+            EFI_SUCCESS => Ok(size),
+            status => Err(status),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), EFI_STATUS> {
+        // SAFETY: the handle is valid.
+        match unsafe { self.protocol().Flush() } {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+}
+
+impl Seek for File {
+    fn seek(&mut self, Position: SeekFrom) -> Result<u64, EFI_STATUS> {
+        let position = match Position {
+            SeekFrom::Start(offset) => offset,
+            SeekFrom::End(offset) => {
+                self.set_position(EFI_FILE_POSITION_END_OF_FILE)?;
+                if offset == 0 {
+                    return self.position();
+                }
+                offset_position(self.position()?, offset)?
+            }
+            SeekFrom::Current(offset) => offset_position(self.position()?, offset)?,
+        };
+        self.set_position(position)?;
+        Ok(position)
+    }
+
+    fn stream_position(&mut self) -> Result<u64, EFI_STATUS> {
+        self.position()
+    }
+}
+
+/// Iterator over the entries of a directory, returned by [`File::read_dir()`].
+///
+/// The entry buffer grows as needed, so entries of any size are returned. Iteration stops after
+/// the first error.
+///
+/// [`File::read_dir()`]: crate::protocols::file_system::File::read_dir
+#[cfg(feature = "alloc")]
+pub struct ReadDir<'a> {
+    directory: &'a mut File,
+    buffer: Vec<UINT8>,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for ReadDir<'_> {
+    type Item = Result<FileInfoBuf, EFI_STATUS>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.directory.read_raw(&mut self.buffer) {
+                (EFI_SUCCESS, 0) => self.done = true,
+                (EFI_SUCCESS, size) => {
+                    return Some(FileInfoBuf::new(self.buffer[..size].to_vec()));
+                }
+                (EFI_BUFFER_TOO_SMALL, size) if size > self.buffer.len() => {
+                    self.buffer.resize(size, 0);
+                }
+                (status, _) => {
+                    self.done = true;
+                    return Some(Err(status));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Encodes `Name` as a null-terminated UCS-2 string into `Buffer`.
+fn encode_name(Name: &str, Buffer: &mut [CHAR16]) -> Result<(), EFI_STATUS> {
+    let mut len = 0;
+    for c in Name.chars() {
+        let c = CHAR16::try_from(u32::from(c)).map_err(|_| EFI_INVALID_PARAMETER)?;
+        if c == 0 || len + 1 >= Buffer.len() {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+        Buffer[len] = c;
+        len += 1;
+    }
+    Buffer[len] = 0;
+    Ok(())
+}
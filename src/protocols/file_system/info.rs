@@ -0,0 +1,385 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # File Information Types
+//!
+//! This module defines the information types that can be retrieved with
+//! [`EFI_FILE_PROTOCOL.GetInfo()`] and set with [`EFI_FILE_PROTOCOL.SetInfo()`]: the
+//! [`EFI_FILE_INFO`], [`EFI_FILE_SYSTEM_INFO`] and [`EFI_FILE_SYSTEM_VOLUME_LABEL`] structures
+//! together with their identifying GUIDs. See [Section 13.5.16 of the UEFI Specification] for
+//! more details.
+//!
+//! Each of these structures ends with a variable-length, null-terminated name, so they are
+//! returned in byte buffers that are not necessarily aligned. The [`FileInfo`] and
+//! [`FileSystemInfo`] types give checked access to such buffers.
+//!
+//! [`EFI_FILE_PROTOCOL.GetInfo()`]: crate::protocols::file_system::EFI_FILE_PROTOCOL::GetInfo
+//! [`EFI_FILE_PROTOCOL.SetInfo()`]: crate::protocols::file_system::EFI_FILE_PROTOCOL::SetInfo
+//! [`EFI_FILE_INFO`]: crate::protocols::file_system::info::EFI_FILE_INFO
+//! [`EFI_FILE_SYSTEM_INFO`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_INFO
+//! [`EFI_FILE_SYSTEM_VOLUME_LABEL`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_VOLUME_LABEL
+//! [`FileInfo`]: crate::protocols::file_system::info::FileInfo
+//! [`FileSystemInfo`]: crate::protocols::file_system::info::FileSystemInfo
+//!
+//! [Section 13.5.16 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/13_Protocols_Media_Access.html#efi-file-info
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter, Write};
+use core::ptr;
+
+use crate::protocols::file_system::EFI_FILE_DIRECTORY;
+use crate::status::EFI_INVALID_PARAMETER;
+use crate::tables::runtime_services::EFI_TIME;
+use crate::types::{BOOLEAN, CHAR16, EFI_GUID, EFI_STATUS, UINT32, UINT64, UINT8};
+
+/// GUID identifying the [`EFI_FILE_INFO`] information type.
+///
+/// [`EFI_FILE_INFO`]: crate::protocols::file_system::info::EFI_FILE_INFO
+pub const EFI_FILE_INFO_ID: EFI_GUID = EFI_GUID(
+    0x09576E92,
+    0x6D3F,
+    0x11D2,
+    [0x8E, 0x39, 0x00, 0xA0, 0xC9, 0x69, 0x72, 0x3B],
+);
+
+/// GUID identifying the [`EFI_FILE_SYSTEM_INFO`] information type.
+///
+/// [`EFI_FILE_SYSTEM_INFO`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_INFO
+pub const EFI_FILE_SYSTEM_INFO_ID: EFI_GUID = EFI_GUID(
+    0x09576E93,
+    0x6D3F,
+    0x11D2,
+    [0x8E, 0x39, 0x00, 0xA0, 0xC9, 0x69, 0x72, 0x3B],
+);
+
+/// GUID identifying the [`EFI_FILE_SYSTEM_VOLUME_LABEL`] information type.
+///
+/// [`EFI_FILE_SYSTEM_VOLUME_LABEL`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_VOLUME_LABEL
+pub const EFI_FILE_SYSTEM_VOLUME_LABEL_ID: EFI_GUID = EFI_GUID(
+    0xDB47D7D3,
+    0xFE81,
+    0x11D3,
+    [0x9A, 0x35, 0x00, 0x90, 0x27, 0x3F, 0xC1, 0x4D],
+);
+
+/// Size of the fixed part of an [`EFI_FILE_INFO`], which is followed by the file name.
+///
+/// [`EFI_FILE_INFO`]: crate::protocols::file_system::info::EFI_FILE_INFO
+pub const SIZE_OF_EFI_FILE_INFO: usize = 80;
+
+/// Size of the fixed part of an [`EFI_FILE_SYSTEM_INFO`], which is followed by the volume label.
+///
+/// [`EFI_FILE_SYSTEM_INFO`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_INFO
+pub const SIZE_OF_EFI_FILE_SYSTEM_INFO: usize = 36;
+
+/// Generic information about a file, identified by [`EFI_FILE_INFO_ID`].
+///
+/// This structure is also returned for every entry read from a directory.
+///
+/// [`EFI_FILE_INFO_ID`]: crate::protocols::file_system::info::EFI_FILE_INFO_ID
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_FILE_INFO {
+    /// Size of the [`EFI_FILE_INFO`] structure, including the null-terminated [`FileName`]
+    /// string.
+    ///
+    /// [`EFI_FILE_INFO`]: crate::protocols::file_system::info::EFI_FILE_INFO
+    /// [`FileName`]: ./struct.EFI_FILE_INFO.html#structfield.FileName
+    pub Size: UINT64,
+    /// The size of the file in bytes.
+    pub FileSize: UINT64,
+    /// The amount of physical space the file consumes on the file system volume.
+    pub PhysicalSize: UINT64,
+    /// The time the file was created.
+    pub CreateTime: EFI_TIME,
+    /// The time when the file was last accessed.
+    pub LastAccessTime: EFI_TIME,
+    /// The time when the file's contents were last modified.
+    pub ModificationTime: EFI_TIME,
+    /// The attribute bits for the file, such as [`EFI_FILE_DIRECTORY`].
+    ///
+    /// [`EFI_FILE_DIRECTORY`]: crate::protocols::file_system::EFI_FILE_DIRECTORY
+    pub Attribute: UINT64,
+    /// The null-terminated name of the file. For a root directory, the name is an empty string.
+    pub FileName: [CHAR16; 0],
+}
+
+/// Information about the system volume, identified by [`EFI_FILE_SYSTEM_INFO_ID`].
+///
+/// [`EFI_FILE_SYSTEM_INFO_ID`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_INFO_ID
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_FILE_SYSTEM_INFO {
+    /// Size of the [`EFI_FILE_SYSTEM_INFO`] structure, including the null-terminated
+    /// [`VolumeLabel`] string.
+    ///
+    /// [`EFI_FILE_SYSTEM_INFO`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_INFO
+    /// [`VolumeLabel`]: ./struct.EFI_FILE_SYSTEM_INFO.html#structfield.VolumeLabel
+    pub Size: UINT64,
+    /// `TRUE` if the volume only supports read access.
+    pub ReadOnly: BOOLEAN,
+    /// The number of bytes managed by the file system.
+    pub VolumeSize: UINT64,
+    /// The number of available bytes for use by the file system.
+    pub FreeSpace: UINT64,
+    /// The nominal block size by which files are typically grown.
+    pub BlockSize: UINT32,
+    /// The null-terminated string that is the volume's label.
+    pub VolumeLabel: [CHAR16; 0],
+}
+
+/// The volume label of a file system, identified by [`EFI_FILE_SYSTEM_VOLUME_LABEL_ID`].
+///
+/// [`EFI_FILE_SYSTEM_VOLUME_LABEL_ID`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_VOLUME_LABEL_ID
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_FILE_SYSTEM_VOLUME_LABEL {
+    /// The null-terminated string that is the volume's label.
+    pub VolumeLabel: [CHAR16; 0],
+}
+
+/// A checked view of an [`EFI_FILE_INFO`] held in a byte buffer.
+///
+/// [`EFI_FILE_INFO`]: crate::protocols::file_system::info::EFI_FILE_INFO
+#[derive(Clone, Copy)]
+pub struct FileInfo<'a> {
+    bytes: &'a [UINT8],
+}
+
+impl<'a> FileInfo<'a> {
+    /// Creates a view of the [`EFI_FILE_INFO`] at the start of `Data`.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if `Data` is shorter than the [`Size`] of the
+    /// structure, or if [`Size`] is smaller than [`SIZE_OF_EFI_FILE_INFO`].
+    ///
+    /// [`EFI_FILE_INFO`]: crate::protocols::file_system::info::EFI_FILE_INFO
+    /// [`Size`]: ./struct.EFI_FILE_INFO.html#structfield.Size
+    /// [`SIZE_OF_EFI_FILE_INFO`]: crate::protocols::file_system::info::SIZE_OF_EFI_FILE_INFO
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub fn new(Data: &'a [UINT8]) -> Result<Self, EFI_STATUS> {
+        Ok(Self {
+            bytes: sized(Data, SIZE_OF_EFI_FILE_INFO)?,
+        })
+    }
+
+    /// Returns a copy of the fixed part of the structure.
+    pub fn header(&self) -> EFI_FILE_INFO {
+        // SAFETY: the buffer holds at least `SIZE_OF_EFI_FILE_INFO` bytes, the size of the
+        // structure, and the structure is valid for any bit pattern.
+        unsafe { ptr::read_unaligned(self.bytes.as_ptr().cast()) }
+    }
+
+    /// Returns the size of the file in bytes.
+    pub fn file_size(&self) -> UINT64 {
+        self.header().FileSize
+    }
+
+    /// Returns the attribute bits of the file.
+    pub fn attribute(&self) -> UINT64 {
+        self.header().Attribute
+    }
+
+    /// Returns `true` if the file is a directory.
+    pub fn is_directory(&self) -> bool {
+        self.attribute() & EFI_FILE_DIRECTORY != 0
+    }
+
+    /// Returns the name of the file.
+    pub fn file_name(&self) -> Ucs2Name<'a> {
+        Ucs2Name::new(&self.bytes[SIZE_OF_EFI_FILE_INFO..])
+    }
+
+    /// Returns the structure as bytes, including the file name.
+    pub fn as_bytes(&self) -> &'a [UINT8] {
+        self.bytes
+    }
+}
+
+/// A checked view of an [`EFI_FILE_SYSTEM_INFO`] held in a byte buffer.
+///
+/// [`EFI_FILE_SYSTEM_INFO`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_INFO
+#[derive(Clone, Copy)]
+pub struct FileSystemInfo<'a> {
+    bytes: &'a [UINT8],
+}
+
+impl<'a> FileSystemInfo<'a> {
+    /// Creates a view of the [`EFI_FILE_SYSTEM_INFO`] at the start of `Data`.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if `Data` is shorter than the [`Size`] of the
+    /// structure, or if [`Size`] is smaller than [`SIZE_OF_EFI_FILE_SYSTEM_INFO`].
+    ///
+    /// [`EFI_FILE_SYSTEM_INFO`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_INFO
+    /// [`Size`]: ./struct.EFI_FILE_SYSTEM_INFO.html#structfield.Size
+    /// [`SIZE_OF_EFI_FILE_SYSTEM_INFO`]: crate::protocols::file_system::info::SIZE_OF_EFI_FILE_SYSTEM_INFO
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub fn new(Data: &'a [UINT8]) -> Result<Self, EFI_STATUS> {
+        Ok(Self {
+            bytes: sized(Data, SIZE_OF_EFI_FILE_SYSTEM_INFO)?,
+        })
+    }
+
+    /// Returns `true` if the volume only supports read access.
+    pub fn read_only(&self) -> bool {
+        self.bytes[8] != 0
+    }
+
+    /// Returns the number of bytes managed by the file system.
+    pub fn volume_size(&self) -> UINT64 {
+        read_u64(&self.bytes[16..24])
+    }
+
+    /// Returns the number of available bytes for use by the file system.
+    pub fn free_space(&self) -> UINT64 {
+        read_u64(&self.bytes[24..32])
+    }
+
+    /// Returns the nominal block size by which files are typically grown.
+    pub fn block_size(&self) -> UINT32 {
+        let bytes = &self.bytes[32..36];
+        UINT32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+
+    /// Returns the label of the volume.
+    pub fn volume_label(&self) -> Ucs2Name<'a> {
+        Ucs2Name::new(&self.bytes[SIZE_OF_EFI_FILE_SYSTEM_INFO..])
+    }
+
+    /// Returns the structure as bytes, including the volume label.
+    pub fn as_bytes(&self) -> &'a [UINT8] {
+        self.bytes
+    }
+}
+
+/// An owned [`EFI_FILE_INFO`], such as a directory entry returned by [`File::read_dir()`].
+///
+/// [`EFI_FILE_INFO`]: crate::protocols::file_system::info::EFI_FILE_INFO
+/// [`File::read_dir()`]: crate::protocols::file_system::File::read_dir
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+pub struct FileInfoBuf {
+    bytes: Vec<UINT8>,
+}
+
+#[cfg(feature = "alloc")]
+impl FileInfoBuf {
+    /// Takes ownership of the [`EFI_FILE_INFO`] held in `Data`, validated as by
+    /// [`FileInfo::new()`].
+    ///
+    /// [`EFI_FILE_INFO`]: crate::protocols::file_system::info::EFI_FILE_INFO
+    /// [`FileInfo::new()`]: crate::protocols::file_system::info::FileInfo::new
+    pub fn new(mut Data: Vec<UINT8>) -> Result<Self, EFI_STATUS> {
+        let len = FileInfo::new(&Data)?.as_bytes().len();
+        Data.truncate(len);
+        Ok(Self { bytes: Data })
+    }
+
+    /// Returns a view of the structure.
+    pub fn info(&self) -> FileInfo<'_> {
+        FileInfo { bytes: &self.bytes }
+    }
+
+    /// Returns the structure as bytes, including the file name.
+    pub fn into_bytes(self) -> Vec<UINT8> {
+        self.bytes
+    }
+}
+
+/// A null-terminated UCS-2 name held in little-endian bytes, such as the [`FileName`] of an
+/// [`EFI_FILE_INFO`].
+///
+/// The name implements [`Display`].
+///
+/// [`FileName`]: ./struct.EFI_FILE_INFO.html#structfield.FileName
+/// [`EFI_FILE_INFO`]: crate::protocols::file_system::info::EFI_FILE_INFO
+/// [`Display`]: core::fmt::Display
+#[derive(Clone, Copy)]
+pub struct Ucs2Name<'a> {
+    bytes: &'a [UINT8],
+}
+
+impl<'a> Ucs2Name<'a> {
+    fn new(Data: &'a [UINT8]) -> Self {
+        let len = Data
+            .chunks_exact(2)
+            .position(|c| c == [0, 0])
+            .unwrap_or(Data.len() / 2);
+        Self {
+            bytes: &Data[..len * 2],
+        }
+    }
+
+    /// Returns an iterator over the UCS-2 characters of the name, excluding the null
+    /// terminator.
+    pub fn chars16(&self) -> impl Iterator<Item = CHAR16> + 'a {
+        self.bytes
+            .chunks_exact(2)
+            .map(|c| CHAR16::from_le_bytes([c[0], c[1]]))
+    }
+
+    /// Returns an iterator over the characters of the name. Unpaired surrogates are replaced by
+    /// [`char::REPLACEMENT_CHARACTER`].
+    pub fn chars(&self) -> impl Iterator<Item = char> + 'a {
+        char::decode_utf16(self.chars16()).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    /// Returns `true` if the name is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns `true` if the name is equal to `Value`.
+    pub fn eq_str(&self, Value: &str) -> bool {
+        self.chars().eq(Value.chars())
+    }
+
+    /// Returns `true` if the name is equal to `Value`, ignoring ASCII case as FAT file systems
+    /// do.
+    pub fn eq_ignore_ascii_case(&self, Value: &str) -> bool {
+        self.chars()
+            .map(|c| c.to_ascii_lowercase())
+            .eq(Value.chars().map(|c| c.to_ascii_lowercase()))
+    }
+}
+
+impl Display for Ucs2Name<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.chars().try_for_each(|c| f.write_char(c))
+    }
+}
+
+/// Returns `Data` truncated to the `Size` field of the structure it starts with, after checking
+/// it against `min` and the length of `Data`.
+fn sized(Data: &[UINT8], min: usize) -> Result<&[UINT8], EFI_STATUS> {
+    if Data.len() < min {
+        return Err(EFI_INVALID_PARAMETER);
+    }
+
+    let size = usize::try_from(read_u64(&Data[..8])).map_err(|_| EFI_INVALID_PARAMETER)?;
+    if size < min || size > Data.len() {
+        return Err(EFI_INVALID_PARAMETER);
+    }
+
+    Ok(&Data[..size])
+}
+
+fn read_u64(bytes: &[UINT8]) -> UINT64 {
+    let mut value = [0; 8];
+    value.copy_from_slice(bytes);
+    UINT64::from_le_bytes(value)
+}
@@ -0,0 +1,937 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Simple File System and File Protocols
+//!
+//! This module defines the Simple File System Protocol, also known as the
+//! [`EFI_SIMPLE_FILE_SYSTEM_PROTOCOL`], which provides access to a file system volume, and the
+//! File Protocol, also known as the [`EFI_FILE_PROTOCOL`], which provides access to the files and
+//! directories of that volume. The information structures returned by
+//! [`EFI_FILE_PROTOCOL.GetInfo()`] are defined in [`info`]. See [Section 13.4] and
+//! [Section 13.5 of the UEFI Specification] for more details.
+//!
+//! File handles opened through these protocols must be closed once they are no longer needed.
+//! The [`File`] type owns a file handle and closes it when dropped, and implements the
+//! [`Read`], [`Write`] and [`Seek`] traits.
+//!
+//! [`EFI_SIMPLE_FILE_SYSTEM_PROTOCOL`]: crate::protocols::file_system::EFI_SIMPLE_FILE_SYSTEM_PROTOCOL
+//! [`EFI_FILE_PROTOCOL`]: crate::protocols::file_system::EFI_FILE_PROTOCOL
+//! [`EFI_FILE_PROTOCOL.GetInfo()`]: ./struct.EFI_FILE_PROTOCOL.html#method.GetInfo
+//! [`info`]: crate::protocols::file_system::info
+//! [`File`]: crate::protocols::file_system::File
+//! [`Read`]: crate::io::Read
+//! [`Write`]: crate::io::Write
+//! [`Seek`]: crate::io::Seek
+//!
+//! [Section 13.4]: https://uefi.org/specs/UEFI/2.10/13_Protocols_Media_Access.html#simple-file-system-protocol
+//! [Section 13.5 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/13_Protocols_Media_Access.html#efi-file-protocol
+
+use crate::protocols::Protocol;
+use crate::status::EFI_SUCCESS;
+use crate::types::{CHAR16, EFI_EVENT, EFI_GUID, EFI_STATUS, UINT64, UINTN, VOID};
+
+pub mod file;
+pub mod info;
+
+pub use file::File;
+
+/// GUID for the [`EFI_SIMPLE_FILE_SYSTEM_PROTOCOL`].
+///
+/// [`EFI_SIMPLE_FILE_SYSTEM_PROTOCOL`]: crate::protocols::file_system::EFI_SIMPLE_FILE_SYSTEM_PROTOCOL
+pub const EFI_SIMPLE_FILE_SYSTEM_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0x964E5B22,
+    0x6459,
+    0x11D2,
+    [0x8E, 0x39, 0x00, 0xA0, 0xC9, 0x69, 0x72, 0x3B],
+);
+
+/// Revision of the [`EFI_SIMPLE_FILE_SYSTEM_PROTOCOL`] defined by this version of the
+/// specification.
+///
+/// [`EFI_SIMPLE_FILE_SYSTEM_PROTOCOL`]: crate::protocols::file_system::EFI_SIMPLE_FILE_SYSTEM_PROTOCOL
+pub const EFI_SIMPLE_FILE_SYSTEM_PROTOCOL_REVISION: UINT64 = 0x00010000;
+
+/// Revision of the [`EFI_FILE_PROTOCOL`] without the asynchronous functions.
+///
+/// [`EFI_FILE_PROTOCOL`]: crate::protocols::file_system::EFI_FILE_PROTOCOL
+pub const EFI_FILE_PROTOCOL_REVISION: UINT64 = 0x00010000;
+/// Revision of the [`EFI_FILE_PROTOCOL`] that adds the [`OpenEx()`], [`ReadEx()`],
+/// [`WriteEx()`] and [`FlushEx()`] functions.
+///
+/// [`EFI_FILE_PROTOCOL`]: crate::protocols::file_system::EFI_FILE_PROTOCOL
+/// [`OpenEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.OpenEx
+/// [`ReadEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.ReadEx
+/// [`WriteEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.WriteEx
+/// [`FlushEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.FlushEx
+pub const EFI_FILE_PROTOCOL_REVISION2: UINT64 = 0x00020000;
+/// Latest revision of the [`EFI_FILE_PROTOCOL`].
+///
+/// [`EFI_FILE_PROTOCOL`]: crate::protocols::file_system::EFI_FILE_PROTOCOL
+pub const EFI_FILE_PROTOCOL_LATEST_REVISION: UINT64 = EFI_FILE_PROTOCOL_REVISION2;
+
+/// Open the file for reading.
+pub const EFI_FILE_MODE_READ: UINT64 = 0x0000000000000001;
+/// Open the file for writing. Must be combined with [`EFI_FILE_MODE_READ`].
+pub const EFI_FILE_MODE_WRITE: UINT64 = 0x0000000000000002;
+/// Create the file if it does not exist. Must be combined with [`EFI_FILE_MODE_READ`] and
+/// [`EFI_FILE_MODE_WRITE`].
+pub const EFI_FILE_MODE_CREATE: UINT64 = 0x8000000000000000;
+
+/// The file is read-only.
+pub const EFI_FILE_READ_ONLY: UINT64 = 0x0000000000000001;
+/// The file is hidden.
+pub const EFI_FILE_HIDDEN: UINT64 = 0x0000000000000002;
+/// The file is a system file.
+pub const EFI_FILE_SYSTEM: UINT64 = 0x0000000000000004;
+/// Reserved.
+pub const EFI_FILE_RESERVED: UINT64 = 0x0000000000000008;
+/// The file is a directory.
+pub const EFI_FILE_DIRECTORY: UINT64 = 0x0000000000000010;
+/// The file is marked for archiving.
+pub const EFI_FILE_ARCHIVE: UINT64 = 0x0000000000000020;
+/// Mask of all the valid file attribute bits.
+pub const EFI_FILE_VALID_ATTR: UINT64 = 0x0000000000000037;
+
+/// Position passed to [`SetPosition()`] to move to the end of the file.
+///
+/// [`SetPosition()`]: ./struct.EFI_FILE_PROTOCOL.html#method.SetPosition
+pub const EFI_FILE_POSITION_END_OF_FILE: UINT64 = 0xFFFFFFFFFFFFFFFF;
+
+/// Provides a minimal interface for file-type access to a device.
+///
+/// The Simple File System protocol allows code running in the EFI boot services environment to
+/// obtain file based access to a device. It is used to open a device volume and return an
+/// [`EFI_FILE_PROTOCOL`] that provides interfaces to access files on that volume.
+///
+/// [`EFI_FILE_PROTOCOL`]: crate::protocols::file_system::EFI_FILE_PROTOCOL
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_SIMPLE_FILE_SYSTEM_PROTOCOL {
+    /// The version of the [`EFI_SIMPLE_FILE_SYSTEM_PROTOCOL`]. All future revisions must be
+    /// backwards compatible.
+    ///
+    /// [`EFI_SIMPLE_FILE_SYSTEM_PROTOCOL`]: crate::protocols::file_system::EFI_SIMPLE_FILE_SYSTEM_PROTOCOL
+    pub Revision: UINT64,
+    OpenVolume: EFI_SIMPLE_FILE_SYSTEM_PROTOCOL_OPEN_VOLUME,
+}
+
+unsafe impl Protocol for EFI_SIMPLE_FILE_SYSTEM_PROTOCOL {
+    const GUID: EFI_GUID = EFI_SIMPLE_FILE_SYSTEM_PROTOCOL_GUID;
+}
+
+impl EFI_SIMPLE_FILE_SYSTEM_PROTOCOL {
+    /// Opens the root directory on a volume.
+    ///
+    /// The [`OpenVolume()`] function opens a volume, and returns a file handle to the volume's
+    /// root directory. This handle is used to perform all other file I/O operations. The volume
+    /// remains open until all the file handles to it are closed.
+    ///
+    /// If the medium is changed while there are open file handles to the volume, all file
+    /// handles to the volume will return [`EFI_MEDIA_CHANGED`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `Root`
+    ///
+    /// A pointer to the location to return the opened file handle for the root directory.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the file system was opened.
+    ///
+    /// [`EFI_UNSUPPORTED`] - the device does not support the file system type.
+    ///
+    /// [`EFI_NO_MEDIA`] - the device has no medium.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error.
+    ///
+    /// [`EFI_VOLUME_CORRUPTED`] - the file system structures are corrupted.
+    ///
+    /// [`EFI_ACCESS_DENIED`] - the service denied access to the file.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - the volume was not opened due to lack of resources.
+    ///
+    /// [`EFI_MEDIA_CHANGED`] - the device has a different medium in it or the medium is no longer
+    /// supported.
+    ///
+    /// [`OpenVolume()`]: ./struct.EFI_SIMPLE_FILE_SYSTEM_PROTOCOL.html#method.OpenVolume
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_VOLUME_CORRUPTED`]: crate::status::EFI_VOLUME_CORRUPTED
+    /// [`EFI_ACCESS_DENIED`]: crate::status::EFI_ACCESS_DENIED
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    /// [`EFI_MEDIA_CHANGED`]: crate::status::EFI_MEDIA_CHANGED
+    pub unsafe fn OpenVolume(&mut self, Root: *mut *mut EFI_FILE_PROTOCOL) -> EFI_STATUS {
+        (self.OpenVolume)(self, Root)
+    }
+
+    /// Opens the root directory of the volume, returning an owned [`File`] that closes it when
+    /// dropped.
+    ///
+    /// [`File`]: crate::protocols::file_system::File
+    pub unsafe fn open_volume(&mut self) -> Result<File, EFI_STATUS> {
+        let mut root = core::ptr::null_mut();
+        match self.OpenVolume(&mut root) {
+            EFI_SUCCESS => Ok(File::from_raw(root)),
+            status => Err(status),
+        }
+    }
+}
+
+/// Provides file based access to supported file systems.
+///
+/// An [`EFI_FILE_PROTOCOL`] provides access to a file's or directory's contents, and is also a
+/// reference to a location in the directory tree of the file system in which the file resides.
+/// With any given file handle, other files may be opened relative to this file's location,
+/// yielding new file handles.
+///
+/// The functions added in [`EFI_FILE_PROTOCOL_REVISION2`] must not be called unless
+/// [`Revision`] is at least [`EFI_FILE_PROTOCOL_REVISION2`].
+///
+/// [`EFI_FILE_PROTOCOL`]: crate::protocols::file_system::EFI_FILE_PROTOCOL
+/// [`EFI_FILE_PROTOCOL_REVISION2`]: crate::protocols::file_system::EFI_FILE_PROTOCOL_REVISION2
+/// [`Revision`]: ./struct.EFI_FILE_PROTOCOL.html#structfield.Revision
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_FILE_PROTOCOL {
+    /// The version of the [`EFI_FILE_PROTOCOL`] interface.
+    ///
+    /// [`EFI_FILE_PROTOCOL`]: crate::protocols::file_system::EFI_FILE_PROTOCOL
+    pub Revision: UINT64,
+    Open: EFI_FILE_OPEN,
+    Close: EFI_FILE_CLOSE,
+    Delete: EFI_FILE_DELETE,
+    Read: EFI_FILE_READ,
+    Write: EFI_FILE_WRITE,
+    GetPosition: EFI_FILE_GET_POSITION,
+    SetPosition: EFI_FILE_SET_POSITION,
+    GetInfo: EFI_FILE_GET_INFO,
+    SetInfo: EFI_FILE_SET_INFO,
+    Flush: EFI_FILE_FLUSH,
+    OpenEx: EFI_FILE_OPEN_EX,
+    ReadEx: EFI_FILE_READ_EX,
+    WriteEx: EFI_FILE_WRITE_EX,
+    FlushEx: EFI_FILE_FLUSH_EX,
+}
+
+impl EFI_FILE_PROTOCOL {
+    /// Opens a new file relative to the source file's location.
+    ///
+    /// The [`Open()`] function opens the file or directory referred to by `FileName` relative to
+    /// the location of this file handle, and returns a `NewHandle`. The `FileName` may include
+    /// the following path modifiers:
+    ///
+    /// - `\` - if the filename starts with a `\`, the relative location is the root directory
+    ///   that this file handle resides on; otherwise, `\` separates name components.
+    /// - `.` - opens the current location.
+    /// - `..` - opens the parent directory for the current location. If the location is the root
+    ///   directory, the request will return an error, as there is no parent directory for the
+    ///   root directory.
+    ///
+    /// If [`EFI_FILE_MODE_CREATE`] is set, then the file is created in the directory. If the
+    /// final location of `FileName` does not refer to a directory, then the operation fails. If
+    /// the file does not exist in the directory, then a new file is created. If the file already
+    /// exists, then the file is opened.
+    ///
+    /// # Parameters
+    ///
+    /// ## `NewHandle`
+    ///
+    /// A pointer to the location to return the opened handle for the new file.
+    ///
+    /// ## `FileName`
+    ///
+    /// The null-terminated string of the name of the file to be opened. The file name may
+    /// contain the path modifiers described above.
+    ///
+    /// ## `OpenMode`
+    ///
+    /// The mode to open the file, one of [`EFI_FILE_MODE_READ`], [`EFI_FILE_MODE_READ`] `|`
+    /// [`EFI_FILE_MODE_WRITE`], or [`EFI_FILE_MODE_READ`] `|` [`EFI_FILE_MODE_WRITE`] `|`
+    /// [`EFI_FILE_MODE_CREATE`].
+    ///
+    /// ## `Attributes`
+    ///
+    /// Only valid for [`EFI_FILE_MODE_CREATE`], in which case these are the attribute bits for
+    /// the newly created file, such as [`EFI_FILE_DIRECTORY`].
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the file was opened.
+    ///
+    /// [`EFI_NOT_FOUND`] - the specified file could not be found on the device.
+    ///
+    /// [`EFI_NO_MEDIA`] - the device has no medium.
+    ///
+    /// [`EFI_MEDIA_CHANGED`] - the device has a different medium in it or the medium is no longer
+    /// supported.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error.
+    ///
+    /// [`EFI_VOLUME_CORRUPTED`] - the file system structures are corrupted.
+    ///
+    /// [`EFI_WRITE_PROTECTED`] - an attempt was made to create a file, or open a file for write
+    /// when the media is write-protected.
+    ///
+    /// [`EFI_ACCESS_DENIED`] - the service denied access to the file.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - not enough resources were available to open the file.
+    ///
+    /// [`EFI_VOLUME_FULL`] - the volume is full.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - `OpenMode` is not a valid mode.
+    ///
+    /// [`Open()`]: ./struct.EFI_FILE_PROTOCOL.html#method.Open
+    /// [`EFI_FILE_MODE_READ`]: crate::protocols::file_system::EFI_FILE_MODE_READ
+    /// [`EFI_FILE_MODE_WRITE`]: crate::protocols::file_system::EFI_FILE_MODE_WRITE
+    /// [`EFI_FILE_MODE_CREATE`]: crate::protocols::file_system::EFI_FILE_MODE_CREATE
+    /// [`EFI_FILE_DIRECTORY`]: crate::protocols::file_system::EFI_FILE_DIRECTORY
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_NOT_FOUND`]: crate::status::EFI_NOT_FOUND
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_MEDIA_CHANGED`]: crate::status::EFI_MEDIA_CHANGED
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_VOLUME_CORRUPTED`]: crate::status::EFI_VOLUME_CORRUPTED
+    /// [`EFI_WRITE_PROTECTED`]: crate::status::EFI_WRITE_PROTECTED
+    /// [`EFI_ACCESS_DENIED`]: crate::status::EFI_ACCESS_DENIED
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    /// [`EFI_VOLUME_FULL`]: crate::status::EFI_VOLUME_FULL
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn Open(
+        &mut self,
+        NewHandle: *mut *mut EFI_FILE_PROTOCOL,
+        FileName: *const CHAR16,
+        OpenMode: UINT64,
+        Attributes: UINT64,
+    ) -> EFI_STATUS {
+        (self.Open)(self, NewHandle, FileName, OpenMode, Attributes)
+    }
+
+    /// Closes a specified file handle.
+    ///
+    /// The [`Close()`] function closes a specified file handle. All "dirty" cached file data is
+    /// flushed to the device, and the file is closed. In all cases the handle is closed.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the file was closed.
+    ///
+    /// [`Close()`]: ./struct.EFI_FILE_PROTOCOL.html#method.Close
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    pub unsafe fn Close(&mut self) -> EFI_STATUS {
+        (self.Close)(self)
+    }
+
+    /// Closes and deletes a file.
+    ///
+    /// The [`Delete()`] function closes and deletes a file. In all cases the file handle is
+    /// closed. If the file cannot be deleted, the warning code [`EFI_WARN_DELETE_FAILURE`] is
+    /// returned, but the handle is still closed.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the file was closed and deleted, and the handle was closed.
+    ///
+    /// [`EFI_WARN_DELETE_FAILURE`] - the handle was closed, but the file was not deleted.
+    ///
+    /// [`Delete()`]: ./struct.EFI_FILE_PROTOCOL.html#method.Delete
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_WARN_DELETE_FAILURE`]: crate::status::EFI_WARN_DELETE_FAILURE
+    pub unsafe fn Delete(&mut self) -> EFI_STATUS {
+        (self.Delete)(self)
+    }
+
+    /// Reads data from a file.
+    ///
+    /// If the file handle is not a directory, the function reads the requested number of bytes
+    /// from the file at the file's current position and returns them in `Buffer`. If the read
+    /// goes beyond the end of the file, the read length is truncated to the end of the file. The
+    /// file's current position is increased by the number of bytes returned.
+    ///
+    /// If the file handle is a directory, the function reads the directory entry at the file's
+    /// current position and returns the entry in `Buffer` as an [`EFI_FILE_INFO`]. If `Buffer`
+    /// is not large enough to hold the current directory entry, then [`EFI_BUFFER_TOO_SMALL`] is
+    /// returned and the current file position is not updated; `BufferSize` is set to the size of
+    /// the buffer needed to read the entry. On success, the current position is updated to the
+    /// next directory entry. If there are no more directory entries, the read returns a
+    /// zero-length buffer.
+    ///
+    /// # Parameters
+    ///
+    /// ## `BufferSize`
+    ///
+    /// On input, the size of `Buffer`. On output, the amount of data returned in `Buffer`. In
+    /// both cases, the size is measured in bytes.
+    ///
+    /// ## `Buffer`
+    ///
+    /// The buffer into which the data is read.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the data was read.
+    ///
+    /// [`EFI_NO_MEDIA`] - the device has no medium.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error, or an attempt was made to read from
+    /// a deleted file.
+    ///
+    /// [`EFI_VOLUME_CORRUPTED`] - the file system structures are corrupted.
+    ///
+    /// [`EFI_BUFFER_TOO_SMALL`] - the `BufferSize` is too small to read the current directory
+    /// entry. `BufferSize` has been updated with the size needed to complete the request.
+    ///
+    /// [`EFI_FILE_INFO`]: crate::protocols::file_system::info::EFI_FILE_INFO
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_VOLUME_CORRUPTED`]: crate::status::EFI_VOLUME_CORRUPTED
+    /// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+    pub unsafe fn Read(&mut self, BufferSize: *mut UINTN, Buffer: *mut VOID) -> EFI_STATUS {
+        (self.Read)(self, BufferSize, Buffer)
+    }
+
+    /// Writes data to a file.
+    ///
+    /// The [`Write()`] function writes the specified number of bytes to the file at the current
+    /// file position. The current file position is advanced the actual number of bytes written,
+    /// which is returned in `BufferSize`. Partial writes only occur when there has been a data
+    /// error during the write attempt, such as "file space full". The file is automatically grown
+    /// to hold the data if required.
+    ///
+    /// Direct writes to opened directories are not supported.
+    ///
+    /// # Parameters
+    ///
+    /// ## `BufferSize`
+    ///
+    /// On input, the size of `Buffer`. On output, the amount of data actually written. In both
+    /// cases, the size is measured in bytes.
+    ///
+    /// ## `Buffer`
+    ///
+    /// The buffer of data to write.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the data was written.
+    ///
+    /// [`EFI_UNSUPPORTED`] - writes to open directory files are not supported.
+    ///
+    /// [`EFI_NO_MEDIA`] - the device has no medium.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error, or an attempt was made to write to
+    /// a deleted file.
+    ///
+    /// [`EFI_VOLUME_CORRUPTED`] - the file system structures are corrupted.
+    ///
+    /// [`EFI_WRITE_PROTECTED`] - the file or medium is write-protected.
+    ///
+    /// [`EFI_ACCESS_DENIED`] - the file was opened read only.
+    ///
+    /// [`EFI_VOLUME_FULL`] - the volume is full.
+    ///
+    /// [`Write()`]: ./struct.EFI_FILE_PROTOCOL.html#method.Write
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_VOLUME_CORRUPTED`]: crate::status::EFI_VOLUME_CORRUPTED
+    /// [`EFI_WRITE_PROTECTED`]: crate::status::EFI_WRITE_PROTECTED
+    /// [`EFI_ACCESS_DENIED`]: crate::status::EFI_ACCESS_DENIED
+    /// [`EFI_VOLUME_FULL`]: crate::status::EFI_VOLUME_FULL
+    pub unsafe fn Write(&mut self, BufferSize: *mut UINTN, Buffer: *const VOID) -> EFI_STATUS {
+        (self.Write)(self, BufferSize, Buffer)
+    }
+
+    /// Returns a file's current position.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Position`
+    ///
+    /// The address to return the file's current position value.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the position was returned.
+    ///
+    /// [`EFI_UNSUPPORTED`] - the request is not valid on open directories.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - an attempt was made to get the position from a deleted file.
+    ///
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    pub unsafe fn GetPosition(&mut self, Position: *mut UINT64) -> EFI_STATUS {
+        (self.GetPosition)(self, Position)
+    }
+
+    /// Sets a file's current position.
+    ///
+    /// The [`SetPosition()`] function sets the current file position for the handle to the
+    /// position supplied. With the exception of seeking to position
+    /// [`EFI_FILE_POSITION_END_OF_FILE`], only absolute positioning is supported, and seeking
+    /// past the end of the file is allowed (a subsequent write would grow the file). Seeking to
+    /// position [`EFI_FILE_POSITION_END_OF_FILE`] causes the current position to be set to the
+    /// end of the file.
+    ///
+    /// If the file handle is a directory, the only position that may be set is zero. This has
+    /// the effect of starting the read process of the directory entries over.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Position`
+    ///
+    /// The byte position from the start of the file to set.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the position was set.
+    ///
+    /// [`EFI_UNSUPPORTED`] - the seek request for nonzero is not valid on open directories.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - an attempt was made to set the position of a deleted file.
+    ///
+    /// [`SetPosition()`]: ./struct.EFI_FILE_PROTOCOL.html#method.SetPosition
+    /// [`EFI_FILE_POSITION_END_OF_FILE`]: crate::protocols::file_system::EFI_FILE_POSITION_END_OF_FILE
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    pub unsafe fn SetPosition(&mut self, Position: UINT64) -> EFI_STATUS {
+        (self.SetPosition)(self, Position)
+    }
+
+    /// Returns information about a file.
+    ///
+    /// The [`GetInfo()`] function returns information of type `InformationType` for the
+    /// requested file. If the file does not support the requested information type, then
+    /// [`EFI_UNSUPPORTED`] is returned. If the buffer is not large enough to fit the requested
+    /// structure, [`EFI_BUFFER_TOO_SMALL`] is returned and the `BufferSize` is set to the size of
+    /// buffer that is required to make the request.
+    ///
+    /// The information types defined by this specification are [`EFI_FILE_INFO_ID`],
+    /// [`EFI_FILE_SYSTEM_INFO_ID`] and [`EFI_FILE_SYSTEM_VOLUME_LABEL_ID`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `InformationType`
+    ///
+    /// The type identifier for the information being requested.
+    ///
+    /// ## `BufferSize`
+    ///
+    /// On input, the size of `Buffer`. On output, the amount of data returned in `Buffer`. In
+    /// both cases, the size is measured in bytes.
+    ///
+    /// ## `Buffer`
+    ///
+    /// A pointer to the data buffer to return. The buffer's type is indicated by
+    /// `InformationType`.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the information was returned.
+    ///
+    /// [`EFI_UNSUPPORTED`] - the `InformationType` is not known.
+    ///
+    /// [`EFI_NO_MEDIA`] - the device has no medium.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error.
+    ///
+    /// [`EFI_VOLUME_CORRUPTED`] - the file system structures are corrupted.
+    ///
+    /// [`EFI_BUFFER_TOO_SMALL`] - the `BufferSize` is too small to read the current directory
+    /// entry. `BufferSize` has been updated with the size needed to complete the request.
+    ///
+    /// [`GetInfo()`]: ./struct.EFI_FILE_PROTOCOL.html#method.GetInfo
+    /// [`EFI_FILE_INFO_ID`]: crate::protocols::file_system::info::EFI_FILE_INFO_ID
+    /// [`EFI_FILE_SYSTEM_INFO_ID`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_INFO_ID
+    /// [`EFI_FILE_SYSTEM_VOLUME_LABEL_ID`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_VOLUME_LABEL_ID
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_VOLUME_CORRUPTED`]: crate::status::EFI_VOLUME_CORRUPTED
+    /// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+    pub unsafe fn GetInfo(
+        &mut self,
+        InformationType: *const EFI_GUID,
+        BufferSize: *mut UINTN,
+        Buffer: *mut VOID,
+    ) -> EFI_STATUS {
+        (self.GetInfo)(self, InformationType, BufferSize, Buffer)
+    }
+
+    /// Sets information about a file.
+    ///
+    /// The [`SetInfo()`] function sets information of type `InformationType` on the requested
+    /// file. Setting [`EFI_FILE_INFO`] may change the size, times, attributes and name of the
+    /// file; setting [`EFI_FILE_SYSTEM_INFO`] or [`EFI_FILE_SYSTEM_VOLUME_LABEL`] may only change
+    /// the volume label.
+    ///
+    /// # Parameters
+    ///
+    /// ## `InformationType`
+    ///
+    /// The type identifier for the information being set.
+    ///
+    /// ## `BufferSize`
+    ///
+    /// The size, in bytes, of `Buffer`.
+    ///
+    /// ## `Buffer`
+    ///
+    /// A pointer to the data buffer to write. The buffer's type is indicated by
+    /// `InformationType`.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the information was set.
+    ///
+    /// [`EFI_UNSUPPORTED`] - the `InformationType` is not known.
+    ///
+    /// [`EFI_NO_MEDIA`] - the device has no medium.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error.
+    ///
+    /// [`EFI_VOLUME_CORRUPTED`] - the file system structures are corrupted.
+    ///
+    /// [`EFI_WRITE_PROTECTED`] - the `InformationType` is read-only, or the media is
+    /// write-protected.
+    ///
+    /// [`EFI_ACCESS_DENIED`] - an attempt was made to change information that is not allowed to
+    /// change, such as the name of a file to that of an existing file, or the file was opened
+    /// read-only.
+    ///
+    /// [`EFI_VOLUME_FULL`] - the volume is full.
+    ///
+    /// [`EFI_BAD_BUFFER_SIZE`] - `BufferSize` is smaller than the size of the type indicated by
+    /// `InformationType`.
+    ///
+    /// [`SetInfo()`]: ./struct.EFI_FILE_PROTOCOL.html#method.SetInfo
+    /// [`EFI_FILE_INFO`]: crate::protocols::file_system::info::EFI_FILE_INFO
+    /// [`EFI_FILE_SYSTEM_INFO`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_INFO
+    /// [`EFI_FILE_SYSTEM_VOLUME_LABEL`]: crate::protocols::file_system::info::EFI_FILE_SYSTEM_VOLUME_LABEL
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_VOLUME_CORRUPTED`]: crate::status::EFI_VOLUME_CORRUPTED
+    /// [`EFI_WRITE_PROTECTED`]: crate::status::EFI_WRITE_PROTECTED
+    /// [`EFI_ACCESS_DENIED`]: crate::status::EFI_ACCESS_DENIED
+    /// [`EFI_VOLUME_FULL`]: crate::status::EFI_VOLUME_FULL
+    /// [`EFI_BAD_BUFFER_SIZE`]: crate::status::EFI_BAD_BUFFER_SIZE
+    pub unsafe fn SetInfo(
+        &mut self,
+        InformationType: *const EFI_GUID,
+        BufferSize: UINTN,
+        Buffer: *const VOID,
+    ) -> EFI_STATUS {
+        (self.SetInfo)(self, InformationType, BufferSize, Buffer)
+    }
+
+    /// Flushes all modified data associated with a file to a device.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the data was flushed.
+    ///
+    /// [`EFI_NO_MEDIA`] - the device has no medium.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error.
+    ///
+    /// [`EFI_VOLUME_CORRUPTED`] - the file system structures are corrupted.
+    ///
+    /// [`EFI_WRITE_PROTECTED`] - the file or medium is write-protected.
+    ///
+    /// [`EFI_ACCESS_DENIED`] - the file was opened read-only.
+    ///
+    /// [`EFI_VOLUME_FULL`] - the volume is full.
+    ///
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_VOLUME_CORRUPTED`]: crate::status::EFI_VOLUME_CORRUPTED
+    /// [`EFI_WRITE_PROTECTED`]: crate::status::EFI_WRITE_PROTECTED
+    /// [`EFI_ACCESS_DENIED`]: crate::status::EFI_ACCESS_DENIED
+    /// [`EFI_VOLUME_FULL`]: crate::status::EFI_VOLUME_FULL
+    pub unsafe fn Flush(&mut self) -> EFI_STATUS {
+        (self.Flush)(self)
+    }
+
+    /// Opens a new file relative to the source directory's location, asynchronously if
+    /// `Token.Event` is not null.
+    ///
+    /// The [`OpenEx()`] function opens a file in the same way as [`Open()`]. If the event in
+    /// `Token` is null, the request is processed synchronously; otherwise the function returns
+    /// immediately and the event is signaled once the request completes, with its status
+    /// stored in `Token.Status`.
+    ///
+    /// Only available if [`Revision`] is at least [`EFI_FILE_PROTOCOL_REVISION2`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `NewHandle`
+    ///
+    /// A pointer to the location to return the opened handle for the new file.
+    ///
+    /// ## `FileName`
+    ///
+    /// The null-terminated string of the name of the file to be opened.
+    ///
+    /// ## `OpenMode`
+    ///
+    /// The mode to open the file, as for [`Open()`].
+    ///
+    /// ## `Attributes`
+    ///
+    /// Only valid for [`EFI_FILE_MODE_CREATE`], in which case these are the attribute bits for
+    /// the newly created file.
+    ///
+    /// ## `Token`
+    ///
+    /// A pointer to the token associated with the transaction.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - if the event is null (blocking I/O), the file was opened successfully;
+    /// otherwise (non-blocking I/O), the request was successfully queued for processing.
+    ///
+    /// The other status codes are the same as for [`Open()`], and are returned either by this
+    /// function or in `Token.Status`.
+    ///
+    /// [`OpenEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.OpenEx
+    /// [`Open()`]: ./struct.EFI_FILE_PROTOCOL.html#method.Open
+    /// [`Revision`]: ./struct.EFI_FILE_PROTOCOL.html#structfield.Revision
+    /// [`EFI_FILE_PROTOCOL_REVISION2`]: crate::protocols::file_system::EFI_FILE_PROTOCOL_REVISION2
+    /// [`EFI_FILE_MODE_CREATE`]: crate::protocols::file_system::EFI_FILE_MODE_CREATE
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    pub unsafe fn OpenEx(
+        &mut self,
+        NewHandle: *mut *mut EFI_FILE_PROTOCOL,
+        FileName: *const CHAR16,
+        OpenMode: UINT64,
+        Attributes: UINT64,
+        Token: *mut EFI_FILE_IO_TOKEN,
+    ) -> EFI_STATUS {
+        (self.OpenEx)(self, NewHandle, FileName, OpenMode, Attributes, Token)
+    }
+
+    /// Reads data from a file, asynchronously if `Token.Event` is not null.
+    ///
+    /// The [`ReadEx()`] function reads `Token.BufferSize` bytes into `Token.Buffer` in the same
+    /// way as [`Read()`], and updates `Token.BufferSize` with the number of bytes read.
+    ///
+    /// Only available if [`Revision`] is at least [`EFI_FILE_PROTOCOL_REVISION2`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `Token`
+    ///
+    /// A pointer to the token associated with the transaction.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - if the event is null (blocking I/O), the data was read successfully;
+    /// otherwise (non-blocking I/O), the request was successfully queued for processing.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - unable to queue the request due to lack of resources.
+    ///
+    /// The other status codes are the same as for [`Read()`], and are returned either by this
+    /// function or in `Token.Status`.
+    ///
+    /// [`ReadEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.ReadEx
+    /// [`Read()`]: ./struct.EFI_FILE_PROTOCOL.html#method.Read
+    /// [`Revision`]: ./struct.EFI_FILE_PROTOCOL.html#structfield.Revision
+    /// [`EFI_FILE_PROTOCOL_REVISION2`]: crate::protocols::file_system::EFI_FILE_PROTOCOL_REVISION2
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    pub unsafe fn ReadEx(&mut self, Token: *mut EFI_FILE_IO_TOKEN) -> EFI_STATUS {
+        (self.ReadEx)(self, Token)
+    }
+
+    /// Writes data to a file, asynchronously if `Token.Event` is not null.
+    ///
+    /// The [`WriteEx()`] function writes `Token.BufferSize` bytes from `Token.Buffer` in the
+    /// same way as [`Write()`], and updates `Token.BufferSize` with the number of bytes written.
+    ///
+    /// Only available if [`Revision`] is at least [`EFI_FILE_PROTOCOL_REVISION2`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `Token`
+    ///
+    /// A pointer to the token associated with the transaction.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - if the event is null (blocking I/O), the data was written successfully;
+    /// otherwise (non-blocking I/O), the request was successfully queued for processing.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - unable to queue the request due to lack of resources.
+    ///
+    /// The other status codes are the same as for [`Write()`], and are returned either by this
+    /// function or in `Token.Status`.
+    ///
+    /// [`WriteEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.WriteEx
+    /// [`Write()`]: ./struct.EFI_FILE_PROTOCOL.html#method.Write
+    /// [`Revision`]: ./struct.EFI_FILE_PROTOCOL.html#structfield.Revision
+    /// [`EFI_FILE_PROTOCOL_REVISION2`]: crate::protocols::file_system::EFI_FILE_PROTOCOL_REVISION2
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    pub unsafe fn WriteEx(&mut self, Token: *mut EFI_FILE_IO_TOKEN) -> EFI_STATUS {
+        (self.WriteEx)(self, Token)
+    }
+
+    /// Flushes all modified data associated with a file to a device, asynchronously if
+    /// `Token.Event` is not null.
+    ///
+    /// Only available if [`Revision`] is at least [`EFI_FILE_PROTOCOL_REVISION2`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `Token`
+    ///
+    /// A pointer to the token associated with the transaction.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - if the event is null (blocking I/O), the data was flushed successfully;
+    /// otherwise (non-blocking I/O), the request was successfully queued for processing.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - unable to queue the request due to lack of resources.
+    ///
+    /// The other status codes are the same as for [`Flush()`], and are returned either by this
+    /// function or in `Token.Status`.
+    ///
+    /// [`Revision`]: ./struct.EFI_FILE_PROTOCOL.html#structfield.Revision
+    /// [`EFI_FILE_PROTOCOL_REVISION2`]: crate::protocols::file_system::EFI_FILE_PROTOCOL_REVISION2
+    /// [`Flush()`]: ./struct.EFI_FILE_PROTOCOL.html#method.Flush
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    pub unsafe fn FlushEx(&mut self, Token: *mut EFI_FILE_IO_TOKEN) -> EFI_STATUS {
+        (self.FlushEx)(self, Token)
+    }
+}
+
+/// The token associated with an asynchronous file I/O request, passed to
+/// [`EFI_FILE_PROTOCOL.OpenEx()`], [`ReadEx()`], [`WriteEx()`] and [`FlushEx()`].
+///
+/// [`EFI_FILE_PROTOCOL.OpenEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.OpenEx
+/// [`ReadEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.ReadEx
+/// [`WriteEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.WriteEx
+/// [`FlushEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.FlushEx
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_FILE_IO_TOKEN {
+    /// If null, the request is processed synchronously. Otherwise, the event is signaled once
+    /// the request completes, and must have been created with the `EVT_NOTIFY_SIGNAL` type.
+    pub Event: EFI_EVENT,
+    /// The completion status of the request, set once [`Event`] is signaled.
+    ///
+    /// [`Event`]: ./struct.EFI_FILE_IO_TOKEN.html#structfield.Event
+    pub Status: EFI_STATUS,
+    /// For [`ReadEx()`] and [`WriteEx()`], the size of [`Buffer`] in bytes on input, and the
+    /// number of bytes transferred on completion. Not used by [`OpenEx()`] and [`FlushEx()`].
+    ///
+    /// [`ReadEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.ReadEx
+    /// [`WriteEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.WriteEx
+    /// [`OpenEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.OpenEx
+    /// [`FlushEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.FlushEx
+    /// [`Buffer`]: ./struct.EFI_FILE_IO_TOKEN.html#structfield.Buffer
+    pub BufferSize: UINTN,
+    /// For [`ReadEx()`] and [`WriteEx()`], the buffer to read into or write from. Not used by
+    /// [`OpenEx()`] and [`FlushEx()`].
+    ///
+    /// [`ReadEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.ReadEx
+    /// [`WriteEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.WriteEx
+    /// [`OpenEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.OpenEx
+    /// [`FlushEx()`]: ./struct.EFI_FILE_PROTOCOL.html#method.FlushEx
+    pub Buffer: *mut VOID,
+}
+
+type EFI_SIMPLE_FILE_SYSTEM_PROTOCOL_OPEN_VOLUME = extern "efiapi" fn(
+    This: *mut EFI_SIMPLE_FILE_SYSTEM_PROTOCOL,
+    Root: *mut *mut EFI_FILE_PROTOCOL,
+) -> EFI_STATUS;
+
+type EFI_FILE_OPEN = extern "efiapi" fn(
+    This: *mut EFI_FILE_PROTOCOL,
+    NewHandle: *mut *mut EFI_FILE_PROTOCOL,
+    FileName: *const CHAR16,
+    OpenMode: UINT64,
+    Attributes: UINT64,
+) -> EFI_STATUS;
+
+type EFI_FILE_CLOSE = extern "efiapi" fn(This: *mut EFI_FILE_PROTOCOL) -> EFI_STATUS;
+
+type EFI_FILE_DELETE = extern "efiapi" fn(This: *mut EFI_FILE_PROTOCOL) -> EFI_STATUS;
+
+type EFI_FILE_READ = extern "efiapi" fn(
+    This: *mut EFI_FILE_PROTOCOL,
+    BufferSize: *mut UINTN,
+    Buffer: *mut VOID,
+) -> EFI_STATUS;
+
+type EFI_FILE_WRITE = extern "efiapi" fn(
+    This: *mut EFI_FILE_PROTOCOL,
+    BufferSize: *mut UINTN,
+    Buffer: *const VOID,
+) -> EFI_STATUS;
+
+type EFI_FILE_GET_POSITION =
+    extern "efiapi" fn(This: *mut EFI_FILE_PROTOCOL, Position: *mut UINT64) -> EFI_STATUS;
+
+type EFI_FILE_SET_POSITION =
+    extern "efiapi" fn(This: *mut EFI_FILE_PROTOCOL, Position: UINT64) -> EFI_STATUS;
+
+type EFI_FILE_GET_INFO = extern "efiapi" fn(
+    This: *mut EFI_FILE_PROTOCOL,
+    InformationType: *const EFI_GUID,
+    BufferSize: *mut UINTN,
+    Buffer: *mut VOID,
+) -> EFI_STATUS;
+
+type EFI_FILE_SET_INFO = extern "efiapi" fn(
+    This: *mut EFI_FILE_PROTOCOL,
+    InformationType: *const EFI_GUID,
+    BufferSize: UINTN,
+    Buffer: *const VOID,
+) -> EFI_STATUS;
+
+type EFI_FILE_FLUSH = extern "efiapi" fn(This: *mut EFI_FILE_PROTOCOL) -> EFI_STATUS;
+
+type EFI_FILE_OPEN_EX = extern "efiapi" fn(
+    This: *mut EFI_FILE_PROTOCOL,
+    NewHandle: *mut *mut EFI_FILE_PROTOCOL,
+    FileName: *const CHAR16,
+    OpenMode: UINT64,
+    Attributes: UINT64,
+    Token: *mut EFI_FILE_IO_TOKEN,
+) -> EFI_STATUS;
+
+type EFI_FILE_READ_EX =
+    extern "efiapi" fn(This: *mut EFI_FILE_PROTOCOL, Token: *mut EFI_FILE_IO_TOKEN) -> EFI_STATUS;
+
+type EFI_FILE_WRITE_EX =
+    extern "efiapi" fn(This: *mut EFI_FILE_PROTOCOL, Token: *mut EFI_FILE_IO_TOKEN) -> EFI_STATUS;
+
+type EFI_FILE_FLUSH_EX =
+    extern "efiapi" fn(This: *mut EFI_FILE_PROTOCOL, Token: *mut EFI_FILE_IO_TOKEN) -> EFI_STATUS;
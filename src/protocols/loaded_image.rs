@@ -0,0 +1,377 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Loaded Image Protocol
+//!
+//! This module defines the Loaded Image Protocol, also known as the [`EFI_LOADED_IMAGE_PROTOCOL`],
+//! which is installed on the handle of every loaded image and describes where the image was
+//! loaded from and where it resides in memory, and the Loaded Image Device Path Protocol, also
+//! known as the [`EFI_LOADED_IMAGE_DEVICE_PATH_PROTOCOL`], which gives the full device path of the
+//! image. See [Section 9 of the UEFI Specification] for more details.
+//!
+//! The [`LoadOptions`] of an image started from the UEFI Shell or a boot option usually hold its
+//! command line as a UCS-2 string, which [`LoadOptionsArgs`] splits into arguments.
+//!
+//! [`EFI_LOADED_IMAGE_PROTOCOL`]: crate::protocols::loaded_image::EFI_LOADED_IMAGE_PROTOCOL
+//! [`EFI_LOADED_IMAGE_DEVICE_PATH_PROTOCOL`]: crate::protocols::loaded_image::EFI_LOADED_IMAGE_DEVICE_PATH_PROTOCOL
+//! [`LoadOptions`]: ./struct.EFI_LOADED_IMAGE_PROTOCOL.html#structfield.LoadOptions
+//! [`LoadOptionsArgs`]: crate::protocols::loaded_image::LoadOptionsArgs
+//!
+//! [Section 9 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/09_Protocols_EFI_Loaded_Image.html
+
+use core::fmt::{self, Display, Formatter, Write};
+use core::iter::FusedIterator;
+use core::{mem, slice};
+
+use crate::protocols::device_path::{DevicePath, DevicePathBuilder, EFI_DEVICE_PATH_PROTOCOL};
+use crate::protocols::Protocol;
+use crate::tables::boot_services::EFI_BOOT_SERVICES;
+use crate::tables::runtime_services::EFI_MEMORY_TYPE;
+use crate::tables::system::EFI_SYSTEM_TABLE;
+use crate::types::{CHAR16, EFI_GUID, EFI_HANDLE, EFI_STATUS, UINT32, UINT64, UINT8, VOID};
+
+/// GUID for the [`EFI_LOADED_IMAGE_PROTOCOL`].
+///
+/// [`EFI_LOADED_IMAGE_PROTOCOL`]: crate::protocols::loaded_image::EFI_LOADED_IMAGE_PROTOCOL
+pub const EFI_LOADED_IMAGE_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0x5B1B31A1,
+    0x9562,
+    0x11D2,
+    [0x8E, 0x3F, 0x00, 0xA0, 0xC9, 0x69, 0x72, 0x3B],
+);
+
+/// GUID for the [`EFI_LOADED_IMAGE_DEVICE_PATH_PROTOCOL`].
+///
+/// [`EFI_LOADED_IMAGE_DEVICE_PATH_PROTOCOL`]: crate::protocols::loaded_image::EFI_LOADED_IMAGE_DEVICE_PATH_PROTOCOL
+pub const EFI_LOADED_IMAGE_DEVICE_PATH_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0xBC62157E,
+    0x3E33,
+    0x4FEC,
+    [0x99, 0x20, 0x2D, 0x3B, 0x36, 0xD7, 0x50, 0xDF],
+);
+
+/// Revision of the [`EFI_LOADED_IMAGE_PROTOCOL`] defined by this version of the specification.
+///
+/// [`EFI_LOADED_IMAGE_PROTOCOL`]: crate::protocols::loaded_image::EFI_LOADED_IMAGE_PROTOCOL
+pub const EFI_LOADED_IMAGE_PROTOCOL_REVISION: UINT32 = 0x1000;
+
+/// Can be used on any image handle to obtain information about the loaded image.
+///
+/// Each loaded image has an image handle that supports this protocol. When an image is started,
+/// it is passed the image handle for itself, which it can use to obtain its
+/// [`EFI_LOADED_IMAGE_PROTOCOL`] structure.
+///
+/// [`EFI_LOADED_IMAGE_PROTOCOL`]: crate::protocols::loaded_image::EFI_LOADED_IMAGE_PROTOCOL
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_LOADED_IMAGE_PROTOCOL {
+    /// Defines the revision of the [`EFI_LOADED_IMAGE_PROTOCOL`] structure. All future revisions
+    /// will be backward compatible to the current revision.
+    ///
+    /// [`EFI_LOADED_IMAGE_PROTOCOL`]: crate::protocols::loaded_image::EFI_LOADED_IMAGE_PROTOCOL
+    pub Revision: UINT32,
+    /// Parent image's image handle. Null if the image is loaded directly from the firmware's boot
+    /// manager.
+    pub ParentHandle: EFI_HANDLE,
+    /// The image's EFI System Table pointer.
+    pub SystemTable: *mut EFI_SYSTEM_TABLE,
+    /// The device handle that the EFI image was loaded from.
+    pub DeviceHandle: EFI_HANDLE,
+    /// A pointer to the file path portion specific to [`DeviceHandle`] that the EFI image was
+    /// loaded from.
+    ///
+    /// [`DeviceHandle`]: ./struct.EFI_LOADED_IMAGE_PROTOCOL.html#structfield.DeviceHandle
+    pub FilePath: *mut EFI_DEVICE_PATH_PROTOCOL,
+    /// Reserved. Must be null.
+    pub Reserved: *mut VOID,
+    /// The size in bytes of [`LoadOptions`].
+    ///
+    /// [`LoadOptions`]: ./struct.EFI_LOADED_IMAGE_PROTOCOL.html#structfield.LoadOptions
+    pub LoadOptionsSize: UINT32,
+    /// A pointer to the image's binary load options.
+    pub LoadOptions: *mut VOID,
+    /// The base address at which the image was loaded.
+    pub ImageBase: *mut VOID,
+    /// The size in bytes of the loaded image.
+    pub ImageSize: UINT64,
+    /// The memory type that the code sections were loaded as.
+    pub ImageCodeType: EFI_MEMORY_TYPE,
+    /// The memory type that the data sections were loaded as.
+    pub ImageDataType: EFI_MEMORY_TYPE,
+    /// Function that unloads the image, or `None` if the image cannot be unloaded. A driver may
+    /// set this field to its unload function from its entry point.
+    pub Unload: Option<EFI_IMAGE_UNLOAD>,
+}
+
+unsafe impl Protocol for EFI_LOADED_IMAGE_PROTOCOL {
+    const GUID: EFI_GUID = EFI_LOADED_IMAGE_PROTOCOL_GUID;
+}
+
+impl EFI_LOADED_IMAGE_PROTOCOL {
+    /// Returns the file path portion of the device path the image was loaded from, or `None` if
+    /// [`FilePath`] is null.
+    ///
+    /// # Safety
+    ///
+    /// [`FilePath`] must be null or point to a well-formed device path.
+    ///
+    /// [`FilePath`]: ./struct.EFI_LOADED_IMAGE_PROTOCOL.html#structfield.FilePath
+    pub unsafe fn file_path(&self) -> Option<DevicePath<'_>> {
+        if self.FilePath.is_null() {
+            None
+        } else {
+            Some(DevicePath::from_ptr(self.FilePath))
+        }
+    }
+
+    /// Writes the full device path of the image into `Buffer`, made of the first instance of the
+    /// device path of [`DeviceHandle`] followed by [`FilePath`].
+    ///
+    /// The device path of [`DeviceHandle`] is the [`EFI_DEVICE_PATH_PROTOCOL`] installed on that
+    /// handle, looked up through `BootServices`. If [`DeviceHandle`] is null, as it is for an
+    /// image loaded from a memory buffer, only [`FilePath`] is written. When the firmware provides
+    /// it, the same device path is also available as the [`EFI_LOADED_IMAGE_DEVICE_PATH_PROTOCOL`]
+    /// on the image handle.
+    ///
+    /// Returns the status of [`HandleProtocol()`] if the device path of [`DeviceHandle`] cannot be
+    /// retrieved, and [`EFI_BUFFER_TOO_SMALL`] if `Buffer` is too small to hold the full device
+    /// path.
+    ///
+    /// # Safety
+    ///
+    /// [`FilePath`] must be null or point to a well-formed device path, and [`DeviceHandle`] must
+    /// be null or a valid handle.
+    ///
+    /// [`DeviceHandle`]: ./struct.EFI_LOADED_IMAGE_PROTOCOL.html#structfield.DeviceHandle
+    /// [`FilePath`]: ./struct.EFI_LOADED_IMAGE_PROTOCOL.html#structfield.FilePath
+    /// [`EFI_DEVICE_PATH_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_PROTOCOL
+    /// [`EFI_LOADED_IMAGE_DEVICE_PATH_PROTOCOL`]: crate::protocols::loaded_image::EFI_LOADED_IMAGE_DEVICE_PATH_PROTOCOL
+    /// [`HandleProtocol()`]: crate::tables::boot_services::EFI_BOOT_SERVICES::HandleProtocol
+    /// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+    pub unsafe fn full_device_path<'b>(
+        &self,
+        BootServices: &EFI_BOOT_SERVICES,
+        Buffer: &'b mut [UINT8],
+    ) -> Result<DevicePath<'b>, EFI_STATUS> {
+        let mut builder = DevicePathBuilder::new(Buffer);
+        if !self.DeviceHandle.is_null() {
+            let device_path =
+                BootServices.handle_protocol::<EFI_DEVICE_PATH_PROTOCOL>(self.DeviceHandle)?;
+            for node in DevicePath::from_ptr(device_path)
+                .nodes()
+                .take_while(|node| !node.is_end_instance())
+            {
+                builder.push_node(node)?;
+            }
+        }
+
+        if let Some(file_path) = self.file_path() {
+            builder.push_path(file_path)?;
+        }
+
+        builder.finish()
+    }
+
+    /// Returns the load options of the image as bytes.
+    ///
+    /// # Safety
+    ///
+    /// [`LoadOptions`] must be null or point to [`LoadOptionsSize`] readable bytes.
+    ///
+    /// [`LoadOptions`]: ./struct.EFI_LOADED_IMAGE_PROTOCOL.html#structfield.LoadOptions
+    /// [`LoadOptionsSize`]: ./struct.EFI_LOADED_IMAGE_PROTOCOL.html#structfield.LoadOptionsSize
+    pub unsafe fn load_options(&self) -> &[UINT8] {
+        if self.LoadOptions.is_null() {
+            &[]
+        } else {
+            slice::from_raw_parts(self.LoadOptions.cast(), self.LoadOptionsSize as usize)
+        }
+    }
+
+    /// Returns the load options of the image as a UCS-2 string, up to its first null character,
+    /// or `None` if they are not suitably aligned or sized to be one.
+    ///
+    /// The load options are not guaranteed to be a string: an image started from a boot option
+    /// receives the optional data of that boot option, which may be binary.
+    ///
+    /// # Safety
+    ///
+    /// [`LoadOptions`] must be null or point to [`LoadOptionsSize`] readable bytes.
+    ///
+    /// [`LoadOptions`]: ./struct.EFI_LOADED_IMAGE_PROTOCOL.html#structfield.LoadOptions
+    /// [`LoadOptionsSize`]: ./struct.EFI_LOADED_IMAGE_PROTOCOL.html#structfield.LoadOptionsSize
+    pub unsafe fn load_options_ucs2(&self) -> Option<&[CHAR16]> {
+        let options = self.load_options();
+        if options.len() % mem::size_of::<CHAR16>() != 0
+            || options.as_ptr() as usize % mem::align_of::<CHAR16>() != 0
+        {
+            return None;
+        }
+
+        let options = slice::from_raw_parts(options.as_ptr().cast::<CHAR16>(), options.len() / 2);
+        let len = options
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(options.len());
+        Some(&options[..len])
+    }
+
+    /// Returns an iterator over the command-line arguments held in the load options of the image.
+    /// The first argument is usually the name of the image itself.
+    ///
+    /// No arguments are returned if the load options are not a UCS-2 string.
+    ///
+    /// # Safety
+    ///
+    /// [`LoadOptions`] must be null or point to [`LoadOptionsSize`] readable bytes.
+    ///
+    /// [`LoadOptions`]: ./struct.EFI_LOADED_IMAGE_PROTOCOL.html#structfield.LoadOptions
+    /// [`LoadOptionsSize`]: ./struct.EFI_LOADED_IMAGE_PROTOCOL.html#structfield.LoadOptionsSize
+    pub unsafe fn args(&self) -> LoadOptionsArgs<'_> {
+        LoadOptionsArgs::new(self.load_options_ucs2().unwrap_or_default())
+    }
+}
+
+/// When installed, the Loaded Image Device Path Protocol specifies the device path that was used
+/// when a PE/COFF image was loaded through the EFI Boot Service [`LoadImage()`].
+///
+/// The Loaded Image Device Path Protocol uses the same protocol interface structure as the
+/// [`EFI_DEVICE_PATH_PROTOCOL`]. The only difference is its GUID.
+///
+/// [`LoadImage()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-loadimage
+/// [`EFI_DEVICE_PATH_PROTOCOL`]: crate::protocols::device_path::EFI_DEVICE_PATH_PROTOCOL
+pub type EFI_LOADED_IMAGE_DEVICE_PATH_PROTOCOL = EFI_DEVICE_PATH_PROTOCOL;
+
+/// An iterator over the arguments of a UCS-2 command line, such as the load options of an image.
+///
+/// Arguments are separated by spaces and tabs. As in the UEFI Shell, double quotes group
+/// characters including separators into a single argument and are removed from it, and `^`
+/// escapes the character following it, so that `^"` stands for a literal double quote.
+#[derive(Clone)]
+pub struct LoadOptionsArgs<'a> {
+    rest: &'a [CHAR16],
+}
+
+impl<'a> LoadOptionsArgs<'a> {
+    /// Creates an iterator over the arguments of `CommandLine`, which ends at its first null
+    /// character, if any.
+    pub fn new(CommandLine: &'a [CHAR16]) -> Self {
+        let len = CommandLine
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(CommandLine.len());
+        Self {
+            rest: &CommandLine[..len],
+        }
+    }
+}
+
+impl<'a> Iterator for LoadOptionsArgs<'a> {
+    type Item = LoadOptionsArg<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.rest.iter().position(|&c| !is_separator(c))?;
+        let rest = &self.rest[start..];
+
+        let mut quoted = false;
+        let mut escaped = false;
+        let mut end = rest.len();
+        for (i, &c) in rest.iter().enumerate() {
+            if escaped {
+                escaped = false;
+            } else if c == CARET {
+                escaped = true;
+            } else if c == QUOTE {
+                quoted = !quoted;
+            } else if !quoted && is_separator(c) {
+                end = i;
+                break;
+            }
+        }
+
+        self.rest = &rest[end..];
+        Some(LoadOptionsArg { raw: &rest[..end] })
+    }
+}
+
+impl FusedIterator for LoadOptionsArgs<'_> {}
+
+/// A single argument returned by [`LoadOptionsArgs`].
+///
+/// The argument implements [`Display`], which writes it without its quotes and escape
+/// characters.
+///
+/// [`LoadOptionsArgs`]: crate::protocols::loaded_image::LoadOptionsArgs
+/// [`Display`]: core::fmt::Display
+#[derive(Clone, Copy)]
+pub struct LoadOptionsArg<'a> {
+    raw: &'a [CHAR16],
+}
+
+impl<'a> LoadOptionsArg<'a> {
+    /// Returns the argument as it appears on the command line, including quotes and escape
+    /// characters.
+    pub fn as_raw(&self) -> &'a [CHAR16] {
+        self.raw
+    }
+
+    /// Returns an iterator over the UCS-2 characters of the argument, without its quotes and
+    /// escape characters.
+    pub fn chars16(&self) -> impl Iterator<Item = CHAR16> + 'a {
+        let mut escaped = false;
+        self.raw.iter().filter_map(move |&c| {
+            if escaped {
+                escaped = false;
+                Some(c)
+            } else if c == CARET {
+                escaped = true;
+                None
+            } else if c == QUOTE {
+                None
+            } else {
+                Some(c)
+            }
+        })
+    }
+
+    /// Returns an iterator over the characters of the argument, without its quotes and escape
+    /// characters. Unpaired surrogates are replaced by [`char::REPLACEMENT_CHARACTER`].
+    pub fn chars(&self) -> impl Iterator<Item = char> + 'a {
+        char::decode_utf16(self.chars16()).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    /// Returns `true` if the argument is equal to `Value`.
+    pub fn eq_str(&self, Value: &str) -> bool {
+        self.chars().eq(Value.chars())
+    }
+}
+
+impl Display for LoadOptionsArg<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.chars().try_for_each(|c| f.write_char(c))
+    }
+}
+
+const QUOTE: CHAR16 = b'"' as CHAR16;
+const CARET: CHAR16 = b'^' as CHAR16;
+
+fn is_separator(c: CHAR16) -> bool {
+    c == b' ' as CHAR16 || c == b'\t' as CHAR16
+}
+
+/// Unloads an image.
+pub type EFI_IMAGE_UNLOAD = extern "efiapi" fn(ImageHandle: EFI_HANDLE) -> EFI_STATUS;
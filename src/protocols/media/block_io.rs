@@ -0,0 +1,523 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Block I/O Protocol
+//!
+//! This module defines the Block I/O Protocol, also known as the [`EFI_BLOCK_IO_PROTOCOL`], and
+//! the [`EFI_BLOCK_IO_MEDIA`] structure describing the medium of a block device. See
+//! [Section 13.9 of the UEFI Specification] for more details.
+//!
+//! [`EFI_BLOCK_IO_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO_PROTOCOL
+//! [`EFI_BLOCK_IO_MEDIA`]: crate::protocols::media::block_io::EFI_BLOCK_IO_MEDIA
+//!
+//! [Section 13.9 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/13_Protocols_Media_Access.html#block-i-o-protocol
+
+use core::ptr;
+
+use crate::protocols::Protocol;
+use crate::status::{
+    EFI_BAD_BUFFER_SIZE, EFI_INVALID_PARAMETER, EFI_MEDIA_CHANGED, EFI_NO_MEDIA, EFI_SUCCESS,
+    EFI_WRITE_PROTECTED,
+};
+use crate::types::{BOOLEAN, EFI_GUID, EFI_LBA, EFI_STATUS, UINT32, UINT64, UINT8, UINTN, VOID};
+
+/// GUID for the [`EFI_BLOCK_IO_PROTOCOL`].
+///
+/// [`EFI_BLOCK_IO_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO_PROTOCOL
+pub const EFI_BLOCK_IO_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0x964E5B21,
+    0x6459,
+    0x11D2,
+    [0x8E, 0x39, 0x00, 0xA0, 0xC9, 0x69, 0x72, 0x3B],
+);
+
+/// Revision of the [`EFI_BLOCK_IO_PROTOCOL`] without the revision 2 and 3 fields of the
+/// [`EFI_BLOCK_IO_MEDIA`].
+///
+/// [`EFI_BLOCK_IO_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO_PROTOCOL
+/// [`EFI_BLOCK_IO_MEDIA`]: crate::protocols::media::block_io::EFI_BLOCK_IO_MEDIA
+pub const EFI_BLOCK_IO_PROTOCOL_REVISION: UINT64 = 0x00010000;
+
+/// Revision of the [`EFI_BLOCK_IO_PROTOCOL`] whose media provides the [`LowestAlignedLba`] and
+/// [`LogicalBlocksPerPhysicalBlock`] fields.
+///
+/// [`EFI_BLOCK_IO_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO_PROTOCOL
+/// [`LowestAlignedLba`]: ./struct.EFI_BLOCK_IO_MEDIA.html#structfield.LowestAlignedLba
+/// [`LogicalBlocksPerPhysicalBlock`]: ./struct.EFI_BLOCK_IO_MEDIA.html#structfield.LogicalBlocksPerPhysicalBlock
+pub const EFI_BLOCK_IO_PROTOCOL_REVISION2: UINT64 = 0x00020001;
+
+/// Revision of the [`EFI_BLOCK_IO_PROTOCOL`] whose media additionally provides the
+/// [`OptimalTransferLengthGranularity`] field.
+///
+/// [`EFI_BLOCK_IO_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO_PROTOCOL
+/// [`OptimalTransferLengthGranularity`]: ./struct.EFI_BLOCK_IO_MEDIA.html#structfield.OptimalTransferLengthGranularity
+pub const EFI_BLOCK_IO_PROTOCOL_REVISION3: UINT64 = (2 << 16) | 31;
+
+/// Provides the services required to access block devices.
+///
+/// The [`EFI_BLOCK_IO_PROTOCOL`] is used to abstract mass storage devices to allow code running
+/// in the EFI boot services environment to access them without specific knowledge of the type of
+/// device or controller that manages the device. Functions are defined to read and write data at
+/// a block level from mass storage devices as well as to manage such devices in the EFI boot
+/// services environment.
+///
+/// [`EFI_BLOCK_IO_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO_PROTOCOL
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_BLOCK_IO_PROTOCOL {
+    /// The revision to which the block I/O interface adheres. All future revisions must be
+    /// backwards compatible.
+    pub Revision: UINT64,
+    /// A pointer to the [`EFI_BLOCK_IO_MEDIA`] data for this device.
+    ///
+    /// [`EFI_BLOCK_IO_MEDIA`]: crate::protocols::media::block_io::EFI_BLOCK_IO_MEDIA
+    pub Media: *mut EFI_BLOCK_IO_MEDIA,
+    Reset: EFI_BLOCK_RESET,
+    ReadBlocks: EFI_BLOCK_READ,
+    WriteBlocks: EFI_BLOCK_WRITE,
+    FlushBlocks: EFI_BLOCK_FLUSH,
+}
+
+unsafe impl Protocol for EFI_BLOCK_IO_PROTOCOL {
+    const GUID: EFI_GUID = EFI_BLOCK_IO_PROTOCOL_GUID;
+}
+
+impl EFI_BLOCK_IO_PROTOCOL {
+    /// Resets the block device hardware.
+    ///
+    /// The [`Reset()`] function resets the block device hardware.
+    ///
+    /// As part of the initialization process, the firmware/device will make a quick but reasonable
+    /// attempt to verify that the device is functioning. If the [`ExtendedVerification`] flag is
+    /// `TRUE` the firmware may take an extended amount of time to verify the device is operating
+    /// on reset. Otherwise the reset operation is to occur as quickly as possible.
+    ///
+    /// # Parameters
+    ///
+    /// ## `ExtendedVerification`
+    ///
+    /// Indicates that the driver may perform a more exhaustive verification operation of the device
+    /// during reset.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the block device was reset.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the block device is not functioning correctly and could not be
+    /// reset.
+    ///
+    /// [`Reset()`]: ./struct.EFI_BLOCK_IO_PROTOCOL.html#method.Reset
+    /// [`ExtendedVerification`]: ./struct.EFI_BLOCK_IO_PROTOCOL.html#extendedverification
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    pub unsafe fn Reset(&mut self, ExtendedVerification: BOOLEAN) -> EFI_STATUS {
+        (self.Reset)(self, ExtendedVerification)
+    }
+
+    /// Reads the requested number of blocks from the device.
+    ///
+    /// The [`ReadBlocks()`] function reads the requested number of blocks from the device. All
+    /// the blocks are read, or an error is returned.
+    ///
+    /// If there is no media in the device, the function returns [`EFI_NO_MEDIA`]. If the
+    /// `MediaId` is not the ID for the current media in the device, the function returns
+    /// [`EFI_MEDIA_CHANGED`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `MediaId`
+    ///
+    /// The media ID that the read request is for.
+    ///
+    /// ## `LBA`
+    ///
+    /// The starting logical block address to read from on the device.
+    ///
+    /// ## `BufferSize`
+    ///
+    /// The size of the `Buffer` in bytes. This must be a multiple of the intrinsic block size of
+    /// the device.
+    ///
+    /// ## `Buffer`
+    ///
+    /// A pointer to the destination buffer for the data. The caller is responsible for either
+    /// having implicit or explicit ownership of the buffer.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the data was read correctly from the device.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error while attempting to perform the read
+    /// operation.
+    ///
+    /// [`EFI_NO_MEDIA`] - there is no media in the device.
+    ///
+    /// [`EFI_MEDIA_CHANGED`] - the `MediaId` is not for the current media.
+    ///
+    /// [`EFI_BAD_BUFFER_SIZE`] - the `BufferSize` parameter is not a multiple of the intrinsic
+    /// block size of the device.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - the read request contains LBAs that are not valid, or the
+    /// buffer is not on proper alignment.
+    ///
+    /// [`ReadBlocks()`]: ./struct.EFI_BLOCK_IO_PROTOCOL.html#method.ReadBlocks
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_MEDIA_CHANGED`]: crate::status::EFI_MEDIA_CHANGED
+    /// [`EFI_BAD_BUFFER_SIZE`]: crate::status::EFI_BAD_BUFFER_SIZE
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn ReadBlocks(
+        &mut self,
+        MediaId: UINT32,
+        LBA: EFI_LBA,
+        BufferSize: UINTN,
+        Buffer: *mut VOID,
+    ) -> EFI_STATUS {
+        (self.ReadBlocks)(self, MediaId, LBA, BufferSize, Buffer)
+    }
+
+    /// Writes a specified number of blocks to the device.
+    ///
+    /// The [`WriteBlocks()`] function writes the requested number of blocks to the device. All
+    /// blocks are written, or an error is returned.
+    ///
+    /// If there is no media in the device, the function returns [`EFI_NO_MEDIA`]. If the
+    /// `MediaId` is not the ID for the current media in the device, the function returns
+    /// [`EFI_MEDIA_CHANGED`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `MediaId`
+    ///
+    /// The media ID that the write request is for.
+    ///
+    /// ## `LBA`
+    ///
+    /// The starting logical block address to be written.
+    ///
+    /// ## `BufferSize`
+    ///
+    /// The size in bytes of `Buffer`. This must be a multiple of the intrinsic block size of the
+    /// device.
+    ///
+    /// ## `Buffer`
+    ///
+    /// A pointer to the source buffer for the data.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the data were written correctly to the device.
+    ///
+    /// [`EFI_WRITE_PROTECTED`] - the device cannot be written to.
+    ///
+    /// [`EFI_NO_MEDIA`] - there is no media in the device.
+    ///
+    /// [`EFI_MEDIA_CHANGED`] - the `MediaId` is not for the current media.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error while attempting to perform the write
+    /// operation.
+    ///
+    /// [`EFI_BAD_BUFFER_SIZE`] - the `BufferSize` parameter is not a multiple of the intrinsic
+    /// block size of the device.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - the write request contains LBAs that are not valid, or the
+    /// buffer is not on proper alignment.
+    ///
+    /// [`WriteBlocks()`]: ./struct.EFI_BLOCK_IO_PROTOCOL.html#method.WriteBlocks
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_WRITE_PROTECTED`]: crate::status::EFI_WRITE_PROTECTED
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_MEDIA_CHANGED`]: crate::status::EFI_MEDIA_CHANGED
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_BAD_BUFFER_SIZE`]: crate::status::EFI_BAD_BUFFER_SIZE
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn WriteBlocks(
+        &mut self,
+        MediaId: UINT32,
+        LBA: EFI_LBA,
+        BufferSize: UINTN,
+        Buffer: *const VOID,
+    ) -> EFI_STATUS {
+        (self.WriteBlocks)(self, MediaId, LBA, BufferSize, Buffer)
+    }
+
+    /// Flushes all modified data to a physical block device.
+    ///
+    /// The [`FlushBlocks()`] function flushes all modified data to the physical block device.
+    ///
+    /// All data written to the device prior to the flush must be physically written before
+    /// returning [`EFI_SUCCESS`] from this function. This would include any cached data the driver
+    /// may have cached, and cached data the device may have cached. A flush may cause a read
+    /// request following the flush to force a device access.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - all outstanding data were written correctly to the device.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error while attempting to write data.
+    ///
+    /// [`EFI_NO_MEDIA`] - there is no media in the device.
+    ///
+    /// [`FlushBlocks()`]: ./struct.EFI_BLOCK_IO_PROTOCOL.html#method.FlushBlocks
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    pub unsafe fn FlushBlocks(&mut self) -> EFI_STATUS {
+        (self.FlushBlocks)(self)
+    }
+
+    /// Returns a copy of the media of the device.
+    ///
+    /// Only the fields provided by the [`Revision`] of the protocol are read from the firmware,
+    /// since the [`EFI_BLOCK_IO_MEDIA`] of older revisions is shorter; the fields it lacks are set
+    /// to `0`.
+    ///
+    /// [`Revision`]: ./struct.EFI_BLOCK_IO_PROTOCOL.html#structfield.Revision
+    /// [`EFI_BLOCK_IO_MEDIA`]: crate::protocols::media::block_io::EFI_BLOCK_IO_MEDIA
+    pub unsafe fn media(&self) -> EFI_BLOCK_IO_MEDIA {
+        let media = self.Media;
+        let mut copy = EFI_BLOCK_IO_MEDIA {
+            MediaId: ptr::addr_of!((*media).MediaId).read(),
+            RemovableMedia: ptr::addr_of!((*media).RemovableMedia).read(),
+            MediaPresent: ptr::addr_of!((*media).MediaPresent).read(),
+            LogicalPartition: ptr::addr_of!((*media).LogicalPartition).read(),
+            ReadOnly: ptr::addr_of!((*media).ReadOnly).read(),
+            WriteCaching: ptr::addr_of!((*media).WriteCaching).read(),
+            BlockSize: ptr::addr_of!((*media).BlockSize).read(),
+            IoAlign: ptr::addr_of!((*media).IoAlign).read(),
+            LastBlock: ptr::addr_of!((*media).LastBlock).read(),
+            LowestAlignedLba: 0,
+            LogicalBlocksPerPhysicalBlock: 0,
+            OptimalTransferLengthGranularity: 0,
+        };
+
+        if self.Revision >= EFI_BLOCK_IO_PROTOCOL_REVISION2 {
+            copy.LowestAlignedLba = ptr::addr_of!((*media).LowestAlignedLba).read();
+            copy.LogicalBlocksPerPhysicalBlock =
+                ptr::addr_of!((*media).LogicalBlocksPerPhysicalBlock).read();
+        }
+        if self.Revision >= EFI_BLOCK_IO_PROTOCOL_REVISION3 {
+            copy.OptimalTransferLengthGranularity =
+                ptr::addr_of!((*media).OptimalTransferLengthGranularity).read();
+        }
+
+        copy
+    }
+
+    /// Reads whole blocks starting at `LBA` into `Buffer`, after checking the request with
+    /// [`EFI_BLOCK_IO_MEDIA::check_request()`].
+    ///
+    /// [`EFI_BLOCK_IO_MEDIA::check_request()`]: crate::protocols::media::block_io::EFI_BLOCK_IO_MEDIA::check_request
+    pub unsafe fn read_blocks(
+        &mut self,
+        MediaId: UINT32,
+        LBA: EFI_LBA,
+        Buffer: &mut [UINT8],
+    ) -> Result<(), EFI_STATUS> {
+        self.media()
+            .check_request(MediaId, LBA, Buffer.as_ptr(), Buffer.len())?;
+        match self.ReadBlocks(MediaId, LBA, Buffer.len(), Buffer.as_mut_ptr().cast()) {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Writes whole blocks starting at `LBA` from `Buffer`, after checking the request with
+    /// [`EFI_BLOCK_IO_MEDIA::check_request()`].
+    ///
+    /// Returns [`EFI_WRITE_PROTECTED`] without calling the firmware if the media is read-only.
+    ///
+    /// [`EFI_BLOCK_IO_MEDIA::check_request()`]: crate::protocols::media::block_io::EFI_BLOCK_IO_MEDIA::check_request
+    /// [`EFI_WRITE_PROTECTED`]: crate::status::EFI_WRITE_PROTECTED
+    pub unsafe fn write_blocks(
+        &mut self,
+        MediaId: UINT32,
+        LBA: EFI_LBA,
+        Buffer: &[UINT8],
+    ) -> Result<(), EFI_STATUS> {
+        let media = self.media();
+        media.check_request(MediaId, LBA, Buffer.as_ptr(), Buffer.len())?;
+        if media.ReadOnly != 0 {
+            return Err(EFI_WRITE_PROTECTED);
+        }
+
+        match self.WriteBlocks(MediaId, LBA, Buffer.len(), Buffer.as_ptr().cast()) {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Flushes all modified data to the device.
+    pub unsafe fn flush_blocks(&mut self) -> Result<(), EFI_STATUS> {
+        match self.FlushBlocks() {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+}
+
+/// Describes the medium of a block device.
+///
+/// The fields after [`LastBlock`] are only present if the [`Revision`] of the
+/// [`EFI_BLOCK_IO_PROTOCOL`] is at least [`EFI_BLOCK_IO_PROTOCOL_REVISION2`] (for
+/// [`LowestAlignedLba`] and [`LogicalBlocksPerPhysicalBlock`]) or
+/// [`EFI_BLOCK_IO_PROTOCOL_REVISION3`] (for [`OptimalTransferLengthGranularity`]).
+///
+/// [`LastBlock`]: ./struct.EFI_BLOCK_IO_MEDIA.html#structfield.LastBlock
+/// [`Revision`]: ./struct.EFI_BLOCK_IO_PROTOCOL.html#structfield.Revision
+/// [`EFI_BLOCK_IO_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO_PROTOCOL
+/// [`EFI_BLOCK_IO_PROTOCOL_REVISION2`]: crate::protocols::media::block_io::EFI_BLOCK_IO_PROTOCOL_REVISION2
+/// [`EFI_BLOCK_IO_PROTOCOL_REVISION3`]: crate::protocols::media::block_io::EFI_BLOCK_IO_PROTOCOL_REVISION3
+/// [`LowestAlignedLba`]: ./struct.EFI_BLOCK_IO_MEDIA.html#structfield.LowestAlignedLba
+/// [`LogicalBlocksPerPhysicalBlock`]: ./struct.EFI_BLOCK_IO_MEDIA.html#structfield.LogicalBlocksPerPhysicalBlock
+/// [`OptimalTransferLengthGranularity`]: ./struct.EFI_BLOCK_IO_MEDIA.html#structfield.OptimalTransferLengthGranularity
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_BLOCK_IO_MEDIA {
+    /// The current media ID. If the media changes, this value is changed.
+    pub MediaId: UINT32,
+    /// `TRUE` if the media is removable; otherwise, `FALSE`.
+    pub RemovableMedia: BOOLEAN,
+    /// `TRUE` if there is a media currently present in the device; otherwise, `FALSE`.
+    pub MediaPresent: BOOLEAN,
+    /// `TRUE` if the Block I/O protocol is produced for a partition of a larger device, and
+    /// `FALSE` if it is produced for the whole device.
+    pub LogicalPartition: BOOLEAN,
+    /// `TRUE` if the media is marked read-only; otherwise, `FALSE`.
+    pub ReadOnly: BOOLEAN,
+    /// `TRUE` if the write operations are cached; otherwise, `FALSE`.
+    pub WriteCaching: BOOLEAN,
+    /// The intrinsic block size of the device.
+    pub BlockSize: UINT32,
+    /// Supplies the alignment requirement for any buffer used in a data transfer. A value of `0`
+    /// or `1` means the buffer can be placed anywhere in memory; otherwise, the buffer must be
+    /// aligned on an `IoAlign` boundary, which is a power of two.
+    pub IoAlign: UINT32,
+    /// The last LBA on the device.
+    pub LastBlock: EFI_LBA,
+    /// The first LBA that is aligned to a physical block boundary. Only present if the
+    /// [`Revision`] is at least [`EFI_BLOCK_IO_PROTOCOL_REVISION2`].
+    ///
+    /// [`Revision`]: ./struct.EFI_BLOCK_IO_PROTOCOL.html#structfield.Revision
+    /// [`EFI_BLOCK_IO_PROTOCOL_REVISION2`]: crate::protocols::media::block_io::EFI_BLOCK_IO_PROTOCOL_REVISION2
+    pub LowestAlignedLba: EFI_LBA,
+    /// The number of logical blocks per physical block. Only present if the [`Revision`] is at
+    /// least [`EFI_BLOCK_IO_PROTOCOL_REVISION2`].
+    ///
+    /// [`Revision`]: ./struct.EFI_BLOCK_IO_PROTOCOL.html#structfield.Revision
+    /// [`EFI_BLOCK_IO_PROTOCOL_REVISION2`]: crate::protocols::media::block_io::EFI_BLOCK_IO_PROTOCOL_REVISION2
+    pub LogicalBlocksPerPhysicalBlock: UINT32,
+    /// The optimal transfer length granularity, in logical blocks, or `0` if it is not reported.
+    /// Only present if the [`Revision`] is at least [`EFI_BLOCK_IO_PROTOCOL_REVISION3`].
+    ///
+    /// [`Revision`]: ./struct.EFI_BLOCK_IO_PROTOCOL.html#structfield.Revision
+    /// [`EFI_BLOCK_IO_PROTOCOL_REVISION3`]: crate::protocols::media::block_io::EFI_BLOCK_IO_PROTOCOL_REVISION3
+    pub OptimalTransferLengthGranularity: UINT32,
+}
+
+impl EFI_BLOCK_IO_MEDIA {
+    /// Checks a transfer of `BufferSize` bytes between `Buffer` and the blocks starting at `LBA`
+    /// against the media, before it is passed to the firmware.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_NO_MEDIA`] - there is no media in the device.
+    ///
+    /// [`EFI_MEDIA_CHANGED`] - the `MediaId` is not for the current media.
+    ///
+    /// [`EFI_BAD_BUFFER_SIZE`] - `BufferSize` is not a multiple of [`BlockSize`].
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - `Buffer` is not aligned on an [`IoAlign`] boundary, or the
+    /// transfer extends past [`LastBlock`].
+    ///
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_MEDIA_CHANGED`]: crate::status::EFI_MEDIA_CHANGED
+    /// [`EFI_BAD_BUFFER_SIZE`]: crate::status::EFI_BAD_BUFFER_SIZE
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`BlockSize`]: ./struct.EFI_BLOCK_IO_MEDIA.html#structfield.BlockSize
+    /// [`IoAlign`]: ./struct.EFI_BLOCK_IO_MEDIA.html#structfield.IoAlign
+    /// [`LastBlock`]: ./struct.EFI_BLOCK_IO_MEDIA.html#structfield.LastBlock
+    pub fn check_request(
+        &self,
+        MediaId: UINT32,
+        LBA: EFI_LBA,
+        Buffer: *const UINT8,
+        BufferSize: usize,
+    ) -> Result<(), EFI_STATUS> {
+        if self.MediaPresent == 0 {
+            return Err(EFI_NO_MEDIA);
+        }
+        if MediaId != self.MediaId {
+            return Err(EFI_MEDIA_CHANGED);
+        }
+        if self.BlockSize == 0 || BufferSize % self.BlockSize as usize != 0 {
+            return Err(EFI_BAD_BUFFER_SIZE);
+        }
+        if !self.is_aligned(Buffer) {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        let blocks = (BufferSize / self.BlockSize as usize) as u64;
+        match LBA.checked_add(blocks) {
+            Some(_) if blocks == 0 => Ok(()),
+            Some(end) if LBA <= self.LastBlock && end - 1 <= self.LastBlock => Ok(()),
+            _ => Err(EFI_INVALID_PARAMETER),
+        }
+    }
+
+    /// Returns `true` if `Buffer` satisfies the [`IoAlign`] requirement of the media.
+    ///
+    /// [`IoAlign`]: ./struct.EFI_BLOCK_IO_MEDIA.html#structfield.IoAlign
+    pub fn is_aligned(&self, Buffer: *const UINT8) -> bool {
+        self.IoAlign <= 1 || Buffer as usize % self.IoAlign as usize == 0
+    }
+
+    /// Returns the size of the media in bytes.
+    pub fn size(&self) -> UINT64 {
+        if self.MediaPresent == 0 {
+            return 0;
+        }
+
+        self.LastBlock
+            .saturating_add(1)
+            .saturating_mul(self.BlockSize as UINT64)
+    }
+}
+
+type EFI_BLOCK_RESET = extern "efiapi" fn(
+    This: *mut EFI_BLOCK_IO_PROTOCOL,
+    ExtendedVerification: BOOLEAN,
+) -> EFI_STATUS;
+
+type EFI_BLOCK_READ = extern "efiapi" fn(
+    This: *mut EFI_BLOCK_IO_PROTOCOL,
+    MediaId: UINT32,
+    LBA: EFI_LBA,
+    BufferSize: UINTN,
+    Buffer: *mut VOID,
+) -> EFI_STATUS;
+
+type EFI_BLOCK_WRITE = extern "efiapi" fn(
+    This: *mut EFI_BLOCK_IO_PROTOCOL,
+    MediaId: UINT32,
+    LBA: EFI_LBA,
+    BufferSize: UINTN,
+    Buffer: *const VOID,
+) -> EFI_STATUS;
+
+type EFI_BLOCK_FLUSH = extern "efiapi" fn(This: *mut EFI_BLOCK_IO_PROTOCOL) -> EFI_STATUS;
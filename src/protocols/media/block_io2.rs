@@ -0,0 +1,454 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Block I/O 2 Protocol
+//!
+//! This module defines the Block I/O 2 Protocol, also known as the [`EFI_BLOCK_IO2_PROTOCOL`],
+//! which extends the [`EFI_BLOCK_IO_PROTOCOL`] with non-blocking transfers. See
+//! [Section 13.10 of the UEFI Specification] for more details.
+//!
+//! [`EFI_BLOCK_IO2_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO2_PROTOCOL
+//! [`EFI_BLOCK_IO_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO_PROTOCOL
+//!
+//! [Section 13.10 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/13_Protocols_Media_Access.html#block-i-o-2-protocol
+
+use core::ptr;
+
+use crate::protocols::media::block_io::EFI_BLOCK_IO_MEDIA;
+use crate::protocols::Protocol;
+use crate::status::{EFI_SUCCESS, EFI_WRITE_PROTECTED};
+use crate::types::{BOOLEAN, EFI_EVENT, EFI_GUID, EFI_LBA, EFI_STATUS, UINT32, UINT8, UINTN, VOID};
+
+/// GUID for the [`EFI_BLOCK_IO2_PROTOCOL`].
+///
+/// [`EFI_BLOCK_IO2_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO2_PROTOCOL
+pub const EFI_BLOCK_IO2_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0xA77B2472,
+    0xE282,
+    0x4E9F,
+    [0xA2, 0x45, 0xC2, 0xC0, 0xE2, 0x7B, 0xBC, 0xC1],
+);
+
+/// Provides the services required to access block devices, with support for non-blocking
+/// transfers.
+///
+/// The [`EFI_BLOCK_IO2_PROTOCOL`] is used to abstract mass storage devices to allow code running
+/// in the EFI boot services environment to access them without specific knowledge of the type of
+/// device or controller that manages the device. Each transfer takes an
+/// [`EFI_BLOCK_IO2_TOKEN`]; if its [`Event`] is not null the transfer is queued and the event is
+/// signaled once it completes, otherwise the transfer is performed synchronously.
+///
+/// [`EFI_BLOCK_IO2_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO2_PROTOCOL
+/// [`EFI_BLOCK_IO2_TOKEN`]: crate::protocols::media::block_io2::EFI_BLOCK_IO2_TOKEN
+/// [`Event`]: ./struct.EFI_BLOCK_IO2_TOKEN.html#structfield.Event
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_BLOCK_IO2_PROTOCOL {
+    /// A pointer to the [`EFI_BLOCK_IO_MEDIA`] data for this device.
+    ///
+    /// [`EFI_BLOCK_IO_MEDIA`]: crate::protocols::media::block_io::EFI_BLOCK_IO_MEDIA
+    pub Media: *mut EFI_BLOCK_IO_MEDIA,
+    Reset: EFI_BLOCK_RESET_EX,
+    ReadBlocksEx: EFI_BLOCK_READ_EX,
+    WriteBlocksEx: EFI_BLOCK_WRITE_EX,
+    FlushBlocksEx: EFI_BLOCK_FLUSH_EX,
+}
+
+unsafe impl Protocol for EFI_BLOCK_IO2_PROTOCOL {
+    const GUID: EFI_GUID = EFI_BLOCK_IO2_PROTOCOL_GUID;
+}
+
+impl EFI_BLOCK_IO2_PROTOCOL {
+    /// Resets the block device hardware.
+    ///
+    /// The [`Reset()`] function resets the block device hardware. It also aborts all
+    /// outstanding non-blocking requests; their events are not signaled.
+    ///
+    /// # Parameters
+    ///
+    /// ## `ExtendedVerification`
+    ///
+    /// Indicates that the driver may perform a more exhaustive verification operation of the device
+    /// during reset.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the device was reset.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device is not functioning properly and could not be reset.
+    ///
+    /// [`Reset()`]: ./struct.EFI_BLOCK_IO2_PROTOCOL.html#method.Reset
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    pub unsafe fn Reset(&mut self, ExtendedVerification: BOOLEAN) -> EFI_STATUS {
+        (self.Reset)(self, ExtendedVerification)
+    }
+
+    /// Reads the requested number of blocks from the device.
+    ///
+    /// The [`ReadBlocksEx()`] function reads the requested number of blocks from the device. All
+    /// the blocks are read, or an error is returned.
+    ///
+    /// If the [`Event`] of the `Token` is null, the read is performed synchronously. Otherwise the
+    /// request is queued, the function returns immediately, and the event is signaled once the
+    /// read has completed; the result of the read is then stored in [`TransactionStatus`]. The
+    /// `Buffer` must remain valid until the event is signaled.
+    ///
+    /// # Parameters
+    ///
+    /// ## `MediaId`
+    ///
+    /// The media ID that the read request is for.
+    ///
+    /// ## `LBA`
+    ///
+    /// The starting logical block address to read from on the device.
+    ///
+    /// ## `Token`
+    ///
+    /// A pointer to the token associated with the transaction.
+    ///
+    /// ## `BufferSize`
+    ///
+    /// The size of the `Buffer` in bytes. This must be a multiple of the intrinsic block size of
+    /// the device.
+    ///
+    /// ## `Buffer`
+    ///
+    /// A pointer to the destination buffer for the data.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the read request was queued if the event is not null, or the data was
+    /// read correctly from the device if the event is null.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error while attempting to perform the read
+    /// operation.
+    ///
+    /// [`EFI_NO_MEDIA`] - there is no media in the device.
+    ///
+    /// [`EFI_MEDIA_CHANGED`] - the `MediaId` is not for the current media.
+    ///
+    /// [`EFI_BAD_BUFFER_SIZE`] - the `BufferSize` parameter is not a multiple of the intrinsic
+    /// block size of the device.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - the read request contains LBAs that are not valid, or the
+    /// buffer is not on proper alignment.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - the request could not be completed due to a lack of resources.
+    ///
+    /// [`ReadBlocksEx()`]: ./struct.EFI_BLOCK_IO2_PROTOCOL.html#method.ReadBlocksEx
+    /// [`Event`]: ./struct.EFI_BLOCK_IO2_TOKEN.html#structfield.Event
+    /// [`TransactionStatus`]: ./struct.EFI_BLOCK_IO2_TOKEN.html#structfield.TransactionStatus
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_MEDIA_CHANGED`]: crate::status::EFI_MEDIA_CHANGED
+    /// [`EFI_BAD_BUFFER_SIZE`]: crate::status::EFI_BAD_BUFFER_SIZE
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    pub unsafe fn ReadBlocksEx(
+        &mut self,
+        MediaId: UINT32,
+        LBA: EFI_LBA,
+        Token: *mut EFI_BLOCK_IO2_TOKEN,
+        BufferSize: UINTN,
+        Buffer: *mut VOID,
+    ) -> EFI_STATUS {
+        (self.ReadBlocksEx)(self, MediaId, LBA, Token, BufferSize, Buffer)
+    }
+
+    /// Writes a specified number of blocks to the device.
+    ///
+    /// The [`WriteBlocksEx()`] function writes the requested number of blocks to the device. All
+    /// blocks are written, or an error is returned.
+    ///
+    /// If the [`Event`] of the `Token` is null, the write is performed synchronously. Otherwise
+    /// the request is queued, the function returns immediately, and the event is signaled once
+    /// the write has completed; the result of the write is then stored in
+    /// [`TransactionStatus`]. The `Buffer` must remain valid until the event is signaled.
+    ///
+    /// # Parameters
+    ///
+    /// ## `MediaId`
+    ///
+    /// The media ID that the write request is for.
+    ///
+    /// ## `LBA`
+    ///
+    /// The starting logical block address to be written.
+    ///
+    /// ## `Token`
+    ///
+    /// A pointer to the token associated with the transaction.
+    ///
+    /// ## `BufferSize`
+    ///
+    /// The size in bytes of `Buffer`. This must be a multiple of the intrinsic block size of the
+    /// device.
+    ///
+    /// ## `Buffer`
+    ///
+    /// A pointer to the source buffer for the data.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the write request was queued if the event is not null, or the data was
+    /// written correctly to the device if the event is null.
+    ///
+    /// [`EFI_WRITE_PROTECTED`] - the device cannot be written to.
+    ///
+    /// [`EFI_NO_MEDIA`] - there is no media in the device.
+    ///
+    /// [`EFI_MEDIA_CHANGED`] - the `MediaId` is not for the current media.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error while attempting to perform the write
+    /// operation.
+    ///
+    /// [`EFI_BAD_BUFFER_SIZE`] - the `BufferSize` parameter is not a multiple of the intrinsic
+    /// block size of the device.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - the write request contains LBAs that are not valid, or the
+    /// buffer is not on proper alignment.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - the request could not be completed due to a lack of resources.
+    ///
+    /// [`WriteBlocksEx()`]: ./struct.EFI_BLOCK_IO2_PROTOCOL.html#method.WriteBlocksEx
+    /// [`Event`]: ./struct.EFI_BLOCK_IO2_TOKEN.html#structfield.Event
+    /// [`TransactionStatus`]: ./struct.EFI_BLOCK_IO2_TOKEN.html#structfield.TransactionStatus
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_WRITE_PROTECTED`]: crate::status::EFI_WRITE_PROTECTED
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_MEDIA_CHANGED`]: crate::status::EFI_MEDIA_CHANGED
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_BAD_BUFFER_SIZE`]: crate::status::EFI_BAD_BUFFER_SIZE
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    pub unsafe fn WriteBlocksEx(
+        &mut self,
+        MediaId: UINT32,
+        LBA: EFI_LBA,
+        Token: *mut EFI_BLOCK_IO2_TOKEN,
+        BufferSize: UINTN,
+        Buffer: *const VOID,
+    ) -> EFI_STATUS {
+        (self.WriteBlocksEx)(self, MediaId, LBA, Token, BufferSize, Buffer)
+    }
+
+    /// Flushes all modified data to a physical block device.
+    ///
+    /// The [`FlushBlocksEx()`] function flushes all modified data to the physical block device.
+    /// If the [`Event`] of the `Token` is null, the flush is performed synchronously; otherwise
+    /// the event is signaled once all data has been written.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Token`
+    ///
+    /// A pointer to the token associated with the transaction.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the flush request was queued if the event is not null, or all
+    /// outstanding data was written correctly to the device if the event is null.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error while writing back the data.
+    ///
+    /// [`EFI_WRITE_PROTECTED`] - the device cannot be written to.
+    ///
+    /// [`EFI_NO_MEDIA`] - there is no media in the device.
+    ///
+    /// [`EFI_MEDIA_CHANGED`] - the media in the device has changed since the last access.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - the request could not be completed due to a lack of resources.
+    ///
+    /// [`FlushBlocksEx()`]: ./struct.EFI_BLOCK_IO2_PROTOCOL.html#method.FlushBlocksEx
+    /// [`Event`]: ./struct.EFI_BLOCK_IO2_TOKEN.html#structfield.Event
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_WRITE_PROTECTED`]: crate::status::EFI_WRITE_PROTECTED
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_MEDIA_CHANGED`]: crate::status::EFI_MEDIA_CHANGED
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    pub unsafe fn FlushBlocksEx(&mut self, Token: *mut EFI_BLOCK_IO2_TOKEN) -> EFI_STATUS {
+        (self.FlushBlocksEx)(self, Token)
+    }
+
+    /// Returns the media of the device.
+    pub unsafe fn media(&self) -> &EFI_BLOCK_IO_MEDIA {
+        &*self.Media
+    }
+
+    /// Starts reading whole blocks starting at `LBA` into `Buffer`, after checking the request
+    /// with [`EFI_BLOCK_IO_MEDIA::check_request()`].
+    ///
+    /// If the [`Event`] of the `Token` is not null, the transfer completes asynchronously and
+    /// both `Token` and `Buffer` must remain valid and untouched until the event is signaled.
+    ///
+    /// [`EFI_BLOCK_IO_MEDIA::check_request()`]: crate::protocols::media::block_io::EFI_BLOCK_IO_MEDIA::check_request
+    /// [`Event`]: ./struct.EFI_BLOCK_IO2_TOKEN.html#structfield.Event
+    pub unsafe fn read_blocks_ex(
+        &mut self,
+        MediaId: UINT32,
+        LBA: EFI_LBA,
+        Token: &mut EFI_BLOCK_IO2_TOKEN,
+        Buffer: &mut [UINT8],
+    ) -> Result<(), EFI_STATUS> {
+        self.media()
+            .check_request(MediaId, LBA, Buffer.as_ptr(), Buffer.len())?;
+        match self.ReadBlocksEx(
+            MediaId,
+            LBA,
+            Token,
+            Buffer.len(),
+            Buffer.as_mut_ptr().cast(),
+        ) {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Starts writing whole blocks starting at `LBA` from `Buffer`, after checking the request
+    /// with [`EFI_BLOCK_IO_MEDIA::check_request()`].
+    ///
+    /// Returns [`EFI_WRITE_PROTECTED`] without calling the firmware if the media is read-only.
+    /// If the [`Event`] of the `Token` is not null, the transfer completes asynchronously and
+    /// both `Token` and `Buffer` must remain valid until the event is signaled.
+    ///
+    /// [`EFI_BLOCK_IO_MEDIA::check_request()`]: crate::protocols::media::block_io::EFI_BLOCK_IO_MEDIA::check_request
+    /// [`EFI_WRITE_PROTECTED`]: crate::status::EFI_WRITE_PROTECTED
+    /// [`Event`]: ./struct.EFI_BLOCK_IO2_TOKEN.html#structfield.Event
+    pub unsafe fn write_blocks_ex(
+        &mut self,
+        MediaId: UINT32,
+        LBA: EFI_LBA,
+        Token: &mut EFI_BLOCK_IO2_TOKEN,
+        Buffer: &[UINT8],
+    ) -> Result<(), EFI_STATUS> {
+        let media = self.media();
+        media.check_request(MediaId, LBA, Buffer.as_ptr(), Buffer.len())?;
+        if media.ReadOnly != 0 {
+            return Err(EFI_WRITE_PROTECTED);
+        }
+
+        match self.WriteBlocksEx(MediaId, LBA, Token, Buffer.len(), Buffer.as_ptr().cast()) {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Reads whole blocks starting at `LBA` into `Buffer` synchronously, using a token without
+    /// an event.
+    pub unsafe fn read_blocks(
+        &mut self,
+        MediaId: UINT32,
+        LBA: EFI_LBA,
+        Buffer: &mut [UINT8],
+    ) -> Result<(), EFI_STATUS> {
+        let mut token = EFI_BLOCK_IO2_TOKEN::blocking();
+        self.read_blocks_ex(MediaId, LBA, &mut token, Buffer)?;
+        token.result()
+    }
+
+    /// Writes whole blocks starting at `LBA` from `Buffer` synchronously, using a token without
+    /// an event.
+    pub unsafe fn write_blocks(
+        &mut self,
+        MediaId: UINT32,
+        LBA: EFI_LBA,
+        Buffer: &[UINT8],
+    ) -> Result<(), EFI_STATUS> {
+        let mut token = EFI_BLOCK_IO2_TOKEN::blocking();
+        self.write_blocks_ex(MediaId, LBA, &mut token, Buffer)?;
+        token.result()
+    }
+
+    /// Flushes all modified data to the device synchronously, using a token without an event.
+    pub unsafe fn flush_blocks(&mut self) -> Result<(), EFI_STATUS> {
+        let mut token = EFI_BLOCK_IO2_TOKEN::blocking();
+        match self.FlushBlocksEx(&mut token) {
+            EFI_SUCCESS => token.result(),
+            status => Err(status),
+        }
+    }
+}
+
+/// The token associated with a transfer of the [`EFI_BLOCK_IO2_PROTOCOL`].
+///
+/// [`EFI_BLOCK_IO2_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO2_PROTOCOL
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_BLOCK_IO2_TOKEN {
+    /// If the event is null, the transfer is performed synchronously. Otherwise the event is
+    /// signaled once the transfer has completed, and the transfer is performed asynchronously.
+    pub Event: EFI_EVENT,
+    /// The status of the transfer, set once it has completed.
+    pub TransactionStatus: EFI_STATUS,
+}
+
+impl EFI_BLOCK_IO2_TOKEN {
+    /// Creates a token for an asynchronous transfer that signals `Event` on completion.
+    pub fn new(Event: EFI_EVENT) -> Self {
+        Self {
+            Event,
+            TransactionStatus: EFI_SUCCESS,
+        }
+    }
+
+    /// Creates a token without an event, for a synchronous transfer.
+    pub fn blocking() -> Self {
+        Self::new(ptr::null_mut())
+    }
+
+    /// Returns the [`TransactionStatus`] of a completed transfer as a [`Result`].
+    ///
+    /// [`TransactionStatus`]: ./struct.EFI_BLOCK_IO2_TOKEN.html#structfield.TransactionStatus
+    pub fn result(&self) -> Result<(), EFI_STATUS> {
+        match self.TransactionStatus {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+}
+
+type EFI_BLOCK_RESET_EX = extern "efiapi" fn(
+    This: *mut EFI_BLOCK_IO2_PROTOCOL,
+    ExtendedVerification: BOOLEAN,
+) -> EFI_STATUS;
+
+type EFI_BLOCK_READ_EX = extern "efiapi" fn(
+    This: *mut EFI_BLOCK_IO2_PROTOCOL,
+    MediaId: UINT32,
+    LBA: EFI_LBA,
+    Token: *mut EFI_BLOCK_IO2_TOKEN,
+    BufferSize: UINTN,
+    Buffer: *mut VOID,
+) -> EFI_STATUS;
+
+type EFI_BLOCK_WRITE_EX = extern "efiapi" fn(
+    This: *mut EFI_BLOCK_IO2_PROTOCOL,
+    MediaId: UINT32,
+    LBA: EFI_LBA,
+    Token: *mut EFI_BLOCK_IO2_TOKEN,
+    BufferSize: UINTN,
+    Buffer: *const VOID,
+) -> EFI_STATUS;
+
+type EFI_BLOCK_FLUSH_EX = extern "efiapi" fn(
+    This: *mut EFI_BLOCK_IO2_PROTOCOL,
+    Token: *mut EFI_BLOCK_IO2_TOKEN,
+) -> EFI_STATUS;
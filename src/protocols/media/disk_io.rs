@@ -0,0 +1,327 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Disk I/O Protocol
+//!
+//! This module defines the Disk I/O Protocol, also known as the [`EFI_DISK_IO_PROTOCOL`], which
+//! gives byte-granular access to a block device, and the [`DiskStream`] adapter that implements
+//! the [`Read`] and [`Seek`] traits over it. See [Section 13.7 of the UEFI Specification] for more
+//! details.
+//!
+//! The Disk I/O protocol is layered on top of the [`EFI_BLOCK_IO_PROTOCOL`] installed on the same
+//! handle, whose [`EFI_BLOCK_IO_MEDIA`] provides the `MediaId` passed to every transfer.
+//!
+//! [`EFI_DISK_IO_PROTOCOL`]: crate::protocols::media::EFI_DISK_IO_PROTOCOL
+//! [`DiskStream`]: crate::protocols::media::disk_io::DiskStream
+//! [`Read`]: crate::io::Read
+//! [`Seek`]: crate::io::Seek
+//! [`EFI_BLOCK_IO_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO_PROTOCOL
+//! [`EFI_BLOCK_IO_MEDIA`]: crate::protocols::media::block_io::EFI_BLOCK_IO_MEDIA
+//!
+//! [Section 13.7 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/13_Protocols_Media_Access.html#disk-i-o-protocol
+
+use crate::io::{offset_position, Read, Seek, SeekFrom};
+use crate::protocols::media::block_io::EFI_BLOCK_IO_MEDIA;
+use crate::protocols::Protocol;
+use crate::status::{EFI_INVALID_PARAMETER, EFI_SUCCESS};
+use crate::types::{EFI_GUID, EFI_STATUS, UINT32, UINT64, UINT8, UINTN, VOID};
+
+/// GUID for the [`EFI_DISK_IO_PROTOCOL`].
+///
+/// [`EFI_DISK_IO_PROTOCOL`]: crate::protocols::media::EFI_DISK_IO_PROTOCOL
+pub const EFI_DISK_IO_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0xCE345171,
+    0xBA0B,
+    0x11D2,
+    [0x8E, 0x4F, 0x00, 0xA0, 0xC9, 0x69, 0x72, 0x3B],
+);
+
+/// Revision of the [`EFI_DISK_IO_PROTOCOL`] defined by this module.
+///
+/// [`EFI_DISK_IO_PROTOCOL`]: crate::protocols::media::EFI_DISK_IO_PROTOCOL
+pub const EFI_DISK_IO_PROTOCOL_REVISION: UINT64 = 0x00010000;
+
+/// Provides services to read and write bytes at arbitrary offsets of a block device.
+///
+/// The Disk I/O protocol is used to abstract the block accesses of the Block I/O protocol to a
+/// more general offset-length protocol. The firmware is responsible for adding this protocol to
+/// any Block I/O interface that appears in the system that does not already have a Disk I/O
+/// protocol. File systems and other disk access code utilize the Disk I/O protocol.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_DISK_IO_PROTOCOL {
+    /// The revision to which the disk I/O interface adheres. All future revisions must be
+    /// backwards compatible.
+    pub Revision: UINT64,
+    ReadDisk: EFI_DISK_READ,
+    WriteDisk: EFI_DISK_WRITE,
+}
+
+unsafe impl Protocol for EFI_DISK_IO_PROTOCOL {
+    const GUID: EFI_GUID = EFI_DISK_IO_PROTOCOL_GUID;
+}
+
+impl EFI_DISK_IO_PROTOCOL {
+    /// Reads a specified number of bytes from a device.
+    ///
+    /// The [`ReadDisk()`] function reads the number of bytes specified by `BufferSize` from the
+    /// device. All the bytes are read, or an error is returned. If there is no medium in the
+    /// device, the function returns [`EFI_NO_MEDIA`]. If the `MediaId` is not the ID of the
+    /// medium currently in the device, the function returns [`EFI_MEDIA_CHANGED`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `MediaId`
+    ///
+    /// ID of the medium to be read.
+    ///
+    /// ## `Offset`
+    ///
+    /// The starting byte offset on the logical block I/O device to read from.
+    ///
+    /// ## `BufferSize`
+    ///
+    /// The size in bytes of `Buffer`. The number of bytes to read from the device.
+    ///
+    /// ## `Buffer`
+    ///
+    /// A pointer to the destination buffer for the data. The caller is responsible for either
+    /// having implicit or explicit ownership of the buffer.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the data was read correctly from the device.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error while performing the read.
+    ///
+    /// [`EFI_NO_MEDIA`] - there is no medium in the device.
+    ///
+    /// [`EFI_MEDIA_CHANGED`] - the `MediaId` is not for the current medium.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - the read request contains device addresses that are not valid
+    /// for the device.
+    ///
+    /// [`ReadDisk()`]: ./struct.EFI_DISK_IO_PROTOCOL.html#method.ReadDisk
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_MEDIA_CHANGED`]: crate::status::EFI_MEDIA_CHANGED
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn ReadDisk(
+        &mut self,
+        MediaId: UINT32,
+        Offset: UINT64,
+        BufferSize: UINTN,
+        Buffer: *mut VOID,
+    ) -> EFI_STATUS {
+        (self.ReadDisk)(self, MediaId, Offset, BufferSize, Buffer)
+    }
+
+    /// Writes a specified number of bytes to a device.
+    ///
+    /// The [`WriteDisk()`] function writes the number of bytes specified by `BufferSize` to the
+    /// device. All bytes are written, or an error is returned. If there is no medium in the
+    /// device, the function returns [`EFI_NO_MEDIA`]. If the `MediaId` is not the ID of the
+    /// medium currently in the device, the function returns [`EFI_MEDIA_CHANGED`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `MediaId`
+    ///
+    /// ID of the medium to be written.
+    ///
+    /// ## `Offset`
+    ///
+    /// The starting byte offset on the logical block I/O device to write.
+    ///
+    /// ## `BufferSize`
+    ///
+    /// The size in bytes of `Buffer`. The number of bytes to write to the device.
+    ///
+    /// ## `Buffer`
+    ///
+    /// A pointer to the buffer containing the data to be written.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the data was written correctly to the device.
+    ///
+    /// [`EFI_WRITE_PROTECTED`] - the device cannot be written to.
+    ///
+    /// [`EFI_NO_MEDIA`] - there is no medium in the device.
+    ///
+    /// [`EFI_MEDIA_CHANGED`] - the `MediaId` is not for the current medium.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error while performing the write.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - the write request contains device addresses that are not
+    /// valid for the device.
+    ///
+    /// [`WriteDisk()`]: ./struct.EFI_DISK_IO_PROTOCOL.html#method.WriteDisk
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_WRITE_PROTECTED`]: crate::status::EFI_WRITE_PROTECTED
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_MEDIA_CHANGED`]: crate::status::EFI_MEDIA_CHANGED
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn WriteDisk(
+        &mut self,
+        MediaId: UINT32,
+        Offset: UINT64,
+        BufferSize: UINTN,
+        Buffer: *const VOID,
+    ) -> EFI_STATUS {
+        (self.WriteDisk)(self, MediaId, Offset, BufferSize, Buffer)
+    }
+
+    /// Reads `Buffer.len()` bytes at `Offset` of the medium identified by `MediaId`.
+    pub unsafe fn read_disk(
+        &mut self,
+        MediaId: UINT32,
+        Offset: UINT64,
+        Buffer: &mut [UINT8],
+    ) -> Result<(), EFI_STATUS> {
+        match self.ReadDisk(MediaId, Offset, Buffer.len(), Buffer.as_mut_ptr().cast()) {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Writes `Buffer` at `Offset` of the medium identified by `MediaId`.
+    pub unsafe fn write_disk(
+        &mut self,
+        MediaId: UINT32,
+        Offset: UINT64,
+        Buffer: &[UINT8],
+    ) -> Result<(), EFI_STATUS> {
+        match self.WriteDisk(MediaId, Offset, Buffer.len(), Buffer.as_ptr().cast()) {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+}
+
+/// A seekable byte stream over one medium of an [`EFI_DISK_IO_PROTOCOL`].
+///
+/// The stream is bound to the `MediaId` it was created with, so reads fail with
+/// [`EFI_MEDIA_CHANGED`] once the medium is replaced. Reads stop at the end of the medium and
+/// return `0` there, so parsers can stream data without handling blocks and LBAs.
+///
+/// [`EFI_DISK_IO_PROTOCOL`]: crate::protocols::media::EFI_DISK_IO_PROTOCOL
+/// [`EFI_MEDIA_CHANGED`]: crate::status::EFI_MEDIA_CHANGED
+pub struct DiskStream<'a> {
+    disk_io: &'a mut EFI_DISK_IO_PROTOCOL,
+    media_id: UINT32,
+    size: UINT64,
+    position: UINT64,
+}
+
+impl<'a> DiskStream<'a> {
+    /// Creates a stream over the medium described by `Media`, which is taken from the
+    /// [`EFI_BLOCK_IO_PROTOCOL`] installed on the same handle as `DiskIo`.
+    ///
+    /// [`EFI_BLOCK_IO_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO_PROTOCOL
+    pub unsafe fn new(DiskIo: &'a mut EFI_DISK_IO_PROTOCOL, Media: &EFI_BLOCK_IO_MEDIA) -> Self {
+        Self::with_size(DiskIo, Media.MediaId, Media.size())
+    }
+
+    /// Creates a stream over the first `Size` bytes of the medium identified by `MediaId`.
+    pub unsafe fn with_size(
+        DiskIo: &'a mut EFI_DISK_IO_PROTOCOL,
+        MediaId: UINT32,
+        Size: UINT64,
+    ) -> Self {
+        Self {
+            disk_io: DiskIo,
+            media_id: MediaId,
+            size: Size,
+            position: 0,
+        }
+    }
+
+    /// Returns the ID of the medium the stream reads from.
+    pub fn media_id(&self) -> UINT32 {
+        self.media_id
+    }
+
+    /// Returns the size of the stream in bytes.
+    pub fn size(&self) -> UINT64 {
+        self.size
+    }
+
+    /// Reads `Buffer.len()` bytes at `Offset` without moving the stream position.
+    ///
+    /// Returns [`EFI_INVALID_PARAMETER`] if the range extends past the end of the stream.
+    ///
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub fn read_at(&mut self, Offset: UINT64, Buffer: &mut [UINT8]) -> Result<(), EFI_STATUS> {
+        match Offset.checked_add(Buffer.len() as UINT64) {
+            Some(end) if end <= self.size => {}
+            _ => return Err(EFI_INVALID_PARAMETER),
+        }
+
+        // SAFETY: the protocol was valid when the stream was created.
+        unsafe { self.disk_io.read_disk(self.media_id, Offset, Buffer) }
+    }
+}
+
+impl Read for DiskStream<'_> {
+    fn read(&mut self, Buffer: &mut [UINT8]) -> Result<usize, EFI_STATUS> {
+        let remaining = self.size.saturating_sub(self.position);
+        let len = Buffer
+            .len()
+            .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        if len == 0 {
+            return Ok(0);
+        }
+
+        self.read_at(self.position, &mut Buffer[..len])?;
+        self.position += len as UINT64;
+        Ok(len)
+    }
+}
+
+impl Seek for DiskStream<'_> {
+    fn seek(&mut self, Position: SeekFrom) -> Result<u64, EFI_STATUS> {
+        self.position = match Position {
+            SeekFrom::Start(offset) => offset,
+            SeekFrom::End(offset) => offset_position(self.size, offset)?,
+            SeekFrom::Current(offset) => offset_position(self.position, offset)?,
+        };
+        Ok(self.position)
+    }
+
+    fn stream_position(&mut self) -> Result<u64, EFI_STATUS> {
+        Ok(self.position)
+    }
+}
+
+type EFI_DISK_READ = extern "efiapi" fn(
+    This: *mut EFI_DISK_IO_PROTOCOL,
+    MediaId: UINT32,
+    Offset: UINT64,
+    BufferSize: UINTN,
+    Buffer: *mut VOID,
+) -> EFI_STATUS;
+
+type EFI_DISK_WRITE = extern "efiapi" fn(
+    This: *mut EFI_DISK_IO_PROTOCOL,
+    MediaId: UINT32,
+    Offset: UINT64,
+    BufferSize: UINTN,
+    Buffer: *const VOID,
+) -> EFI_STATUS;
@@ -0,0 +1,416 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Disk I/O 2 Protocol
+//!
+//! This module defines the Disk I/O 2 Protocol, also known as the [`EFI_DISK_IO2_PROTOCOL`],
+//! which extends the [`EFI_DISK_IO_PROTOCOL`] with non-blocking transfers. See
+//! [Section 13.8 of the UEFI Specification] for more details.
+//!
+//! [`EFI_DISK_IO2_PROTOCOL`]: crate::protocols::media::EFI_DISK_IO2_PROTOCOL
+//! [`EFI_DISK_IO_PROTOCOL`]: crate::protocols::media::EFI_DISK_IO_PROTOCOL
+//!
+//! [Section 13.8 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/13_Protocols_Media_Access.html#disk-i-o-2-protocol
+
+use core::ptr;
+
+use crate::protocols::Protocol;
+use crate::status::EFI_SUCCESS;
+use crate::types::{EFI_EVENT, EFI_GUID, EFI_STATUS, UINT32, UINT64, UINT8, UINTN, VOID};
+
+/// GUID for the [`EFI_DISK_IO2_PROTOCOL`].
+///
+/// [`EFI_DISK_IO2_PROTOCOL`]: crate::protocols::media::EFI_DISK_IO2_PROTOCOL
+pub const EFI_DISK_IO2_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0x151C8EAE,
+    0x7F2C,
+    0x472C,
+    [0x9E, 0x54, 0x98, 0x28, 0x19, 0x4F, 0x6A, 0x88],
+);
+
+/// Revision of the [`EFI_DISK_IO2_PROTOCOL`] defined by this module.
+///
+/// [`EFI_DISK_IO2_PROTOCOL`]: crate::protocols::media::EFI_DISK_IO2_PROTOCOL
+pub const EFI_DISK_IO2_PROTOCOL_REVISION: UINT64 = 0x00020000;
+
+/// Provides services to read and write bytes at arbitrary offsets of a block device, with
+/// support for non-blocking transfers.
+///
+/// Each transfer takes an [`EFI_DISK_IO2_TOKEN`]; if its [`Event`] is not null the transfer is
+/// queued and the event is signaled once it completes, otherwise the transfer is performed
+/// synchronously.
+///
+/// [`EFI_DISK_IO2_TOKEN`]: crate::protocols::media::disk_io2::EFI_DISK_IO2_TOKEN
+/// [`Event`]: ./struct.EFI_DISK_IO2_TOKEN.html#structfield.Event
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_DISK_IO2_PROTOCOL {
+    /// The revision to which the disk I/O interface adheres. All future revisions must be
+    /// backwards compatible.
+    pub Revision: UINT64,
+    Cancel: EFI_DISK_CANCEL_EX,
+    ReadDiskEx: EFI_DISK_READ_EX,
+    WriteDiskEx: EFI_DISK_WRITE_EX,
+    FlushDiskEx: EFI_DISK_FLUSH_EX,
+}
+
+unsafe impl Protocol for EFI_DISK_IO2_PROTOCOL {
+    const GUID: EFI_GUID = EFI_DISK_IO2_PROTOCOL_GUID;
+}
+
+impl EFI_DISK_IO2_PROTOCOL {
+    /// Terminates outstanding asynchronous requests to a device.
+    ///
+    /// The [`Cancel()`] function terminates all outstanding non-blocking requests. The
+    /// [`TransactionStatus`] of each aborted request is set to [`EFI_ABORTED`] and its event is
+    /// signaled.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - all outstanding requests were successfully terminated.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error while performing the cancel operation.
+    ///
+    /// [`Cancel()`]: ./struct.EFI_DISK_IO2_PROTOCOL.html#method.Cancel
+    /// [`TransactionStatus`]: ./struct.EFI_DISK_IO2_TOKEN.html#structfield.TransactionStatus
+    /// [`EFI_ABORTED`]: crate::status::EFI_ABORTED
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    pub unsafe fn Cancel(&mut self) -> EFI_STATUS {
+        (self.Cancel)(self)
+    }
+
+    /// Reads a specified number of bytes from a device.
+    ///
+    /// The [`ReadDiskEx()`] function reads the number of bytes specified by `BufferSize` from the
+    /// device. All the bytes are read, or an error is returned.
+    ///
+    /// If the [`Event`] of the `Token` is null, the read is performed synchronously. Otherwise the
+    /// request is queued, the function returns immediately, and the event is signaled once the
+    /// read has completed; the result of the read is then stored in [`TransactionStatus`]. The
+    /// `Buffer` must remain valid until the event is signaled.
+    ///
+    /// # Parameters
+    ///
+    /// ## `MediaId`
+    ///
+    /// ID of the medium to be read.
+    ///
+    /// ## `Offset`
+    ///
+    /// The starting byte offset on the logical block I/O device to read from.
+    ///
+    /// ## `Token`
+    ///
+    /// A pointer to the token associated with the transaction.
+    ///
+    /// ## `BufferSize`
+    ///
+    /// The size in bytes of `Buffer`. The number of bytes to read from the device.
+    ///
+    /// ## `Buffer`
+    ///
+    /// A pointer to the destination buffer for the data.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the read request was queued if the event is not null, or the data was
+    /// read correctly from the device if the event is null.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error while performing the read operation.
+    ///
+    /// [`EFI_NO_MEDIA`] - there is no medium in the device.
+    ///
+    /// [`EFI_MEDIA_CHANGED`] - the `MediaId` is not for the current medium.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - the read request contains device addresses that are not valid
+    /// for the device.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - the request could not be completed due to a lack of resources.
+    ///
+    /// [`ReadDiskEx()`]: ./struct.EFI_DISK_IO2_PROTOCOL.html#method.ReadDiskEx
+    /// [`Event`]: ./struct.EFI_DISK_IO2_TOKEN.html#structfield.Event
+    /// [`TransactionStatus`]: ./struct.EFI_DISK_IO2_TOKEN.html#structfield.TransactionStatus
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_MEDIA_CHANGED`]: crate::status::EFI_MEDIA_CHANGED
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    pub unsafe fn ReadDiskEx(
+        &mut self,
+        MediaId: UINT32,
+        Offset: UINT64,
+        Token: *mut EFI_DISK_IO2_TOKEN,
+        BufferSize: UINTN,
+        Buffer: *mut VOID,
+    ) -> EFI_STATUS {
+        (self.ReadDiskEx)(self, MediaId, Offset, Token, BufferSize, Buffer)
+    }
+
+    /// Writes a specified number of bytes to a device.
+    ///
+    /// The [`WriteDiskEx()`] function writes the number of bytes specified by `BufferSize` to
+    /// the device. All bytes are written, or an error is returned.
+    ///
+    /// If the [`Event`] of the `Token` is null, the write is performed synchronously. Otherwise
+    /// the request is queued, the function returns immediately, and the event is signaled once
+    /// the write has completed; the result of the write is then stored in
+    /// [`TransactionStatus`]. The `Buffer` must remain valid until the event is signaled.
+    ///
+    /// # Parameters
+    ///
+    /// ## `MediaId`
+    ///
+    /// ID of the medium to be written.
+    ///
+    /// ## `Offset`
+    ///
+    /// The starting byte offset on the logical block I/O device to write to.
+    ///
+    /// ## `Token`
+    ///
+    /// A pointer to the token associated with the transaction.
+    ///
+    /// ## `BufferSize`
+    ///
+    /// The size in bytes of `Buffer`. The number of bytes to write to the device.
+    ///
+    /// ## `Buffer`
+    ///
+    /// A pointer to the buffer containing the data to be written.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the write request was queued if the event is not null, or the data was
+    /// written correctly to the device if the event is null.
+    ///
+    /// [`EFI_WRITE_PROTECTED`] - the device cannot be written to.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error while performing the write operation.
+    ///
+    /// [`EFI_NO_MEDIA`] - there is no medium in the device.
+    ///
+    /// [`EFI_MEDIA_CHANGED`] - the `MediaId` is not for the current medium.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - the write request contains device addresses that are not
+    /// valid for the device.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - the request could not be completed due to a lack of resources.
+    ///
+    /// [`WriteDiskEx()`]: ./struct.EFI_DISK_IO2_PROTOCOL.html#method.WriteDiskEx
+    /// [`Event`]: ./struct.EFI_DISK_IO2_TOKEN.html#structfield.Event
+    /// [`TransactionStatus`]: ./struct.EFI_DISK_IO2_TOKEN.html#structfield.TransactionStatus
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_WRITE_PROTECTED`]: crate::status::EFI_WRITE_PROTECTED
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_MEDIA_CHANGED`]: crate::status::EFI_MEDIA_CHANGED
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    pub unsafe fn WriteDiskEx(
+        &mut self,
+        MediaId: UINT32,
+        Offset: UINT64,
+        Token: *mut EFI_DISK_IO2_TOKEN,
+        BufferSize: UINTN,
+        Buffer: *const VOID,
+    ) -> EFI_STATUS {
+        (self.WriteDiskEx)(self, MediaId, Offset, Token, BufferSize, Buffer)
+    }
+
+    /// Flushes all modified data to the physical device.
+    ///
+    /// The [`FlushDiskEx()`] function flushes all modified data to the physical device. If the
+    /// [`Event`] of the `Token` is null, the flush is performed synchronously; otherwise the
+    /// event is signaled once all data has been written.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Token`
+    ///
+    /// A pointer to the token associated with the transaction.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the flush request was queued if the event is not null, or all
+    /// outstanding data was written correctly to the device if the event is null.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the device reported an error while performing the flush operation.
+    ///
+    /// [`EFI_WRITE_PROTECTED`] - the device cannot be written to.
+    ///
+    /// [`EFI_NO_MEDIA`] - there is no medium in the device.
+    ///
+    /// [`EFI_MEDIA_CHANGED`] - the medium in the device has changed since the last access.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - the request could not be completed due to a lack of resources.
+    ///
+    /// [`FlushDiskEx()`]: ./struct.EFI_DISK_IO2_PROTOCOL.html#method.FlushDiskEx
+    /// [`Event`]: ./struct.EFI_DISK_IO2_TOKEN.html#structfield.Event
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_WRITE_PROTECTED`]: crate::status::EFI_WRITE_PROTECTED
+    /// [`EFI_NO_MEDIA`]: crate::status::EFI_NO_MEDIA
+    /// [`EFI_MEDIA_CHANGED`]: crate::status::EFI_MEDIA_CHANGED
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    pub unsafe fn FlushDiskEx(&mut self, Token: *mut EFI_DISK_IO2_TOKEN) -> EFI_STATUS {
+        (self.FlushDiskEx)(self, Token)
+    }
+
+    /// Starts reading `Buffer.len()` bytes at `Offset` of the medium identified by `MediaId`.
+    ///
+    /// If the [`Event`] of the `Token` is not null, the transfer completes asynchronously and
+    /// both `Token` and `Buffer` must remain valid and untouched until the event is signaled.
+    ///
+    /// [`Event`]: ./struct.EFI_DISK_IO2_TOKEN.html#structfield.Event
+    pub unsafe fn read_disk_ex(
+        &mut self,
+        MediaId: UINT32,
+        Offset: UINT64,
+        Token: &mut EFI_DISK_IO2_TOKEN,
+        Buffer: &mut [UINT8],
+    ) -> Result<(), EFI_STATUS> {
+        match self.ReadDiskEx(
+            MediaId,
+            Offset,
+            Token,
+            Buffer.len(),
+            Buffer.as_mut_ptr().cast(),
+        ) {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Starts writing `Buffer` at `Offset` of the medium identified by `MediaId`.
+    ///
+    /// If the [`Event`] of the `Token` is not null, the transfer completes asynchronously and
+    /// both `Token` and `Buffer` must remain valid until the event is signaled.
+    ///
+    /// [`Event`]: ./struct.EFI_DISK_IO2_TOKEN.html#structfield.Event
+    pub unsafe fn write_disk_ex(
+        &mut self,
+        MediaId: UINT32,
+        Offset: UINT64,
+        Token: &mut EFI_DISK_IO2_TOKEN,
+        Buffer: &[UINT8],
+    ) -> Result<(), EFI_STATUS> {
+        match self.WriteDiskEx(MediaId, Offset, Token, Buffer.len(), Buffer.as_ptr().cast()) {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Reads `Buffer.len()` bytes at `Offset` of the medium identified by `MediaId`
+    /// synchronously, using a token without an event.
+    pub unsafe fn read_disk(
+        &mut self,
+        MediaId: UINT32,
+        Offset: UINT64,
+        Buffer: &mut [UINT8],
+    ) -> Result<(), EFI_STATUS> {
+        let mut token = EFI_DISK_IO2_TOKEN::blocking();
+        self.read_disk_ex(MediaId, Offset, &mut token, Buffer)?;
+        token.result()
+    }
+
+    /// Writes `Buffer` at `Offset` of the medium identified by `MediaId` synchronously, using a
+    /// token without an event.
+    pub unsafe fn write_disk(
+        &mut self,
+        MediaId: UINT32,
+        Offset: UINT64,
+        Buffer: &[UINT8],
+    ) -> Result<(), EFI_STATUS> {
+        let mut token = EFI_DISK_IO2_TOKEN::blocking();
+        self.write_disk_ex(MediaId, Offset, &mut token, Buffer)?;
+        token.result()
+    }
+
+    /// Flushes all modified data to the device synchronously, using a token without an event.
+    pub unsafe fn flush_disk(&mut self) -> Result<(), EFI_STATUS> {
+        let mut token = EFI_DISK_IO2_TOKEN::blocking();
+        match self.FlushDiskEx(&mut token) {
+            EFI_SUCCESS => token.result(),
+            status => Err(status),
+        }
+    }
+}
+
+/// The token associated with a transfer of the [`EFI_DISK_IO2_PROTOCOL`].
+///
+/// [`EFI_DISK_IO2_PROTOCOL`]: crate::protocols::media::EFI_DISK_IO2_PROTOCOL
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_DISK_IO2_TOKEN {
+    /// If the event is null, the transfer is performed synchronously. Otherwise the event is
+    /// signaled once the transfer has completed, and the transfer is performed asynchronously.
+    pub Event: EFI_EVENT,
+    /// The status of the transfer, set once it has completed.
+    pub TransactionStatus: EFI_STATUS,
+}
+
+impl EFI_DISK_IO2_TOKEN {
+    /// Creates a token for an asynchronous transfer that signals `Event` on completion.
+    pub fn new(Event: EFI_EVENT) -> Self {
+        Self {
+            Event,
+            TransactionStatus: EFI_SUCCESS,
+        }
+    }
+
+    /// Creates a token without an event, for a synchronous transfer.
+    pub fn blocking() -> Self {
+        Self::new(ptr::null_mut())
+    }
+
+    /// Returns the [`TransactionStatus`] of a completed transfer as a [`Result`].
+    ///
+    /// [`TransactionStatus`]: ./struct.EFI_DISK_IO2_TOKEN.html#structfield.TransactionStatus
+    pub fn result(&self) -> Result<(), EFI_STATUS> {
+        match self.TransactionStatus {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+}
+
+type EFI_DISK_CANCEL_EX = extern "efiapi" fn(This: *mut EFI_DISK_IO2_PROTOCOL) -> EFI_STATUS;
+
+type EFI_DISK_READ_EX = extern "efiapi" fn(
+    This: *mut EFI_DISK_IO2_PROTOCOL,
+    MediaId: UINT32,
+    Offset: UINT64,
+    Token: *mut EFI_DISK_IO2_TOKEN,
+    BufferSize: UINTN,
+    Buffer: *mut VOID,
+) -> EFI_STATUS;
+
+type EFI_DISK_WRITE_EX = extern "efiapi" fn(
+    This: *mut EFI_DISK_IO2_PROTOCOL,
+    MediaId: UINT32,
+    Offset: UINT64,
+    Token: *mut EFI_DISK_IO2_TOKEN,
+    BufferSize: UINTN,
+    Buffer: *const VOID,
+) -> EFI_STATUS;
+
+type EFI_DISK_FLUSH_EX = extern "efiapi" fn(
+    This: *mut EFI_DISK_IO2_PROTOCOL,
+    Token: *mut EFI_DISK_IO2_TOKEN,
+) -> EFI_STATUS;
@@ -0,0 +1,47 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Media Access Protocols
+//!
+//! This module defines protocols that give block and byte level access to storage media. These
+//! are correspondingly defined in this module as follows:
+//!
+//! | Protocol       | Definition in Module            |
+//! | -------------- | ------------------------------- |
+//! | Block I/O      | [`EFI_BLOCK_IO_PROTOCOL`]       |
+//! | Block I/O 2    | [`EFI_BLOCK_IO2_PROTOCOL`]      |
+//! | Disk I/O       | [`EFI_DISK_IO_PROTOCOL`]        |
+//! | Disk I/O 2     | [`EFI_DISK_IO2_PROTOCOL`]       |
+//! | Partition Info | [`EFI_PARTITION_INFO_PROTOCOL`] |
+//!
+//! [`EFI_BLOCK_IO_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO_PROTOCOL
+//! [`EFI_BLOCK_IO2_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO2_PROTOCOL
+//! [`EFI_DISK_IO_PROTOCOL`]: crate::protocols::media::EFI_DISK_IO_PROTOCOL
+//! [`EFI_DISK_IO2_PROTOCOL`]: crate::protocols::media::EFI_DISK_IO2_PROTOCOL
+//! [`EFI_PARTITION_INFO_PROTOCOL`]: crate::protocols::media::EFI_PARTITION_INFO_PROTOCOL
+
+pub mod block_io;
+pub mod block_io2;
+pub mod disk_io;
+pub mod disk_io2;
+pub mod partition_info;
+
+pub use block_io::EFI_BLOCK_IO_PROTOCOL;
+pub use block_io2::EFI_BLOCK_IO2_PROTOCOL;
+pub use disk_io::EFI_DISK_IO_PROTOCOL;
+pub use disk_io2::EFI_DISK_IO2_PROTOCOL;
+pub use partition_info::EFI_PARTITION_INFO_PROTOCOL;
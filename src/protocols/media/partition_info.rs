@@ -0,0 +1,143 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+
+//! # UEFI Partition Info Protocol
+//!
+//! This module defines the Partition Info Protocol, also known as the
+//! [`EFI_PARTITION_INFO_PROTOCOL`], which is installed by the firmware on the handle of every
+//! partition it discovers, next to its [`EFI_BLOCK_IO_PROTOCOL`]. It holds a copy of the MBR
+//! partition record or GPT partition entry describing the partition. See
+//! [Section 13.18 of the UEFI Specification] for more details.
+//!
+//! [`EFI_PARTITION_INFO_PROTOCOL`]: crate::protocols::media::EFI_PARTITION_INFO_PROTOCOL
+//! [`EFI_BLOCK_IO_PROTOCOL`]: crate::protocols::media::EFI_BLOCK_IO_PROTOCOL
+//!
+//! [Section 13.18 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/13_Protocols_Media_Access.html#partition-information-protocol
+
+use crate::partition::gpt::EFI_PARTITION_ENTRY;
+use crate::partition::mbr::MBR_PARTITION_RECORD;
+use crate::protocols::Protocol;
+use crate::types::{EFI_GUID, UINT32, UINT8};
+
+/// GUID for the [`EFI_PARTITION_INFO_PROTOCOL`].
+///
+/// [`EFI_PARTITION_INFO_PROTOCOL`]: crate::protocols::media::EFI_PARTITION_INFO_PROTOCOL
+pub const EFI_PARTITION_INFO_PROTOCOL_GUID: EFI_GUID = EFI_GUID(
+    0x8CF2F62C,
+    0xBC9B,
+    0x4821,
+    [0x80, 0x8D, 0xEC, 0x9E, 0xC4, 0x21, 0xA1, 0xA0],
+);
+
+/// Revision of the [`EFI_PARTITION_INFO_PROTOCOL`] defined by this module.
+///
+/// [`EFI_PARTITION_INFO_PROTOCOL`]: crate::protocols::media::EFI_PARTITION_INFO_PROTOCOL
+pub const EFI_PARTITION_INFO_PROTOCOL_REVISION: UINT32 = 0x0001000;
+
+/// Partition type of a partition that is neither an MBR nor a GPT partition, such as an El
+/// Torito partition of a CD-ROM.
+pub const PARTITION_TYPE_OTHER: UINT32 = 0x00;
+/// Partition type of a partition described by an [`MBR_PARTITION_RECORD`].
+///
+/// [`MBR_PARTITION_RECORD`]: crate::partition::mbr::MBR_PARTITION_RECORD
+pub const PARTITION_TYPE_MBR: UINT32 = 0x01;
+/// Partition type of a partition described by an [`EFI_PARTITION_ENTRY`].
+///
+/// [`EFI_PARTITION_ENTRY`]: crate::partition::gpt::EFI_PARTITION_ENTRY
+pub const PARTITION_TYPE_GPT: UINT32 = 0x02;
+
+/// Provides cached partition information for MBR and GPT partitions.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_PARTITION_INFO_PROTOCOL {
+    /// Set to [`EFI_PARTITION_INFO_PROTOCOL_REVISION`].
+    ///
+    /// [`EFI_PARTITION_INFO_PROTOCOL_REVISION`]: crate::protocols::media::partition_info::EFI_PARTITION_INFO_PROTOCOL_REVISION
+    pub Revision: UINT32,
+    /// Partition type: [`PARTITION_TYPE_OTHER`], [`PARTITION_TYPE_MBR`] or
+    /// [`PARTITION_TYPE_GPT`].
+    ///
+    /// [`PARTITION_TYPE_OTHER`]: crate::protocols::media::partition_info::PARTITION_TYPE_OTHER
+    /// [`PARTITION_TYPE_MBR`]: crate::protocols::media::partition_info::PARTITION_TYPE_MBR
+    /// [`PARTITION_TYPE_GPT`]: crate::protocols::media::partition_info::PARTITION_TYPE_GPT
+    pub Type: UINT32,
+    /// If `1`, the partition is an EFI System Partition.
+    pub System: UINT8,
+    /// Reserved, must be zero.
+    pub Reserved: [UINT8; 7],
+    /// The partition record or entry, as selected by [`Type`].
+    ///
+    /// [`Type`]: ./struct.EFI_PARTITION_INFO_PROTOCOL.html#structfield.Type
+    pub Info: EFI_PARTITION_INFO,
+}
+
+unsafe impl Protocol for EFI_PARTITION_INFO_PROTOCOL {
+    const GUID: EFI_GUID = EFI_PARTITION_INFO_PROTOCOL_GUID;
+}
+
+impl EFI_PARTITION_INFO_PROTOCOL {
+    /// Returns `true` if the partition is an EFI System Partition.
+    pub fn is_system(&self) -> bool {
+        self.System == 1
+    }
+
+    /// Returns a copy of the partition record or entry selected by [`Type`].
+    ///
+    /// [`Type`]: ./struct.EFI_PARTITION_INFO_PROTOCOL.html#structfield.Type
+    pub fn info(&self) -> PartitionInfo {
+        // SAFETY: `Type` selects the active member of the union, and both members are valid for
+        // any bit pattern.
+        unsafe {
+            match self.Type {
+                PARTITION_TYPE_MBR => PartitionInfo::Mbr(self.Info.Mbr),
+                PARTITION_TYPE_GPT => PartitionInfo::Gpt(self.Info.Gpt),
+                _ => PartitionInfo::Other,
+            }
+        }
+    }
+}
+
+/// The partition record or entry of an [`EFI_PARTITION_INFO_PROTOCOL`].
+///
+/// [`EFI_PARTITION_INFO_PROTOCOL`]: crate::protocols::media::EFI_PARTITION_INFO_PROTOCOL
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub union EFI_PARTITION_INFO {
+    /// The MBR partition record, if the type is [`PARTITION_TYPE_MBR`].
+    ///
+    /// [`PARTITION_TYPE_MBR`]: crate::protocols::media::partition_info::PARTITION_TYPE_MBR
+    pub Mbr: MBR_PARTITION_RECORD,
+    /// The GPT partition entry, if the type is [`PARTITION_TYPE_GPT`].
+    ///
+    /// [`PARTITION_TYPE_GPT`]: crate::protocols::media::partition_info::PARTITION_TYPE_GPT
+    pub Gpt: EFI_PARTITION_ENTRY,
+}
+
+/// The partition record or entry of an [`EFI_PARTITION_INFO_PROTOCOL`], as returned by
+/// [`EFI_PARTITION_INFO_PROTOCOL::info()`].
+///
+/// [`EFI_PARTITION_INFO_PROTOCOL`]: crate::protocols::media::EFI_PARTITION_INFO_PROTOCOL
+/// [`EFI_PARTITION_INFO_PROTOCOL::info()`]: crate::protocols::media::EFI_PARTITION_INFO_PROTOCOL::info
+#[derive(Clone, Copy)]
+pub enum PartitionInfo {
+    /// A partition of a legacy MBR disk.
+    Mbr(MBR_PARTITION_RECORD),
+    /// A partition of a GPT disk.
+    Gpt(EFI_PARTITION_ENTRY),
+    /// Any other kind of partition.
+    Other,
+}
@@ -23,5 +23,33 @@
 //! Every module in this module categorizes UEFI protocols into its respective functionalities
 //! and usage. See the module-level documentation for each module for more information on that
 //! module and the protocols they define and include.
+//!
+//! Each protocol interface structure implements the [`Protocol`] trait, tying it to the GUID that
+//! identifies it in the handle database, so that it can be looked up with the typed helpers of
+//! the [`EFI_BOOT_SERVICES`], such as [`locate_protocol()`] and [`open_protocol()`].
+//!
+//! [`Protocol`]: crate::protocols::Protocol
+//! [`EFI_BOOT_SERVICES`]: crate::tables::boot_services::EFI_BOOT_SERVICES
+//! [`locate_protocol()`]: crate::tables::boot_services::EFI_BOOT_SERVICES::locate_protocol
+//! [`open_protocol()`]: crate::tables::boot_services::EFI_BOOT_SERVICES::open_protocol
+
+use crate::types::EFI_GUID;
 
 pub mod console;
+pub mod device_path;
+pub mod file_system;
+pub mod loaded_image;
+pub mod media;
+
+/// A protocol interface structure, identified in the handle database by [`GUID`].
+///
+/// # Safety
+///
+/// Every protocol interface installed with [`GUID`] must have the layout of the implementing
+/// type.
+///
+/// [`GUID`]: crate::protocols::Protocol::GUID
+pub unsafe trait Protocol {
+    /// The GUID of the protocol.
+    const GUID: EFI_GUID;
+}
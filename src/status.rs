@@ -32,6 +32,13 @@ use crate::types::EFI_STATUS;
 
 const ERROR_BIT: usize = 1 << (mem::size_of::<EFI_STATUS>() * 8 - 1);
 
+/// Returns `true` if the Status Code is an error code, that is, if its high bit is set.
+///
+/// Warning codes are not considered errors.
+pub const fn EFI_ERROR(Status: EFI_STATUS) -> bool {
+    Status & ERROR_BIT != 0
+}
+
 /// The operation completed successfully.
 pub const EFI_SUCCESS: EFI_STATUS = 0;
 
@@ -0,0 +1,2474 @@
+// RawUEFI: Idiomatic Raw Bindings to UEFI
+//
+// Copyright (C) 2023 HTGAzureX1212.
+//
+// RawUEFI is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// RawUEFI is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with RawUEFI.  If not, see <https://www.gnu.org/licenses/>.
+//! # UEFI Boot Services
+//!
+//! UEFI uses the EFI Boot Services Table, which contains a table header and pointers to all of the
+//! boot services. The definition for this table is shown in the following code fragments. Except
+//! for the table header, all elements in the EFI Boot Services Tables are prototypes of function
+//! pointers to functions as defined in Services — Boot Services. The function pointers in this
+//! table are not valid after the operating system has taken control of the platform with a call to
+//! [`ExitBootServices()`].
+//!
+//! ## Event, Timer, and Task Priority Services
+//!
+//! Execution in the boot services environment occurs at different task priority levels, or TPLs.
+//! The boot services environment exposes only three of these levels to UEFI applications and
+//! drivers: [`TPL_APPLICATION`], [`TPL_CALLBACK`] and [`TPL_NOTIFY`]. Events are created with
+//! [`CreateEvent()`] or [`CreateEventEx()`], and may carry a notification function which the
+//! firmware queues at the event's TPL whenever the event is signaled or checked. See
+//! [Section 7.1 of the UEFI Specification] for more details.
+//!
+//! The [`Event`] type owns an event, closes it when dropped, and allows a Rust closure to be used
+//! as its notification function through [`create_notify_event()`].
+//!
+//! Notification functions preempt code running at a lower TPL. State shared between the two is
+//! protected by raising the TPL with [`raise_tpl()`], which returns a [`TplGuard`] restoring the
+//! previous level when dropped, or by wrapping it in a [`TplMutex`].
+//!
+//! [`ExitBootServices()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-exitbootservices
+//! [`TPL_APPLICATION`]: crate::tables::boot_services::TPL_APPLICATION
+//! [`TPL_CALLBACK`]: crate::tables::boot_services::TPL_CALLBACK
+//! [`TPL_NOTIFY`]: crate::tables::boot_services::TPL_NOTIFY
+//! [`CreateEvent()`]: ./struct.EFI_BOOT_SERVICES.html#method.CreateEvent
+//! [`CreateEventEx()`]: ./struct.EFI_BOOT_SERVICES.html#method.CreateEventEx
+//! [`Event`]: crate::tables::boot_services::Event
+//! [`create_notify_event()`]: ./struct.EFI_BOOT_SERVICES.html#method.create_notify_event
+//! [`raise_tpl()`]: ./struct.EFI_BOOT_SERVICES.html#method.raise_tpl
+//! [`TplGuard`]: crate::tables::boot_services::TplGuard
+//! [`TplMutex`]: crate::tables::boot_services::TplMutex
+//!
+//! ## Protocol Handler Services
+//!
+//! The handle database is a list of handles, each carrying one or more protocol interfaces
+//! identified by a GUID. Protocol interfaces are found with [`LocateProtocol()`],
+//! [`LocateHandleBuffer()`] and [`LocateDevicePath()`], and obtained from a handle with
+//! [`OpenProtocol()`], which records the agent using them until [`CloseProtocol()`] is called.
+//! The typed helpers, such as [`open_protocol()`], take the protocol as a type implementing
+//! [`Protocol`], and [`open_scoped_protocol()`] returns a [`ScopedProtocol`] which closes the
+//! protocol when dropped. Buffers allocated by the firmware are returned as a [`PoolBuffer`], which frees
+//! them when dropped. See [Section 7.3 of the UEFI Specification] for more details.
+//!
+//! [`LocateProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.LocateProtocol
+//! [`LocateHandleBuffer()`]: ./struct.EFI_BOOT_SERVICES.html#method.LocateHandleBuffer
+//! [`LocateDevicePath()`]: ./struct.EFI_BOOT_SERVICES.html#method.LocateDevicePath
+//! [`OpenProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.OpenProtocol
+//! [`CloseProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.CloseProtocol
+//! [`open_protocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.open_protocol
+//! [`Protocol`]: crate::protocols::Protocol
+//! [`open_scoped_protocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.open_scoped_protocol
+//! [`ScopedProtocol`]: crate::tables::boot_services::ScopedProtocol
+//! [`PoolBuffer`]: crate::tables::boot_services::PoolBuffer
+//!
+//! [Section 7.1 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#event-timer-and-task-priority-services
+//! [Section 7.3 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#protocol-handler-services
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cell::{Cell, UnsafeCell};
+#[cfg(feature = "alloc")]
+use core::mem;
+use core::mem::ManuallyDrop;
+use core::ops::{Deref, DerefMut};
+use core::{ptr, slice};
+
+use crate::protocols::device_path::EFI_DEVICE_PATH_PROTOCOL;
+use crate::protocols::Protocol;
+#[cfg(feature = "alloc")]
+use crate::status::EFI_BUFFER_TOO_SMALL;
+use crate::status::{EFI_INVALID_PARAMETER, EFI_NOT_READY, EFI_SUCCESS, EFI_UNSUPPORTED};
+use crate::tables::system::EFI_SPECIFICATION_VERSION;
+use crate::tables::EFI_TABLE_HEADER;
+use crate::types::{
+    EFI_EVENT, EFI_GUID, EFI_HANDLE, EFI_STATUS, EFI_TPL, UINT32, UINT64, UINTN, VOID,
+};
+
+/// Signature for the EFI Boot Services Table.
+pub const EFI_BOOT_SERVICES_SIGNATURE: UINT64 = 0x56524553544f4f42;
+
+/// The EFI Boot Services Table revision.
+pub const EFI_BOOT_SERVICES_REVISION: UINT32 = EFI_SPECIFICATION_VERSION;
+
+/// Contains a table header and pointers to all of the boot services.
+///
+/// Services which are not wrapped by this crate yet are kept as opaque pointers so that the layout
+/// of the table matches the specification.
+#[derive(Clone, Copy)]
+#[repr(C)]
+#[allow(dead_code)]
+pub struct EFI_BOOT_SERVICES {
+    /// The table header for the EFI Boot Services Table. This header contains the
+    /// [`EFI_BOOT_SERVICES_SIGNATURE`] and [`EFI_BOOT_SERVICES_REVISION`] values along with the
+    /// size of the [`EFI_BOOT_SERVICES`] structure and a 32-bit CRC to verify that the contents of
+    /// the EFI Boot Services Table are valid.
+    ///
+    /// [`EFI_BOOT_SERVICES_SIGNATURE`]: crate::tables::boot_services::EFI_BOOT_SERVICES_SIGNATURE
+    /// [`EFI_BOOT_SERVICES_REVISION`]: crate::tables::boot_services::EFI_BOOT_SERVICES_REVISION
+    /// [`EFI_BOOT_SERVICES`]: crate::tables::boot_services::EFI_BOOT_SERVICES
+    pub Hdr: EFI_TABLE_HEADER,
+
+    RaiseTPL: EFI_RAISE_TPL,
+    RestoreTPL: EFI_RESTORE_TPL,
+
+    AllocatePages: *mut VOID,
+    FreePages: *mut VOID,
+    GetMemoryMap: *mut VOID,
+    AllocatePool: *mut VOID,
+    FreePool: EFI_FREE_POOL,
+
+    CreateEvent: EFI_CREATE_EVENT,
+    SetTimer: EFI_SET_TIMER,
+    WaitForEvent: EFI_WAIT_FOR_EVENT,
+    SignalEvent: EFI_SIGNAL_EVENT,
+    CloseEvent: EFI_CLOSE_EVENT,
+    CheckEvent: EFI_CHECK_EVENT,
+
+    InstallProtocolInterface: *mut VOID,
+    ReinstallProtocolInterface: *mut VOID,
+    UninstallProtocolInterface: *mut VOID,
+    HandleProtocol: EFI_HANDLE_PROTOCOL,
+    Reserved: *mut VOID,
+    RegisterProtocolNotify: *mut VOID,
+    LocateHandle: EFI_LOCATE_HANDLE,
+    LocateDevicePath: EFI_LOCATE_DEVICE_PATH,
+    InstallConfigurationTable: *mut VOID,
+
+    LoadImage: *mut VOID,
+    StartImage: *mut VOID,
+    Exit: *mut VOID,
+    UnloadImage: *mut VOID,
+    ExitBootServices: *mut VOID,
+
+    GetNextMonotonicCount: *mut VOID,
+    Stall: *mut VOID,
+    SetWatchdogTimer: *mut VOID,
+
+    ConnectController: *mut VOID,
+    DisconnectController: *mut VOID,
+
+    OpenProtocol: EFI_OPEN_PROTOCOL,
+    CloseProtocol: EFI_CLOSE_PROTOCOL,
+    OpenProtocolInformation: EFI_OPEN_PROTOCOL_INFORMATION,
+
+    ProtocolsPerHandle: EFI_PROTOCOLS_PER_HANDLE,
+    LocateHandleBuffer: EFI_LOCATE_HANDLE_BUFFER,
+    LocateProtocol: EFI_LOCATE_PROTOCOL,
+    InstallMultipleProtocolInterfaces: *mut VOID,
+    UninstallMultipleProtocolInterfaces: *mut VOID,
+
+    CalculateCrc32: *mut VOID,
+
+    CopyMem: *mut VOID,
+    SetMem: *mut VOID,
+    CreateEventEx: EFI_CREATE_EVENT_EX,
+}
+
+impl EFI_BOOT_SERVICES {
+    /// Raises a task’s priority level and returns its previous level.
+    ///
+    /// The [`RaiseTPL()`] function raises the priority of the currently executing task and returns
+    /// its previous priority level. Only three task priority levels are exposed outside of the
+    /// firmware during boot services execution: [`TPL_APPLICATION`], [`TPL_CALLBACK`] and
+    /// [`TPL_NOTIFY`]. [`TPL_HIGH_LEVEL`] is used by the firmware to disable interrupts.
+    ///
+    /// If [`NewTpl`] is below the current TPL level, then the system behavior is indeterminate.
+    /// Additionally, only [`TPL_APPLICATION`], [`TPL_CALLBACK`], [`TPL_NOTIFY`], and
+    /// [`TPL_HIGH_LEVEL`] may be used. All other values are reserved for use by the firmware.
+    ///
+    /// # Parameters
+    ///
+    /// ## `NewTpl`
+    ///
+    /// The new task priority level. It must be greater than or equal to the current task priority
+    /// level.
+    ///
+    /// [`RaiseTPL()`]: ./struct.EFI_BOOT_SERVICES.html#method.RaiseTPL
+    /// [`NewTpl`]: ./struct.EFI_BOOT_SERVICES.html#newtpl
+    /// [`TPL_APPLICATION`]: crate::tables::boot_services::TPL_APPLICATION
+    /// [`TPL_CALLBACK`]: crate::tables::boot_services::TPL_CALLBACK
+    /// [`TPL_NOTIFY`]: crate::tables::boot_services::TPL_NOTIFY
+    /// [`TPL_HIGH_LEVEL`]: crate::tables::boot_services::TPL_HIGH_LEVEL
+    pub unsafe fn RaiseTPL(&self, NewTpl: EFI_TPL) -> EFI_TPL {
+        (self.RaiseTPL)(NewTpl)
+    }
+
+    /// Restores a task’s priority level to its previous value.
+    ///
+    /// The [`RestoreTPL()`] function restores a task’s priority level to its previous value. Calls
+    /// to [`RestoreTPL()`] are matched with calls to [`RaiseTPL()`]. If [`OldTpl`] is above the
+    /// current TPL level, then the system behavior is indeterminate. Any event notification
+    /// functions queued at a level above [`OldTpl`] are dispatched before this function returns.
+    ///
+    /// # Parameters
+    ///
+    /// ## `OldTpl`
+    ///
+    /// The previous task priority level to restore, as returned from a previous call to
+    /// [`RaiseTPL()`].
+    ///
+    /// [`RestoreTPL()`]: ./struct.EFI_BOOT_SERVICES.html#method.RestoreTPL
+    /// [`RaiseTPL()`]: ./struct.EFI_BOOT_SERVICES.html#method.RaiseTPL
+    /// [`OldTpl`]: ./struct.EFI_BOOT_SERVICES.html#oldtpl
+    pub unsafe fn RestoreTPL(&self, OldTpl: EFI_TPL) {
+        (self.RestoreTPL)(OldTpl)
+    }
+
+    /// Returns pool memory to the system.
+    ///
+    /// The [`FreePool()`] function returns the memory specified by [`Buffer`] to the system. On
+    /// return, the memory’s type is [`EfiConventionalMemory`]. The [`Buffer`] that is freed must
+    /// have been allocated from pool, either by the caller or by a service returning a buffer
+    /// allocated from pool.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Buffer`
+    ///
+    /// Pointer to the buffer to free.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the memory was returned to the system.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - [`Buffer`] was invalid.
+    ///
+    /// [`FreePool()`]: ./struct.EFI_BOOT_SERVICES.html#method.FreePool
+    /// [`Buffer`]: ./struct.EFI_BOOT_SERVICES.html#buffer
+    /// [`EfiConventionalMemory`]: crate::tables::runtime_services::EfiConventionalMemory
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn FreePool(&self, Buffer: *mut VOID) -> EFI_STATUS {
+        (self.FreePool)(Buffer)
+    }
+
+    /// Creates an event.
+    ///
+    /// The [`CreateEvent()`] function creates a new event of type [`Type`] and returns it in the
+    /// location referenced by [`Event`]. The event’s notification function, context, and task
+    /// priority level are specified by [`NotifyFunction`], [`NotifyContext`], and [`NotifyTpl`],
+    /// respectively.
+    ///
+    /// Events exist in one of two states, “waiting” or “signaled”. When an event is created,
+    /// firmware puts it in the “waiting” state. When the event is signaled, firmware changes its
+    /// state to “signaled” and, if [`EVT_NOTIFY_SIGNAL`] is specified, places a call to its
+    /// notification function in a FIFO queue. There is a queue for each of the “basic” task
+    /// priority levels.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Type`
+    ///
+    /// The type of event to create and its mode and attributes, as a combination of the `EVT_*`
+    /// constants.
+    ///
+    /// ## `NotifyTpl`
+    ///
+    /// The task priority level of event notifications, if needed.
+    ///
+    /// ## `NotifyFunction`
+    ///
+    /// The notification function to call when the event is signaled, if needed.
+    ///
+    /// ## `NotifyContext`
+    ///
+    /// The context pointer to pass to the notification function.
+    ///
+    /// ## `Event`
+    ///
+    /// Pointer to the newly created event if the call succeeds; undefined otherwise.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the event structure was created.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - one of the parameters has an invalid value, for example
+    /// [`Event`] is null, [`Type`] has an unsupported bit set, [`NotifyFunction`] is null while a
+    /// notification type is requested, or [`NotifyTpl`] is not a valid TPL.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - the event could not be allocated.
+    ///
+    /// [`CreateEvent()`]: ./struct.EFI_BOOT_SERVICES.html#method.CreateEvent
+    /// [`Type`]: ./struct.EFI_BOOT_SERVICES.html#type
+    /// [`NotifyTpl`]: ./struct.EFI_BOOT_SERVICES.html#notifytpl
+    /// [`NotifyFunction`]: ./struct.EFI_BOOT_SERVICES.html#notifyfunction
+    /// [`NotifyContext`]: ./struct.EFI_BOOT_SERVICES.html#notifycontext
+    /// [`Event`]: ./struct.EFI_BOOT_SERVICES.html#event
+    /// [`EVT_NOTIFY_SIGNAL`]: crate::tables::boot_services::EVT_NOTIFY_SIGNAL
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    pub unsafe fn CreateEvent(
+        &self,
+        Type: EFI_EVENT_TYPE,
+        NotifyTpl: EFI_TPL,
+        NotifyFunction: Option<EFI_EVENT_NOTIFY>,
+        NotifyContext: *mut VOID,
+        Event: *mut EFI_EVENT,
+    ) -> EFI_STATUS {
+        (self.CreateEvent)(Type, NotifyTpl, NotifyFunction, NotifyContext, Event)
+    }
+
+    /// Sets the type of timer and the trigger time for a timer event.
+    ///
+    /// The [`SetTimer()`] function cancels any previous time trigger setting for the event, and
+    /// sets the new trigger time for the event. This function can only be used on events of type
+    /// [`EVT_TIMER`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `Event`
+    ///
+    /// The timer event that is to be signaled at the specified time.
+    ///
+    /// ## `Type`
+    ///
+    /// The type of time that is specified in [`TriggerTime`].
+    ///
+    /// ## `TriggerTime`
+    ///
+    /// The number of 100ns units until the timer expires. A value of `0` for
+    /// [`TimerRelative`] signals the event on the next timer tick, and for [`TimerPeriodic`]
+    /// signals the event on every timer tick.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the event has been set to be signaled at the requested time.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - [`Event`] or [`Type`] is not valid.
+    ///
+    /// [`SetTimer()`]: ./struct.EFI_BOOT_SERVICES.html#method.SetTimer
+    /// [`Event`]: ./struct.EFI_BOOT_SERVICES.html#event-1
+    /// [`Type`]: ./struct.EFI_BOOT_SERVICES.html#type-1
+    /// [`TriggerTime`]: ./struct.EFI_BOOT_SERVICES.html#triggertime
+    /// [`TimerRelative`]: crate::tables::boot_services::EFI_TIMER_DELAY::TimerRelative
+    /// [`TimerPeriodic`]: crate::tables::boot_services::EFI_TIMER_DELAY::TimerPeriodic
+    /// [`EVT_TIMER`]: crate::tables::boot_services::EVT_TIMER
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn SetTimer(
+        &self,
+        Event: EFI_EVENT,
+        Type: EFI_TIMER_DELAY,
+        TriggerTime: UINT64,
+    ) -> EFI_STATUS {
+        (self.SetTimer)(Event, Type, TriggerTime)
+    }
+
+    /// Stops execution until an event is signaled.
+    ///
+    /// The [`WaitForEvent()`] function waits for any event in the [`Event`] array to be signaled.
+    /// The events are checked in order, and the first signaled event is reset to the “waiting”
+    /// state and has its position returned in [`Index`]. This function must be called at priority
+    /// level [`TPL_APPLICATION`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `NumberOfEvents`
+    ///
+    /// The number of events in the [`Event`] array.
+    ///
+    /// ## `Event`
+    ///
+    /// An array of events to wait on.
+    ///
+    /// ## `Index`
+    ///
+    /// Pointer to the index of the event which satisfied the wait condition.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the event indicated by [`Index`] was signaled.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - [`NumberOfEvents`] is `0`, or the event indicated by [`Index`]
+    /// is of type [`EVT_NOTIFY_SIGNAL`].
+    ///
+    /// [`EFI_UNSUPPORTED`] - the current TPL is not [`TPL_APPLICATION`].
+    ///
+    /// [`WaitForEvent()`]: ./struct.EFI_BOOT_SERVICES.html#method.WaitForEvent
+    /// [`NumberOfEvents`]: ./struct.EFI_BOOT_SERVICES.html#numberofevents
+    /// [`Event`]: ./struct.EFI_BOOT_SERVICES.html#event-2
+    /// [`Index`]: ./struct.EFI_BOOT_SERVICES.html#index
+    /// [`TPL_APPLICATION`]: crate::tables::boot_services::TPL_APPLICATION
+    /// [`EVT_NOTIFY_SIGNAL`]: crate::tables::boot_services::EVT_NOTIFY_SIGNAL
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    pub unsafe fn WaitForEvent(
+        &self,
+        NumberOfEvents: UINTN,
+        Event: *mut EFI_EVENT,
+        Index: *mut UINTN,
+    ) -> EFI_STATUS {
+        (self.WaitForEvent)(NumberOfEvents, Event, Index)
+    }
+
+    /// Signals an event.
+    ///
+    /// The supplied [`Event`] is placed in the signaled state. If [`Event`] is already in the
+    /// signaled state, then [`EFI_SUCCESS`] is returned. If [`Event`] is of type
+    /// [`EVT_NOTIFY_SIGNAL`], then the event’s notification function is scheduled to be invoked at
+    /// the event’s notification task priority level. If the event is part of an event group, all
+    /// events in the group are signaled.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Event`
+    ///
+    /// The event to signal.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the event was signaled.
+    ///
+    /// [`Event`]: ./struct.EFI_BOOT_SERVICES.html#event-3
+    /// [`EVT_NOTIFY_SIGNAL`]: crate::tables::boot_services::EVT_NOTIFY_SIGNAL
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    pub unsafe fn SignalEvent(&self, Event: EFI_EVENT) -> EFI_STATUS {
+        (self.SignalEvent)(Event)
+    }
+
+    /// Closes an event.
+    ///
+    /// The [`CloseEvent()`] function removes the caller’s reference to the event, removes it from
+    /// any event group to which it belongs, and closes it. Once the event is closed, the event is
+    /// no longer valid and may not be used on any subsequent function calls. If [`Event`] was
+    /// registered with [`RegisterProtocolNotify()`] then the corresponding registration will be
+    /// removed.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Event`
+    ///
+    /// The event to close.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the event has been closed.
+    ///
+    /// [`CloseEvent()`]: ./struct.EFI_BOOT_SERVICES.html#method.CloseEvent
+    /// [`Event`]: ./struct.EFI_BOOT_SERVICES.html#event-4
+    /// [`RegisterProtocolNotify()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-registerprotocolnotify
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    pub unsafe fn CloseEvent(&self, Event: EFI_EVENT) -> EFI_STATUS {
+        (self.CloseEvent)(Event)
+    }
+
+    /// Checks whether an event is in the signaled state.
+    ///
+    /// The [`CheckEvent()`] function checks to see whether [`Event`] is in the signaled state. If
+    /// [`Event`] is of type [`EVT_NOTIFY_SIGNAL`], then [`EFI_INVALID_PARAMETER`] is returned.
+    /// Otherwise, if the event is signaled it is cleared and [`EFI_SUCCESS`] is returned. If the
+    /// event is not signaled and has a notification function, the notification function is queued
+    /// at the event’s notification task priority level, and the event is checked again after the
+    /// notification function has run.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Event`
+    ///
+    /// The event to check.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the event is in the signaled state.
+    ///
+    /// [`EFI_NOT_READY`] - the event is not in the signaled state.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - [`Event`] is of type [`EVT_NOTIFY_SIGNAL`].
+    ///
+    /// [`CheckEvent()`]: ./struct.EFI_BOOT_SERVICES.html#method.CheckEvent
+    /// [`Event`]: ./struct.EFI_BOOT_SERVICES.html#event-5
+    /// [`EVT_NOTIFY_SIGNAL`]: crate::tables::boot_services::EVT_NOTIFY_SIGNAL
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_NOT_READY`]: crate::status::EFI_NOT_READY
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn CheckEvent(&self, Event: EFI_EVENT) -> EFI_STATUS {
+        (self.CheckEvent)(Event)
+    }
+
+    /// Queries a handle to determine if it supports a specified protocol.
+    ///
+    /// The [`HandleProtocol()`] function queries [`Handle`] to determine if it supports
+    /// [`Protocol`]. If it does, then on return [`Interface`] points to a pointer to the
+    /// corresponding protocol interface. [`Interface`] can then be passed to any protocol service
+    /// to identify the context of the request.
+    ///
+    /// [`HandleProtocol()`] is a simplified version of [`OpenProtocol()`] using the image handle of
+    /// the firmware as the agent, which means the protocol is never closed. New code should use
+    /// [`OpenProtocol()`] instead.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Handle`
+    ///
+    /// The handle being queried.
+    ///
+    /// ## `Protocol`
+    ///
+    /// The published unique identifier of the protocol.
+    ///
+    /// ## `Interface`
+    ///
+    /// Supplies the address where a pointer to the corresponding protocol interface is returned.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the interface information for the specified protocol was returned.
+    ///
+    /// [`EFI_UNSUPPORTED`] - the device does not support the specified protocol.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - [`Handle`] is null, or [`Protocol`] or [`Interface`] is null.
+    ///
+    /// [`HandleProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.HandleProtocol
+    /// [`OpenProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.OpenProtocol
+    /// [`Handle`]: ./struct.EFI_BOOT_SERVICES.html#handle
+    /// [`Protocol`]: ./struct.EFI_BOOT_SERVICES.html#protocol
+    /// [`Interface`]: ./struct.EFI_BOOT_SERVICES.html#interface
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn HandleProtocol(
+        &self,
+        Handle: EFI_HANDLE,
+        Protocol: *const EFI_GUID,
+        Interface: *mut *mut VOID,
+    ) -> EFI_STATUS {
+        (self.HandleProtocol)(Handle, Protocol, Interface)
+    }
+
+    /// Returns an array of handles that support a specified protocol.
+    ///
+    /// The [`LocateHandle()`] function returns an array of handles that match the [`SearchType`]
+    /// request. If the input value of [`BufferSize`] is too small, the function returns
+    /// [`EFI_BUFFER_TOO_SMALL`] and updates [`BufferSize`] to the size of the buffer needed to
+    /// obtain the array.
+    ///
+    /// # Parameters
+    ///
+    /// ## `SearchType`
+    ///
+    /// Specifies which handle(s) are to be returned.
+    ///
+    /// ## `Protocol`
+    ///
+    /// Specifies the protocol to search by. This parameter is only valid if [`SearchType`] is
+    /// [`ByProtocol`].
+    ///
+    /// ## `SearchKey`
+    ///
+    /// Specifies the search key. This parameter is ignored if [`SearchType`] is [`AllHandles`] or
+    /// [`ByProtocol`]. If [`SearchType`] is [`ByRegisterNotify`], the parameter must be the
+    /// registration value returned by [`RegisterProtocolNotify()`].
+    ///
+    /// ## `BufferSize`
+    ///
+    /// On input, the size in bytes of [`Buffer`]. On output, the size in bytes of the array
+    /// returned in [`Buffer`] (if the buffer was large enough) or the size, in bytes, of the buffer
+    /// needed to obtain the array (if the buffer was not large enough).
+    ///
+    /// ## `Buffer`
+    ///
+    /// The buffer in which the array is returned.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the array of handles was returned.
+    ///
+    /// [`EFI_NOT_FOUND`] - no handles match the search.
+    ///
+    /// [`EFI_BUFFER_TOO_SMALL`] - the [`BufferSize`] is too small for the result.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - [`SearchType`] is not a member of [`EFI_LOCATE_SEARCH_TYPE`],
+    /// [`SearchKey`] or [`Protocol`] is null while required, [`BufferSize`] is null, or
+    /// [`BufferSize`] is large enough for the result and [`Buffer`] is null.
+    ///
+    /// [`LocateHandle()`]: ./struct.EFI_BOOT_SERVICES.html#method.LocateHandle
+    /// [`SearchType`]: ./struct.EFI_BOOT_SERVICES.html#searchtype
+    /// [`Protocol`]: ./struct.EFI_BOOT_SERVICES.html#protocol-1
+    /// [`SearchKey`]: ./struct.EFI_BOOT_SERVICES.html#searchkey
+    /// [`BufferSize`]: ./struct.EFI_BOOT_SERVICES.html#buffersize
+    /// [`Buffer`]: ./struct.EFI_BOOT_SERVICES.html#buffer-1
+    /// [`AllHandles`]: crate::tables::boot_services::EFI_LOCATE_SEARCH_TYPE::AllHandles
+    /// [`ByRegisterNotify`]: crate::tables::boot_services::EFI_LOCATE_SEARCH_TYPE::ByRegisterNotify
+    /// [`ByProtocol`]: crate::tables::boot_services::EFI_LOCATE_SEARCH_TYPE::ByProtocol
+    /// [`EFI_LOCATE_SEARCH_TYPE`]: crate::tables::boot_services::EFI_LOCATE_SEARCH_TYPE
+    /// [`RegisterProtocolNotify()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-registerprotocolnotify
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_NOT_FOUND`]: crate::status::EFI_NOT_FOUND
+    /// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn LocateHandle(
+        &self,
+        SearchType: EFI_LOCATE_SEARCH_TYPE,
+        Protocol: *const EFI_GUID,
+        SearchKey: *mut VOID,
+        BufferSize: *mut UINTN,
+        Buffer: *mut EFI_HANDLE,
+    ) -> EFI_STATUS {
+        (self.LocateHandle)(SearchType, Protocol, SearchKey, BufferSize, Buffer)
+    }
+
+    /// Locates the handle to a device on the device path that supports the specified protocol.
+    ///
+    /// The [`LocateDevicePath()`] function locates all devices on [`DevicePath`] that support
+    /// [`Protocol`] and returns the handle to the device that is closest to [`DevicePath`].
+    /// [`DevicePath`] is advanced over the device path nodes that were matched.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Protocol`
+    ///
+    /// The protocol to search for.
+    ///
+    /// ## `DevicePath`
+    ///
+    /// On input, a pointer to a pointer to the device path. On output, the device path pointer is
+    /// modified to point to the remaining part of the device path.
+    ///
+    /// ## `Device`
+    ///
+    /// A pointer to the returned device handle.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the resulting handle was returned.
+    ///
+    /// [`EFI_NOT_FOUND`] - no handles matched the search.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - [`Protocol`], [`DevicePath`] or [`Device`] is null, or a handle
+    /// matched the search but [`Device`] is null.
+    ///
+    /// [`LocateDevicePath()`]: ./struct.EFI_BOOT_SERVICES.html#method.LocateDevicePath
+    /// [`Protocol`]: ./struct.EFI_BOOT_SERVICES.html#protocol-2
+    /// [`DevicePath`]: ./struct.EFI_BOOT_SERVICES.html#devicepath
+    /// [`Device`]: ./struct.EFI_BOOT_SERVICES.html#device
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_NOT_FOUND`]: crate::status::EFI_NOT_FOUND
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn LocateDevicePath(
+        &self,
+        Protocol: *const EFI_GUID,
+        DevicePath: *mut *mut EFI_DEVICE_PATH_PROTOCOL,
+        Device: *mut EFI_HANDLE,
+    ) -> EFI_STATUS {
+        (self.LocateDevicePath)(Protocol, DevicePath, Device)
+    }
+
+    /// Queries a handle to determine if it supports a specified protocol. If the protocol is
+    /// supported by the handle, it opens the protocol on behalf of the calling agent.
+    ///
+    /// [`Attributes`] specifies how the protocol is opened, as one of the `EFI_OPEN_PROTOCOL_*`
+    /// constants. Protocols opened with [`EFI_OPEN_PROTOCOL_BY_DRIVER`] or
+    /// [`EFI_OPEN_PROTOCOL_EXCLUSIVE`] are tracked by the firmware and must be closed with
+    /// [`CloseProtocol()`] once they are no longer used.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Handle`
+    ///
+    /// The handle for the protocol interface that is being opened.
+    ///
+    /// ## `Protocol`
+    ///
+    /// The published unique identifier of the protocol.
+    ///
+    /// ## `Interface`
+    ///
+    /// Supplies the address where a pointer to the corresponding protocol interface is returned.
+    /// May be null only if [`Attributes`] is [`EFI_OPEN_PROTOCOL_TEST_PROTOCOL`].
+    ///
+    /// ## `AgentHandle`
+    ///
+    /// The handle of the agent that is opening the protocol interface specified by [`Protocol`]
+    /// and [`Interface`]. For agents that follow the UEFI Driver Model, this is the handle that
+    /// contains the EFI Driver Binding Protocol. For UEFI applications, this is the image handle
+    /// of the application.
+    ///
+    /// ## `ControllerHandle`
+    ///
+    /// If the agent that is opening a protocol is a driver that follows the UEFI Driver Model,
+    /// this is the controller handle that requires the protocol interface. If the agent does not
+    /// follow the UEFI Driver Model, then this parameter is optional and may be null.
+    ///
+    /// ## `Attributes`
+    ///
+    /// The open mode of the protocol interface specified by [`Handle`] and [`Protocol`].
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - an item was added to the open list for the protocol interface, and the
+    /// protocol interface was returned in [`Interface`].
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - one of the handles or [`Protocol`] is invalid, or
+    /// [`Interface`] is null while [`Attributes`] is not [`EFI_OPEN_PROTOCOL_TEST_PROTOCOL`].
+    ///
+    /// [`EFI_UNSUPPORTED`] - [`Handle`] does not support [`Protocol`].
+    ///
+    /// [`EFI_ACCESS_DENIED`] - the protocol is already opened in a mode that conflicts with
+    /// [`Attributes`], or an [`EFI_OPEN_PROTOCOL_EXCLUSIVE`] open could not remove the agents
+    /// currently using the protocol.
+    ///
+    /// [`EFI_ALREADY_STARTED`] - [`AgentHandle`] has already opened the protocol with the same
+    /// [`Attributes`], which is one of [`EFI_OPEN_PROTOCOL_BY_DRIVER`] or
+    /// [`EFI_OPEN_PROTOCOL_EXCLUSIVE`].
+    ///
+    /// [`Attributes`]: ./struct.EFI_BOOT_SERVICES.html#attributes
+    /// [`Handle`]: ./struct.EFI_BOOT_SERVICES.html#handle-1
+    /// [`Protocol`]: ./struct.EFI_BOOT_SERVICES.html#protocol-3
+    /// [`Interface`]: ./struct.EFI_BOOT_SERVICES.html#interface-1
+    /// [`AgentHandle`]: ./struct.EFI_BOOT_SERVICES.html#agenthandle
+    /// [`CloseProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.CloseProtocol
+    /// [`EFI_OPEN_PROTOCOL_BY_DRIVER`]: crate::tables::boot_services::EFI_OPEN_PROTOCOL_BY_DRIVER
+    /// [`EFI_OPEN_PROTOCOL_EXCLUSIVE`]: crate::tables::boot_services::EFI_OPEN_PROTOCOL_EXCLUSIVE
+    /// [`EFI_OPEN_PROTOCOL_TEST_PROTOCOL`]: crate::tables::boot_services::EFI_OPEN_PROTOCOL_TEST_PROTOCOL
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    /// [`EFI_ACCESS_DENIED`]: crate::status::EFI_ACCESS_DENIED
+    /// [`EFI_ALREADY_STARTED`]: crate::status::EFI_ALREADY_STARTED
+    pub unsafe fn OpenProtocol(
+        &self,
+        Handle: EFI_HANDLE,
+        Protocol: *const EFI_GUID,
+        Interface: *mut *mut VOID,
+        AgentHandle: EFI_HANDLE,
+        ControllerHandle: EFI_HANDLE,
+        Attributes: UINT32,
+    ) -> EFI_STATUS {
+        (self.OpenProtocol)(
+            Handle,
+            Protocol,
+            Interface,
+            AgentHandle,
+            ControllerHandle,
+            Attributes,
+        )
+    }
+
+    /// Closes a protocol on a handle that was opened using [`OpenProtocol()`].
+    ///
+    /// The [`CloseProtocol()`] function removes the entries of the open list of [`Protocol`] on
+    /// [`Handle`] that match [`AgentHandle`] and [`ControllerHandle`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `Handle`
+    ///
+    /// The handle for the protocol interface that was previously opened with [`OpenProtocol()`],
+    /// and is now being closed.
+    ///
+    /// ## `Protocol`
+    ///
+    /// The published unique identifier of the protocol.
+    ///
+    /// ## `AgentHandle`
+    ///
+    /// The handle of the agent that is closing the protocol interface.
+    ///
+    /// ## `ControllerHandle`
+    ///
+    /// If the agent that opened a protocol is a driver that follows the UEFI Driver Model, then
+    /// this parameter is the controller handle that required the protocol interface. If the agent
+    /// does not follow the UEFI Driver Model, then this parameter is optional and may be null.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the protocol instance was closed.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - [`Handle`] or [`AgentHandle`] is invalid, [`ControllerHandle`]
+    /// is neither null nor a valid handle, or [`Protocol`] is null.
+    ///
+    /// [`EFI_NOT_FOUND`] - [`Handle`] does not support [`Protocol`], or the protocol interface is
+    /// not currently open by [`AgentHandle`] and [`ControllerHandle`].
+    ///
+    /// [`OpenProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.OpenProtocol
+    /// [`CloseProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.CloseProtocol
+    /// [`Handle`]: ./struct.EFI_BOOT_SERVICES.html#handle-2
+    /// [`Protocol`]: ./struct.EFI_BOOT_SERVICES.html#protocol-4
+    /// [`AgentHandle`]: ./struct.EFI_BOOT_SERVICES.html#agenthandle-1
+    /// [`ControllerHandle`]: ./struct.EFI_BOOT_SERVICES.html#controllerhandle-1
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_NOT_FOUND`]: crate::status::EFI_NOT_FOUND
+    pub unsafe fn CloseProtocol(
+        &self,
+        Handle: EFI_HANDLE,
+        Protocol: *const EFI_GUID,
+        AgentHandle: EFI_HANDLE,
+        ControllerHandle: EFI_HANDLE,
+    ) -> EFI_STATUS {
+        (self.CloseProtocol)(Handle, Protocol, AgentHandle, ControllerHandle)
+    }
+
+    /// Retrieves the list of agents that currently have a protocol interface opened.
+    ///
+    /// The [`OpenProtocolInformation()`] function allocates and returns a buffer of
+    /// [`EFI_OPEN_PROTOCOL_INFORMATION_ENTRY`] structures, which must be freed by the caller with
+    /// [`FreePool()`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `Handle`
+    ///
+    /// The handle for the protocol interface that is being queried.
+    ///
+    /// ## `Protocol`
+    ///
+    /// The published unique identifier of the protocol.
+    ///
+    /// ## `EntryBuffer`
+    ///
+    /// A pointer to a buffer of open protocol information in the form of
+    /// [`EFI_OPEN_PROTOCOL_INFORMATION_ENTRY`] structures.
+    ///
+    /// ## `EntryCount`
+    ///
+    /// A pointer to the number of entries in [`EntryBuffer`].
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the open protocol information was returned in [`EntryBuffer`], and the
+    /// number of entries was returned [`EntryCount`].
+    ///
+    /// [`EFI_NOT_FOUND`] - [`Handle`] does not support the protocol specified by [`Protocol`].
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - there are not enough resources available to allocate
+    /// [`EntryBuffer`].
+    ///
+    /// [`OpenProtocolInformation()`]: ./struct.EFI_BOOT_SERVICES.html#method.OpenProtocolInformation
+    /// [`FreePool()`]: ./struct.EFI_BOOT_SERVICES.html#method.FreePool
+    /// [`Handle`]: ./struct.EFI_BOOT_SERVICES.html#handle-3
+    /// [`Protocol`]: ./struct.EFI_BOOT_SERVICES.html#protocol-5
+    /// [`EntryBuffer`]: ./struct.EFI_BOOT_SERVICES.html#entrybuffer
+    /// [`EntryCount`]: ./struct.EFI_BOOT_SERVICES.html#entrycount
+    /// [`EFI_OPEN_PROTOCOL_INFORMATION_ENTRY`]: crate::tables::boot_services::EFI_OPEN_PROTOCOL_INFORMATION_ENTRY
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_NOT_FOUND`]: crate::status::EFI_NOT_FOUND
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    pub unsafe fn OpenProtocolInformation(
+        &self,
+        Handle: EFI_HANDLE,
+        Protocol: *const EFI_GUID,
+        EntryBuffer: *mut *mut EFI_OPEN_PROTOCOL_INFORMATION_ENTRY,
+        EntryCount: *mut UINTN,
+    ) -> EFI_STATUS {
+        (self.OpenProtocolInformation)(Handle, Protocol, EntryBuffer, EntryCount)
+    }
+
+    /// Retrieves the list of protocol interface GUIDs that are installed on a handle in a buffer
+    /// allocated from pool.
+    ///
+    /// The buffer returned in [`ProtocolBuffer`] must be freed by the caller with [`FreePool()`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `Handle`
+    ///
+    /// The handle from which to retrieve the list of protocol interface GUIDs.
+    ///
+    /// ## `ProtocolBuffer`
+    ///
+    /// A pointer to the list of protocol interface GUID pointers that are installed on
+    /// [`Handle`].
+    ///
+    /// ## `ProtocolBufferCount`
+    ///
+    /// A pointer to the number of GUID pointers present in [`ProtocolBuffer`].
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the list of protocol interface GUIDs installed on [`Handle`] was returned
+    /// in [`ProtocolBuffer`].
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - there is not enough pool memory to store the results.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - [`Handle`] is null or invalid, or [`ProtocolBuffer`] or
+    /// [`ProtocolBufferCount`] is null.
+    ///
+    /// [`FreePool()`]: ./struct.EFI_BOOT_SERVICES.html#method.FreePool
+    /// [`Handle`]: ./struct.EFI_BOOT_SERVICES.html#handle-4
+    /// [`ProtocolBuffer`]: ./struct.EFI_BOOT_SERVICES.html#protocolbuffer
+    /// [`ProtocolBufferCount`]: ./struct.EFI_BOOT_SERVICES.html#protocolbuffercount
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    pub unsafe fn ProtocolsPerHandle(
+        &self,
+        Handle: EFI_HANDLE,
+        ProtocolBuffer: *mut *mut *mut EFI_GUID,
+        ProtocolBufferCount: *mut UINTN,
+    ) -> EFI_STATUS {
+        (self.ProtocolsPerHandle)(Handle, ProtocolBuffer, ProtocolBufferCount)
+    }
+
+    /// Returns an array of handles that support the requested protocol in a buffer allocated from
+    /// pool.
+    ///
+    /// The [`LocateHandleBuffer()`] function behaves like [`LocateHandle()`], except that it
+    /// allocates a buffer large enough for the result, which must be freed by the caller with
+    /// [`FreePool()`].
+    ///
+    /// # Parameters
+    ///
+    /// ## `SearchType`
+    ///
+    /// Specifies which handle(s) are to be returned.
+    ///
+    /// ## `Protocol`
+    ///
+    /// Provides the protocol to search by. This parameter is only valid for a [`SearchType`] of
+    /// [`ByProtocol`].
+    ///
+    /// ## `SearchKey`
+    ///
+    /// Supplies the search key depending on the [`SearchType`].
+    ///
+    /// ## `NoHandles`
+    ///
+    /// The number of handles returned in [`Buffer`].
+    ///
+    /// ## `Buffer`
+    ///
+    /// A pointer to the buffer to return the requested array of handles that support
+    /// [`Protocol`].
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the array of handles was returned in [`Buffer`], and the number of handles
+    /// in [`Buffer`] was returned in [`NoHandles`].
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - [`NoHandles`] or [`Buffer`] is null.
+    ///
+    /// [`EFI_NOT_FOUND`] - no handles match the search.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - there is not enough pool memory to store the matching results.
+    ///
+    /// [`LocateHandleBuffer()`]: ./struct.EFI_BOOT_SERVICES.html#method.LocateHandleBuffer
+    /// [`LocateHandle()`]: ./struct.EFI_BOOT_SERVICES.html#method.LocateHandle
+    /// [`FreePool()`]: ./struct.EFI_BOOT_SERVICES.html#method.FreePool
+    /// [`SearchType`]: ./struct.EFI_BOOT_SERVICES.html#searchtype-1
+    /// [`Protocol`]: ./struct.EFI_BOOT_SERVICES.html#protocol-6
+    /// [`NoHandles`]: ./struct.EFI_BOOT_SERVICES.html#nohandles
+    /// [`Buffer`]: ./struct.EFI_BOOT_SERVICES.html#buffer-2
+    /// [`ByProtocol`]: crate::tables::boot_services::EFI_LOCATE_SEARCH_TYPE::ByProtocol
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_NOT_FOUND`]: crate::status::EFI_NOT_FOUND
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    pub unsafe fn LocateHandleBuffer(
+        &self,
+        SearchType: EFI_LOCATE_SEARCH_TYPE,
+        Protocol: *const EFI_GUID,
+        SearchKey: *mut VOID,
+        NoHandles: *mut UINTN,
+        Buffer: *mut *mut EFI_HANDLE,
+    ) -> EFI_STATUS {
+        (self.LocateHandleBuffer)(SearchType, Protocol, SearchKey, NoHandles, Buffer)
+    }
+
+    /// Returns the first protocol instance that matches the given protocol.
+    ///
+    /// The [`LocateProtocol()`] function finds the first device handle that supports
+    /// [`Protocol`], and returns a pointer to the protocol interface from that handle in
+    /// [`Interface`]. If no protocol instances are found, then [`Interface`] is set to null.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Protocol`
+    ///
+    /// Provides the protocol to search for.
+    ///
+    /// ## `Registration`
+    ///
+    /// Optional registration key returned from [`RegisterProtocolNotify()`]. If it is not null,
+    /// the next handle on which the protocol was installed since the last call is returned.
+    ///
+    /// ## `Interface`
+    ///
+    /// On return, a pointer to the first interface that matches [`Protocol`] and
+    /// [`Registration`].
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - a protocol instance matching [`Protocol`] was found and returned in
+    /// [`Interface`].
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - [`Interface`] is null.
+    ///
+    /// [`EFI_NOT_FOUND`] - no protocol instances were found that match [`Protocol`] and
+    /// [`Registration`].
+    ///
+    /// [`LocateProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.LocateProtocol
+    /// [`Protocol`]: ./struct.EFI_BOOT_SERVICES.html#protocol-7
+    /// [`Registration`]: ./struct.EFI_BOOT_SERVICES.html#registration
+    /// [`Interface`]: ./struct.EFI_BOOT_SERVICES.html#interface-2
+    /// [`RegisterProtocolNotify()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-registerprotocolnotify
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_NOT_FOUND`]: crate::status::EFI_NOT_FOUND
+    pub unsafe fn LocateProtocol(
+        &self,
+        Protocol: *const EFI_GUID,
+        Registration: *mut VOID,
+        Interface: *mut *mut VOID,
+    ) -> EFI_STATUS {
+        (self.LocateProtocol)(Protocol, Registration, Interface)
+    }
+
+    /// Creates an event in a group.
+    ///
+    /// The [`CreateEventEx()`] function creates a new event of type [`Type`] and returns it in the
+    /// location indicated by [`Event`]. It behaves exactly like [`CreateEvent()`], except that if
+    /// [`EventGroup`] is not null, the event is added to that group. When any event in a group is
+    /// signaled, every event in the group is signaled as well.
+    ///
+    /// The [`EVT_SIGNAL_EXIT_BOOT_SERVICES`] and [`EVT_SIGNAL_VIRTUAL_ADDRESS_CHANGE`] types may
+    /// not be used with this function; use [`EFI_EVENT_GROUP_EXIT_BOOT_SERVICES`] and
+    /// [`EFI_EVENT_GROUP_VIRTUAL_ADDRESS_CHANGE`] as the [`EventGroup`] instead.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Type`
+    ///
+    /// The type of event to create and its mode and attributes.
+    ///
+    /// ## `NotifyTpl`
+    ///
+    /// The task priority level of event notifications, if needed.
+    ///
+    /// ## `NotifyFunction`
+    ///
+    /// The notification function to call when the event is signaled, if needed.
+    ///
+    /// ## `NotifyContext`
+    ///
+    /// The context pointer to pass to the notification function.
+    ///
+    /// ## `EventGroup`
+    ///
+    /// The unique identifier of the group to which this event belongs. If this is null, then the
+    /// function behaves as if the parameters were passed to [`CreateEvent()`].
+    ///
+    /// ## `Event`
+    ///
+    /// Pointer to the newly created event if the call succeeds; undefined otherwise.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the event structure was created.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - one of the parameters has an invalid value.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - the event could not be allocated.
+    ///
+    /// [`CreateEventEx()`]: ./struct.EFI_BOOT_SERVICES.html#method.CreateEventEx
+    /// [`CreateEvent()`]: ./struct.EFI_BOOT_SERVICES.html#method.CreateEvent
+    /// [`Type`]: ./struct.EFI_BOOT_SERVICES.html#type-2
+    /// [`EventGroup`]: ./struct.EFI_BOOT_SERVICES.html#eventgroup
+    /// [`Event`]: ./struct.EFI_BOOT_SERVICES.html#event-6
+    /// [`EVT_SIGNAL_EXIT_BOOT_SERVICES`]: crate::tables::boot_services::EVT_SIGNAL_EXIT_BOOT_SERVICES
+    /// [`EVT_SIGNAL_VIRTUAL_ADDRESS_CHANGE`]: crate::tables::boot_services::EVT_SIGNAL_VIRTUAL_ADDRESS_CHANGE
+    /// [`EFI_EVENT_GROUP_EXIT_BOOT_SERVICES`]: crate::tables::boot_services::EFI_EVENT_GROUP_EXIT_BOOT_SERVICES
+    /// [`EFI_EVENT_GROUP_VIRTUAL_ADDRESS_CHANGE`]: crate::tables::boot_services::EFI_EVENT_GROUP_VIRTUAL_ADDRESS_CHANGE
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    pub unsafe fn CreateEventEx(
+        &self,
+        Type: EFI_EVENT_TYPE,
+        NotifyTpl: EFI_TPL,
+        NotifyFunction: Option<EFI_EVENT_NOTIFY>,
+        NotifyContext: *const VOID,
+        EventGroup: *const EFI_GUID,
+        Event: *mut EFI_EVENT,
+    ) -> EFI_STATUS {
+        (self.CreateEventEx)(
+            Type,
+            NotifyTpl,
+            NotifyFunction,
+            NotifyContext,
+            EventGroup,
+            Event,
+        )
+    }
+
+    /// Creates an event that is closed when the returned [`Event`] is dropped.
+    ///
+    /// The event is created with [`CreateEventEx()`] if [`EventGroup`] is given, and with
+    /// [`CreateEvent()`] otherwise.
+    ///
+    /// # Safety
+    ///
+    /// If [`NotifyFunction`] is given, [`NotifyContext`] must be valid for the notification
+    /// function for as long as the returned [`Event`] is alive.
+    ///
+    /// [`Event`]: crate::tables::boot_services::Event
+    /// [`CreateEventEx()`]: ./struct.EFI_BOOT_SERVICES.html#method.CreateEventEx
+    /// [`CreateEvent()`]: ./struct.EFI_BOOT_SERVICES.html#method.CreateEvent
+    /// [`EventGroup`]: ./struct.EFI_BOOT_SERVICES.html#eventgroup
+    /// [`NotifyFunction`]: ./struct.EFI_BOOT_SERVICES.html#notifyfunction
+    /// [`NotifyContext`]: ./struct.EFI_BOOT_SERVICES.html#notifycontext
+    pub unsafe fn create_event(
+        &self,
+        Type: EFI_EVENT_TYPE,
+        NotifyTpl: EFI_TPL,
+        NotifyFunction: Option<EFI_EVENT_NOTIFY>,
+        NotifyContext: *mut VOID,
+        EventGroup: Option<&EFI_GUID>,
+    ) -> Result<Event<'_>, EFI_STATUS> {
+        let mut event = ptr::null_mut();
+        let status = match EventGroup {
+            Some(group) => self.CreateEventEx(
+                Type,
+                NotifyTpl,
+                NotifyFunction,
+                NotifyContext,
+                group,
+                &mut event,
+            ),
+            None => self.CreateEvent(Type, NotifyTpl, NotifyFunction, NotifyContext, &mut event),
+        };
+
+        match status {
+            EFI_SUCCESS => Ok(Event {
+                boot_services: self,
+                event,
+                context: ptr::null_mut(),
+                drop_context: None,
+            }),
+            status => Err(status),
+        }
+    }
+
+    /// Creates an [`EVT_TIMER`] event without a notification function, to be armed with
+    /// [`Event::set_timer()`] and waited on or checked.
+    ///
+    /// [`EVT_TIMER`]: crate::tables::boot_services::EVT_TIMER
+    /// [`Event::set_timer()`]: ./struct.Event.html#method.set_timer
+    pub unsafe fn create_timer(&self) -> Result<Event<'_>, EFI_STATUS> {
+        self.create_event(EVT_TIMER, TPL_APPLICATION, None, ptr::null_mut(), None)
+    }
+
+    /// Creates an event whose notification function calls [`Notify`] with the raw event handle.
+    ///
+    /// [`Type`] must contain [`EVT_NOTIFY_WAIT`] or [`EVT_NOTIFY_SIGNAL`]. The closure runs at
+    /// [`NotifyTpl`], so any state it shares with code running at a lower TPL must be protected by
+    /// raising the TPL around accesses to it. The closure stays borrowed by the returned [`Event`],
+    /// which closes the event, so that the firmware no longer calls the closure, when dropped.
+    ///
+    /// # Safety
+    ///
+    /// The returned [`Event`] must be dropped or closed, and not leaked with [`mem::forget()`],
+    /// as the firmware would keep calling [`Notify`] after its borrow has ended. [`Type`] must
+    /// contain [`EVT_NOTIFY_WAIT`] or [`EVT_NOTIFY_SIGNAL`].
+    ///
+    /// [`Notify`]: ./struct.EFI_BOOT_SERVICES.html#method.create_notify_event
+    /// [`Type`]: ./struct.EFI_BOOT_SERVICES.html#method.create_notify_event
+    /// [`NotifyTpl`]: ./struct.EFI_BOOT_SERVICES.html#method.create_notify_event
+    /// [`EVT_NOTIFY_WAIT`]: crate::tables::boot_services::EVT_NOTIFY_WAIT
+    /// [`EVT_NOTIFY_SIGNAL`]: crate::tables::boot_services::EVT_NOTIFY_SIGNAL
+    /// [`Event`]: crate::tables::boot_services::Event
+    /// [`mem::forget()`]: core::mem::forget
+    pub unsafe fn create_notify_event<'a, F: FnMut(EFI_EVENT)>(
+        &'a self,
+        Type: EFI_EVENT_TYPE,
+        NotifyTpl: EFI_TPL,
+        Notify: &'a mut F,
+        EventGroup: Option<&EFI_GUID>,
+    ) -> Result<Event<'a>, EFI_STATUS> {
+        let context: *mut F = Notify;
+        self.create_event(
+            Type,
+            NotifyTpl,
+            Some(notify_trampoline::<F>),
+            context.cast(),
+            EventGroup,
+        )
+    }
+
+    /// Creates an event whose notification function calls the boxed [`Notify`] closure, which is
+    /// dropped once the returned [`Event`] has been closed.
+    ///
+    /// See [`create_notify_event()`] for details.
+    ///
+    /// # Safety
+    ///
+    /// [`Type`] must contain [`EVT_NOTIFY_WAIT`] or [`EVT_NOTIFY_SIGNAL`]. Unless [`Notify`] is
+    /// `'static`, the returned [`Event`] must be dropped or closed, and not leaked with
+    /// [`mem::forget()`], as the firmware would keep calling the closure after the data it borrows
+    /// is gone. A leaked event with a `'static` closure only leaks the closure.
+    ///
+    /// [`Notify`]: ./struct.EFI_BOOT_SERVICES.html#method.create_boxed_notify_event
+    /// [`Type`]: ./struct.EFI_BOOT_SERVICES.html#method.create_boxed_notify_event
+    /// [`EVT_NOTIFY_WAIT`]: crate::tables::boot_services::EVT_NOTIFY_WAIT
+    /// [`EVT_NOTIFY_SIGNAL`]: crate::tables::boot_services::EVT_NOTIFY_SIGNAL
+    /// [`Event`]: crate::tables::boot_services::Event
+    /// [`create_notify_event()`]: ./struct.EFI_BOOT_SERVICES.html#method.create_notify_event
+    /// [`mem::forget()`]: core::mem::forget
+    #[cfg(feature = "alloc")]
+    pub unsafe fn create_boxed_notify_event<'a, F: FnMut(EFI_EVENT) + 'a>(
+        &'a self,
+        Type: EFI_EVENT_TYPE,
+        NotifyTpl: EFI_TPL,
+        Notify: F,
+        EventGroup: Option<&EFI_GUID>,
+    ) -> Result<Event<'a>, EFI_STATUS> {
+        let context = Box::into_raw(Box::new(Notify)).cast::<VOID>();
+        match self.create_event(
+            Type,
+            NotifyTpl,
+            Some(notify_trampoline::<F>),
+            context,
+            EventGroup,
+        ) {
+            Ok(mut event) => {
+                event.context = context;
+                event.drop_context = Some(drop_boxed::<F>);
+                Ok(event)
+            }
+            Err(status) => {
+                drop_boxed::<F>(context);
+                Err(status)
+            }
+        }
+    }
+
+    /// Waits until one of [`Events`] is signaled and returns its index.
+    ///
+    /// See [`WaitForEvent()`] for details.
+    ///
+    /// [`Events`]: ./struct.EFI_BOOT_SERVICES.html#method.wait_for_event
+    /// [`WaitForEvent()`]: ./struct.EFI_BOOT_SERVICES.html#method.WaitForEvent
+    pub unsafe fn wait_for_event(&self, Events: &[EFI_EVENT]) -> Result<usize, EFI_STATUS> {
+        let mut index = 0;
+        match self.WaitForEvent(Events.len(), Events.as_ptr() as *mut EFI_EVENT, &mut index) {
+            EFI_SUCCESS => Ok(index),
+            status => Err(status),
+        }
+    }
+
+    /// Returns the current task priority level.
+    ///
+    /// The level is read by briefly raising the TPL to [`TPL_HIGH_LEVEL`] and restoring it.
+    ///
+    /// [`TPL_HIGH_LEVEL`]: crate::tables::boot_services::TPL_HIGH_LEVEL
+    pub unsafe fn current_tpl(&self) -> EFI_TPL {
+        let current = self.RaiseTPL(TPL_HIGH_LEVEL);
+        self.RestoreTPL(current);
+
+        current
+    }
+
+    /// Raises the task priority level to at least [`NewTpl`], returning a [`TplGuard`] that
+    /// restores the previous level when dropped.
+    ///
+    /// Unlike [`RaiseTPL()`], requesting a level below the current one is not an error: the TPL is
+    /// left unchanged in that case.
+    ///
+    /// [`NewTpl`]: ./struct.EFI_BOOT_SERVICES.html#method.raise_tpl
+    /// [`TplGuard`]: crate::tables::boot_services::TplGuard
+    /// [`RaiseTPL()`]: ./struct.EFI_BOOT_SERVICES.html#method.RaiseTPL
+    pub unsafe fn raise_tpl(&self, NewTpl: Tpl) -> TplGuard<'_> {
+        let old = self.RaiseTPL(TPL_HIGH_LEVEL);
+        self.RestoreTPL(old.max(NewTpl.as_raw()));
+
+        TplGuard {
+            boot_services: self,
+            old,
+        }
+    }
+
+    /// Returns the interface of the protocol [`P`] on [`Handle`].
+    ///
+    /// See [`HandleProtocol()`] for details; [`open_protocol()`] should be preferred.
+    ///
+    /// [`P`]: crate::protocols::Protocol
+    /// [`Handle`]: ./struct.EFI_BOOT_SERVICES.html#method.handle_protocol
+    /// [`HandleProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.HandleProtocol
+    /// [`open_protocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.open_protocol
+    pub unsafe fn handle_protocol<P: Protocol>(
+        &self,
+        Handle: EFI_HANDLE,
+    ) -> Result<*mut P, EFI_STATUS> {
+        let mut interface = ptr::null_mut();
+        match self.HandleProtocol(Handle, &P::GUID, &mut interface) {
+            EFI_SUCCESS => Ok(interface.cast()),
+            status => Err(status),
+        }
+    }
+
+    /// Returns the first instance of the protocol [`P`] found in the handle database.
+    ///
+    /// See [`LocateProtocol()`] for details.
+    ///
+    /// [`P`]: crate::protocols::Protocol
+    /// [`LocateProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.LocateProtocol
+    pub unsafe fn locate_protocol<P: Protocol>(&self) -> Result<*mut P, EFI_STATUS> {
+        let mut interface = ptr::null_mut();
+        match self.LocateProtocol(&P::GUID, ptr::null_mut(), &mut interface) {
+            EFI_SUCCESS => Ok(interface.cast()),
+            status => Err(status),
+        }
+    }
+
+    /// Opens the protocol [`P`] on [`Handle`] on behalf of [`AgentHandle`] and
+    /// [`ControllerHandle`], returning its interface.
+    ///
+    /// See [`OpenProtocol()`] for details. The interface is null if [`Attributes`] is
+    /// [`EFI_OPEN_PROTOCOL_TEST_PROTOCOL`].
+    ///
+    /// [`P`]: crate::protocols::Protocol
+    /// [`Handle`]: ./struct.EFI_BOOT_SERVICES.html#method.open_protocol
+    /// [`AgentHandle`]: ./struct.EFI_BOOT_SERVICES.html#method.open_protocol
+    /// [`ControllerHandle`]: ./struct.EFI_BOOT_SERVICES.html#method.open_protocol
+    /// [`Attributes`]: ./struct.EFI_BOOT_SERVICES.html#method.open_protocol
+    /// [`OpenProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.OpenProtocol
+    /// [`EFI_OPEN_PROTOCOL_TEST_PROTOCOL`]: crate::tables::boot_services::EFI_OPEN_PROTOCOL_TEST_PROTOCOL
+    pub unsafe fn open_protocol<P: Protocol>(
+        &self,
+        Handle: EFI_HANDLE,
+        AgentHandle: EFI_HANDLE,
+        ControllerHandle: EFI_HANDLE,
+        Attributes: UINT32,
+    ) -> Result<*mut P, EFI_STATUS> {
+        let mut interface = ptr::null_mut();
+        match self.OpenProtocol(
+            Handle,
+            &P::GUID,
+            &mut interface,
+            AgentHandle,
+            ControllerHandle,
+            Attributes,
+        ) {
+            EFI_SUCCESS => Ok(interface.cast()),
+            status => Err(status),
+        }
+    }
+
+    /// Opens the protocol [`P`] on [`Handle`] on behalf of [`AgentHandle`] and
+    /// [`ControllerHandle`], returning a [`ScopedProtocol`] which closes it with the same handles
+    /// when dropped.
+    ///
+    /// See [`OpenProtocol()`] for details. [`EFI_INVALID_PARAMETER`] is returned if
+    /// [`Attributes`] is [`EFI_OPEN_PROTOCOL_TEST_PROTOCOL`], which does not return an interface;
+    /// use [`test_protocol()`] instead.
+    ///
+    /// [`P`]: crate::protocols::Protocol
+    /// [`Handle`]: ./struct.EFI_BOOT_SERVICES.html#method.open_scoped_protocol
+    /// [`AgentHandle`]: ./struct.EFI_BOOT_SERVICES.html#method.open_scoped_protocol
+    /// [`ControllerHandle`]: ./struct.EFI_BOOT_SERVICES.html#method.open_scoped_protocol
+    /// [`Attributes`]: ./struct.EFI_BOOT_SERVICES.html#method.open_scoped_protocol
+    /// [`ScopedProtocol`]: crate::tables::boot_services::ScopedProtocol
+    /// [`OpenProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.OpenProtocol
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_OPEN_PROTOCOL_TEST_PROTOCOL`]: crate::tables::boot_services::EFI_OPEN_PROTOCOL_TEST_PROTOCOL
+    /// [`test_protocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.test_protocol
+    pub unsafe fn open_scoped_protocol<P: Protocol>(
+        &self,
+        Handle: EFI_HANDLE,
+        AgentHandle: EFI_HANDLE,
+        ControllerHandle: EFI_HANDLE,
+        Attributes: UINT32,
+    ) -> Result<ScopedProtocol<'_, P>, EFI_STATUS> {
+        if Attributes & EFI_OPEN_PROTOCOL_TEST_PROTOCOL != 0 {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        let interface =
+            self.open_protocol::<P>(Handle, AgentHandle, ControllerHandle, Attributes)?;
+        let scoped = ScopedProtocol {
+            boot_services: self,
+            interface,
+            handle: Handle,
+            agent_handle: AgentHandle,
+            controller_handle: ControllerHandle,
+        };
+
+        if interface.is_null() {
+            return Err(EFI_UNSUPPORTED);
+        }
+
+        Ok(scoped)
+    }
+
+    /// Opens the protocol [`P`] on [`Handle`] with [`EFI_OPEN_PROTOCOL_EXCLUSIVE`] on behalf of
+    /// the application [`AgentHandle`], disconnecting any driver using it until the returned
+    /// [`ScopedProtocol`] is dropped.
+    ///
+    /// [`P`]: crate::protocols::Protocol
+    /// [`Handle`]: ./struct.EFI_BOOT_SERVICES.html#method.open_protocol_exclusive
+    /// [`AgentHandle`]: ./struct.EFI_BOOT_SERVICES.html#method.open_protocol_exclusive
+    /// [`EFI_OPEN_PROTOCOL_EXCLUSIVE`]: crate::tables::boot_services::EFI_OPEN_PROTOCOL_EXCLUSIVE
+    /// [`ScopedProtocol`]: crate::tables::boot_services::ScopedProtocol
+    pub unsafe fn open_protocol_exclusive<P: Protocol>(
+        &self,
+        Handle: EFI_HANDLE,
+        AgentHandle: EFI_HANDLE,
+    ) -> Result<ScopedProtocol<'_, P>, EFI_STATUS> {
+        self.open_scoped_protocol::<P>(
+            Handle,
+            AgentHandle,
+            ptr::null_mut(),
+            EFI_OPEN_PROTOCOL_EXCLUSIVE,
+        )
+    }
+
+    /// Returns whether [`Handle`] supports the protocol [`P`], using
+    /// [`EFI_OPEN_PROTOCOL_TEST_PROTOCOL`].
+    ///
+    /// [`Handle`]: ./struct.EFI_BOOT_SERVICES.html#method.test_protocol
+    /// [`P`]: crate::protocols::Protocol
+    /// [`EFI_OPEN_PROTOCOL_TEST_PROTOCOL`]: crate::tables::boot_services::EFI_OPEN_PROTOCOL_TEST_PROTOCOL
+    pub unsafe fn test_protocol<P: Protocol>(
+        &self,
+        Handle: EFI_HANDLE,
+        AgentHandle: EFI_HANDLE,
+        ControllerHandle: EFI_HANDLE,
+    ) -> Result<bool, EFI_STATUS> {
+        match self.open_protocol::<P>(
+            Handle,
+            AgentHandle,
+            ControllerHandle,
+            EFI_OPEN_PROTOCOL_TEST_PROTOCOL,
+        ) {
+            Ok(_) => Ok(true),
+            Err(EFI_UNSUPPORTED) => Ok(false),
+            Err(status) => Err(status),
+        }
+    }
+
+    /// Closes the protocol [`P`] on [`Handle`], which was opened with [`open_protocol()`] by
+    /// [`AgentHandle`] and [`ControllerHandle`].
+    ///
+    /// See [`CloseProtocol()`] for details.
+    ///
+    /// [`P`]: crate::protocols::Protocol
+    /// [`Handle`]: ./struct.EFI_BOOT_SERVICES.html#method.close_protocol
+    /// [`AgentHandle`]: ./struct.EFI_BOOT_SERVICES.html#method.close_protocol
+    /// [`ControllerHandle`]: ./struct.EFI_BOOT_SERVICES.html#method.close_protocol
+    /// [`open_protocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.open_protocol
+    /// [`CloseProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.CloseProtocol
+    pub unsafe fn close_protocol<P: Protocol>(
+        &self,
+        Handle: EFI_HANDLE,
+        AgentHandle: EFI_HANDLE,
+        ControllerHandle: EFI_HANDLE,
+    ) -> Result<(), EFI_STATUS> {
+        match self.CloseProtocol(Handle, &P::GUID, AgentHandle, ControllerHandle) {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Returns the handles matching a search in a buffer allocated from pool, which is freed when
+    /// the returned [`PoolBuffer`] is dropped.
+    ///
+    /// See [`LocateHandleBuffer()`] for details.
+    ///
+    /// [`PoolBuffer`]: crate::tables::boot_services::PoolBuffer
+    /// [`LocateHandleBuffer()`]: ./struct.EFI_BOOT_SERVICES.html#method.LocateHandleBuffer
+    pub unsafe fn locate_handle_buffer(
+        &self,
+        SearchType: EFI_LOCATE_SEARCH_TYPE,
+        Protocol: Option<&EFI_GUID>,
+        SearchKey: *mut VOID,
+    ) -> Result<PoolBuffer<'_, EFI_HANDLE>, EFI_STATUS> {
+        let protocol = Protocol.map_or(ptr::null(), |guid| guid);
+        let mut count = 0;
+        let mut buffer = ptr::null_mut();
+        match self.LocateHandleBuffer(SearchType, protocol, SearchKey, &mut count, &mut buffer) {
+            EFI_SUCCESS => Ok(PoolBuffer::from_raw(self, buffer, count)),
+            status => Err(status),
+        }
+    }
+
+    /// Returns the handles supporting the protocol [`P`].
+    ///
+    /// [`EFI_NOT_FOUND`] is returned if no handle supports the protocol.
+    ///
+    /// [`P`]: crate::protocols::Protocol
+    /// [`EFI_NOT_FOUND`]: crate::status::EFI_NOT_FOUND
+    pub unsafe fn handles_by_protocol<P: Protocol>(
+        &self,
+    ) -> Result<PoolBuffer<'_, EFI_HANDLE>, EFI_STATUS> {
+        self.locate_handle_buffer(
+            EFI_LOCATE_SEARCH_TYPE::ByProtocol,
+            Some(&P::GUID),
+            ptr::null_mut(),
+        )
+    }
+
+    /// Returns the handles matching a search, growing the buffer passed to [`LocateHandle()`] as
+    /// needed.
+    ///
+    /// [`LocateHandle()`]: ./struct.EFI_BOOT_SERVICES.html#method.LocateHandle
+    #[cfg(feature = "alloc")]
+    pub unsafe fn locate_handle(
+        &self,
+        SearchType: EFI_LOCATE_SEARCH_TYPE,
+        Protocol: Option<&EFI_GUID>,
+        SearchKey: *mut VOID,
+    ) -> Result<Vec<EFI_HANDLE>, EFI_STATUS> {
+        let protocol = Protocol.map_or(ptr::null(), |guid| guid);
+        let mut handles = Vec::new();
+        loop {
+            let mut size = handles.len() * mem::size_of::<EFI_HANDLE>();
+            let status = self.LocateHandle(
+                SearchType,
+                protocol,
+                SearchKey,
+                &mut size,
+                handles.as_mut_ptr(),
+            );
+
+            match status {
+                EFI_SUCCESS => {
+                    handles.truncate(size / mem::size_of::<EFI_HANDLE>());
+                    return Ok(handles);
+                }
+                EFI_BUFFER_TOO_SMALL => {
+                    handles.resize(size / mem::size_of::<EFI_HANDLE>(), ptr::null_mut())
+                }
+                _ => return Err(status),
+            }
+        }
+    }
+
+    /// Returns the GUIDs of the protocols installed on [`Handle`].
+    ///
+    /// See [`ProtocolsPerHandle()`] for details.
+    ///
+    /// [`Handle`]: ./struct.EFI_BOOT_SERVICES.html#method.protocols_per_handle
+    /// [`ProtocolsPerHandle()`]: ./struct.EFI_BOOT_SERVICES.html#method.ProtocolsPerHandle
+    pub unsafe fn protocols_per_handle(
+        &self,
+        Handle: EFI_HANDLE,
+    ) -> Result<PoolBuffer<'_, *mut EFI_GUID>, EFI_STATUS> {
+        let mut count = 0;
+        let mut buffer = ptr::null_mut();
+        match self.ProtocolsPerHandle(Handle, &mut buffer, &mut count) {
+            EFI_SUCCESS => Ok(PoolBuffer::from_raw(self, buffer, count)),
+            status => Err(status),
+        }
+    }
+
+    /// Returns the agents that currently have the protocol [`P`] on [`Handle`] opened.
+    ///
+    /// See [`OpenProtocolInformation()`] for details.
+    ///
+    /// [`P`]: crate::protocols::Protocol
+    /// [`Handle`]: ./struct.EFI_BOOT_SERVICES.html#method.open_protocol_information
+    /// [`OpenProtocolInformation()`]: ./struct.EFI_BOOT_SERVICES.html#method.OpenProtocolInformation
+    pub unsafe fn open_protocol_information<P: Protocol>(
+        &self,
+        Handle: EFI_HANDLE,
+    ) -> Result<PoolBuffer<'_, EFI_OPEN_PROTOCOL_INFORMATION_ENTRY>, EFI_STATUS> {
+        let mut count = 0;
+        let mut buffer = ptr::null_mut();
+        match self.OpenProtocolInformation(Handle, &P::GUID, &mut buffer, &mut count) {
+            EFI_SUCCESS => Ok(PoolBuffer::from_raw(self, buffer, count)),
+            status => Err(status),
+        }
+    }
+
+    /// Returns the handle of the device closest to [`DevicePath`] which supports the protocol
+    /// [`P`], together with the remaining part of the device path.
+    ///
+    /// See [`LocateDevicePath()`] for details.
+    ///
+    /// [`DevicePath`]: ./struct.EFI_BOOT_SERVICES.html#method.locate_device_path
+    /// [`P`]: crate::protocols::Protocol
+    /// [`LocateDevicePath()`]: ./struct.EFI_BOOT_SERVICES.html#method.LocateDevicePath
+    pub unsafe fn locate_device_path<P: Protocol>(
+        &self,
+        DevicePath: *mut EFI_DEVICE_PATH_PROTOCOL,
+    ) -> Result<(EFI_HANDLE, *mut EFI_DEVICE_PATH_PROTOCOL), EFI_STATUS> {
+        let mut remaining = DevicePath;
+        let mut device = ptr::null_mut();
+        match self.LocateDevicePath(&P::GUID, &mut remaining, &mut device) {
+            EFI_SUCCESS => Ok((device, remaining)),
+            status => Err(status),
+        }
+    }
+}
+
+/// An event owned by the caller, closed with [`CloseEvent()`] when dropped.
+///
+/// Events are created through [`create_event()`], [`create_timer()`], [`create_notify_event()`]
+/// or [`create_boxed_notify_event()`]. A closure attached as the notification function is only
+/// released after the event has been closed, so it can never be called once it is gone.
+///
+/// [`CloseEvent()`]: ./struct.EFI_BOOT_SERVICES.html#method.CloseEvent
+/// [`create_event()`]: ./struct.EFI_BOOT_SERVICES.html#method.create_event
+/// [`create_timer()`]: ./struct.EFI_BOOT_SERVICES.html#method.create_timer
+/// [`create_notify_event()`]: ./struct.EFI_BOOT_SERVICES.html#method.create_notify_event
+/// [`create_boxed_notify_event()`]: ./struct.EFI_BOOT_SERVICES.html#method.create_boxed_notify_event
+pub struct Event<'a> {
+    boot_services: &'a EFI_BOOT_SERVICES,
+    event: EFI_EVENT,
+    context: *mut VOID,
+    drop_context: Option<unsafe fn(*mut VOID)>,
+}
+
+impl<'a> Event<'a> {
+    /// Returns the boot services table the event was created from.
+    pub fn boot_services(&self) -> &'a EFI_BOOT_SERVICES {
+        self.boot_services
+    }
+
+    /// Returns the raw event handle, for use with raw functions such as [`WaitForEvent()`].
+    ///
+    /// [`WaitForEvent()`]: ./struct.EFI_BOOT_SERVICES.html#method.WaitForEvent
+    pub fn as_raw(&self) -> EFI_EVENT {
+        self.event
+    }
+
+    /// Arms, re-arms or cancels the timer of an [`EVT_TIMER`] event. [`TriggerTime`] is in units
+    /// of 100ns.
+    ///
+    /// [`EVT_TIMER`]: crate::tables::boot_services::EVT_TIMER
+    /// [`TriggerTime`]: ./struct.EFI_BOOT_SERVICES.html#triggertime
+    pub fn set_timer(&self, Type: EFI_TIMER_DELAY, TriggerTime: UINT64) -> Result<(), EFI_STATUS> {
+        // SAFETY: the event is valid until it is dropped.
+        match unsafe { self.boot_services.SetTimer(self.event, Type, TriggerTime) } {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Places the event, and every event in its group, in the signaled state.
+    pub fn signal(&self) -> Result<(), EFI_STATUS> {
+        // SAFETY: the event is valid until it is dropped.
+        match unsafe { self.boot_services.SignalEvent(self.event) } {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Returns whether the event was signaled, clearing the signaled state.
+    pub fn check(&self) -> Result<bool, EFI_STATUS> {
+        // SAFETY: the event is valid until it is dropped.
+        match unsafe { self.boot_services.CheckEvent(self.event) } {
+            EFI_SUCCESS => Ok(true),
+            EFI_NOT_READY => Ok(false),
+            status => Err(status),
+        }
+    }
+
+    /// Blocks until the event is signaled. Must be called at [`TPL_APPLICATION`].
+    ///
+    /// [`TPL_APPLICATION`]: crate::tables::boot_services::TPL_APPLICATION
+    pub fn wait(&self) -> Result<(), EFI_STATUS> {
+        // SAFETY: the event is valid until it is dropped.
+        unsafe { self.boot_services.wait_for_event(&[self.event]) }.map(|_| ())
+    }
+
+    /// Closes the event, returning the status reported by [`CloseEvent()`].
+    ///
+    /// [`CloseEvent()`]: ./struct.EFI_BOOT_SERVICES.html#method.CloseEvent
+    pub fn close(self) -> Result<(), EFI_STATUS> {
+        let mut this = core::mem::ManuallyDrop::new(self);
+        match this.release() {
+            EFI_SUCCESS => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    fn release(&mut self) -> EFI_STATUS {
+        // SAFETY: the event is valid until it is dropped, and once it is closed the firmware no
+        // longer calls the notification function, so the context can be released.
+        unsafe {
+            let status = self.boot_services.CloseEvent(self.event);
+            if let Some(drop_context) = self.drop_context.take() {
+                drop_context(self.context);
+            }
+
+            status
+        }
+    }
+}
+
+impl Drop for Event<'_> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+/// A protocol interface opened with [`OpenProtocol()`], closed with [`CloseProtocol()`] using the
+/// same agent and controller handles when dropped.
+///
+/// The guard dereferences to the protocol interface, which cannot outlive it, so the interface is
+/// not used once the firmware is free to uninstall it.
+///
+/// [`OpenProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.OpenProtocol
+/// [`CloseProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.CloseProtocol
+pub struct ScopedProtocol<'a, P: Protocol> {
+    boot_services: &'a EFI_BOOT_SERVICES,
+    interface: *mut P,
+    handle: EFI_HANDLE,
+    agent_handle: EFI_HANDLE,
+    controller_handle: EFI_HANDLE,
+}
+
+impl<P: Protocol> ScopedProtocol<'_, P> {
+    /// Returns a raw pointer to the protocol interface, which is valid until the guard is
+    /// dropped.
+    pub fn as_ptr(&self) -> *mut P {
+        self.interface
+    }
+
+    /// Returns the handle the protocol was opened on.
+    pub fn handle(&self) -> EFI_HANDLE {
+        self.handle
+    }
+
+    /// Returns the handle of the agent that opened the protocol.
+    pub fn agent_handle(&self) -> EFI_HANDLE {
+        self.agent_handle
+    }
+
+    /// Returns the controller handle the protocol was opened for, which may be null.
+    pub fn controller_handle(&self) -> EFI_HANDLE {
+        self.controller_handle
+    }
+
+    /// Closes the protocol, returning the status reported by [`CloseProtocol()`].
+    ///
+    /// [`CloseProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.CloseProtocol
+    pub fn close(self) -> Result<(), EFI_STATUS> {
+        let this = ManuallyDrop::new(self);
+
+        // SAFETY: the protocol was opened with these handles and has not been closed yet.
+        unsafe {
+            this.boot_services.close_protocol::<P>(
+                this.handle,
+                this.agent_handle,
+                this.controller_handle,
+            )
+        }
+    }
+}
+
+impl<P: Protocol> Deref for ScopedProtocol<'_, P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        // SAFETY: the interface is non-null and stays valid while the protocol is open.
+        unsafe { &*self.interface }
+    }
+}
+
+impl<P: Protocol> DerefMut for ScopedProtocol<'_, P> {
+    fn deref_mut(&mut self) -> &mut P {
+        // SAFETY: the interface is non-null and stays valid while the protocol is open.
+        unsafe { &mut *self.interface }
+    }
+}
+
+impl<P: Protocol> Drop for ScopedProtocol<'_, P> {
+    fn drop(&mut self) {
+        // SAFETY: the protocol was opened with these handles and has not been closed yet.
+        let _ = unsafe {
+            self.boot_services.close_protocol::<P>(
+                self.handle,
+                self.agent_handle,
+                self.controller_handle,
+            )
+        };
+    }
+}
+
+/// A buffer of `T` allocated from pool by the firmware, freed with [`FreePool()`] when dropped.
+///
+/// [`FreePool()`]: ./struct.EFI_BOOT_SERVICES.html#method.FreePool
+pub struct PoolBuffer<'a, T> {
+    boot_services: &'a EFI_BOOT_SERVICES,
+    buffer: *mut T,
+    len: usize,
+}
+
+impl<'a, T> PoolBuffer<'a, T> {
+    /// Takes ownership of [`Buffer`], an array of [`Len`] elements allocated from pool.
+    ///
+    /// # Safety
+    ///
+    /// [`Buffer`] must be null, or point to [`Len`] initialized elements allocated from pool
+    /// through [`BootServices`] and not freed elsewhere.
+    ///
+    /// [`Buffer`]: ./struct.PoolBuffer.html#method.from_raw
+    /// [`Len`]: ./struct.PoolBuffer.html#method.from_raw
+    /// [`BootServices`]: ./struct.PoolBuffer.html#method.from_raw
+    pub unsafe fn from_raw(
+        BootServices: &'a EFI_BOOT_SERVICES,
+        Buffer: *mut T,
+        Len: usize,
+    ) -> Self {
+        Self {
+            boot_services: BootServices,
+            buffer: Buffer,
+            len: Len,
+        }
+    }
+}
+
+impl<T> Deref for PoolBuffer<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        if self.buffer.is_null() {
+            return &[];
+        }
+
+        // SAFETY: the buffer holds `len` elements per the contract of `from_raw`.
+        unsafe { slice::from_raw_parts(self.buffer, self.len) }
+    }
+}
+
+impl<T> DerefMut for PoolBuffer<'_, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        if self.buffer.is_null() {
+            return &mut [];
+        }
+
+        // SAFETY: the buffer holds `len` elements per the contract of `from_raw`.
+        unsafe { slice::from_raw_parts_mut(self.buffer, self.len) }
+    }
+}
+
+impl<T> Drop for PoolBuffer<'_, T> {
+    fn drop(&mut self) {
+        if !self.buffer.is_null() {
+            // SAFETY: the buffer was allocated from pool per the contract of `from_raw`.
+            unsafe { self.boot_services.FreePool(self.buffer.cast()) };
+        }
+    }
+}
+
+/// Restores the task priority level that was current before [`raise_tpl()`] when dropped.
+///
+/// Guards must be dropped in the reverse order of their creation.
+///
+/// [`raise_tpl()`]: ./struct.EFI_BOOT_SERVICES.html#method.raise_tpl
+#[must_use = "the previous TPL is restored as soon as the guard is dropped"]
+pub struct TplGuard<'a> {
+    boot_services: &'a EFI_BOOT_SERVICES,
+    old: EFI_TPL,
+}
+
+impl TplGuard<'_> {
+    /// Returns the task priority level restored when the guard is dropped.
+    pub fn old_tpl(&self) -> EFI_TPL {
+        self.old
+    }
+}
+
+impl Drop for TplGuard<'_> {
+    fn drop(&mut self) {
+        // SAFETY: the level was current when the guard was created, so it is not above the
+        // current one.
+        unsafe { self.boot_services.RestoreTPL(self.old) }
+    }
+}
+
+/// A mutual exclusion primitive for data shared between normal code and notification functions.
+///
+/// Locking raises the TPL to the level of the mutex, which must be at least the notification TPL
+/// of every event whose notification function accesses the data, so that those functions cannot
+/// preempt the holder of the lock. Locking fails instead of deadlocking if the mutex is already
+/// held by the code that was preempted, or if the current TPL is above the level of the mutex.
+pub struct TplMutex<'a, T> {
+    boot_services: &'a EFI_BOOT_SERVICES,
+    tpl: Tpl,
+    locked: Cell<bool>,
+    value: UnsafeCell<T>,
+}
+
+impl<'a, T> TplMutex<'a, T> {
+    /// Creates a mutex protecting [`Value`] at the task priority level [`Tpl`].
+    ///
+    /// # Safety
+    ///
+    /// [`BootServices`] must be the boot services table of the running firmware.
+    ///
+    /// [`Value`]: ./struct.TplMutex.html#method.new
+    /// [`Tpl`]: ./struct.TplMutex.html#method.new
+    /// [`BootServices`]: ./struct.TplMutex.html#method.new
+    pub unsafe fn new(BootServices: &'a EFI_BOOT_SERVICES, Tpl: Tpl, Value: T) -> Self {
+        Self {
+            boot_services: BootServices,
+            tpl: Tpl,
+            locked: Cell::new(false),
+            value: UnsafeCell::new(Value),
+        }
+    }
+
+    /// Returns the task priority level of the mutex.
+    pub fn tpl(&self) -> Tpl {
+        self.tpl
+    }
+
+    /// Acquires the mutex, raising the TPL until the returned guard is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex cannot be acquired. See [`try_lock()`] for a non-panicking variant.
+    ///
+    /// [`try_lock()`]: ./struct.TplMutex.html#method.try_lock
+    pub fn lock(&self) -> TplMutexGuard<'_, 'a, T> {
+        self.try_lock()
+            .expect("TplMutex locked re-entrantly or above its TPL")
+    }
+
+    /// Attempts to acquire the mutex, raising the TPL until the returned guard is dropped.
+    ///
+    /// Returns [`None`] if the mutex is held by code preempted by the caller, or if the current TPL
+    /// is above the level of the mutex.
+    pub fn try_lock(&self) -> Option<TplMutexGuard<'_, 'a, T>> {
+        // SAFETY: the boot services table is valid per the contract of `new`. Raising to
+        // `TPL_HIGH_LEVEL` is always allowed, and no notification function can run while the
+        // state of the mutex is checked.
+        unsafe {
+            let old = self.boot_services.RaiseTPL(TPL_HIGH_LEVEL);
+            if self.locked.get() || old > self.tpl.as_raw() {
+                self.boot_services.RestoreTPL(old);
+                return None;
+            }
+
+            self.locked.set(true);
+            self.boot_services.RestoreTPL(self.tpl.as_raw());
+
+            Some(TplMutexGuard { mutex: self, old })
+        }
+    }
+
+    /// Returns a mutable reference to the protected value. No locking is needed since the mutex
+    /// is borrowed mutably.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    /// Consumes the mutex, returning the protected value.
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+/// Grants access to the value protected by a [`TplMutex`], releasing the mutex and restoring the
+/// previous task priority level when dropped.
+///
+/// [`TplMutex`]: crate::tables::boot_services::TplMutex
+#[must_use = "the mutex is released as soon as the guard is dropped"]
+pub struct TplMutexGuard<'m, 'a, T> {
+    mutex: &'m TplMutex<'a, T>,
+    old: EFI_TPL,
+}
+
+impl<T> Deref for TplMutexGuard<'_, '_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the guard holds the mutex.
+        unsafe { &*self.mutex.value.get() }
+    }
+}
+
+impl<T> DerefMut for TplMutexGuard<'_, '_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: the guard holds the mutex.
+        unsafe { &mut *self.mutex.value.get() }
+    }
+}
+
+impl<T> Drop for TplMutexGuard<'_, '_, T> {
+    fn drop(&mut self) {
+        self.mutex.locked.set(false);
+
+        // SAFETY: the level was current when the mutex was acquired, so it is not above the
+        // current one. Notification functions queued while the mutex was held run here, after it
+        // has been released.
+        unsafe { self.mutex.boot_services.RestoreTPL(self.old) }
+    }
+}
+
+extern "efiapi" fn notify_trampoline<F: FnMut(EFI_EVENT)>(Event: EFI_EVENT, Context: *mut VOID) {
+    // SAFETY: the context was created from a `&mut F` or `Box<F>` that outlives the event, and the
+    // firmware never runs the notification function of an event concurrently with itself.
+    let notify = unsafe { &mut *Context.cast::<F>() };
+    notify(Event);
+}
+
+#[cfg(feature = "alloc")]
+unsafe fn drop_boxed<F>(Context: *mut VOID) {
+    drop(Box::from_raw(Context.cast::<F>()));
+}
+
+/// The type of an event, as a combination of the `EVT_*` constants.
+pub type EFI_EVENT_TYPE = UINT32;
+
+/// The event is a timer event and may be passed to [`SetTimer()`].
+///
+/// [`SetTimer()`]: ./struct.EFI_BOOT_SERVICES.html#method.SetTimer
+pub const EVT_TIMER: EFI_EVENT_TYPE = 0x80000000;
+/// The event is allocated from runtime memory.
+pub const EVT_RUNTIME: EFI_EVENT_TYPE = 0x40000000;
+/// The event’s notification function is queued whenever the event is being waited on or checked.
+pub const EVT_NOTIFY_WAIT: EFI_EVENT_TYPE = 0x00000100;
+/// The event’s notification function is queued whenever the event is signaled.
+pub const EVT_NOTIFY_SIGNAL: EFI_EVENT_TYPE = 0x00000200;
+/// The event is signaled when [`ExitBootServices()`] is invoked.
+///
+/// [`ExitBootServices()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-exitbootservices
+pub const EVT_SIGNAL_EXIT_BOOT_SERVICES: EFI_EVENT_TYPE = 0x00000201;
+/// The event is signaled when [`SetVirtualAddressMap()`] is invoked.
+///
+/// [`SetVirtualAddressMap()`]: https://uefi.org/specs/UEFI/2.10/08_Services_Runtime_Services.html#setvirtualaddressmap
+pub const EVT_SIGNAL_VIRTUAL_ADDRESS_CHANGE: EFI_EVENT_TYPE = 0x60000202;
+
+/// The priority level of normal execution.
+pub const TPL_APPLICATION: EFI_TPL = 4;
+/// The priority level of most notification functions and of long-running operations.
+pub const TPL_CALLBACK: EFI_TPL = 8;
+/// The priority level of notification functions that must run with minimal latency.
+pub const TPL_NOTIFY: EFI_TPL = 16;
+/// The priority level at which interrupts are disabled.
+pub const TPL_HIGH_LEVEL: EFI_TPL = 31;
+
+/// The task priority levels that may be used outside of the firmware.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[repr(usize)]
+pub enum Tpl {
+    /// [`TPL_APPLICATION`].
+    ///
+    /// [`TPL_APPLICATION`]: crate::tables::boot_services::TPL_APPLICATION
+    Application = TPL_APPLICATION,
+    /// [`TPL_CALLBACK`].
+    ///
+    /// [`TPL_CALLBACK`]: crate::tables::boot_services::TPL_CALLBACK
+    Callback = TPL_CALLBACK,
+    /// [`TPL_NOTIFY`].
+    ///
+    /// [`TPL_NOTIFY`]: crate::tables::boot_services::TPL_NOTIFY
+    Notify = TPL_NOTIFY,
+    /// [`TPL_HIGH_LEVEL`].
+    ///
+    /// [`TPL_HIGH_LEVEL`]: crate::tables::boot_services::TPL_HIGH_LEVEL
+    HighLevel = TPL_HIGH_LEVEL,
+}
+
+impl Tpl {
+    /// Returns the [`Tpl`] corresponding to a raw task priority level, if it is one of the levels
+    /// exposed by the specification.
+    ///
+    /// [`Tpl`]: crate::tables::boot_services::Tpl
+    pub const fn from_raw(Tpl: EFI_TPL) -> Option<Self> {
+        match Tpl {
+            TPL_APPLICATION => Some(Self::Application),
+            TPL_CALLBACK => Some(Self::Callback),
+            TPL_NOTIFY => Some(Self::Notify),
+            TPL_HIGH_LEVEL => Some(Self::HighLevel),
+            _ => None,
+        }
+    }
+
+    /// Returns the raw task priority level.
+    pub const fn as_raw(self) -> EFI_TPL {
+        self as EFI_TPL
+    }
+}
+
+/// The type of time passed to [`SetTimer()`].
+///
+/// [`SetTimer()`]: ./struct.EFI_BOOT_SERVICES.html#method.SetTimer
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub enum EFI_TIMER_DELAY {
+    /// The event’s timer setting is to be cancelled and no timer trigger is to be set.
+    TimerCancel,
+    /// The event is to be signaled periodically at the specified trigger time interval.
+    TimerPeriodic,
+    /// The event is to be signaled once, after the specified trigger time has elapsed.
+    TimerRelative,
+}
+
+/// Used by [`OpenProtocol()`] to get the protocol interface, as [`HandleProtocol()`] does. The
+/// protocol is not tracked by the firmware as being used by a driver.
+///
+/// [`OpenProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.OpenProtocol
+/// [`HandleProtocol()`]: ./struct.EFI_BOOT_SERVICES.html#method.HandleProtocol
+pub const EFI_OPEN_PROTOCOL_BY_HANDLE_PROTOCOL: UINT32 = 0x00000001;
+/// Used by a driver to get a protocol interface from a handle. Care must be taken when using this
+/// open mode because the driver that opens a protocol interface in this manner will not be
+/// informed if the protocol interface is uninstalled or reinstalled.
+pub const EFI_OPEN_PROTOCOL_GET_PROTOCOL: UINT32 = 0x00000002;
+/// Used by a driver to test for the existence of a protocol interface on a handle. The interface
+/// pointer is not returned.
+pub const EFI_OPEN_PROTOCOL_TEST_PROTOCOL: UINT32 = 0x00000004;
+/// Used by bus drivers to show that a protocol interface is being used by one of the child
+/// controllers of a bus.
+pub const EFI_OPEN_PROTOCOL_BY_CHILD_CONTROLLER: UINT32 = 0x00000008;
+/// Used by a driver to gain access to a protocol interface. When this mode is used, the driver’s
+/// `Stop()` function will be called if the protocol interface is reinstalled or uninstalled.
+pub const EFI_OPEN_PROTOCOL_BY_DRIVER: UINT32 = 0x00000010;
+/// Used by applications to gain exclusive access to a protocol interface. If any drivers have the
+/// protocol interface opened with an attribute of [`EFI_OPEN_PROTOCOL_BY_DRIVER`], then an
+/// attempt will be made to remove them by calling the driver’s `Stop()` function.
+///
+/// [`EFI_OPEN_PROTOCOL_BY_DRIVER`]: crate::tables::boot_services::EFI_OPEN_PROTOCOL_BY_DRIVER
+pub const EFI_OPEN_PROTOCOL_EXCLUSIVE: UINT32 = 0x00000020;
+
+/// Specifies which handles are returned by [`LocateHandle()`] and [`LocateHandleBuffer()`].
+///
+/// [`LocateHandle()`]: ./struct.EFI_BOOT_SERVICES.html#method.LocateHandle
+/// [`LocateHandleBuffer()`]: ./struct.EFI_BOOT_SERVICES.html#method.LocateHandleBuffer
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub enum EFI_LOCATE_SEARCH_TYPE {
+    /// Every handle in the system is returned.
+    AllHandles,
+    /// The next handle that is new for the registration key is returned.
+    ByRegisterNotify,
+    /// All handles that support the protocol are returned.
+    ByProtocol,
+}
+
+/// An entry of the open list of a protocol interface, returned by [`OpenProtocolInformation()`].
+///
+/// [`OpenProtocolInformation()`]: ./struct.EFI_BOOT_SERVICES.html#method.OpenProtocolInformation
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_OPEN_PROTOCOL_INFORMATION_ENTRY {
+    /// The handle of the agent that opened the protocol interface.
+    pub AgentHandle: EFI_HANDLE,
+    /// The controller handle the protocol interface was opened for, if any.
+    pub ControllerHandle: EFI_HANDLE,
+    /// The `EFI_OPEN_PROTOCOL_*` attributes the protocol interface was opened with.
+    pub Attributes: UINT32,
+    /// The number of times the protocol interface was opened by the agent with these attributes.
+    pub OpenCount: UINT32,
+}
+
+/// Signaled when [`ExitBootServices()`] is invoked, after [`EFI_EVENT_GROUP_BEFORE_EXIT_BOOT_SERVICES`].
+///
+/// [`ExitBootServices()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-exitbootservices
+/// [`EFI_EVENT_GROUP_BEFORE_EXIT_BOOT_SERVICES`]: crate::tables::boot_services::EFI_EVENT_GROUP_BEFORE_EXIT_BOOT_SERVICES
+pub const EFI_EVENT_GROUP_EXIT_BOOT_SERVICES: EFI_GUID = EFI_GUID(
+    0x27ABF055,
+    0xB1B8,
+    0x4C26,
+    [0x80, 0x48, 0x74, 0x8F, 0x37, 0xBA, 0xA2, 0xDF],
+);
+
+/// Signaled when [`ExitBootServices()`] is invoked, before the memory map is checked.
+///
+/// [`ExitBootServices()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-exitbootservices
+pub const EFI_EVENT_GROUP_BEFORE_EXIT_BOOT_SERVICES: EFI_GUID = EFI_GUID(
+    0x8BE0E274,
+    0x3970,
+    0x4B44,
+    [0x80, 0xC5, 0x1A, 0xB9, 0x50, 0x2F, 0x3B, 0xFC],
+);
+
+/// Signaled when [`SetVirtualAddressMap()`] is invoked.
+///
+/// [`SetVirtualAddressMap()`]: https://uefi.org/specs/UEFI/2.10/08_Services_Runtime_Services.html#setvirtualaddressmap
+pub const EFI_EVENT_GROUP_VIRTUAL_ADDRESS_CHANGE: EFI_GUID = EFI_GUID(
+    0x13FA7698,
+    0xC831,
+    0x49C7,
+    [0x87, 0xEA, 0x8F, 0x43, 0xFC, 0xC2, 0x51, 0x96],
+);
+
+/// Signaled whenever the memory map changes.
+pub const EFI_EVENT_GROUP_MEMORY_MAP_CHANGE: EFI_GUID = EFI_GUID(
+    0x78BEE926,
+    0x692F,
+    0x48FD,
+    [0x9E, 0xDB, 0x01, 0x42, 0x2E, 0xF0, 0xD7, 0xAB],
+);
+
+/// Signaled when the boot manager is about to load and execute a boot option.
+pub const EFI_EVENT_GROUP_READY_TO_BOOT: EFI_GUID = EFI_GUID(
+    0x7CE88FB3,
+    0x4BD7,
+    0x4679,
+    [0x87, 0xA8, 0xA8, 0xD8, 0xDE, 0xE5, 0x0D, 0x2B],
+);
+
+/// Signaled right after [`EFI_EVENT_GROUP_READY_TO_BOOT`].
+///
+/// [`EFI_EVENT_GROUP_READY_TO_BOOT`]: crate::tables::boot_services::EFI_EVENT_GROUP_READY_TO_BOOT
+pub const EFI_EVENT_GROUP_AFTER_READY_TO_BOOT: EFI_GUID = EFI_GUID(
+    0x3A2A00AD,
+    0x98B9,
+    0x4CDF,
+    [0xA4, 0x78, 0x70, 0x27, 0x77, 0xF1, 0xC1, 0x0B],
+);
+
+/// Signaled when [`ResetSystem()`] is invoked, before the platform is reset.
+///
+/// [`ResetSystem()`]: https://uefi.org/specs/UEFI/2.10/08_Services_Runtime_Services.html#resetsystem
+pub const EFI_EVENT_GROUP_RESET_SYSTEM: EFI_GUID = EFI_GUID(
+    0x62DA6A56,
+    0x13FB,
+    0x485A,
+    [0xA8, 0xDA, 0xA3, 0xDD, 0x79, 0x12, 0xCB, 0x6B],
+);
+
+/// A notification function, called with the event and the context given when the event was
+/// created.
+pub type EFI_EVENT_NOTIFY = extern "efiapi" fn(Event: EFI_EVENT, Context: *mut VOID);
+
+type EFI_RAISE_TPL = extern "efiapi" fn(NewTpl: EFI_TPL) -> EFI_TPL;
+
+type EFI_RESTORE_TPL = extern "efiapi" fn(OldTpl: EFI_TPL);
+
+type EFI_CREATE_EVENT = extern "efiapi" fn(
+    Type: EFI_EVENT_TYPE,
+    NotifyTpl: EFI_TPL,
+    NotifyFunction: Option<EFI_EVENT_NOTIFY>,
+    NotifyContext: *mut VOID,
+    Event: *mut EFI_EVENT,
+) -> EFI_STATUS;
+
+type EFI_SET_TIMER =
+    extern "efiapi" fn(Event: EFI_EVENT, Type: EFI_TIMER_DELAY, TriggerTime: UINT64) -> EFI_STATUS;
+
+type EFI_WAIT_FOR_EVENT = extern "efiapi" fn(
+    NumberOfEvents: UINTN,
+    Event: *mut EFI_EVENT,
+    Index: *mut UINTN,
+) -> EFI_STATUS;
+
+type EFI_SIGNAL_EVENT = extern "efiapi" fn(Event: EFI_EVENT) -> EFI_STATUS;
+
+type EFI_CLOSE_EVENT = extern "efiapi" fn(Event: EFI_EVENT) -> EFI_STATUS;
+
+type EFI_CHECK_EVENT = extern "efiapi" fn(Event: EFI_EVENT) -> EFI_STATUS;
+
+type EFI_CREATE_EVENT_EX = extern "efiapi" fn(
+    Type: EFI_EVENT_TYPE,
+    NotifyTpl: EFI_TPL,
+    NotifyFunction: Option<EFI_EVENT_NOTIFY>,
+    NotifyContext: *const VOID,
+    EventGroup: *const EFI_GUID,
+    Event: *mut EFI_EVENT,
+) -> EFI_STATUS;
+
+type EFI_FREE_POOL = extern "efiapi" fn(Buffer: *mut VOID) -> EFI_STATUS;
+
+type EFI_HANDLE_PROTOCOL = extern "efiapi" fn(
+    Handle: EFI_HANDLE,
+    Protocol: *const EFI_GUID,
+    Interface: *mut *mut VOID,
+) -> EFI_STATUS;
+
+type EFI_LOCATE_HANDLE = extern "efiapi" fn(
+    SearchType: EFI_LOCATE_SEARCH_TYPE,
+    Protocol: *const EFI_GUID,
+    SearchKey: *mut VOID,
+    BufferSize: *mut UINTN,
+    Buffer: *mut EFI_HANDLE,
+) -> EFI_STATUS;
+
+type EFI_LOCATE_DEVICE_PATH = extern "efiapi" fn(
+    Protocol: *const EFI_GUID,
+    DevicePath: *mut *mut EFI_DEVICE_PATH_PROTOCOL,
+    Device: *mut EFI_HANDLE,
+) -> EFI_STATUS;
+
+type EFI_OPEN_PROTOCOL = extern "efiapi" fn(
+    Handle: EFI_HANDLE,
+    Protocol: *const EFI_GUID,
+    Interface: *mut *mut VOID,
+    AgentHandle: EFI_HANDLE,
+    ControllerHandle: EFI_HANDLE,
+    Attributes: UINT32,
+) -> EFI_STATUS;
+
+type EFI_CLOSE_PROTOCOL = extern "efiapi" fn(
+    Handle: EFI_HANDLE,
+    Protocol: *const EFI_GUID,
+    AgentHandle: EFI_HANDLE,
+    ControllerHandle: EFI_HANDLE,
+) -> EFI_STATUS;
+
+type EFI_OPEN_PROTOCOL_INFORMATION = extern "efiapi" fn(
+    Handle: EFI_HANDLE,
+    Protocol: *const EFI_GUID,
+    EntryBuffer: *mut *mut EFI_OPEN_PROTOCOL_INFORMATION_ENTRY,
+    EntryCount: *mut UINTN,
+) -> EFI_STATUS;
+
+type EFI_PROTOCOLS_PER_HANDLE = extern "efiapi" fn(
+    Handle: EFI_HANDLE,
+    ProtocolBuffer: *mut *mut *mut EFI_GUID,
+    ProtocolBufferCount: *mut UINTN,
+) -> EFI_STATUS;
+
+type EFI_LOCATE_HANDLE_BUFFER = extern "efiapi" fn(
+    SearchType: EFI_LOCATE_SEARCH_TYPE,
+    Protocol: *const EFI_GUID,
+    SearchKey: *mut VOID,
+    NoHandles: *mut UINTN,
+    Buffer: *mut *mut EFI_HANDLE,
+) -> EFI_STATUS;
+
+type EFI_LOCATE_PROTOCOL = extern "efiapi" fn(
+    Protocol: *const EFI_GUID,
+    Registration: *mut VOID,
+    Interface: *mut *mut VOID,
+) -> EFI_STATUS;
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::cell::{Cell, RefCell};
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::status::EFI_UNSUPPORTED;
+
+    std::thread_local! {
+        static CURRENT: Cell<EFI_TPL> = const { Cell::new(TPL_APPLICATION) };
+        // The levels passed to `RestoreTPL()`, in order.
+        static RESTORED: RefCell<Vec<EFI_TPL>> = const { RefCell::new(Vec::new()) };
+    }
+
+    extern "efiapi" fn raise_tpl(NewTpl: EFI_TPL) -> EFI_TPL {
+        let old = CURRENT.with(Cell::get);
+        assert!(NewTpl >= old, "RaiseTPL() to a lower level");
+        CURRENT.with(|current| current.set(NewTpl));
+        old
+    }
+
+    extern "efiapi" fn restore_tpl(OldTpl: EFI_TPL) {
+        assert!(
+            OldTpl <= CURRENT.with(Cell::get),
+            "RestoreTPL() to a higher level"
+        );
+        CURRENT.with(|current| current.set(OldTpl));
+        RESTORED.with(|restored| restored.borrow_mut().push(OldTpl));
+    }
+
+    extern "efiapi" fn unsupported() -> EFI_STATUS {
+        EFI_UNSUPPORTED
+    }
+
+    const SLOTS: usize = core::mem::size_of::<EFI_BOOT_SERVICES>() / core::mem::size_of::<usize>();
+
+    /// Builds a boot services table tracking the current TPL, with every service other than
+    /// `RaiseTPL()` and `RestoreTPL()` unsupported.
+    fn boot_services() -> EFI_BOOT_SERVICES {
+        let mut table = [unsupported as *const () as usize; SLOTS];
+        table[..3].fill(0);
+        table[3] = raise_tpl as *const () as usize;
+        table[4] = restore_tpl as *const () as usize;
+        // SAFETY: the table holds the header and function pointers of the boot services.
+        unsafe { core::mem::transmute(table) }
+    }
+
+    fn current() -> EFI_TPL {
+        CURRENT.with(Cell::get)
+    }
+
+    fn restored() -> Vec<EFI_TPL> {
+        RESTORED.with(|restored| core::mem::take(&mut *restored.borrow_mut()))
+    }
+
+    #[test]
+    fn raise_tpl_restores_the_previous_level() {
+        let boot_services = boot_services();
+
+        let callback = unsafe { boot_services.raise_tpl(Tpl::Callback) };
+        assert_eq!(callback.old_tpl(), TPL_APPLICATION);
+        assert_eq!(current(), TPL_CALLBACK);
+
+        // A lower level leaves the TPL unchanged.
+        let application = unsafe { boot_services.raise_tpl(Tpl::Application) };
+        assert_eq!(application.old_tpl(), TPL_CALLBACK);
+        assert_eq!(current(), TPL_CALLBACK);
+
+        let notify = unsafe { boot_services.raise_tpl(Tpl::Notify) };
+        assert_eq!(current(), TPL_NOTIFY);
+        assert_eq!(restored(), [TPL_CALLBACK, TPL_CALLBACK, TPL_NOTIFY]);
+
+        drop(notify);
+        assert_eq!(current(), TPL_CALLBACK);
+        drop(application);
+        assert_eq!(current(), TPL_CALLBACK);
+        drop(callback);
+        assert_eq!(current(), TPL_APPLICATION);
+        assert_eq!(restored(), [TPL_CALLBACK, TPL_CALLBACK, TPL_APPLICATION]);
+    }
+
+    #[test]
+    fn tpl_mutex_raises_the_tpl_while_locked() {
+        let boot_services = boot_services();
+        let mutex = unsafe { TplMutex::new(&boot_services, Tpl::Notify, 1) };
+
+        let mut guard = mutex.lock();
+        assert_eq!(current(), TPL_NOTIFY);
+        *guard += 1;
+
+        // A notification function preempting the holder cannot lock the mutex again.
+        assert!(mutex.try_lock().is_none());
+        assert_eq!(current(), TPL_NOTIFY);
+        assert_eq!(restored(), [TPL_NOTIFY, TPL_NOTIFY]);
+
+        drop(guard);
+        assert_eq!(current(), TPL_APPLICATION);
+        assert_eq!(restored(), [TPL_APPLICATION]);
+
+        // The mutex can be locked again once released, including at its own level.
+        let notify = unsafe { boot_services.raise_tpl(Tpl::Notify) };
+        assert_eq!(*mutex.try_lock().unwrap(), 2);
+        assert_eq!(current(), TPL_NOTIFY);
+        drop(notify);
+        assert_eq!(mutex.into_inner(), 2);
+    }
+
+    #[test]
+    fn tpl_mutex_cannot_be_locked_above_its_level() {
+        let boot_services = boot_services();
+        let mut mutex = unsafe { TplMutex::new(&boot_services, Tpl::Callback, 0) };
+
+        let notify = unsafe { boot_services.raise_tpl(Tpl::Notify) };
+        restored();
+        assert!(mutex.try_lock().is_none());
+        assert_eq!(current(), TPL_NOTIFY);
+        assert_eq!(restored(), [TPL_NOTIFY]);
+        drop(notify);
+
+        let guard = mutex.try_lock().unwrap();
+        assert_eq!(current(), TPL_CALLBACK);
+        drop(guard);
+        assert_eq!(current(), TPL_APPLICATION);
+        assert_eq!(*mutex.get_mut(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "TplMutex locked re-entrantly or above its TPL")]
+    fn tpl_mutex_lock_panics_when_reentered() {
+        let boot_services = boot_services();
+        let mutex = unsafe { TplMutex::new(&boot_services, Tpl::Callback, ()) };
+
+        let _guard = mutex.lock();
+        let _reentered = mutex.lock();
+    }
+}
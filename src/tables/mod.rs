@@ -34,6 +34,7 @@
 
 use crate::types::{UINT32, UINT64};
 
+pub mod boot_services;
 pub mod runtime_services;
 pub mod system;
 
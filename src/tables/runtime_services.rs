@@ -25,10 +25,39 @@
 //! loader and OS have taken control of the platform with a call to [`EFI_BOOT_SERVICES.ExitBootServices()`].
 //! If a call to [`SetVirtualAddressMap()`] is made by the OS, then the function pointers in this
 //! table are fixed up to point to the new virtually mapped entry points.
+//!
+//! ## Variable Services
+//!
+//! Variables are defined as key/value pairs that consist of identifying information plus
+//! attributes (the key) and arbitrary data (the value). Variables are intended for use as a means
+//! to store data that is passed between the EFI environment implemented in the platform and EFI
+//! OS loaders and other applications that run in the EFI environment. See
+//! [Section 8.2 of the UEFI Specification] for more details.
+//!
+//! Variables are accessed through [`GetVariable()`], [`GetNextVariableName()`], [`SetVariable()`]
+//! and [`QueryVariableInfo()`]. The architecturally defined variables all live in the
+//! [`EFI_GLOBAL_VARIABLE`] namespace, and their names are provided in this module as
+//! null-terminated UCS-2 constants, such as [`EFI_BOOT_ORDER_VARIABLE_NAME`].
+//!
+//! [`GetVariable()`]: ./struct.EFI_RUNTIME_SERVICES.html#method.GetVariable
+//! [`GetNextVariableName()`]: ./struct.EFI_RUNTIME_SERVICES.html#method.GetNextVariableName
+//! [`SetVariable()`]: ./struct.EFI_RUNTIME_SERVICES.html#method.SetVariable
+//! [`QueryVariableInfo()`]: ./struct.EFI_RUNTIME_SERVICES.html#method.QueryVariableInfo
+//! [`EFI_GLOBAL_VARIABLE`]: crate::tables::runtime_services::EFI_GLOBAL_VARIABLE
+//! [`EFI_BOOT_ORDER_VARIABLE_NAME`]: crate::tables::runtime_services::EFI_BOOT_ORDER_VARIABLE_NAME
+//!
+//! [Section 8.2 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/08_Services_Runtime_Services.html#variable-services
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::mem;
 
+#[cfg(feature = "alloc")]
+use crate::status::{EFI_BUFFER_TOO_SMALL, EFI_NOT_FOUND, EFI_SUCCESS};
 use crate::tables::EFI_TABLE_HEADER;
 use crate::tables::system::EFI_SPECIFICATION_VERSION;
-use crate::types::{BOOLEAN, EFI_STATUS, UINT16, UINT32, UINT64, UINT8};
+use crate::types::{BOOLEAN, CHAR16, EFI_GUID, EFI_STATUS, UINT16, UINT32, UINT64, UINT8, UINTN, VOID};
 
 /// Signature for the EFI Runtime Services Table.
 pub const EFI_RUNTIME_SERVICES_SIGNATURE: UINT64 = 0x56524553544e5552;
@@ -52,6 +81,23 @@ pub struct EFI_RUNTIME_SERVICES {
 
     GetTime: EFI_GET_TIME,
     SetTime: EFI_SET_TIME,
+    GetWakeupTime: EFI_GET_WAKEUP_TIME,
+    SetWakeupTime: EFI_SET_WAKEUP_TIME,
+
+    SetVirtualAddressMap: EFI_SET_VIRTUAL_ADDRESS_MAP,
+    ConvertPointer: EFI_CONVERT_POINTER,
+
+    GetVariable: EFI_GET_VARIABLE,
+    GetNextVariableName: EFI_GET_NEXT_VARIABLE_NAME,
+    SetVariable: EFI_SET_VARIABLE,
+
+    GetNextHighMonotonicCount: EFI_GET_NEXT_HIGH_MONO_COUNT,
+    ResetSystem: EFI_RESET_SYSTEM,
+
+    UpdateCapsule: EFI_UPDATE_CAPSULE,
+    QueryCapsuleCapabilities: EFI_QUERY_CAPSULE_CAPABILITIES,
+
+    QueryVariableInfo: EFI_QUERY_VARIABLE_INFO,
 }
 
 impl EFI_RUNTIME_SERVICES {
@@ -88,6 +134,429 @@ impl EFI_RUNTIME_SERVICES {
     pub unsafe fn GetTime(&self, Time: *mut EFI_TIME, Capabilities: *mut EFI_TIME_CAPABILITIES) -> EFI_STATUS {
         (self.GetTime)(Time, Capabilities)
     }
+
+    /// Returns the value of a variable.
+    ///
+    /// Each vendor may create and manage its own variables without the risk of name conflicts by
+    /// using a unique [`VendorGuid`]. When a variable is set its [`Attributes`] are supplied to
+    /// indicate how the data variable should be stored and maintained by the system. The
+    /// attributes affect when the variable may be accessed and volatility of the data.
+    ///
+    /// If the [`Data`] buffer is too small to hold the contents of the variable, the error
+    /// [`EFI_BUFFER_TOO_SMALL`] is returned and [`DataSize`] is set to the required buffer size to
+    /// obtain the data.
+    ///
+    /// # Parameters
+    ///
+    /// ## `VariableName`
+    ///
+    /// A null-terminated string that is the name of the vendor’s variable.
+    ///
+    /// ## `VendorGuid`
+    ///
+    /// A unique identifier for the vendor.
+    ///
+    /// ## `Attributes`
+    ///
+    /// If not null, a pointer to the memory location to return the attributes bitmask for the
+    /// variable. See [`EFI_VARIABLE_ATTRIBUTES`].
+    ///
+    /// ## `DataSize`
+    ///
+    /// On input, the size in bytes of the return [`Data`] buffer. On output the size of data
+    /// returned in [`Data`].
+    ///
+    /// ## `Data`
+    ///
+    /// The buffer to return the contents of the variable. May be null with a zero [`DataSize`] in
+    /// order to determine the size buffer needed.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the function completed successfully.
+    ///
+    /// [`EFI_NOT_FOUND`] - the variable was not found.
+    ///
+    /// [`EFI_BUFFER_TOO_SMALL`] - the [`DataSize`] is too small for the result. [`DataSize`] has
+    /// been updated with the size needed to complete the request.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - one of the pointer parameters is null, or [`DataSize`] is not
+    /// zero and [`Data`] is null.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the variable could not be retrieved due to a hardware error.
+    ///
+    /// [`EFI_SECURITY_VIOLATION`] - the variable could not be retrieved due to an authentication
+    /// failure.
+    ///
+    /// [`EFI_UNSUPPORTED`] - after [`ExitBootServices()`] has been called, this return code may be
+    /// returned if no variable storage is supported.
+    ///
+    /// [`VendorGuid`]: ./struct.EFI_RUNTIME_SERVICES.html#vendorguid
+    /// [`Attributes`]: ./struct.EFI_RUNTIME_SERVICES.html#attributes
+    /// [`Data`]: ./struct.EFI_RUNTIME_SERVICES.html#data
+    /// [`DataSize`]: ./struct.EFI_RUNTIME_SERVICES.html#datasize
+    /// [`EFI_VARIABLE_ATTRIBUTES`]: crate::tables::runtime_services::EFI_VARIABLE_ATTRIBUTES
+    /// [`ExitBootServices()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-exitbootservices
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_NOT_FOUND`]: crate::status::EFI_NOT_FOUND
+    /// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_SECURITY_VIOLATION`]: crate::status::EFI_SECURITY_VIOLATION
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    pub unsafe fn GetVariable(
+        &self,
+        VariableName: *const CHAR16,
+        VendorGuid: *const EFI_GUID,
+        Attributes: *mut EFI_VARIABLE_ATTRIBUTES,
+        DataSize: *mut UINTN,
+        Data: *mut VOID,
+    ) -> EFI_STATUS {
+        (self.GetVariable)(VariableName, VendorGuid, Attributes, DataSize, Data)
+    }
+
+    /// Enumerates the current variable names.
+    ///
+    /// [`GetNextVariableName()`] is called multiple times to retrieve the [`VariableName`] and
+    /// [`VendorGuid`] of all variables currently available in the system. On each call, the
+    /// previous results are passed into the interface, and, on output, the interface returns the
+    /// next variable name data. When the entire variable list has been returned, the error
+    /// [`EFI_NOT_FOUND`] is returned.
+    ///
+    /// To start the search, a null-terminated string is passed in [`VariableName`]; that is,
+    /// [`VariableName`] is a pointer to a null Unicode character. This is always done on the
+    /// initial call. When [`VariableName`] is a pointer to a null Unicode character,
+    /// [`VendorGuid`] is ignored.
+    ///
+    /// The buffer passed in [`VariableName`] must keep the previously returned name between calls.
+    /// When the buffer is too small, [`EFI_BUFFER_TOO_SMALL`] is returned and the contents of
+    /// [`VariableName`] are left untouched, so the caller may grow the buffer and retry.
+    ///
+    /// # Parameters
+    ///
+    /// ## `VariableNameSize`
+    ///
+    /// The size of the [`VariableName`] buffer, in bytes. On output, the size of the returned
+    /// [`VariableName`] including its terminating null character, in bytes.
+    ///
+    /// ## `VariableName`
+    ///
+    /// On input, supplies the last [`VariableName`] that was returned by [`GetNextVariableName()`].
+    /// On output, returns the null-terminated string of the current variable.
+    ///
+    /// ## `VendorGuid`
+    ///
+    /// On input, supplies the last [`VendorGuid`] that was returned by [`GetNextVariableName()`].
+    /// On output, returns the [`VendorGuid`] of the current variable.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the function completed successfully.
+    ///
+    /// [`EFI_NOT_FOUND`] - the next variable was not found.
+    ///
+    /// [`EFI_BUFFER_TOO_SMALL`] - the [`VariableNameSize`] is too small for the result.
+    /// [`VariableNameSize`] has been updated with the size needed to complete the request.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - one of the pointer parameters is null, or the input values of
+    /// [`VariableName`] and [`VendorGuid`] are not the name and GUID of an existing variable.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the variable name could not be retrieved due to a hardware error.
+    ///
+    /// [`EFI_UNSUPPORTED`] - after [`ExitBootServices()`] has been called, this return code may be
+    /// returned if no variable storage is supported.
+    ///
+    /// [`GetNextVariableName()`]: ./struct.EFI_RUNTIME_SERVICES.html#method.GetNextVariableName
+    /// [`VariableName`]: ./struct.EFI_RUNTIME_SERVICES.html#variablename-1
+    /// [`VariableNameSize`]: ./struct.EFI_RUNTIME_SERVICES.html#variablenamesize
+    /// [`VendorGuid`]: ./struct.EFI_RUNTIME_SERVICES.html#vendorguid-1
+    /// [`ExitBootServices()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-exitbootservices
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_NOT_FOUND`]: crate::status::EFI_NOT_FOUND
+    /// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    pub unsafe fn GetNextVariableName(
+        &self,
+        VariableNameSize: *mut UINTN,
+        VariableName: *mut CHAR16,
+        VendorGuid: *mut EFI_GUID,
+    ) -> EFI_STATUS {
+        (self.GetNextVariableName)(VariableNameSize, VariableName, VendorGuid)
+    }
+
+    /// Sets the value of a variable.
+    ///
+    /// Variables are stored by the firmware and may maintain their values across power cycles.
+    /// Each vendor may create and manage its own variables without the risk of name conflicts by
+    /// using a unique [`VendorGuid`].
+    ///
+    /// Unless the [`EFI_VARIABLE_APPEND_WRITE`], [`EFI_VARIABLE_TIME_BASED_AUTHENTICATED_WRITE_ACCESS`]
+    /// or [`EFI_VARIABLE_ENHANCED_AUTHENTICATED_ACCESS`] attribute is set, setting a data variable
+    /// with a [`DataSize`] of zero causes it to be deleted. Setting a data variable with no access
+    /// attributes also causes it to be deleted.
+    ///
+    /// # Parameters
+    ///
+    /// ## `VariableName`
+    ///
+    /// A null-terminated string that is the name of the vendor’s variable. Each [`VariableName`]
+    /// is unique for each [`VendorGuid`]. [`VariableName`] must contain 1 or more characters.
+    ///
+    /// ## `VendorGuid`
+    ///
+    /// A unique identifier for the vendor.
+    ///
+    /// ## `Attributes`
+    ///
+    /// Attributes bitmask to set for the variable. See [`EFI_VARIABLE_ATTRIBUTES`].
+    ///
+    /// ## `DataSize`
+    ///
+    /// The size in bytes of the [`Data`] buffer.
+    ///
+    /// ## `Data`
+    ///
+    /// The contents for the variable.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - the firmware has successfully stored the variable and its data as defined
+    /// by the [`Attributes`].
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - an invalid combination of attribute bits, name, and GUID was
+    /// supplied, or the [`DataSize`] exceeds the maximum allowed.
+    ///
+    /// [`EFI_OUT_OF_RESOURCES`] - not enough storage is available to hold the variable and its
+    /// data.
+    ///
+    /// [`EFI_DEVICE_ERROR`] - the variable could not be saved due to a hardware failure.
+    ///
+    /// [`EFI_WRITE_PROTECTED`] - the variable in question is read-only or cannot be deleted.
+    ///
+    /// [`EFI_SECURITY_VIOLATION`] - the variable could not be written due to an authentication
+    /// failure.
+    ///
+    /// [`EFI_NOT_FOUND`] - the variable trying to be updated or deleted was not found.
+    ///
+    /// [`EFI_UNSUPPORTED`] - this call is not supported by this platform at the time the call is
+    /// made.
+    ///
+    /// [`VariableName`]: ./struct.EFI_RUNTIME_SERVICES.html#variablename-2
+    /// [`VendorGuid`]: ./struct.EFI_RUNTIME_SERVICES.html#vendorguid-2
+    /// [`Attributes`]: ./struct.EFI_RUNTIME_SERVICES.html#attributes-1
+    /// [`DataSize`]: ./struct.EFI_RUNTIME_SERVICES.html#datasize-1
+    /// [`Data`]: ./struct.EFI_RUNTIME_SERVICES.html#data-1
+    /// [`EFI_VARIABLE_ATTRIBUTES`]: crate::tables::runtime_services::EFI_VARIABLE_ATTRIBUTES
+    /// [`EFI_VARIABLE_APPEND_WRITE`]: crate::tables::runtime_services::EFI_VARIABLE_APPEND_WRITE
+    /// [`EFI_VARIABLE_TIME_BASED_AUTHENTICATED_WRITE_ACCESS`]: crate::tables::runtime_services::EFI_VARIABLE_TIME_BASED_AUTHENTICATED_WRITE_ACCESS
+    /// [`EFI_VARIABLE_ENHANCED_AUTHENTICATED_ACCESS`]: crate::tables::runtime_services::EFI_VARIABLE_ENHANCED_AUTHENTICATED_ACCESS
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_OUT_OF_RESOURCES`]: crate::status::EFI_OUT_OF_RESOURCES
+    /// [`EFI_DEVICE_ERROR`]: crate::status::EFI_DEVICE_ERROR
+    /// [`EFI_WRITE_PROTECTED`]: crate::status::EFI_WRITE_PROTECTED
+    /// [`EFI_SECURITY_VIOLATION`]: crate::status::EFI_SECURITY_VIOLATION
+    /// [`EFI_NOT_FOUND`]: crate::status::EFI_NOT_FOUND
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    pub unsafe fn SetVariable(
+        &self,
+        VariableName: *const CHAR16,
+        VendorGuid: *const EFI_GUID,
+        Attributes: EFI_VARIABLE_ATTRIBUTES,
+        DataSize: UINTN,
+        Data: *const VOID,
+    ) -> EFI_STATUS {
+        (self.SetVariable)(VariableName, VendorGuid, Attributes, DataSize, Data)
+    }
+
+    /// Returns information about the EFI variables.
+    ///
+    /// The [`QueryVariableInfo()`] function allows a caller to obtain the information about the
+    /// maximum size of the storage space available for the EFI variables, the remaining size of
+    /// the storage space available for the EFI variables and the maximum size of each individual
+    /// EFI variable, associated with the attributes specified.
+    ///
+    /// # Parameters
+    ///
+    /// ## `Attributes`
+    ///
+    /// Attributes bitmask to specify the type of variables on which to return information. See
+    /// [`EFI_VARIABLE_ATTRIBUTES`].
+    ///
+    /// ## `MaximumVariableStorageSize`
+    ///
+    /// On output the maximum size of the storage space available for the EFI variables associated
+    /// with the attributes specified.
+    ///
+    /// ## `RemainingVariableStorageSize`
+    ///
+    /// Returns the remaining size of the storage space available for EFI variables associated
+    /// with the attributes specified.
+    ///
+    /// ## `MaximumVariableSize`
+    ///
+    /// Returns the maximum size of an individual EFI variable associated with the attributes
+    /// specified.
+    ///
+    /// # Status Codes Returned
+    ///
+    /// [`EFI_SUCCESS`] - valid answer returned.
+    ///
+    /// [`EFI_INVALID_PARAMETER`] - an invalid combination of attribute bits was supplied.
+    ///
+    /// [`EFI_UNSUPPORTED`] - the attribute is not supported on this platform, and the
+    /// `MaximumVariableStorageSize`, `RemainingVariableStorageSize` and `MaximumVariableSize` are
+    /// undefined.
+    ///
+    /// [`QueryVariableInfo()`]: ./struct.EFI_RUNTIME_SERVICES.html#method.QueryVariableInfo
+    /// [`EFI_VARIABLE_ATTRIBUTES`]: crate::tables::runtime_services::EFI_VARIABLE_ATTRIBUTES
+    /// [`EFI_SUCCESS`]: crate::status::EFI_SUCCESS
+    /// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+    /// [`EFI_UNSUPPORTED`]: crate::status::EFI_UNSUPPORTED
+    pub unsafe fn QueryVariableInfo(
+        &self,
+        Attributes: EFI_VARIABLE_ATTRIBUTES,
+        MaximumVariableStorageSize: *mut UINT64,
+        RemainingVariableStorageSize: *mut UINT64,
+        MaximumVariableSize: *mut UINT64,
+    ) -> EFI_STATUS {
+        (self.QueryVariableInfo)(
+            Attributes,
+            MaximumVariableStorageSize,
+            RemainingVariableStorageSize,
+            MaximumVariableSize,
+        )
+    }
+
+    /// Reads the whole contents of a variable into a newly allocated buffer.
+    ///
+    /// The buffer is grown as reported by [`GetVariable()`] until the contents fit, so the size of
+    /// the variable does not need to be known in advance. Returns the attributes of the variable
+    /// together with its contents.
+    ///
+    /// `VariableName` must be a null-terminated string.
+    ///
+    /// [`GetVariable()`]: ./struct.EFI_RUNTIME_SERVICES.html#method.GetVariable
+    #[cfg(feature = "alloc")]
+    pub unsafe fn get_variable(
+        &self,
+        VariableName: &[CHAR16],
+        VendorGuid: &EFI_GUID,
+    ) -> Result<(EFI_VARIABLE_ATTRIBUTES, Vec<UINT8>), EFI_STATUS> {
+        let mut data = Vec::<UINT8>::new();
+        loop {
+            let mut attributes = 0;
+            let mut size = data.len();
+            let status = self.GetVariable(
+                VariableName.as_ptr(),
+                VendorGuid,
+                &mut attributes,
+                &mut size,
+                data.as_mut_ptr().cast(),
+            );
+
+            match status {
+                EFI_SUCCESS => {
+                    data.truncate(size);
+                    return Ok((attributes, data));
+                }
+                EFI_BUFFER_TOO_SMALL => data.resize(size, 0),
+                _ => return Err(status),
+            }
+        }
+    }
+
+    /// Returns an iterator over the names and vendor GUIDs of every variable currently available
+    /// in the system.
+    ///
+    /// See [`VariableNames`] for more details.
+    ///
+    /// [`VariableNames`]: crate::tables::runtime_services::VariableNames
+    #[cfg(feature = "alloc")]
+    pub fn variable_names(&self) -> VariableNames<'_> {
+        VariableNames {
+            runtime_services: self,
+            name: Vec::from([0]),
+            guid: EFI_GUID(0, 0, 0, [0; 8]),
+            done: false,
+        }
+    }
+}
+
+/// An iterator over the variable names known to the firmware, driven by
+/// [`GetNextVariableName()`].
+///
+/// Each item is the null-terminated name of a variable together with its vendor GUID. The name
+/// buffer is grown whenever the firmware reports [`EFI_BUFFER_TOO_SMALL`], so variables with
+/// arbitrarily long names are returned in full. Iteration stops after the firmware reports
+/// [`EFI_NOT_FOUND`], or after the first error, which is yielded as the final item.
+///
+/// Creating or deleting variables while iterating is not supported by the firmware and may cause
+/// the iteration to end early with [`EFI_INVALID_PARAMETER`].
+///
+/// [`GetNextVariableName()`]: ./struct.EFI_RUNTIME_SERVICES.html#method.GetNextVariableName
+/// [`EFI_BUFFER_TOO_SMALL`]: crate::status::EFI_BUFFER_TOO_SMALL
+/// [`EFI_NOT_FOUND`]: crate::status::EFI_NOT_FOUND
+/// [`EFI_INVALID_PARAMETER`]: crate::status::EFI_INVALID_PARAMETER
+#[cfg(feature = "alloc")]
+pub struct VariableNames<'a> {
+    runtime_services: &'a EFI_RUNTIME_SERVICES,
+    name: Vec<CHAR16>,
+    guid: EFI_GUID,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for VariableNames<'_> {
+    type Item = Result<(Vec<CHAR16>, EFI_GUID), EFI_STATUS>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            let mut size = self.name.len() * mem::size_of::<CHAR16>();
+            let status = unsafe {
+                self.runtime_services.GetNextVariableName(
+                    &mut size,
+                    self.name.as_mut_ptr(),
+                    &mut self.guid,
+                )
+            };
+
+            match status {
+                EFI_SUCCESS => {
+                    let len = self
+                        .name
+                        .iter()
+                        .position(|&c| c == 0)
+                        .map_or(self.name.len(), |nul| nul + 1);
+
+                    return Some(Ok((self.name[..len].to_vec(), self.guid)));
+                }
+                EFI_BUFFER_TOO_SMALL => {
+                    let required = (size + mem::size_of::<CHAR16>() - 1) / mem::size_of::<CHAR16>();
+                    if required <= self.name.len() {
+                        self.done = true;
+                        return Some(Err(status));
+                    }
+
+                    self.name.resize(required, 0);
+                }
+                EFI_NOT_FOUND => {
+                    self.done = true;
+                    return None;
+                }
+                _ => {
+                    self.done = true;
+                    return Some(Err(status));
+                }
+            }
+        }
+    }
 }
 
 /// Represents current time information.
@@ -156,6 +625,122 @@ pub struct EFI_TIME_CAPABILITIES {
     pub SetsToZero: BOOLEAN,
 }
 
+/// Describes a single region of the memory map.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_MEMORY_DESCRIPTOR {
+    /// Type of the memory region, one of the [`EFI_MEMORY_TYPE`] values.
+    ///
+    /// [`EFI_MEMORY_TYPE`]: crate::tables::runtime_services::EFI_MEMORY_TYPE
+    pub Type: EFI_MEMORY_TYPE,
+    /// Physical address of the first byte in the memory region. Must be aligned on a 4 KiB
+    /// boundary.
+    pub PhysicalStart: UINT64,
+    /// Virtual address of the first byte in the memory region. Must be aligned on a 4 KiB
+    /// boundary.
+    pub VirtualStart: UINT64,
+    /// Number of 4 KiB pages in the memory region.
+    pub NumberOfPages: UINT64,
+    /// Attributes of the memory region that describe the bit mask of capabilities for that
+    /// memory region, and not necessarily the current settings for that memory region.
+    pub Attribute: UINT64,
+}
+
+/// The type of a region of memory.
+///
+/// Values from `0x70000000` to `0x7FFFFFFF` are reserved for OEM use, and values from
+/// `0x80000000` to `0xFFFFFFFF` are reserved for use by UEFI OS loaders.
+pub type EFI_MEMORY_TYPE = UINT32;
+
+/// Not usable.
+pub const EfiReservedMemoryType: EFI_MEMORY_TYPE = 0;
+/// The code portions of a loaded UEFI application.
+pub const EfiLoaderCode: EFI_MEMORY_TYPE = 1;
+/// The data portions of a loaded UEFI application and the default data allocation type used by
+/// a UEFI application to allocate pool memory.
+pub const EfiLoaderData: EFI_MEMORY_TYPE = 2;
+/// The code portions of a loaded UEFI Boot Service Driver.
+pub const EfiBootServicesCode: EFI_MEMORY_TYPE = 3;
+/// The data portions of a loaded UEFI Boot Service Driver, and the default data allocation type
+/// used by a UEFI Boot Service Driver to allocate pool memory.
+pub const EfiBootServicesData: EFI_MEMORY_TYPE = 4;
+/// The code portions of a loaded UEFI Runtime Driver.
+pub const EfiRuntimeServicesCode: EFI_MEMORY_TYPE = 5;
+/// The data portions of a loaded UEFI Runtime Driver and the default data allocation type used
+/// by a UEFI Runtime Driver to allocate pool memory.
+pub const EfiRuntimeServicesData: EFI_MEMORY_TYPE = 6;
+/// Free (unallocated) memory.
+pub const EfiConventionalMemory: EFI_MEMORY_TYPE = 7;
+/// Memory in which errors have been detected.
+pub const EfiUnusableMemory: EFI_MEMORY_TYPE = 8;
+/// Memory that holds the ACPI tables.
+pub const EfiACPIReclaimMemory: EFI_MEMORY_TYPE = 9;
+/// Address space reserved for use by the firmware.
+pub const EfiACPIMemoryNVS: EFI_MEMORY_TYPE = 10;
+/// Used by system firmware to request that a memory-mapped IO region be mapped by the OS to a
+/// virtual address so it can be accessed by EFI runtime services.
+pub const EfiMemoryMappedIO: EFI_MEMORY_TYPE = 11;
+/// System memory-mapped IO region that is used to translate memory cycles to IO cycles by the
+/// processor.
+pub const EfiMemoryMappedIOPortSpace: EFI_MEMORY_TYPE = 12;
+/// Address space reserved by the firmware for code that is part of the processor.
+pub const EfiPalCode: EFI_MEMORY_TYPE = 13;
+/// A memory region that operates as [`EfiConventionalMemory`], but also supports
+/// byte-addressable non-volatility.
+pub const EfiPersistentMemory: EFI_MEMORY_TYPE = 14;
+/// A memory region that represents unaccepted memory, that must be accepted by the boot target
+/// before it can be used.
+pub const EfiUnacceptedMemoryType: EFI_MEMORY_TYPE = 15;
+/// Upper bound of the memory types defined by the specification.
+pub const EfiMaxMemoryType: EFI_MEMORY_TYPE = 16;
+
+/// The header that precedes every capsule passed to [`UpdateCapsule()`].
+///
+/// [`UpdateCapsule()`]: https://uefi.org/specs/UEFI/2.10/08_Services_Runtime_Services.html#updatecapsule
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_CAPSULE_HEADER {
+    /// A GUID that defines the contents of a capsule.
+    pub CapsuleGuid: EFI_GUID,
+    /// The size of the capsule header.
+    pub HeaderSize: UINT32,
+    /// Bit-mapped list describing the capsule attributes.
+    pub Flags: UINT32,
+    /// Size in bytes of the capsule, including the capsule header.
+    pub CapsuleImageSize: UINT32,
+}
+
+/// The type of reset to perform.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub enum EFI_RESET_TYPE {
+    /// A system-wide reset, setting all circuitry within the system to its initial state.
+    EfiResetCold,
+    /// A system-wide initialization, with processors set to their initial state and pending
+    /// cycles not corrupted.
+    EfiResetWarm,
+    /// The system enters a power state equivalent to the ACPI G2/S5 or G3 states.
+    EfiResetShutdown,
+    /// A system-wide reset, the exact type of which is defined by a GUID in the reset data.
+    EfiResetPlatformSpecific,
+}
+
+impl EFI_RESET_TYPE {
+    /// Returns the [`EFI_RESET_TYPE`] corresponding to a raw reset type reported by the firmware,
+    /// such as the one returned by `QueryCapsuleCapabilities()`, if it is a known reset type.
+    ///
+    /// [`EFI_RESET_TYPE`]: crate::tables::runtime_services::EFI_RESET_TYPE
+    pub const fn from_raw(ResetType: UINT32) -> Option<Self> {
+        match ResetType {
+            0 => Some(Self::EfiResetCold),
+            1 => Some(Self::EfiResetWarm),
+            2 => Some(Self::EfiResetShutdown),
+            3 => Some(Self::EfiResetPlatformSpecific),
+            _ => None,
+        }
+    }
+}
+
 pub const EFI_UNSPECIFIED_TIMEZONE: UINT16 = 0x07FF;
 
 pub const EFI_TIME_ADJUST_DAYLIGHT: UINT8 = 0x01;
@@ -166,6 +751,208 @@ type EFI_GET_TIME = extern "efiapi" fn(
     Capabilities: *mut EFI_TIME_CAPABILITIES,
 ) -> EFI_STATUS;
 
+/// Bitmask of attributes of a UEFI variable.
+///
+/// See the `EFI_VARIABLE_*` constants in this module for the individual bits.
+pub type EFI_VARIABLE_ATTRIBUTES = UINT32;
+
+/// The variable is stored in non-volatile storage and persists across resets.
+pub const EFI_VARIABLE_NON_VOLATILE: EFI_VARIABLE_ATTRIBUTES = 0x00000001;
+/// The variable is accessible before [`ExitBootServices()`] is called.
+///
+/// [`ExitBootServices()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-exitbootservices
+pub const EFI_VARIABLE_BOOTSERVICE_ACCESS: EFI_VARIABLE_ATTRIBUTES = 0x00000002;
+/// The variable is accessible after [`ExitBootServices()`] is called. Must be combined with
+/// [`EFI_VARIABLE_BOOTSERVICE_ACCESS`].
+///
+/// [`ExitBootServices()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-exitbootservices
+/// [`EFI_VARIABLE_BOOTSERVICE_ACCESS`]: crate::tables::runtime_services::EFI_VARIABLE_BOOTSERVICE_ACCESS
+pub const EFI_VARIABLE_RUNTIME_ACCESS: EFI_VARIABLE_ATTRIBUTES = 0x00000004;
+/// The variable is a hardware error record, stored in the `HwErrRec####` namespace.
+pub const EFI_VARIABLE_HARDWARE_ERROR_RECORD: EFI_VARIABLE_ATTRIBUTES = 0x00000008;
+/// Deprecated count-based authenticated write access. Firmware should not produce variables with
+/// this attribute.
+pub const EFI_VARIABLE_AUTHENTICATED_WRITE_ACCESS: EFI_VARIABLE_ATTRIBUTES = 0x00000010;
+/// Writes to the variable must be authenticated with an `EFI_VARIABLE_AUTHENTICATION_2`
+/// descriptor carrying a timestamp.
+pub const EFI_VARIABLE_TIME_BASED_AUTHENTICATED_WRITE_ACCESS: EFI_VARIABLE_ATTRIBUTES = 0x00000020;
+/// The data passed to [`SetVariable()`] is appended to the current value of the variable instead
+/// of replacing it.
+///
+/// [`SetVariable()`]: ./struct.EFI_RUNTIME_SERVICES.html#method.SetVariable
+pub const EFI_VARIABLE_APPEND_WRITE: EFI_VARIABLE_ATTRIBUTES = 0x00000040;
+/// Writes to the variable must be authenticated with an `EFI_VARIABLE_AUTHENTICATION_3`
+/// descriptor.
+pub const EFI_VARIABLE_ENHANCED_AUTHENTICATED_ACCESS: EFI_VARIABLE_ATTRIBUTES = 0x00000080;
+
+/// Vendor GUID of the architecturally defined global variables.
+pub const EFI_GLOBAL_VARIABLE: EFI_GUID = EFI_GUID(
+    0x8BE4DF61,
+    0x93CA,
+    0x11D2,
+    [0xAA, 0x0D, 0x00, 0xE0, 0x98, 0x03, 0x2B, 0x8C],
+);
+
+/// Vendor GUID of the image security database variables, `db`, `dbx`, `dbt` and `dbr`.
+pub const EFI_IMAGE_SECURITY_DATABASE_GUID: EFI_GUID = EFI_GUID(
+    0xD719B2CB,
+    0x3D3A,
+    0x4596,
+    [0xA3, 0xBC, 0xDA, 0xD0, 0x0E, 0x67, 0x65, 0x6F],
+);
+
+/// The language codes that the firmware supports. Deprecated.
+pub const EFI_LANG_CODES_VARIABLE_NAME: &[CHAR16] = ucs2!("LangCodes");
+/// The language code that the system is configured for. Deprecated.
+pub const EFI_LANG_VARIABLE_NAME: &[CHAR16] = ucs2!("Lang");
+/// The firmware’s boot managers timeout, in seconds, before initiating the default boot selection.
+pub const EFI_TIME_OUT_VARIABLE_NAME: &[CHAR16] = ucs2!("Timeout");
+/// The language codes that the firmware supports.
+pub const EFI_PLATFORM_LANG_CODES_VARIABLE_NAME: &[CHAR16] = ucs2!("PlatformLangCodes");
+/// The language code that the system is configured for.
+pub const EFI_PLATFORM_LANG_VARIABLE_NAME: &[CHAR16] = ucs2!("PlatformLang");
+/// The device path of the default input console.
+pub const EFI_CON_IN_VARIABLE_NAME: &[CHAR16] = ucs2!("ConIn");
+/// The device path of the default output console.
+pub const EFI_CON_OUT_VARIABLE_NAME: &[CHAR16] = ucs2!("ConOut");
+/// The device path of the default error output device.
+pub const EFI_ERR_OUT_VARIABLE_NAME: &[CHAR16] = ucs2!("ErrOut");
+/// The device path of all possible console input devices.
+pub const EFI_CON_IN_DEV_VARIABLE_NAME: &[CHAR16] = ucs2!("ConInDev");
+/// The device path of all possible console output devices.
+pub const EFI_CON_OUT_DEV_VARIABLE_NAME: &[CHAR16] = ucs2!("ConOutDev");
+/// The device path of all possible error output devices.
+pub const EFI_ERR_OUT_DEV_VARIABLE_NAME: &[CHAR16] = ucs2!("ErrOutDev");
+/// The ordered boot option load list.
+pub const EFI_BOOT_ORDER_VARIABLE_NAME: &[CHAR16] = ucs2!("BootOrder");
+/// The boot option for the next boot only.
+pub const EFI_BOOT_NEXT_VARIABLE_NAME: &[CHAR16] = ucs2!("BootNext");
+/// The boot option that was selected for the current boot.
+pub const EFI_BOOT_CURRENT_VARIABLE_NAME: &[CHAR16] = ucs2!("BootCurrent");
+/// The types of boot options supported by the boot manager.
+pub const EFI_BOOT_OPTION_SUPPORT_VARIABLE_NAME: &[CHAR16] = ucs2!("BootOptionSupport");
+/// The ordered driver load option list.
+pub const EFI_DRIVER_ORDER_VARIABLE_NAME: &[CHAR16] = ucs2!("DriverOrder");
+/// The ordered System Prep Application load option list.
+pub const EFI_SYS_PREP_ORDER_VARIABLE_NAME: &[CHAR16] = ucs2!("SysPrepOrder");
+/// Identifies the level of hardware error record persistence support implemented by the platform.
+pub const EFI_HW_ERR_REC_SUPPORT_VARIABLE_NAME: &[CHAR16] = ucs2!("HwErrRecSupport");
+/// Whether the system is operating in setup mode (`1`) or not (`0`).
+pub const EFI_SETUP_MODE_NAME: &[CHAR16] = ucs2!("SetupMode");
+/// Whether the system is operating in audit mode (`1`) or not (`0`).
+pub const EFI_AUDIT_MODE_NAME: &[CHAR16] = ucs2!("AuditMode");
+/// Whether the system is operating in deployed mode (`1`) or not (`0`).
+pub const EFI_DEPLOYED_MODE_NAME: &[CHAR16] = ucs2!("DeployedMode");
+/// The Key Exchange Key signature database.
+pub const EFI_KEY_EXCHANGE_KEY_NAME: &[CHAR16] = ucs2!("KEK");
+/// The public Platform Key.
+pub const EFI_PLATFORM_KEY_NAME: &[CHAR16] = ucs2!("PK");
+/// Whether the platform firmware is operating in Secure Boot mode (`1`) or not (`0`).
+pub const EFI_SECURE_BOOT_MODE_NAME: &[CHAR16] = ucs2!("SecureBoot");
+/// An array of GUIDs representing the type of signatures supported by the platform firmware.
+pub const EFI_SIGNATURE_SUPPORT_NAME: &[CHAR16] = ucs2!("SignatureSupport");
+/// OS-specified indications of which features the firmware should enable.
+pub const EFI_OS_INDICATIONS_VARIABLE_NAME: &[CHAR16] = ucs2!("OsIndications");
+/// The OS indications supported by the firmware.
+pub const EFI_OS_INDICATIONS_SUPPORT_VARIABLE_NAME: &[CHAR16] = ucs2!("OsIndicationsSupported");
+/// Whether the Secure Boot keys are the ones provided by the platform vendor (`1`) or not (`0`).
+pub const EFI_VENDOR_KEYS_VARIABLE_NAME: &[CHAR16] = ucs2!("VendorKeys");
+/// The ordered OS recovery option list.
+pub const EFI_OS_RECOVERY_ORDER_VARIABLE_NAME: &[CHAR16] = ucs2!("OsRecoveryOrder");
+
+/// The authorized signature database, in the [`EFI_IMAGE_SECURITY_DATABASE_GUID`] namespace.
+///
+/// [`EFI_IMAGE_SECURITY_DATABASE_GUID`]: crate::tables::runtime_services::EFI_IMAGE_SECURITY_DATABASE_GUID
+pub const EFI_IMAGE_SECURITY_DATABASE: &[CHAR16] = ucs2!("db");
+/// The forbidden signature database, in the [`EFI_IMAGE_SECURITY_DATABASE_GUID`] namespace.
+///
+/// [`EFI_IMAGE_SECURITY_DATABASE_GUID`]: crate::tables::runtime_services::EFI_IMAGE_SECURITY_DATABASE_GUID
+pub const EFI_IMAGE_SECURITY_DATABASE1: &[CHAR16] = ucs2!("dbx");
+/// The timestamp signature database, in the [`EFI_IMAGE_SECURITY_DATABASE_GUID`] namespace.
+///
+/// [`EFI_IMAGE_SECURITY_DATABASE_GUID`]: crate::tables::runtime_services::EFI_IMAGE_SECURITY_DATABASE_GUID
+pub const EFI_IMAGE_SECURITY_DATABASE2: &[CHAR16] = ucs2!("dbt");
+/// The recovery signature database, in the [`EFI_IMAGE_SECURITY_DATABASE_GUID`] namespace.
+///
+/// [`EFI_IMAGE_SECURITY_DATABASE_GUID`]: crate::tables::runtime_services::EFI_IMAGE_SECURITY_DATABASE_GUID
+pub const EFI_IMAGE_SECURITY_DATABASE3: &[CHAR16] = ucs2!("dbr");
+
 type EFI_SET_TIME = extern "efiapi" fn(
     Time: *mut EFI_TIME,
 ) -> EFI_STATUS;
+
+type EFI_GET_WAKEUP_TIME = extern "efiapi" fn(
+    Enabled: *mut BOOLEAN,
+    Pending: *mut BOOLEAN,
+    Time: *mut EFI_TIME,
+) -> EFI_STATUS;
+
+type EFI_SET_WAKEUP_TIME = extern "efiapi" fn(
+    Enable: BOOLEAN,
+    Time: *mut EFI_TIME,
+) -> EFI_STATUS;
+
+type EFI_SET_VIRTUAL_ADDRESS_MAP = extern "efiapi" fn(
+    MemoryMapSize: UINTN,
+    DescriptorSize: UINTN,
+    DescriptorVersion: UINT32,
+    VirtualMap: *mut EFI_MEMORY_DESCRIPTOR,
+) -> EFI_STATUS;
+
+type EFI_CONVERT_POINTER = extern "efiapi" fn(
+    DebugDisposition: UINTN,
+    Address: *mut *mut VOID,
+) -> EFI_STATUS;
+
+type EFI_GET_VARIABLE = extern "efiapi" fn(
+    VariableName: *const CHAR16,
+    VendorGuid: *const EFI_GUID,
+    Attributes: *mut EFI_VARIABLE_ATTRIBUTES,
+    DataSize: *mut UINTN,
+    Data: *mut VOID,
+) -> EFI_STATUS;
+
+type EFI_GET_NEXT_VARIABLE_NAME = extern "efiapi" fn(
+    VariableNameSize: *mut UINTN,
+    VariableName: *mut CHAR16,
+    VendorGuid: *mut EFI_GUID,
+) -> EFI_STATUS;
+
+type EFI_SET_VARIABLE = extern "efiapi" fn(
+    VariableName: *const CHAR16,
+    VendorGuid: *const EFI_GUID,
+    Attributes: EFI_VARIABLE_ATTRIBUTES,
+    DataSize: UINTN,
+    Data: *const VOID,
+) -> EFI_STATUS;
+
+type EFI_GET_NEXT_HIGH_MONO_COUNT = extern "efiapi" fn(
+    HighCount: *mut UINT32,
+) -> EFI_STATUS;
+
+type EFI_RESET_SYSTEM = extern "efiapi" fn(
+    ResetType: EFI_RESET_TYPE,
+    ResetStatus: EFI_STATUS,
+    DataSize: UINTN,
+    ResetData: *const VOID,
+);
+
+type EFI_UPDATE_CAPSULE = extern "efiapi" fn(
+    CapsuleHeaderArray: *mut *mut EFI_CAPSULE_HEADER,
+    CapsuleCount: UINTN,
+    ScatterGatherList: UINT64,
+) -> EFI_STATUS;
+
+type EFI_QUERY_CAPSULE_CAPABILITIES = extern "efiapi" fn(
+    CapsuleHeaderArray: *mut *mut EFI_CAPSULE_HEADER,
+    CapsuleCount: UINTN,
+    MaximumCapsuleSize: *mut UINT64,
+    ResetType: *mut UINT32,
+) -> EFI_STATUS;
+
+type EFI_QUERY_VARIABLE_INFO = extern "efiapi" fn(
+    Attributes: EFI_VARIABLE_ATTRIBUTES,
+    MaximumVariableStorageSize: *mut UINT64,
+    RemainingVariableStorageSize: *mut UINT64,
+    MaximumVariableSize: *mut UINT64,
+) -> EFI_STATUS;
@@ -33,12 +33,19 @@
 //! [`Hdr`]: ./struct.EFI_SYSTEM_TABLE.html#structfield.Hdr
 //! [`FirmwareVendor`]: ./struct.EFI_SYSTEM_TABLE.html#structfield.FirmwareVendor
 //! [`FirmwareRevision`]: ./struct.EFI_SYSTEM_TABLE.html#structfield.FirmwareRevision
+//! [`RuntimeServices`]: ./struct.EFI_SYSTEM_TABLE.html#structfield.RuntimeServices
+//! [`NumberOfTableEntries`]: ./struct.EFI_SYSTEM_TABLE.html#structfield.NumberOfTableEntries
+//! [`ConfigurationTable`]: ./struct.EFI_SYSTEM_TABLE.html#structfield.ConfigurationTable
+//! [`EFI_BOOT_SERVICES.ExitBootServices()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-exitbootservices
+//! [`ExitBootServices()`]: https://uefi.org/specs/UEFI/2.10/07_Services_Boot_Services.html#efi-boot-services-exitbootservices
 //!
 //! [Section 4.3 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/04_EFI_System_Table.html#efi-system-table-1
 
 use crate::protocols::console::{EFI_SIMPLE_TEXT_INPUT_PROTOCOL, EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL};
+use crate::tables::boot_services::EFI_BOOT_SERVICES;
+use crate::tables::runtime_services::EFI_RUNTIME_SERVICES;
 use crate::tables::EFI_TABLE_HEADER;
-use crate::types::{CHAR16, EFI_HANDLE, UINT32, UINT64};
+use crate::types::{CHAR16, EFI_GUID, EFI_HANDLE, UINT32, UINT64, UINTN, VOID};
 
 /// Signature for the EFI System Table.
 pub const EFI_SYSTEM_TABLE_SIGNATURE: UINT64 = 0x5453595320494249;
@@ -70,7 +77,7 @@ pub const EFI_2_00_SYSTEM_TABLE_REVISION: UINT32 = 2 << 16;
 /// Revision of the 1.1 EFI System Table.
 pub const EFI_1_10_SYSTEM_TABLE_REVISION: UINT32 = (1 << 16) | 10;
 /// Revision of the 1.0.2 EFI System Table.
-pub const EFI_1_02_SYSTEM_TABLE_REVISION: UINT32 = (1 << 16) | 02;
+pub const EFI_1_02_SYSTEM_TABLE_REVISION: UINT32 = (1 << 16) | 2;
 
 /// The EFI specification version.
 pub const EFI_SPECIFICATION_VERSION: UINT32 = EFI_SYSTEM_TABLE_REVISION;
@@ -132,4 +139,36 @@ pub struct EFI_SYSTEM_TABLE {
     /// [`EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL`]: crate::protocols::console::EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL
     /// [`StandardErrorHandle`]: ./struct.EFI_SYSTEM_TABLE.html#structfield.StandardErrorHandle
     pub StdErr: *mut EFI_SIMPLE_TEXT_OUTPUT_PROTOCOL,
+    /// A pointer to the [`EFI_RUNTIME_SERVICES`] table.
+    ///
+    /// [`EFI_RUNTIME_SERVICES`]: crate::tables::runtime_services::EFI_RUNTIME_SERVICES
+    pub RuntimeServices: *mut EFI_RUNTIME_SERVICES,
+    /// A pointer to the [`EFI_BOOT_SERVICES`] table.
+    ///
+    /// [`EFI_BOOT_SERVICES`]: crate::tables::boot_services::EFI_BOOT_SERVICES
+    pub BootServices: *mut EFI_BOOT_SERVICES,
+    /// The number of system configuration tables in the buffer [`ConfigurationTable`].
+    ///
+    /// [`ConfigurationTable`]: ./struct.EFI_SYSTEM_TABLE.html#structfield.ConfigurationTable
+    pub NumberOfTableEntries: UINTN,
+    /// A pointer to the system configuration tables. The number of entries in the table is
+    /// [`NumberOfTableEntries`].
+    ///
+    /// [`NumberOfTableEntries`]: ./struct.EFI_SYSTEM_TABLE.html#structfield.NumberOfTableEntries
+    pub ConfigurationTable: *mut EFI_CONFIGURATION_TABLE,
+}
+
+/// Contains a set of GUID/pointer pairs comprised of the [`ConfigurationTable`] field in the EFI
+/// System Table.
+///
+/// [`ConfigurationTable`]: ./struct.EFI_SYSTEM_TABLE.html#structfield.ConfigurationTable
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct EFI_CONFIGURATION_TABLE {
+    /// The 128-bit GUID value that uniquely identifies the system configuration table.
+    pub VendorGuid: EFI_GUID,
+    /// A pointer to the table associated with [`VendorGuid`].
+    ///
+    /// [`VendorGuid`]: ./struct.EFI_CONFIGURATION_TABLE.html#structfield.VendorGuid
+    pub VendorTable: *mut VOID,
 }
@@ -25,34 +25,35 @@
 //! The data types are defined as type aliases in this crate, and are listed below as follows, with
 //! the corresponding types in Rust.
 //!
-//! | Data Type            |  Type in Rust        |
-//! | -------------------- | -------------------- |
-//! | [`BOOLEAN`]          | [`u8`]               |
-//! | [`INTN`]             | [`isize`]            |
-//! | [`UINTN`]            | [`usize`]            |
-//! | [`INT8`]             | [`i8`]               |
-//! | [`UINT8`]            | [`u8`]               |
-//! | [`INT16`]            | [`i16`]              |
-//! | [`UINT16`]           | [`u16`]              |
-//! | [`INT32`]            | [`i32`]              |
-//! | [`UINT32`]           | [`u32`]              |
-//! | [`INT64`]            | [`i64`]              |
-//! | [`UINT64`]           | [`u64`]              |
-//! | [`INT128`]           | [`i128`]             |
-//! | [`UINT128`]          | [`u128`]             |
-//! | [`CHAR8`]            | [`u8`]               |
-//! | [`CHAR16`]           | [`u16`]              |
-//! | [`VOID`]             | [`VOID`]             |
-//! | [`EFI_GUID`]         | [`EFI_GUID`]         |
-//! | [`EFI_STATUS`]       | [`UINTN`]            |
-//! | [`EFI_HANDLE`]       | `*mut` [`VOID`]      |
-//! | [`EFI_EVENT`]        | `*mut` [`VOID`]      |
-//! | [`EFI_LBA`]          | [`UINT64`]           |
-//! | [`EFI_TPL`]          | [`UINTN`]            |
-//! | [`EFI_MAC_ADDRESS`]  | [`EFI_MAC_ADDRESS`]  |
-//! | [`EFI_IPv4_ADDRESS`] | [`EFI_IPv4_ADDRESS`] |
-//! | [`EFI_IPv6_ADDRESS`] | [`EFI_IPv6_ADDRESS`] |
-//! | [`EFI_IP_ADDRESS`]   | [`EFI_IP_ADDRESS`]   |
+//! | Data Type                | Type in Rust         |
+//! | ------------------------ | -------------------- |
+//! | [`BOOLEAN`]              | [`u8`]               |
+//! | [`INTN`]                 | [`isize`]            |
+//! | [`UINTN`]                | [`usize`]            |
+//! | [`INT8`]                 | [`i8`]               |
+//! | [`UINT8`]                | [`u8`]               |
+//! | [`INT16`]                | [`i16`]              |
+//! | [`UINT16`]               | [`u16`]              |
+//! | [`INT32`]                | [`i32`]              |
+//! | [`UINT32`]               | [`u32`]              |
+//! | [`INT64`]                | [`i64`]              |
+//! | [`UINT64`]               | [`u64`]              |
+//! | [`INT128`]               | [`i128`]             |
+//! | [`UINT128`]              | [`u128`]             |
+//! | [`CHAR8`]                | [`u8`]               |
+//! | [`CHAR16`]               | [`u16`]              |
+//! | [`VOID`]                 | [`VOID`]             |
+//! | [`EFI_GUID`]             | [`EFI_GUID`]         |
+//! | [`EFI_STATUS`]           | [`UINTN`]            |
+//! | [`EFI_HANDLE`]           | `*mut` [`VOID`]      |
+//! | [`EFI_EVENT`]            | `*mut` [`VOID`]      |
+//! | [`EFI_LBA`]              | [`UINT64`]           |
+//! | [`EFI_TPL`]              | [`UINTN`]            |
+//! | [`EFI_PHYSICAL_ADDRESS`] | [`UINT64`]           |
+//! | [`EFI_MAC_ADDRESS`]      | [`EFI_MAC_ADDRESS`]  |
+//! | [`EFI_IPv4_ADDRESS`]     | [`EFI_IPv4_ADDRESS`] |
+//! | [`EFI_IPv6_ADDRESS`]     | [`EFI_IPv6_ADDRESS`] |
+//! | [`EFI_IP_ADDRESS`]       | [`EFI_IP_ADDRESS`]   |
 //!
 //! [`BOOLEAN`]: crate::types::BOOLEAN
 //! [`INTN`]: crate::types::INTN
@@ -75,6 +76,7 @@
 //! [`EFI_EVENT`]: crate::types::EFI_EVENT
 //! [`EFI_LBA`]: crate::types::EFI_LBA
 //! [`EFI_TPL`]: crate::types::EFI_TPL
+//! [`EFI_PHYSICAL_ADDRESS`]: crate::types::EFI_PHYSICAL_ADDRESS
 //! [`EFI_MAC_ADDRESS`]: crate::types::EFI_MAC_ADDRESS
 //! [`EFI_IPv4_ADDRESS`]: crate::types::EFI_IPv4_ADDRESS
 //! [`EFI_IPv6_ADDRESS`]: crate::types::EFI_IPv6_ADDRESS
@@ -95,6 +97,11 @@
 //!
 //! [Section 2.3.1 of the UEFI Specification]: https://uefi.org/specs/UEFI/2.10/02_Overview.html#data-types
 
+use core::fmt;
+use core::str::FromStr;
+
+use crate::status::EFI_INVALID_PARAMETER;
+
 // ----- BEGIN PRIMITIVE TYPES -----
 
 /// Logical Boolean. 1-byte value containing a `0` for `FALSE` and `1` for `TRUE`. Any other values are *undefined*.
@@ -135,10 +142,94 @@ pub enum VOID {}
 // ----- BEGIN COMPOUND TYPES -----
 
 /// 128-bit buffer containing a unique identifier value. Unless otherwise specified, aligned on a 64-bit boundary.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Eq, PartialEq)]
 #[repr(C)]
 pub struct EFI_GUID(pub UINT32, pub UINT16, pub UINT16, pub [UINT8; 8]);
 
+impl EFI_GUID {
+    /// Creates a GUID from its 16-byte in-memory representation, in which the first three fields
+    /// are stored in little-endian byte order.
+    pub const fn from_bytes(Bytes: [UINT8; 16]) -> Self {
+        Self(
+            UINT32::from_le_bytes([Bytes[0], Bytes[1], Bytes[2], Bytes[3]]),
+            UINT16::from_le_bytes([Bytes[4], Bytes[5]]),
+            UINT16::from_le_bytes([Bytes[6], Bytes[7]]),
+            [
+                Bytes[8], Bytes[9], Bytes[10], Bytes[11], Bytes[12], Bytes[13], Bytes[14], Bytes[15],
+            ],
+        )
+    }
+
+    /// Returns the 16-byte in-memory representation of the GUID.
+    pub const fn to_bytes(&self) -> [UINT8; 16] {
+        let a = self.0.to_le_bytes();
+        let b = self.1.to_le_bytes();
+        let c = self.2.to_le_bytes();
+        let d = self.3;
+        [
+            a[0], a[1], a[2], a[3], b[0], b[1], c[0], c[1], d[0], d[1], d[2], d[3], d[4], d[5], d[6],
+            d[7],
+        ]
+    }
+}
+
+/// Formats the GUID in its registry format, `XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX`, with upper
+/// case hexadecimal digits.
+impl fmt::Display for EFI_GUID {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let d = self.3;
+        write!(
+            f,
+            "{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}",
+            self.0, self.1, self.2, d[0], d[1], d[2], d[3], d[4], d[5], d[6], d[7]
+        )
+    }
+}
+
+impl fmt::Debug for EFI_GUID {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Parses a GUID in its registry format, `XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX`, accepting both
+/// upper and lower case hexadecimal digits.
+impl FromStr for EFI_GUID {
+    type Err = EFI_STATUS;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.as_bytes();
+        if s.len() != 36 || [8, 13, 18, 23].iter().any(|&i| s[i] != b'-') {
+            return Err(EFI_INVALID_PARAMETER);
+        }
+
+        let hex = |range: core::ops::Range<usize>| -> Result<u64, EFI_STATUS> {
+            s[range].iter().try_fold(0, |acc, &c| {
+                let digit = (c as char).to_digit(16).ok_or(EFI_INVALID_PARAMETER)?;
+                Ok(acc << 4 | digit as u64)
+            })
+        };
+
+        let node = hex(24..36)?.to_be_bytes();
+        let clock = hex(19..23)? as UINT16;
+        Ok(Self(
+            hex(0..8)? as UINT32,
+            hex(9..13)? as UINT16,
+            hex(14..18)? as UINT16,
+            [
+                (clock >> 8) as UINT8,
+                clock as UINT8,
+                node[2],
+                node[3],
+                node[4],
+                node[5],
+                node[6],
+                node[7],
+            ],
+        ))
+    }
+}
+
 /// Status code.
 pub type EFI_STATUS = UINTN;
 
@@ -154,6 +245,9 @@ pub type EFI_LBA = UINT64;
 /// Task priority level.
 pub type EFI_TPL = UINTN;
 
+/// Physical memory address.
+pub type EFI_PHYSICAL_ADDRESS = UINT64;
+
 /// 32-byte buffer containing a network Media Access Control address.
 #[derive(Clone, Copy)]
 #[repr(C)]